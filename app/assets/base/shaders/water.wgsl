@@ -0,0 +1,13 @@
+#import bevy_pbr::{forward_io::VertexOutput, mesh_view_bindings::globals}
+
+@group(2) @binding(0) var<uniform> color: vec4<f32>;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let position = in.world_position.xz;
+    let time = globals.time;
+    let ripples = sin(position.x * 3.0 + time * 1.5) * cos(position.y * 2.5 + time * 1.1)
+        + 0.5 * sin((position.x + position.y) * 5.0 - time * 2.0);
+
+    return vec4(color.rgb + ripples * 0.04, color.a);
+}
@@ -1,4 +1,7 @@
-use std::net::{IpAddr, Ipv4Addr};
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    path::PathBuf,
+};
 
 use anyhow::{Context, Result};
 use bevy::prelude::*;
@@ -16,11 +19,14 @@ use project_harmonia_base::{
         actor::SelectedActor,
         city::{ActiveCity, City},
         family::FamilyMembers,
+        loading::LoadingStart,
         GameLoad, WorldName, WorldState,
     },
-    network::{self, DEFAULT_PORT},
+    network::{self, replay_log::Replay, SessionPassword, DEFAULT_PORT},
 };
 
+use crate::object_import::ObjectImport;
+
 /// Logic for command line interface.
 ///
 /// This plugin expects [`Cli`] to be initialized early.
@@ -54,7 +60,12 @@ fn apply_subcommand(
                 commands.insert_resource(WorldName(world_load.world_name.clone()));
                 commands.trigger(GameLoad);
             }
-            GameCommand::Host { world_load, port } => {
+            GameCommand::Host {
+                world_load,
+                port,
+                password,
+                ..
+            } => {
                 info!(
                     "hosting world '{}' on port {port} from CLI",
                     world_load.world_name
@@ -64,25 +75,39 @@ fn apply_subcommand(
                     client_channels_config: network_channels.get_client_configs(),
                     ..Default::default()
                 });
-                let transport = network::create_server(*port).context("unable to create server")?;
+                let (transport, token_server) =
+                    network::create_server(*port, password).context("unable to create server")?;
 
                 commands.insert_resource(server);
                 commands.insert_resource(transport);
+                commands.insert_resource(token_server);
+                commands.insert_resource(SessionPassword(password.clone()));
                 commands.insert_resource(WorldName(world_load.world_name.clone()));
                 commands.trigger(GameLoad);
             }
-            GameCommand::Join { ip, port } => {
+            GameCommand::Join { ip, port, password } => {
                 info!("joining world at {ip}:{port} from CLI");
                 let client = RenetClient::new(ConnectionConfig {
                     server_channels_config: network_channels.get_server_configs(),
                     client_channels_config: network_channels.get_client_configs(),
                     ..Default::default()
                 });
-                let transport =
-                    network::create_client(*ip, *port).context("unable to create client")?;
+                let transport = network::create_client(*ip, *port, password)
+                    .context("unable to create client")?;
 
                 commands.insert_resource(client);
                 commands.insert_resource(transport);
+                commands.insert_resource(SessionPassword(password.clone()));
+            }
+            GameCommand::Replay { path } => {
+                info!("replaying network messages from {path:?} from CLI");
+                let replay = Replay::read(path)?;
+                commands.insert_resource(WorldName(replay.world_name().to_string()));
+                commands.insert_resource(replay);
+                commands.trigger(GameLoad);
+            }
+            GameCommand::ImportObject(_) => {
+                unreachable!("tools should be executed before the app creation")
             }
         }
     }
@@ -106,7 +131,7 @@ fn quick_load(
                     .with_context(|| format!("unable to find city named '{name}'"))?;
 
                 commands.entity(entity).insert(ActiveCity);
-                commands.set_state(WorldState::City);
+                commands.trigger(LoadingStart(WorldState::City));
             }
             QuickLoad::Family { name } => {
                 info!("selecting family '{name}' from CLI");
@@ -119,7 +144,7 @@ fn quick_load(
                     .first()
                     .expect("family should contain at least one actor");
                 commands.entity(entity).insert(SelectedActor);
-                commands.set_state(WorldState::Family);
+                commands.trigger(LoadingStart(WorldState::Family));
             }
         }
     }
@@ -127,9 +152,32 @@ fn quick_load(
     Ok(())
 }
 
+const DEFAULT_TICK_RATE: f64 = 30.0;
+
+/// Maximum tick rate accepted from CLI.
+const MAX_TICK_RATE: f64 = 1000.0;
+
+/// Parses tick rate and validates its range.
+///
+/// Non-positive rates would panic when creating [`Time<Fixed>`].
+fn parse_tick_rate(value: &str) -> Result<f64, String> {
+    let tick_rate = value.parse::<f64>().map_err(|e| e.to_string())?;
+    if tick_rate > 0.0 && tick_rate <= MAX_TICK_RATE {
+        Ok(tick_rate)
+    } else {
+        Err(format!("should be greater than 0 and at most {MAX_TICK_RATE}"))
+    }
+}
+
 #[derive(Parser, Clone, Resource)]
 #[command(author, version, about)]
 pub(crate) struct Cli {
+    /// Reload object manifests, scenes and previews when their files change on disk.
+    ///
+    /// Intended for asset development.
+    #[arg(long)]
+    watch_assets: bool,
+
     /// Game command to run.
     #[command(subcommand)]
     subcommand: Option<GameCommand>,
@@ -144,6 +192,34 @@ impl Cli {
             _ => None,
         }
     }
+
+    /// Returns `true` if the game should run as a dedicated server without rendering and UI.
+    pub(crate) fn headless(&self) -> bool {
+        matches!(
+            self.subcommand,
+            Some(GameCommand::Host { headless: true, .. } | GameCommand::Replay { .. })
+        )
+    }
+
+    /// Returns the rate of [`FixedUpdate`] that drives the simulation and physics.
+    pub(crate) fn tick_rate(&self) -> f64 {
+        match self.subcommand {
+            Some(GameCommand::Host { tick_rate, .. }) => tick_rate,
+            _ => DEFAULT_TICK_RATE,
+        }
+    }
+
+    pub(crate) fn watch_assets(&self) -> bool {
+        self.watch_assets
+    }
+
+    /// Returns developer tool that should be executed instead of the game.
+    pub(crate) fn tool(&self) -> Option<&ObjectImport> {
+        match &self.subcommand {
+            Some(GameCommand::ImportObject(object_import)) => Some(object_import),
+            _ => None,
+        }
+    }
 }
 
 impl Default for Cli {
@@ -162,6 +238,20 @@ enum GameCommand {
         /// Port to use.
         #[clap(short, long, default_value_t = DEFAULT_PORT)]
         port: u16,
+
+        /// Password that clients need to join.
+        #[clap(long, default_value_t)]
+        password: String,
+
+        /// Run as a dedicated server without window, rendering and UI.
+        ///
+        /// Commands for administration are read from stdin.
+        #[clap(long)]
+        headless: bool,
+
+        /// Simulation ticks per second.
+        #[clap(long, default_value_t = DEFAULT_TICK_RATE, value_parser = parse_tick_rate)]
+        tick_rate: f64,
     },
     Join {
         /// Server IP address.
@@ -171,7 +261,20 @@ enum GameCommand {
         /// Server port.
         #[clap(short, long, default_value_t = DEFAULT_PORT)]
         port: u16,
+
+        /// Server password.
+        #[clap(long, default_value_t)]
+        password: String,
+    },
+    /// Replays network messages dumped by the server into a headless world.
+    ///
+    /// Loads the world in which the messages were recorded.
+    Replay {
+        /// Path to the dump.
+        path: PathBuf,
     },
+    /// Converts a glTF file into an object with generated manifest.
+    ImportObject(ObjectImport),
 }
 
 /// Arguments for quick load.
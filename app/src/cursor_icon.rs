@@ -0,0 +1,37 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, SystemCursorIcon},
+    winit::cursor::CursorIcon,
+};
+use project_harmonia_base::game_world::cursor_mode::CursorMode;
+
+/// Swaps the system cursor icon based on [`CursorMode`].
+pub(super) struct CursorIconPlugin;
+
+impl Plugin for CursorIconPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_icon
+                .never_param_warn()
+                .run_if(resource_changed::<CursorMode>),
+        );
+    }
+}
+
+fn update_icon(
+    mut commands: Commands,
+    cursor_mode: Res<CursorMode>,
+    window_entity: Single<Entity, With<PrimaryWindow>>,
+) {
+    let icon = match *cursor_mode {
+        CursorMode::Default => SystemCursorIcon::Default,
+        CursorMode::Placing => SystemCursorIcon::Move,
+        CursorMode::Interact => SystemCursorIcon::Pointer,
+    };
+
+    debug!("changing cursor icon to `{icon:?}`");
+    commands
+        .entity(*window_entity)
+        .insert(CursorIcon::from(icon));
+}
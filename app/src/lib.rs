@@ -1,27 +1,45 @@
 mod cli;
 mod cursor_controller;
+mod cursor_icon;
+mod object_import;
+mod server_console;
+#[cfg(feature = "inspector")]
+mod world_inspector;
+
+use std::{process, time::Duration};
 
 use avian3d::{prelude::*, sync::SyncConfig};
 use bevy::{
-    app::PluginGroupBuilder, core_pipeline::experimental::taa::TemporalAntiAliasPlugin,
-    pbr::wireframe::WireframePlugin, prelude::*, render::RenderPlugin,
+    app::{PluginGroupBuilder, ScheduleRunnerPlugin},
+    asset::io::AssetSourceId,
+    core_pipeline::experimental::taa::TemporalAntiAliasPlugin,
+    log::LogPlugin,
+    pbr::wireframe::WireframePlugin,
+    prelude::*,
+    render::{settings::WgpuSettings, RenderPlugin},
+    window::ExitCondition,
+    winit::WinitPlugin,
 };
 use bevy_atmosphere::prelude::*;
 use bevy_enhanced_input::prelude::*;
-#[cfg(feature = "inspector")]
-use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use bevy_mod_billboard::prelude::*;
 use bevy_mod_outline::OutlinePlugin;
-use bevy_replicon::prelude::*;
+use bevy_replicon::{client::diagnostics::ClientDiagnosticsPlugin, prelude::*};
 use bevy_replicon_renet::RepliconRenetPlugins;
 use bevy_simple_text_input::TextInputPlugin;
-use project_harmonia_base::{game_world::navigation::Obstacle, CorePlugins};
+use project_harmonia_base::{
+    asset::mods, game_paths::GamePaths, game_world::navigation::Obstacle, log_files, CorePlugins,
+};
 use project_harmonia_ui::UiPlugins;
 use project_harmonia_widgets::WidgetsPlugin;
 use vleue_navigator::prelude::*;
 
 use cli::{Cli, CliPlugin};
 use cursor_controller::CursorControllerPlugin;
+use cursor_icon::CursorIconPlugin;
+use server_console::ServerConsolePlugin;
+#[cfg(feature = "inspector")]
+use world_inspector::InspectorPlugin;
 
 struct AppPlugins;
 
@@ -30,21 +48,70 @@ impl PluginGroup for AppPlugins {
         PluginGroupBuilder::start::<Self>()
             .add(CliPlugin)
             .add(CursorControllerPlugin)
+            .add(CursorIconPlugin)
     }
 }
 
 // Separate entry point for Android, which doesn't use `main.rs`.
 #[bevy_main]
 pub fn main() {
+    let cli = Cli::default();
+    if let Some(tool) = cli.tool() {
+        if let Err(e) = tool.run() {
+            eprintln!("{e:#}");
+            process::exit(1);
+        }
+        return;
+    }
+
+    let headless = cli.headless();
+    let tick_rate = cli.tick_rate();
+    let asset_plugin = AssetPlugin {
+        watch_for_changes_override: Some(cli.watch_assets()),
+        ..Default::default()
+    };
+    let game_paths = GamePaths::default();
+    let log_plugin = LogPlugin {
+        level: log_files::configured_level(&game_paths),
+        custom_layer: log_files::file_layer,
+        ..Default::default()
+    };
     let mut app = App::new();
-    app.init_resource::<Cli>()
+    app.register_asset_source(AssetSourceId::Default, mods::asset_source(&game_paths.mods))
+        .insert_resource(game_paths)
+        .insert_resource(cli)
         .insert_resource(SyncConfig {
             position_to_transform: false,
             ..Default::default()
         })
-        .insert_resource(Time::<Fixed>::from_hz(30.0))
-        .add_plugins((
+        .insert_resource(Time::<Fixed>::from_hz(tick_rate));
+
+    if headless {
+        app.add_plugins((
+            DefaultPlugins
+                .set(asset_plugin)
+                .set(log_plugin)
+                .set(RenderPlugin {
+                    render_creation: WgpuSettings {
+                        backends: None,
+                        ..Default::default()
+                    }
+                    .into(),
+                    ..Default::default()
+                })
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    ..Default::default()
+                })
+                .disable::<WinitPlugin>(),
+            ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1.0 / 60.0)),
+        ));
+    } else {
+        app.add_plugins((
             DefaultPlugins
+                .set(asset_plugin)
+                .set(log_plugin)
                 .set(RenderPlugin {
                     synchronous_pipeline_compilation: true,
                     ..Default::default()
@@ -57,27 +124,38 @@ pub fn main() {
                     ..Default::default()
                 }),
             TemporalAntiAliasPlugin,
-            RepliconPlugins,
-            RepliconRenetPlugins,
-            WireframePlugin,
             AtmospherePlugin,
-            EnhancedInputPlugin,
-            VleueNavigatorPlugin,
-            NavmeshUpdaterPlugin::<Collider, Obstacle>::default(),
-            PhysicsPlugins::default()
-                .build()
-                .disable::<CcdPlugin>()
-                .disable::<SleepingPlugin>(),
-            PhysicsPickingPlugin,
-            PhysicsDebugPlugin::default(),
             TextInputPlugin,
             OutlinePlugin,
             BillboardPlugin,
-        ))
-        .add_plugins((CorePlugins, WidgetsPlugin, UiPlugins, AppPlugins));
+        ));
+    }
+
+    app.add_plugins((
+        RepliconPlugins,
+        RepliconRenetPlugins,
+        ClientDiagnosticsPlugin,
+        WireframePlugin,
+        EnhancedInputPlugin,
+        VleueNavigatorPlugin,
+        NavmeshUpdaterPlugin::<Collider, Obstacle>::default(),
+        PhysicsPlugins::default()
+            .build()
+            .disable::<CcdPlugin>()
+            .disable::<SleepingPlugin>(),
+        PhysicsPickingPlugin,
+        PhysicsDebugPlugin::default(),
+        CorePlugins,
+    ));
+
+    if headless {
+        app.add_plugins((CliPlugin, ServerConsolePlugin));
+    } else {
+        app.add_plugins((WidgetsPlugin, UiPlugins, AppPlugins));
+    }
 
     #[cfg(feature = "inspector")]
-    app.add_plugins(WorldInspectorPlugin::default());
+    app.add_plugins(InspectorPlugin);
 
     app.run();
 }
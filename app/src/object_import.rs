@@ -0,0 +1,251 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{ensure, Context, Result};
+use bevy::{prelude::*, scene::ron};
+use clap::Args;
+use gltf::{buffer, image, Node};
+
+use project_harmonia_base::asset::manifest::{object_manifest::ObjectCategory, GeneralManifest};
+
+/// Arguments for converting a glTF file into an object.
+#[derive(Args, Clone)]
+pub(super) struct ObjectImport {
+    /// Path to the glTF file.
+    path: PathBuf,
+
+    /// Object category, as named in manifests.
+    #[arg(short, long)]
+    category: String,
+
+    /// Display name, file name will be used if not specified.
+    #[arg(short, long)]
+    name: Option<String>,
+
+    /// Author of the model.
+    #[arg(short, long, default_value = "Unknown")]
+    author: String,
+
+    /// License of the model.
+    #[arg(short, long, default_value = "CC BY-SA 4.0")]
+    license: String,
+
+    /// Directory with objects where the new object will be placed.
+    #[arg(short, long, default_value = "app/assets/base/objects")]
+    objects_dir: PathBuf,
+}
+
+impl ObjectImport {
+    /// Validates the file, copies it with all dependencies and generates a manifest.
+    pub(super) fn run(&self) -> Result<()> {
+        ron::from_str::<ObjectCategory>(&self.category)
+            .with_context(|| format!("'{}' is not a valid category", self.category))?;
+
+        let (document, ..) = gltf::import(&self.path)
+            .with_context(|| format!("unable to import {:?}", self.path))?;
+        let scene = document
+            .default_scene()
+            .or_else(|| document.scenes().next())
+            .context("file should contain at least one scene")?;
+
+        let mut bounds = (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN));
+        for node in scene.nodes() {
+            extend_bounds(&mut bounds, node, Mat4::IDENTITY);
+        }
+        let (min, max) = bounds;
+        ensure!(min.cmple(max).all(), "scene should contain meshes");
+
+        let file_stem = self
+            .path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .context("file should have a valid name")?;
+        let object_dir = self
+            .objects_dir
+            .join(to_snake_case(&self.category))
+            .join(file_stem);
+        ensure!(!object_dir.exists(), "{object_dir:?} already exists");
+
+        fs::create_dir_all(&object_dir)
+            .with_context(|| format!("unable to create {object_dir:?}"))?;
+
+        let source_dir = self.path.parent().unwrap_or(Path::new(""));
+        let buffer_uris = document
+            .buffers()
+            .filter_map(|buffer| match buffer.source() {
+                buffer::Source::Uri(uri) => Some(uri),
+                buffer::Source::Bin => None,
+            });
+        let image_uris = document.images().filter_map(|image| match image.source() {
+            image::Source::Uri { uri, .. } => Some(uri),
+            image::Source::View { .. } => None,
+        });
+        let file_name = self.path.file_name().unwrap().to_string_lossy();
+        for relative_path in buffer_uris
+            .chain(image_uris)
+            .filter(|uri| !uri.starts_with("data:"))
+            .chain([&*file_name])
+        {
+            let from = source_dir.join(relative_path);
+            let to = object_dir.join(relative_path);
+            if let Some(parent) = to.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("unable to create {parent:?}"))?;
+            }
+            fs::copy(&from, &to).with_context(|| format!("unable to copy {from:?} to {to:?}"))?;
+        }
+
+        let name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| to_title_case(file_stem));
+        let general = GeneralManifest {
+            name,
+            author: self.author.clone(),
+            license: self.license.clone(),
+        };
+        let scene_path = format!("{file_name}#Scene{}", scene.index());
+        let manifest = manifest_text(
+            &general,
+            &scene_path,
+            &self.category,
+            preview_translation(min, max),
+        )?;
+
+        let manifest_path = object_dir.join(format!("{file_stem}.object.ron"));
+        fs::write(&manifest_path, manifest)
+            .with_context(|| format!("unable to write {manifest_path:?}"))?;
+
+        println!("imported '{}' into {object_dir:?}", general.name);
+
+        Ok(())
+    }
+}
+
+/// Generates object manifest with a collider and an obstacle.
+///
+/// Strings are serialized to escape quotes and backslashes.
+fn manifest_text(
+    general: &GeneralManifest,
+    scene_path: &str,
+    category: &str,
+    preview_translation: Vec3,
+) -> Result<String> {
+    let manifest = format!(
+        r#"(
+    general: {general},
+    scene: {scene},
+    category: {category},
+    preview_translation: ({x:.2}, {y:.2}, {z:.2}),
+    components: [
+        {{ "SceneColliderConstructor": Aabb }},
+    ],
+    spawn_components: [{{ "Obstacle": () }}],
+)
+"#,
+        general = ron::to_string(general)?,
+        scene = ron::to_string(scene_path)?,
+        x = preview_translation.x,
+        y = preview_translation.y,
+        z = preview_translation.z,
+    );
+
+    Ok(manifest)
+}
+
+/// Extends `bounds` with all meshes from the node and its children.
+fn extend_bounds(bounds: &mut (Vec3, Vec3), node: Node, parent_transform: Mat4) {
+    let transform = parent_transform * Mat4::from_cols_array_2d(&node.transform().matrix());
+    if let Some(mesh) = node.mesh() {
+        for primitive in mesh.primitives() {
+            let aabb = primitive.bounding_box();
+            let (min, max) = (Vec3::from(aabb.min), Vec3::from(aabb.max));
+            for x in [min.x, max.x] {
+                for y in [min.y, max.y] {
+                    for z in [min.z, max.z] {
+                        let corner = transform.transform_point3(Vec3::new(x, y, z));
+                        bounds.0 = bounds.0.min(corner);
+                        bounds.1 = bounds.1.max(corner);
+                    }
+                }
+            }
+        }
+    }
+
+    for child in node.children() {
+        extend_bounds(bounds, child, transform);
+    }
+}
+
+/// Calculates translation for the preview camera to fit the whole object.
+///
+/// Preview camera uses the default perspective projection.
+fn preview_translation(min: Vec3, max: Vec3) -> Vec3 {
+    let center = (min + max) / 2.0;
+    let radius = (max - min).length() / 2.0;
+    let fov = PerspectiveProjection::default().fov;
+    let distance = radius / (fov / 2.0).tan();
+
+    // Preview rotates the scene by 180 degrees around Y, so X and Z are flipped.
+    Vec3::new(center.x, -center.y, center.z - distance)
+}
+
+fn to_snake_case(camel_case: &str) -> String {
+    let mut result = String::new();
+    for (index, c) in camel_case.chars().enumerate() {
+        if c.is_uppercase() && index != 0 {
+            result.push('_');
+        }
+        result.push(c.to_ascii_lowercase());
+    }
+    result
+}
+
+fn to_title_case(snake_case: &str) -> String {
+    let mut result = snake_case.replace('_', " ");
+    if let Some(first) = result.get_mut(..1) {
+        first.make_ascii_uppercase();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escaping() -> Result<()> {
+        let general = GeneralManifest {
+            name: r#"The "Best" \ chair"#.to_string(),
+            author: "Author".to_string(),
+            license: "CC0".to_string(),
+        };
+        let manifest = manifest_text(&general, "chair.gltf#Scene0", "Furniture", Vec3::ZERO)?;
+
+        let general_line = manifest
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("general: "))
+            .and_then(|line| line.strip_suffix(','))
+            .expect("manifest should contain general section");
+        let parsed: GeneralManifest = ron::from_str(general_line)?;
+        assert_eq!(parsed.name, general.name);
+
+        Ok(())
+    }
+
+    #[test]
+    fn snake_case() {
+        assert_eq!(to_snake_case("Furniture"), "furniture");
+        assert_eq!(to_snake_case("OutdoorFurniture"), "outdoor_furniture");
+        assert_eq!(to_snake_case(""), "");
+    }
+
+    #[test]
+    fn title_case() {
+        assert_eq!(to_title_case("wooden_chair"), "Wooden chair");
+        assert_eq!(to_title_case("lamp"), "Lamp");
+        assert_eq!(to_title_case(""), "");
+    }
+}
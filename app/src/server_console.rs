@@ -0,0 +1,122 @@
+use std::{
+    io::{self, BufRead},
+    sync::{
+        mpsc::{self, Receiver},
+        Mutex,
+    },
+    thread,
+};
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+use project_harmonia_base::{
+    core::GameState,
+    game_world::{
+        players::{Permission, Players},
+        GameSave,
+    },
+    network::replay_log::ReplayLogDump,
+};
+
+/// Reads admin commands from stdin for the headless server.
+pub(super) struct ServerConsolePlugin;
+
+impl Plugin for ServerConsolePlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        app.insert_resource(ConsoleInput(Mutex::new(receiver)))
+            .add_systems(Update, execute);
+    }
+}
+
+fn execute(
+    mut commands: Commands,
+    mut exit_events: EventWriter<AppExit>,
+    mut server: ResMut<RepliconServer>,
+    console_input: Res<ConsoleInput>,
+    game_state: Res<State<GameState>>,
+    mut players: ResMut<Players>,
+) {
+    let receiver = console_input
+        .lock()
+        .expect("console receiver should be accessible");
+    loop {
+        let Ok(line) = receiver.try_recv() else {
+            return;
+        };
+
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+        };
+
+        match command {
+            "help" => info!(
+                "available commands: help, clients, kick <id>, permission <id> <spectator|guest|builder|host>, save, dump-replay, quit"
+            ),
+            "clients" => {
+                for (client_id, player) in players.iter() {
+                    match player.family_entity {
+                        Some(family_entity) => info!(
+                            "client `{client_id:?}` with `{}` permission plays for `{family_entity}`",
+                            player.permission
+                        ),
+                        None => info!(
+                            "client `{client_id:?}` with `{}` permission",
+                            player.permission
+                        ),
+                    }
+                }
+            }
+            "kick" => match words.next().map(str::parse::<u64>) {
+                Some(Ok(id)) => {
+                    info!("kicking client {id}");
+                    server.disconnect(ClientId::new(id));
+                }
+                _ => error!("kick requires a numeric client id"),
+            },
+            "permission" => {
+                let id = words.next().map(str::parse::<u64>);
+                let permission = words.next().map(str::parse::<Permission>);
+                match (id, permission) {
+                    (Some(Ok(id)), Some(Ok(permission))) => {
+                        if players.set_permission(&mut commands, ClientId::new(id), permission) {
+                            info!("setting `{permission}` permission for client {id}");
+                        } else {
+                            error!("client {id} is not connected");
+                        }
+                    }
+                    _ => error!("permission requires a numeric client id and a permission level"),
+                }
+            }
+            "save" => {
+                if **game_state == GameState::InGame {
+                    commands.trigger(GameSave);
+                } else {
+                    error!("unable to save outside of a game");
+                }
+            }
+            "dump-replay" => commands.trigger(ReplayLogDump),
+            "quit" => {
+                info!("shutting down server");
+                exit_events.send(AppExit::Success);
+            }
+            _ => error!("unknown command '{command}', type 'help' for available commands"),
+        }
+    }
+}
+
+#[derive(Resource, Deref)]
+struct ConsoleInput(Mutex<Receiver<String>>);
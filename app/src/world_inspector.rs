@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::quick::{FilterQueryInspectorPlugin, WorldInspectorPlugin};
+use bevy_replicon::prelude::*;
+use project_harmonia_base::{
+    core::GameState,
+    game_world::{
+        actor::{needs::Need, Actor},
+        family::building::wall::Wall,
+        players::{LocalPermission, Permission},
+        time_speed::{TimeSpeed, TimeSpeedRequest},
+    },
+    settings::{InspectorFilter, Settings},
+};
+
+/// Displays the world inspector when enabled in developer settings.
+pub(super) struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            WorldInspectorPlugin::new().run_if(inspector_shown(InspectorFilter::All)),
+            FilterQueryInspectorPlugin::<With<Actor>>::default()
+                .run_if(inspector_shown(InspectorFilter::Actors)),
+            FilterQueryInspectorPlugin::<With<Wall>>::default()
+                .run_if(inspector_shown(InspectorFilter::Walls)),
+            FilterQueryInspectorPlugin::<With<Need>>::default()
+                .run_if(inspector_shown(InspectorFilter::Needs)),
+        ))
+        .add_systems(Update, pause.run_if(in_state(GameState::InGame)));
+    }
+}
+
+fn inspector_shown(
+    filter: InspectorFilter,
+) -> impl FnMut(Res<Settings>, Res<RepliconClient>, Res<LocalPermission>) -> bool + Clone {
+    move |settings, client, local_permission| {
+        settings.developer.inspector_filter == filter
+            && inspector_allowed(&settings, &client, &local_permission)
+    }
+}
+
+/// Returns `true` if the inspector is enabled and the local player is allowed to use it.
+///
+/// Clients can inspect the world only with host permission.
+fn inspector_allowed(
+    settings: &Settings,
+    client: &RepliconClient,
+    local_permission: &LocalPermission,
+) -> bool {
+    settings.developer.world_inspector
+        && (client.is_disconnected() || **local_permission == Permission::Host)
+}
+
+/// Pauses single player game when the inspector is displayed and restores the speed after hiding.
+fn pause(
+    mut commands: Commands,
+    mut was_shown: Local<bool>,
+    mut previous_speed: Local<Option<TimeSpeed>>,
+    settings: Res<Settings>,
+    server: Res<RepliconServer>,
+    client: Res<RepliconClient>,
+    local_permission: Res<LocalPermission>,
+    time_speed: Res<TimeSpeed>,
+) {
+    if server.is_running() || !client.is_disconnected() {
+        return;
+    }
+
+    let shown = settings.developer.pause_on_inspector
+        && inspector_allowed(&settings, &client, &local_permission);
+    if shown == *was_shown {
+        return;
+    }
+    *was_shown = shown;
+
+    if shown {
+        if *time_speed != TimeSpeed::Paused {
+            info!("pausing game for inspection");
+            *previous_speed = Some(*time_speed);
+            commands.client_trigger(TimeSpeedRequest(TimeSpeed::Paused));
+        }
+    } else if let Some(speed) = previous_speed.take() {
+        info!("resuming game after inspection");
+        commands.client_trigger(TimeSpeedRequest(speed));
+    }
+}
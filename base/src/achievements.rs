@@ -0,0 +1,174 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use bevy::{
+    prelude::*,
+    scene::ron,
+    utils::{HashMap, HashSet},
+};
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::{
+    error_message::error_message,
+    game_paths::{backup_corrupted, write_atomic, GamePaths},
+    notification::Notification,
+};
+
+/// Tracks counters reported by other systems and unlocks achievements.
+///
+/// Progress is stored per user in [`GamePaths::achievements`].
+pub(super) struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(count.pipe(error_message))
+            .add_systems(Startup, load);
+    }
+}
+
+fn load(mut commands: Commands, game_paths: Res<GamePaths>) {
+    info!("loading achievements");
+
+    let achievements = Achievements::read(&game_paths.achievements).unwrap_or_else(|e| {
+        error!("{e:#}");
+        Default::default()
+    });
+    commands.insert_resource(achievements);
+}
+
+fn count(
+    trigger: Trigger<CounterIncrease>,
+    mut commands: Commands,
+    mut achievements: ResMut<Achievements>,
+    game_paths: Res<GamePaths>,
+) -> Result<()> {
+    let counter = **trigger;
+    let value = achievements.counters.entry(counter).or_default();
+    *value += 1;
+    let value = *value;
+    debug!("increasing `{counter:?}` to {value}");
+
+    for achievement in Achievement::iter().filter(|achievement| achievement.counter() == counter) {
+        if value >= achievement.goal() && achievements.unlocked.insert(achievement) {
+            info!("unlocking `{achievement:?}`");
+            commands.trigger(Notification::info(format!(
+                "Achievement unlocked: {}",
+                achievement.name()
+            )));
+        }
+    }
+
+    achievements.write(&game_paths.achievements)
+}
+
+/// Counter values and unlocked achievements.
+#[derive(Default, Deserialize, Resource, Serialize)]
+#[serde(default)]
+pub struct Achievements {
+    counters: HashMap<Counter, u32>,
+    unlocked: HashSet<Achievement>,
+}
+
+impl Achievements {
+    pub fn count(&self, counter: Counter) -> u32 {
+        self.counters.get(&counter).copied().unwrap_or_default()
+    }
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+
+    /// Reads achievements from the file, returns defaults if it doesn't exist.
+    fn read(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Default::default());
+        }
+
+        let content =
+            fs::read_to_string(path).with_context(|| format!("unable to read {path:?}"))?;
+        ron::from_str(&content).or_else(|e| {
+            let backup_path = backup_corrupted(path)?;
+            Err(e).with_context(|| format!("unable to parse {path:?}, moved to {backup_path:?}"))
+        })
+    }
+
+    fn write(&self, path: &Path) -> Result<()> {
+        let content = ron::ser::to_string_pretty(self, Default::default())
+            .context("unable to serialize achievements")?;
+        write_atomic(path, content)
+    }
+}
+
+/// Triggered by gameplay systems to advance a counter by one.
+///
+/// Counted only where the gameplay logic runs: on server or in singleplayer.
+#[derive(Clone, Copy, Deref, Event)]
+pub struct CounterIncrease(pub Counter);
+
+#[derive(Clone, Copy, Debug, Deserialize, EnumIter, Eq, Hash, PartialEq, Serialize)]
+pub enum Counter {
+    WallsBuilt,
+    MealsEaten,
+    SecretsTold,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, EnumIter, Eq, Hash, PartialEq, Serialize)]
+pub enum Achievement {
+    FirstWall,
+    Architect,
+    FirstMeal,
+    Gourmet,
+    Confidant,
+    Gossip,
+}
+
+impl Achievement {
+    pub fn name(self) -> &'static str {
+        match self {
+            Achievement::FirstWall => "First wall",
+            Achievement::Architect => "Architect",
+            Achievement::FirstMeal => "First meal",
+            Achievement::Gourmet => "Gourmet",
+            Achievement::Confidant => "Confidant",
+            Achievement::Gossip => "Gossip",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Achievement::FirstWall => "Build a wall.",
+            Achievement::Architect => "Build 100 walls.",
+            Achievement::FirstMeal => "Satisfy hunger of an actor.",
+            Achievement::Gourmet => "Satisfy hunger 50 times.",
+            Achievement::Confidant => "Tell a secret to another actor.",
+            Achievement::Gossip => "Tell 25 secrets.",
+        }
+    }
+
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Achievement::FirstWall | Achievement::Architect => "🧱",
+            Achievement::FirstMeal | Achievement::Gourmet => "🍴",
+            Achievement::Confidant | Achievement::Gossip => "💬",
+        }
+    }
+
+    pub fn counter(self) -> Counter {
+        match self {
+            Achievement::FirstWall | Achievement::Architect => Counter::WallsBuilt,
+            Achievement::FirstMeal | Achievement::Gourmet => Counter::MealsEaten,
+            Achievement::Confidant | Achievement::Gossip => Counter::SecretsTold,
+        }
+    }
+
+    /// Counter value required to unlock.
+    pub fn goal(self) -> u32 {
+        match self {
+            Achievement::FirstWall | Achievement::FirstMeal | Achievement::Confidant => 1,
+            Achievement::Architect => 100,
+            Achievement::Gourmet => 50,
+            Achievement::Gossip => 25,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 pub(super) mod collection;
 pub mod manifest;
 pub(super) mod material;
+pub mod mods;
 
 use std::path::Path;
 
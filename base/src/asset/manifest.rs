@@ -1,3 +1,5 @@
+pub mod clothing_manifest;
+pub mod fence_manifest;
 pub mod object_manifest;
 pub mod road_manifest;
 
@@ -8,7 +10,10 @@ use serde::{Deserialize, Serialize};
 use strum::{EnumIter, IntoEnumIterator};
 use walkdir::WalkDir;
 
-use crate::core::GameState;
+use super::mods;
+use crate::{core::GameState, game_paths::GamePaths, settings::Settings};
+use clothing_manifest::{ClothingLoader, ClothingManifest};
+use fence_manifest::{FenceLoader, FenceManifest};
 use object_manifest::{ObjectLoader, ObjectManifest};
 use road_manifest::{RoadLoader, RoadManifest};
 
@@ -18,8 +23,14 @@ impl Plugin for ManifestPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<ObjectManifest>()
             .init_asset::<RoadManifest>()
+            .init_asset::<FenceManifest>()
+            .init_asset::<ClothingManifest>()
             .init_asset_loader::<ObjectLoader>()
             .init_asset_loader::<RoadLoader>()
+            .init_asset_loader::<FenceLoader>()
+            .init_asset_loader::<ClothingLoader>()
+            // Settings are loaded on startup.
+            .add_systems(PostStartup, load_mods)
             .add_systems(
                 Update,
                 wait_for_loading.run_if(in_state(GameState::ManifestsLoading)),
@@ -39,8 +50,12 @@ fn wait_for_loading(
 ) {
     let objects = manifests.objects.iter().map(|handle| handle.id().untyped());
     let roads = manifests.roads.iter().map(Into::into);
+    let fences = manifests.fences.iter().map(Into::into);
+    let clothing = manifests.clothing.iter().map(Into::into);
     if objects
         .chain(roads)
+        .chain(fences)
+        .chain(clothing)
         .all(|handle| asset_server.is_loaded(handle))
     {
         info!("finished loading asset manifests");
@@ -48,11 +63,38 @@ fn wait_for_loading(
     }
 }
 
+fn load_mods(
+    mut manifests: ResMut<AssetManifests>,
+    asset_server: Res<AssetServer>,
+    game_paths: Res<GamePaths>,
+    settings: Res<Settings>,
+) {
+    let mod_names = game_paths
+        .get_mod_names()
+        .map_err(|e| error!("unable to get mod names: {e}"))
+        .unwrap_or_default();
+    for mod_name in mod_names {
+        if !settings.mods.enabled(&mod_name) {
+            info!("skipping disabled mod '{mod_name}'");
+            continue;
+        }
+
+        info!("loading mod '{mod_name}'");
+        manifests.load_dir(
+            &asset_server,
+            &game_paths.mods.join(&mod_name),
+            &mods::mod_prefix(&mod_name),
+        );
+    }
+}
+
 /// Resource keep manifests loaded.
 #[derive(Resource)]
 struct AssetManifests {
     objects: Vec<Handle<ObjectManifest>>,
     roads: Vec<Handle<RoadManifest>>,
+    fences: Vec<Handle<FenceManifest>>,
+    clothing: Vec<Handle<ClothingManifest>>,
 }
 
 impl FromWorld for AssetManifests {
@@ -63,9 +105,22 @@ impl FromWorld for AssetManifests {
         let mut manifests = AssetManifests {
             objects: Default::default(),
             roads: Default::default(),
+            fences: Default::default(),
+            clothing: Default::default(),
         };
         let asset_server = world.resource::<AssetServer>();
-        for path in WalkDir::new(&assets_dir)
+        manifests.load_dir(asset_server, &assets_dir, Path::new(""));
+
+        manifests
+    }
+}
+
+impl AssetManifests {
+    /// Loads all manifests from `dir`.
+    ///
+    /// Asset paths are created relative to `dir` with `prefix` prepended.
+    fn load_dir(&mut self, asset_server: &AssetServer, dir: &Path, prefix: &Path) {
+        for path in WalkDir::new(dir)
             .into_iter()
             .filter_map(|entry| entry.ok())
             .map(|entry| entry.into_path())
@@ -75,21 +130,26 @@ impl FromWorld for AssetManifests {
             };
 
             let relative_path = path
-                .strip_prefix(&assets_dir)
-                .unwrap_or_else(|e| panic!("entries should start with {assets_dir:?}: {e}"));
+                .strip_prefix(dir)
+                .unwrap_or_else(|e| panic!("entries should start with {dir:?}: {e}"));
+            let asset_path = prefix.join(relative_path);
 
-            debug!("loading manifest {relative_path:?}");
+            debug!("loading manifest {asset_path:?}");
             match format {
                 ManifestFormat::Object => {
-                    manifests.objects.push(asset_server.load(relative_path));
+                    self.objects.push(asset_server.load(asset_path));
                 }
                 ManifestFormat::Road => {
-                    manifests.roads.push(asset_server.load(relative_path));
+                    self.roads.push(asset_server.load(asset_path));
+                }
+                ManifestFormat::Fence => {
+                    self.fences.push(asset_server.load(asset_path));
+                }
+                ManifestFormat::Clothing => {
+                    self.clothing.push(asset_server.load(asset_path));
                 }
             }
         }
-
-        manifests
     }
 }
 
@@ -97,6 +157,8 @@ impl FromWorld for AssetManifests {
 enum ManifestFormat {
     Object,
     Road,
+    Fence,
+    Clothing,
 }
 
 impl ManifestFormat {
@@ -118,6 +180,8 @@ impl ManifestFormat {
         match self {
             ManifestFormat::Object => &["object.ron"],
             ManifestFormat::Road => &["road.ron"],
+            ManifestFormat::Fence => &["fence.ron"],
+            ManifestFormat::Clothing => &["clothing.ron"],
         }
     }
 }
@@ -141,18 +205,31 @@ mod tests {
     use std::fs;
 
     use anyhow::Result;
-    use bevy::{reflect::TypeRegistry, scene::ron};
+    use bevy::{reflect::TypeRegistry, scene::ron, utils::HashSet};
     use walkdir::WalkDir;
 
     use super::*;
     use crate::{
         combined_scene_collider::SceneColliderConstructor,
-        game_world::object::{
-            door::Door,
-            placing_object::{side_snap::SideSnap, wall_snap::WallSnap},
-            wall_mount::WallMount,
+        game_world::{
+            actor::task::{cook::Stove, eat::Meal, sleep::Bed, swim::PoolLadder},
+            fire::{Flammable, IgnitionSource},
+            navigation::Obstacle,
+            object::{
+                door::Door,
+                light::ObjectLight,
+                placing_object::{
+                    placement_rules::PlacementRules, side_snap::SideSnap, slot_snap::SlotSnap,
+                    wall_snap::WallSnap,
+                },
+                slots::ObjectSlots,
+                wall_mount::WallMount,
+            },
         },
+        scripting::ObjectScript,
     };
+    use clothing_manifest::{BodySlot, ClothingManifestDeserializer};
+    use fence_manifest::FenceManifestDeserializer;
     use object_manifest::ObjectManifestDeserializer;
     use road_manifest::RoadManifestDeserializer;
 
@@ -162,11 +239,26 @@ mod tests {
         registry.register::<WallMount>();
         registry.register::<WallSnap>();
         registry.register::<SideSnap>();
+        registry.register::<PlacementRules>();
+        registry.register::<SlotSnap>();
+        registry.register::<ObjectSlots>();
         registry.register::<Door>();
         registry.register::<SceneColliderConstructor>();
+        registry.register::<Obstacle>();
+        registry.register::<Flammable>();
+        registry.register::<IgnitionSource>();
+        registry.register::<Bed>();
+        registry.register::<Stove>();
+        registry.register::<Meal>();
+        registry.register::<PoolLadder>();
+        registry.register::<ObjectLight>();
+        registry.register::<ObjectScript>();
 
         let mut objects_count = 0;
         let mut roads_count = 0;
+        let mut fences_count = 0;
+        let mut clothing_count = 0;
+        let mut covered_slots = HashSet::default();
         for path in WalkDir::new("../app/assets/base")
             .into_iter()
             .filter_map(|entry| entry.ok())
@@ -192,11 +284,30 @@ mod tests {
                     ron::Options::default().from_str_seed(&string, seed)?;
                     roads_count += 1;
                 }
+                ManifestFormat::Fence => {
+                    let seed = FenceManifestDeserializer { dir: None };
+                    ron::Options::default().from_str_seed(&string, seed)?;
+                    fences_count += 1;
+                }
+                ManifestFormat::Clothing => {
+                    let seed = ClothingManifestDeserializer { dir: None };
+                    let manifest = ron::Options::default().from_str_seed(&string, seed)?;
+                    covered_slots.extend(manifest.slots);
+                    clothing_count += 1;
+                }
             }
         }
 
         assert!(objects_count > 0);
         assert!(roads_count > 0);
+        assert!(fences_count > 0);
+        assert!(clothing_count > 0);
+        for slot in BodySlot::iter() {
+            assert!(
+                covered_slots.contains(&slot),
+                "{slot:?} should have at least one clothing item"
+            );
+        }
 
         Ok(())
     }
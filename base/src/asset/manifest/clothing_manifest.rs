@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AssetPath, AsyncReadExt, LoadContext},
+    prelude::*,
+    scene::ron,
+};
+use serde::{de::DeserializeSeed, Deserialize, Deserializer, Serialize};
+use strum::EnumIter;
+
+use super::{GeneralManifest, ManifestFormat, MapPaths};
+use crate::asset;
+
+#[derive(Default)]
+pub(super) struct ClothingLoader;
+
+impl AssetLoader for ClothingLoader {
+    type Asset = ClothingManifest;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut string = String::new();
+        reader.read_to_string(&mut string).await?;
+
+        let dir = load_context.path().parent();
+        let seed = ClothingManifestDeserializer { dir };
+
+        let manifest = ron::Options::default().from_str_seed(&string, seed)?;
+
+        Ok(manifest)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        ManifestFormat::Clothing.extensions()
+    }
+}
+
+/// Clothing item that can be worn by actors.
+///
+/// The scene should contain a mesh skinned to a skeleton with the same bone names as actors.
+#[derive(TypePath, Serialize, Deserialize, Asset)]
+pub struct ClothingManifest {
+    pub general: GeneralManifest,
+    pub scene: AssetPath<'static>,
+    /// Body parts covered by the item.
+    ///
+    /// Items with overlapping slots can't be worn together.
+    pub slots: Vec<BodySlot>,
+}
+
+impl MapPaths for ClothingManifest {
+    fn map_paths(&mut self, dir: &Path) {
+        asset::change_parent_dir(&mut self.scene, dir);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, EnumIter, Eq, Hash, PartialEq, Reflect, Serialize)]
+pub enum BodySlot {
+    Head,
+    Top,
+    Bottom,
+    Feet,
+}
+
+impl BodySlot {
+    pub fn text(self) -> &'static str {
+        match self {
+            BodySlot::Head => "Head",
+            BodySlot::Top => "Top",
+            BodySlot::Bottom => "Bottom",
+            BodySlot::Feet => "Feet",
+        }
+    }
+}
+
+pub(super) struct ClothingManifestDeserializer<'a> {
+    pub(super) dir: Option<&'a Path>,
+}
+
+impl<'de> DeserializeSeed<'de> for ClothingManifestDeserializer<'_> {
+    type Value = ClothingManifest;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        ClothingManifest::deserialize(deserializer).map(|mut manifest| {
+            if let Some(dir) = self.dir {
+                manifest.map_paths(dir);
+            }
+            manifest
+        })
+    }
+}
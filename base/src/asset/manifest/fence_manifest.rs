@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AssetPath, AsyncReadExt, LoadContext},
+    prelude::*,
+    scene::ron,
+};
+use serde::{de::DeserializeSeed, Deserialize, Deserializer, Serialize};
+
+use super::{GeneralManifest, ManifestFormat, MapPaths};
+use crate::asset;
+
+#[derive(Default)]
+pub(super) struct FenceLoader;
+
+impl AssetLoader for FenceLoader {
+    type Asset = FenceManifest;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut string = String::new();
+        reader.read_to_string(&mut string).await?;
+
+        let dir = load_context.path().parent();
+        let seed = FenceManifestDeserializer { dir };
+
+        let manifest = ron::Options::default().from_str_seed(&string, seed)?;
+
+        Ok(manifest)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        ManifestFormat::Fence.extensions()
+    }
+}
+
+#[derive(TypePath, Serialize, Deserialize, Asset)]
+pub struct FenceManifest {
+    pub general: GeneralManifest,
+    pub material: AssetPath<'static>,
+    pub modules: FenceModules,
+}
+
+impl MapPaths for FenceManifest {
+    fn map_paths(&mut self, dir: &Path) {
+        asset::change_parent_dir(&mut self.material, dir);
+    }
+}
+
+/// Dimensions of the parts from which the fence mesh is assembled.
+#[derive(Clone, Default, Deserialize, Reflect, Serialize)]
+pub struct FenceModules {
+    pub height: f32,
+    pub post_width: f32,
+    /// Maximum distance between two posts.
+    ///
+    /// Posts are distributed evenly along the segment.
+    pub post_spacing: f32,
+    /// Heights of horizontal rails from the ground.
+    pub rails: Vec<f32>,
+    pub rail_thickness: f32,
+}
+
+pub(super) struct FenceManifestDeserializer<'a> {
+    pub(super) dir: Option<&'a Path>,
+}
+
+impl<'de> DeserializeSeed<'de> for FenceManifestDeserializer<'_> {
+    type Value = FenceManifest;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        FenceManifest::deserialize(deserializer).map(|mut manifest| {
+            if let Some(dir) = self.dir {
+                manifest.map_paths(dir);
+            }
+            manifest
+        })
+    }
+}
@@ -66,6 +66,8 @@ pub struct ObjectManifest {
     pub general: GeneralManifest,
     pub scene: AssetPath<'static>,
     pub category: ObjectCategory,
+    /// Cost of buying the object, free if not specified.
+    pub price: u32,
     pub preview_translation: Vec3,
     pub components: Vec<Box<dyn PartialReflect>>,
     pub place_components: Vec<Box<dyn PartialReflect>>,
@@ -86,6 +88,7 @@ enum ObjectManifestField {
     General,
     Scene,
     Category,
+    Price,
     PreviewTranslation,
     Components,
     PlaceComponents,
@@ -103,6 +106,8 @@ pub enum ObjectCategory {
     Furniture,
     Windows,
     Doors,
+    /// Delivered by services, can't be bought.
+    Food,
 }
 
 impl ObjectCategory {
@@ -124,6 +129,21 @@ impl ObjectCategory {
         ObjectCategory::Doors,
     ];
 
+    pub fn text(self) -> &'static str {
+        match self {
+            ObjectCategory::Rocks => "Rocks",
+            ObjectCategory::Foliage => "Foliage",
+            ObjectCategory::OutdoorFurniture => "Outdoor furniture",
+            ObjectCategory::OutdoorActivities => "Outdoor activities",
+            ObjectCategory::Street => "Street",
+            ObjectCategory::Electronics => "Electronics",
+            ObjectCategory::Furniture => "Furniture",
+            ObjectCategory::Windows => "Windows",
+            ObjectCategory::Doors => "Doors",
+            ObjectCategory::Food => "Food",
+        }
+    }
+
     pub fn glyph(self) -> &'static str {
         match self {
             ObjectCategory::Rocks => "🗻",
@@ -135,6 +155,7 @@ impl ObjectCategory {
             ObjectCategory::Furniture => "💺",
             ObjectCategory::Windows => "🔲",
             ObjectCategory::Doors => "🚪",
+            ObjectCategory::Food => "🍕",
         }
     }
 }
@@ -167,6 +188,7 @@ impl<'de> Visitor<'de> for ObjectManifestDeserializer<'_> {
         let mut general = None;
         let mut scene = None;
         let mut category = None;
+        let mut price = None;
         let mut preview_translation = None;
         let mut components = None;
         let mut place_components = None;
@@ -197,6 +219,14 @@ impl<'de> Visitor<'de> for ObjectManifestDeserializer<'_> {
                     }
                     category = Some(map.next_value()?);
                 }
+                ObjectManifestField::Price => {
+                    if price.is_some() {
+                        return Err(de::Error::duplicate_field(
+                            ObjectManifestField::Price.into(),
+                        ));
+                    }
+                    price = Some(map.next_value()?);
+                }
                 ObjectManifestField::PreviewTranslation => {
                     if preview_translation.is_some() {
                         return Err(de::Error::duplicate_field(
@@ -244,6 +274,7 @@ impl<'de> Visitor<'de> for ObjectManifestDeserializer<'_> {
             scene.ok_or_else(|| de::Error::missing_field(ObjectManifestField::Scene.into()))?;
         let category = category
             .ok_or_else(|| de::Error::missing_field(ObjectManifestField::Category.into()))?;
+        let price = price.unwrap_or_default();
         let preview_translation = preview_translation.ok_or_else(|| {
             de::Error::missing_field(ObjectManifestField::PreviewTranslation.into())
         })?;
@@ -255,6 +286,7 @@ impl<'de> Visitor<'de> for ObjectManifestDeserializer<'_> {
             general,
             scene,
             category,
+            price,
             preview_translation,
             components,
             place_components,
@@ -0,0 +1,153 @@
+use std::{
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use bevy::{
+    asset::io::{
+        AssetReader, AssetReaderError, AssetSource, AssetSourceBuilder, AssetSourceEvent,
+        AssetWatcher, ErasedAssetReader, PathStream, Reader,
+    },
+    tasks::futures_lite::StreamExt,
+};
+
+/// Directory prefix for asset paths from mods.
+///
+/// Assets from mods are addressed as `mods/<mod name>/<path>`, which keeps their IDs
+/// separate from the built-in assets and from each other.
+pub const MODS_PREFIX: &str = "mods";
+
+/// Creates the default asset source that also reads mods from the specified directory.
+///
+/// Should be registered before [`bevy::asset::AssetPlugin`].
+/// Both directories are watched for changes if enabled in the plugin.
+pub fn asset_source(mods_dir: &Path) -> AssetSourceBuilder {
+    let mods_dir = mods_dir.to_string_lossy().into_owned();
+    let watcher_dir = mods_dir.clone();
+    AssetSource::build()
+        .with_reader(move || {
+            Box::new(ModsAssetReader {
+                assets: AssetSource::get_default_reader(ASSETS_DIR.to_string())(),
+                mods: AssetSource::get_default_reader(mods_dir.clone())(),
+            })
+        })
+        .with_watcher(move |sender| {
+            let assets = AssetSource::get_default_watcher(ASSETS_DIR.to_string(), DEBOUNCE_WAIT)(
+                sender.clone(),
+            )?;
+
+            // Mod watcher reports paths relative to the mods directory,
+            // so forward its events with the prefix.
+            let (mods_sender, mods_receiver) = crossbeam_channel::unbounded();
+            let mods =
+                AssetSource::get_default_watcher(watcher_dir.clone(), DEBOUNCE_WAIT)(mods_sender)?;
+            thread::spawn(move || {
+                for event in mods_receiver {
+                    if sender.send(prefix_event(event)).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            Some(Box::new(ModsAssetWatcher {
+                _assets: assets,
+                _mods: mods,
+            }))
+        })
+}
+
+const ASSETS_DIR: &str = "assets";
+
+/// Same delay as for the default watcher.
+const DEBOUNCE_WAIT: Duration = Duration::from_millis(300);
+
+fn prefix_event(event: AssetSourceEvent) -> AssetSourceEvent {
+    let prefix = |path: PathBuf| Path::new(MODS_PREFIX).join(path);
+    match event {
+        AssetSourceEvent::AddedAsset(path) => AssetSourceEvent::AddedAsset(prefix(path)),
+        AssetSourceEvent::ModifiedAsset(path) => AssetSourceEvent::ModifiedAsset(prefix(path)),
+        AssetSourceEvent::RemovedAsset(path) => AssetSourceEvent::RemovedAsset(prefix(path)),
+        AssetSourceEvent::RenamedAsset { old, new } => AssetSourceEvent::RenamedAsset {
+            old: prefix(old),
+            new: prefix(new),
+        },
+        AssetSourceEvent::AddedMeta(path) => AssetSourceEvent::AddedMeta(prefix(path)),
+        AssetSourceEvent::ModifiedMeta(path) => AssetSourceEvent::ModifiedMeta(prefix(path)),
+        AssetSourceEvent::RemovedMeta(path) => AssetSourceEvent::RemovedMeta(prefix(path)),
+        AssetSourceEvent::RenamedMeta { old, new } => AssetSourceEvent::RenamedMeta {
+            old: prefix(old),
+            new: prefix(new),
+        },
+        AssetSourceEvent::AddedFolder(path) => AssetSourceEvent::AddedFolder(prefix(path)),
+        AssetSourceEvent::RemovedFolder(path) => AssetSourceEvent::RemovedFolder(prefix(path)),
+        AssetSourceEvent::RenamedFolder { old, new } => AssetSourceEvent::RenamedFolder {
+            old: prefix(old),
+            new: prefix(new),
+        },
+        AssetSourceEvent::RemovedUnknown { path, is_meta } => AssetSourceEvent::RemovedUnknown {
+            path: prefix(path),
+            is_meta,
+        },
+    }
+}
+
+/// Routes paths with [`MODS_PREFIX`] to the mods directory and the rest to built-in assets.
+struct ModsAssetReader {
+    assets: Box<dyn ErasedAssetReader>,
+    mods: Box<dyn ErasedAssetReader>,
+}
+
+impl ModsAssetReader {
+    fn route<'a>(&'a self, path: &'a Path) -> (&'a dyn ErasedAssetReader, &'a Path) {
+        match path.strip_prefix(MODS_PREFIX) {
+            Ok(mod_path) => (&*self.mods, mod_path),
+            Err(_) => (&*self.assets, path),
+        }
+    }
+}
+
+impl AssetReader for ModsAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let (reader, path) = self.route(path);
+        reader.read(path).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let (reader, path) = self.route(path);
+        reader.read_meta(path).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        match path.strip_prefix(MODS_PREFIX) {
+            Ok(mod_path) => {
+                let stream = self.mods.read_directory(mod_path).await?;
+                Ok(Box::new(
+                    stream.map(|path| Path::new(MODS_PREFIX).join(path)),
+                ))
+            }
+            Err(_) => self.assets.read_directory(path).await,
+        }
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        let (reader, path) = self.route(path);
+        reader.is_directory(path).await
+    }
+}
+
+/// Keeps watchers for both directories alive.
+struct ModsAssetWatcher {
+    _assets: Box<dyn AssetWatcher>,
+    _mods: Box<dyn AssetWatcher>,
+}
+
+impl AssetWatcher for ModsAssetWatcher {}
+
+/// Returns asset path prefix for the mod.
+pub(super) fn mod_prefix(mod_name: &str) -> PathBuf {
+    Path::new(MODS_PREFIX).join(mod_name)
+}
@@ -0,0 +1,227 @@
+mod occlusion;
+
+use bevy::{asset::AssetPath, audio::Volume, prelude::*};
+use num_enum::IntoPrimitive;
+use strum::EnumIter;
+
+use crate::{
+    asset::collection::{AssetCollection, Collection},
+    core::GameState,
+    game_world::WorldState,
+    settings::{AudioSettings, Settings, SettingsApply},
+};
+use occlusion::OcclusionPlugin;
+
+/// Plays music and sound effects.
+///
+/// Master volume is applied globally, other volumes depend on [`AudioChannel`] of the sound.
+/// Spatial sounds are also muffled by walls.
+pub(super) struct AudioPlugin;
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(OcclusionPlugin)
+            .init_resource::<Collection<Sound>>()
+            .add_observer(init)
+            .add_observer(apply_volumes)
+            .add_observer(play_sound)
+            .add_systems(OnEnter(GameState::Menu), switch_playlist(Playlist::Menu))
+            .add_systems(OnEnter(WorldState::World), switch_playlist(Playlist::Menu))
+            .add_systems(
+                OnEnter(WorldState::FamilyEditor),
+                switch_playlist(Playlist::Family),
+            )
+            .add_systems(
+                OnEnter(WorldState::Family),
+                switch_playlist(Playlist::Family),
+            )
+            .add_systems(OnEnter(WorldState::City), switch_playlist(Playlist::City))
+            .add_systems(
+                Update,
+                play_next_track.run_if(resource_exists::<MusicPlayback>),
+            );
+    }
+}
+
+fn init(
+    trigger: Trigger<OnAdd, AudioChannel>,
+    settings: Res<Settings>,
+    mut sounds: Query<(&AudioChannel, &mut PlaybackSettings)>,
+) {
+    let (&channel, mut playback_settings) = sounds.get_mut(trigger.entity()).unwrap();
+    // Master volume will be applied from `GlobalVolume`.
+    playback_settings.volume = Volume::new(channel.volume(&settings.audio));
+}
+
+/// Updates already playing sounds.
+///
+/// Sink volume overrides [`GlobalVolume`], so master volume is applied manually.
+fn apply_volumes(
+    _trigger: Trigger<SettingsApply>,
+    settings: Res<Settings>,
+    sinks: Query<(&AudioSink, &AudioChannel)>,
+) {
+    for (sink, &channel) in &sinks {
+        sink.set_volume(settings.audio.master * channel.volume(&settings.audio));
+    }
+}
+
+fn play_sound(trigger: Trigger<SoundPlay>, mut commands: Commands, sounds: Res<Collection<Sound>>) {
+    let sound = trigger.sound;
+    let player = AudioPlayer(sounds.handle(sound));
+    if trigger.entity() == Entity::PLACEHOLDER {
+        trace!("playing `{sound:?}`");
+        commands.spawn((player, sound.channel(), PlaybackSettings::DESPAWN));
+    } else {
+        trace!("playing `{sound:?}` on `{}`", trigger.entity());
+        commands.entity(trigger.entity()).with_child((
+            player,
+            sound.channel(),
+            PlaybackSettings::DESPAWN.with_spatial(true),
+            Transform::from_translation(trigger.translation),
+        ));
+    }
+}
+
+fn switch_playlist(
+    playlist: Playlist,
+) -> impl Fn(Commands, Option<Res<MusicPlayback>>, Query<Entity, With<Music>>) {
+    move |mut commands, playback, music| {
+        if playback.is_some_and(|playback| playback.playlist == playlist) {
+            return;
+        }
+
+        debug!("switching to `{playlist:?}` playlist");
+        for entity in &music {
+            commands.entity(entity).despawn();
+        }
+        commands.insert_resource(MusicPlayback { playlist, track: 0 });
+    }
+}
+
+/// Starts the next track after the previous one is finished.
+///
+/// Finished tracks are despawned automatically.
+fn play_next_track(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut playback: ResMut<MusicPlayback>,
+    music: Query<(), With<Music>>,
+) {
+    if !music.is_empty() {
+        return;
+    }
+
+    let tracks = playback.playlist.tracks();
+    let path = tracks[playback.track % tracks.len()];
+    debug!("playing '{path}'");
+    commands.spawn((
+        Music,
+        AudioPlayer::<AudioSource>(asset_server.load(path)),
+        PlaybackSettings::DESPAWN,
+    ));
+    playback.track += 1;
+}
+
+/// Category of a sound that determines its volume.
+#[derive(Component, Clone, Copy)]
+#[require(PlaybackSettings)]
+pub enum AudioChannel {
+    Music,
+    Sfx,
+    Ui,
+}
+
+impl AudioChannel {
+    fn volume(self, audio: &AudioSettings) -> f32 {
+        match self {
+            AudioChannel::Music => audio.music,
+            AudioChannel::Sfx => audio.sfx,
+            AudioChannel::Ui => audio.ui,
+        }
+    }
+}
+
+/// Plays a sound effect.
+///
+/// If triggered for an entity, the sound will be spatial and attached to it.
+#[derive(Event)]
+pub struct SoundPlay {
+    pub sound: Sound,
+    /// Position relative to the target entity.
+    pub translation: Vec3,
+}
+
+impl SoundPlay {
+    pub fn new(sound: Sound) -> Self {
+        Self {
+            sound,
+            translation: Vec3::ZERO,
+        }
+    }
+
+    pub fn with_translation(mut self, translation: Vec3) -> Self {
+        self.translation = translation;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, EnumIter, IntoPrimitive)]
+#[repr(usize)]
+pub enum Sound {
+    Click,
+    PlaceObject,
+    BuildWall,
+    Footstep,
+}
+
+impl Sound {
+    fn channel(self) -> AudioChannel {
+        match self {
+            Sound::Click => AudioChannel::Ui,
+            Sound::PlaceObject | Sound::BuildWall | Sound::Footstep => AudioChannel::Sfx,
+        }
+    }
+}
+
+impl AssetCollection for Sound {
+    type AssetType = AudioSource;
+
+    fn asset_path(&self) -> AssetPath<'static> {
+        match self {
+            Sound::Click => "base/sounds/click.ogg".into(),
+            Sound::PlaceObject => "base/sounds/place_object.ogg".into(),
+            Sound::BuildWall => "base/sounds/build_wall.ogg".into(),
+            Sound::Footstep => "base/sounds/footstep.ogg".into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Playlist {
+    Menu,
+    Family,
+    City,
+}
+
+impl Playlist {
+    /// Tracks are loaded on demand since only one is playing at a time.
+    fn tracks(self) -> &'static [&'static str] {
+        match self {
+            Playlist::Menu => &["base/music/menu.ogg"],
+            Playlist::Family => &["base/music/family_1.ogg", "base/music/family_2.ogg"],
+            Playlist::City => &["base/music/city_1.ogg", "base/music/city_2.ogg"],
+        }
+    }
+}
+
+#[derive(Resource)]
+struct MusicPlayback {
+    playlist: Playlist,
+    /// Index of the next track, wraps around the playlist.
+    track: usize,
+}
+
+#[derive(Component)]
+#[require(AudioChannel(|| AudioChannel::Music))]
+struct Music;
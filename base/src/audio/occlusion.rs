@@ -0,0 +1,56 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use super::AudioChannel;
+use crate::{game_world::Layer, settings::Settings};
+
+/// Muffles spatial sounds behind walls.
+///
+/// Walls between the listener and a sound are counted with a ray and each one lowers the volume.
+/// Bevy audio doesn't support filters, so muffling is done only by attenuation.
+pub(super) struct OcclusionPlugin;
+
+impl Plugin for OcclusionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            occlude
+                .never_param_warn()
+                .after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Volume multiplier for each wall between the listener and the sound.
+const WALL_ATTENUATION: f32 = 0.35;
+
+/// Maximum number of walls that affect the volume.
+const MAX_WALLS: u32 = 3;
+
+fn occlude(
+    settings: Res<Settings>,
+    spatial_query: SpatialQuery,
+    listener_transform: Single<&GlobalTransform, With<SpatialListener>>,
+    sounds: Query<(Entity, &SpatialAudioSink, &AudioChannel, &GlobalTransform)>,
+) {
+    let origin = listener_transform.translation();
+    let filter = SpatialQueryFilter::from_mask(Layer::Wall);
+    for (entity, sink, &channel, transform) in &sounds {
+        let Ok((direction, distance)) = Dir3::new_and_length(transform.translation() - origin)
+        else {
+            continue;
+        };
+
+        let walls = spatial_query
+            .ray_hits(origin, direction, distance, MAX_WALLS, true, &filter)
+            .len();
+        // Sink volume overrides `GlobalVolume`, so master volume is applied manually.
+        let volume = settings.audio.master
+            * channel.volume(&settings.audio)
+            * WALL_ATTENUATION.powi(walls as i32);
+        if sink.volume() != volume {
+            trace!("occluding `{entity}` by {walls} walls");
+            sink.set_volume(volume);
+        }
+    }
+}
@@ -54,6 +54,44 @@ impl DynamicMesh {
         self.indices.clear();
     }
 
+    /// Adds an axis-aligned box between two points.
+    ///
+    /// UVs are in world units to keep texture scale consistent between boxes.
+    pub(super) fn add_cuboid(&mut self, min: Vec3, max: Vec3) {
+        let center = (min + max) / 2.0;
+        let half_size = (max - min) / 2.0;
+
+        // Face axes are ordered so that `u × v = normal` for counterclockwise winding.
+        for (normal, u, v) in [
+            (Vec3::X, Vec3::NEG_Z, Vec3::Y),
+            (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+            (Vec3::Z, Vec3::X, Vec3::Y),
+            (Vec3::NEG_Z, Vec3::NEG_X, Vec3::Y),
+            (Vec3::Y, Vec3::X, Vec3::NEG_Z),
+            (Vec3::NEG_Y, Vec3::X, Vec3::Z),
+        ] {
+            let face_center = center + normal * (normal * half_size).length();
+            let half_u = (u * half_size).length();
+            let half_v = (v * half_size).length();
+
+            let vertices_start = self.vertices_count();
+            for (sign_u, sign_v) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                let position = face_center + u * half_u * sign_u + v * half_v * sign_v;
+                self.positions.push(position.into());
+                self.normals.push(normal.into());
+                self.uvs
+                    .push([(sign_u + 1.0) * half_u, (1.0 - sign_v) * half_v]);
+            }
+
+            self.indices.push(vertices_start);
+            self.indices.push(vertices_start + 1);
+            self.indices.push(vertices_start + 2);
+            self.indices.push(vertices_start);
+            self.indices.push(vertices_start + 2);
+            self.indices.push(vertices_start + 3);
+        }
+    }
+
     pub(super) fn apply(self, mesh: &mut Mesh) {
         mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, self.positions);
         mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs);
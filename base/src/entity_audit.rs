@@ -0,0 +1,66 @@
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
+
+use crate::{core::GameState, error_message::ErrorMessage, settings::Settings};
+
+/// Detects entities that outlive the game session.
+///
+/// Every entity spawned in game should be state-scoped or belong to a state-scoped hierarchy.
+/// When [`DeveloperSettings::entity_audit`](crate::settings::DeveloperSettings::entity_audit)
+/// is enabled, entities are compared on each return to the menu with the ones that existed
+/// on the previous visit, and leftovers are reported as an error.
+pub(super) struct EntityAuditPlugin;
+
+impl Plugin for EntityAuditPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Menu), audit);
+    }
+}
+
+/// Maximum number of leaked entities listed in the error message.
+const MAX_LISTED: usize = 20;
+
+/// Runs after state-scoped entities are despawned, but before
+/// commands from other [`OnEnter`] systems are applied.
+fn audit(
+    mut commands: Commands,
+    mut baseline: Local<Option<EntityHashSet>>,
+    settings: Res<Settings>,
+    entities: Query<(Entity, Option<&Name>, Option<&Parent>)>,
+) {
+    if !settings.developer.entity_audit {
+        *baseline = None;
+        return;
+    }
+
+    let current: EntityHashSet = entities.iter().map(|(entity, ..)| entity).collect();
+    if let Some(baseline) = &*baseline {
+        let leaked: Vec<_> = entities
+            .iter()
+            .filter(|&(entity, ..)| !baseline.contains(&entity))
+            .collect();
+        if leaked.is_empty() {
+            info!("entity count returned to baseline of {}", baseline.len());
+        } else {
+            let mut message = format!(
+                "{} entities outlived the game session (baseline {}, now {}):",
+                leaked.len(),
+                baseline.len(),
+                current.len(),
+            );
+            for &(entity, name, parent) in &leaked {
+                let name = name.map(Name::as_str).unwrap_or("unnamed");
+                match parent {
+                    Some(parent) => error!("leaked `{entity}` ({name}) with parent `{}`", **parent),
+                    None => error!("leaked `{entity}` ({name})"),
+                }
+            }
+            for (entity, name, _) in leaked.iter().take(MAX_LISTED) {
+                let name = name.map(Name::as_str).unwrap_or("unnamed");
+                message += &format!("\n`{entity}` ({name})");
+            }
+            commands.trigger(ErrorMessage::new(message));
+        }
+    }
+
+    *baseline = Some(current);
+}
@@ -1,11 +1,13 @@
 use std::{
-    fs::{self, DirEntry},
-    path::PathBuf,
+    ffi::OsString,
+    fs::{self, DirEntry, File},
+    io::Write,
+    path::{Path, PathBuf},
 };
 
 use anyhow::{Context, Result};
 use app_dirs2::{AppDataType, AppInfo};
-use bevy::prelude::*;
+use bevy::{asset::AssetPath, prelude::*};
 
 /// Initializes [`GamePaths`] resource.
 pub(super) struct GamePathsPlugin;
@@ -17,12 +19,33 @@ impl Plugin for GamePathsPlugin {
 }
 
 const SCENE_EXTENSION: &str = "scn";
+const TIMELINE_EXTENSION: &str = "timeline";
+const SNAPSHOTS_EXTENSION: &str = "snapshots";
+const HEADER_EXTENSION: &str = "header";
+const PREVIEW_EXTENSION: &str = "png";
+const FAMILY_EXTENSION: &str = "family";
+const PACKAGE_EXTENSION: &str = "lifescape";
+pub(crate) const LOG_EXTENSION: &str = "log";
 
 /// Paths with game files, such as settings and savegames.
 #[derive(Resource)]
 pub struct GamePaths {
     pub settings: PathBuf,
+    /// Achievement counters and unlocks of the player.
+    pub achievements: PathBuf,
     pub worlds: PathBuf,
+    pub screenshots: PathBuf,
+    /// Cached thumbnails of rendered objects.
+    pub previews: PathBuf,
+    /// Families saved to the gallery.
+    pub families: PathBuf,
+    /// Packages dropped by the player for import.
+    pub packages: PathBuf,
+    /// Packages exported from the gallery.
+    pub exported_packages: PathBuf,
+    pub mods: PathBuf,
+    /// Log files of game sessions.
+    pub logs: PathBuf,
 }
 
 impl GamePaths {
@@ -32,6 +55,104 @@ impl GamePaths {
         path
     }
 
+    pub fn timeline_path(&self, name: &str) -> PathBuf {
+        let mut path = self.worlds.join(name);
+        path.set_extension(TIMELINE_EXTENSION);
+        path
+    }
+
+    /// Returns path to the metadata written next to the world.
+    pub fn world_header_path(&self, name: &str) -> PathBuf {
+        let mut path = self.worlds.join(name);
+        path.set_extension(HEADER_EXTENSION);
+        path
+    }
+
+    /// Returns directory with named snapshots of a world.
+    pub fn snapshots_dir(&self, world_name: &str) -> PathBuf {
+        let mut path = self.worlds.join(world_name);
+        path.set_extension(SNAPSHOTS_EXTENSION);
+        path
+    }
+
+    pub fn snapshot_path(&self, world_name: &str, name: &str) -> PathBuf {
+        let mut path = self.snapshots_dir(world_name).join(name);
+        path.set_extension(SCENE_EXTENSION);
+        path
+    }
+
+    pub fn snapshot_header_path(&self, world_name: &str, name: &str) -> PathBuf {
+        let mut path = self.snapshots_dir(world_name).join(name);
+        path.set_extension(HEADER_EXTENSION);
+        path
+    }
+
+    /// Returns path to the screenshot captured on the last world save.
+    pub fn world_preview_path(&self, name: &str) -> PathBuf {
+        let mut path = self.worlds.join(name);
+        path.set_extension(PREVIEW_EXTENSION);
+        path
+    }
+
+    /// Returns path to the thumbnail for an object manifest.
+    ///
+    /// The file name is derived from the asset path of the manifest.
+    pub fn preview_path(&self, manifest_path: &AssetPath) -> PathBuf {
+        let name: String = manifest_path
+            .to_string()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let mut path = self.previews.join(name);
+        path.set_extension(PREVIEW_EXTENSION);
+        path
+    }
+
+    pub fn family_path(&self, name: &str) -> PathBuf {
+        let mut path = self.families.join(name);
+        path.set_extension(FAMILY_EXTENSION);
+        path
+    }
+
+    /// Returns path to the thumbnail of a family from the gallery.
+    pub fn family_preview_path(&self, name: &str) -> PathBuf {
+        let mut path = self.families.join(name);
+        path.set_extension(PREVIEW_EXTENSION);
+        path
+    }
+
+    pub fn exported_package_path(&self, name: &str) -> PathBuf {
+        let mut path = self.exported_packages.join(name);
+        path.set_extension(PACKAGE_EXTENSION);
+        path
+    }
+
+    /// Returns paths to all log files, sorted from the oldest to the newest.
+    pub fn get_log_paths(&self) -> Result<Vec<PathBuf>> {
+        let entries = self
+            .logs
+            .read_dir()
+            .with_context(|| format!("unable to read {:?}", self.logs))?;
+        let mut logs = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            if file_stem(&entry, LOG_EXTENSION).is_some() {
+                let modified = entry
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .with_context(|| format!("unable to read {:?} metadata", entry.path()))?;
+                logs.push((modified, entry.path()));
+            }
+        }
+        logs.sort();
+        Ok(logs.into_iter().map(|(_, path)| path).collect())
+    }
+
     pub fn get_world_names(&self) -> Result<Vec<String>> {
         let entries = self
             .worlds
@@ -39,12 +160,83 @@ impl GamePaths {
             .with_context(|| format!("unable to read {:?}", self.worlds))?;
         let mut worlds = Vec::new();
         for entry in entries.filter_map(Result::ok) {
-            if let Some(name) = world_name(&entry) {
+            if let Some(name) = file_stem(&entry, SCENE_EXTENSION) {
                 worlds.push(name);
             }
         }
         Ok(worlds)
     }
+
+    /// Returns names of all snapshots of a world.
+    ///
+    /// Returns an empty list if the world has no snapshots.
+    pub fn get_snapshot_names(&self, world_name: &str) -> Result<Vec<String>> {
+        let dir = self.snapshots_dir(world_name);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = dir
+            .read_dir()
+            .with_context(|| format!("unable to read {dir:?}"))?;
+        let mut snapshots = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            if let Some(name) = file_stem(&entry, SCENE_EXTENSION) {
+                snapshots.push(name);
+            }
+        }
+        Ok(snapshots)
+    }
+
+    /// Returns names of all families saved to the gallery, sorted alphabetically.
+    pub fn get_family_names(&self) -> Result<Vec<String>> {
+        let entries = self
+            .families
+            .read_dir()
+            .with_context(|| format!("unable to read {:?}", self.families))?;
+        let mut families = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            if let Some(name) = file_stem(&entry, FAMILY_EXTENSION) {
+                families.push(name);
+            }
+        }
+        families.sort();
+        Ok(families)
+    }
+
+    /// Returns paths to all packages waiting for import, sorted alphabetically.
+    pub fn get_package_paths(&self) -> Result<Vec<PathBuf>> {
+        let entries = self
+            .packages
+            .read_dir()
+            .with_context(|| format!("unable to read {:?}", self.packages))?;
+        let mut packages = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            if file_stem(&entry, PACKAGE_EXTENSION).is_some() {
+                packages.push(entry.path());
+            }
+        }
+        packages.sort();
+        Ok(packages)
+    }
+
+    /// Returns names of all mods, each mod is a directory inside [`Self::mods`].
+    pub fn get_mod_names(&self) -> Result<Vec<String>> {
+        let entries = self
+            .mods
+            .read_dir()
+            .with_context(|| format!("unable to read {:?}", self.mods))?;
+        let mut mods = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                if let Some(name) = entry.file_name().to_str() {
+                    mods.push(name.to_string());
+                }
+            }
+        }
+        mods.sort();
+        Ok(mods)
+    }
 }
 
 impl Default for GamePaths {
@@ -64,16 +256,100 @@ impl Default for GamePaths {
         settings.push(app_info.name);
         settings.set_extension("ron");
 
-        let mut worlds = config_dir;
+        let mut achievements = config_dir.clone();
+        achievements.push("achievements");
+        achievements.set_extension("ron");
+
+        let mut worlds = config_dir.clone();
         worlds.push("worlds");
         fs::create_dir_all(&worlds)
             .unwrap_or_else(|e| panic!("{worlds:?} should be writable: {e}"));
 
-        Self { settings, worlds }
+        let mut screenshots = config_dir.clone();
+        screenshots.push("screenshots");
+        fs::create_dir_all(&screenshots)
+            .unwrap_or_else(|e| panic!("{screenshots:?} should be writable: {e}"));
+
+        let mut previews = config_dir.clone();
+        previews.push("previews");
+        fs::create_dir_all(&previews)
+            .unwrap_or_else(|e| panic!("{previews:?} should be writable: {e}"));
+
+        let mut families = config_dir.clone();
+        families.push("families");
+        fs::create_dir_all(&families)
+            .unwrap_or_else(|e| panic!("{families:?} should be writable: {e}"));
+
+        let mut packages = config_dir.clone();
+        packages.push("packages");
+        fs::create_dir_all(&packages)
+            .unwrap_or_else(|e| panic!("{packages:?} should be writable: {e}"));
+
+        let mut exported_packages = packages.clone();
+        exported_packages.push("exported");
+        fs::create_dir_all(&exported_packages)
+            .unwrap_or_else(|e| panic!("{exported_packages:?} should be writable: {e}"));
+
+        let mut logs = config_dir.clone();
+        logs.push("logs");
+        fs::create_dir_all(&logs).unwrap_or_else(|e| panic!("{logs:?} should be writable: {e}"));
+
+        let mut mods = config_dir;
+        mods.push("mods");
+        fs::create_dir_all(&mods).unwrap_or_else(|e| panic!("{mods:?} should be writable: {e}"));
+
+        Self {
+            settings,
+            achievements,
+            worlds,
+            screenshots,
+            previews,
+            families,
+            packages,
+            exported_packages,
+            mods,
+            logs,
+        }
     }
 }
 
-fn world_name(entry: &DirEntry) -> Option<String> {
+/// Writes the whole content to the file or leaves the previous content untouched.
+///
+/// The content is written into a temporary file next to the target, flushed to the disk
+/// and then renamed over the target, so a crash in the middle never leaves a partial file.
+pub fn write_atomic(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    let temp_path = path_with_suffix(path, "tmp");
+    let mut file =
+        File::create(&temp_path).with_context(|| format!("unable to create {temp_path:?}"))?;
+    file.write_all(content.as_ref())
+        .with_context(|| format!("unable to write {temp_path:?}"))?;
+    file.sync_all()
+        .with_context(|| format!("unable to flush {temp_path:?}"))?;
+
+    fs::rename(&temp_path, path)
+        .with_context(|| format!("unable to replace {path:?} with {temp_path:?}"))
+}
+
+/// Moves a file that failed to parse aside, so it won't be overwritten with defaults.
+///
+/// Returns the path of the backup.
+pub fn backup_corrupted(path: &Path) -> Result<PathBuf> {
+    let backup_path = path_with_suffix(path, "corrupted");
+    fs::rename(path, &backup_path)
+        .with_context(|| format!("unable to move {path:?} to {backup_path:?}"))?;
+
+    Ok(backup_path)
+}
+
+/// Appends a suffix after the file extension.
+fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(".");
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+fn file_stem(entry: &DirEntry, expected_extension: &str) -> Option<String> {
     let file_type = entry.file_type().ok()?;
     if !file_type.is_file() {
         return None;
@@ -81,9 +357,65 @@ fn world_name(entry: &DirEntry) -> Option<String> {
 
     let path = entry.path();
     let extension = path.extension()?;
-    if extension != SCENE_EXTENSION {
+    if extension != expected_extension {
         return None;
     }
 
     path.file_stem()?.to_str().map(|stem| stem.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    #[test]
+    fn atomic_write() -> Result<()> {
+        let dir = test_dir("atomic_write")?;
+        let path = dir.join("settings.ron");
+
+        write_atomic(&path, "first")?;
+        write_atomic(&path, "second")?;
+
+        assert_eq!(fs::read_to_string(&path)?, "second");
+        assert!(!path_with_suffix(&path, "tmp").exists());
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn corrupted_backup() -> Result<()> {
+        let dir = test_dir("corrupted_backup")?;
+        let path = dir.join("world.scn");
+        fs::write(&path, "invalid")?;
+
+        let backup_path = backup_corrupted(&path)?;
+
+        assert_eq!(backup_path, dir.join("world.scn.corrupted"));
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(backup_path)?, "invalid");
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn suffix() {
+        assert_eq!(
+            path_with_suffix(Path::new("dir/file.ron"), "tmp"),
+            Path::new("dir/file.ron.tmp")
+        );
+        assert_eq!(
+            path_with_suffix(Path::new("file"), "corrupted"),
+            Path::new("file.corrupted")
+        );
+    }
+
+    fn test_dir(name: &str) -> Result<PathBuf> {
+        let dir = env::temp_dir().join(format!("project_harmonia_{name}_{}", process::id()));
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+}
@@ -1,34 +1,73 @@
 pub mod actor;
+pub mod auto_pause;
 pub mod city;
 pub mod commands_history;
+pub mod cursor_mode;
+pub mod difficulty;
+pub mod event_log;
 pub mod family;
+pub(crate) mod fire;
+mod graphics_quality;
+mod grid_picking;
 pub mod highlighting;
+pub mod loading;
 pub mod navigation;
 pub mod object;
-mod player_camera;
+pub mod photo_mode;
+pub mod play_time;
+pub mod player_camera;
+pub mod players;
+mod scene_validation;
 mod segment;
+pub mod stats;
+pub mod time_speed;
+pub mod world_generation;
+pub mod world_management;
 
-use std::fs;
+use std::{fs, path::Path};
 
 use anyhow::{Context, Result};
 use avian3d::prelude::*;
 use bevy::{
     prelude::*,
+    reflect::TypeRegistry,
+    render::view::screenshot::{save_to_disk, Screenshot},
     scene::{ron, serde::SceneDeserializer},
+    window::PrimaryWindow,
 };
 use bevy_replicon::prelude::*;
 use serde::de::DeserializeSeed;
 
-use super::{core::GameState, error_message::error_message, game_paths::GamePaths};
+use super::{
+    core::GameState,
+    error_message::{error_message, ErrorMessage},
+    game_paths::{backup_corrupted, write_atomic, GamePaths},
+    notification::Notification,
+};
 use actor::{Actor, ActorPlugin};
+use auto_pause::AutoPausePlugin;
 use city::CityPlugin;
 use commands_history::CommandHistoryPlugin;
-use family::FamilyPlugin;
+use cursor_mode::CursorModePlugin;
+use difficulty::DifficultyPlugin;
+use event_log::EventLogPlugin;
+use family::{Family, FamilyPlugin};
+use fire::FirePlugin;
+use graphics_quality::GraphicsQualityPlugin;
+use grid_picking::GridPickingPlugin;
 use highlighting::HighlightingPlugin;
+use loading::LoadingPlugin;
 use navigation::NavigationPlugin;
 use object::ObjectPlugin;
+use photo_mode::PhotoModePlugin;
+use play_time::{PlayTime, PlayTimePlugin, WorldPlayTime};
 use player_camera::PlayerCameraPlugin;
+use players::PlayersPlugin;
 use segment::SegmentPlugin;
+use stats::StatsPlugin;
+use time_speed::TimeSpeedPlugin;
+use world_generation::WorldGenerationPlugin;
+use world_management::SaveHeader;
 
 pub(super) struct GameWorldPlugin;
 
@@ -36,19 +75,50 @@ impl Plugin for GameWorldPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             ActorPlugin,
+            AutoPausePlugin,
             CityPlugin,
             SegmentPlugin,
             FamilyPlugin,
+            GraphicsQualityPlugin,
             HighlightingPlugin,
+            LoadingPlugin,
             NavigationPlugin,
             ObjectPlugin,
+            PhotoModePlugin,
             PlayerCameraPlugin,
+            PlayersPlugin,
             CommandHistoryPlugin,
+            CursorModePlugin,
+        ))
+        .add_plugins((
+            DifficultyPlugin,
+            EventLogPlugin,
+            FirePlugin,
+            GridPickingPlugin,
+            PlayTimePlugin,
+            StatsPlugin,
+            TimeSpeedPlugin,
+            WorldGenerationPlugin,
         ))
+        .configure_sets(
+            FixedUpdate,
+            (
+                SimulationSet::Clock,
+                SimulationSet::Needs,
+                SimulationSet::Autonomy,
+                SimulationSet::Hazards,
+                SimulationSet::Economy,
+            )
+                .chain()
+                .run_if(server_or_singleplayer)
+                .run_if(in_state(GameState::InGame)),
+        )
         .add_sub_state::<WorldState>()
         .enable_state_scoped_entities::<WorldState>()
         .add_observer(save.pipe(error_message))
+        .add_observer(save_snapshot.pipe(error_message))
         .add_observer(load.pipe(error_message))
+        .add_observer(load_snapshot.pipe(error_message))
         .add_systems(
             PreUpdate,
             start_game
@@ -62,11 +132,14 @@ impl Plugin for GameWorldPlugin {
 /// Saves world to disk with the name from [`WorldName`] resource.
 fn save(
     _trigger: Trigger<GameSave>,
+    mut commands: Commands,
     world: &World,
     world_name: Res<WorldName>,
     game_paths: Res<GamePaths>,
-    registry: Res<AppTypeRegistry>,
+    world_play_time: Res<WorldPlayTime>,
     actors: Query<Entity, With<Actor>>,
+    families: Query<(&Name, &PlayTime), With<Family>>,
+    windows: Query<(), With<PrimaryWindow>>,
 ) -> Result<()> {
     let world_path = game_paths.world_path(&world_name.0);
     info!("saving world to {world_path:?}");
@@ -74,45 +147,148 @@ fn save(
     fs::create_dir_all(&game_paths.worlds)
         .with_context(|| format!("unable to create {world_path:?}"))?;
 
-    // Extract components that we don't replicate, but serialize.
-    let mut scene = DynamicSceneBuilder::from_world(world)
-        .deny_all()
-        .allow_component::<Transform>()
-        .extract_entities(actors.iter())
-        .build();
+    let bytes = serialize_world(world, actors.iter());
+    write_atomic(&world_path, bytes)
+        .with_context(|| format!("unable to save game to {world_path:?}"))?;
+    create_header(&world_play_time, &families)
+        .write(&game_paths.world_header_path(&world_name.0))?;
 
-    // Extract all replicated components that are reflected.
-    let registry = registry.read();
-    bevy_replicon::scene::replicate_into(&mut scene, world);
-    let bytes = scene
-        .serialize(&registry)
-        .expect("game world should be serialized");
+    // Displayed in the world browser.
+    if !windows.is_empty() {
+        let preview_path = game_paths.world_preview_path(&world_name.0);
+        debug!("capturing world preview to {preview_path:?}");
+        commands
+            .spawn(Screenshot::primary_window())
+            .observe(save_to_disk(preview_path));
+    }
+
+    commands.trigger(Notification::info("Game saved"));
+
+    Ok(())
+}
+
+/// Saves world to a named snapshot of the world from [`WorldName`] resource.
+fn save_snapshot(
+    trigger: Trigger<SnapshotSave>,
+    mut commands: Commands,
+    world: &World,
+    world_name: Res<WorldName>,
+    game_paths: Res<GamePaths>,
+    world_play_time: Res<WorldPlayTime>,
+    actors: Query<Entity, With<Actor>>,
+    families: Query<(&Name, &PlayTime), With<Family>>,
+) -> Result<()> {
+    let snapshots_dir = game_paths.snapshots_dir(&world_name.0);
+    let snapshot_path = game_paths.snapshot_path(&world_name.0, &trigger.0);
+    info!("saving snapshot to {snapshot_path:?}");
+
+    fs::create_dir_all(&snapshots_dir)
+        .with_context(|| format!("unable to create {snapshots_dir:?}"))?;
+
+    let bytes = serialize_world(world, actors.iter());
+    write_atomic(&snapshot_path, bytes)
+        .with_context(|| format!("unable to save snapshot to {snapshot_path:?}"))?;
+    create_header(&world_play_time, &families)
+        .write(&game_paths.snapshot_header_path(&world_name.0, &trigger.0))?;
 
-    fs::write(&world_path, bytes).with_context(|| format!("unable to save game to {world_path:?}"))
+    commands.trigger(Notification::info(format!("Saved as {}", trigger.0)));
+
+    Ok(())
 }
 
 /// Loads world from disk with the name from [`WorldName`] resource.
 fn load(
     _trigger: Trigger<GameLoad>,
-    mut commands: Commands,
-    mut scene_spawner: ResMut<SceneSpawner>,
-    mut scenes: ResMut<Assets<DynamicScene>>,
+    commands: Commands,
+    scene_spawner: ResMut<SceneSpawner>,
+    scenes: ResMut<Assets<DynamicScene>>,
     world_name: Res<WorldName>,
     game_paths: Res<GamePaths>,
     registry: Res<AppTypeRegistry>,
 ) -> Result<()> {
     let world_path = game_paths.world_path(&world_name.0);
-    info!("loading world from {world_path:?}");
+    let header_path = game_paths.world_header_path(&world_name.0);
+    load_scene(
+        &world_path,
+        &header_path,
+        commands,
+        scene_spawner,
+        scenes,
+        &registry.read(),
+    )
+}
 
-    let bytes = fs::read(&world_path).with_context(|| format!("unable to load {world_path:?}"))?;
-    let mut deserializer = ron::Deserializer::from_bytes(&bytes)
-        .with_context(|| format!("unable to parse {world_path:?}"))?;
-    let scene_deserializer = SceneDeserializer {
-        type_registry: &registry.read(),
-    };
-    let scene = scene_deserializer
-        .deserialize(&mut deserializer)
-        .with_context(|| format!("unable to deserialize {world_path:?}"))?;
+/// Loads world from a named snapshot of the world from [`WorldName`] resource.
+///
+/// Further saves without a name will be written to the world itself.
+fn load_snapshot(
+    trigger: Trigger<SnapshotLoad>,
+    commands: Commands,
+    scene_spawner: ResMut<SceneSpawner>,
+    scenes: ResMut<Assets<DynamicScene>>,
+    world_name: Res<WorldName>,
+    game_paths: Res<GamePaths>,
+    registry: Res<AppTypeRegistry>,
+) -> Result<()> {
+    let snapshot_path = game_paths.snapshot_path(&world_name.0, &trigger.0);
+    let header_path = game_paths.snapshot_header_path(&world_name.0, &trigger.0);
+    load_scene(
+        &snapshot_path,
+        &header_path,
+        commands,
+        scene_spawner,
+        scenes,
+        &registry.read(),
+    )
+}
+
+fn create_header(
+    world_play_time: &WorldPlayTime,
+    families: &Query<(&Name, &PlayTime), With<Family>>,
+) -> SaveHeader {
+    let families = families
+        .iter()
+        .map(|(name, &play_time)| (name.to_string(), *play_time))
+        .collect();
+    SaveHeader::new(**world_play_time, families)
+}
+
+fn load_scene(
+    path: &Path,
+    header_path: &Path,
+    mut commands: Commands,
+    mut scene_spawner: ResMut<SceneSpawner>,
+    mut scenes: ResMut<Assets<DynamicScene>>,
+    registry: &TypeRegistry,
+) -> Result<()> {
+    info!("loading world from {path:?}");
+
+    let bytes = fs::read(path).with_context(|| format!("unable to load {path:?}"))?;
+    let mut scene = deserialize_world(&bytes, registry).or_else(|e| {
+        let backup_path = backup_corrupted(path)?;
+        Err(e).with_context(|| format!("unable to deserialize {path:?}, moved to {backup_path:?}"))
+    })?;
+
+    let broken = scene_validation::drop_broken_entities(&mut scene);
+    if !broken.is_empty() {
+        let mut message = format!(
+            "World {path:?} is damaged, {} entities were dropped:",
+            broken.len()
+        );
+        for reference in &broken {
+            error!("{reference}");
+            message += &format!("\n{reference}");
+        }
+        commands.trigger(ErrorMessage::new(message));
+    }
+
+    // Worlds saved without a header start counting play time from zero.
+    if header_path.exists() {
+        match SaveHeader::read(header_path) {
+            Ok(header) => commands.insert_resource(WorldPlayTime(header.play_time)),
+            Err(e) => error!("{e:#}"),
+        }
+    }
 
     scene_spawner.spawn_dynamic(scenes.add(scene));
     commands.set_state(GameState::InGame);
@@ -120,6 +296,35 @@ fn load(
     Ok(())
 }
 
+/// Serializes all replicated components and transforms of actors.
+pub(crate) fn serialize_world(world: &World, actors: impl Iterator<Item = Entity>) -> Vec<u8> {
+    // Extract components that we don't replicate, but serialize.
+    let mut scene = DynamicSceneBuilder::from_world(world)
+        .deny_all()
+        .allow_component::<Transform>()
+        .extract_entities(actors)
+        .build();
+
+    // Extract all replicated components that are reflected.
+    bevy_replicon::scene::replicate_into(&mut scene, world);
+    let registry = world.resource::<AppTypeRegistry>().read();
+    scene
+        .serialize(&registry)
+        .expect("game world should be serialized")
+        .into_bytes()
+}
+
+/// Deserializes a scene produced by [`serialize_world`].
+pub(crate) fn deserialize_world(bytes: &[u8], registry: &TypeRegistry) -> Result<DynamicScene> {
+    let mut deserializer = ron::Deserializer::from_bytes(bytes)?;
+    let scene_deserializer = SceneDeserializer {
+        type_registry: registry,
+    };
+    let scene = scene_deserializer.deserialize(&mut deserializer)?;
+
+    Ok(scene)
+}
+
 fn start_game(mut commands: Commands) {
     info!("joining replicated world");
     commands.insert_resource(WorldName::default());
@@ -140,15 +345,50 @@ pub struct GameSave;
 #[derive(Default, Event)]
 pub struct GameLoad;
 
+/// Saves the world as a named snapshot of the world from [`WorldName`] resource.
+///
+/// Overwrites the snapshot with the same name.
+#[derive(Event)]
+pub struct SnapshotSave(pub String);
+
+/// Loads the world from a named snapshot of the world from [`WorldName`] resource.
+///
+/// Sets game state to [`GameState::InGame`].
+#[derive(Event)]
+pub struct SnapshotLoad(pub String);
+
 /// Contains metadata of the currently loaded world.
 #[derive(Default, Resource)]
 pub struct WorldName(pub String);
 
+/// Server-side simulation steps in [`FixedUpdate`].
+///
+/// Decoupled from the render frame rate, so the simulation advances
+/// with the same pace on dedicated servers and on slow clients.
+/// Sets run in the declared order, only on server or in singleplayer.
+#[derive(SystemSet, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SimulationSet {
+    /// Calendar and other world clocks.
+    Clock,
+    /// Decay of actor needs.
+    Needs,
+    /// Decisions of NPCs.
+    Autonomy,
+    /// Fires and other accidents.
+    Hazards,
+    /// Budget tracking.
+    Economy,
+}
+
 #[derive(SubStates, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
 #[source(GameState = GameState::InGame)]
 pub enum WorldState {
     #[default]
     World,
+    /// Waits for the active city to load before switching to [`Self::City`] or [`Self::Family`].
+    ///
+    /// Should be entered via [`loading::LoadingStart`].
+    Loading,
     FamilyEditor,
     City,
     Family,
@@ -166,4 +406,6 @@ pub(super) enum Layer {
     PlacingWall,
     Road,
     PlacingRoad,
+    Fence,
+    PlacingFence,
 }
@@ -1,6 +1,13 @@
 mod animation_state;
+pub mod debug_tools;
+mod exposure;
+mod footsteps;
 pub(super) mod human;
+mod interpolation;
+pub(crate) mod name_generator;
 pub mod needs;
+pub mod outfit;
+pub mod skills;
 pub mod task;
 
 use std::fmt::Write;
@@ -14,12 +21,16 @@ use bevy::{
 use bevy_mod_outline::OutlineVolume;
 use bevy_replicon::prelude::*;
 use num_enum::IntoPrimitive;
+use rand::{
+    distributions::{Distribution, Standard},
+    Rng,
+};
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
 use super::{
     family::editor::{EditorFirstName, EditorLastName, EditorSex},
-    highlighting::HIGHLIGHTING_VOLUME,
+    highlighting::{Selected, HIGHLIGHTING_VOLUME},
     navigation::Navigation,
     Layer, WorldState,
 };
@@ -28,8 +39,15 @@ use crate::{
     core::GameState,
 };
 use animation_state::{AnimationState, AnimationStatePlugin};
+use debug_tools::DebugToolsPlugin;
+use exposure::ExposurePlugin;
+use footsteps::FootstepsPlugin;
 use human::HumanPlugin;
+use interpolation::{InterpolationBuffer, InterpolationPlugin, NetworkTransform};
+use name_generator::NameGeneratorPlugin;
 use needs::NeedsPlugin;
+use outfit::{ActiveOutfit, OutfitPlugin, Outfits};
+use skills::{CookingSkill, SkillsPlugin};
 use task::{TaskGroups, TaskPlugin};
 
 pub(super) struct ActorPlugin;
@@ -37,7 +55,19 @@ pub(super) struct ActorPlugin;
 impl Plugin for ActorPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Collection<ActorAnimation>>()
-            .add_plugins((AnimationStatePlugin, NeedsPlugin, HumanPlugin, TaskPlugin))
+            .add_plugins((
+                AnimationStatePlugin,
+                DebugToolsPlugin,
+                ExposurePlugin,
+                FootstepsPlugin,
+                NeedsPlugin,
+                HumanPlugin,
+                InterpolationPlugin,
+                NameGeneratorPlugin,
+                OutfitPlugin,
+                SkillsPlugin,
+                TaskPlugin,
+            ))
             .register_type::<Transform>()
             .register_type::<Actor>()
             .register_type::<FirstName>()
@@ -48,6 +78,8 @@ impl Plugin for ActorPlugin {
             .replicate::<FirstName>()
             .replicate::<Sex>()
             .replicate::<LastName>()
+            .add_observer(select)
+            .add_observer(deselect)
             .add_systems(
                 OnExit(WorldState::Family),
                 remove_selection.never_param_warn(),
@@ -56,7 +88,7 @@ impl Plugin for ActorPlugin {
     }
 }
 
-const ACTOR_HEIGHT: f32 = 1.8;
+pub const ACTOR_HEIGHT: f32 = 1.8;
 pub(super) const ACTOR_RADIUS: f32 = 0.4;
 
 fn update_names(
@@ -74,9 +106,27 @@ fn update_names(
     }
 }
 
-fn remove_selection(mut commands: Commands, selected_entity: Single<Entity, With<SelectedActor>>) {
+/// Outlines the selected actor and its group.
+fn select(trigger: Trigger<OnAdd, (SelectedActor, GroupedActor)>, mut commands: Commands) {
+    commands.entity(trigger.entity()).insert(Selected);
+}
+
+fn deselect(trigger: Trigger<OnRemove, (SelectedActor, GroupedActor)>, mut commands: Commands) {
+    if let Some(mut entity) = commands.get_entity(trigger.entity()) {
+        entity.remove::<Selected>();
+    }
+}
+
+fn remove_selection(
+    mut commands: Commands,
+    selected_entity: Single<Entity, With<SelectedActor>>,
+    grouped_actors: Query<Entity, With<GroupedActor>>,
+) {
     info!("deselecting actor `{}`", *selected_entity);
     commands.entity(*selected_entity).remove::<SelectedActor>();
+    for actor_entity in &grouped_actors {
+        commands.entity(actor_entity).remove::<GroupedActor>();
+    }
 }
 
 #[derive(Clone, Component, Default, Deref, DerefMut, Deserialize, Reflect, Serialize)]
@@ -107,6 +157,16 @@ pub enum Sex {
     Female,
 }
 
+impl Distribution<Sex> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Sex {
+        if rng.gen() {
+            Sex::Male
+        } else {
+            Sex::Female
+        }
+    }
+}
+
 impl From<EditorSex> for Sex {
     fn from(value: EditorSex) -> Self {
         match value {
@@ -120,6 +180,12 @@ impl From<EditorSex> for Sex {
 #[derive(Component)]
 pub struct SelectedActor;
 
+/// Family members that receive the same tasks as [`SelectedActor`].
+///
+/// Used to command multiple actors at once.
+#[derive(Component)]
+pub struct GroupedActor;
+
 /// Marks entity as an actor.
 #[derive(Component, Deserialize, Reflect, Serialize)]
 #[reflect(Component, MapEntities)]
@@ -127,9 +193,14 @@ pub struct SelectedActor;
     FirstName,
     LastName,
     Sex,
+    Outfits,
+    ActiveOutfit,
+    CookingSkill,
     Replicated,
     ParentSync,
     Navigation,
+    NetworkTransform,
+    InterpolationBuffer,
     Name,
     AnimationState,
     SceneRoot,
@@ -177,6 +248,7 @@ pub(super) enum ActorAnimation {
     FemaleRun,
     TellSecret,
     ThoughtfulNod,
+    Swim,
 }
 
 impl AssetCollection for ActorAnimation {
@@ -204,6 +276,9 @@ impl AssetCollection for ActorAnimation {
             }
             ActorAnimation::ThoughtfulNod => GltfAssetLabel::Animation(0)
                 .from_asset("base/actors/animations/thoughtful_nod.gltf"),
+            ActorAnimation::Swim => {
+                GltfAssetLabel::Animation(0).from_asset("base/actors/animations/swim.gltf")
+            }
         }
     }
 }
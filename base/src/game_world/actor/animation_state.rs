@@ -164,6 +164,11 @@ impl AnimationState {
         self.montage_state = MontageState::Pending(montage);
     }
 
+    /// Returns `true` if a montage is pending or playing.
+    pub(super) fn montage_active(&self) -> bool {
+        !matches!(self.montage_state, MontageState::Stopped)
+    }
+
     /// Stops the current montage, if any.
     ///
     /// Resumes the animation based on the current state.
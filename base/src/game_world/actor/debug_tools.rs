@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    animation_state::{AnimationState, MontageFinished},
+    needs::Need,
+    task::{ActiveTask, AvailableTasks, TaskSelect},
+    Actor, SelectedActor,
+};
+use crate::{
+    game_world::{
+        city::Ground,
+        navigation::NavDestination,
+        players::{LocalPermission, Permission, Players},
+    },
+    settings::Settings,
+};
+
+/// Developer tools to manipulate actors directly.
+///
+/// Enabled by [`DeveloperSettings::debug_tools`](crate::settings::DeveloperSettings::debug_tools)
+/// and accepted by the server only from the host.
+pub(super) struct DebugToolsPlugin;
+
+impl Plugin for DebugToolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_client_trigger::<ActorTeleport>(ChannelKind::Ordered)
+            .add_client_trigger::<NeedSet>(ChannelKind::Ordered)
+            .add_client_trigger::<TaskComplete>(ChannelKind::Ordered)
+            .add_observer(add_to_list)
+            .add_observer(request_teleport.never_param_warn())
+            .add_observer(teleport)
+            .add_observer(set_need)
+            .add_observer(complete_task);
+    }
+}
+
+fn add_to_list(
+    trigger: Trigger<OnAdd, AvailableTasks>,
+    mut commands: Commands,
+    settings: Res<Settings>,
+    client: Res<RepliconClient>,
+    local_permission: Res<LocalPermission>,
+    available_tasks: Single<&AvailableTasks>,
+    grounds: Query<(), With<Ground>>,
+) {
+    if !debug_tools_allowed(&settings, &client, &local_permission) {
+        return;
+    }
+    if grounds.get(available_tasks.interaction_entity).is_err() {
+        return;
+    }
+
+    debug!("listing teleport");
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn((
+            Name::new("Teleport here"),
+            TeleportHere(available_tasks.click_point),
+        ));
+    });
+}
+
+fn request_teleport(
+    trigger: Trigger<TaskSelect>,
+    mut commands: Commands,
+    teleports: Query<&TeleportHere>,
+    tasks_entity: Single<Entity, With<AvailableTasks>>,
+    selected_entity: Single<Entity, With<SelectedActor>>,
+) {
+    let Ok(&teleport) = teleports.get(trigger.entity()) else {
+        return;
+    };
+
+    info!("requesting teleport to `{}`", teleport.0);
+    commands.entity(*tasks_entity).despawn_recursive();
+    commands.client_trigger_targets(ActorTeleport(teleport.0), *selected_entity);
+}
+
+fn teleport(
+    trigger: Trigger<FromClient<ActorTeleport>>,
+    players: Res<Players>,
+    mut actors: Query<(&mut Transform, &mut NavDestination), With<Actor>>,
+) {
+    if !players.is_host(trigger.client_id) {
+        error!(
+            "`{:?}` is not allowed to teleport actors",
+            trigger.client_id
+        );
+        return;
+    }
+    let Ok((mut transform, mut dest)) = actors.get_mut(trigger.entity()) else {
+        error!("entity `{}` is not an actor", trigger.entity());
+        return;
+    };
+
+    info!(
+        "`{:?}` teleports `{}` to `{}`",
+        trigger.client_id,
+        trigger.entity(),
+        trigger.event.0
+    );
+    transform.translation = trigger.event.0;
+    if dest.is_some() {
+        // Recalculate the path from the new position.
+        dest.set_changed();
+    }
+}
+
+fn set_need(
+    trigger: Trigger<FromClient<NeedSet>>,
+    players: Res<Players>,
+    mut needs: Query<(&mut Need, &Name)>,
+) {
+    if !players.is_host(trigger.client_id) {
+        error!("`{:?}` is not allowed to change needs", trigger.client_id);
+        return;
+    }
+    let Ok((mut need, name)) = needs.get_mut(trigger.entity()) else {
+        error!("entity `{}` is not a need", trigger.entity());
+        return;
+    };
+
+    let value = trigger.event.0.clamp(0.0, 100.0);
+    info!(
+        "`{:?}` sets '{name}' of `{}` to {value}",
+        trigger.client_id,
+        trigger.entity()
+    );
+    need.0 = value;
+}
+
+/// Skips the current stage of the task.
+///
+/// Tasks finish on reaching the destination or after their animation,
+/// so the actor is moved to the destination or the animation is finished.
+/// Other tasks are just removed.
+fn complete_task(
+    trigger: Trigger<FromClient<TaskComplete>>,
+    mut commands: Commands,
+    players: Res<Players>,
+    tasks: Query<&Parent, With<ActiveTask>>,
+    mut actors: Query<(&mut Transform, &mut NavDestination, &mut AnimationState)>,
+) {
+    if !players.is_host(trigger.client_id) {
+        error!("`{:?}` is not allowed to complete tasks", trigger.client_id);
+        return;
+    }
+    let Ok(parent) = tasks.get(trigger.entity()) else {
+        error!("task `{}` is not active", trigger.entity());
+        return;
+    };
+    let (mut transform, mut dest, mut animation_state) = actors
+        .get_mut(**parent)
+        .expect("task parent should be an actor");
+
+    info!(
+        "`{:?}` completes task `{}`",
+        trigger.client_id,
+        trigger.entity()
+    );
+    if let Some(endpoint) = **dest {
+        debug!("moving `{}` to the destination", **parent);
+        transform.translation = endpoint;
+        **dest = None;
+    } else if animation_state.montage_active() {
+        debug!("finishing montage for `{}`", **parent);
+        animation_state.stop_montage();
+        commands.trigger_targets(MontageFinished, **parent);
+    } else {
+        commands.entity(trigger.entity()).despawn();
+    }
+}
+
+/// Returns `true` if debug tools are enabled and the local player is allowed to use them.
+///
+/// Clients can use them only with host permission.
+pub fn debug_tools_allowed(
+    settings: &Settings,
+    client: &RepliconClient,
+    local_permission: &LocalPermission,
+) -> bool {
+    settings.developer.debug_tools
+        && (client.is_disconnected() || **local_permission == Permission::Host)
+}
+
+/// Entry in the task menu to teleport the selected actor to the clicked point.
+#[derive(Component, Clone, Copy)]
+struct TeleportHere(Vec3);
+
+/// Moves the targeted actor to the specified point in city coordinates.
+#[derive(Deserialize, Event, Serialize)]
+struct ActorTeleport(Vec3);
+
+/// Sets the value of the targeted need.
+#[derive(Deserialize, Event, Serialize)]
+pub struct NeedSet(pub f32);
+
+/// Forces the targeted active task to complete.
+#[derive(Deserialize, Event, Serialize)]
+pub struct TaskComplete;
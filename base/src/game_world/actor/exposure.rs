@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    needs::{Energy, Fun, Need},
+    outfit::{ActiveOutfit, OutfitKind, Outfits},
+};
+use crate::game_world::{
+    auto_pause::{CriticalEvent, CriticalEventKind},
+    city::{
+        season::{Calendar, Season},
+        weather::Weather,
+    },
+    difficulty::Difficulty,
+    family::building::wall,
+    SimulationSet,
+};
+
+/// Makes actors react to the weather when they are outside.
+///
+/// Actors change into outerwear in winter or bad weather and open an umbrella in rain.
+/// Unprotected actors lose [`Energy`] and [`Fun`] faster.
+pub(super) struct ExposurePlugin;
+
+impl Plugin for ExposurePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Umbrella>()
+            .replicate::<Umbrella>()
+            .init_resource::<UmbrellaScene>()
+            .add_observer(open_umbrella)
+            .add_observer(close_umbrella)
+            .add_systems(
+                FixedUpdate,
+                react
+                    .run_if(on_timer(Duration::from_secs(1)))
+                    .in_set(SimulationSet::Needs),
+            );
+    }
+}
+
+/// Additional loss per second of affected needs at full exposure.
+const EXPOSURE_RATE: f32 = 0.3;
+
+/// Position of the umbrella handle relative to the actor.
+const UMBRELLA_OFFSET: Vec3 = Vec3::new(0.25, 0.95, 0.0);
+
+fn react(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    difficulty: Option<Single<&Difficulty>>,
+    cities: Query<(&Calendar, &Weather)>,
+    mut actors: Query<(
+        Entity,
+        &Parent,
+        &GlobalTransform,
+        &Children,
+        &Outfits,
+        &mut ActiveOutfit,
+        Has<Umbrella>,
+    )>,
+    mut needs: Query<(&mut Need, &Name), Or<(With<Energy>, With<Fun>)>>,
+) {
+    let scale = difficulty.map(|d| **d).unwrap_or_default().need_scale();
+    for (actor_entity, parent, transform, children, outfits, mut active_outfit, has_umbrella) in
+        &mut actors
+    {
+        let Ok((calendar, &weather)) = cities.get(**parent) else {
+            continue;
+        };
+
+        let cold = calendar.season() == Season::Winter;
+        let outside = !wall::is_indoors(&spatial_query, transform.translation());
+        let bad_weather = outside && (cold || weather.is_precipitation());
+        let outfit_kind = **active_outfit;
+        match outfit_kind {
+            // Task outfits are left untouched.
+            OutfitKind::Everyday if bad_weather && !outfits.outerwear.is_empty() => {
+                debug!("changing `{actor_entity}` into outerwear");
+                **active_outfit = OutfitKind::Outerwear;
+            }
+            OutfitKind::Outerwear if !bad_weather => {
+                debug!("changing `{actor_entity}` out of outerwear");
+                **active_outfit = OutfitKind::Everyday;
+            }
+            _ => (),
+        }
+
+        let umbrella = outside && weather == Weather::Rain;
+        if umbrella != has_umbrella {
+            if umbrella {
+                debug!("opening umbrella for `{actor_entity}`");
+                commands.entity(actor_entity).insert(Umbrella);
+            } else {
+                debug!("closing umbrella for `{actor_entity}`");
+                commands.entity(actor_entity).remove::<Umbrella>();
+            }
+        }
+
+        if !outside {
+            continue;
+        }
+        let outerwear = **active_outfit == OutfitKind::Outerwear;
+        let factor = exposure(weather, cold, outerwear, umbrella);
+        if factor == 0.0 {
+            continue;
+        }
+
+        let mut iter = needs.iter_many_mut(children);
+        while let Some((mut need, name)) = iter.fetch_next() {
+            if need.0 == 0.0 {
+                continue;
+            }
+
+            need.0 = (need.0 - EXPOSURE_RATE * factor * scale).max(0.0);
+            if need.0 == 0.0 {
+                debug!("'{name}' depleted for `{actor_entity}` because of the weather");
+                commands.trigger(CriticalEvent {
+                    kind: CriticalEventKind::NeedDepleted {
+                        need_name: name.to_string(),
+                    },
+                    actor_entity,
+                });
+            }
+        }
+    }
+}
+
+/// Returns how much the weather affects an actor outside, from 0 to 1.
+///
+/// Outerwear protects from everything, an umbrella only partially protects from rain.
+fn exposure(weather: Weather, cold: bool, outerwear: bool, umbrella: bool) -> f32 {
+    if outerwear {
+        return 0.0;
+    }
+
+    match weather {
+        Weather::Snow => 1.0,
+        Weather::Rain if umbrella => 0.25,
+        Weather::Rain => 1.0,
+        Weather::Clear if cold => 0.5,
+        Weather::Clear => 0.0,
+    }
+}
+
+fn open_umbrella(
+    trigger: Trigger<OnAdd, Umbrella>,
+    mut commands: Commands,
+    umbrella_scene: Res<UmbrellaScene>,
+) {
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn((
+            UmbrellaProp,
+            SceneRoot(umbrella_scene.0.clone()),
+            Transform::from_translation(UMBRELLA_OFFSET),
+        ));
+    });
+}
+
+fn close_umbrella(
+    trigger: Trigger<OnRemove, Umbrella>,
+    mut commands: Commands,
+    actors: Query<&Children>,
+    props: Query<Entity, With<UmbrellaProp>>,
+) {
+    let Ok(children) = actors.get(trigger.entity()) else {
+        return;
+    };
+
+    for prop_entity in props.iter_many(children) {
+        commands.entity(prop_entity).despawn_recursive();
+    }
+}
+
+#[derive(Resource)]
+struct UmbrellaScene(Handle<Scene>);
+
+impl FromWorld for UmbrellaScene {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self(
+            asset_server.load(
+                GltfAssetLabel::Scene(0).from_asset("base/actors/props/umbrella/umbrella.gltf"),
+            ),
+        )
+    }
+}
+
+/// Actor holds an umbrella over the head.
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+struct Umbrella;
+
+/// Umbrella scene spawned as a child of the actor with [`Umbrella`].
+#[derive(Component)]
+#[require(Name(|| Name::new("Umbrella")), SceneRoot)]
+struct UmbrellaProp;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protection() {
+        assert_eq!(exposure(Weather::Clear, false, false, false), 0.0);
+        assert_eq!(exposure(Weather::Clear, true, false, false), 0.5);
+        assert_eq!(exposure(Weather::Rain, false, false, false), 1.0);
+        assert!(exposure(Weather::Rain, false, false, true) < 1.0);
+        assert_eq!(exposure(Weather::Snow, true, true, false), 0.0);
+    }
+}
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+use super::ActorAnimation;
+use crate::{
+    asset::collection::Collection,
+    audio::{Sound, SoundPlay},
+};
+
+/// Plays footstep sounds from events inside walk and run animations.
+pub(super) struct FootstepsPlugin;
+
+impl Plugin for FootstepsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(play).add_systems(
+            Update,
+            add_events.run_if(on_event::<AssetEvent<AnimationClip>>),
+        );
+    }
+}
+
+/// Number of steps in a single loop of movement animations.
+const STEPS_PER_CYCLE: usize = 2;
+
+fn add_events(
+    mut asset_events: EventReader<AssetEvent<AnimationClip>>,
+    actor_animations: Res<Collection<ActorAnimation>>,
+    mut clips: ResMut<Assets<AnimationClip>>,
+) {
+    for event in asset_events.read() {
+        let AssetEvent::LoadedWithDependencies { id } = *event else {
+            continue;
+        };
+
+        let is_movement = [
+            ActorAnimation::MaleWalk,
+            ActorAnimation::FemaleWalk,
+            ActorAnimation::MaleRun,
+            ActorAnimation::FemaleRun,
+        ]
+        .into_iter()
+        .any(|animation| actor_animations.handle(animation).id() == id);
+        if !is_movement {
+            continue;
+        }
+
+        let clip = clips.get_mut(id).expect("loaded clip should be available");
+        let step_duration = clip.duration() / STEPS_PER_CYCLE as f32;
+        debug!("adding footstep events to `{id}`");
+        for step in 0..STEPS_PER_CYCLE {
+            clip.add_event(step as f32 * step_duration, Footstep);
+        }
+    }
+}
+
+fn play(trigger: Trigger<Footstep>, mut commands: Commands) {
+    commands.trigger_targets(SoundPlay::new(Sound::Footstep), trigger.entity());
+}
+
+/// Triggered on the animation player entity when a foot touches the ground.
+#[derive(Clone, Event, Reflect)]
+struct Footstep;
@@ -6,6 +6,7 @@ use strum::EnumIter;
 
 use super::{
     needs::{Bladder, Energy, Fun, Hunger, Hygiene, Need, Social},
+    outfit::Outfits,
     FirstName, LastName, Sex,
 };
 use crate::{
@@ -65,9 +66,9 @@ fn update_sex<C: Component + Into<HumanScene> + Copy>(
 /// Fills [`FamilyScene`] with editing human actors.
 fn fill_scene(
     mut family_scene: ResMut<FamilyScene>,
-    actors: Query<(&EditorFirstName, &EditorLastName, &EditorSex), With<EditorHuman>>,
+    actors: Query<(&EditorFirstName, &EditorLastName, &EditorSex, &Outfits), With<EditorHuman>>,
 ) {
-    for (first_name, last_name, &sex) in &actors {
+    for (first_name, last_name, &sex, outfits) in &actors {
         debug!(
             "adding human '{} {}' to family scene '{}'",
             first_name.0, last_name.0, family_scene.name
@@ -76,6 +77,7 @@ fn fill_scene(
             first_name: first_name.clone().into(),
             last_name: last_name.clone().into(),
             sex: sex.into(),
+            outfits: outfits.clone(),
             human: Human,
         }));
     }
@@ -94,6 +96,7 @@ struct HumanBundle {
     first_name: FirstName,
     last_name: LastName,
     sex: Sex,
+    outfits: Outfits,
     human: Human,
 }
 
@@ -101,6 +104,15 @@ impl ActorBundle for HumanBundle {
     fn glyph(&self) -> &'static str {
         "👤"
     }
+
+    fn insert_editor(&self, entity: &mut EntityCommands) {
+        entity.insert((
+            EditorFirstName(self.first_name.0.clone()),
+            EditorLastName(self.last_name.0.clone()),
+            EditorSex::from(self.sex),
+            self.outfits.clone(),
+        ));
+    }
 }
 
 #[derive(Clone, Copy, IntoPrimitive, EnumIter, Default)]
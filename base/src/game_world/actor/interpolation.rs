@@ -0,0 +1,339 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+use vleue_navigator::prelude::*;
+
+use crate::game_world::{
+    city::CityNavMesh,
+    navigation::{self, NavDestination, Navigation},
+};
+
+/// Smooths replicated actor movement on clients.
+///
+/// Remote actors are displayed slightly in the past by interpolating between received snapshots.
+/// Movement of the selected actor is predicted locally to respond to commands without waiting for the server.
+pub(super) struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.replicate::<NetworkTransform>()
+            .add_systems(
+                PreUpdate,
+                buffer_snapshots
+                    .after(ClientSet::Receive)
+                    .run_if(client_connected),
+            )
+            .add_systems(Update, (predict, interpolate).run_if(client_connected))
+            .add_systems(
+                PostUpdate,
+                update_network_transforms
+                    .before(ServerSet::Send)
+                    .run_if(server_or_singleplayer),
+            );
+    }
+}
+
+/// How far in the past remote actors are displayed.
+///
+/// Gives enough time to receive the next snapshot to interpolate to.
+const INTERPOLATION_DELAY: f64 = 0.1;
+
+/// Maximum number of snapshots kept per actor.
+const MAX_SNAPSHOTS: usize = 32;
+
+/// How long to wait for the server to accept the predicted movement.
+const CONFIRMATION_TIMEOUT: f32 = 1.0;
+
+/// Distance from the server position after which the predicted actor starts to correct itself.
+const CORRECTION_DISTANCE: f32 = 1.0;
+
+/// How quickly the predicted actor is pulled to the server position.
+const CORRECTION_SPEED: f32 = 5.0;
+
+fn update_network_transforms(
+    mut actors: Query<(&Transform, &mut NetworkTransform), Changed<Transform>>,
+) {
+    for (transform, mut network_transform) in &mut actors {
+        network_transform.set_if_neq(NetworkTransform {
+            translation: transform.translation,
+            rotation: transform.rotation,
+        });
+    }
+}
+
+fn buffer_snapshots(
+    time: Res<Time<Real>>,
+    mut actors: Query<(&NetworkTransform, &mut InterpolationBuffer), Changed<NetworkTransform>>,
+) {
+    let now = time.elapsed_secs_f64();
+    for (&network_transform, mut buffer) in &mut actors {
+        buffer.push(now, network_transform);
+    }
+}
+
+fn interpolate(
+    time: Res<Time<Real>>,
+    mut actors: Query<(&InterpolationBuffer, &mut Transform), Without<PredictedMovement>>,
+) {
+    let render_time = time.elapsed_secs_f64() - INTERPOLATION_DELAY;
+    for (buffer, mut transform) in &mut actors {
+        let Some(network_transform) = buffer.sample(render_time) else {
+            continue;
+        };
+
+        transform.set_if_neq(Transform {
+            translation: network_transform.translation,
+            rotation: network_transform.rotation,
+            ..*transform
+        });
+    }
+}
+
+fn predict(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut navmeshes: ResMut<Assets<NavMesh>>,
+    cities: Query<&CityNavMesh>,
+    city_navmeshes: Query<&ManagedNavMesh>,
+    mut actors: Query<(
+        Entity,
+        &Parent,
+        &NavDestination,
+        &InterpolationBuffer,
+        &mut PredictedMovement,
+        &mut Transform,
+    )>,
+) {
+    for (entity, parent, dest, buffer, mut prediction, mut transform) in &mut actors {
+        if prediction.path.is_empty() {
+            let navmesh_entity = cities
+                .get(**parent)
+                .expect("all actors should have city as parents");
+            let navmesh_handle = city_navmeshes
+                .get(**navmesh_entity)
+                .expect("city navmesh should always be valid");
+            let Some(navmesh) = navmeshes.get_mut(navmesh_handle) else {
+                continue;
+            };
+
+            let Some(transformed) =
+                navmesh.transformed_path(transform.translation, prediction.endpoint)
+            else {
+                debug!("unable to predict path for `{entity}`");
+                commands.entity(entity).remove::<PredictedMovement>();
+                continue;
+            };
+
+            debug!("predicting path for `{entity}`");
+            prediction.path.push(transform.translation);
+            prediction.path.extend(transformed.path);
+        }
+
+        if dest.is_some() {
+            prediction.confirmed = true;
+        } else if !prediction.confirmed {
+            prediction.elapsed += time.delta_secs();
+            if prediction.elapsed > CONFIRMATION_TIMEOUT {
+                debug!("server didn't confirm predicted movement for `{entity}`");
+                commands.entity(entity).remove::<PredictedMovement>();
+                continue;
+            }
+        }
+
+        let target_index = prediction.path_index + 1;
+        if target_index < prediction.path.len() {
+            match navigation::move_agent(
+                &mut transform,
+                prediction.navigation,
+                &prediction.path[target_index..],
+                Vec3::ZERO,
+                time.delta_secs(),
+            ) {
+                Some(passed_points) => prediction.path_index += passed_points,
+                None => prediction.path_index = prediction.path.len() - 1,
+            }
+        } else if prediction.confirmed && dest.is_none() {
+            debug!("ending predicted movement for `{entity}`");
+            commands.entity(entity).remove::<PredictedMovement>();
+            continue;
+        }
+
+        if prediction.confirmed {
+            if let Some(server_transform) = buffer.latest() {
+                let error = transform.translation.distance(server_transform.translation);
+                if error > CORRECTION_DISTANCE {
+                    trace!("correcting `{entity}` by {error}");
+                    transform.translation = transform.translation.lerp(
+                        server_transform.translation,
+                        (CORRECTION_SPEED * time.delta_secs()).min(1.0),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Replicated actor transform.
+///
+/// [`Transform`] isn't replicated directly because clients apply it with a delay
+/// using [`InterpolationBuffer`].
+#[derive(Clone, Component, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub(super) struct NetworkTransform {
+    translation: Vec3,
+    rotation: Quat,
+}
+
+/// Received [`NetworkTransform`] values with their arrival time.
+#[derive(Component, Default)]
+pub(super) struct InterpolationBuffer(VecDeque<Snapshot>);
+
+impl InterpolationBuffer {
+    fn push(&mut self, time: f64, network_transform: NetworkTransform) {
+        if let Some(&last) = self.0.back() {
+            // Snapshots are received only on change, so after a long pause
+            // start interpolation from the last known position instead of jumping.
+            if time - last.time > INTERPOLATION_DELAY {
+                self.0.push_back(Snapshot {
+                    time: time - INTERPOLATION_DELAY,
+                    ..last
+                });
+            }
+        }
+        self.0.push_back(Snapshot {
+            time,
+            network_transform,
+        });
+
+        // Keep one snapshot before the render time to interpolate from.
+        let render_time = time - INTERPOLATION_DELAY;
+        while self.0.len() > MAX_SNAPSHOTS
+            || self
+                .0
+                .get(1)
+                .is_some_and(|snapshot| snapshot.time < render_time)
+        {
+            self.0.pop_front();
+        }
+    }
+
+    /// Returns interpolated transform at the specified time.
+    ///
+    /// Clamps to the first or the last snapshot if the time is outside the buffer.
+    fn sample(&self, time: f64) -> Option<NetworkTransform> {
+        let next_index = self.0.iter().position(|snapshot| snapshot.time > time);
+        match next_index {
+            Some(0) => self.0.front().map(|snapshot| snapshot.network_transform),
+            Some(index) => {
+                let prev = self.0[index - 1];
+                let next = self.0[index];
+                let t = ((time - prev.time) / (next.time - prev.time)) as f32;
+                Some(NetworkTransform {
+                    translation: prev
+                        .network_transform
+                        .translation
+                        .lerp(next.network_transform.translation, t),
+                    rotation: prev
+                        .network_transform
+                        .rotation
+                        .slerp(next.network_transform.rotation, t),
+                })
+            }
+            None => self.latest(),
+        }
+    }
+
+    fn latest(&self) -> Option<NetworkTransform> {
+        self.0.back().map(|snapshot| snapshot.network_transform)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Snapshot {
+    time: f64,
+    network_transform: NetworkTransform,
+}
+
+/// Locally simulated movement of the selected actor.
+///
+/// Inserted on clients when a movement task is requested and removed
+/// after the server finishes the movement or rejects it.
+#[derive(Component)]
+pub(super) struct PredictedMovement {
+    endpoint: Vec3,
+    navigation: Navigation,
+    path: Vec<Vec3>,
+    path_index: usize,
+    /// Time passed without server confirmation.
+    elapsed: f32,
+    confirmed: bool,
+}
+
+impl PredictedMovement {
+    pub(super) fn new(endpoint: Vec3, speed: f32) -> Self {
+        Self {
+            endpoint,
+            navigation: Navigation::new(speed),
+            path: Default::default(),
+            path_index: 0,
+            elapsed: 0.0,
+            confirmed: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sampling() {
+        let mut buffer = InterpolationBuffer::default();
+        buffer.push(0.0, transform_at(0.0));
+        buffer.push(0.05, transform_at(1.0));
+
+        assert_eq!(buffer.sample(0.025), Some(transform_at(0.5)));
+        assert_eq!(buffer.sample(-1.0), Some(transform_at(0.0)));
+        assert_eq!(buffer.sample(1.0), Some(transform_at(1.0)));
+    }
+
+    #[test]
+    fn pause() {
+        let mut buffer = InterpolationBuffer::default();
+        buffer.push(0.0, transform_at(0.0));
+        buffer.push(1.0, transform_at(1.0));
+
+        assert_eq!(
+            buffer.sample(1.0 - INTERPOLATION_DELAY),
+            Some(transform_at(0.0)),
+            "movement after a pause should start from the last known position"
+        );
+        let middle = buffer.sample(1.0 - INTERPOLATION_DELAY / 2.0).unwrap();
+        assert!(middle.translation.abs_diff_eq(Vec3::X * 0.5, 1e-4));
+    }
+
+    #[test]
+    fn pruning() {
+        const STEP: f64 = 0.0625;
+
+        let mut buffer = InterpolationBuffer::default();
+        for index in 0..=16 {
+            buffer.push(index as f64 * STEP, transform_at(index as f32));
+        }
+
+        let times: Vec<_> = buffer.0.iter().map(|snapshot| snapshot.time).collect();
+        assert_eq!(
+            times,
+            [14.0 * STEP, 15.0 * STEP, 16.0 * STEP],
+            "only one snapshot before the render time should be kept"
+        );
+    }
+
+    fn transform_at(x: f32) -> NetworkTransform {
+        NetworkTransform {
+            translation: Vec3::X * x,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
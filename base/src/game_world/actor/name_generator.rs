@@ -0,0 +1,195 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    ecs::system::SystemParam,
+    prelude::*,
+    scene::ron,
+};
+use rand::{seq::SliceRandom, Rng};
+use serde::Deserialize;
+
+use super::Sex;
+use crate::settings::{Settings, SettingsApply};
+
+/// Generates actor names from lists selected in gameplay settings.
+///
+/// Lists are loaded from [`NAMES_DIR`], one file per locale. If a list is missing
+/// or not loaded yet, names are composed from syllables.
+pub(super) struct NameGeneratorPlugin;
+
+impl Plugin for NameGeneratorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<NameList>()
+            .init_asset_loader::<NameListLoader>()
+            .add_observer(apply)
+            // Settings are loaded on startup.
+            .add_systems(PostStartup, load);
+    }
+}
+
+const NAMES_DIR: &str = "base/names";
+const NAMES_EXTENSION: &str = ".names.ron";
+
+const SYLLABLES: &[&str] = &[
+    "ka", "ri", "lo", "me", "na", "to", "vi", "sa", "del", "mar", "en", "ol", "ta", "ber", "lin",
+];
+
+fn load(mut commands: Commands, asset_server: Res<AssetServer>, settings: Res<Settings>) {
+    debug!("selecting names '{}'", settings.gameplay.names);
+    commands.insert_resource(SelectedNames(
+        asset_server.load(names_path(&settings.gameplay.names)),
+    ));
+}
+
+fn apply(
+    _trigger: Trigger<SettingsApply>,
+    mut selected_names: ResMut<SelectedNames>,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+) {
+    let handle = asset_server.load(names_path(&settings.gameplay.names));
+    if handle != **selected_names {
+        debug!("selecting names '{}'", settings.gameplay.names);
+        **selected_names = handle;
+    }
+}
+
+fn names_path(locale: &str) -> String {
+    format!("{NAMES_DIR}/{locale}{NAMES_EXTENSION}")
+}
+
+/// Composes a capitalized name from 2-3 random syllables.
+fn syllable_name(rng: &mut impl Rng) -> String {
+    let count = rng.gen_range(2..=3);
+    let name: String = (0..count)
+        .map(|_| *SYLLABLES.choose(rng).unwrap())
+        .collect();
+
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Generates random names using the selected list.
+#[derive(SystemParam)]
+pub(crate) struct NameGenerator<'w> {
+    selected_names: Res<'w, SelectedNames>,
+    lists: Res<'w, Assets<NameList>>,
+}
+
+impl NameGenerator<'_> {
+    pub(crate) fn first_name(&self, rng: &mut impl Rng, sex: Sex) -> String {
+        let names = self.list().map(|list| match sex {
+            Sex::Male => &list.male,
+            Sex::Female => &list.female,
+        });
+
+        names
+            .and_then(|names| names.choose(rng))
+            .cloned()
+            .unwrap_or_else(|| syllable_name(rng))
+    }
+
+    pub(crate) fn last_name(&self, rng: &mut impl Rng) -> String {
+        self.list()
+            .and_then(|list| list.last.choose(rng))
+            .cloned()
+            .unwrap_or_else(|| syllable_name(rng))
+    }
+
+    fn list(&self) -> Option<&NameList> {
+        self.lists.get(&**self.selected_names)
+    }
+}
+
+#[derive(Resource, Deref, DerefMut)]
+pub(crate) struct SelectedNames(Handle<NameList>);
+
+/// Names for a single locale.
+#[derive(Asset, Deserialize, TypePath)]
+pub(crate) struct NameList {
+    male: Vec<String>,
+    female: Vec<String>,
+    last: Vec<String>,
+}
+
+#[derive(Default)]
+struct NameListLoader;
+
+impl AssetLoader for NameListLoader {
+    type Asset = NameList;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut string = String::new();
+        reader.read_to_string(&mut string).await?;
+        let list = ron::from_str(&string)?;
+
+        Ok(list)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["names.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use rand::{rngs::StdRng, SeedableRng};
+    use walkdir::WalkDir;
+
+    use super::*;
+
+    #[test]
+    fn syllables() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let name = syllable_name(&mut rng);
+            let mut chars = name.chars();
+            let first = chars.next().expect("name shouldn't be empty");
+            assert!(first.is_uppercase(), "'{name}' should be capitalized");
+            assert!(chars.all(|c| c.is_lowercase()));
+            assert!(
+                name.len() >= 4,
+                "'{name}' should contain at least 2 syllables"
+            );
+        }
+    }
+
+    #[test]
+    fn lists() -> Result<()> {
+        let mut count = 0;
+        for entry in WalkDir::new(format!("../app/assets/{NAMES_DIR}"))
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+        {
+            let path = entry.path();
+            if !path.to_string_lossy().ends_with(NAMES_EXTENSION) {
+                continue;
+            }
+
+            let list: NameList = ron::from_str(&fs::read_to_string(path)?)?;
+            assert!(!list.male.is_empty(), "{path:?} should contain male names");
+            assert!(
+                !list.female.is_empty(),
+                "{path:?} should contain female names"
+            );
+            assert!(!list.last.is_empty(), "{path:?} should contain last names");
+            count += 1;
+        }
+
+        assert!(count > 0);
+
+        Ok(())
+    }
+}
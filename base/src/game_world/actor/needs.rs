@@ -1,9 +1,17 @@
 use std::time::Duration;
 
-use bevy::{prelude::*, time::common_conditions::on_timer};
+use bevy::{ecs::system::SystemParam, prelude::*, time::common_conditions::on_timer};
 use bevy_replicon::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use super::task::ActiveTask;
+use crate::game_world::{
+    auto_pause::{CriticalEvent, CriticalEventKind},
+    city::season::HOUR_DURATION,
+    difficulty::Difficulty,
+    SimulationSet,
+};
+
 pub(super) struct NeedsPlugin;
 
 impl Plugin for NeedsPlugin {
@@ -23,27 +31,90 @@ impl Plugin for NeedsPlugin {
             .replicate::<Bladder>()
             .replicate::<Need>()
             .add_systems(
-                Update,
+                FixedUpdate,
                 update_values
                     .run_if(on_timer(Duration::from_secs(1)))
-                    .run_if(server_or_singleplayer),
+                    .in_set(SimulationSet::Needs),
             );
     }
 }
 
-fn update_values(mut needs: Query<(&mut Need, &NeedRate)>) {
-    for (mut need, rate) in &mut needs {
-        if need.0 > rate.0 {
-            need.0 += rate.0;
-        } else {
-            need.0 = 0.0;
+fn update_values(
+    mut commands: Commands,
+    difficulty: Option<Single<&Difficulty>>,
+    mut needs: Query<(&mut Need, &NeedRate, &Name, &Parent)>,
+) {
+    let scale = difficulty.map(|d| **d).unwrap_or_default().need_scale();
+    for (mut need, rate, name, parent) in &mut needs {
+        if need.0 == 0.0 {
+            continue;
+        }
+
+        need.0 = (need.0 + rate.0 * scale).max(0.0);
+        if need.0 == 0.0 {
+            debug!("'{name}' depleted for `{}`", **parent);
+            commands.trigger(CriticalEvent {
+                kind: CriticalEventKind::NeedDepleted {
+                    need_name: name.to_string(),
+                },
+                actor_entity: **parent,
+            });
         }
     }
 }
 
+/// Predicts need values based on their rates and active tasks of actors.
+#[derive(SystemParam)]
+pub struct NeedForecast<'w, 's> {
+    difficulty: Option<Single<'w, &'static Difficulty>>,
+    needs: Query<
+        'w,
+        's,
+        (
+            &'static Need,
+            &'static NeedRate,
+            &'static Name,
+            &'static Parent,
+        ),
+    >,
+    children: Query<'w, 's, &'static Children>,
+    task_rates: Query<'w, 's, &'static TaskNeedRate, With<ActiveTask>>,
+}
+
+impl NeedForecast<'_, '_> {
+    /// Returns the value of the need after one in-game hour.
+    ///
+    /// Only continuous changes are taken into account,
+    /// instant changes from finished tasks are ignored.
+    pub fn in_hour(&self, need_entity: Entity) -> Option<f32> {
+        let (need, rate, name, parent) = self.needs.get(need_entity).ok()?;
+        let scale = self
+            .difficulty
+            .as_ref()
+            .map(|d| ***d)
+            .unwrap_or_default()
+            .need_scale();
+
+        // Depletion stops at zero, see `update_values`.
+        let mut per_second = if need.0 > 0.0 { rate.0 * scale } else { 0.0 };
+        if let Ok(children) = self.children.get(**parent) {
+            per_second += self
+                .task_rates
+                .iter_many(children)
+                .filter(|task_rate| task_rate.need_name == name.as_str())
+                .map(|task_rate| task_rate.rate)
+                .sum::<f32>();
+        }
+
+        let value = need.0 + per_second * HOUR_DURATION.as_secs_f32();
+        Some(value.clamp(0.0, 100.0))
+    }
+}
+
 #[derive(Component, Default, Deserialize, Reflect, Serialize)]
 #[reflect(Component)]
 #[require(
+    Name(|| Name::new("Hunger")),
     Need,
     NeedGlyph(|| NeedGlyph("🍴")),
     NeedRate(|| NeedRate(-0.4)),
@@ -53,6 +124,7 @@ pub(crate) struct Hunger;
 #[derive(Component, Default, Deserialize, Reflect, Serialize)]
 #[reflect(Component)]
 #[require(
+    Name(|| Name::new("Social")),
     Need,
     NeedGlyph(|| NeedGlyph("💬")),
     NeedRate(|| NeedRate(-0.1)),
@@ -62,6 +134,7 @@ pub(crate) struct Social;
 #[derive(Component, Default, Deserialize, Reflect, Serialize)]
 #[reflect(Component)]
 #[require(
+    Name(|| Name::new("Hygiene")),
     Need,
     NeedGlyph(|| NeedGlyph("🚿")),
     NeedRate(|| NeedRate(-0.3)),
@@ -71,6 +144,7 @@ pub(crate) struct Hygiene;
 #[derive(Component, Default, Deserialize, Reflect, Serialize)]
 #[reflect(Component)]
 #[require(
+    Name(|| Name::new("Fun")),
     Need,
     NeedGlyph(|| NeedGlyph("🎉")),
     NeedRate(|| NeedRate(-0.1)),
@@ -80,6 +154,7 @@ pub(crate) struct Fun;
 #[derive(Component, Default, Deserialize, Reflect, Serialize)]
 #[reflect(Component)]
 #[require(
+    Name(|| Name::new("Energy")),
     Need,
     NeedGlyph(|| NeedGlyph("🔋")),
     NeedRate(|| NeedRate(-0.2)),
@@ -89,25 +164,47 @@ pub(crate) struct Energy;
 #[derive(Component, Default, Deserialize, Reflect, Serialize)]
 #[reflect(Component)]
 #[require(
+    Name(|| Name::new("Bladder")),
     Need,
     NeedGlyph(|| NeedGlyph("🚽")),
     NeedRate(|| NeedRate(-0.5)),
 )]
 pub(crate) struct Bladder;
 
+/// Need value below which the need is considered critical.
+pub const CRITICAL_NEED: f32 = 20.0;
+
 #[derive(Component, Debug, Deserialize, Reflect, Serialize)]
 #[reflect(Component)]
 #[require(ParentSync, Replicated)]
 pub struct Need(pub f32);
 
+impl Need {
+    pub fn is_critical(&self) -> bool {
+        self.0 < CRITICAL_NEED
+    }
+}
+
 impl Default for Need {
     fn default() -> Self {
         Self(100.0)
     }
 }
 
+/// Change of a need per second without any tasks.
 #[derive(Component)]
-struct NeedRate(f32);
+pub struct NeedRate(f32);
+
+/// Continuous change of a need while the task is active.
+///
+/// Used only for forecasting, tasks apply the change themselves.
+#[derive(Component, Clone, Copy)]
+pub struct TaskNeedRate {
+    /// Name of the affected need.
+    pub(crate) need_name: &'static str,
+    /// Change per second.
+    pub(crate) rate: f32,
+}
 
 #[derive(Component)]
 pub struct NeedGlyph(pub &'static str);
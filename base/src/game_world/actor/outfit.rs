@@ -0,0 +1,248 @@
+use std::iter;
+
+use bevy::{
+    asset::AssetPath, prelude::*, render::mesh::skinning::SkinnedMesh, scene::SceneInstanceReady,
+    utils::HashMap,
+};
+use bevy_replicon::prelude::*;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, EnumString};
+
+use crate::asset::manifest::clothing_manifest::{BodySlot, ClothingManifest};
+
+pub(super) struct OutfitPlugin;
+
+impl Plugin for OutfitPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Outfits>()
+            .register_type::<ActiveOutfit>()
+            .replicate::<Outfits>()
+            .replicate::<ActiveOutfit>()
+            .add_observer(bind_skeleton)
+            .add_observer(wear_task_outfit)
+            .add_observer(restore_outfit)
+            .add_systems(Update, dress);
+    }
+}
+
+/// Spawns clothing pieces for the active outfit.
+fn dress(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    manifests: Res<Assets<ClothingManifest>>,
+    actors: Query<
+        (Entity, &Outfits, &ActiveOutfit, Option<&Children>),
+        Or<(Changed<Outfits>, Changed<ActiveOutfit>)>,
+    >,
+    pieces: Query<Entity, With<ClothingPiece>>,
+) {
+    for (actor_entity, outfits, &active_outfit, children) in &actors {
+        for piece_entity in pieces.iter_many(children.into_iter().flatten()) {
+            commands.entity(piece_entity).despawn_recursive();
+        }
+
+        debug!("dressing `{actor_entity}` in {:?} outfit", *active_outfit);
+        commands.entity(actor_entity).with_children(|parent| {
+            for item_path in outfits.get(*active_outfit).items() {
+                let Some(manifest) = asset_server
+                    .get_handle(item_path)
+                    .and_then(|handle| manifests.get(&handle))
+                else {
+                    error!("'{item_path}' is missing, ignoring");
+                    continue;
+                };
+
+                parent.spawn((
+                    ClothingPiece,
+                    SceneRoot(asset_server.load(manifest.scene.clone())),
+                ));
+            }
+        });
+    }
+}
+
+/// Attaches skinned meshes of clothing pieces to the actor skeleton.
+///
+/// Triggered for both pieces and actors since the actor scene can be respawned,
+/// for example, on sex change.
+fn bind_skeleton(
+    trigger: Trigger<SceneInstanceReady>,
+    actors: Query<&Children, With<Outfits>>,
+    pieces: Query<&Parent, With<ClothingPiece>>,
+    children: Query<&Children>,
+    names: Query<&Name>,
+    mut skinned_meshes: Query<&mut SkinnedMesh>,
+) {
+    let (actor_entity, actor_children) = if let Ok(parent) = pieces.get(trigger.entity()) {
+        let Ok(actor_children) = actors.get(**parent) else {
+            return;
+        };
+        (**parent, actor_children)
+    } else if let Ok(actor_children) = actors.get(trigger.entity()) {
+        (trigger.entity(), actor_children)
+    } else {
+        return;
+    };
+
+    let (piece_entities, scene_entities): (Vec<Entity>, Vec<Entity>) = actor_children
+        .iter()
+        .partition(|&&entity| pieces.get(entity).is_ok());
+    let bones: HashMap<_, _> = scene_entities
+        .into_iter()
+        .flat_map(|entity| iter::once(entity).chain(children.iter_descendants(entity)))
+        .filter_map(|entity| names.get(entity).ok().map(|name| (name, entity)))
+        .collect();
+    if bones.is_empty() {
+        // Actor scene is not ready yet, will be bound after spawning.
+        return;
+    }
+
+    for piece_entity in piece_entities {
+        debug!("binding `{piece_entity}` to skeleton of `{actor_entity}`");
+        let mut iter = skinned_meshes.iter_many_mut(children.iter_descendants(piece_entity));
+        while let Some(mut skinned_mesh) = iter.fetch_next() {
+            for joint in &mut skinned_mesh.joints {
+                if let Some(&bone_entity) = names.get(*joint).ok().and_then(|name| bones.get(name))
+                {
+                    *joint = bone_entity;
+                }
+            }
+        }
+    }
+}
+
+fn wear_task_outfit(
+    trigger: Trigger<OnAdd, TaskOutfit>,
+    tasks: Query<(&Parent, &TaskOutfit)>,
+    mut actors: Query<&mut ActiveOutfit>,
+) {
+    let (parent, &task_outfit) = tasks.get(trigger.entity()).unwrap();
+    let Ok(mut active_outfit) = actors.get_mut(**parent) else {
+        return;
+    };
+
+    debug!("changing outfit to {:?} for `{}`", *task_outfit, **parent);
+    active_outfit.0 = *task_outfit;
+}
+
+fn restore_outfit(
+    trigger: Trigger<OnRemove, TaskOutfit>,
+    tasks: Query<&Parent>,
+    mut actors: Query<&mut ActiveOutfit>,
+) {
+    let Ok(parent) = tasks.get(trigger.entity()) else {
+        return;
+    };
+    let Ok(mut active_outfit) = actors.get_mut(**parent) else {
+        return;
+    };
+
+    debug!("restoring everyday outfit for `{}`", **parent);
+    active_outfit.0 = OutfitKind::Everyday;
+}
+
+/// Clothing of an actor for each situation.
+#[derive(Clone, Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct Outfits {
+    pub everyday: Outfit,
+    pub sleep: Outfit,
+    #[reflect(default)]
+    pub outerwear: Outfit,
+}
+
+impl Outfits {
+    pub fn get(&self, kind: OutfitKind) -> &Outfit {
+        match kind {
+            OutfitKind::Everyday => &self.everyday,
+            OutfitKind::Sleep => &self.sleep,
+            OutfitKind::Outerwear => &self.outerwear,
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: OutfitKind) -> &mut Outfit {
+        match kind {
+            OutfitKind::Everyday => &mut self.everyday,
+            OutfitKind::Sleep => &mut self.sleep,
+            OutfitKind::Outerwear => &mut self.outerwear,
+        }
+    }
+}
+
+/// Clothing manifest paths by body slots.
+///
+/// An item that covers multiple slots is stored for each of them.
+#[derive(Clone, Default, Deserialize, Reflect, Serialize)]
+pub struct Outfit(HashMap<BodySlot, AssetPath<'static>>);
+
+impl Outfit {
+    pub fn item(&self, slot: BodySlot) -> Option<&AssetPath<'static>> {
+        self.0.get(&slot)
+    }
+
+    /// Returns all worn items without duplicates.
+    pub fn items(&self) -> impl Iterator<Item = &AssetPath<'static>> {
+        self.0.values().unique()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Wears the item in the specified slots, taking off items that occupy them.
+    pub fn put_on(&mut self, item_path: AssetPath<'static>, slots: &[BodySlot]) {
+        for &slot in slots {
+            self.take_off(slot);
+        }
+        for &slot in slots {
+            self.0.insert(slot, item_path.clone());
+        }
+    }
+
+    /// Takes off the item in the slot, including its other slots.
+    pub fn take_off(&mut self, slot: BodySlot) {
+        if let Some(item_path) = self.0.remove(&slot) {
+            self.0.retain(|_, path| *path != item_path);
+        }
+    }
+}
+
+/// Situation for which an outfit is worn.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, EnumIter, EnumString, PartialEq, Reflect, Serialize,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum OutfitKind {
+    #[default]
+    Everyday,
+    Sleep,
+    /// Worn outside in winter or bad weather.
+    Outerwear,
+}
+
+impl OutfitKind {
+    pub fn text(self) -> &'static str {
+        match self {
+            OutfitKind::Everyday => "Everyday",
+            OutfitKind::Sleep => "Sleep",
+            OutfitKind::Outerwear => "Outerwear",
+        }
+    }
+}
+
+/// Outfit from [`Outfits`] that the actor is currently wearing.
+#[derive(Clone, Component, Copy, Default, Deref, DerefMut, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct ActiveOutfit(pub OutfitKind);
+
+/// Outfit that the parent actor wears while the task exists.
+///
+/// Switches back to [`OutfitKind::Everyday`] on removal.
+#[derive(Clone, Component, Copy, Deref)]
+pub(super) struct TaskOutfit(pub(super) OutfitKind);
+
+/// Scene of a worn clothing item, spawned as a child of the actor.
+#[derive(Component)]
+#[require(Name(|| Name::new("Clothing piece")), SceneRoot)]
+struct ClothingPiece;
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(super) struct SkillsPlugin;
+
+impl Plugin for SkillsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CookingSkill>()
+            .replicate::<CookingSkill>();
+    }
+}
+
+/// Maximum level of a skill.
+pub const MAX_SKILL: f32 = 10.0;
+
+/// Improves by cooking and lowers the chance to set the stove on fire.
+#[derive(Component, Default, Deref, DerefMut, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct CookingSkill(pub f32);
+
+impl CookingSkill {
+    /// Returns the multiplier for the ignition chance of a used stove.
+    ///
+    /// Fully skilled cooks still have a small chance to start a fire.
+    pub fn ignition_scale(&self) -> f64 {
+        1.0 - 0.9 * (self.0 / MAX_SKILL).clamp(0.0, 1.0) as f64
+    }
+}
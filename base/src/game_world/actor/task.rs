@@ -1,6 +1,15 @@
-mod friendly;
+pub(crate) mod cook;
+pub(crate) mod eat;
+mod extinguish;
+pub(crate) mod friendly;
 mod linked_task;
-mod move_here;
+pub(crate) mod move_here;
+mod phone;
+mod scripted_task;
+pub(crate) mod sleep;
+pub(crate) mod swim;
+mod switch_lights;
+mod visit;
 
 use std::any;
 
@@ -9,23 +18,47 @@ use bevy_replicon::prelude::*;
 use bitflags::bitflags;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use super::{animation_state::AnimationState, Actor, ActorTaskGroups, SelectedActor};
-use crate::game_world::{city::ActiveCity, family::FamilyMode, navigation::NavDestination};
+use super::{animation_state::AnimationState, Actor, ActorTaskGroups, GroupedActor, SelectedActor};
+use crate::game_world::{
+    city::ActiveCity, family::FamilyMode, navigation::NavDestination, players::Players,
+};
+use cook::CookPlugin;
+use eat::EatPlugin;
+use extinguish::ExtinguishPlugin;
 use friendly::FriendlyPlugins;
 use linked_task::LinkedTaskPlugin;
 use move_here::MoveHerePlugin;
+use phone::PhonePlugin;
+use scripted_task::ScriptedTaskPlugin;
+use sleep::SleepPlugin;
+use swim::SwimPlugin;
+use switch_lights::SwitchLightsPlugin;
+use visit::VisitPlugin;
 
 pub(super) struct TaskPlugin;
 
 impl Plugin for TaskPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((FriendlyPlugins, LinkedTaskPlugin, MoveHerePlugin))
-            .replicate::<ActiveTask>()
-            .add_client_trigger::<TaskCancel>(ChannelKind::Unordered)
-            .add_observer(spawn_available.never_param_warn())
-            .add_observer(cleanup)
-            .add_observer(cancel)
-            .add_systems(PostUpdate, activate_queued.run_if(server_or_singleplayer));
+        app.add_plugins((
+            CookPlugin,
+            EatPlugin,
+            ExtinguishPlugin,
+            FriendlyPlugins,
+            LinkedTaskPlugin,
+            MoveHerePlugin,
+            PhonePlugin,
+            ScriptedTaskPlugin,
+            SleepPlugin,
+            SwimPlugin,
+            SwitchLightsPlugin,
+            VisitPlugin,
+        ))
+        .replicate::<ActiveTask>()
+        .add_client_trigger::<TaskCancel>(ChannelKind::Unordered)
+        .add_observer(spawn_available.never_param_warn())
+        .add_observer(cleanup)
+        .add_observer(cancel)
+        .add_systems(PostUpdate, activate_queued.run_if(server_or_singleplayer));
     }
 }
 
@@ -86,9 +119,28 @@ fn activate_queued(
 fn cancel(
     trigger: Trigger<FromClient<TaskCancel>>,
     mut commands: Commands,
-    tasks: Query<(), With<Task>>,
+    players: Res<Players>,
+    tasks: Query<&Parent, With<Task>>,
+    actors: Query<&Actor>,
 ) {
-    if tasks.get(trigger.entity()).is_ok() {
+    if !players.can_play(trigger.client_id) {
+        error!("`{:?}` is not allowed to cancel tasks", trigger.client_id);
+        return;
+    }
+
+    if let Ok(parent) = tasks.get(trigger.entity()) {
+        let Ok(actor) = actors.get(**parent) else {
+            error!("task `{}` doesn't belong to an actor", trigger.entity());
+            return;
+        };
+        if !players.owns(trigger.client_id, actor.family_entity) {
+            error!(
+                "`{:?}` is not allowed to cancel tasks of family `{}`",
+                trigger.client_id, actor.family_entity
+            );
+            return;
+        }
+
         info!(
             "`{:?}` cancels task `{}`",
             trigger.client_id,
@@ -131,8 +183,8 @@ fn cleanup(
 /// Stores available tasks for an entity, triggered by picking.
 pub struct AvailableTasks {
     // TODO 0.16: Use `Parent` when hierarchy will be accessible in observers.
-    interaction_entity: Entity,
-    click_point: Vec3,
+    pub(super) interaction_entity: Entity,
+    pub(super) click_point: Vec3,
 }
 
 #[derive(Component, Default)]
@@ -142,6 +194,21 @@ pub struct Task;
 #[derive(Component, Serialize, Deserialize)]
 pub struct ActiveTask;
 
+/// Object that the actor is currently using for the task.
+///
+/// Inserted on server when the interaction starts, so ignition sources
+/// can catch fire only while in use.
+#[derive(Component)]
+pub(crate) struct UsedObject {
+    pub(crate) object_entity: Entity,
+    /// Multiplier for the ignition chance of the object.
+    pub(crate) ignition_scale: f64,
+}
+
+/// Marks tasks in which an actor talks to someone.
+#[derive(Component, Default)]
+pub struct Conversation;
+
 bitflags! {
     #[derive(Default, Component, Clone, Copy, Debug)]
     pub(super) struct TaskGroups: u8 {
@@ -171,20 +238,37 @@ impl<C: MapEntities> MapEntities for TaskRequest<C> {
     }
 }
 
+/// Customizes how a task is distributed between grouped actors.
+pub(super) trait GroupTask: Sized {
+    /// Returns the task for the grouped actor with the specified index.
+    ///
+    /// Index 0 corresponds to the selected actor.
+    /// By default all actors receive the same task.
+    fn for_member(self, _index: usize) -> Self {
+        self
+    }
+}
+
 pub(super) trait TaskAppExt {
     fn add_task<C>(&mut self) -> &mut Self
     where
-        C: Component + GetTypeRegistration + Copy + Serialize + DeserializeOwned;
+        C: Component + GetTypeRegistration + Copy + Serialize + DeserializeOwned + GroupTask;
 
     fn add_mapped_task<C>(&mut self) -> &mut Self
     where
-        C: Component + GetTypeRegistration + Copy + Serialize + DeserializeOwned + MapEntities;
+        C: Component
+            + GetTypeRegistration
+            + Copy
+            + Serialize
+            + DeserializeOwned
+            + MapEntities
+            + GroupTask;
 }
 
 impl TaskAppExt for App {
     fn add_task<C>(&mut self) -> &mut Self
     where
-        C: Component + GetTypeRegistration + Copy + Serialize + DeserializeOwned,
+        C: Component + GetTypeRegistration + Copy + Serialize + DeserializeOwned + GroupTask,
     {
         self.register_type::<C>()
             .replicate::<C>()
@@ -195,7 +279,13 @@ impl TaskAppExt for App {
 
     fn add_mapped_task<C>(&mut self) -> &mut Self
     where
-        C: Component + GetTypeRegistration + Copy + Serialize + DeserializeOwned + MapEntities,
+        C: Component
+            + GetTypeRegistration
+            + Copy
+            + Serialize
+            + DeserializeOwned
+            + MapEntities
+            + GroupTask,
     {
         self.register_type::<C>()
             .replicate_mapped::<C>()
@@ -205,12 +295,13 @@ impl TaskAppExt for App {
     }
 }
 
-fn request<C: Component + Copy>(
+fn request<C: Component + Copy + GroupTask>(
     trigger: Trigger<TaskSelect>,
     mut commands: Commands,
     tasks: Query<(&Name, &C)>,
     tasks_entity: Single<Entity, With<AvailableTasks>>,
     selected_entity: Single<Entity, With<SelectedActor>>,
+    grouped_actors: Query<Entity, (With<GroupedActor>, Without<SelectedActor>)>,
 ) {
     let Ok((name, &task)) = tasks.get(trigger.entity()) else {
         return;
@@ -219,14 +310,32 @@ fn request<C: Component + Copy>(
     info!("selecting `{name}`");
     commands.entity(*tasks_entity).despawn_recursive();
     commands.client_trigger_targets(TaskRequest(task), *selected_entity);
+    for (index, actor_entity) in grouped_actors.iter().enumerate() {
+        debug!("sharing `{name}` with grouped actor `{actor_entity}`");
+        commands.client_trigger_targets(TaskRequest(task.for_member(index + 1)), actor_entity);
+    }
 }
 
 fn queue<C: Component + Copy>(
     trigger: Trigger<FromClient<TaskRequest<C>>>,
     mut commands: Commands,
-    actors: Query<(), With<Actor>>,
+    players: Res<Players>,
+    actors: Query<&Actor>,
 ) {
-    if actors.get(trigger.entity()).is_ok() {
+    if !players.can_play(trigger.client_id) {
+        error!("`{:?}` is not allowed to request tasks", trigger.client_id);
+        return;
+    }
+
+    if let Ok(actor) = actors.get(trigger.entity()) {
+        if !players.owns(trigger.client_id, actor.family_entity) {
+            error!(
+                "`{:?}` is not allowed to request tasks for family `{}`",
+                trigger.client_id, actor.family_entity
+            );
+            return;
+        }
+
         info!(
             "`{:?}` requests task `{}`",
             trigger.client_id,
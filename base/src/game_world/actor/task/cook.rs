@@ -0,0 +1,159 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ActiveTask, AvailableTasks, GroupTask, Task, TaskAppExt, TaskGroups, UsedObject};
+use crate::{
+    achievements::{Counter, CounterIncrease},
+    core::GameState,
+    game_world::{
+        actor::{
+            needs::{Hunger, Need, TaskNeedRate},
+            skills::{CookingSkill, MAX_SKILL},
+            Movement,
+        },
+        fire::OnFire,
+        navigation::{NavDestination, Navigation},
+    },
+};
+
+pub(super) struct CookPlugin;
+
+impl Plugin for CookPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Stove>()
+            .add_mapped_task::<Cook>()
+            .add_observer(add_to_list)
+            .add_observer(activate)
+            .add_systems(
+                Update,
+                cook.run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// How much [`Hunger`] restores per second of cooking.
+const HUNGER_PER_SECOND: f32 = 2.0;
+
+/// How much [`CookingSkill`] improves per second of cooking.
+const SKILL_PER_SECOND: f32 = 0.02;
+
+fn add_to_list(
+    trigger: Trigger<OnAdd, AvailableTasks>,
+    mut commands: Commands,
+    available_tasks: Single<&AvailableTasks>,
+    stoves: Query<(), (With<Stove>, Without<OnFire>)>,
+) {
+    if stoves.get(available_tasks.interaction_entity).is_err() {
+        return;
+    }
+
+    debug!("listing task");
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn(Cook::new(
+            available_tasks.interaction_entity,
+            available_tasks.click_point,
+        ));
+    });
+}
+
+fn activate(
+    trigger: Trigger<OnAdd, ActiveTask>,
+    mut actors: Query<(&mut Navigation, &mut NavDestination)>,
+    tasks: Query<(&Parent, &Cook)>,
+) {
+    let Ok((parent, cook)) = tasks.get(trigger.entity()) else {
+        return;
+    };
+
+    debug!("walking to the stove");
+    let (mut navigation, mut dest) = actors
+        .get_mut(**parent)
+        .expect("actors should have navigation component");
+    *navigation = Navigation::new(Movement::Walk.speed()).with_offset(0.8);
+    **dest = Some(cook.endpoint);
+}
+
+/// Uses the stove after reaching it, restores [`Hunger`] and improves [`CookingSkill`].
+///
+/// The stove can catch fire while in use, less likely for skilled cooks.
+fn cook(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut actors: Query<(&NavDestination, &Children, &mut CookingSkill)>,
+    tasks: Query<(Entity, &Parent, &Cook, Has<UsedObject>), With<ActiveTask>>,
+    stoves: Query<(), (With<Stove>, Without<OnFire>)>,
+    mut needs: Query<&mut Need, With<Hunger>>,
+) {
+    for (task_entity, parent, cook, used) in &tasks {
+        let (dest, children, mut skill) = actors
+            .get_mut(**parent)
+            .expect("actors should have destination");
+        if dest.is_some() {
+            continue;
+        }
+        if stoves.get(cook.stove_entity).is_err() {
+            debug!("stove `{}` was removed or caught fire", cook.stove_entity);
+            commands.entity(task_entity).despawn();
+            continue;
+        }
+
+        if !used {
+            debug!("`{}` starts cooking", **parent);
+            commands.entity(task_entity).insert(UsedObject {
+                object_entity: cook.stove_entity,
+                ignition_scale: skill.ignition_scale(),
+            });
+        }
+
+        skill.0 = (skill.0 + SKILL_PER_SECOND * time.delta_secs()).min(MAX_SKILL);
+
+        let Some(mut hunger) = needs.iter_many_mut(children).fetch_next() else {
+            continue;
+        };
+        hunger.0 = (hunger.0 + HUNGER_PER_SECOND * time.delta_secs()).min(100.0);
+        if hunger.0 >= 100.0 {
+            debug!("`{}` finishes cooking", **parent);
+            commands.trigger(CounterIncrease(Counter::MealsEaten));
+            commands.entity(task_entity).despawn();
+        }
+    }
+}
+
+/// Object on which actors can cook.
+///
+/// Inserted from object manifests.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub(crate) struct Stove;
+
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Cook")),
+    Task,
+    TaskGroups(|| TaskGroups::LEGS | TaskGroups::BOTH_HANDS),
+    TaskNeedRate(|| TaskNeedRate { need_name: "Hunger", rate: HUNGER_PER_SECOND }),
+)]
+pub(crate) struct Cook {
+    stove_entity: Entity,
+    endpoint: Vec3,
+}
+
+impl Cook {
+    pub(crate) fn new(stove_entity: Entity, endpoint: Vec3) -> Self {
+        Self {
+            stove_entity,
+            endpoint,
+        }
+    }
+}
+
+impl MapEntities for Cook {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.stove_entity = entity_mapper.map_entity(self.stove_entity);
+    }
+}
+
+impl GroupTask for Cook {}
@@ -0,0 +1,143 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ActiveTask, AvailableTasks, GroupTask, Task, TaskAppExt, TaskGroups};
+use crate::{
+    achievements::{Counter, CounterIncrease},
+    core::GameState,
+    game_world::{
+        actor::{
+            needs::{Hunger, Need, TaskNeedRate},
+            Movement,
+        },
+        navigation::{NavDestination, Navigation},
+    },
+};
+
+pub(super) struct EatPlugin;
+
+impl Plugin for EatPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Meal>()
+            .add_mapped_task::<Eat>()
+            .add_observer(add_to_list)
+            .add_observer(activate)
+            .add_systems(
+                Update,
+                eat.run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// How much [`Hunger`] restores per second of eating.
+const HUNGER_PER_SECOND: f32 = 4.0;
+
+fn add_to_list(
+    trigger: Trigger<OnAdd, AvailableTasks>,
+    mut commands: Commands,
+    available_tasks: Single<&AvailableTasks>,
+    meals: Query<(), With<Meal>>,
+) {
+    if meals.get(available_tasks.interaction_entity).is_err() {
+        return;
+    }
+
+    debug!("listing task");
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn(Eat::new(
+            available_tasks.interaction_entity,
+            available_tasks.click_point,
+        ));
+    });
+}
+
+fn activate(
+    trigger: Trigger<OnAdd, ActiveTask>,
+    mut actors: Query<(&mut Navigation, &mut NavDestination)>,
+    tasks: Query<(&Parent, &Eat)>,
+) {
+    let Ok((parent, eat)) = tasks.get(trigger.entity()) else {
+        return;
+    };
+
+    debug!("walking to the meal");
+    let (mut navigation, mut dest) = actors
+        .get_mut(**parent)
+        .expect("actors should have navigation component");
+    *navigation = Navigation::new(Movement::Walk.speed()).with_offset(0.6);
+    **dest = Some(eat.endpoint);
+}
+
+/// Restores [`Hunger`] after reaching the meal and removes the meal when the actor is full.
+fn eat(
+    mut commands: Commands,
+    time: Res<Time>,
+    actors: Query<(&NavDestination, &Children)>,
+    tasks: Query<(Entity, &Parent, &Eat), With<ActiveTask>>,
+    meals: Query<(), With<Meal>>,
+    mut needs: Query<&mut Need, With<Hunger>>,
+) {
+    for (task_entity, parent, eat) in &tasks {
+        let (dest, children) = actors
+            .get(**parent)
+            .expect("actors should have destination");
+        if dest.is_some() {
+            continue;
+        }
+        if meals.get(eat.meal_entity).is_err() {
+            debug!("meal `{}` was removed", eat.meal_entity);
+            commands.entity(task_entity).despawn();
+            continue;
+        }
+
+        let Some(mut hunger) = needs.iter_many_mut(children).fetch_next() else {
+            continue;
+        };
+        hunger.0 = (hunger.0 + HUNGER_PER_SECOND * time.delta_secs()).min(100.0);
+        if hunger.0 >= 100.0 {
+            debug!("`{}` finishes `{}`", **parent, eat.meal_entity);
+            commands.trigger(CounterIncrease(Counter::MealsEaten));
+            commands.entity(eat.meal_entity).despawn_recursive();
+            commands.entity(task_entity).despawn();
+        }
+    }
+}
+
+/// Object that actors can eat.
+///
+/// Inserted from object manifests.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub(crate) struct Meal;
+
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Eat")),
+    Task,
+    TaskGroups(|| TaskGroups::LEGS | TaskGroups::BOTH_HANDS),
+    TaskNeedRate(|| TaskNeedRate { need_name: "Hunger", rate: HUNGER_PER_SECOND }),
+)]
+pub(crate) struct Eat {
+    meal_entity: Entity,
+    endpoint: Vec3,
+}
+
+impl Eat {
+    pub(crate) fn new(meal_entity: Entity, endpoint: Vec3) -> Self {
+        Self {
+            meal_entity,
+            endpoint,
+        }
+    }
+}
+
+impl MapEntities for Eat {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.meal_entity = entity_mapper.map_entity(self.meal_entity);
+    }
+}
+
+impl GroupTask for Eat {}
@@ -0,0 +1,108 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ActiveTask, AvailableTasks, GroupTask, Task, TaskAppExt, TaskGroups};
+use crate::{
+    core::GameState,
+    game_world::{
+        actor::Movement,
+        fire::OnFire,
+        navigation::{NavDestination, Navigation},
+    },
+};
+
+pub(super) struct ExtinguishPlugin;
+
+impl Plugin for ExtinguishPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_mapped_task::<Extinguish>()
+            .add_observer(add_to_list)
+            .add_observer(activate)
+            .add_systems(
+                Update,
+                finish
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+fn add_to_list(
+    trigger: Trigger<OnAdd, AvailableTasks>,
+    mut commands: Commands,
+    available_tasks: Single<&AvailableTasks>,
+    burning: Query<(), With<OnFire>>,
+) {
+    if burning.get(available_tasks.interaction_entity).is_err() {
+        return;
+    }
+
+    debug!("listing task");
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn(Extinguish {
+            target_entity: available_tasks.interaction_entity,
+            endpoint: available_tasks.click_point,
+        });
+    });
+}
+
+fn activate(
+    trigger: Trigger<OnAdd, ActiveTask>,
+    mut actors: Query<(&mut Navigation, &mut NavDestination)>,
+    tasks: Query<(&Parent, &Extinguish)>,
+) {
+    let Ok((parent, extinguish)) = tasks.get(trigger.entity()) else {
+        return;
+    };
+
+    debug!("running to fire");
+    let (mut navigation, mut dest) = actors
+        .get_mut(**parent)
+        .expect("actors should have navigation component");
+    *navigation = Navigation::new(Movement::Run.speed()).with_offset(1.0);
+    **dest = Some(extinguish.endpoint);
+}
+
+/// Puts out the fire when the actor reaches it.
+fn finish(
+    mut commands: Commands,
+    actors: Query<&NavDestination>,
+    tasks: Query<(Entity, &Parent, &Extinguish), With<ActiveTask>>,
+    burning: Query<(), With<OnFire>>,
+) {
+    for (task_entity, parent, extinguish) in &tasks {
+        let dest = actors
+            .get(**parent)
+            .expect("actors should have always have destination");
+        if dest.is_some() {
+            continue;
+        }
+
+        if burning.get(extinguish.target_entity).is_ok() {
+            info!("`{}` extinguishes `{}`", **parent, extinguish.target_entity);
+            commands.entity(extinguish.target_entity).remove::<OnFire>();
+        }
+        commands.entity(task_entity).despawn();
+    }
+}
+
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Extinguish")),
+    Task,
+    TaskGroups(|| TaskGroups::LEGS | TaskGroups::BOTH_HANDS),
+)]
+struct Extinguish {
+    target_entity: Entity,
+    endpoint: Vec3,
+}
+
+impl MapEntities for Extinguish {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.target_entity = entity_mapper.map_entity(self.target_entity);
+    }
+}
+
+impl GroupTask for Extinguish {}
@@ -1,4 +1,4 @@
-mod tell_secret;
+pub(crate) mod tell_secret;
 
 use bevy::{app::PluginGroupBuilder, prelude::*};
 
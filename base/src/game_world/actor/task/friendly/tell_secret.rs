@@ -1,16 +1,20 @@
 use bevy::{animation::RepeatAnimation, ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    achievements::{Counter, CounterIncrease},
     asset::collection::Collection,
     game_world::{
         actor::{
             animation_state::{AnimationState, Montage, MontageFinished},
             task::{
-                linked_task::LinkedTask, ActiveTask, AvailableTasks, Task, TaskAppExt, TaskGroups,
+                linked_task::LinkedTask, ActiveTask, AvailableTasks, Conversation, GroupTask, Task,
+                TaskAppExt, TaskGroups,
             },
             Actor, ActorAnimation, Movement,
         },
+        event_log::{EventCategory, GameEvent},
         navigation::{following::Following, Navigation},
     },
 };
@@ -38,9 +42,7 @@ fn add_to_list(
     if actors.get(available_tasks.interaction_entity).is_ok() {
         debug!("listing task");
         commands.entity(trigger.entity()).with_children(|parent| {
-            parent.spawn(TellSecret {
-                target_entity: available_tasks.interaction_entity,
-            });
+            parent.spawn(TellSecret::new(available_tasks.interaction_entity));
         });
     }
 }
@@ -125,15 +127,30 @@ fn start_listening(
 fn finish(
     trigger: Trigger<MontageFinished>,
     mut commands: Commands,
+    client: Res<RepliconClient>,
     children: Query<&Children>,
-    tasks: Query<Entity, (With<TellSecret>, With<ActiveTask>)>,
+    names: Query<&Name, With<Actor>>,
+    tasks: Query<(Entity, &TellSecret), With<ActiveTask>>,
 ) {
     let Ok(children) = children.get(trigger.entity()) else {
         return;
     };
 
-    if let Some(task_entity) = tasks.iter_many(children).next() {
+    if let Some((task_entity, tell_secret)) = tasks.iter_many(children).next() {
         commands.entity(task_entity).despawn();
+        commands.trigger(CounterIncrease(Counter::SecretsTold));
+
+        // Events are replicated, so only log them where the simulation runs.
+        if client.is_disconnected() {
+            if let Ok([teller_name, listener_name]) =
+                names.get_many([trigger.entity(), tell_secret.target_entity])
+            {
+                commands.spawn(GameEvent::new(
+                    EventCategory::Social,
+                    format!("{teller_name} told {listener_name} a secret"),
+                ));
+            }
+        }
     }
 }
 
@@ -142,24 +159,34 @@ fn finish(
 #[require(
     Name(|| Name::new("Tell secret")),
     Task,
+    Conversation,
     LinkedTask,
     TaskGroups(|| TaskGroups::LEGS),
 )]
-struct TellSecret {
+pub(crate) struct TellSecret {
     target_entity: Entity,
 }
 
+impl TellSecret {
+    pub(crate) fn new(target_entity: Entity) -> Self {
+        Self { target_entity }
+    }
+}
+
 impl MapEntities for TellSecret {
     fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
         self.target_entity = entity_mapper.map_entity(self.target_entity);
     }
 }
 
+impl GroupTask for TellSecret {}
+
 #[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
 #[reflect(Component)]
 #[require(
     Name(|| Name::new("Listen secret")),
     Task,
+    Conversation,
     TaskGroups(|| TaskGroups::LEGS),
 )]
 struct ListenSecret {
@@ -171,3 +198,5 @@ impl MapEntities for ListenSecret {
         self.teller_entity = entity_mapper.map_entity(self.teller_entity);
     }
 }
+
+impl GroupTask for ListenSecret {}
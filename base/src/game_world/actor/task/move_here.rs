@@ -1,11 +1,14 @@
+use std::f32::consts::PI;
+
 use bevy::prelude::*;
+use bevy_replicon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use super::{ActiveTask, AvailableTasks, Task, TaskAppExt, TaskGroups};
+use super::{ActiveTask, AvailableTasks, GroupTask, Task, TaskAppExt, TaskGroups, TaskSelect};
 use crate::{
     core::GameState,
     game_world::{
-        actor::Movement,
+        actor::{interpolation::PredictedMovement, Movement, SelectedActor, ACTOR_RADIUS},
         city::Ground,
         navigation::{NavDestination, Navigation},
     },
@@ -17,6 +20,7 @@ impl Plugin for MoveHerePlugin {
     fn build(&self, app: &mut App) {
         app.add_task::<MoveHere>()
             .add_observer(add_to_list)
+            .add_observer(predict)
             .add_observer(activate)
             .add_systems(Update, finish.run_if(in_state(GameState::InGame)));
     }
@@ -51,6 +55,36 @@ fn add_to_list(
     });
 }
 
+/// Starts moving the selected actor on client without waiting for the server.
+fn predict(
+    trigger: Trigger<TaskSelect>,
+    mut commands: Commands,
+    client: Res<RepliconClient>,
+    tasks: Query<&MoveHere>,
+    selected_entity: Single<Entity, With<SelectedActor>>,
+) {
+    if !client.is_connected() {
+        return;
+    }
+    let Ok(move_here) = tasks.get(trigger.entity()) else {
+        return;
+    };
+
+    debug!("predicting movement for `{}`", *selected_entity);
+    commands
+        .entity(*selected_entity)
+        .insert(PredictedMovement::new(
+            move_here.endpoint,
+            move_here.movement.speed(),
+        ));
+}
+
+/// Distance between actors that move as a group.
+const FORMATION_SPACING: f32 = ACTOR_RADIUS * 3.0;
+
+/// Angle between consecutive formation slots, produces evenly distributed points.
+const GOLDEN_ANGLE: f32 = PI * (3.0 - 2.236_068);
+
 fn activate(
     trigger: Trigger<OnAdd, ActiveTask>,
     mut actors: Query<(&mut Navigation, &mut NavDestination)>,
@@ -87,7 +121,64 @@ fn finish(
 #[derive(Clone, Reflect, Component, Copy, Deserialize, Serialize)]
 #[reflect(Component)]
 #[require(Task, TaskGroups(|| TaskGroups::LEGS))]
-struct MoveHere {
+pub(crate) struct MoveHere {
     endpoint: Vec3,
     movement: Movement,
 }
+
+impl MoveHere {
+    /// Creates a task for walking to the specified point.
+    pub(crate) fn walk(endpoint: Vec3) -> Self {
+        Self {
+            endpoint,
+            movement: Movement::Walk,
+        }
+    }
+
+    /// Creates a task for running to the specified point.
+    pub(crate) fn run(endpoint: Vec3) -> Self {
+        Self {
+            endpoint,
+            movement: Movement::Run,
+        }
+    }
+}
+
+impl GroupTask for MoveHere {
+    /// Places grouped actors around the endpoint to avoid stacking them at the same point.
+    fn for_member(mut self, index: usize) -> Self {
+        let radius = FORMATION_SPACING * (index as f32).sqrt();
+        let angle = GOLDEN_ANGLE * index as f32;
+        self.endpoint += Vec3::new(radius * angle.cos(), 0.0, radius * angle.sin());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formation() {
+        let endpoint = Vec3::new(1.0, 0.0, 2.0);
+        let task = MoveHere::walk(endpoint);
+        assert_eq!(
+            task.for_member(0).endpoint,
+            endpoint,
+            "selected actor should walk to the clicked point"
+        );
+
+        let endpoints: Vec<_> = (0..16)
+            .map(|index| task.for_member(index).endpoint)
+            .collect();
+        for (index, &a) in endpoints.iter().enumerate() {
+            assert_eq!(a.y, endpoint.y, "formation should stay on the same level");
+            for &b in &endpoints[index + 1..] {
+                assert!(
+                    a.distance(b) > ACTOR_RADIUS * 2.0,
+                    "actors shouldn't overlap, but {a} and {b} are too close"
+                );
+            }
+        }
+    }
+}
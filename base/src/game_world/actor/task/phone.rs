@@ -0,0 +1,327 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use rand::seq::IteratorRandom;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    eat::Eat,
+    visit::{Visit, Visiting},
+    ActiveTask, AvailableTasks, Conversation, GroupTask, Task, TaskAppExt, TaskGroups,
+};
+use crate::{
+    core::GameState,
+    game_world::{
+        actor::{
+            needs::{Need, Social},
+            Actor, Movement, SelectedActor,
+        },
+        difficulty::Difficulty,
+        event_log::{EventCategory, GameEvent},
+        family::{npc::Npc, Budget, FamilyOwner, TransactionKind, TransactionLog},
+        navigation::{following::Following, Navigation},
+        object::Object,
+    },
+};
+
+pub(super) struct PhonePlugin;
+
+impl Plugin for PhonePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_mapped_task::<Call>()
+            .add_mapped_task::<Errand>()
+            .add_observer(add_to_list)
+            .add_observer(start_errand)
+            .add_observer(finish_errand)
+            .add_systems(
+                Update,
+                finish_call
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+const PIZZA_PRICE: u32 = 40;
+
+const PIZZA_BOX: &str = "base/objects/food/pizza_box/pizza_box.object.ron";
+
+/// Distance in front of the caller at which the delivered meal is placed.
+const MEAL_DISTANCE: f32 = 0.8;
+
+/// Distance from the caller at which dispatched NPCs from other cities appear.
+const ARRIVAL_DISTANCE: f32 = 15.0;
+
+/// Lists phone calls when the selected actor is clicked.
+fn add_to_list(
+    trigger: Trigger<OnAdd, AvailableTasks>,
+    mut commands: Commands,
+    available_tasks: Single<&AvailableTasks>,
+    selected_actor: Single<(Entity, &Actor), With<SelectedActor>>,
+    actors: Query<(Entity, &Name, &Actor)>,
+    npc_families: Query<(), With<Npc>>,
+) {
+    let (selected_entity, selected_actor) = *selected_actor;
+    if available_tasks.interaction_entity != selected_entity {
+        return;
+    }
+
+    debug!("listing calls");
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn((Name::new("Call for pizza"), Call(CallKind::Pizza)));
+        for (actor_entity, name, actor) in &actors {
+            if actor.family_entity != selected_actor.family_entity
+                && npc_families.get(actor.family_entity).is_ok()
+            {
+                parent.spawn((
+                    Name::new(format!("Invite {name} over")),
+                    Call(CallKind::Invite(actor_entity)),
+                ));
+            }
+        }
+    });
+}
+
+/// Dispatches an NPC to the caller after the call.
+///
+/// NPCs from other cities are transferred next to the caller.
+fn finish_call(
+    mut commands: Commands,
+    time: Res<Time>,
+    difficulty: Option<Single<&Difficulty>>,
+    mut tasks: Query<(Entity, &Parent, &Call, &mut CallTimer), With<ActiveTask>>,
+    mut actors: Query<(Entity, &Actor, &Name, &Parent, &mut Transform, &Children)>,
+    mut families: Query<(&mut Budget, &mut TransactionLog)>,
+    npc_families: Query<(), With<Npc>>,
+    visitors: Query<&Visiting>,
+    all_tasks: Query<(), With<Task>>,
+) {
+    let difficulty = difficulty.map(|d| **d).unwrap_or_default();
+    for (task_entity, caller_entity, call, mut timer) in &mut tasks {
+        timer.tick(time.delta());
+        if !timer.finished() {
+            continue;
+        }
+
+        commands.entity(task_entity).despawn();
+        let Ok((_, caller, caller_name, city_entity, caller_transform, _)) =
+            actors.get(**caller_entity)
+        else {
+            continue;
+        };
+        let caller_name = caller_name.clone();
+        let caller_family = caller.family_entity;
+        let caller_city = **city_entity;
+        let caller_position = caller_transform.translation;
+
+        let npc_entity = match call.0 {
+            CallKind::Pizza => {
+                // Any NPC can work as a courier.
+                let mut rng = rand::thread_rng();
+                let Some(courier_entity) = actors
+                    .iter()
+                    .filter(|(_, actor, ..)| npc_families.get(actor.family_entity).is_ok())
+                    .map(|(entity, ..)| entity)
+                    .choose(&mut rng)
+                else {
+                    debug!("no couriers available");
+                    continue;
+                };
+                if let Ok((mut budget, mut log)) = families.get_mut(caller_family) {
+                    budget.spend(
+                        &mut log,
+                        TransactionKind::Service,
+                        "Pizza delivery",
+                        difficulty.scale_cost(PIZZA_PRICE),
+                    );
+                }
+                courier_entity
+            }
+            CallKind::Invite(actor_entity) => actor_entity,
+        };
+
+        let Ok((_, _, npc_name, npc_parent, mut npc_transform, npc_children)) =
+            actors.get_mut(npc_entity)
+        else {
+            continue;
+        };
+
+        info!("dispatching `{npc_entity}` to `{}`", **caller_entity);
+        for &child_entity in npc_children {
+            if all_tasks.get(child_entity).is_ok() {
+                commands.entity(child_entity).despawn_recursive();
+            }
+        }
+        if **npc_parent != caller_city {
+            if visitors.get(npc_entity).is_err() {
+                commands
+                    .entity(npc_entity)
+                    .insert(Visiting::new(**npc_parent, npc_transform.translation));
+            }
+            npc_transform.translation = caller_position + Vec3::X * ARRIVAL_DISTANCE;
+            commands.entity(npc_entity).set_parent(caller_city);
+        }
+        commands.entity(npc_entity).with_children(|parent| {
+            parent.spawn(Errand {
+                caller_entity: **caller_entity,
+                kind: call.0,
+            });
+        });
+
+        let message = match call.0 {
+            CallKind::Pizza => format!("{caller_name} ordered a pizza"),
+            CallKind::Invite(_) => format!("{caller_name} invited {npc_name} over"),
+        };
+        commands.spawn(GameEvent::new(EventCategory::Social, message));
+    }
+}
+
+fn start_errand(
+    trigger: Trigger<OnAdd, ActiveTask>,
+    mut commands: Commands,
+    mut actors: Query<&mut Navigation>,
+    tasks: Query<(&Parent, &Errand)>,
+) {
+    let Ok((parent, errand)) = tasks.get(trigger.entity()) else {
+        return;
+    };
+
+    debug!("walking to the caller");
+    let mut navigation = actors
+        .get_mut(**parent)
+        .expect("actors should have navigation component");
+    *navigation = Navigation::new(Movement::Walk.speed()).with_offset(1.0);
+
+    commands
+        .entity(**parent)
+        .insert(Following(errand.caller_entity));
+}
+
+/// Completes the errand when the NPC reaches the caller and sends it home.
+///
+/// Delivered pizza is placed in front of the caller, who is sent to eat it.
+/// Triggered only on server since [`Following`] is not replicated.
+fn finish_errand(
+    trigger: Trigger<OnRemove, Following>,
+    mut commands: Commands,
+    actors: Query<(&Name, &Children)>,
+    callers: Query<(&Actor, &Parent, &Transform)>,
+    tasks: Query<(Entity, &Errand), With<ActiveTask>>,
+    visitors: Query<&Visiting>,
+    mut social: Query<&mut Need, With<Social>>,
+) {
+    let Ok((npc_name, children)) = actors.get(trigger.entity()) else {
+        return;
+    };
+    let Some((task_entity, errand)) = tasks.iter_many(children).next() else {
+        return;
+    };
+    commands.entity(task_entity).despawn();
+
+    let Ok((caller_name, caller_children)) = actors.get(errand.caller_entity) else {
+        return;
+    };
+
+    let message = match errand.kind {
+        CallKind::Pizza => {
+            if let Ok((caller, city_entity, caller_transform)) = callers.get(errand.caller_entity) {
+                let translation =
+                    caller_transform.translation + caller_transform.forward() * MEAL_DISTANCE;
+                let meal_entity = commands
+                    .spawn((
+                        Object(PIZZA_BOX.into()),
+                        Transform::from_translation(translation),
+                        FamilyOwner(caller.family_entity),
+                    ))
+                    .set_parent(**city_entity)
+                    .id();
+                debug!("sending `{}` to eat `{meal_entity}`", errand.caller_entity);
+                commands
+                    .entity(errand.caller_entity)
+                    .with_children(|parent| {
+                        parent.spawn(Eat::new(meal_entity, translation));
+                    });
+            }
+            format!("{npc_name} delivered a pizza to {caller_name}")
+        }
+        CallKind::Invite(_) => {
+            for actor_children in [caller_children, children] {
+                let mut iter = social.iter_many_mut(actor_children);
+                while let Some(mut need) = iter.fetch_next() {
+                    need.0 = 100.0;
+                }
+            }
+            format!("{npc_name} came over to {caller_name}")
+        }
+    };
+    commands.spawn(GameEvent::new(EventCategory::Social, message));
+
+    if let Ok(&visiting) = visitors.get(trigger.entity()) {
+        debug!("sending `{}` home", trigger.entity());
+        commands.entity(trigger.entity()).with_children(|parent| {
+            parent.spawn(Visit::home(visiting));
+        });
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Reflect, Serialize)]
+enum CallKind {
+    Pizza,
+    Invite(Entity),
+}
+
+impl MapEntities for CallKind {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        if let Self::Invite(entity) = self {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Call")),
+    Task,
+    Conversation,
+    CallTimer,
+    TaskGroups(|| TaskGroups::BOTH_HANDS),
+)]
+struct Call(CallKind);
+
+impl MapEntities for Call {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.0.map_entities(entity_mapper);
+    }
+}
+
+impl GroupTask for Call {}
+
+/// Time the actor spends on the phone.
+///
+/// Ticked only on server.
+#[derive(Component, Deref, DerefMut)]
+struct CallTimer(Timer);
+
+impl Default for CallTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(3.0, TimerMode::Once))
+    }
+}
+
+/// Task for a dispatched NPC to reach the caller.
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component)]
+#[require(Name(|| Name::new("Errand")), Task, TaskGroups(|| TaskGroups::LEGS))]
+struct Errand {
+    caller_entity: Entity,
+    kind: CallKind,
+}
+
+impl MapEntities for Errand {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.caller_entity = entity_mapper.map_entity(self.caller_entity);
+        self.kind.map_entities(entity_mapper);
+    }
+}
+
+impl GroupTask for Errand {}
@@ -0,0 +1,279 @@
+use bevy::{asset::AssetPath, ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ActiveTask, AvailableTasks, GroupTask, Task, TaskAppExt, TaskGroups, UsedObject};
+use crate::{
+    achievements::{Counter, CounterIncrease},
+    game_world::{
+        actor::{
+            animation_state::{AnimationState, Montage, MontageFinished},
+            needs::Need,
+            outfit::TaskOutfit,
+            Movement,
+        },
+        navigation::{following::Following, Navigation},
+    },
+    scripting::{ObjectScript, Script, ScriptCommand, ScriptEngine},
+};
+
+pub(super) struct ScriptedTaskPlugin;
+
+impl Plugin for ScriptedTaskPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_mapped_task::<ScriptedTask>()
+            .replicate::<ScriptAnimation>()
+            .replicate::<ScriptEffect>()
+            .add_observer(add_to_list)
+            .add_observer(init)
+            .add_observer(activate)
+            .add_observer(perform)
+            .add_observer(play_animation)
+            .add_observer(finish)
+            .add_observer(init_effect)
+            .add_systems(Update, despawn_effects.run_if(server_or_singleplayer));
+    }
+}
+
+fn add_to_list(
+    trigger: Trigger<OnAdd, AvailableTasks>,
+    mut commands: Commands,
+    scripts: Res<Assets<Script>>,
+    available_tasks: Single<&AvailableTasks>,
+    objects: Query<&ObjectScript>,
+) {
+    let object_entity = available_tasks.interaction_entity;
+    let Ok(object_script) = objects.get(object_entity) else {
+        return;
+    };
+    let Some(script) = scripts.get(&object_script.handle) else {
+        debug!("script for `{object_entity}` is not loaded");
+        return;
+    };
+
+    debug!("listing scripted tasks");
+    commands.entity(trigger.entity()).with_children(|parent| {
+        for index in 0..script.interactions.len() {
+            parent.spawn(ScriptedTask {
+                object_entity,
+                index,
+            });
+        }
+    });
+}
+
+/// Names the task after the interaction from the script.
+fn init(
+    trigger: Trigger<OnAdd, ScriptedTask>,
+    scripts: Res<Assets<Script>>,
+    objects: Query<&ObjectScript>,
+    mut tasks: Query<(&ScriptedTask, &mut Name)>,
+) {
+    let (task, mut name) = tasks.get_mut(trigger.entity()).unwrap();
+    let Some(interaction) = objects
+        .get(task.object_entity)
+        .ok()
+        .and_then(|object_script| scripts.get(&object_script.handle))
+        .and_then(|script| script.interactions.get(task.index))
+    else {
+        error!(
+            "unable to find interaction {} for `{}`",
+            task.index, task.object_entity
+        );
+        return;
+    };
+
+    *name = Name::new(interaction.clone());
+}
+
+fn activate(
+    trigger: Trigger<OnAdd, ActiveTask>,
+    mut commands: Commands,
+    mut actors: Query<&mut Navigation>,
+    tasks: Query<(&Parent, &ScriptedTask)>,
+) {
+    let Ok((parent, task)) = tasks.get(trigger.entity()) else {
+        return;
+    };
+
+    let mut navigation = actors
+        .get_mut(**parent)
+        .expect("actors should have navigation component");
+    *navigation = Navigation::new(Movement::Walk.speed()).with_offset(0.5);
+
+    commands
+        .entity(**parent)
+        .insert(Following(task.object_entity));
+}
+
+/// Runs the script when the actor reaches the object.
+///
+/// Following is removed only on server, so scripts are never executed on clients.
+fn perform(
+    trigger: Trigger<OnRemove, Following>,
+    mut commands: Commands,
+    engine: Res<ScriptEngine>,
+    scripts: Res<Assets<Script>>,
+    objects: Query<&ObjectScript>,
+    actors: Query<&Children>,
+    tasks: Query<(Entity, &Name, &ScriptedTask), With<ActiveTask>>,
+    mut needs: Query<(&Name, &mut Need)>,
+) {
+    let Ok(children) = actors.get(trigger.entity()) else {
+        return;
+    };
+    let Some((task_entity, name, task)) = tasks.iter_many(children).next() else {
+        return;
+    };
+    let Some(script) = objects
+        .get(task.object_entity)
+        .ok()
+        .and_then(|object_script| scripts.get(&object_script.handle))
+    else {
+        error!("script for `{}` is not loaded", task.object_entity);
+        commands.entity(task_entity).despawn();
+        return;
+    };
+
+    debug!("performing '{name}' for `{}`", trigger.entity());
+    let script_commands = match engine.perform(script, name.as_str()) {
+        Ok(script_commands) => script_commands,
+        Err(e) => {
+            error!("{e:#}");
+            commands.entity(task_entity).despawn();
+            return;
+        }
+    };
+
+    let mut animation = None;
+    for command in script_commands {
+        match command {
+            ScriptCommand::ChangeNeed {
+                name: need_name,
+                delta,
+            } => {
+                let mut iter = needs.iter_many_mut(children);
+                while let Some((name, mut need)) = iter.fetch_next() {
+                    if name.as_str() == need_name {
+                        need.0 = (need.0 + delta).clamp(0.0, 100.0);
+                    }
+                }
+                if need_name == "Hunger" && delta > 0.0 {
+                    commands.trigger(CounterIncrease(Counter::MealsEaten));
+                }
+            }
+            ScriptCommand::PlayAnimation(path) => animation = Some(path),
+            ScriptCommand::SpawnEffect { scene, duration } => {
+                commands.entity(task.object_entity).with_children(|parent| {
+                    parent.spawn((
+                        ScriptEffect(scene),
+                        EffectTimer(Timer::from_seconds(duration, TimerMode::Once)),
+                    ));
+                });
+            }
+            ScriptCommand::ChangeOutfit(kind) => {
+                commands.entity(task_entity).insert(TaskOutfit(kind));
+            }
+        }
+    }
+
+    match animation {
+        Some(path) => {
+            commands.entity(task_entity).insert((
+                ScriptAnimation(path),
+                UsedObject {
+                    object_entity: task.object_entity,
+                    ignition_scale: 1.0,
+                },
+            ));
+        }
+        None => commands.entity(task_entity).despawn(),
+    }
+}
+
+fn play_animation(
+    trigger: Trigger<OnAdd, ScriptAnimation>,
+    asset_server: Res<AssetServer>,
+    tasks: Query<(&Parent, &ScriptAnimation)>,
+    mut actors: Query<&mut AnimationState>,
+) {
+    let (parent, animation) = tasks.get(trigger.entity()).unwrap();
+    let mut animation_state = actors
+        .get_mut(**parent)
+        .expect("task parent should be an actor");
+
+    debug!("playing '{}' for `{}`", animation.0, **parent);
+    let montage = Montage::new(asset_server.load(animation.0.clone()));
+    animation_state.play_montage(montage);
+}
+
+fn finish(
+    trigger: Trigger<MontageFinished>,
+    mut commands: Commands,
+    children: Query<&Children>,
+    tasks: Query<Entity, (With<ScriptAnimation>, With<ActiveTask>)>,
+) {
+    let Ok(children) = children.get(trigger.entity()) else {
+        return;
+    };
+
+    if let Some(task_entity) = tasks.iter_many(children).next() {
+        commands.entity(task_entity).despawn();
+    }
+}
+
+fn init_effect(
+    trigger: Trigger<OnAdd, ScriptEffect>,
+    asset_server: Res<AssetServer>,
+    mut effects: Query<(&ScriptEffect, &mut SceneRoot)>,
+) {
+    let (effect, mut scene_root) = effects.get_mut(trigger.entity()).unwrap();
+    debug!("spawning effect '{}'", effect.0);
+    scene_root.0 = asset_server.load(effect.0.clone());
+}
+
+fn despawn_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut effects: Query<(Entity, &mut EffectTimer)>,
+) {
+    for (entity, mut timer) in &mut effects {
+        timer.tick(time.delta());
+        if timer.finished() {
+            debug!("despawning effect `{entity}`");
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Interaction with the specified index from the object script.
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component)]
+#[require(Task, TaskGroups(|| TaskGroups::LEGS | TaskGroups::BOTH_HANDS))]
+struct ScriptedTask {
+    object_entity: Entity,
+    index: usize,
+}
+
+impl MapEntities for ScriptedTask {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.object_entity = entity_mapper.map_entity(self.object_entity);
+    }
+}
+
+impl GroupTask for ScriptedTask {}
+
+/// Animation requested by the script.
+///
+/// Inserted on server after executing the script and the task finishes with the animation.
+#[derive(Component, Deserialize, Serialize)]
+struct ScriptAnimation(AssetPath<'static>);
+
+/// Scene spawned by the script on the object.
+#[derive(Component, Deserialize, Serialize)]
+#[require(ParentSync, Replicated, SceneRoot)]
+struct ScriptEffect(AssetPath<'static>);
+
+/// Remaining lifetime of [`ScriptEffect`], exists only on server.
+#[derive(Component, Deref, DerefMut)]
+struct EffectTimer(Timer);
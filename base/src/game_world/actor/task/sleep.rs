@@ -0,0 +1,151 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ActiveTask, AvailableTasks, GroupTask, Task, TaskAppExt, TaskGroups};
+use crate::{
+    core::GameState,
+    game_world::{
+        actor::{
+            needs::{Energy, Need, TaskNeedRate},
+            outfit::{OutfitKind, TaskOutfit},
+            Movement,
+        },
+        navigation::{NavDestination, Navigation},
+    },
+};
+
+pub(super) struct SleepPlugin;
+
+impl Plugin for SleepPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Bed>()
+            .add_mapped_task::<Sleep>()
+            .add_observer(add_to_list)
+            .add_observer(activate)
+            .add_systems(
+                Update,
+                sleep
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// How much [`Energy`] restores per second of sleep.
+const ENERGY_PER_SECOND: f32 = 1.5;
+
+fn add_to_list(
+    trigger: Trigger<OnAdd, AvailableTasks>,
+    mut commands: Commands,
+    available_tasks: Single<&AvailableTasks>,
+    beds: Query<(), With<Bed>>,
+) {
+    if beds.get(available_tasks.interaction_entity).is_err() {
+        return;
+    }
+
+    debug!("listing task");
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn(Sleep::new(
+            available_tasks.interaction_entity,
+            available_tasks.click_point,
+        ));
+    });
+}
+
+fn activate(
+    trigger: Trigger<OnAdd, ActiveTask>,
+    mut actors: Query<(&mut Navigation, &mut NavDestination)>,
+    tasks: Query<(&Parent, &Sleep)>,
+) {
+    let Ok((parent, sleep)) = tasks.get(trigger.entity()) else {
+        return;
+    };
+
+    debug!("walking to the bed");
+    let (mut navigation, mut dest) = actors
+        .get_mut(**parent)
+        .expect("actors should have navigation component");
+    *navigation = Navigation::new(Movement::Walk.speed()).with_offset(1.0);
+    **dest = Some(sleep.endpoint);
+}
+
+/// Changes into the sleep outfit after reaching the bed and restores [`Energy`] until it's full.
+///
+/// The everyday outfit is restored when the task is removed, including cancellation.
+fn sleep(
+    mut commands: Commands,
+    time: Res<Time>,
+    actors: Query<(&NavDestination, &Children)>,
+    tasks: Query<(Entity, &Parent, &Sleep, Has<TaskOutfit>), With<ActiveTask>>,
+    beds: Query<(), With<Bed>>,
+    mut needs: Query<&mut Need, With<Energy>>,
+) {
+    for (task_entity, parent, sleep, dressed) in &tasks {
+        let (dest, children) = actors
+            .get(**parent)
+            .expect("actors should have destination");
+        if dest.is_some() {
+            continue;
+        }
+        if beds.get(sleep.bed_entity).is_err() {
+            debug!("bed `{}` was removed", sleep.bed_entity);
+            commands.entity(task_entity).despawn();
+            continue;
+        }
+
+        if !dressed {
+            debug!("`{}` goes to sleep", **parent);
+            commands
+                .entity(task_entity)
+                .insert(TaskOutfit(OutfitKind::Sleep));
+        }
+
+        let Some(mut energy) = needs.iter_many_mut(children).fetch_next() else {
+            continue;
+        };
+        energy.0 = (energy.0 + ENERGY_PER_SECOND * time.delta_secs()).min(100.0);
+        if energy.0 >= 100.0 {
+            debug!("`{}` wakes up", **parent);
+            commands.entity(task_entity).despawn();
+        }
+    }
+}
+
+/// Object on which actors can sleep.
+///
+/// Inserted from object manifests.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub(crate) struct Bed;
+
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Sleep")),
+    Task,
+    TaskGroups(|| TaskGroups::LEGS | TaskGroups::BOTH_HANDS),
+    TaskNeedRate(|| TaskNeedRate { need_name: "Energy", rate: ENERGY_PER_SECOND }),
+)]
+pub(crate) struct Sleep {
+    bed_entity: Entity,
+    endpoint: Vec3,
+}
+
+impl Sleep {
+    pub(crate) fn new(bed_entity: Entity, endpoint: Vec3) -> Self {
+        Self {
+            bed_entity,
+            endpoint,
+        }
+    }
+}
+
+impl MapEntities for Sleep {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.bed_entity = entity_mapper.map_entity(self.bed_entity);
+    }
+}
+
+impl GroupTask for Sleep {}
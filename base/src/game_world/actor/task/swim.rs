@@ -0,0 +1,254 @@
+use bevy::{animation::RepeatAnimation, ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ActiveTask, AvailableTasks, GroupTask, Task, TaskAppExt, TaskGroups};
+use crate::{
+    asset::collection::Collection,
+    core::GameState,
+    game_world::{
+        actor::{
+            animation_state::{AnimationState, Montage},
+            needs::{Fun, Need, TaskNeedRate},
+            ActorAnimation, Movement, ACTOR_HEIGHT, ACTOR_RADIUS,
+        },
+        family::building::pool::Pool,
+        navigation::{NavDestination, Navigation},
+    },
+};
+
+pub(super) struct SwimPlugin;
+
+impl Plugin for SwimPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PoolLadder>()
+            .add_mapped_task::<Swim>()
+            .add_observer(add_to_list)
+            .add_observer(activate)
+            .add_observer(leave_water)
+            .add_observer(stop_animation)
+            .add_systems(
+                Update,
+                (swim.run_if(server_or_singleplayer), animate).run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// How much [`Fun`] restores per second of swimming.
+const FUN_PER_SECOND: f32 = 2.0;
+
+/// How deep the actor is submerged, only the head stays above the water.
+const SWIM_DEPTH: f32 = ACTOR_HEIGHT * 0.75;
+
+/// Maximum distance from a pool to a ladder that can be used to enter it.
+const LADDER_DISTANCE: f32 = 1.0;
+
+fn add_to_list(
+    trigger: Trigger<OnAdd, AvailableTasks>,
+    mut commands: Commands,
+    available_tasks: Single<&AvailableTasks>,
+    pools: Query<&Pool>,
+    ladders: Query<&Transform, With<PoolLadder>>,
+) {
+    let Ok(pool) = pools.get(available_tasks.interaction_entity) else {
+        return;
+    };
+
+    let swim_point = available_tasks.click_point.xz();
+    let Some(ladder_point) = ladders
+        .iter()
+        .map(|transform| transform.translation.xz())
+        .filter(|&point| point.distance(point.clamp(pool.0.min, pool.0.max)) <= LADDER_DISTANCE)
+        .min_by(|a, b| a.distance(swim_point).total_cmp(&b.distance(swim_point)))
+    else {
+        debug!("ignoring pool without a ladder");
+        return;
+    };
+
+    debug!("listing task");
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn(Swim {
+            pool_entity: available_tasks.interaction_entity,
+            edge_point: edge_point(pool.0, ladder_point),
+            swim_point,
+        });
+    });
+}
+
+/// Returns the closest point just outside the pool.
+///
+/// The point can be inside or outside the pool.
+fn edge_point(rect: Rect, point: Vec2) -> Vec2 {
+    let offset = ACTOR_RADIUS * 2.0;
+    let distances = [
+        (
+            point.x - rect.min.x,
+            Vec2::new(rect.min.x - offset, point.y),
+        ),
+        (
+            rect.max.x - point.x,
+            Vec2::new(rect.max.x + offset, point.y),
+        ),
+        (
+            point.y - rect.min.y,
+            Vec2::new(point.x, rect.min.y - offset),
+        ),
+        (
+            rect.max.y - point.y,
+            Vec2::new(point.x, rect.max.y + offset),
+        ),
+    ];
+
+    distances
+        .into_iter()
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, point)| point)
+        .unwrap()
+}
+
+fn activate(
+    trigger: Trigger<OnAdd, ActiveTask>,
+    mut actors: Query<(&mut Navigation, &mut NavDestination)>,
+    tasks: Query<(&Parent, &Swim)>,
+) {
+    let Ok((parent, swim)) = tasks.get(trigger.entity()) else {
+        return;
+    };
+
+    debug!("walking to the pool");
+    let (mut navigation, mut dest) = actors
+        .get_mut(**parent)
+        .expect("actors should have navigation component");
+    *navigation = Navigation::new(Movement::Walk.speed());
+    **dest = Some(Vec3::new(swim.edge_point.x, 0.0, swim.edge_point.y));
+}
+
+/// Jumps into the water after reaching the edge and restores [`Fun`] until it's full.
+fn swim(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut actors: Query<(&NavDestination, &mut Transform, &Children)>,
+    tasks: Query<(Entity, &Parent, &Swim), With<ActiveTask>>,
+    pools: Query<(), With<Pool>>,
+    mut needs: Query<&mut Need, With<Fun>>,
+) {
+    for (task_entity, parent, swim) in &tasks {
+        let (dest, mut transform, children) = actors
+            .get_mut(**parent)
+            .expect("actors should have destination");
+        if dest.is_some() {
+            continue;
+        }
+        if pools.get(swim.pool_entity).is_err() {
+            debug!("pool `{}` was removed", swim.pool_entity);
+            commands.entity(task_entity).despawn();
+            continue;
+        }
+
+        if transform.translation.y >= 0.0 {
+            debug!("`{}` jumps into the water", **parent);
+            transform.translation = Vec3::new(swim.swim_point.x, -SWIM_DEPTH, swim.swim_point.y);
+        }
+
+        let Some(mut fun) = needs.iter_many_mut(children).fetch_next() else {
+            continue;
+        };
+        fun.0 = (fun.0 + FUN_PER_SECOND * time.delta_secs()).min(100.0);
+        if fun.0 >= 100.0 {
+            debug!("`{}` finishes swimming", **parent);
+            commands.entity(task_entity).despawn();
+        }
+    }
+}
+
+/// Plays swimming animation while the actor is in the water.
+fn animate(
+    actor_animations: Res<Collection<ActorAnimation>>,
+    tasks: Query<&Parent, (With<Swim>, With<ActiveTask>)>,
+    mut actors: Query<(&Transform, &mut AnimationState)>,
+) {
+    for parent in &tasks {
+        let Ok((transform, mut animation_state)) = actors.get_mut(**parent) else {
+            continue;
+        };
+
+        if transform.translation.y < 0.0 && !animation_state.montage_active() {
+            debug!("playing swim animation for `{}`", **parent);
+            let montage = Montage::new(actor_animations.handle(ActorAnimation::Swim))
+                .with_repeat(RepeatAnimation::Forever);
+            animation_state.play_montage(montage);
+        }
+    }
+}
+
+fn stop_animation(
+    trigger: Trigger<OnRemove, Swim>,
+    tasks: Query<&Parent>,
+    mut actors: Query<&mut AnimationState>,
+) {
+    let Ok(parent) = tasks.get(trigger.entity()) else {
+        return;
+    };
+    let Ok(mut animation_state) = actors.get_mut(**parent) else {
+        return;
+    };
+
+    if animation_state.montage_active() {
+        debug!("stopping swim animation for `{}`", **parent);
+        animation_state.stop_montage();
+    }
+}
+
+/// Moves the actor out of the water when the task ends, including cancellation.
+fn leave_water(
+    trigger: Trigger<OnRemove, Swim>,
+    client: Res<RepliconClient>,
+    tasks: Query<(&Parent, &Swim)>,
+    mut actors: Query<&mut Transform>,
+) {
+    if !client.is_disconnected() {
+        return;
+    }
+    let Ok((parent, swim)) = tasks.get(trigger.entity()) else {
+        return;
+    };
+    let Ok(mut transform) = actors.get_mut(**parent) else {
+        return;
+    };
+
+    if transform.translation.y < 0.0 {
+        debug!("`{}` leaves the water", **parent);
+        transform.translation = Vec3::new(swim.edge_point.x, 0.0, swim.edge_point.y);
+    }
+}
+
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Swim")),
+    Task,
+    TaskGroups(|| TaskGroups::LEGS | TaskGroups::BOTH_HANDS),
+    TaskNeedRate(|| TaskNeedRate { need_name: "Fun", rate: FUN_PER_SECOND }),
+)]
+struct Swim {
+    pool_entity: Entity,
+    /// Point outside the pool to enter and leave the water.
+    edge_point: Vec2,
+    /// Point inside the pool where the actor swims.
+    swim_point: Vec2,
+}
+
+impl MapEntities for Swim {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.pool_entity = entity_mapper.map_entity(self.pool_entity);
+    }
+}
+
+impl GroupTask for Swim {}
+
+/// Object near a pool that allows actors to enter it.
+///
+/// Inserted from object manifests.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub(crate) struct PoolLadder;
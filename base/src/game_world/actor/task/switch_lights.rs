@@ -0,0 +1,131 @@
+use avian3d::prelude::*;
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ActiveTask, AvailableTasks, GroupTask, Task, TaskAppExt, TaskGroups};
+use crate::{
+    core::GameState,
+    game_world::{
+        actor::Movement,
+        navigation::{NavDestination, Navigation},
+        object::{
+            light::{self, LightOff, ObjectLight},
+            placing_object::PlacingObject,
+        },
+    },
+};
+
+pub(super) struct SwitchLightsPlugin;
+
+impl Plugin for SwitchLightsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_mapped_task::<SwitchLights>()
+            .add_observer(add_to_list)
+            .add_observer(activate)
+            .add_systems(
+                Update,
+                finish
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+fn add_to_list(
+    trigger: Trigger<OnAdd, AvailableTasks>,
+    mut commands: Commands,
+    available_tasks: Single<&AvailableTasks>,
+    lights: Query<Has<LightOff>, With<ObjectLight>>,
+) {
+    let Ok(off) = lights.get(available_tasks.interaction_entity) else {
+        return;
+    };
+
+    debug!("listing task");
+    let name = if off {
+        "Turn on lights"
+    } else {
+        "Turn off lights"
+    };
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn((
+            Name::new(name),
+            SwitchLights {
+                target_entity: available_tasks.interaction_entity,
+                endpoint: available_tasks.click_point,
+                on: off,
+            },
+        ));
+    });
+}
+
+fn activate(
+    trigger: Trigger<OnAdd, ActiveTask>,
+    mut actors: Query<(&mut Navigation, &mut NavDestination)>,
+    tasks: Query<(&Parent, &SwitchLights)>,
+) {
+    let Ok((parent, switch)) = tasks.get(trigger.entity()) else {
+        return;
+    };
+
+    debug!("walking to switch lights");
+    let (mut navigation, mut dest) = actors
+        .get_mut(**parent)
+        .expect("actors should have navigation component");
+    *navigation = Navigation::new(Movement::Walk.speed()).with_offset(1.0);
+    **dest = Some(switch.endpoint);
+}
+
+/// Switches all lights in the room when the actor reaches the target light.
+fn finish(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    actors: Query<&NavDestination>,
+    tasks: Query<(Entity, &Parent, &SwitchLights), With<ActiveTask>>,
+    lights: Query<(Entity, &GlobalTransform), (With<ObjectLight>, Without<PlacingObject>)>,
+) {
+    for (task_entity, parent, switch) in &tasks {
+        let dest = actors
+            .get(**parent)
+            .expect("actors should have always have destination");
+        if dest.is_some() {
+            continue;
+        }
+
+        info!(
+            "`{}` switches lights to `{}` near `{}`",
+            **parent, switch.on, switch.target_entity
+        );
+        for light_entity in light::room_lights(&spatial_query, &lights, switch.target_entity) {
+            if switch.on {
+                commands.entity(light_entity).remove::<LightOff>();
+            } else {
+                commands.entity(light_entity).insert(LightOff);
+            }
+        }
+        commands.entity(task_entity).despawn();
+    }
+}
+
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Switch lights")),
+    Task,
+    TaskGroups(|| TaskGroups::LEGS | TaskGroups::LEFT_HAND),
+)]
+struct SwitchLights {
+    target_entity: Entity,
+    endpoint: Vec3,
+    /// Turn lights on if `true` or off otherwise.
+    on: bool,
+}
+
+impl MapEntities for SwitchLights {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.target_entity = entity_mapper.map_entity(self.target_entity);
+    }
+}
+
+impl GroupTask for SwitchLights {}
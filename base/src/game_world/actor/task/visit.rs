@@ -0,0 +1,201 @@
+use bevy::{
+    ecs::{entity::MapEntities, reflect::ReflectMapEntities},
+    prelude::*,
+};
+use bevy_replicon::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{ActiveTask, AvailableTasks, GroupTask, Task, TaskAppExt, TaskGroups};
+use crate::{
+    core::GameState,
+    game_world::{
+        actor::SelectedActor,
+        city::{City, Ground, LotKind},
+        event_log::{EventCategory, GameEvent},
+    },
+};
+
+pub(super) struct VisitPlugin;
+
+impl Plugin for VisitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_mapped_task::<Visit>()
+            .register_type::<Visiting>()
+            .replicate_mapped::<Visiting>()
+            .add_observer(add_to_list)
+            .add_systems(
+                Update,
+                transfer
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// Radius around the city center in which visitors appear.
+const ARRIVAL_RADIUS: f32 = 3.0;
+
+fn add_to_list(
+    trigger: Trigger<OnAdd, AvailableTasks>,
+    mut commands: Commands,
+    available_tasks: Single<&AvailableTasks>,
+    selected_actor: Single<(&Parent, Option<&Visiting>), With<SelectedActor>>,
+    grounds: Query<(), With<Ground>>,
+    cities: Query<(Entity, &Name, &LotKind)>,
+) {
+    if grounds.get(available_tasks.interaction_entity).is_err() {
+        return;
+    }
+
+    let (actor_parent, visiting) = *selected_actor;
+    debug!("listing tasks");
+    commands.entity(trigger.entity()).with_children(|parent| {
+        for (city_entity, name, &lot_kind) in &cities {
+            if lot_kind == LotKind::Community && city_entity != **actor_parent {
+                parent.spawn((
+                    Name::new(format!("Visit {name}")),
+                    Visit {
+                        city_entity,
+                        return_home: false,
+                    },
+                ));
+            }
+        }
+
+        if let Some(visiting) = visiting {
+            parent.spawn((
+                Name::new("Return home"),
+                Visit {
+                    city_entity: visiting.home_entity,
+                    return_home: true,
+                },
+            ));
+        }
+    });
+}
+
+/// Moves the actor into the target city.
+///
+/// Remaining tasks are cancelled since they reference entities from the previous city.
+fn transfer(
+    mut commands: Commands,
+    tasks: Query<(Entity, &Parent, &Visit), With<ActiveTask>>,
+    mut actors: Query<(&Name, &Parent, &mut Transform, &Children, Option<&Visiting>)>,
+    cities: Query<&Name, With<City>>,
+    all_tasks: Query<(), With<Task>>,
+) {
+    let mut rng = rand::thread_rng();
+    for (task_entity, actor_entity, visit) in &tasks {
+        commands.entity(task_entity).despawn();
+
+        let Ok((name, city_entity, mut transform, children, visiting)) =
+            actors.get_mut(**actor_entity)
+        else {
+            continue;
+        };
+        // Grouped actors could live in different cities.
+        let target_entity = match (visit.return_home, visiting) {
+            (true, Some(visiting)) => visiting.home_entity,
+            (true, None) => continue,
+            (false, _) => visit.city_entity,
+        };
+        let Ok(city_name) = cities.get(target_entity) else {
+            debug!("ignoring visit to removed city `{target_entity}`");
+            continue;
+        };
+
+        if let (true, Some(&visiting)) = (visit.return_home, visiting) {
+            info!("`{}` returns home", **actor_entity);
+            transform.translation = visiting.home_position;
+            commands.entity(**actor_entity).remove::<Visiting>();
+            commands.spawn(GameEvent::new(
+                EventCategory::Family,
+                format!("{name} returned home"),
+            ));
+        } else {
+            if target_entity == **city_entity {
+                continue;
+            }
+            info!("`{}` visits `{target_entity}`", **actor_entity);
+            let home = visiting
+                .copied()
+                .unwrap_or(Visiting::new(**city_entity, transform.translation));
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let offset = rng.gen_range(0.0..ARRIVAL_RADIUS);
+            transform.translation = Vec3::new(angle.cos() * offset, 0.0, angle.sin() * offset);
+            commands.entity(**actor_entity).insert(home);
+            commands.spawn(GameEvent::new(
+                EventCategory::Family,
+                format!("{name} went to {city_name}"),
+            ));
+        }
+
+        for &child_entity in children {
+            if child_entity != task_entity && all_tasks.get(child_entity).is_ok() {
+                debug!("cancelling task `{child_entity}` after transfer");
+                commands.entity(child_entity).despawn_recursive();
+            }
+        }
+        commands.entity(**actor_entity).set_parent(target_entity);
+    }
+}
+
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component)]
+#[require(Name(|| Name::new("Visit")), Task, TaskGroups(|| TaskGroups::LEGS))]
+pub(super) struct Visit {
+    city_entity: Entity,
+    return_home: bool,
+}
+
+impl Visit {
+    /// Creates a task that returns the visiting actor to its home city.
+    pub(super) fn home(visiting: Visiting) -> Self {
+        Self {
+            city_entity: visiting.home_entity,
+            return_home: true,
+        }
+    }
+}
+
+impl MapEntities for Visit {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.city_entity = entity_mapper.map_entity(self.city_entity);
+    }
+}
+
+impl GroupTask for Visit {}
+
+/// Stores where the actor came from while it stays on a community lot.
+#[derive(Component, Reflect, Deserialize, Serialize, Clone, Copy)]
+#[reflect(Component, MapEntities)]
+pub(super) struct Visiting {
+    home_entity: Entity,
+    /// Position inside the home city.
+    home_position: Vec3,
+}
+
+impl Visiting {
+    pub(super) fn new(home_entity: Entity, home_position: Vec3) -> Self {
+        Self {
+            home_entity,
+            home_position,
+        }
+    }
+}
+
+impl FromWorld for Visiting {
+    fn from_world(_world: &mut World) -> Self {
+        Self {
+            home_entity: Entity::PLACEHOLDER,
+            home_position: Vec3::ZERO,
+        }
+    }
+}
+
+impl MapEntities for Visiting {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.home_entity = entity_mapper.map_entity(self.home_entity);
+    }
+}
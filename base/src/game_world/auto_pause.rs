@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+use super::{
+    actor::Actor,
+    family::SelectedFamily,
+    player_camera::{CameraFocus, PlayerCamera},
+    time_speed::{TimeSpeed, TimeSpeedRequest},
+    WorldState,
+};
+use crate::settings::Settings;
+
+pub(super) struct AutoPausePlugin;
+
+impl Plugin for AutoPausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(pause)
+            .add_systems(OnExit(WorldState::Family), resume);
+    }
+}
+
+/// Pauses the game in single player if the event happened with the selected family.
+fn pause(
+    trigger: Trigger<CriticalEvent>,
+    mut commands: Commands,
+    settings: Res<Settings>,
+    time_speed: Res<TimeSpeed>,
+    server: Res<RepliconServer>,
+    client: Res<RepliconClient>,
+    actors: Query<(&Actor, &Name, &Transform)>,
+    families: Query<(), With<SelectedFamily>>,
+    camera: Query<(), With<PlayerCamera>>,
+) {
+    if server.is_running() || !client.is_disconnected() {
+        return;
+    }
+    if !trigger.kind.enabled(&settings) {
+        return;
+    }
+    let Ok((actor, name, transform)) = actors.get(trigger.actor_entity) else {
+        return;
+    };
+    if families.get(actor.family_entity).is_err() {
+        return;
+    }
+
+    info!("pausing game due to `{:?}` for '{name}'", trigger.kind);
+    commands.client_trigger(TimeSpeedRequest(TimeSpeed::Paused));
+    if !camera.is_empty() {
+        commands.trigger(CameraFocus(transform.translation));
+    }
+    commands.trigger(AutoPaused {
+        message: trigger.kind.message(name),
+        resume_speed: *time_speed,
+    });
+}
+
+/// Resumes the game if it was left paused.
+fn resume(mut commands: Commands, time_speed: Res<TimeSpeed>) {
+    if *time_speed == TimeSpeed::Paused {
+        info!("resuming game on exit");
+        commands.client_trigger(TimeSpeedRequest(TimeSpeed::Normal));
+    }
+}
+
+/// An event that may require player's attention.
+///
+/// Triggered by gameplay systems.
+#[derive(Event)]
+pub struct CriticalEvent {
+    pub kind: CriticalEventKind,
+    pub actor_entity: Entity,
+}
+
+#[derive(Debug)]
+pub enum CriticalEventKind {
+    NeedDepleted { need_name: String },
+    Fire { object_name: String },
+}
+
+impl CriticalEventKind {
+    fn enabled(&self, settings: &Settings) -> bool {
+        match self {
+            CriticalEventKind::NeedDepleted { .. } => settings.gameplay.pause_on_depleted_need,
+            CriticalEventKind::Fire { .. } => settings.gameplay.pause_on_fire,
+        }
+    }
+
+    pub(crate) fn message(&self, actor_name: &str) -> String {
+        match self {
+            CriticalEventKind::NeedDepleted { need_name } => {
+                format!("{actor_name} urgently needs attention: {need_name} is depleted!")
+            }
+            CriticalEventKind::Fire { object_name } => {
+                format!("{object_name} caught fire near {actor_name}!")
+            }
+        }
+    }
+}
+
+/// Triggered after pausing the game due to [`CriticalEvent`].
+#[derive(Event, Deref)]
+pub struct AutoPaused {
+    #[deref]
+    message: String,
+    /// Speed that was active before the pause.
+    pub resume_speed: TimeSpeed,
+}
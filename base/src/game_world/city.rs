@@ -1,4 +1,7 @@
 pub mod road;
+pub mod season;
+pub mod time_lapse;
+pub mod weather;
 
 use std::f32::consts::FRAC_PI_2;
 
@@ -10,26 +13,44 @@ use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 use vleue_navigator::prelude::*;
 
-use super::{actor::SelectedActor, WorldState};
+use super::{actor::SelectedActor, stats::CityStats, WorldState};
 use crate::{
     core::GameState,
-    game_world::{actor::ACTOR_RADIUS, player_camera::PlayerCamera, Layer},
+    game_world::{
+        actor::ACTOR_RADIUS, navigation::nav_tiles::NavTiles, player_camera::PlayerCamera, Layer,
+    },
 };
 use road::RoadPlugin;
+use season::{Calendar, SeasonPlugin};
+use time_lapse::TimeLapsePlugin;
+use weather::{Weather, WeatherPlugin};
 
 pub(super) struct CityPlugin;
 
 impl Plugin for CityPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(RoadPlugin)
+        app.add_plugins((RoadPlugin, SeasonPlugin, TimeLapsePlugin, WeatherPlugin))
             .add_sub_state::<CityMode>()
             .enable_state_scoped_entities::<CityMode>()
             .register_type::<City>()
+            .register_type::<LotKind>()
+            .register_type::<Lot>()
             .replicate_group::<(City, Name)>()
+            .replicate::<LotKind>()
+            .replicate::<Lot>()
             .init_resource::<PlacedCities>()
             .add_observer(init)
             .add_observer(activate)
-            .add_systems(OnEnter(WorldState::Family), activate_by_actor)
+            .add_systems(
+                OnEnter(WorldState::Loading),
+                activate_by_actor.never_param_warn(),
+            )
+            .add_systems(
+                PreUpdate,
+                follow_actor
+                    .never_param_warn()
+                    .run_if(in_state(WorldState::Family)),
+            )
             .add_systems(OnExit(WorldState::City), deactivate.never_param_warn())
             .add_systems(OnExit(WorldState::Family), deactivate.never_param_warn())
             .add_systems(OnExit(GameState::InGame), cleanup);
@@ -79,7 +100,9 @@ fn init(
                     ..Default::default()
                 },
                 Transform::from_rotation(Quat::from_rotation_x(FRAC_PI_2)),
-                NavMeshUpdateMode::Direct,
+                // Further rebuilds are requested on dirty tiles.
+                NavMeshUpdateMode::OnDemand(true),
+                NavTiles::default(),
             ))
             .id();
     });
@@ -106,11 +129,38 @@ fn activate(
     });
 }
 
-fn activate_by_actor(mut commands: Commands, actor_parent: Single<&Parent, With<SelectedActor>>) {
+pub(super) fn activate_by_actor(
+    mut commands: Commands,
+    actor_parent: Single<&Parent, With<SelectedActor>>,
+) {
     info!("activating selected actor's city `{}`", ***actor_parent);
     commands.entity(***actor_parent).insert(ActiveCity);
 }
 
+/// Switches the active city when the selected actor moves to another city.
+fn follow_actor(
+    mut commands: Commands,
+    actor_parent: Single<&Parent, With<SelectedActor>>,
+    active_city: Single<(Entity, &mut Visibility), With<ActiveCity>>,
+    sun_entity: Single<Entity, With<Sun>>,
+    camera_entity: Single<Entity, With<PlayerCamera>>,
+) {
+    let (city_entity, mut visibility) = active_city.into_inner();
+    if ***actor_parent == city_entity {
+        return;
+    }
+
+    info!(
+        "switching active city from `{city_entity}` to `{}`",
+        ***actor_parent
+    );
+    *visibility = Visibility::Hidden;
+    commands.entity(city_entity).remove::<ActiveCity>();
+    commands.entity(*sun_entity).despawn();
+    commands.entity(*camera_entity).despawn();
+    commands.entity(***actor_parent).insert(ActiveCity);
+}
+
 fn deactivate(
     mut commands: Commands,
     active_city: Single<(Entity, &mut Visibility), With<ActiveCity>>,
@@ -160,6 +210,7 @@ pub enum CityMode {
     #[default]
     Objects,
     Roads,
+    TimeLapse,
 }
 
 impl CityMode {
@@ -167,6 +218,7 @@ impl CityMode {
         match self {
             Self::Objects => "🌳",
             Self::Roads => "🚧",
+            Self::TimeLapse => "🎞",
         }
     }
 }
@@ -178,6 +230,10 @@ impl CityMode {
     Replicated,
     Transform,
     Visibility(|| Visibility::Hidden),
+    CityStats,
+    Calendar,
+    Weather,
+    LotKind,
     CityNavMesh(|| CityNavMesh(Entity::PLACEHOLDER)),
     StateScoped<GameState>(|| StateScoped(GameState::InGame)),
 )]
@@ -187,6 +243,35 @@ pub struct City;
 #[require(City)]
 pub struct ActiveCity;
 
+/// Purpose of a city.
+///
+/// Applies to all lots of the city.
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, Eq, PartialEq, Reflect, Serialize,
+)]
+#[reflect(Component)]
+pub enum LotKind {
+    /// Families live here.
+    #[default]
+    Residential,
+    /// Public place that any actor can visit, like a park or a gym.
+    Community,
+}
+
+/// Fenced area inside a city where a family can live.
+///
+/// Owned by the family from [`FamilyOwner`](crate::game_world::family::FamilyOwner).
+/// Lots without an owner or whose owner no longer exists are free to move in.
+#[derive(Component, Clone, Copy, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+#[require(Name(|| Name::new("Lot")), ParentSync, Replicated)]
+pub(crate) struct Lot {
+    /// Area in city coordinates.
+    pub(crate) rect: Rect,
+    /// Point inside the lot right behind the gate.
+    pub(crate) entrance: Vec2,
+}
+
 /// Points to assigned navmesh for a city.
 #[derive(Component, Deref)]
 pub(super) struct CityNavMesh(Entity);
@@ -1,5 +1,6 @@
 pub mod placing_road;
 pub(crate) mod road_mesh;
+mod vehicle;
 
 use avian3d::prelude::*;
 use bevy::{asset::AssetPath, ecs::entity::MapEntities, prelude::*};
@@ -17,17 +18,19 @@ use crate::{
             CommandConfirmation, CommandId, CommandRequest, ConfirmableCommand, EntityRecorder,
             PendingCommand,
         },
+        players::Players,
         segment::{self, PointKind, Segment, SegmentConnections},
         Layer,
     },
 };
 use placing_road::PlacingRoadPlugin;
+use vehicle::VehiclePlugin;
 
 pub(super) struct RoadPlugin;
 
 impl Plugin for RoadPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(PlacingRoadPlugin)
+        app.add_plugins((PlacingRoadPlugin, VehiclePlugin))
             .add_sub_state::<RoadTool>()
             .enable_state_scoped_entities::<RoadTool>()
             .register_type::<Road>()
@@ -108,7 +111,13 @@ fn apply_command(
     trigger: Trigger<FromClient<CommandRequest<RoadCommand>>>,
     mut commands: Commands,
     mut roads: Query<&mut Segment, With<Road>>,
+    players: Res<Players>,
 ) {
+    if !players.can_build(trigger.client_id) {
+        error!("`{:?}` is not allowed to build", trigger.client_id);
+        return;
+    }
+
     // TODO: validate if command can be applied.
     let mut confirmation = CommandConfirmation::new(trigger.event.id);
     match &trigger.event.command {
@@ -181,15 +190,23 @@ impl RoadTool {
     Mesh3d,
     MeshMaterial3d::<StandardMaterial>,
     Collider,
-    CollisionLayers(|| CollisionLayers::new(Layer::Road, [Layer::Wall, Layer::PlacingWall])),
+    CollisionLayers(|| CollisionLayers::new(
+        Layer::Road,
+        [
+            Layer::Wall,
+            Layer::PlacingWall,
+            Layer::Fence,
+            Layer::PlacingFence,
+        ],
+    )),
 )]
-struct Road(AssetPath<'static>);
+pub(crate) struct Road(pub(crate) AssetPath<'static>);
 
 /// Stores road information needed at runtime from [`RoadManifest`].
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
-struct RoadData {
-    half_width: f32,
+pub(crate) struct RoadData {
+    pub(crate) half_width: f32,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -91,6 +91,7 @@ fn pick(
         parent.spawn((
             Ghost::new(entity),
             PlacingRoad::EditPoint { entity },
+            StateScoped(RoadTool::Move),
             RoadData {
                 half_width: manifest.half_width,
             },
@@ -152,6 +153,7 @@ fn spawn(
     commands.entity(*city_entity).with_children(|parent| {
         parent.spawn((
             PlacingRoad::Spawning(placing_id.0),
+            StateScoped(RoadTool::Create),
             RoadData {
                 half_width: manifest.half_width,
             },
@@ -254,7 +256,12 @@ pub struct SpawnRoadId(pub AssetId<RoadManifest>);
     Collider,
     CollisionLayers(|| CollisionLayers::new(
         Layer::PlacingRoad,
-        [Layer::Wall, Layer::PlacingWall],
+        [
+            Layer::Wall,
+            Layer::PlacingWall,
+            Layer::Fence,
+            Layer::PlacingFence,
+        ],
     )),
 )]
 enum PlacingRoad {
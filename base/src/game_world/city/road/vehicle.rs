@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use rand::{seq::SliceRandom, Rng};
+
+use super::{Road, RoadData};
+use crate::{
+    core::GameState,
+    game_world::{
+        city::ActiveCity,
+        segment::{PointKind, Segment, SegmentConnections},
+    },
+};
+
+/// Ambient vehicles that drive along roads of the active city.
+///
+/// Purely cosmetic, so vehicles are simulated locally and not replicated.
+pub(super) struct VehiclePlugin;
+
+impl Plugin for VehiclePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VehicleAssets>().add_systems(
+            Update,
+            (
+                populate
+                    .never_param_warn()
+                    .run_if(on_timer(Duration::from_secs(2))),
+                drive,
+            )
+                .run_if(in_state(GameState::InGame)),
+        );
+    }
+}
+
+/// Number of road segments per vehicle.
+const ROADS_PER_VEHICLE: usize = 2;
+
+const MAX_VEHICLES: usize = 30;
+
+/// Spawns vehicles on random roads until the city has enough of them.
+fn populate(
+    mut commands: Commands,
+    vehicle_assets: Res<VehicleAssets>,
+    city: Single<(Entity, &Children), With<ActiveCity>>,
+    roads: Query<Entity, With<Road>>,
+    vehicles: Query<(), With<Vehicle>>,
+) {
+    let (city_entity, children) = *city;
+    let city_roads: Vec<_> = roads.iter_many(children).collect();
+    let desired = (city_roads.len() / ROADS_PER_VEHICLE).min(MAX_VEHICLES);
+    let current = vehicles.iter_many(children).count();
+    if current >= desired {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let &road_entity = city_roads
+        .choose(&mut rng)
+        .expect("desired count should be zero without roads");
+    let from = if rng.gen() {
+        PointKind::Start
+    } else {
+        PointKind::End
+    };
+
+    debug!("spawning vehicle on `{road_entity}`");
+    let material = vehicle_assets
+        .materials
+        .choose(&mut rng)
+        .expect("vehicle materials shouldn't be empty")
+        .clone();
+    commands.entity(city_entity).with_children(|parent| {
+        parent.spawn((
+            Vehicle {
+                road_entity,
+                from,
+                distance: 0.0,
+            },
+            Mesh3d(vehicle_assets.mesh.clone()),
+            MeshMaterial3d(material),
+        ));
+    });
+}
+
+/// Moves vehicles along their roads and picks a random connected road at the end.
+///
+/// Turns around on dead ends.
+fn drive(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut vehicles: Query<(Entity, &mut Vehicle, &mut Transform)>,
+    roads: Query<(&Segment, &SegmentConnections, &RoadData), With<Road>>,
+) {
+    let mut rng = rand::thread_rng();
+    for (entity, mut vehicle, mut transform) in &mut vehicles {
+        let Ok((&segment, connections, road_data)) = roads.get(vehicle.road_entity) else {
+            debug!("despawning vehicle `{entity}` without road");
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        vehicle.distance += VEHICLE_SPEED * time.delta_secs();
+        if vehicle.distance >= segment.len() {
+            vehicle.distance -= segment.len();
+            let to = vehicle.from.inverse();
+            let next_roads: Vec<_> = connections
+                .connected(to)
+                .filter(|&(road_entity, _)| roads.get(road_entity).is_ok())
+                .collect();
+            if let Some(&(road_entity, point_kind)) = next_roads.choose(&mut rng) {
+                vehicle.road_entity = road_entity;
+                vehicle.from = point_kind;
+            } else {
+                vehicle.from = to;
+            }
+            continue;
+        }
+
+        let start = segment.point(vehicle.from);
+        let end = segment.point(vehicle.from.inverse());
+        let Some(direction) = (end - start).try_normalize() else {
+            continue;
+        };
+
+        // Drive on the right side of the road.
+        let lane_offset = -direction.perp() * road_data.half_width / 2.0;
+        let position = start + direction * vehicle.distance + lane_offset;
+        transform.translation = Vec3::new(position.x, VEHICLE_SIZE.y / 2.0, position.y);
+        transform.rotation = Quat::from_rotation_y(-direction.to_angle());
+    }
+}
+
+const VEHICLE_SPEED: f32 = 8.0;
+
+/// Length, height and width of a vehicle.
+const VEHICLE_SIZE: Vec3 = Vec3::new(4.0, 1.4, 1.8);
+
+#[derive(Resource)]
+struct VehicleAssets {
+    mesh: Handle<Mesh>,
+    materials: Vec<Handle<StandardMaterial>>,
+}
+
+impl FromWorld for VehicleAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Cuboid::from_size(VEHICLE_SIZE));
+
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let materials = [
+            Color::srgb(0.7, 0.1, 0.1),
+            Color::srgb(0.1, 0.3, 0.7),
+            Color::srgb(0.9, 0.9, 0.9),
+            Color::srgb(0.1, 0.1, 0.1),
+            Color::srgb(0.8, 0.7, 0.2),
+        ]
+        .into_iter()
+        .map(|color| materials.add(color))
+        .collect();
+
+        Self { mesh, materials }
+    }
+}
+
+/// Drives along [`Self::road_entity`] from the point [`Self::from`].
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Vehicle")),
+    Mesh3d,
+    MeshMaterial3d::<StandardMaterial>,
+    StateScoped::<GameState>(|| StateScoped(GameState::InGame)),
+)]
+struct Vehicle {
+    road_entity: Entity,
+    from: PointKind,
+    /// Distance passed along the current road.
+    distance: f32,
+}
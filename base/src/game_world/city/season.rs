@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use bevy::{pbr::light_consts::lux, prelude::*, time::common_conditions::on_timer};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{weather::Weather, ActiveCity, City, Ground, Sun};
+use crate::{core::GameState, game_world::SimulationSet};
+
+/// Advances days in cities and applies the visuals of the current season to the active city.
+pub(super) struct SeasonPlugin;
+
+impl Plugin for SeasonPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Calendar>()
+            .replicate::<Calendar>()
+            .add_systems(
+                FixedUpdate,
+                advance
+                    .run_if(on_timer(DAY_DURATION))
+                    .in_set(SimulationSet::Clock),
+            )
+            .add_systems(
+                Update,
+                (tint_ground, update_sun)
+                    .never_param_warn()
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// Game time of a single day.
+const DAY_DURATION: Duration = Duration::from_secs(120);
+
+/// Game time of a single hour.
+pub(crate) const HOUR_DURATION: Duration = Duration::from_secs(DAY_DURATION.as_secs() / 24);
+
+const DAYS_PER_SEASON: u32 = 7;
+
+fn advance(mut cities: Query<(&Name, &mut Calendar), With<City>>) {
+    for (name, mut calendar) in &mut cities {
+        let season = calendar.season();
+        calendar.day += 1;
+        if calendar.season() != season {
+            info!("'{name}' enters {:?}", calendar.season());
+        }
+    }
+}
+
+/// Sets the ground color of the active city to match its season.
+///
+/// The ground material is shared between cities, but only the active city is visible.
+fn tint_ground(
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    city: Single<(&Calendar, &Children), With<ActiveCity>>,
+    grounds: Query<&MeshMaterial3d<StandardMaterial>, With<Ground>>,
+) {
+    let (calendar, children) = *city;
+    let tint = calendar.season().ground_tint();
+    for material_handle in grounds.iter_many(children) {
+        // Check before mutating to avoid marking the material as modified every frame.
+        let Some(material) = materials.get(material_handle) else {
+            continue;
+        };
+        if material.base_color != tint {
+            debug!("tinting ground to `{tint:?}`");
+            materials.get_mut(material_handle).unwrap().base_color = tint;
+        }
+    }
+}
+
+fn update_sun(
+    city: Single<(&Calendar, &Weather), With<ActiveCity>>,
+    sun: Single<(&mut DirectionalLight, &mut Transform), With<Sun>>,
+) {
+    let (calendar, weather) = *city;
+    let (mut light, mut transform) = sun.into_inner();
+    let season = calendar.season();
+    let illuminance = season.sun_illuminance() * weather.sun_factor();
+    if light.illuminance != illuminance {
+        debug!("updating sun for {season:?} with {weather:?} weather");
+        light.illuminance = illuminance;
+        *transform =
+            Transform::from_xyz(4.0, season.sun_height(), 5.0).looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}
+
+/// Days passed in a city.
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct Calendar {
+    pub day: u32,
+}
+
+impl Calendar {
+    pub fn season(&self) -> Season {
+        match (self.day / DAYS_PER_SEASON) % 4 {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    /// Returns the day number inside the current season starting from 1.
+    pub fn day_of_season(&self) -> u32 {
+        self.day % DAYS_PER_SEASON + 1
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Season::Spring => "🌱",
+            Season::Summer => "☀",
+            Season::Autumn => "🍂",
+            Season::Winter => "❄",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+
+    fn ground_tint(self) -> Color {
+        match self {
+            Season::Spring => Color::WHITE,
+            Season::Summer => Color::srgb(1.0, 0.95, 0.75),
+            Season::Autumn => Color::srgb(0.9, 0.7, 0.45),
+            Season::Winter => Color::srgb(0.85, 0.9, 1.0),
+        }
+    }
+
+    /// Shorter days in winter are represented by a lower and weaker sun.
+    fn sun_height(self) -> f32 {
+        match self {
+            Season::Spring => 7.0,
+            Season::Summer => 9.0,
+            Season::Autumn => 5.0,
+            Season::Winter => 3.0,
+        }
+    }
+
+    fn sun_illuminance(self) -> f32 {
+        match self {
+            Season::Spring => lux::AMBIENT_DAYLIGHT,
+            Season::Summer => lux::AMBIENT_DAYLIGHT * 1.2,
+            Season::Autumn => lux::AMBIENT_DAYLIGHT * 0.8,
+            Season::Winter => lux::AMBIENT_DAYLIGHT * 0.6,
+        }
+    }
+
+    /// Chance of rain or snow each time the weather changes.
+    pub(super) fn precipitation_chance(self) -> f64 {
+        match self {
+            Season::Spring => 0.3,
+            Season::Summer => 0.15,
+            Season::Autumn => 0.4,
+            Season::Winter => 0.35,
+        }
+    }
+}
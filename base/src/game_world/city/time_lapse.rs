@@ -0,0 +1,374 @@
+use std::{fs, time::Duration};
+
+use anyhow::{Context, Result};
+use bevy::{asset::AssetPath, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{road::Road, ActiveCity, City, CityMode};
+use crate::{
+    asset::manifest::{object_manifest::ObjectManifest, road_manifest::RoadManifest},
+    core::GameState,
+    error_message::error_message,
+    game_paths::{write_atomic, GamePaths},
+    game_world::{
+        family::building::wall::{wall_mesh, Wall, WallMaterial},
+        object::Object,
+        segment::Segment,
+        GameLoad, GameSave, WorldName,
+    },
+    settings::Settings,
+};
+
+/// Records snapshots of cities into a [`Timeline`] and plays them back in [`CityMode::TimeLapse`].
+///
+/// Recording happens only on server or in singleplayer, clients have an empty timeline.
+pub(super) struct TimeLapsePlugin;
+
+impl Plugin for TimeLapsePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(save.pipe(error_message))
+            .add_observer(load.pipe(error_message))
+            .add_systems(OnEnter(GameState::InGame), init)
+            .add_systems(OnEnter(CityMode::TimeLapse), start_playback)
+            .add_systems(OnExit(CityMode::TimeLapse), stop_playback)
+            .add_systems(
+                Update,
+                (
+                    record
+                        .run_if(server_or_singleplayer)
+                        .run_if(in_state(GameState::InGame)),
+                    (
+                        advance,
+                        show_frame
+                            .never_param_warn()
+                            .run_if(resource_exists_and_changed::<TimelinePlayback>),
+                    )
+                        .chain()
+                        .run_if(in_state(CityMode::TimeLapse)),
+                ),
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup);
+    }
+}
+
+fn save(
+    _trigger: Trigger<GameSave>,
+    world_name: Res<WorldName>,
+    game_paths: Res<GamePaths>,
+    timeline: Res<Timeline>,
+) -> Result<()> {
+    let timeline_path = game_paths.timeline_path(&world_name.0);
+    info!("saving timeline to {timeline_path:?}");
+
+    let bytes = bincode::serialize(&*timeline).expect("timeline should be serialized");
+    write_atomic(&timeline_path, bytes)
+        .with_context(|| format!("unable to save timeline to {timeline_path:?}"))
+}
+
+fn load(
+    _trigger: Trigger<GameLoad>,
+    mut commands: Commands,
+    world_name: Res<WorldName>,
+    game_paths: Res<GamePaths>,
+) -> Result<()> {
+    let timeline_path = game_paths.timeline_path(&world_name.0);
+    if !timeline_path.exists() {
+        debug!("{timeline_path:?} is missing, starting a new timeline");
+        commands.insert_resource(Timeline::default());
+        return Ok(());
+    }
+
+    info!("loading timeline from {timeline_path:?}");
+    let bytes =
+        fs::read(&timeline_path).with_context(|| format!("unable to load {timeline_path:?}"))?;
+    let timeline = bincode::deserialize(&bytes)
+        .with_context(|| format!("unable to deserialize {timeline_path:?}"))?;
+    commands.insert_resource::<Timeline>(timeline);
+
+    Ok(())
+}
+
+/// Creates an empty timeline if it wasn't loaded.
+fn init(mut commands: Commands) {
+    commands.init_resource::<Timeline>();
+}
+
+fn record(
+    mut elapsed: Local<Duration>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut timeline: ResMut<Timeline>,
+    cities: Query<(&Name, Option<&Children>), With<City>>,
+    roads: Query<(&Road, &Segment)>,
+    walls: Query<&Segment, With<Wall>>,
+    objects: Query<(&Object, &Transform)>,
+) {
+    let interval = settings.gameplay.time_lapse_interval;
+    if interval <= 0.0 {
+        return;
+    }
+
+    *elapsed += time.delta();
+    if elapsed.as_secs_f32() < interval {
+        return;
+    }
+    *elapsed = Duration::ZERO;
+
+    let mut frame = TimelineFrame::default();
+    for (name, children) in &cities {
+        let mut snapshot = CitySnapshot {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        for &child_entity in children.into_iter().flatten() {
+            if let Ok((road, &segment)) = roads.get(child_entity) {
+                snapshot.roads.push(RoadSnapshot {
+                    manifest_path: road.0.clone(),
+                    segment,
+                });
+            } else if let Ok(&segment) = walls.get(child_entity) {
+                snapshot.walls.push(segment);
+            } else if let Ok((object, transform)) = objects.get(child_entity) {
+                snapshot.objects.push(ObjectSnapshot {
+                    manifest_path: object.0.clone(),
+                    translation: transform.translation,
+                    rotation: transform.rotation,
+                });
+            }
+        }
+        frame.cities.push(snapshot);
+    }
+
+    if timeline.frames.last() == Some(&frame) {
+        trace!("skipping unchanged timeline frame");
+        return;
+    }
+
+    debug!("recording timeline frame {}", timeline.frames.len());
+    timeline.frames.push(frame);
+    if timeline.frames.len() > MAX_FRAMES {
+        debug!("thinning out timeline frames");
+        let mut index = 0;
+        timeline.frames.retain(|_| {
+            index += 1;
+            index % 2 == 0
+        });
+    }
+}
+
+fn start_playback(
+    mut commands: Commands,
+    timeline: Res<Timeline>,
+    mut recorded: Query<&mut Visibility, Or<(With<Road>, With<Wall>, With<Object>)>>,
+) {
+    info!("starting time-lapse playback");
+    commands.insert_resource(TimelinePlayback {
+        frame: timeline.len().saturating_sub(1),
+        playing: false,
+    });
+
+    for mut visibility in &mut recorded {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn stop_playback(
+    mut commands: Commands,
+    mut recorded: Query<&mut Visibility, Or<(With<Road>, With<Wall>, With<Object>)>>,
+) {
+    info!("stopping time-lapse playback");
+    commands.remove_resource::<TimelinePlayback>();
+
+    for mut visibility in &mut recorded {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn advance(
+    mut elapsed: Local<Duration>,
+    time: Res<Time<Real>>,
+    timeline: Res<Timeline>,
+    mut playback: ResMut<TimelinePlayback>,
+) {
+    if !playback.playing {
+        return;
+    }
+
+    *elapsed += time.delta();
+    if *elapsed < FRAME_DURATION {
+        return;
+    }
+    *elapsed = Duration::ZERO;
+
+    if playback.frame + 1 < timeline.len() {
+        playback.frame += 1;
+    } else {
+        debug!("reached the end of the timeline");
+        playback.playing = false;
+    }
+}
+
+/// Replaces displayed proxies with the ones from the current frame.
+fn show_frame(
+    proxy_mesh: Local<ProxyMesh>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    wall_material: Res<WallMaterial>,
+    road_manifests: Res<Assets<RoadManifest>>,
+    object_manifests: Res<Assets<ObjectManifest>>,
+    timeline: Res<Timeline>,
+    playback: Res<TimelinePlayback>,
+    proxies: Query<Entity, With<PlaybackProxy>>,
+    active_city: Single<(Entity, &Name), With<ActiveCity>>,
+) {
+    for proxy_entity in &proxies {
+        commands.entity(proxy_entity).despawn_recursive();
+    }
+
+    let (city_entity, city_name) = active_city.into_inner();
+    let Some(snapshot) = timeline
+        .frames
+        .get(playback.frame)
+        .and_then(|frame| frame.cities.iter().find(|city| city.name == **city_name))
+    else {
+        debug!("no recorded frame {} for `{city_entity}`", playback.frame);
+        return;
+    };
+
+    debug!("showing frame {} for `{city_entity}`", playback.frame);
+    commands.entity(city_entity).with_children(|parent| {
+        for road in &snapshot.roads {
+            let Some(manifest) = asset_server
+                .get_handle(&road.manifest_path)
+                .and_then(|handle| road_manifests.get(&handle))
+            else {
+                error!("'{}' is missing, ignoring", road.manifest_path);
+                continue;
+            };
+
+            parent.spawn((
+                PlaybackProxy,
+                Mesh3d(proxy_mesh.0.clone()),
+                MeshMaterial3d::<StandardMaterial>(asset_server.load(manifest.material.clone())),
+                segment_transform(road.segment, ROAD_HEIGHT, manifest.half_width * 2.0),
+            ));
+        }
+
+        for &segment in &snapshot.walls {
+            parent.spawn((
+                PlaybackProxy,
+                Mesh3d(proxy_mesh.0.clone()),
+                wall_material.0.clone(),
+                segment_transform(segment, wall_mesh::HEIGHT, wall_mesh::HALF_WIDTH * 2.0),
+            ));
+        }
+
+        for object in &snapshot.objects {
+            let Some(manifest) = asset_server
+                .get_handle(&object.manifest_path)
+                .and_then(|handle| object_manifests.get(&handle))
+            else {
+                error!("'{}' is missing, ignoring", object.manifest_path);
+                continue;
+            };
+
+            parent.spawn((
+                PlaybackProxy,
+                SceneRoot(asset_server.load(manifest.scene.clone())),
+                Transform::from_translation(object.translation).with_rotation(object.rotation),
+            ));
+        }
+    });
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<Timeline>();
+}
+
+/// Returns transform for [`ProxyMesh`] to cover the segment.
+fn segment_transform(segment: Segment, height: f32, width: f32) -> Transform {
+    let center = (segment.start + segment.end) / 2.0;
+    Transform::from_xyz(center.x, height / 2.0, center.y)
+        .with_rotation(Quat::from_rotation_y(-segment.displacement().to_angle()))
+        .with_scale(Vec3::new(segment.len(), height, width))
+}
+
+/// Maximum number of stored frames.
+///
+/// When exceeded, every second frame is removed to keep the file compact.
+const MAX_FRAMES: usize = 1000;
+
+/// Duration of a single frame during playback.
+const FRAME_DURATION: Duration = Duration::from_millis(500);
+
+const ROAD_HEIGHT: f32 = 0.001;
+
+/// Recorded history of all cities.
+///
+/// Saved next to the world file.
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct Timeline {
+    frames: Vec<TimelineFrame>,
+}
+
+impl Timeline {
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+#[derive(Default, PartialEq, Serialize, Deserialize)]
+struct TimelineFrame {
+    cities: Vec<CitySnapshot>,
+}
+
+/// Cities are matched by their names since entities are not preserved between sessions.
+#[derive(Default, PartialEq, Serialize, Deserialize)]
+struct CitySnapshot {
+    name: String,
+    roads: Vec<RoadSnapshot>,
+    walls: Vec<Segment>,
+    objects: Vec<ObjectSnapshot>,
+}
+
+#[derive(PartialEq, Serialize, Deserialize)]
+struct RoadSnapshot {
+    manifest_path: AssetPath<'static>,
+    segment: Segment,
+}
+
+#[derive(PartialEq, Serialize, Deserialize)]
+struct ObjectSnapshot {
+    manifest_path: AssetPath<'static>,
+    translation: Vec3,
+    rotation: Quat,
+}
+
+/// Current playback position, exists only in [`CityMode::TimeLapse`].
+///
+/// Modify it to scrub through the [`Timeline`].
+#[derive(Resource)]
+pub struct TimelinePlayback {
+    pub frame: usize,
+    pub playing: bool,
+}
+
+/// Simplified visual representation of a recorded entity.
+#[derive(Component)]
+#[require(StateScoped<CityMode>(|| StateScoped(CityMode::TimeLapse)))]
+struct PlaybackProxy;
+
+/// Unit cube that stretched to represent segments.
+struct ProxyMesh(Handle<Mesh>);
+
+impl FromWorld for ProxyMesh {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        Self(meshes.add(Cuboid::default()))
+    }
+}
@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use bevy_replicon::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    season::{Calendar, Season, HOUR_DURATION},
+    City,
+};
+use crate::game_world::SimulationSet;
+
+/// Randomly changes weather in cities depending on their season.
+pub(super) struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Weather>()
+            .replicate::<Weather>()
+            .add_systems(
+                FixedUpdate,
+                change
+                    .run_if(on_timer(WEATHER_DURATION))
+                    .in_set(SimulationSet::Clock),
+            );
+    }
+}
+
+/// How long the weather lasts before it can change.
+const WEATHER_DURATION: Duration = Duration::from_secs(HOUR_DURATION.as_secs() * 4);
+
+fn change(mut cities: Query<(&Name, &Calendar, &mut Weather), With<City>>) {
+    let mut rng = rand::thread_rng();
+    for (name, calendar, mut weather) in &mut cities {
+        let season = calendar.season();
+        let new_weather = if rng.gen_bool(season.precipitation_chance()) {
+            if season == Season::Winter {
+                Weather::Snow
+            } else {
+                Weather::Rain
+            }
+        } else {
+            Weather::Clear
+        };
+
+        if *weather != new_weather {
+            info!("changing weather in '{name}' to `{new_weather:?}`");
+            *weather = new_weather;
+        }
+    }
+}
+
+/// Current weather in a city.
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, Eq, PartialEq, Reflect, Serialize,
+)]
+#[reflect(Component)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Rain,
+    Snow,
+}
+
+impl Weather {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Weather::Clear => "🌤",
+            Weather::Rain => "🌧",
+            Weather::Snow => "🌨",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Weather::Clear => "Clear",
+            Weather::Rain => "Rain",
+            Weather::Snow => "Snow",
+        }
+    }
+
+    /// Returns `true` if actors get wet outside.
+    pub fn is_precipitation(self) -> bool {
+        self != Weather::Clear
+    }
+
+    /// Clouds block part of the sunlight.
+    pub(super) fn sun_factor(self) -> f32 {
+        match self {
+            Weather::Clear => 1.0,
+            Weather::Rain => 0.5,
+            Weather::Snow => 0.7,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+/// Tracks the current cursor tool state.
+///
+/// Displaying the actual cursor icon is up to the application.
+pub(super) struct CursorModePlugin;
+
+impl Plugin for CursorModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CursorMode>()
+            .add_observer(start_placing)
+            .add_observer(finish_placing);
+    }
+}
+
+fn start_placing(_trigger: Trigger<OnAdd, PlacingCursor>, mut cursor_mode: ResMut<CursorMode>) {
+    debug!("switching cursor to placing");
+    *cursor_mode = CursorMode::Placing;
+}
+
+fn finish_placing(
+    _trigger: Trigger<OnRemove, PlacingCursor>,
+    mut cursor_mode: ResMut<CursorMode>,
+    placing: Query<(), With<PlacingCursor>>,
+) {
+    // The removed component is still present.
+    if placing.iter().count() <= 1 {
+        debug!("resetting cursor after placing");
+        *cursor_mode = CursorMode::Default;
+    }
+}
+
+/// Current cursor tool state.
+///
+/// Can be set by any plugin, but entities with [`PlacingCursor`] have priority.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub enum CursorMode {
+    #[default]
+    Default,
+    /// Something is attached to the cursor, like an object or a wall.
+    Placing,
+    /// Hovering an entity that can be picked.
+    Interact,
+}
+
+/// Switches [`CursorMode`] to [`CursorMode::Placing`] while any entity with this component exists.
+#[derive(Component, Default)]
+pub(crate) struct PlacingCursor;
@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+use crate::core::GameState;
+
+pub(super) struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Difficulty>()
+            .replicate::<Difficulty>()
+            .add_systems(OnExit(GameState::InGame), cleanup);
+    }
+}
+
+fn cleanup(mut commands: Commands, difficulties: Query<Entity, With<Difficulty>>) {
+    for entity in &difficulties {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// World-level simulation pressure chosen at world creation.
+///
+/// Spawned as a standalone entity to be saved and replicated with the world.
+/// Worlds saved without it are treated as [`Difficulty::Normal`].
+#[derive(
+    Component,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Display,
+    EnumIter,
+    Eq,
+    PartialEq,
+    Reflect,
+    Serialize,
+)]
+#[reflect(Component)]
+#[require(Name(|| Name::new("Difficulty")), Replicated)]
+pub enum Difficulty {
+    Relaxed,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Multiplier for need decay rates.
+    pub(crate) fn need_scale(self) -> f32 {
+        match self {
+            Self::Relaxed => 0.5,
+            Self::Normal => 1.0,
+            Self::Hard => 1.5,
+        }
+    }
+
+    /// Multiplier for prices of services that families pay for.
+    pub(crate) fn cost_scale(self) -> f32 {
+        match self {
+            Self::Relaxed => 0.75,
+            Self::Normal => 1.0,
+            Self::Hard => 1.25,
+        }
+    }
+
+    pub(crate) fn scale_cost(self, cost: u32) -> u32 {
+        (cost as f32 * self.cost_scale()).round() as u32
+    }
+}
@@ -0,0 +1,124 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+use super::{
+    actor::Actor,
+    auto_pause::{CriticalEvent, CriticalEventKind},
+};
+use crate::core::GameState;
+
+/// Records notable world events as replicated entities.
+///
+/// Since they are regular replicated entities, they are saved together with the world.
+pub(super) struct EventLogPlugin;
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<GameEvent>()
+            .replicate::<GameEvent>()
+            .add_observer(log_critical)
+            .add_systems(
+                PostUpdate,
+                trim.run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// Maximum number of stored events, older events are removed first.
+const MAX_EVENTS: usize = 200;
+
+fn log_critical(
+    trigger: Trigger<CriticalEvent>,
+    mut commands: Commands,
+    actors: Query<&Name, With<Actor>>,
+) {
+    let Ok(name) = actors.get(trigger.actor_entity) else {
+        return;
+    };
+
+    let category = match trigger.kind {
+        CriticalEventKind::NeedDepleted { .. } => EventCategory::Needs,
+        CriticalEventKind::Fire { .. } => EventCategory::Hazards,
+    };
+    commands.spawn(GameEvent::new(category, trigger.kind.message(name)));
+}
+
+fn trim(mut commands: Commands, events: Query<(Entity, &GameEvent)>) {
+    let excess = events.iter().len().saturating_sub(MAX_EVENTS);
+    if excess == 0 {
+        return;
+    }
+
+    debug!("removing {excess} old events");
+    let mut events: Vec<_> = events.iter().collect();
+    events.sort_by_key(|(entity, event)| (event.timestamp, *entity));
+    for (entity, _) in events.into_iter().take(excess) {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// A single log entry.
+///
+/// Should be spawned only on server or in singleplayer.
+#[derive(Component, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+#[require(Replicated, StateScoped<GameState>(|| StateScoped(GameState::InGame)))]
+pub struct GameEvent {
+    pub category: EventCategory,
+    pub message: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl GameEvent {
+    pub fn new(category: EventCategory, message: impl Into<String>) -> Self {
+        let message = message.into();
+        info!("logging `{category:?}` event: {message}");
+        Self {
+            category,
+            message,
+            timestamp: unix_timestamp(),
+        }
+    }
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, EnumIter, Eq, PartialEq, Reflect, Serialize)]
+pub enum EventCategory {
+    Family,
+    Needs,
+    Social,
+    Hazards,
+}
+
+impl EventCategory {
+    pub fn name(self) -> &'static str {
+        match self {
+            EventCategory::Family => "Family",
+            EventCategory::Needs => "Needs",
+            EventCategory::Social => "Social",
+            EventCategory::Hazards => "Hazards",
+        }
+    }
+
+    pub fn glyph(self) -> &'static str {
+        match self {
+            EventCategory::Family => "👪",
+            EventCategory::Needs => "❗",
+            EventCategory::Social => "💬",
+            EventCategory::Hazards => "🔥",
+        }
+    }
+}
@@ -1,12 +1,24 @@
 pub mod building;
 pub mod editor;
+pub mod gallery;
+pub mod npc;
 
-use std::{io::Cursor, mem};
+use std::{
+    collections::VecDeque,
+    io::{self, Cursor, Read, Write},
+    mem,
+};
 
 use bevy::{
-    ecs::reflect::ReflectCommandExt,
+    ecs::{
+        entity::MapEntities,
+        reflect::{ReflectCommandExt, ReflectMapEntities},
+    },
     prelude::*,
-    reflect::serde::{ReflectDeserializer, ReflectSerializer},
+    reflect::{
+        serde::{ReflectDeserializer, ReflectSerializer},
+        TypeRegistry,
+    },
 };
 use bevy_replicon::{
     core::event::ctx::{ClientSendCtx, ServerReceiveCtx},
@@ -17,23 +29,38 @@ use serde::{de::DeserializeSeed, Deserialize, Serialize};
 use strum::EnumIter;
 
 use super::{
-    actor::{Actor, SelectedActor},
+    actor::{Actor, GroupedActor, SelectedActor},
+    city::Lot,
+    event_log::{EventCategory, GameEvent},
+    play_time::PlayTime,
+    player_camera::CameraFocus,
+    players::{ClaimedFamily, Players},
+    stats::FamilyStats,
+    world_generation::StartingBudget,
     WorldState,
 };
 use crate::core::GameState;
 use building::BuildingPlugin;
 use editor::{EditorPlugin, FamilyScene, ReflectActorBundle};
+use gallery::GalleryPlugin;
+use npc::NpcPlugin;
 
 pub(super) struct FamilyPlugin;
 
 impl Plugin for FamilyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((EditorPlugin, BuildingPlugin))
+        app.add_plugins((EditorPlugin, GalleryPlugin, BuildingPlugin, NpcPlugin))
             .add_sub_state::<FamilyMode>()
             .enable_state_scoped_entities::<FamilyMode>()
             .register_type::<Family>()
             .register_type::<Budget>()
+            .register_type::<FamilyAppearance>()
+            .register_type::<TransactionLog>()
+            .register_type::<FamilyOwner>()
             .replicate::<Budget>()
+            .replicate::<TransactionLog>()
+            .replicate::<FamilyAppearance>()
+            .replicate_mapped::<FamilyOwner>()
             .replicate_group::<(Family, Name)>()
             .add_client_trigger_with(
                 ChannelKind::Unordered,
@@ -46,6 +73,7 @@ impl Plugin for FamilyPlugin {
             .add_observer(update_members)
             .add_observer(create)
             .add_observer(delete)
+            .add_observer(switch)
             .add_systems(OnEnter(WorldState::Family), select)
             .add_systems(OnExit(WorldState::Family), deselect.never_param_warn());
     }
@@ -65,16 +93,57 @@ fn update_members(trigger: Trigger<FamilyMemberAdded>, mut families: Query<&mut
     members.push(**trigger)
 }
 
-fn create(mut trigger: Trigger<FromClient<FamilyCreate>>, mut commands: Commands) {
+fn create(
+    mut trigger: Trigger<FromClient<FamilyCreate>>,
+    mut commands: Commands,
+    players: Res<Players>,
+    starting_budget: Option<Single<&StartingBudget>>,
+    children: Query<&Children>,
+    lots: Query<(Entity, &Lot, Option<&FamilyOwner>)>,
+    families: Query<(), With<Family>>,
+) {
+    if !players.can_play(trigger.client_id) {
+        error!(
+            "`{:?}` is not allowed to create families",
+            trigger.client_id
+        );
+        return;
+    }
+
     info!("creating new family");
+    let name = mem::take(&mut trigger.event.scene.name);
+    commands.spawn(GameEvent::new(
+        EventCategory::Family,
+        format!("The {name} family moved in"),
+    ));
     let family_entity = commands
-        .spawn((Family, Name::new(mem::take(&mut trigger.event.scene.name))))
+        .spawn((Family, Name::new(name), trigger.event.scene.appearance))
         .id();
+    if let Some(starting_budget) = starting_budget {
+        commands
+            .entity(family_entity)
+            .insert(Budget(***starting_budget));
+    }
     let entity = trigger.entity();
-    for actor in trigger.event.scene.actors.drain(..) {
+    let free_lot = children.get(entity).ok().and_then(|children| {
+        lots.iter_many(children)
+            .find(|(.., owner)| owner.is_none_or(|owner| families.get(owner.0).is_err()))
+    });
+    let mut origin = Vec3::ZERO;
+    if let Some((lot_entity, lot, _)) = free_lot {
+        debug!("moving the family into lot `{lot_entity}`");
+        commands
+            .entity(lot_entity)
+            .insert(FamilyOwner(family_entity));
+        origin = Vec3::new(lot.entrance.x, 0.0, lot.entrance.y);
+    }
+    for (index, actor) in trigger.event.scene.actors.drain(..).enumerate() {
         commands.entity(entity).with_children(|parent| {
             parent
-                .spawn(Actor { family_entity })
+                .spawn((
+                    Actor { family_entity },
+                    Transform::from_translation(origin + Vec3::X * index as f32),
+                ))
                 .insert_reflect(actor.into_partial_reflect());
         });
     }
@@ -92,31 +161,92 @@ fn create(mut trigger: Trigger<FromClient<FamilyCreate>>, mut commands: Commands
 fn delete(
     trigger: Trigger<FromClient<FamilyDelete>>,
     mut commands: Commands,
-    families: Query<&mut FamilyMembers>,
+    mut players: ResMut<Players>,
+    families: Query<(&Name, &FamilyMembers)>,
 ) {
+    if !players.can_play(trigger.client_id) {
+        error!(
+            "`{:?}` is not allowed to delete families",
+            trigger.client_id
+        );
+        return;
+    }
+
     match families.get(trigger.entity()) {
-        Ok(members) => {
+        Ok((name, members)) => {
             info!(
                 "`{:?}` deletes family `{}`",
                 trigger.client_id,
                 trigger.entity()
             );
+            commands.spawn(GameEvent::new(
+                EventCategory::Family,
+                format!("The {name} family moved out"),
+            ));
             commands.entity(trigger.entity()).despawn();
             for &entity in &members.0 {
                 commands.entity(entity).despawn_recursive();
             }
+            players.forget_family(&mut commands, trigger.entity());
         }
         Err(e) => error!("received an invalid family to despawn: {e}"),
     }
 }
 
-pub fn select(mut commands: Commands, selected_actor: Single<&Actor, With<SelectedActor>>) {
+fn select(mut commands: Commands, selected_actor: Single<&Actor, With<SelectedActor>>) {
     info!("selecting `{}`", selected_actor.family_entity);
     commands
         .entity(selected_actor.family_entity)
         .insert(SelectedFamily);
 }
 
+/// Moves control to another family without leaving [`WorldState::Family`].
+///
+/// The previous family is released and the new one is claimed,
+/// so the server keeps both owned by the player.
+fn switch(
+    trigger: Trigger<FamilySwitch>,
+    mut commands: Commands,
+    selected_actor: Single<(Entity, &Actor), With<SelectedActor>>,
+    grouped_actors: Query<Entity, With<GroupedActor>>,
+    families: Query<&FamilyMembers, Without<ClaimedFamily>>,
+    transforms: Query<&Transform>,
+) {
+    let (actor_entity, actor) = *selected_actor;
+    let family_entity = trigger.0;
+    if actor.family_entity == family_entity {
+        return;
+    }
+    let Ok(members) = families.get(family_entity) else {
+        error!("unable to switch to family `{family_entity}` owned by another player");
+        return;
+    };
+    let &new_actor_entity = members
+        .first()
+        .expect("family always have at least one member");
+
+    info!(
+        "switching from family `{}` to `{family_entity}`",
+        actor.family_entity
+    );
+    for grouped_entity in &grouped_actors {
+        commands.entity(grouped_entity).remove::<GroupedActor>();
+    }
+    commands.entity(actor_entity).remove::<SelectedActor>();
+    commands
+        .entity(actor.family_entity)
+        .remove::<SelectedFamily>();
+    commands.entity(new_actor_entity).insert(SelectedActor);
+    commands.entity(family_entity).insert(SelectedFamily);
+    commands.set_state(FamilyMode::Life);
+
+    // If the family lives in another city, the city will be switched
+    // and the new camera will start at the selected actor.
+    if let Ok(transform) = transforms.get(new_actor_entity) {
+        commands.trigger(CameraFocus(transform.translation));
+    }
+}
+
 fn deselect(mut commands: Commands, selected_actor: Single<&Actor, With<SelectedActor>>) {
     info!("deselecting `{}`", selected_actor.family_entity);
     commands
@@ -129,12 +259,7 @@ fn serialize_family_create(
     event: &FamilyCreate,
     cursor: &mut Vec<u8>,
 ) -> bincode::Result<()> {
-    DefaultOptions::new().serialize_into(&mut *cursor, &event.scene.name)?;
-    DefaultOptions::new().serialize_into(&mut *cursor, &event.scene.actors.len())?;
-    for actor in &event.scene.actors {
-        let serializer = ReflectSerializer::new(actor.as_partial_reflect(), ctx.registry);
-        DefaultOptions::new().serialize_into(&mut *cursor, &serializer)?;
-    }
+    serialize_scene(&mut *cursor, &event.scene, ctx.registry)?;
     DefaultOptions::new().serialize_into(cursor, &event.select)?;
 
     Ok(())
@@ -144,22 +269,47 @@ fn deserialize_family_create(
     ctx: &mut ServerReceiveCtx,
     cursor: &mut Cursor<&[u8]>,
 ) -> bincode::Result<FamilyCreate> {
-    let name = DefaultOptions::new().deserialize_from(&mut *cursor)?;
-    let actors_count = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+    let scene = deserialize_scene(&mut *cursor, ctx.registry)?;
+    let select = DefaultOptions::new().deserialize_from(cursor)?;
+
+    Ok(FamilyCreate { scene, select })
+}
+
+/// Serializes the family name and actor bundles using reflection.
+pub(crate) fn serialize_scene(
+    mut writer: impl Write,
+    scene: &FamilyScene,
+    registry: &TypeRegistry,
+) -> bincode::Result<()> {
+    DefaultOptions::new().serialize_into(&mut writer, &scene.name)?;
+    DefaultOptions::new().serialize_into(&mut writer, &scene.actors.len())?;
+    for actor in &scene.actors {
+        let serializer = ReflectSerializer::new(actor.as_partial_reflect(), registry);
+        DefaultOptions::new().serialize_into(&mut writer, &serializer)?;
+    }
+    DefaultOptions::new().serialize_into(&mut writer, &scene.appearance)?;
+
+    Ok(())
+}
+
+/// Deserializes a scene written by [`serialize_scene`].
+pub(crate) fn deserialize_scene(
+    mut reader: impl Read,
+    registry: &TypeRegistry,
+) -> bincode::Result<FamilyScene> {
+    let name = DefaultOptions::new().deserialize_from(&mut reader)?;
+    let actors_count = DefaultOptions::new().deserialize_from(&mut reader)?;
     let mut actors = Vec::with_capacity(actors_count);
     for _ in 0..actors_count {
         let mut deserializer =
-            bincode::Deserializer::with_reader(&mut *cursor, DefaultOptions::new());
-        let partial_reflect =
-            ReflectDeserializer::new(ctx.registry).deserialize(&mut deserializer)?;
+            bincode::Deserializer::with_reader(&mut reader, DefaultOptions::new());
+        let partial_reflect = ReflectDeserializer::new(registry).deserialize(&mut deserializer)?;
         let type_info = partial_reflect.get_represented_type_info().unwrap();
         let type_path = type_info.type_path();
-        let registration = ctx
-            .registry
+        let registration = registry
             .get(type_info.type_id())
             .ok_or_else(|| ErrorKind::Custom(format!("{type_path} is not registered")))?;
-        let from_reflect = ctx
-            .registry
+        let from_reflect = registry
             .get_type_data::<ReflectFromReflect>(registration.type_id())
             .unwrap_or_else(|| panic!("`{type_path}` should reflect `FromReflect`"));
         let reflect = from_reflect
@@ -175,11 +325,20 @@ fn deserialize_family_create(
             .map_err(|_| ErrorKind::Custom(format!("`{type_path}` is not an `ActorBundle`")))?;
         actors.push(actor);
     }
-    let select = DefaultOptions::new().deserialize_from(cursor)?;
 
-    Ok(FamilyCreate {
-        scene: FamilyScene { name, actors },
-        select,
+    // Written last to keep families saved before appearance was introduced readable.
+    let appearance = match DefaultOptions::new().deserialize_from(&mut reader) {
+        Ok(appearance) => appearance,
+        Err(e) if matches!(&*e, ErrorKind::Io(io_error) if io_error.kind() == io::ErrorKind::UnexpectedEof) => {
+            Default::default()
+        }
+        Err(e) => return Err(e),
+    };
+
+    Ok(FamilyScene {
+        name,
+        actors,
+        appearance,
     })
 }
 
@@ -205,22 +364,181 @@ impl FamilyMode {
 #[require(
     Name,
     Budget,
+    TransactionLog,
+    FamilyStats,
+    PlayTime,
     Replicated,
     FamilyMembers,
+    FamilyAppearance,
     StateScoped<GameState>(|| StateScoped(GameState::InGame))
 )]
 pub struct Family;
 
+/// Color and crest that distinguish a family in the UI.
+///
+/// Chosen in the family editor.
+#[derive(Clone, Component, Copy, Debug, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct FamilyAppearance {
+    pub color: FamilyColor,
+    pub crest: Crest,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, EnumIter, Eq, PartialEq, Reflect, Serialize)]
+pub enum FamilyColor {
+    #[default]
+    Blue,
+    Green,
+    Red,
+    Orange,
+    Purple,
+    Teal,
+}
+
+impl FamilyColor {
+    pub fn color(self) -> Color {
+        match self {
+            Self::Blue => Color::srgb(0.25, 0.45, 0.85),
+            Self::Green => Color::srgb(0.3, 0.7, 0.3),
+            Self::Red => Color::srgb(0.8, 0.25, 0.25),
+            Self::Orange => Color::srgb(0.9, 0.55, 0.2),
+            Self::Purple => Color::srgb(0.6, 0.35, 0.8),
+            Self::Teal => Color::srgb(0.2, 0.65, 0.65),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, EnumIter, Eq, PartialEq, Reflect, Serialize)]
+pub enum Crest {
+    #[default]
+    House,
+    Tree,
+    Star,
+    Heart,
+    Crown,
+    Anchor,
+}
+
+impl Crest {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Self::House => "🏠",
+            Self::Tree => "🌳",
+            Self::Star => "⭐",
+            Self::Heart => "❤",
+            Self::Crown => "👑",
+            Self::Anchor => "⚓",
+        }
+    }
+}
+
 #[derive(Clone, Component, Copy, Debug, Deserialize, Reflect, Serialize, Deref)]
 #[reflect(Component)]
 pub struct Budget(u32);
 
+impl Budget {
+    /// Subtracts the amount and records it to the log, the budget can't go below zero.
+    pub(crate) fn spend(
+        &mut self,
+        log: &mut TransactionLog,
+        kind: TransactionKind,
+        description: impl Into<String>,
+        amount: u32,
+    ) {
+        self.0 = self.0.saturating_sub(amount);
+        log.push(Transaction {
+            kind,
+            description: description.into(),
+            amount: -(amount as i64),
+        });
+    }
+
+    /// Adds the amount and records it to the log.
+    pub(crate) fn earn(
+        &mut self,
+        log: &mut TransactionLog,
+        kind: TransactionKind,
+        description: impl Into<String>,
+        amount: u32,
+    ) {
+        self.0 = self.0.saturating_add(amount);
+        log.push(Transaction {
+            kind,
+            description: description.into(),
+            amount: amount.into(),
+        });
+    }
+}
+
 impl Default for Budget {
     fn default() -> Self {
         Self(20_000)
     }
 }
 
+/// Maximum number of transactions kept in [`TransactionLog`].
+const MAX_TRANSACTIONS: usize = 30;
+
+/// Recent changes of [`Budget`], from the oldest to the newest.
+#[derive(Component, Default, Deref, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct TransactionLog(VecDeque<Transaction>);
+
+impl TransactionLog {
+    fn push(&mut self, transaction: Transaction) {
+        if self.0.len() == MAX_TRANSACTIONS {
+            self.0.pop_front();
+        }
+        self.0.push_back(transaction);
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Reflect, Serialize)]
+pub struct Transaction {
+    pub kind: TransactionKind,
+    pub description: String,
+    /// Negative for expenses.
+    pub amount: i64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Reflect, Serialize)]
+pub enum TransactionKind {
+    Purchase,
+    Sale,
+    Service,
+    Damage,
+}
+
+impl TransactionKind {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Self::Purchase => "🛒",
+            Self::Sale => "💰",
+            Self::Service => "🍕",
+            Self::Damage => "🔥",
+        }
+    }
+}
+
+/// Family that bought the object or built the wall.
+///
+/// Charged for damage. Absent for entities placed without a family, like in the city editor.
+#[derive(Clone, Component, Copy, Deref, Deserialize, Reflect, Serialize)]
+#[reflect(Component, MapEntities)]
+pub(crate) struct FamilyOwner(pub(crate) Entity);
+
+impl FromWorld for FamilyOwner {
+    fn from_world(_world: &mut World) -> Self {
+        Self(Entity::PLACEHOLDER)
+    }
+}
+
+impl MapEntities for FamilyOwner {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
 /// Contains the entities of all the actors that belong to the family.
 ///
 /// Automatically created and updated based on [`Actor`].
@@ -234,6 +552,12 @@ pub struct FamilyMembers(Vec<Entity>);
 #[derive(Event, Deref)]
 struct FamilyMemberAdded(Entity);
 
+/// Switches control to another family owned by the local player.
+///
+/// Should be triggered only in [`WorldState::Family`].
+#[derive(Event)]
+pub struct FamilySwitch(pub Entity);
+
 /// Indicates locally controlled family.
 ///
 /// Inserted automatically on [`ActiveActor`] insertion.
@@ -1,9 +1,17 @@
+pub mod basement;
+pub mod fence;
+pub mod pool;
+pub mod walkthrough;
 pub mod wall;
 
 use bevy::prelude::*;
 use strum::EnumIter;
 
 use super::FamilyMode;
+use basement::BasementPlugin;
+use fence::FencePlugin;
+use pool::PoolPlugin;
+use walkthrough::WalkthroughPlugin;
 use wall::WallPlugin;
 
 pub(super) struct BuildingPlugin;
@@ -12,7 +20,13 @@ impl Plugin for BuildingPlugin {
     fn build(&self, app: &mut App) {
         app.add_sub_state::<BuildingMode>()
             .enable_state_scoped_entities::<BuildingMode>()
-            .add_plugins(WallPlugin);
+            .add_plugins((
+                BasementPlugin,
+                FencePlugin,
+                PoolPlugin,
+                WalkthroughPlugin,
+                WallPlugin,
+            ));
     }
 }
 
@@ -22,6 +36,9 @@ pub enum BuildingMode {
     #[default]
     Objects,
     Walls,
+    Fences,
+    Pools,
+    Basements,
 }
 
 impl BuildingMode {
@@ -29,6 +46,9 @@ impl BuildingMode {
         match self {
             Self::Objects => "💺",
             Self::Walls => "🔰",
+            Self::Fences => "🚧",
+            Self::Pools => "🏊",
+            Self::Basements => "⛏",
         }
     }
 }
@@ -0,0 +1,514 @@
+mod basement_mesh;
+mod placing_basement;
+
+use std::mem;
+
+use avian3d::prelude::*;
+use bevy::{ecs::entity::MapEntities, math::Vec3Swizzles, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::wall::WallMaterial;
+use crate::{
+    core::GameState,
+    dynamic_mesh::DynamicMesh,
+    game_world::{
+        city::{City, Ground, HALF_CITY_SIZE},
+        commands_history::{
+            CommandConfirmation, CommandId, CommandRequest, ConfirmableCommand, EntityRecorder,
+            PendingCommand,
+        },
+        navigation::Obstacle,
+        players::Players,
+        Layer,
+    },
+};
+use placing_basement::PlacingBasementPlugin;
+
+pub(super) struct BasementPlugin;
+
+impl Plugin for BasementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PlacingBasementPlugin)
+            .register_type::<Basement>()
+            .replicate::<Basement>()
+            .add_mapped_client_trigger::<CommandRequest<BasementCommand>>(ChannelKind::Unordered)
+            .add_observer(init)
+            .add_observer(apply_command)
+            .add_systems(
+                PostUpdate,
+                (update_meshes, cut_ground).run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// Depth of the basement floor.
+const DEPTH: f32 = 2.5;
+
+/// Distance between points of paths that go through basements.
+///
+/// Paths are subdivided to let actors follow the stairs.
+const PATH_STEP: f32 = 0.25;
+
+fn init(
+    trigger: Trigger<OnAdd, Basement>,
+    wall_material: Res<WallMaterial>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut basements: Query<(&mut Mesh3d, &mut MeshMaterial3d<StandardMaterial>)>,
+) {
+    debug!("initializing basement `{}`", trigger.entity());
+    let (mut mesh, mut material) = basements.get_mut(trigger.entity()).unwrap();
+    **mesh = meshes.add(DynamicMesh::create_empty());
+    *material = wall_material.0.clone();
+}
+
+fn update_meshes(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut changed_basements: Query<(&Mesh3d, &Basement, &mut Collider), Changed<Basement>>,
+) {
+    for (mesh_handle, basement, mut collider) in &mut changed_basements {
+        let mesh = meshes
+            .get_mut(mesh_handle)
+            .expect("basement handles should be valid");
+
+        trace!("regenerating basement mesh");
+        let mut dyn_mesh = DynamicMesh::take(mesh);
+        basement_mesh::generate(&mut dyn_mesh, basement.0);
+        dyn_mesh.apply(mesh);
+
+        *collider = basement_mesh::generate_collider(basement.0);
+    }
+}
+
+/// Regenerates ground meshes and colliders with holes for basements.
+///
+/// Basement floors are included into the collider to let ground raycasts reach them.
+/// Also reacts on parent changes because on clients [`ParentSync`] could assign the parent later.
+fn cut_ground(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut removed_basements: RemovedComponents<Basement>,
+    changed_basements: Query<(), (With<Basement>, Or<(Changed<Basement>, Changed<Parent>)>)>,
+    basements: Query<&Basement>,
+    cities: Query<&Children, With<City>>,
+    mut grounds: Query<(&mut Mesh3d, &mut Collider), With<Ground>>,
+) {
+    if changed_basements.is_empty() && removed_basements.read().count() == 0 {
+        return;
+    }
+
+    for children in &cities {
+        let holes: Vec<_> = basements
+            .iter_many(children)
+            .map(|basement| basement.0)
+            .collect();
+
+        let rects = ground_rects(&holes);
+        let mut iter = grounds.iter_many_mut(children);
+        while let Some((mut mesh, mut collider)) = iter.fetch_next() {
+            debug!("cutting {} holes in the ground", holes.len());
+            **mesh = meshes.add(ground_mesh(&rects));
+            *collider = ground_collider(&rects, &holes);
+        }
+    }
+}
+
+/// Splits the city ground plane into rectangles around the given holes.
+///
+/// Splits the ground into vertical strips on hole edges
+/// and fills each strip with rectangles between holes.
+fn ground_rects(holes: &[Rect]) -> Vec<Rect> {
+    let bounds = Rect::new(
+        -HALF_CITY_SIZE,
+        -HALF_CITY_SIZE,
+        HALF_CITY_SIZE,
+        HALF_CITY_SIZE,
+    );
+    let holes: Vec<_> = holes
+        .iter()
+        .map(|hole| hole.intersect(bounds))
+        .filter(|hole| !hole.is_empty())
+        .collect();
+
+    let mut edges = vec![bounds.min.x, bounds.max.x];
+    for hole in &holes {
+        edges.push(hole.min.x);
+        edges.push(hole.max.x);
+    }
+    edges.sort_by(f32::total_cmp);
+    edges.dedup();
+
+    let mut rects = Vec::new();
+    for strip in edges.windows(2) {
+        let (left, right) = (strip[0], strip[1]);
+        let mut strip_holes: Vec<_> = holes
+            .iter()
+            .filter(|hole| hole.min.x < right && hole.max.x > left)
+            .collect();
+        strip_holes.sort_by(|a, b| a.min.y.total_cmp(&b.min.y));
+
+        let mut top = bounds.min.y;
+        for hole in strip_holes {
+            if hole.min.y > top {
+                rects.push(Rect::new(left, top, right, hole.min.y));
+            }
+            top = top.max(hole.max.y);
+        }
+        if top < bounds.max.y {
+            rects.push(Rect::new(left, top, right, bounds.max.y));
+        }
+    }
+
+    rects
+}
+
+fn ground_mesh(rects: &[Rect]) -> Mesh {
+    let mut mesh = DynamicMesh::default();
+    for &rect in rects {
+        add_ground_quad(&mut mesh, rect);
+    }
+
+    let mut ground_mesh = DynamicMesh::create_empty();
+    mesh.apply(&mut ground_mesh);
+    ground_mesh
+}
+
+fn add_ground_quad(mesh: &mut DynamicMesh, rect: Rect) {
+    let vertices_start = mesh.vertices_count();
+    for point in [
+        Vec2::new(rect.min.x, rect.max.y),
+        rect.max,
+        Vec2::new(rect.max.x, rect.min.y),
+        rect.min,
+    ] {
+        mesh.positions.push([point.x, 0.0, point.y]);
+        mesh.normals.push([0.0, 1.0, 0.0]);
+        // Tile the texture the same way as the uncut ground.
+        mesh.uvs
+            .push([point.x + HALF_CITY_SIZE, point.y + HALF_CITY_SIZE]);
+    }
+
+    mesh.indices.push(vertices_start);
+    mesh.indices.push(vertices_start + 1);
+    mesh.indices.push(vertices_start + 2);
+    mesh.indices.push(vertices_start);
+    mesh.indices.push(vertices_start + 2);
+    mesh.indices.push(vertices_start + 3);
+}
+
+/// Generates a flat collider from ground rectangles and floors of the given basements.
+fn ground_collider(rects: &[Rect], basements: &[Rect]) -> Collider {
+    let ground = rects.iter().map(|&rect| (rect, 0.0));
+    let floors = basements.iter().map(|&basement| (basement, -DEPTH));
+    let colliders = ground
+        .chain(floors)
+        .map(|(rect, height)| {
+            let center = rect.center();
+            let size = rect.size();
+            (
+                Vec3::new(center.x, height, center.y),
+                Quat::IDENTITY,
+                Collider::cuboid(size.x, 0.0, size.y),
+            )
+        })
+        .collect();
+
+    Collider::compound(colliders)
+}
+
+/// Adjusts path height to walk on floors and stairs of the given basements.
+///
+/// Segments that cross basements are subdivided to follow the stairs.
+pub(crate) fn follow_floors(path: &mut Vec<Vec3>, basements: &[Rect]) {
+    if basements.is_empty() || path.is_empty() {
+        return;
+    }
+
+    let points = mem::take(path);
+    path.push(points[0]);
+    for segment in points.windows(2) {
+        let (from, to) = (segment[0], segment[1]);
+        // Inflate to handle axis-aligned segments.
+        let bounds = Rect::from_corners(from.xz(), to.xz()).inflate(PATH_STEP);
+        if basements
+            .iter()
+            .all(|basement| basement.intersect(bounds).is_empty())
+        {
+            path.push(Vec3::new(to.x, 0.0, to.z));
+            continue;
+        }
+
+        let steps = (from.xz().distance(to.xz()) / PATH_STEP).ceil().max(1.0) as u32;
+        for step in 1..=steps {
+            let point = from.lerp(to, step as f32 / steps as f32);
+            let height = floor_height(basements, point.xz());
+            path.push(Vec3::new(point.x, height, point.z));
+        }
+    }
+}
+
+/// Returns the walkable height at the given point.
+fn floor_height(basements: &[Rect], point: Vec2) -> f32 {
+    basements
+        .iter()
+        .find(|basement| basement.contains(point))
+        .map(|&basement| basement_mesh::floor_height(basement, point))
+        .unwrap_or_default()
+}
+
+fn apply_command(
+    trigger: Trigger<FromClient<CommandRequest<BasementCommand>>>,
+    mut commands: Commands,
+    players: Res<Players>,
+    mut basements: Query<&mut Basement>,
+) {
+    if !players.can_build(trigger.client_id) {
+        error!("`{:?}` is not allowed to build", trigger.client_id);
+        return;
+    }
+
+    let mut confirmation = CommandConfirmation::new(trigger.event.id);
+    match trigger.event.command {
+        BasementCommand::Create { city_entity, rect } => {
+            info!("`{:?}` digs basement", trigger.client_id);
+            commands.entity(city_entity).with_children(|parent| {
+                let entity = parent.spawn(Basement(rect)).id();
+                confirmation.entity = Some(entity);
+            });
+        }
+        BasementCommand::Edit { entity, rect } => match basements.get_mut(entity) {
+            Ok(mut basement) => {
+                info!("`{:?}` resizes basement `{entity}`", trigger.client_id);
+                basement.0 = rect;
+            }
+            Err(e) => {
+                error!("unable to resize basement `{entity}`: {e}");
+                return;
+            }
+        },
+        BasementCommand::Delete { entity } => {
+            if basements.get(entity).is_err() {
+                error!("unable to fill `{entity}`: not a basement");
+                return;
+            }
+            info!("`{:?}` fills basement `{entity}`", trigger.client_id);
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    commands.server_trigger(ToClients {
+        mode: SendMode::Direct(trigger.client_id),
+        event: confirmation,
+    });
+}
+
+/// A rectangular basement dug into the ground.
+///
+/// Stores its area in city coordinates. Includes retaining walls, floor and stairs.
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Basement")),
+    ParentSync,
+    Replicated,
+    Transform,
+    Visibility,
+    Mesh3d,
+    MeshMaterial3d::<StandardMaterial>,
+    Obstacle,
+    Collider,
+    CollisionLayers(|| CollisionLayers::new(
+        Layer::Wall,
+        [
+            Layer::Object,
+            Layer::PlacingObject,
+            Layer::Road,
+            Layer::PlacingRoad,
+        ],
+    )),
+)]
+pub(crate) struct Basement(pub(crate) Rect);
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+enum BasementCommand {
+    Create { city_entity: Entity, rect: Rect },
+    Edit { entity: Entity, rect: Rect },
+    Delete { entity: Entity },
+}
+
+impl PendingCommand for BasementCommand {
+    fn apply(
+        self: Box<Self>,
+        id: CommandId,
+        mut recorder: EntityRecorder,
+        world: &mut World,
+    ) -> Box<dyn ConfirmableCommand> {
+        let reverse_command = match *self {
+            Self::Create { .. } => Self::Delete {
+                // Correct entity will be set after the server confirmation.
+                entity: Entity::PLACEHOLDER,
+            },
+            Self::Edit { entity, .. } => {
+                let basement = world.get::<Basement>(entity).unwrap();
+                Self::Edit {
+                    entity,
+                    rect: basement.0,
+                }
+            }
+            Self::Delete { entity } => {
+                recorder.record(entity);
+                let entity = world.entity(entity);
+                let rect = entity.get::<Basement>().unwrap().0;
+                let city_entity = **entity.get::<Parent>().unwrap();
+                Self::Create { city_entity, rect }
+            }
+        };
+
+        world.client_trigger(CommandRequest { id, command: *self });
+
+        Box::new(reverse_command)
+    }
+}
+
+impl ConfirmableCommand for BasementCommand {
+    fn confirm(
+        mut self: Box<Self>,
+        mut recorder: EntityRecorder,
+        confirmation: CommandConfirmation,
+    ) -> Box<dyn PendingCommand> {
+        if let Self::Delete { entity } = &mut *self {
+            *entity = confirmation
+                .entity
+                .expect("confirmation for basement creation should contain an entity");
+            recorder.record(*entity);
+        }
+
+        self
+    }
+}
+
+impl MapEntities for BasementCommand {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        match self {
+            Self::Create { city_entity, .. } => {
+                *city_entity = entity_mapper.map_entity(*city_entity)
+            }
+            Self::Edit { entity, .. } => *entity = entity_mapper.map_entity(*entity),
+            Self::Delete { entity } => *entity = entity_mapper.map_entity(*entity),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::mesh::{Indices, VertexAttributeValues};
+
+    use super::*;
+
+    #[test]
+    fn ground_holes() {
+        let holes = [
+            Rect::new(-5.0, -5.0, 5.0, 5.0),
+            Rect::new(10.0, -3.0, 14.0, 3.0),
+            // Partially outside of the city.
+            Rect::new(HALF_CITY_SIZE - 2.0, 0.0, HALF_CITY_SIZE + 2.0, 4.0),
+        ];
+        let mesh = ground_mesh(&ground_rects(&holes));
+
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("ground should have positions");
+        };
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("ground should have U32 indices");
+        };
+
+        let mut area = 0.0;
+        for triangle in indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|index| Vec3::from(positions[triangle[index] as usize]));
+            let normal = (b - a).cross(c - a);
+            assert!(normal.y > 0.0, "ground should face up");
+            area += normal.length() as f64 / 2.0;
+
+            let centroid = ((a + b + c) / 3.0).xz();
+            for hole in &holes {
+                assert!(
+                    !hole.contains(centroid),
+                    "triangle at {centroid} shouldn't cover hole {hole:?}"
+                );
+            }
+        }
+
+        let city_area = (HALF_CITY_SIZE as f64 * 2.0).powi(2);
+        let holes_area = 10.0 * 10.0 + 4.0 * 6.0 + 2.0 * 4.0;
+        assert!(
+            (area - (city_area - holes_area)).abs() < 0.1,
+            "ground should cover everything except holes, but covers {area}"
+        );
+    }
+
+    #[test]
+    fn ground_without_holes() {
+        let rects = ground_rects(&[]);
+        assert_eq!(
+            rects,
+            [Rect::new(
+                -HALF_CITY_SIZE,
+                -HALF_CITY_SIZE,
+                HALF_CITY_SIZE,
+                HALF_CITY_SIZE
+            )]
+        );
+    }
+
+    #[test]
+    fn path_outside() {
+        let basements = [Rect::new(0.0, 0.0, 10.0, 10.0)];
+        let mut path = vec![Vec3::new(-5.0, 0.0, -5.0), Vec3::new(15.0, 0.0, -5.0)];
+        let original = path.clone();
+        follow_floors(&mut path, &basements);
+        assert_eq!(path, original, "paths around basements shouldn't change");
+    }
+
+    #[test]
+    fn path_through_floor() {
+        let basement = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let from = Vec3::new(-5.0, 0.0, 5.0);
+        let to = Vec3::new(15.0, 0.0, 5.0);
+        let mut path = vec![from, to];
+        follow_floors(&mut path, &[basement]);
+
+        assert_eq!(path.first(), Some(&from));
+        assert_eq!(path.last(), Some(&to));
+        for segment in path.windows(2) {
+            assert!(
+                segment[0].xz().distance(segment[1].xz()) <= PATH_STEP + f32::EPSILON * 100.0,
+                "path should be subdivided inside basements"
+            );
+        }
+        for point in &path {
+            if point.x > 1.0 && point.x < 9.0 {
+                assert_eq!(point.y, -DEPTH, "point {point} should be on the floor");
+            } else if !basement.contains(point.xz()) {
+                assert_eq!(point.y, 0.0, "point {point} should be on the ground");
+            }
+        }
+    }
+
+    #[test]
+    fn path_down_stairs() {
+        let basement = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let mut path = vec![Vec3::new(-1.0, 0.0, 0.75), Vec3::new(5.0, 0.0, 0.75)];
+        follow_floors(&mut path, &[basement]);
+
+        for segment in path.windows(2) {
+            assert!(
+                segment[1].y <= segment[0].y,
+                "height should decrease along the stairs, but {} is after {}",
+                segment[1],
+                segment[0]
+            );
+        }
+        assert_eq!(path.last().unwrap().y, -DEPTH);
+    }
+}
@@ -0,0 +1,113 @@
+use avian3d::prelude::Collider;
+use bevy::prelude::*;
+
+use super::DEPTH;
+use crate::dynamic_mesh::DynamicMesh;
+
+const WALL_THICKNESS: f32 = 0.1;
+const FLOOR_THICKNESS: f32 = 0.1;
+
+/// Stairs go along the minimal Z side, starting from the minimal X side.
+const STAIRS_WIDTH: f32 = 1.5;
+const STAIRS_LENGTH: f32 = 3.5;
+const STEPS_COUNT: u32 = 14;
+
+/// How much retaining wall colliders stick out of the ground.
+///
+/// Needed for navigation mesh to detect them.
+const COLLIDER_HEIGHT: f32 = 0.5;
+
+/// Minimum side of a basement to fit the stairs.
+pub(super) const MIN_SIZE: f32 = STAIRS_LENGTH + STAIRS_WIDTH;
+
+pub(super) fn generate(mesh: &mut DynamicMesh, rect: Rect) {
+    mesh.clear();
+
+    if rect.is_empty() {
+        return;
+    }
+
+    mesh.add_cuboid(
+        Vec3::new(rect.min.x, -DEPTH - FLOOR_THICKNESS, rect.min.y),
+        Vec3::new(rect.max.x, -DEPTH, rect.max.y),
+    );
+
+    for wall in retaining_walls(rect) {
+        mesh.add_cuboid(
+            Vec3::new(wall.min.x, -DEPTH, wall.min.y),
+            Vec3::new(wall.max.x, 0.0, wall.max.y),
+        );
+    }
+
+    let step_length = STAIRS_LENGTH / STEPS_COUNT as f32;
+    for index in 0..STEPS_COUNT {
+        let start = rect.min.x + step_length * index as f32;
+        let height = -DEPTH * index as f32 / STEPS_COUNT as f32;
+        mesh.add_cuboid(
+            Vec3::new(start, -DEPTH, rect.min.y + WALL_THICKNESS),
+            Vec3::new(start + step_length, height, rect.min.y + STAIRS_WIDTH),
+        );
+    }
+}
+
+/// Generates colliders for retaining walls.
+///
+/// Also adds a wall on the side of the stairs, so actors can enter only from its top or bottom.
+pub(super) fn generate_collider(rect: Rect) -> Collider {
+    if rect.is_empty() {
+        return Default::default();
+    }
+
+    let stairs_side = Rect::new(
+        rect.min.x,
+        rect.min.y + STAIRS_WIDTH,
+        rect.min.x + STAIRS_LENGTH,
+        rect.min.y + STAIRS_WIDTH + WALL_THICKNESS,
+    );
+
+    let colliders = retaining_walls(rect)
+        .into_iter()
+        .chain([stairs_side])
+        .map(|wall| {
+            let height = DEPTH + COLLIDER_HEIGHT;
+            let center = wall.center();
+            let size = wall.size();
+            (
+                Vec3::new(center.x, COLLIDER_HEIGHT - height / 2.0, center.y),
+                Quat::IDENTITY,
+                Collider::cuboid(size.x, height, size.y),
+            )
+        })
+        .collect();
+
+    Collider::compound(colliders)
+}
+
+/// Returns walkable height inside the basement.
+pub(super) fn floor_height(rect: Rect, point: Vec2) -> f32 {
+    let stairs = Rect::new(
+        rect.min.x,
+        rect.min.y,
+        rect.min.x + STAIRS_LENGTH,
+        rect.min.y + STAIRS_WIDTH,
+    );
+
+    if stairs.contains(point) {
+        -DEPTH * (point.x - stairs.min.x) / STAIRS_LENGTH
+    } else {
+        -DEPTH
+    }
+}
+
+/// Returns wall areas along the basement perimeter.
+///
+/// The wall at the top of the stairs leaves an opening to enter the basement.
+fn retaining_walls(rect: Rect) -> [Rect; 4] {
+    let Rect { min, max } = rect;
+    [
+        Rect::new(min.x, min.y, max.x, min.y + WALL_THICKNESS),
+        Rect::new(min.x, max.y - WALL_THICKNESS, max.x, max.y),
+        Rect::new(min.x, min.y + STAIRS_WIDTH, min.x + WALL_THICKNESS, max.y),
+        Rect::new(max.x - WALL_THICKNESS, min.y, max.x, max.y),
+    ]
+}
@@ -0,0 +1,292 @@
+use bevy::{math::Vec3Swizzles, prelude::*, render::view::NoFrustumCulling};
+use bevy_enhanced_input::prelude::*;
+
+use super::{basement_mesh, Basement, BasementCommand};
+use crate::{
+    dynamic_mesh::DynamicMesh,
+    game_world::{
+        city::ActiveCity,
+        commands_history::{CommandsHistory, PendingDespawn},
+        cursor_mode::PlacingCursor,
+        family::building::{wall::WallMaterial, BuildingMode},
+        player_camera::CameraCaster,
+    },
+    ghost::Ghost,
+    settings::Settings,
+};
+
+pub(super) struct PlacingBasementPlugin;
+
+impl Plugin for PlacingBasementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_input_context::<PlacingBasement>()
+            .add_observer(pick.never_param_warn())
+            .add_observer(spawn.never_param_warn())
+            .add_observer(delete.never_param_warn())
+            .add_observer(cancel)
+            .add_observer(confirm.never_param_warn())
+            .add_systems(
+                Update,
+                update_corner
+                    .never_param_warn()
+                    .run_if(in_state(BuildingMode::Basements)),
+            )
+            .add_systems(PostUpdate, update_mesh.never_param_warn());
+    }
+}
+
+/// Basements are dug on a grid with this step.
+const GRID_STEP: f32 = 0.5;
+
+/// Starts resizing the basement from the corner nearest to the cursor.
+///
+/// Clicks inside basements hit the ground, so the basement is searched by the cursor position.
+fn pick(
+    mut trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    camera_caster: CameraCaster,
+    wall_material: Res<WallMaterial>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    state: Res<State<BuildingMode>>,
+    city_entity: Single<Entity, With<ActiveCity>>,
+    basements: Query<(Entity, &Parent, &Basement)>,
+    placing_basements: Query<(), With<PlacingBasement>>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    if *state != BuildingMode::Basements {
+        return;
+    }
+    if !placing_basements.is_empty() {
+        return;
+    }
+    let Some(point) = camera_caster.intersect_ground() else {
+        return;
+    };
+    let Some((basement_entity, _, basement)) = basements
+        .iter()
+        .find(|(_, parent, basement)| ***parent == *city_entity && basement.0.contains(point.xz()))
+    else {
+        return;
+    };
+    trigger.propagate(false);
+
+    let Rect { min, max } = basement.0;
+    let corner = [min, max, Vec2::new(min.x, max.y), Vec2::new(max.x, min.y)]
+        .into_iter()
+        .min_by(|a, b| {
+            let a = a.distance_squared(point.xz());
+            let b = b.distance_squared(point.xz());
+            a.total_cmp(&b)
+        })
+        .unwrap();
+    let origin = min + max - corner;
+
+    info!("picking basement `{basement_entity}`");
+    commands.entity(*city_entity).with_children(|parent| {
+        parent.spawn((
+            Ghost::new(basement_entity),
+            PlacingBasement {
+                kind: PlacingBasementKind::Editing(basement_entity),
+                origin,
+                corner,
+            },
+            Mesh3d(meshes.add(DynamicMesh::create_empty())),
+            wall_material.0.clone(),
+        ));
+    });
+}
+
+fn spawn(
+    mut trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    camera_caster: CameraCaster,
+    wall_material: Res<WallMaterial>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    state: Res<State<BuildingMode>>,
+    city_entity: Single<Entity, With<ActiveCity>>,
+    basements: Query<(&Parent, &Basement)>,
+    placing_basements: Query<(), With<PlacingBasement>>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    if *state != BuildingMode::Basements {
+        return;
+    }
+    if !placing_basements.is_empty() {
+        return;
+    }
+    let Some(point) = camera_caster.intersect_ground() else {
+        return;
+    };
+    if basements
+        .iter()
+        .any(|(parent, basement)| **parent == *city_entity && basement.0.contains(point.xz()))
+    {
+        // Existing basements are handled by `pick`.
+        return;
+    }
+    trigger.propagate(false);
+
+    let origin = round_to_grid(point.xz());
+    info!("spawning new basement at `{origin}`");
+    commands.entity(*city_entity).with_children(|parent| {
+        parent.spawn((
+            PlacingBasement {
+                kind: PlacingBasementKind::Spawning,
+                origin,
+                corner: origin,
+            },
+            Mesh3d(meshes.add(DynamicMesh::create_empty())),
+            wall_material.0.clone(),
+        ));
+    });
+}
+
+fn update_corner(camera_caster: CameraCaster, mut placing_basement: Single<&mut PlacingBasement>) {
+    let Some(point) = camera_caster.intersect_ground() else {
+        return;
+    };
+
+    let corner = round_to_grid(point.xz());
+    if placing_basement.corner != corner {
+        trace!("updating basement corner to `{corner}`");
+        placing_basement.corner = corner;
+    }
+}
+
+fn update_mesh(
+    mut meshes: ResMut<Assets<Mesh>>,
+    placing_basement: Single<(&Mesh3d, &PlacingBasement), Changed<PlacingBasement>>,
+) {
+    let (mesh_handle, placing_basement) = *placing_basement;
+    let mesh = meshes
+        .get_mut(mesh_handle)
+        .expect("placing basement handle should be valid");
+
+    let mut dyn_mesh = DynamicMesh::take(mesh);
+    basement_mesh::generate(&mut dyn_mesh, placing_basement.rect());
+    dyn_mesh.apply(mesh);
+}
+
+fn round_to_grid(point: Vec2) -> Vec2 {
+    (point / GRID_STEP).round() * GRID_STEP
+}
+
+fn delete(
+    trigger: Trigger<Completed<DeleteBasement>>,
+    mut commands: Commands,
+    mut history: CommandsHistory,
+    placing_basement: Single<&PlacingBasement>,
+) {
+    if let PlacingBasementKind::Editing(entity) = placing_basement.kind {
+        info!("filling basement `{entity}`");
+        let command_id = history.push_pending(BasementCommand::Delete { entity });
+        commands
+            .entity(trigger.entity())
+            .insert(PendingDespawn { command_id })
+            .remove::<PlacingBasement>();
+    } else {
+        commands.entity(trigger.entity()).despawn_recursive();
+    }
+}
+
+fn cancel(trigger: Trigger<Completed<CancelBasement>>, mut commands: Commands) {
+    debug!("cancelling basement placing");
+    commands.entity(trigger.entity()).despawn_recursive();
+}
+
+fn confirm(
+    trigger: Trigger<Completed<ConfirmBasement>>,
+    mut commands: Commands,
+    mut history: CommandsHistory,
+    placing_basement: Single<(&Parent, &PlacingBasement)>,
+) {
+    let (parent, placing_basement) = *placing_basement;
+    let rect = placing_basement.rect();
+    if rect.width() < basement_mesh::MIN_SIZE || rect.height() < basement_mesh::MIN_SIZE {
+        debug!("ignoring too small basement `{rect:?}`");
+        return;
+    }
+
+    info!("confirming basement `{rect:?}`");
+    let command_id = match placing_basement.kind {
+        PlacingBasementKind::Spawning => history.push_pending(BasementCommand::Create {
+            city_entity: **parent,
+            rect,
+        }),
+        PlacingBasementKind::Editing(entity) => {
+            history.push_pending(BasementCommand::Edit { entity, rect })
+        }
+    };
+
+    commands
+        .entity(trigger.entity())
+        .insert(PendingDespawn { command_id })
+        .remove::<PlacingBasement>();
+}
+
+#[derive(Component, Clone, Copy)]
+#[require(
+    Name(|| Name::new("Placing basement")),
+    PlacingCursor,
+    StateScoped::<BuildingMode>(|| StateScoped(BuildingMode::Basements)),
+    // Looks like AABB is not recalculated when we edit the mesh.
+    // But we don't need to cull currently placed basement anyway.
+    NoFrustumCulling,
+    Transform,
+    Visibility,
+    Mesh3d,
+    MeshMaterial3d::<StandardMaterial>,
+)]
+struct PlacingBasement {
+    kind: PlacingBasementKind,
+    /// Fixed corner.
+    origin: Vec2,
+    /// Corner that follows the cursor.
+    corner: Vec2,
+}
+
+impl PlacingBasement {
+    fn rect(&self) -> Rect {
+        Rect::from_corners(self.origin, self.corner)
+    }
+}
+
+impl InputContext for PlacingBasement {
+    const PRIORITY: isize = 1;
+
+    fn context_instance(world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        let settings = world.resource::<Settings>();
+
+        ctx.bind::<DeleteBasement>()
+            .to((&settings.keyboard.delete, &settings.gamepad.delete));
+        ctx.bind::<CancelBasement>()
+            .to((KeyCode::Escape, &settings.gamepad.cancel));
+        ctx.bind::<ConfirmBasement>()
+            .to((MouseButton::Left, &settings.gamepad.confirm));
+
+        ctx
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum PlacingBasementKind {
+    Spawning,
+    Editing(Entity),
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct DeleteBasement;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct CancelBasement;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct ConfirmBasement;
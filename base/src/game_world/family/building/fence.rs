@@ -0,0 +1,311 @@
+mod fence_mesh;
+pub mod placing_fence;
+
+use avian3d::prelude::*;
+use bevy::{asset::AssetPath, ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+use super::BuildingMode;
+use crate::{
+    asset::manifest::fence_manifest::{FenceManifest, FenceModules},
+    core::GameState,
+    dynamic_mesh::DynamicMesh,
+    game_world::{
+        commands_history::{
+            CommandConfirmation, CommandId, CommandRequest, ConfirmableCommand, EntityRecorder,
+            PendingCommand,
+        },
+        navigation::Obstacle,
+        players::Players,
+        segment::{PointKind, Segment},
+        Layer,
+    },
+};
+use placing_fence::PlacingFencePlugin;
+
+pub(super) struct FencePlugin;
+
+impl Plugin for FencePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PlacingFencePlugin)
+            .add_sub_state::<FenceTool>()
+            .enable_state_scoped_entities::<FenceTool>()
+            .register_type::<Fence>()
+            .register_type::<Gate>()
+            .replicate::<Fence>()
+            .replicate::<Gate>()
+            .add_mapped_client_trigger::<CommandRequest<FenceCommand>>(ChannelKind::Unordered)
+            .add_observer(init)
+            .add_observer(apply_command)
+            .add_systems(
+                PostUpdate,
+                update_meshes.run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+fn init(
+    trigger: Trigger<OnAdd, Fence>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    manifests: Res<Assets<FenceManifest>>,
+    mut fences: Query<(
+        &Fence,
+        &mut FenceData,
+        &mut Mesh3d,
+        &mut MeshMaterial3d<StandardMaterial>,
+        Has<Gate>,
+    )>,
+) {
+    let (fence, mut fence_data, mut mesh, mut material, gate) =
+        fences.get_mut(trigger.entity()).unwrap();
+    let Some(manifest_handle) = asset_server.get_handle(&**fence) else {
+        error!("'{}' is missing, ignoring", &**fence);
+        return;
+    };
+
+    debug!(
+        "initializing fence '{}' for `{}`",
+        &**fence,
+        trigger.entity()
+    );
+
+    let manifest = manifests
+        .get(&manifest_handle)
+        .unwrap_or_else(|| panic!("'{:?}' should be loaded", &**fence));
+
+    fence_data.0 = manifest.modules.clone();
+    **mesh = meshes.add(DynamicMesh::create_empty());
+    **material = asset_server.load(manifest.material.clone());
+
+    // Actors should be able to walk through gates.
+    if !gate {
+        commands.entity(trigger.entity()).insert(Obstacle);
+    }
+}
+
+fn update_meshes(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut changed_fences: Query<
+        (&Mesh3d, &Segment, &FenceData, Has<Gate>, &mut Collider),
+        Or<(Changed<Segment>, Changed<FenceData>)>,
+    >,
+) {
+    for (mesh_handle, &segment, fence_data, gate, mut collider) in &mut changed_fences {
+        let Some(mesh) = meshes.get_mut(mesh_handle) else {
+            // Mesh is assigned after the manifest lookup in `init`.
+            continue;
+        };
+
+        trace!("regenerating fence mesh");
+        let mut dyn_mesh = DynamicMesh::take(mesh);
+        fence_mesh::generate(&mut dyn_mesh, segment, fence_data, gate);
+        dyn_mesh.apply(mesh);
+
+        *collider = fence_mesh::generate_collider(segment, fence_data);
+    }
+}
+
+fn apply_command(
+    trigger: Trigger<FromClient<CommandRequest<FenceCommand>>>,
+    mut commands: Commands,
+    mut fences: Query<&mut Segment, With<Fence>>,
+    players: Res<Players>,
+) {
+    if !players.can_build(trigger.client_id) {
+        error!("`{:?}` is not allowed to build", trigger.client_id);
+        return;
+    }
+
+    // TODO: validate if command can be applied.
+    let mut confirmation = CommandConfirmation::new(trigger.event.id);
+    match &trigger.event.command {
+        FenceCommand::Create {
+            city_entity,
+            manifest_path,
+            gate,
+            segment,
+        } => {
+            info!("`{:?}` spawns fence", trigger.client_id);
+            commands.entity(*city_entity).with_children(|parent| {
+                let mut entity = parent.spawn((Fence(manifest_path.clone()), *segment));
+                if *gate {
+                    entity.insert(Gate);
+                }
+                confirmation.entity = Some(entity.id());
+            });
+        }
+        FenceCommand::EditPoint {
+            entity,
+            kind,
+            point,
+        } => match fences.get_mut(*entity) {
+            Ok(mut segment) => {
+                info!(
+                    "`{:?}` edits `{kind:?}` for fence `{entity}`",
+                    trigger.client_id
+                );
+                segment.set_point(*kind, *point);
+            }
+            Err(e) => {
+                error!("unable to move fence `{entity}`: {e}");
+                return;
+            }
+        },
+        FenceCommand::Delete { entity } => {
+            info!("`{:?}` removes fence `{entity}`", trigger.client_id);
+            commands.entity(*entity).despawn();
+        }
+    }
+
+    commands.server_trigger(ToClients {
+        mode: SendMode::Direct(trigger.client_id),
+        event: confirmation,
+    });
+}
+
+#[derive(Clone, Component, Copy, Debug, Default, EnumIter, Eq, Hash, PartialEq, SubStates)]
+#[source(BuildingMode = BuildingMode::Fences)]
+pub enum FenceTool {
+    #[default]
+    Create,
+    Gate,
+    Move,
+}
+
+impl FenceTool {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Self::Create => "✏",
+            Self::Gate => "🚪",
+            Self::Move => "↔",
+        }
+    }
+}
+
+/// Stores path to the fence manifest.
+///
+/// Unlike walls, fences are not cut away and don't block the camera.
+#[derive(Component, Deserialize, Reflect, Serialize, Deref)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Fence")),
+    Segment,
+    ParentSync,
+    Replicated,
+    FenceData,
+    Mesh3d,
+    MeshMaterial3d::<StandardMaterial>,
+    Collider,
+    CollisionLayers(|| CollisionLayers::new(
+        Layer::Fence,
+        [
+            Layer::Object,
+            Layer::PlacingObject,
+            Layer::Road,
+            Layer::PlacingRoad,
+        ],
+    )),
+)]
+pub(crate) struct Fence(pub(crate) AssetPath<'static>);
+
+/// Marks a fence segment as a gate.
+///
+/// Gates don't affect the navigation mesh.
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub(crate) struct Gate;
+
+/// Stores fence modules from [`FenceManifest`] needed at runtime.
+#[derive(Component, Default, Deref)]
+pub(crate) struct FenceData(FenceModules);
+
+#[derive(Serialize, Deserialize, Clone)]
+enum FenceCommand {
+    Create {
+        city_entity: Entity,
+        manifest_path: AssetPath<'static>,
+        gate: bool,
+        segment: Segment,
+    },
+    EditPoint {
+        entity: Entity,
+        kind: PointKind,
+        point: Vec2,
+    },
+    Delete {
+        entity: Entity,
+    },
+}
+
+impl PendingCommand for FenceCommand {
+    fn apply(
+        self: Box<Self>,
+        id: CommandId,
+        mut recorder: EntityRecorder,
+        world: &mut World,
+    ) -> Box<dyn ConfirmableCommand> {
+        let reverse_command = match *self {
+            Self::Create { .. } => Self::Delete {
+                // Correct entity will be set after the server confirmation.
+                entity: Entity::PLACEHOLDER,
+            },
+            Self::EditPoint { entity, kind, .. } => {
+                let segment = world.get::<Segment>(entity).unwrap();
+                let point = segment.point(kind);
+                Self::EditPoint {
+                    entity,
+                    kind,
+                    point,
+                }
+            }
+            Self::Delete { entity } => {
+                recorder.record(entity);
+                let entity = world.entity(entity);
+                let fence = entity.get::<Fence>().unwrap();
+                let segment = *entity.get::<Segment>().unwrap();
+                let city_entity = **entity.get::<Parent>().unwrap();
+                Self::Create {
+                    city_entity,
+                    manifest_path: fence.0.clone(),
+                    gate: entity.contains::<Gate>(),
+                    segment,
+                }
+            }
+        };
+
+        world.client_trigger(CommandRequest { id, command: *self });
+
+        Box::new(reverse_command)
+    }
+}
+
+impl ConfirmableCommand for FenceCommand {
+    fn confirm(
+        mut self: Box<Self>,
+        mut recorder: EntityRecorder,
+        confirmation: CommandConfirmation,
+    ) -> Box<dyn PendingCommand> {
+        if let Self::Delete { entity } = &mut *self {
+            *entity = confirmation
+                .entity
+                .expect("confirmation for fence creation should contain an entity");
+            recorder.record(*entity);
+        }
+
+        self
+    }
+}
+
+impl MapEntities for FenceCommand {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        match self {
+            Self::Create { .. } => (),
+            Self::EditPoint { entity, .. } => *entity = entity_mapper.map_entity(*entity),
+            Self::Delete { entity } => *entity = entity_mapper.map_entity(*entity),
+        };
+    }
+}
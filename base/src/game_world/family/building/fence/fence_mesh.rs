@@ -0,0 +1,61 @@
+use avian3d::prelude::Collider;
+use bevy::prelude::*;
+
+use super::FenceData;
+use crate::{dynamic_mesh::DynamicMesh, game_world::segment::Segment};
+
+/// How much gate posts are taller than the fence.
+const GATE_POST_SCALE: f32 = 1.2;
+
+pub(super) fn generate(
+    mesh: &mut DynamicMesh,
+    segment: Segment,
+    fence_data: &FenceData,
+    gate: bool,
+) {
+    mesh.clear();
+
+    if segment.is_zero() {
+        return;
+    }
+
+    // Gates have posts only on the ends.
+    let len = segment.len();
+    let (spans, post_height) = if gate {
+        (1, fence_data.height * GATE_POST_SCALE)
+    } else {
+        let spans = (len / fence_data.post_spacing).ceil().max(1.0) as u32;
+        (spans, fence_data.height)
+    };
+
+    let step = len / spans as f32;
+    let half_post = fence_data.post_width / 2.0;
+    for index in 0..=spans {
+        let x = index as f32 * step;
+        mesh.add_cuboid(
+            Vec3::new(x - half_post, 0.0, -half_post),
+            Vec3::new(x + half_post, post_height, half_post),
+        );
+    }
+
+    let half_rail = fence_data.rail_thickness / 2.0;
+    for &height in &fence_data.rails {
+        mesh.add_cuboid(
+            Vec3::new(0.0, height - half_rail, -half_rail),
+            Vec3::new(len, height + half_rail, half_rail),
+        );
+    }
+}
+
+pub(super) fn generate_collider(segment: Segment, fence_data: &FenceData) -> Collider {
+    if segment.is_zero() {
+        return Default::default();
+    }
+
+    let len = segment.len();
+    Collider::compound(vec![(
+        Vec3::new(len / 2.0, fence_data.height / 2.0, 0.0),
+        Quat::IDENTITY,
+        Collider::cuboid(len, fence_data.height, fence_data.post_width),
+    )])
+}
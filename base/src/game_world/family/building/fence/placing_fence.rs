@@ -0,0 +1,276 @@
+use avian3d::prelude::*;
+use bevy::{
+    color::palettes::css::{RED, WHITE},
+    math::Vec3Swizzles,
+    prelude::*,
+    render::view::NoFrustumCulling,
+};
+use bevy_enhanced_input::prelude::*;
+
+use super::{Fence, FenceCommand, FenceData, FenceTool, Gate};
+use crate::{
+    alpha_color::{self, AlphaColor},
+    asset::manifest::fence_manifest::FenceManifest,
+    dynamic_mesh::DynamicMesh,
+    game_world::{
+        city::ActiveCity,
+        commands_history::{CommandsHistory, PendingDespawn},
+        family::building::BuildingMode,
+        segment::{
+            placing_segment::{ConfirmSegment, DeleteSegment, PlacingSegment},
+            ruler::Ruler,
+            PointKind, Segment,
+        },
+        Layer,
+    },
+    ghost::Ghost,
+};
+
+pub(super) struct PlacingFencePlugin;
+
+impl Plugin for PlacingFencePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(pick.never_param_warn())
+            .add_observer(spawn.never_param_warn())
+            .add_observer(delete.never_param_warn())
+            .add_observer(confirm.never_param_warn())
+            .add_systems(
+                PostUpdate,
+                update_alpha
+                    .never_param_warn()
+                    .before(alpha_color::update_materials)
+                    .run_if(in_state(BuildingMode::Fences)),
+            );
+    }
+}
+
+const SNAP_DELTA: f32 = 0.5;
+
+fn pick(
+    mut trigger: Trigger<Pointer<Click>>,
+    fence_tool: Res<State<FenceTool>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    fences: Query<(
+        Entity,
+        &Parent,
+        &MeshMaterial3d<StandardMaterial>,
+        &Segment,
+        &FenceData,
+        Has<Gate>,
+    )>,
+    placing_fences: Query<(), With<PlacingFence>>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    if *fence_tool != FenceTool::Move {
+        return;
+    }
+    if !placing_fences.is_empty() {
+        return;
+    }
+    let Ok((entity, parent, material, &segment, fence_data, gate)) = fences.get(trigger.entity())
+    else {
+        return;
+    };
+    trigger.propagate(false);
+
+    const PICK_DELTA: f32 = 0.4;
+    let point = trigger.hit.position.unwrap();
+    let point_kind = if segment.start.distance(point.xz()) < PICK_DELTA {
+        PointKind::Start
+    } else if segment.end.distance(point.xz()) < PICK_DELTA {
+        PointKind::End
+    } else {
+        return;
+    };
+
+    info!("picking `{point_kind:?}` for `{entity}`");
+    commands.entity(**parent).with_children(|parent| {
+        let mut placing_entity = parent.spawn((
+            Ghost::new(entity),
+            PlacingFence::EditPoint { entity },
+            StateScoped(FenceTool::Move),
+            FenceData(fence_data.0.clone()),
+            segment,
+            PlacingSegment {
+                point_kind,
+                snap_offset: SNAP_DELTA,
+            },
+            material.clone(),
+            Mesh3d(meshes.add(DynamicMesh::create_empty())),
+        ));
+        if gate {
+            placing_entity.insert(Gate);
+        }
+    });
+}
+
+fn spawn(
+    mut trigger: Trigger<Pointer<Click>>,
+    fence_tool: Res<State<FenceTool>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    asset_server: Res<AssetServer>,
+    manifests: Res<Assets<FenceManifest>>,
+    placing_id: Option<Res<SpawnFenceId>>,
+    city_entity: Single<Entity, With<ActiveCity>>,
+    fences: Query<(&Parent, &Segment), With<Fence>>,
+    placing_fences: Query<(), With<PlacingFence>>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    let gate = match **fence_tool {
+        FenceTool::Create => false,
+        FenceTool::Gate => true,
+        FenceTool::Move => return,
+    };
+    if !placing_fences.is_empty() {
+        return;
+    }
+    let Some(point) = trigger.hit.position else {
+        // Consider only world clicking.
+        return;
+    };
+    let Some(placing_id) = placing_id else {
+        return;
+    };
+
+    trigger.propagate(false);
+
+    let manifest = manifests
+        .get(placing_id.0)
+        .expect("manifests should be preloaded");
+
+    // Use an existing point if it is within the `SNAP_DELTA` distance.
+    let snapped_point = fences
+        .iter()
+        .filter(|(parent, _)| ***parent == *city_entity)
+        .flat_map(|(_, segment)| segment.points())
+        .find(|vertex| vertex.distance(point.xz()) < SNAP_DELTA)
+        .unwrap_or(point.xz());
+
+    info!("spawning new fence");
+    commands.entity(*city_entity).with_children(|parent| {
+        let mut placing_entity = parent.spawn((
+            PlacingFence::Spawning(placing_id.0),
+            StateScoped(**fence_tool),
+            FenceData(manifest.modules.clone()),
+            Segment::splat(snapped_point),
+            PlacingSegment {
+                point_kind: PointKind::End,
+                snap_offset: SNAP_DELTA,
+            },
+            Mesh3d(meshes.add(DynamicMesh::create_empty())),
+            MeshMaterial3d::<StandardMaterial>(asset_server.load(manifest.material.clone())),
+        ));
+        if gate {
+            placing_entity.insert(Gate);
+        }
+    });
+}
+
+fn update_alpha(
+    placing_fence: Single<
+        (&mut AlphaColor, &CollidingEntities),
+        (Changed<CollidingEntities>, With<PlacingFence>),
+    >,
+) {
+    let (mut alpha, colliding_entities) = placing_fence.into_inner();
+    if colliding_entities.is_empty() {
+        **alpha = WHITE.into();
+    } else {
+        **alpha = RED.into();
+    };
+}
+
+fn delete(
+    trigger: Trigger<Completed<DeleteSegment>>,
+    mut commands: Commands,
+    mut history: CommandsHistory,
+    placing_fence: Single<&PlacingFence>,
+) {
+    info!("deleting fence");
+    if let PlacingFence::EditPoint { entity } = **placing_fence {
+        let command_id = history.push_pending(FenceCommand::Delete { entity });
+        commands
+            .entity(trigger.entity())
+            .insert(PendingDespawn { command_id })
+            .remove::<(PlacingFence, Segment)>();
+    } else {
+        commands.entity(trigger.entity()).despawn_recursive();
+    }
+}
+
+fn confirm(
+    trigger: Trigger<Completed<ConfirmSegment>>,
+    mut commands: Commands,
+    mut history: CommandsHistory,
+    asset_server: Res<AssetServer>,
+    placing_fence: Single<(&Parent, &Segment, &PlacingFence, &PlacingSegment, Has<Gate>)>,
+) {
+    let (parent, &segment, &placing_fence, placing_segment, gate) = *placing_fence;
+
+    info!("confirming {placing_fence:?}");
+    let command_id = match placing_fence {
+        PlacingFence::Spawning(id) => {
+            let manifest_path = asset_server
+                .get_path(id)
+                .expect("manifest should always come from file");
+            history.push_pending(FenceCommand::Create {
+                city_entity: **parent,
+                manifest_path: manifest_path.into_owned(),
+                gate,
+                segment,
+            })
+        }
+        PlacingFence::EditPoint { entity } => {
+            let point = segment.point(placing_segment.point_kind);
+            history.push_pending(FenceCommand::EditPoint {
+                entity,
+                kind: placing_segment.point_kind,
+                point,
+            })
+        }
+    };
+
+    commands
+        .entity(trigger.entity())
+        .insert(PendingDespawn { command_id })
+        .remove::<(PlacingFence, Segment)>();
+}
+
+/// ID to spawn new fences with.
+///
+/// Spawning won't start until this resource is inserted.
+#[derive(Resource)]
+pub struct SpawnFenceId(pub AssetId<FenceManifest>);
+
+#[derive(Debug, Clone, Copy, Component)]
+#[require(
+    Name(|| Name::new("Placing fence")),
+    FenceData,
+    // Looks like AABB is not recalculated when we edit the mesh.
+    // But we don't need to cull currently placed fence anyway.
+    NoFrustumCulling,
+    Ruler,
+    AlphaColor(|| AlphaColor(WHITE.into())),
+    Mesh3d,
+    MeshMaterial3d::<StandardMaterial>,
+    Collider,
+    CollisionLayers(|| CollisionLayers::new(
+        Layer::PlacingFence,
+        [
+            Layer::Object,
+            Layer::PlacingObject,
+            Layer::Road,
+            Layer::PlacingRoad,
+        ],
+    )),
+)]
+enum PlacingFence {
+    Spawning(AssetId<FenceManifest>),
+    EditPoint { entity: Entity },
+}
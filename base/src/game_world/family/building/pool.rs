@@ -0,0 +1,503 @@
+use avian3d::prelude::*;
+use bevy::{
+    ecs::entity::MapEntities,
+    pbr::MaterialPlugin,
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef},
+};
+use bevy_enhanced_input::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::BuildingMode;
+use crate::{
+    game_world::{
+        city::ActiveCity,
+        commands_history::{
+            CommandConfirmation, CommandId, CommandRequest, CommandsHistory, ConfirmableCommand,
+            EntityRecorder, PendingCommand, PendingDespawn,
+        },
+        cursor_mode::PlacingCursor,
+        navigation::Obstacle,
+        player_camera::CameraCaster,
+        players::Players,
+        Layer,
+    },
+    ghost::Ghost,
+    settings::Settings,
+};
+
+pub(super) struct PoolPlugin;
+
+impl Plugin for PoolPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<WaterMaterial>::default())
+            .add_input_context::<PlacingPool>()
+            .init_resource::<PoolAssets>()
+            .register_type::<Pool>()
+            .replicate::<Pool>()
+            .add_mapped_client_trigger::<CommandRequest<PoolCommand>>(ChannelKind::Unordered)
+            .add_observer(init)
+            .add_observer(apply_command)
+            .add_observer(pick.never_param_warn())
+            .add_observer(spawn.never_param_warn())
+            .add_observer(delete.never_param_warn())
+            .add_observer(cancel)
+            .add_observer(confirm.never_param_warn())
+            .add_systems(
+                Update,
+                update_corner
+                    .never_param_warn()
+                    .run_if(in_state(BuildingMode::Pools)),
+            )
+            .add_systems(PostUpdate, update_transforms);
+    }
+}
+
+/// Pools are dug on a grid with this step.
+const GRID_STEP: f32 = 0.5;
+
+/// Minimum side of a pool.
+const MIN_SIZE: f32 = 1.0;
+
+/// Height of the water surface above the ground.
+///
+/// Prevents z-fighting with the ground plane.
+const WATER_OFFSET: f32 = 0.01;
+
+fn init(
+    trigger: Trigger<OnAdd, Pool>,
+    pool_assets: Res<PoolAssets>,
+    mut pools: Query<(&mut Mesh3d, &mut MeshMaterial3d<WaterMaterial>)>,
+) {
+    debug!("initializing pool `{}`", trigger.entity());
+    let (mut mesh, mut material) = pools.get_mut(trigger.entity()).unwrap();
+    mesh.0 = pool_assets.mesh.clone();
+    material.0 = pool_assets.material.clone();
+}
+
+fn update_transforms(
+    mut pools: Query<(&Pool, &mut Transform), Changed<Pool>>,
+    mut placing_pools: Query<(&PlacingPool, &mut Transform), (Changed<PlacingPool>, Without<Pool>)>,
+) {
+    for (pool, mut transform) in &mut pools {
+        *transform = rect_transform(pool.0);
+    }
+    for (placing_pool, mut transform) in &mut placing_pools {
+        *transform = rect_transform(placing_pool.rect());
+    }
+}
+
+/// Returns transform for the unit water plane that covers the rectangle.
+fn rect_transform(rect: Rect) -> Transform {
+    let center = rect.center();
+    let size = rect.size();
+    Transform::from_xyz(center.x, WATER_OFFSET, center.y).with_scale(Vec3::new(
+        size.x.max(0.01),
+        1.0,
+        size.y.max(0.01),
+    ))
+}
+
+fn apply_command(
+    trigger: Trigger<FromClient<CommandRequest<PoolCommand>>>,
+    mut commands: Commands,
+    players: Res<Players>,
+    mut pools: Query<&mut Pool>,
+) {
+    if !players.can_build(trigger.client_id) {
+        error!("`{:?}` is not allowed to build", trigger.client_id);
+        return;
+    }
+
+    let mut confirmation = CommandConfirmation::new(trigger.event.id);
+    match trigger.event.command {
+        PoolCommand::Create { city_entity, rect } => {
+            info!("`{:?}` digs pool", trigger.client_id);
+            commands.entity(city_entity).with_children(|parent| {
+                let entity = parent.spawn(Pool(rect)).id();
+                confirmation.entity = Some(entity);
+            });
+        }
+        PoolCommand::Edit { entity, rect } => match pools.get_mut(entity) {
+            Ok(mut pool) => {
+                info!("`{:?}` resizes pool `{entity}`", trigger.client_id);
+                pool.0 = rect;
+            }
+            Err(e) => {
+                error!("unable to resize pool `{entity}`: {e}");
+                return;
+            }
+        },
+        PoolCommand::Delete { entity } => {
+            if pools.get(entity).is_err() {
+                error!("unable to fill `{entity}`: not a pool");
+                return;
+            }
+            info!("`{:?}` fills pool `{entity}`", trigger.client_id);
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    commands.server_trigger(ToClients {
+        mode: SendMode::Direct(trigger.client_id),
+        event: confirmation,
+    });
+}
+
+/// Starts resizing the pool from the corner nearest to the cursor.
+fn pick(
+    mut trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    camera_caster: CameraCaster,
+    pool_assets: Res<PoolAssets>,
+    state: Res<State<BuildingMode>>,
+    pools: Query<(&Parent, &Pool)>,
+    placing_pools: Query<(), With<PlacingPool>>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    if *state != BuildingMode::Pools {
+        return;
+    }
+    if !placing_pools.is_empty() {
+        return;
+    }
+    let Ok((parent, pool)) = pools.get(trigger.entity()) else {
+        return;
+    };
+    let Some(point) = camera_caster.intersect_ground() else {
+        return;
+    };
+    trigger.propagate(false);
+
+    let Rect { min, max } = pool.0;
+    let corner = [min, max, Vec2::new(min.x, max.y), Vec2::new(max.x, min.y)]
+        .into_iter()
+        .min_by(|a, b| {
+            let a = a.distance_squared(point.xz());
+            let b = b.distance_squared(point.xz());
+            a.total_cmp(&b)
+        })
+        .unwrap();
+    let origin = min + max - corner;
+
+    info!("picking pool `{}`", trigger.entity());
+    commands.entity(**parent).with_children(|parent| {
+        parent.spawn((
+            Ghost::new(trigger.entity()),
+            PlacingPool {
+                kind: PlacingPoolKind::Editing(trigger.entity()),
+                origin,
+                corner,
+            },
+            Mesh3d(pool_assets.mesh.clone()),
+            MeshMaterial3d(pool_assets.material.clone()),
+        ));
+    });
+}
+
+fn spawn(
+    mut trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    camera_caster: CameraCaster,
+    pool_assets: Res<PoolAssets>,
+    state: Res<State<BuildingMode>>,
+    city_entity: Single<Entity, With<ActiveCity>>,
+    pools: Query<(), With<Pool>>,
+    placing_pools: Query<(), With<PlacingPool>>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    if *state != BuildingMode::Pools {
+        return;
+    }
+    if !placing_pools.is_empty() || pools.get(trigger.entity()).is_ok() {
+        // Existing pools are handled by `pick`.
+        return;
+    }
+    let Some(point) = camera_caster.intersect_ground() else {
+        return;
+    };
+    trigger.propagate(false);
+
+    let origin = round_to_grid(point.xz());
+    info!("spawning new pool at `{origin}`");
+    commands.entity(*city_entity).with_children(|parent| {
+        parent.spawn((
+            PlacingPool {
+                kind: PlacingPoolKind::Spawning,
+                origin,
+                corner: origin,
+            },
+            Mesh3d(pool_assets.mesh.clone()),
+            MeshMaterial3d(pool_assets.material.clone()),
+        ));
+    });
+}
+
+fn update_corner(camera_caster: CameraCaster, mut placing_pool: Single<&mut PlacingPool>) {
+    let Some(point) = camera_caster.intersect_ground() else {
+        return;
+    };
+
+    let corner = round_to_grid(point.xz());
+    if placing_pool.corner != corner {
+        trace!("updating pool corner to `{corner}`");
+        placing_pool.corner = corner;
+    }
+}
+
+fn round_to_grid(point: Vec2) -> Vec2 {
+    (point / GRID_STEP).round() * GRID_STEP
+}
+
+fn delete(
+    trigger: Trigger<Completed<DeletePool>>,
+    mut commands: Commands,
+    mut history: CommandsHistory,
+    placing_pool: Single<&PlacingPool>,
+) {
+    if let PlacingPoolKind::Editing(entity) = placing_pool.kind {
+        info!("filling pool `{entity}`");
+        let command_id = history.push_pending(PoolCommand::Delete { entity });
+        commands
+            .entity(trigger.entity())
+            .insert(PendingDespawn { command_id })
+            .remove::<PlacingPool>();
+    } else {
+        commands.entity(trigger.entity()).despawn_recursive();
+    }
+}
+
+fn cancel(trigger: Trigger<Completed<CancelPool>>, mut commands: Commands) {
+    debug!("cancelling pool placing");
+    commands.entity(trigger.entity()).despawn_recursive();
+}
+
+fn confirm(
+    trigger: Trigger<Completed<ConfirmPool>>,
+    mut commands: Commands,
+    mut history: CommandsHistory,
+    placing_pool: Single<(&Parent, &PlacingPool)>,
+) {
+    let (parent, placing_pool) = *placing_pool;
+    let rect = placing_pool.rect();
+    if rect.width() < MIN_SIZE || rect.height() < MIN_SIZE {
+        debug!("ignoring too small pool `{rect:?}`");
+        return;
+    }
+
+    info!("confirming pool `{rect:?}`");
+    let command_id = match placing_pool.kind {
+        PlacingPoolKind::Spawning => history.push_pending(PoolCommand::Create {
+            city_entity: **parent,
+            rect,
+        }),
+        PlacingPoolKind::Editing(entity) => {
+            history.push_pending(PoolCommand::Edit { entity, rect })
+        }
+    };
+
+    commands
+        .entity(trigger.entity())
+        .insert(PendingDespawn { command_id })
+        .remove::<PlacingPool>();
+}
+
+#[derive(Resource)]
+struct PoolAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<WaterMaterial>,
+}
+
+impl FromWorld for PoolAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Plane3d::default().mesh().size(1.0, 1.0));
+        let material = world
+            .resource_mut::<Assets<WaterMaterial>>()
+            .add(WaterMaterial {
+                color: LinearRgba::new(0.1, 0.45, 0.7, 0.8),
+            });
+
+        Self { mesh, material }
+    }
+}
+
+/// Water surface with animated ripples.
+#[derive(Asset, AsBindGroup, Clone, TypePath)]
+pub(crate) struct WaterMaterial {
+    #[uniform(0)]
+    color: LinearRgba,
+}
+
+impl Material for WaterMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "base/shaders/water.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+}
+
+/// A rectangular pool dug into the ground.
+///
+/// Stores its area in city coordinates.
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Pool")),
+    ParentSync,
+    Replicated,
+    Transform,
+    Visibility,
+    Mesh3d,
+    MeshMaterial3d::<WaterMaterial>,
+    Obstacle,
+    Collider(|| Collider::cuboid(1.0, 0.1, 1.0)),
+    CollisionLayers(|| CollisionLayers::new(
+        Layer::Wall,
+        [
+            Layer::Object,
+            Layer::PlacingObject,
+            Layer::Road,
+            Layer::PlacingRoad,
+        ],
+    )),
+)]
+pub(crate) struct Pool(pub(crate) Rect);
+
+#[derive(Component, Clone, Copy)]
+#[require(
+    Name(|| Name::new("Placing pool")),
+    PlacingCursor,
+    StateScoped::<BuildingMode>(|| StateScoped(BuildingMode::Pools)),
+    Transform,
+    Visibility,
+    Mesh3d,
+    MeshMaterial3d::<WaterMaterial>,
+)]
+struct PlacingPool {
+    kind: PlacingPoolKind,
+    /// Fixed corner.
+    origin: Vec2,
+    /// Corner that follows the cursor.
+    corner: Vec2,
+}
+
+impl PlacingPool {
+    fn rect(&self) -> Rect {
+        Rect::from_corners(self.origin, self.corner)
+    }
+}
+
+impl InputContext for PlacingPool {
+    const PRIORITY: isize = 1;
+
+    fn context_instance(world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        let settings = world.resource::<Settings>();
+
+        ctx.bind::<DeletePool>()
+            .to((&settings.keyboard.delete, &settings.gamepad.delete));
+        ctx.bind::<CancelPool>()
+            .to((KeyCode::Escape, &settings.gamepad.cancel));
+        ctx.bind::<ConfirmPool>()
+            .to((MouseButton::Left, &settings.gamepad.confirm));
+
+        ctx
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum PlacingPoolKind {
+    Spawning,
+    Editing(Entity),
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct DeletePool;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct CancelPool;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct ConfirmPool;
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+enum PoolCommand {
+    Create { city_entity: Entity, rect: Rect },
+    Edit { entity: Entity, rect: Rect },
+    Delete { entity: Entity },
+}
+
+impl PendingCommand for PoolCommand {
+    fn apply(
+        self: Box<Self>,
+        id: CommandId,
+        mut recorder: EntityRecorder,
+        world: &mut World,
+    ) -> Box<dyn ConfirmableCommand> {
+        let reverse_command = match *self {
+            Self::Create { .. } => Self::Delete {
+                // Correct entity will be set after the server confirmation.
+                entity: Entity::PLACEHOLDER,
+            },
+            Self::Edit { entity, .. } => {
+                let pool = world.get::<Pool>(entity).unwrap();
+                Self::Edit {
+                    entity,
+                    rect: pool.0,
+                }
+            }
+            Self::Delete { entity } => {
+                recorder.record(entity);
+                let entity = world.entity(entity);
+                let rect = entity.get::<Pool>().unwrap().0;
+                let city_entity = **entity.get::<Parent>().unwrap();
+                Self::Create { city_entity, rect }
+            }
+        };
+
+        world.client_trigger(CommandRequest { id, command: *self });
+
+        Box::new(reverse_command)
+    }
+}
+
+impl ConfirmableCommand for PoolCommand {
+    fn confirm(
+        mut self: Box<Self>,
+        mut recorder: EntityRecorder,
+        confirmation: CommandConfirmation,
+    ) -> Box<dyn PendingCommand> {
+        if let Self::Delete { entity } = &mut *self {
+            *entity = confirmation
+                .entity
+                .expect("confirmation for pool creation should contain an entity");
+            recorder.record(*entity);
+        }
+
+        self
+    }
+}
+
+impl MapEntities for PoolCommand {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        match self {
+            Self::Create { city_entity, .. } => {
+                *city_entity = entity_mapper.map_entity(*city_entity)
+            }
+            Self::Edit { entity, .. } => *entity = entity_mapper.map_entity(*entity),
+            Self::Delete { entity } => *entity = entity_mapper.map_entity(*entity),
+        };
+    }
+}
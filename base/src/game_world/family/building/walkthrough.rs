@@ -0,0 +1,155 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+use crate::{
+    game_world::{
+        actor::{SelectedActor, ACTOR_HEIGHT, ACTOR_RADIUS},
+        family::FamilyMode,
+        player_camera::PlayerCamera,
+        Layer,
+    },
+    settings::Settings,
+};
+
+pub(super) struct WalkthroughPlugin;
+
+impl Plugin for WalkthroughPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_input_context::<WalkthroughActor>()
+            .add_observer(start)
+            .add_observer(walk)
+            .add_observer(exit);
+    }
+}
+
+/// Spawns a test actor next to the selected actor.
+fn start(
+    _trigger: Trigger<WalkthroughStart>,
+    mut commands: Commands,
+    walkthrough_actors: Query<(), With<WalkthroughActor>>,
+    selected_actor: Single<(&Parent, &Transform, &SceneRoot), With<SelectedActor>>,
+) {
+    if !walkthrough_actors.is_empty() {
+        debug!("ignoring walkthrough start because it's already active");
+        return;
+    }
+
+    info!("starting walkthrough");
+    let (parent, transform, scene_root) = *selected_actor;
+    commands.entity(**parent).with_children(|parent| {
+        parent.spawn((
+            WalkthroughActor,
+            Transform::from_translation(transform.translation),
+            scene_root.clone(),
+        ));
+    });
+}
+
+fn walk(
+    trigger: Trigger<Fired<WalkthroughMove>>,
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    camera_transform: Single<&Transform, (With<PlayerCamera>, Without<WalkthroughActor>)>,
+    walkthrough_actor: Single<
+        (&mut Transform, &GlobalTransform, &Collider),
+        With<WalkthroughActor>,
+    >,
+) {
+    // Calculate direction without camera's tilt.
+    let forward = camera_transform.forward();
+    let camera_dir = Vec3::new(forward.x, 0.0, forward.z).normalize();
+    let rotation = Quat::from_rotation_arc(Vec3::NEG_Z, camera_dir);
+
+    // Movement consists of X and -Z components, so swap Y and Z with negation.
+    let mut movement = trigger.value.extend(0.0).xzy();
+    movement.z = -movement.z;
+
+    let Ok(direction) = Dir3::new(rotation * movement) else {
+        return;
+    };
+
+    let (mut transform, global_transform, collider) = walkthrough_actor.into_inner();
+    let mut distance = WALK_SPEED * time.delta_secs();
+    if let Some(hit) = spatial_query.cast_shape(
+        collider,
+        global_transform.translation(),
+        Quat::IDENTITY,
+        direction,
+        &ShapeCastConfig::from_max_distance(distance),
+        &SpatialQueryFilter::from_mask([Layer::Wall, Layer::Object]),
+    ) {
+        trace!("walkthrough actor blocked by `{}`", hit.entity);
+        distance = hit.distance;
+    }
+
+    transform.translation += direction * distance;
+    transform.look_to(direction, Vec3::Y);
+}
+
+fn exit(
+    _trigger: Trigger<Started<WalkthroughExit>>,
+    mut commands: Commands,
+    actor_entity: Single<Entity, With<WalkthroughActor>>,
+) {
+    info!("exiting walkthrough");
+    commands.entity(*actor_entity).despawn_recursive();
+}
+
+const WALK_SPEED: f32 = 2.0;
+
+/// Spawns an actor for previewing the house.
+#[derive(Event)]
+pub struct WalkthroughStart;
+
+/// A local actor for checking a building from inside.
+///
+/// It's not replicated, not a family member and doesn't have needs or tasks.
+/// Automatically despawns on exiting building mode.
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Walkthrough actor")),
+    StateScoped::<FamilyMode>(|| StateScoped(FamilyMode::Building)),
+    SceneRoot,
+    Collider(|| Collider::capsule_endpoints(
+        ACTOR_RADIUS,
+        Vec3::Y * ACTOR_RADIUS,
+        Vec3::Y * (ACTOR_HEIGHT - ACTOR_RADIUS),
+    )),
+    CollisionLayers(|| CollisionLayers::new(LayerMask::NONE, LayerMask::NONE)),
+)]
+pub struct WalkthroughActor;
+
+impl InputContext for WalkthroughActor {
+    // Above placing cursors and the camera to consume movement and exit keys.
+    const PRIORITY: isize = 2;
+
+    fn context_instance(world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        let settings = world.resource::<Settings>();
+
+        ctx.bind::<WalkthroughMove>()
+            .to((
+                Cardinal {
+                    north: &settings.keyboard.camera_forward,
+                    east: &settings.keyboard.camera_left,
+                    south: &settings.keyboard.camera_backward,
+                    west: &settings.keyboard.camera_right,
+                },
+                GamepadStick::Left,
+            ))
+            .with_modifiers(DeadZone::default());
+        ctx.bind::<WalkthroughExit>()
+            .to((KeyCode::Escape, &settings.gamepad.cancel));
+
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = Vec2)]
+struct WalkthroughMove;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct WalkthroughExit;
@@ -1,15 +1,25 @@
+mod cutaway;
 pub mod placing_wall;
 mod triangulator;
 pub(crate) mod wall_mesh;
 
+use std::time::Duration;
+
 use avian3d::prelude::*;
-use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy::{
+    diagnostic::Diagnostics,
+    ecs::entity::MapEntities,
+    prelude::*,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+    utils::Instant,
+};
 use bevy_replicon::prelude::*;
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
 use super::BuildingMode;
 use crate::{
+    achievements::{Counter, CounterIncrease},
     core::GameState,
     dynamic_mesh::DynamicMesh,
     game_world::{
@@ -17,11 +27,15 @@ use crate::{
             CommandConfirmation, CommandId, CommandRequest, ConfirmableCommand, EntityRecorder,
             PendingCommand,
         },
+        family::FamilyOwner,
         navigation::Obstacle,
+        players::Players,
         segment::{self, PointKind, Segment, SegmentConnections},
         Layer,
     },
+    profiling::WALL_MESHING,
 };
+use cutaway::CutawayPlugin;
 use placing_wall::PlacingWallPlugin;
 use triangulator::Triangulator;
 
@@ -29,7 +43,7 @@ pub(super) struct WallPlugin;
 
 impl Plugin for WallPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(PlacingWallPlugin)
+        app.add_plugins((CutawayPlugin, PlacingWallPlugin))
             .add_sub_state::<WallTool>()
             .enable_state_scoped_entities::<WallTool>()
             .init_resource::<WallMaterial>()
@@ -40,8 +54,11 @@ impl Plugin for WallPlugin {
             .add_observer(apply_command)
             .add_systems(
                 PostUpdate,
-                update_meshes
-                    .after(segment::update_connections)
+                (
+                    update_meshes.after(segment::update_connections),
+                    apply_meshes,
+                )
+                    .chain()
                     .run_if(in_state(GameState::InGame)),
             );
     }
@@ -59,12 +76,15 @@ fn init(
     *material = wall_material.0.clone();
 }
 
+/// Spawns tasks to regenerate meshes of changed walls.
+///
+/// Inserting a new task drops the previous one, which cancels it.
+/// So rapid consecutive edits are coalesced into a single mesh update.
 pub(crate) fn update_meshes(
-    mut triangulator: Local<Triangulator>,
-    mut meshes: ResMut<Assets<Mesh>>,
+    mut commands: Commands,
     mut changed_walls: Query<
         (
-            &Mesh3d,
+            Entity,
             Ref<Segment>,
             &SegmentConnections,
             &mut Apertures,
@@ -73,22 +93,28 @@ pub(crate) fn update_meshes(
         Or<(Changed<SegmentConnections>, Changed<Apertures>)>,
     >,
 ) {
-    for (mesh_handle, segment, connections, mut apertures, mut collider) in &mut changed_walls {
-        let mesh = meshes
-            .get_mut(mesh_handle)
-            .expect("wall handles should be valid");
-
-        trace!("regenerating wall mesh");
-        let mut dyn_mesh = DynamicMesh::take(mesh);
-        wall_mesh::generate(
-            &mut dyn_mesh,
-            *segment,
-            connections,
-            &apertures,
-            &mut triangulator,
-        );
-        dyn_mesh.apply(mesh);
+    let task_pool = AsyncComputeTaskPool::get();
+    for (entity, segment, connections, mut apertures, mut collider) in &mut changed_walls {
+        trace!("scheduling mesh regeneration for wall `{entity}`");
+        let segment_copy = *segment;
+        let connections_copy = connections.clone();
+        let apertures_copy = apertures.clone();
+        let task = task_pool.spawn(async move {
+            let _span = info_span!("wall_meshing").entered();
+            let start = Instant::now();
+            let mut dyn_mesh = DynamicMesh::default();
+            wall_mesh::generate(
+                &mut dyn_mesh,
+                segment_copy,
+                &connections_copy,
+                &apertures_copy,
+                &mut Triangulator::default(),
+            );
+            (dyn_mesh, start.elapsed())
+        });
+        commands.entity(entity).insert(WallMeshTask(task));
 
+        // Colliders are cheap and required immediately for placement checks.
         if apertures.collision_outdated || segment.is_changed() || collider.is_added() {
             trace!("regenerating wall collision");
             *collider = wall_mesh::generate_collider(*segment, &apertures);
@@ -97,11 +123,38 @@ pub(crate) fn update_meshes(
     }
 }
 
+fn apply_meshes(
+    mut commands: Commands,
+    mut diagnostics: Diagnostics,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut walls: Query<(Entity, &Mesh3d, &mut WallMeshTask)>,
+) {
+    for (entity, mesh_handle, mut task) in &mut walls {
+        let Some((dyn_mesh, duration)) = block_on(future::poll_once(&mut task.0)) else {
+            continue;
+        };
+
+        trace!("applying regenerated mesh for wall `{entity}`");
+        let mesh = meshes
+            .get_mut(mesh_handle)
+            .expect("wall handles should be valid");
+        dyn_mesh.apply(mesh);
+        diagnostics.add_measurement(&WALL_MESHING, || duration.as_secs_f64() * 1000.0);
+        commands.entity(entity).remove::<WallMeshTask>();
+    }
+}
+
 fn apply_command(
     trigger: Trigger<FromClient<CommandRequest<WallCommand>>>,
     mut commands: Commands,
     mut walls: Query<&mut Segment, With<Wall>>,
+    players: Res<Players>,
 ) {
+    if !players.can_build(trigger.client_id) {
+        error!("`{:?}` is not allowed to build", trigger.client_id);
+        return;
+    }
+
     // TODO: validate if command can be applied.
     let mut confirmation = CommandConfirmation::new(trigger.event.id);
     match trigger.event.command {
@@ -111,9 +164,13 @@ fn apply_command(
         } => {
             info!("`{:?}` creates wall", trigger.client_id);
             commands.entity(city_entity).with_children(|parent| {
-                let entity = parent.spawn((Wall, segment)).id();
-                confirmation.entity = Some(entity);
+                let mut entity = parent.spawn((Wall, segment));
+                if let Some(family_entity) = players.family(trigger.client_id) {
+                    entity.insert(FamilyOwner(family_entity));
+                }
+                confirmation.entity = Some(entity.id());
             });
+            commands.trigger(CounterIncrease(Counter::WallsBuilt));
         }
         WallCommand::EditPoint {
             entity,
@@ -144,8 +201,29 @@ fn apply_command(
     });
 }
 
+/// Height above the ground from which walls around a point are searched.
+const INDOOR_RAY_HEIGHT: f32 = 0.5;
+
+/// Maximum distance to a wall in each direction for a place to be considered indoors.
+const INDOOR_DISTANCE: f32 = 20.0;
+
+/// Returns `true` if the point on the ground is surrounded by walls.
+///
+/// There are no rooms, so a place is considered indoors when walls are found in all directions.
+pub(crate) fn is_indoors(spatial_query: &SpatialQuery, point: Vec3) -> bool {
+    let origin = point + Vec3::Y * INDOOR_RAY_HEIGHT;
+    let filter = SpatialQueryFilter::from_mask(Layer::Wall);
+    [Dir3::X, Dir3::NEG_X, Dir3::Z, Dir3::NEG_Z]
+        .into_iter()
+        .all(|direction| {
+            spatial_query
+                .cast_ray(origin, direction, INDOOR_DISTANCE, true, &filter)
+                .is_some()
+        })
+}
+
 #[derive(Resource)]
-struct WallMaterial(MeshMaterial3d<StandardMaterial>);
+pub(crate) struct WallMaterial(pub(crate) MeshMaterial3d<StandardMaterial>);
 
 impl FromWorld for WallMaterial {
     fn from_world(world: &mut World) -> Self {
@@ -193,12 +271,16 @@ impl WallTool {
         ],
     )),
 )]
-pub(crate) struct Wall;
+pub struct Wall;
+
+/// Pending mesh generation for a wall.
+#[derive(Component)]
+struct WallMeshTask(Task<(DynamicMesh, Duration)>);
 
 /// Dynamically updated component with precalculated apertures for wall objects.
 ///
 /// Apertures are sorted by distance to the wall starting point.
-#[derive(Component, Default)]
+#[derive(Clone, Component, Default)]
 pub(crate) struct Apertures {
     apertures: Vec<Aperture>,
     collision_outdated: bool,
@@ -238,6 +320,7 @@ impl Apertures {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct Aperture {
     /// The entity that cut this aperture.
     pub(crate) object_entity: Entity,
@@ -0,0 +1,92 @@
+use avian3d::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
+
+use super::{Wall, WallMaterial};
+use crate::{
+    core::GameState,
+    game_world::{player_camera::PlayerCamera, Layer},
+    settings::{Settings, WallView},
+};
+
+pub(super) struct CutawayPlugin;
+
+impl Plugin for CutawayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_materials
+                .never_param_warn()
+                .run_if(in_state(GameState::InGame)),
+        );
+    }
+}
+
+/// Switches walls that block the camera view to a transparent material.
+fn update_materials(
+    mut cutaway_material: Local<Option<Handle<StandardMaterial>>>,
+    settings: Res<Settings>,
+    wall_material: Res<WallMaterial>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    spatial_query: SpatialQuery,
+    camera_transform: Single<&GlobalTransform, With<PlayerCamera>>,
+    mut walls: Query<(Entity, &mut MeshMaterial3d<StandardMaterial>), With<Wall>>,
+) {
+    if cutaway_material.is_none() {
+        let Some(material) = materials.get(&*wall_material.0) else {
+            return;
+        };
+
+        debug!("creating cutaway material");
+        let mut material = material.clone();
+        material.base_color.set_alpha(CUTAWAY_ALPHA);
+        material.alpha_mode = AlphaMode::Blend;
+        *cutaway_material = Some(materials.add(material));
+    }
+    let cutaway_material = cutaway_material.as_ref().unwrap();
+
+    let cut_entities: HashSet<_> = match settings.camera.wall_view {
+        WallView::Up => Default::default(),
+        WallView::Down => walls.iter().map(|(entity, _)| entity).collect(),
+        WallView::Auto => {
+            let origin = camera_transform.translation();
+            let direction = camera_transform.forward();
+            let ray = Ray3d::new(origin, direction);
+            match ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y)) {
+                Some(distance) => spatial_query
+                    .shape_hits(
+                        &Collider::sphere(CUTAWAY_RADIUS),
+                        origin,
+                        Quat::IDENTITY,
+                        direction,
+                        MAX_CUT_WALLS,
+                        &ShapeCastConfig::from_max_distance(distance),
+                        &SpatialQueryFilter::from_mask(Layer::Wall),
+                    )
+                    .into_iter()
+                    .map(|hit| hit.entity)
+                    .collect(),
+                None => Default::default(),
+            }
+        }
+    };
+
+    for (entity, mut material) in &mut walls {
+        let expected = if cut_entities.contains(&entity) {
+            cutaway_material
+        } else {
+            &*wall_material.0
+        };
+
+        if material.0 != *expected {
+            trace!("updating cutaway for wall `{entity}`");
+            material.0 = expected.clone();
+        }
+    }
+}
+
+/// Radius around the camera view line in which walls are cut.
+const CUTAWAY_RADIUS: f32 = 1.5;
+
+const MAX_CUT_WALLS: u32 = 32;
+
+const CUTAWAY_ALPHA: f32 = 0.2;
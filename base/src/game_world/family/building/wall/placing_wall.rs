@@ -10,6 +10,7 @@ use bevy_enhanced_input::prelude::*;
 use super::{Wall, WallCommand, WallMaterial, WallTool};
 use crate::{
     alpha_color::{self, AlphaColor},
+    audio::{Sound, SoundPlay},
     dynamic_mesh::DynamicMesh,
     game_world::{
         city::ActiveCity,
@@ -83,7 +84,7 @@ fn pick(
         parent.spawn((
             Ghost::new(entity),
             PlacingWall::EditingPoint { entity },
-            WallTool::Move,
+            StateScoped(WallTool::Move),
             segment,
             PlacingSegment {
                 point_kind,
@@ -133,7 +134,7 @@ fn spawn(
     commands.entity(*city_entity).with_children(|parent| {
         parent.spawn((
             PlacingWall::Spawning,
-            WallTool::Create,
+            StateScoped(WallTool::Create),
             Segment::splat(snapped_point),
             PlacingSegment {
                 point_kind: PointKind::End,
@@ -205,6 +206,12 @@ fn confirm(
         .entity(trigger.entity())
         .insert(PendingDespawn { command_id })
         .remove::<(PlacingWall, Segment)>();
+
+    let center = (segment.start + segment.end) / 2.0;
+    commands.trigger_targets(
+        SoundPlay::new(Sound::BuildWall).with_translation(Vec3::new(center.x, 0.0, center.y)),
+        **parent,
+    );
 }
 
 #[derive(Debug, Clone, Copy, Component)]
@@ -9,7 +9,7 @@ use crate::{
 };
 
 const WIDTH: f32 = 0.15;
-const HEIGHT: f32 = 2.8;
+pub(crate) const HEIGHT: f32 = 2.8;
 pub(crate) const HALF_WIDTH: f32 = WIDTH / 2.0;
 
 pub(super) fn generate(
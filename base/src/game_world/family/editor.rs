@@ -1,12 +1,23 @@
 use std::fmt::Write;
 
 use bevy::prelude::*;
-
-use crate::game_world::{
-    actor::{human::EditorHuman, SelectedActor},
-    family::{FamilyMembers, SelectedFamilyCreated},
-    player_camera::PlayerCamera,
-    WorldState,
+use rand::{seq::IteratorRandom, Rng};
+use strum::IntoEnumIterator;
+
+use crate::{
+    asset::manifest::clothing_manifest::{BodySlot, ClothingManifest},
+    game_world::{
+        actor::{
+            human::EditorHuman,
+            name_generator::NameGenerator,
+            outfit::{ActiveOutfit, Outfit, Outfits},
+            SelectedActor, Sex,
+        },
+        family::{FamilyAppearance, FamilyMembers, SelectedFamilyCreated},
+        loading::LoadingStart,
+        player_camera::PlayerCamera,
+        WorldState,
+    },
 };
 
 pub(super) struct EditorPlugin;
@@ -14,6 +25,7 @@ pub(super) struct EditorPlugin;
 impl Plugin for EditorPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(reset_family)
+            .add_observer(randomize_family)
             .add_observer(show)
             .add_observer(hide)
             .add_observer(play)
@@ -51,7 +63,7 @@ fn play(
             .first()
             .expect("family should always have at least one member");
         commands.entity(actor_entity).insert(SelectedActor);
-        commands.set_state(WorldState::Family);
+        commands.trigger(LoadingStart(WorldState::Family));
     } else {
         error!("received create confirmation for invalid family");
     }
@@ -74,6 +86,53 @@ fn reset_family(
     });
 }
 
+/// Rolls names, sex and everyday outfit for all editing actors.
+///
+/// All members share the same last name.
+fn randomize_family(
+    _trigger: Trigger<EditorFamilyRandomize>,
+    name_generator: NameGenerator,
+    asset_server: Res<AssetServer>,
+    clothing_manifests: Res<Assets<ClothingManifest>>,
+    mut actors: Query<
+        (
+            &mut EditorFirstName,
+            &mut EditorLastName,
+            &mut EditorSex,
+            &mut Outfits,
+        ),
+        With<EditorActor>,
+    >,
+) {
+    info!("randomizing family");
+    let mut rng = rand::thread_rng();
+    let last_name = name_generator.last_name(&mut rng);
+    for (mut first_name, mut editor_last_name, mut editor_sex, mut outfits) in &mut actors {
+        let sex: Sex = rng.gen();
+        first_name.0 = name_generator.first_name(&mut rng, sex);
+        editor_last_name.0.clone_from(&last_name);
+        *editor_sex = sex.into();
+
+        let mut outfit = Outfit::default();
+        for slot in BodySlot::iter() {
+            if outfit.item(slot).is_some() {
+                continue;
+            }
+
+            if let Some((id, manifest)) = clothing_manifests
+                .iter()
+                .filter(|(_, manifest)| manifest.slots.contains(&slot))
+                .choose(&mut rng)
+            {
+                if let Some(path) = asset_server.get_path(id) {
+                    outfit.put_on(path.into_owned(), &manifest.slots);
+                }
+            }
+        }
+        outfits.everyday = outfit;
+    }
+}
+
 fn update_names(
     mut changed_names: Query<
         (Entity, &EditorFirstName, &EditorLastName, &mut Name),
@@ -107,13 +166,22 @@ fn hide(trigger: Trigger<OnRemove, EditorSelectedActor>, mut actors: Query<&mut
     Name(|| Name::new("Editor family")),
     Transform,
     Visibility,
+    FamilyAppearance,
     StateScoped::<WorldState>(|| StateScoped(WorldState::FamilyEditor))
 )]
 pub struct EditorFamily;
 
 /// Component for a actor inside the editor.
 #[derive(Component, Default)]
-#[require(EditorFirstName, EditorLastName, EditorSex, SceneRoot, EditorHuman)] // TODO: Select race.
+#[require(
+    EditorFirstName,
+    EditorLastName,
+    EditorSex,
+    Outfits,
+    ActiveOutfit,
+    SceneRoot,
+    EditorHuman
+)] // TODO: Select race.
 pub struct EditorActor;
 
 #[derive(Component, Default, Deref, DerefMut, Clone)]
@@ -129,10 +197,23 @@ pub enum EditorSex {
     Female,
 }
 
+impl From<Sex> for EditorSex {
+    fn from(value: Sex) -> Self {
+        match value {
+            Sex::Male => Self::Male,
+            Sex::Female => Self::Female,
+        }
+    }
+}
+
 /// Event that resets currently editing family.
 #[derive(Event)]
 pub struct EditorFamilyReset;
 
+/// Event that randomizes currently editing family.
+#[derive(Event)]
+pub struct EditorFamilyRandomize;
+
 /// Indicates currently editing actor.
 #[derive(Component)]
 #[require(EditorActor)]
@@ -142,13 +223,15 @@ pub struct EditorSelectedActor;
 pub struct FamilyScene {
     pub name: String,
     pub actors: Vec<Box<dyn ActorBundle>>,
+    pub appearance: FamilyAppearance,
 }
 
 impl FamilyScene {
-    pub fn new(name: String) -> Self {
+    pub fn new(name: String, appearance: FamilyAppearance) -> Self {
         Self {
             name,
             actors: Default::default(),
+            appearance,
         }
     }
 }
@@ -157,4 +240,7 @@ impl FamilyScene {
 pub trait ActorBundle: Reflect {
     #[allow(dead_code)]
     fn glyph(&self) -> &'static str;
+
+    /// Inserts editor components that represent this actor into [`EditorActor`].
+    fn insert_editor(&self, entity: &mut EntityCommands);
 }
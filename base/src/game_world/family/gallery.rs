@@ -0,0 +1,128 @@
+pub mod package;
+
+use std::fs;
+
+use anyhow::{ensure, Context, Result};
+use bevy::prelude::*;
+
+use super::editor::{EditorActor, EditorFamily, EditorSelectedActor, FamilyScene};
+use crate::{
+    error_message::error_message,
+    game_paths::{backup_corrupted, write_atomic, GamePaths},
+    notification::Notification,
+};
+use package::PackagePlugin;
+
+/// Stores family templates on disk to reuse them in any world.
+pub(super) struct GalleryPlugin;
+
+impl Plugin for GalleryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PackagePlugin)
+            .add_observer(save.pipe(error_message))
+            .add_observer(load.pipe(error_message))
+            .add_observer(delete.pipe(error_message));
+    }
+}
+
+fn save(
+    _trigger: Trigger<GallerySave>,
+    mut commands: Commands,
+    family_scene: Res<FamilyScene>,
+    registry: Res<AppTypeRegistry>,
+    game_paths: Res<GamePaths>,
+) -> Result<()> {
+    let family_path = game_paths.family_path(&family_scene.name);
+    info!("saving family to {family_path:?}");
+
+    let mut bytes = Vec::new();
+    super::serialize_scene(&mut bytes, &family_scene, &registry.read())
+        .with_context(|| format!("unable to serialize family '{}'", family_scene.name))?;
+    write_atomic(&family_path, bytes)
+        .with_context(|| format!("unable to save family to {family_path:?}"))?;
+
+    commands.trigger(Notification::info("Family saved to gallery"));
+    commands.trigger(GalleryChanged);
+
+    Ok(())
+}
+
+/// Replaces editing actors with actors from the saved family.
+fn load(
+    trigger: Trigger<GalleryLoad>,
+    mut commands: Commands,
+    registry: Res<AppTypeRegistry>,
+    game_paths: Res<GamePaths>,
+    family_entity: Single<Entity, With<EditorFamily>>,
+    actors: Query<Entity, With<EditorActor>>,
+) -> Result<()> {
+    let family_path = game_paths.family_path(&trigger.0);
+    info!("loading family from {family_path:?}");
+
+    let bytes =
+        fs::read(&family_path).with_context(|| format!("unable to load {family_path:?}"))?;
+    let scene = super::deserialize_scene(&*bytes, &registry.read()).or_else(|e| {
+        let backup_path = backup_corrupted(&family_path)?;
+        commands.trigger(GalleryChanged);
+        Err(e).with_context(|| {
+            format!("unable to deserialize {family_path:?}, moved to {backup_path:?}")
+        })
+    })?;
+    ensure!(!scene.actors.is_empty(), "{family_path:?} has no actors");
+
+    for entity in &actors {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    commands
+        .entity(*family_entity)
+        .insert(scene.appearance)
+        .with_children(|parent| {
+            for (index, actor) in scene.actors.iter().enumerate() {
+                let mut entity = if index == 0 {
+                    parent.spawn(EditorSelectedActor)
+                } else {
+                    parent.spawn(EditorActor)
+                };
+                actor.insert_editor(&mut entity);
+            }
+        });
+
+    Ok(())
+}
+
+fn delete(
+    trigger: Trigger<GalleryDelete>,
+    mut commands: Commands,
+    game_paths: Res<GamePaths>,
+) -> Result<()> {
+    let family_path = game_paths.family_path(&trigger.0);
+    info!("deleting {family_path:?}");
+    fs::remove_file(&family_path).with_context(|| format!("unable to remove {family_path:?}"))?;
+
+    let preview_path = game_paths.family_preview_path(&trigger.0);
+    if preview_path.exists() {
+        fs::remove_file(&preview_path)
+            .with_context(|| format!("unable to remove {preview_path:?}"))?;
+    }
+
+    commands.trigger(GalleryChanged);
+
+    Ok(())
+}
+
+/// Saves [`FamilyScene`] to the gallery under its name.
+#[derive(Event)]
+pub struct GallerySave;
+
+/// Loads a family from the gallery by name into the editor.
+#[derive(Event)]
+pub struct GalleryLoad(pub String);
+
+/// Deletes a family from the gallery by name.
+#[derive(Event)]
+pub struct GalleryDelete(pub String);
+
+/// Triggered after a family is added to or removed from the gallery.
+#[derive(Event)]
+pub struct GalleryChanged;
@@ -0,0 +1,203 @@
+use std::{
+    fs::{self, File},
+    io::{Cursor, Read, Write},
+    path::Path,
+};
+
+use anyhow::{ensure, Context, Result};
+use bevy::{prelude::*, reflect::TypeRegistry, scene::ron};
+use serde::{Deserialize, Serialize};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+use super::GalleryChanged;
+use crate::{
+    error_message::error_message,
+    game_paths::{write_atomic, GamePaths},
+    notification::Notification,
+};
+
+/// Shares gallery content between players via `.lifescape` packages.
+///
+/// A package is a zip archive with a manifest, the content data and an optional thumbnail.
+pub(super) struct PackagePlugin;
+
+impl Plugin for PackagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(export.pipe(error_message))
+            .add_observer(import.pipe(error_message));
+    }
+}
+
+/// Incremented on incompatible changes of the package layout.
+const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.ron";
+const FAMILY_FILE: &str = "family.bin";
+const THUMBNAIL_FILE: &str = "thumbnail.png";
+
+fn export(
+    trigger: Trigger<PackageExport>,
+    mut commands: Commands,
+    game_paths: Res<GamePaths>,
+) -> Result<()> {
+    let package_path = game_paths.exported_package_path(&trigger.0);
+    info!("exporting family '{}' to {package_path:?}", trigger.0);
+
+    export_family(&game_paths, &trigger.0, &package_path)
+        .with_context(|| format!("unable to export family '{}'", trigger.0))?;
+
+    commands.trigger(Notification::info(format!(
+        "Family exported to {package_path:?}"
+    )));
+
+    Ok(())
+}
+
+/// Imports all packages from [`GamePaths::packages`].
+///
+/// Broken packages are reported and skipped.
+fn import(
+    _trigger: Trigger<PackageImport>,
+    mut commands: Commands,
+    registry: Res<AppTypeRegistry>,
+    game_paths: Res<GamePaths>,
+) -> Result<()> {
+    let mut imported = 0;
+    for package_path in game_paths.get_package_paths()? {
+        info!("importing {package_path:?}");
+        match import_family(&game_paths, &package_path, &registry.read()) {
+            Ok(name) => {
+                debug!("imported family '{name}'");
+                imported += 1;
+            }
+            Err(e) => {
+                error!("unable to import {package_path:?}: {e:#}");
+                commands.trigger(Notification::error(format!(
+                    "Unable to import {package_path:?}"
+                )));
+            }
+        }
+    }
+
+    if imported > 0 {
+        commands.trigger(GalleryChanged);
+    }
+    commands.trigger(Notification::info(format!("Imported {imported} packages")));
+
+    Ok(())
+}
+
+/// Writes a family from the gallery into a package.
+pub fn export_family(game_paths: &GamePaths, name: &str, package_path: &Path) -> Result<()> {
+    let family_path = game_paths.family_path(name);
+    let family =
+        fs::read(&family_path).with_context(|| format!("unable to read {family_path:?}"))?;
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+
+    let manifest = PackageManifest {
+        format_version: FORMAT_VERSION,
+        kind: PackageKind::Family,
+        name: name.to_string(),
+    };
+    zip.start_file(MANIFEST_FILE, options)?;
+    zip.write_all(ron::ser::to_string_pretty(&manifest, Default::default())?.as_bytes())?;
+
+    zip.start_file(FAMILY_FILE, options)?;
+    zip.write_all(&family)?;
+
+    let preview_path = game_paths.family_preview_path(name);
+    if preview_path.exists() {
+        let thumbnail =
+            fs::read(&preview_path).with_context(|| format!("unable to read {preview_path:?}"))?;
+        zip.start_file(THUMBNAIL_FILE, options)?;
+        zip.write_all(&thumbnail)?;
+    }
+
+    let bytes = zip.finish()?.into_inner();
+    write_atomic(package_path, bytes)
+}
+
+/// Adds a family from a package to the gallery.
+///
+/// Replaces the family with the same name if it exists.
+/// Returns the name of the imported family.
+pub fn import_family(
+    game_paths: &GamePaths,
+    package_path: &Path,
+    registry: &TypeRegistry,
+) -> Result<String> {
+    let file =
+        File::open(package_path).with_context(|| format!("unable to open {package_path:?}"))?;
+    let mut zip = ZipArchive::new(file)?;
+
+    let manifest: PackageManifest = ron::de::from_bytes(&read_entry(&mut zip, MANIFEST_FILE)?)
+        .context("unable to parse package manifest")?;
+    ensure!(
+        manifest.format_version <= FORMAT_VERSION,
+        "package format version {} is not supported",
+        manifest.format_version
+    );
+    ensure!(
+        !manifest.name.is_empty()
+            && !manifest.name.starts_with('.')
+            && !manifest.name.contains(['/', '\\']),
+        "package name '{}' is invalid",
+        manifest.name
+    );
+
+    match manifest.kind {
+        PackageKind::Family => {
+            let family = read_entry(&mut zip, FAMILY_FILE)?;
+            crate::game_world::family::deserialize_scene(&*family, registry)
+                .context("package contains invalid family")?;
+
+            let family_path = game_paths.family_path(&manifest.name);
+            write_atomic(&family_path, family)
+                .with_context(|| format!("unable to write {family_path:?}"))?;
+
+            if zip.index_for_name(THUMBNAIL_FILE).is_some() {
+                let thumbnail = read_entry(&mut zip, THUMBNAIL_FILE)?;
+                let preview_path = game_paths.family_preview_path(&manifest.name);
+                write_atomic(&preview_path, thumbnail)
+                    .with_context(|| format!("unable to write {preview_path:?}"))?;
+            }
+        }
+    }
+
+    Ok(manifest.name)
+}
+
+fn read_entry(zip: &mut ZipArchive<File>, name: &str) -> Result<Vec<u8>> {
+    let mut entry = zip
+        .by_name(name)
+        .with_context(|| format!("package has no '{name}'"))?;
+    let mut bytes = Vec::new();
+    entry
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("unable to read '{name}'"))?;
+
+    Ok(bytes)
+}
+
+/// Describes the package content, stored as [`MANIFEST_FILE`].
+#[derive(Deserialize, Serialize)]
+struct PackageManifest {
+    format_version: u32,
+    kind: PackageKind,
+    name: String,
+}
+
+#[derive(Deserialize, Serialize)]
+enum PackageKind {
+    Family,
+}
+
+/// Exports a family from the gallery by name into [`GamePaths::exported_packages`].
+#[derive(Event)]
+pub struct PackageExport(pub String);
+
+/// Imports all packages from [`GamePaths::packages`] into the gallery.
+#[derive(Event)]
+pub struct PackageImport;
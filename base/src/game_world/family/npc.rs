@@ -0,0 +1,249 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use bevy_replicon::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use super::{Family, FamilyOwner};
+use crate::game_world::{
+    actor::{
+        human::Human,
+        name_generator::NameGenerator,
+        needs::{Energy, Hunger, Need, Social},
+        task::{
+            cook::{Cook, Stove},
+            eat::{Eat, Meal},
+            friendly::tell_secret::TellSecret,
+            move_here::MoveHere,
+            sleep::{Bed, Sleep},
+            Task,
+        },
+        Actor, FirstName, LastName, Sex,
+    },
+    city::{City, Lot},
+    event_log::{EventCategory, GameEvent},
+    fire::OnFire,
+    SimulationSet,
+};
+
+pub(super) struct NpcPlugin;
+
+impl Plugin for NpcPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Npc>().replicate::<Npc>().add_systems(
+            FixedUpdate,
+            (
+                populate.run_if(on_timer(Duration::from_secs(5))),
+                choose_task.run_if(on_timer(Duration::from_secs(10))),
+            )
+                .in_set(SimulationSet::Autonomy),
+        );
+    }
+}
+
+/// Number of NPC households that each city should have.
+const FAMILIES_PER_CITY: usize = 2;
+
+/// Needs below this value make NPC actors look for a task that restores them.
+const AUTONOMY_THRESHOLD: f32 = 50.0;
+
+/// Maximum distance at which NPC actors look for someone to talk to.
+const SOCIAL_DISTANCE: f32 = 30.0;
+
+/// Distance from lot fences that NPC actors keep while walking around their lot.
+const LOT_MARGIN: f32 = 1.0;
+
+/// Moves NPC households into free lots of cities that don't have enough of them.
+fn populate(
+    mut commands: Commands,
+    name_generator: NameGenerator,
+    cities: Query<(Entity, &Name, &Children), With<City>>,
+    lots: Query<(Entity, &Lot, Option<&FamilyOwner>)>,
+    families: Query<Has<Npc>, With<Family>>,
+) {
+    let mut rng = rand::thread_rng();
+    for (city_entity, city_name, children) in &cities {
+        let mut families_count = 0;
+        let mut free_lots = Vec::new();
+        for (lot_entity, lot, owner) in lots.iter_many(children) {
+            match owner.and_then(|owner| families.get(owner.0).ok()) {
+                Some(true) => families_count += 1,
+                Some(false) => (),
+                None => free_lots.push((lot_entity, lot)),
+            }
+        }
+
+        free_lots.shuffle(&mut rng);
+        let missing = FAMILIES_PER_CITY.saturating_sub(families_count);
+        for (lot_entity, lot) in free_lots.into_iter().take(missing) {
+            let last_name = name_generator.last_name(&mut rng);
+            info!("spawning NPC family '{last_name}' on lot `{lot_entity}` in city '{city_name}'");
+            commands.spawn(GameEvent::new(
+                EventCategory::Family,
+                format!("The {last_name} family moved into {city_name}"),
+            ));
+
+            let family_entity = commands
+                .spawn((Family, Npc, Name::new(last_name.clone())))
+                .id();
+            commands
+                .entity(lot_entity)
+                .insert(FamilyOwner(family_entity));
+
+            let members_count = rng.gen_range(1..=3);
+            let entrance = Vec3::new(lot.entrance.x, 0.0, lot.entrance.y);
+            commands.entity(city_entity).with_children(|parent| {
+                for index in 0..members_count {
+                    let sex: Sex = rng.gen();
+                    parent.spawn((
+                        Actor { family_entity },
+                        Human,
+                        FirstName(name_generator.first_name(&mut rng, sex)),
+                        LastName(last_name.clone()),
+                        sex,
+                        Transform::from_translation(entrance + Vec3::X * index as f32),
+                    ));
+                }
+            });
+        }
+    }
+}
+
+/// Runs simplified autonomy for idle NPC actors.
+///
+/// Picks a task for the lowest need below [`AUTONOMY_THRESHOLD`] that has something to satisfy it.
+/// Actors without such needs walk around their lot.
+/// Executed less often than the player's simulation to keep the cost low.
+fn choose_task(
+    mut commands: Commands,
+    actors: Query<(Entity, &Actor, &Parent, &Transform, &Children)>,
+    npc_families: Query<(), With<Npc>>,
+    tasks: Query<(), With<Task>>,
+    needs: Query<(&Need, Has<Energy>, Has<Hunger>, Has<Social>)>,
+    lots: Query<(&Lot, &FamilyOwner)>,
+    beds: Query<UsableObject, With<Bed>>,
+    meals: Query<UsableObject, With<Meal>>,
+    stoves: Query<UsableObject, (With<Stove>, Without<OnFire>)>,
+) {
+    let mut rng = rand::thread_rng();
+    'actors: for (actor_entity, actor, actor_parent, transform, children) in &actors {
+        if npc_families.get(actor.family_entity).is_err() {
+            continue;
+        }
+        if tasks.iter_many(children).next().is_some() {
+            continue;
+        }
+
+        let mut low_needs: Vec<_> = needs
+            .iter_many(children)
+            .filter(|(need, ..)| need.0 < AUTONOMY_THRESHOLD)
+            .collect();
+        low_needs.sort_by(|(a, ..), (b, ..)| a.0.total_cmp(&b.0));
+
+        let city_entity = **actor_parent;
+        let family_entity = actor.family_entity;
+        let translation = transform.translation;
+        for (_, energy, hunger, social) in low_needs {
+            if energy {
+                if let Some((bed_entity, endpoint)) =
+                    nearest_object(beds.iter(), city_entity, family_entity, translation)
+                {
+                    debug!("queuing sleep for NPC actor `{actor_entity}`");
+                    commands
+                        .entity(actor_entity)
+                        .with_child(Sleep::new(bed_entity, endpoint));
+                    continue 'actors;
+                }
+            } else if hunger {
+                if let Some((meal_entity, endpoint)) =
+                    nearest_object(meals.iter(), city_entity, family_entity, translation)
+                {
+                    debug!("queuing eating for NPC actor `{actor_entity}`");
+                    commands
+                        .entity(actor_entity)
+                        .with_child(Eat::new(meal_entity, endpoint));
+                    continue 'actors;
+                }
+                if let Some((stove_entity, endpoint)) =
+                    nearest_object(stoves.iter(), city_entity, family_entity, translation)
+                {
+                    debug!("queuing cooking for NPC actor `{actor_entity}`");
+                    commands
+                        .entity(actor_entity)
+                        .with_child(Cook::new(stove_entity, endpoint));
+                    continue 'actors;
+                }
+            } else if social {
+                let target = actors
+                    .iter()
+                    .filter(|&(entity, _, parent, ..)| {
+                        entity != actor_entity && **parent == city_entity
+                    })
+                    .map(|(entity, _, _, transform, _)| {
+                        (entity, transform.translation.distance(translation))
+                    })
+                    .filter(|&(_, distance)| distance <= SOCIAL_DISTANCE)
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b));
+                if let Some((target_entity, _)) = target {
+                    debug!("queuing secret telling for NPC actor `{actor_entity}`");
+                    commands
+                        .entity(actor_entity)
+                        .with_child(TellSecret::new(target_entity));
+                    continue 'actors;
+                }
+            }
+        }
+
+        let Some((lot, _)) = lots.iter().find(|(_, owner)| owner.0 == family_entity) else {
+            continue;
+        };
+        let rect = lot.rect.inflate(-LOT_MARGIN);
+        let endpoint = Vec3::new(
+            rng.gen_range(rect.min.x..=rect.max.x),
+            0.0,
+            rng.gen_range(rect.min.y..=rect.max.y),
+        );
+
+        debug!("queuing walk around the lot for NPC actor `{actor_entity}`");
+        commands
+            .entity(actor_entity)
+            .with_child((Name::new("Walk here"), MoveHere::walk(endpoint)));
+    }
+}
+
+/// Object data to pick the closest one with [`nearest_object`].
+type UsableObject = (
+    Entity,
+    &'static Parent,
+    &'static Transform,
+    Option<&'static FamilyOwner>,
+);
+
+/// Returns the closest object from the city that belongs to nobody or to the family.
+///
+/// Returns the object entity and its translation in city coordinates.
+fn nearest_object<'a>(
+    objects: impl Iterator<Item = (Entity, &'a Parent, &'a Transform, Option<&'a FamilyOwner>)>,
+    city_entity: Entity,
+    family_entity: Entity,
+    translation: Vec3,
+) -> Option<(Entity, Vec3)> {
+    objects
+        .filter(|&(_, parent, _, owner)| {
+            **parent == city_entity && owner.is_none_or(|owner| owner.0 == family_entity)
+        })
+        .map(|(entity, _, transform, _)| (entity, transform.translation))
+        .min_by(|(_, a), (_, b)| {
+            a.distance_squared(translation)
+                .total_cmp(&b.distance_squared(translation))
+        })
+}
+
+/// Marks family as controlled by AI.
+///
+/// Such families are spawned automatically and can't be played,
+/// but other players can visit them and interact with their members.
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct Npc;
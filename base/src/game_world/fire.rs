@@ -0,0 +1,314 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use bevy_replicon::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    actor::{
+        task::{move_here::MoveHere, ActiveTask, UsedObject},
+        Actor,
+    },
+    auto_pause::{CriticalEvent, CriticalEventKind},
+    event_log::{EventCategory, GameEvent},
+    family::{building::wall::Wall, Budget, FamilyOwner, TransactionKind, TransactionLog},
+    object::Object,
+    segment::Segment,
+    SimulationSet,
+};
+use crate::asset::manifest::object_manifest::ObjectManifest;
+
+/// Ignites objects, spreads fire and destroys burned objects and walls.
+pub(super) struct FirePlugin;
+
+impl Plugin for FirePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<IgnitionSource>()
+            .register_type::<Flammable>()
+            .register_type::<OnFire>()
+            .replicate::<OnFire>()
+            .add_observer(init)
+            .add_observer(cleanup)
+            .add_observer(panic)
+            .add_systems(Update, flicker)
+            .add_systems(
+                FixedUpdate,
+                (
+                    ignite.run_if(on_timer(IGNITION_INTERVAL)),
+                    spread.run_if(on_timer(SPREAD_INTERVAL)),
+                    burn,
+                )
+                    .in_set(SimulationSet::Hazards),
+            );
+    }
+}
+
+const IGNITION_INTERVAL: Duration = Duration::from_secs(10);
+const SPREAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Chance for fire to jump to each flammable entity in range on every spread check.
+const SPREAD_CHANCE: f64 = 0.3;
+
+/// Maximum distance at which fire can jump.
+const SPREAD_DISTANCE: f32 = 1.5;
+
+/// Actors closer than this distance run away from a new fire.
+const PANIC_DISTANCE: f32 = 8.0;
+
+/// Distance at which panicking actors stop.
+const FLEE_DISTANCE: f32 = 10.0;
+
+/// Budget loss for a burned wall.
+const WALL_LOSS: u32 = 100;
+
+/// Spawns a flickering light on burning entities.
+fn init(trigger: Trigger<OnAdd, OnFire>, mut commands: Commands, segments: Query<&Segment>) {
+    debug!("igniting `{}`", trigger.entity());
+
+    // Walls are positioned by their segments, objects by their transforms.
+    let translation = match segments.get(trigger.entity()) {
+        Ok(segment) => {
+            let center = segment.start.lerp(segment.end, 0.5);
+            Vec3::new(center.x, 1.5, center.y)
+        }
+        Err(_) => Vec3::Y,
+    };
+
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn((FireLight, Transform::from_translation(translation)));
+    });
+}
+
+/// Removes the light and resets the timer for the next fire.
+fn cleanup(
+    trigger: Trigger<OnRemove, OnFire>,
+    mut commands: Commands,
+    children: Query<&Children>,
+    lights: Query<(), With<FireLight>>,
+) {
+    debug!("extinguishing `{}`", trigger.entity());
+    commands.entity(trigger.entity()).remove::<BurnTimer>();
+
+    let Ok(children) = children.get(trigger.entity()) else {
+        return;
+    };
+    for &child_entity in children {
+        if lights.get(child_entity).is_ok() {
+            commands.entity(child_entity).despawn_recursive();
+        }
+    }
+}
+
+fn flicker(time: Res<Time>, mut lights: Query<&mut PointLight, With<FireLight>>) {
+    let secs = time.elapsed_secs();
+    for mut light in &mut lights {
+        light.intensity = FIRE_INTENSITY * (1.0 + 0.2 * (secs * 13.0).sin() * (secs * 7.0).cos());
+    }
+}
+
+/// Makes nearby actors run away and notifies about the fire.
+///
+/// Triggered only on server since [`OnFire`] is inserted there first.
+fn panic(
+    trigger: Trigger<OnAdd, OnFire>,
+    mut commands: Commands,
+    client: Res<RepliconClient>,
+    burning: Query<(&Name, &Parent, Option<&Transform>, Option<&Segment>)>,
+    actors: Query<(Entity, &Parent, &Transform), With<Actor>>,
+) {
+    if !client.is_disconnected() {
+        return;
+    }
+    let Ok((name, city_entity, transform, segment)) = burning.get(trigger.entity()) else {
+        return;
+    };
+    let position = fire_position(transform, segment);
+
+    let mut nearest = None;
+    let mut nearest_distance = f32::MAX;
+    for (actor_entity, parent, actor_transform) in &actors {
+        if **parent != **city_entity {
+            continue;
+        }
+
+        let offset = actor_transform.translation.xz() - position;
+        let distance = offset.length();
+        if distance < nearest_distance {
+            nearest = Some(actor_entity);
+            nearest_distance = distance;
+        }
+
+        if distance < PANIC_DISTANCE {
+            debug!("`{actor_entity}` panics");
+            let direction = offset.try_normalize().unwrap_or(Vec2::X);
+            let endpoint = position + direction * FLEE_DISTANCE;
+            commands.entity(actor_entity).with_children(|parent| {
+                parent.spawn((
+                    Name::new("Run from fire"),
+                    MoveHere::run(Vec3::new(endpoint.x, 0.0, endpoint.y)),
+                ));
+            });
+        }
+    }
+
+    if let Some(actor_entity) = nearest {
+        commands.trigger(CriticalEvent {
+            kind: CriticalEventKind::Fire {
+                object_name: name.to_string(),
+            },
+            actor_entity,
+        });
+    }
+}
+
+/// Randomly sets ignition sources on fire while actors use them.
+fn ignite(
+    mut commands: Commands,
+    tasks: Query<&UsedObject, With<ActiveTask>>,
+    sources: Query<&IgnitionSource, Without<OnFire>>,
+) {
+    let mut rng = rand::thread_rng();
+    for used in &tasks {
+        let Ok(source) = sources.get(used.object_entity) else {
+            continue;
+        };
+
+        let chance = source.0 * used.ignition_scale;
+        if rng.gen_bool(chance.clamp(0.0, 1.0)) {
+            info!("`{}` caught fire during use", used.object_entity);
+            commands.entity(used.object_entity).insert(OnFire);
+        }
+    }
+}
+
+/// Sets flammable objects and walls near burning entities on fire.
+fn spread(
+    mut commands: Commands,
+    burning: Query<(&Parent, Option<&Transform>, Option<&Segment>), With<OnFire>>,
+    objects: Query<(Entity, &Parent, &Transform), (With<Flammable>, Without<OnFire>)>,
+    walls: Query<(Entity, &Parent, &Segment), (With<Wall>, Without<OnFire>)>,
+) {
+    let mut rng = rand::thread_rng();
+    for (city_entity, transform, segment) in &burning {
+        let position = fire_position(transform, segment);
+        for (entity, parent, object_transform) in &objects {
+            if **parent == **city_entity
+                && object_transform.translation.xz().distance(position) < SPREAD_DISTANCE
+                && rng.gen_bool(SPREAD_CHANCE)
+            {
+                debug!("spreading fire to object `{entity}`");
+                commands.entity(entity).insert(OnFire);
+            }
+        }
+        for (entity, parent, wall_segment) in &walls {
+            if **parent == **city_entity
+                && wall_segment.closest_point(position).distance(position) < SPREAD_DISTANCE
+                && rng.gen_bool(SPREAD_CHANCE)
+            {
+                debug!("spreading fire to wall `{entity}`");
+                commands.entity(entity).insert(OnFire);
+            }
+        }
+    }
+}
+
+/// Destroys entities that have been burning for too long and charges their owners.
+fn burn(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    manifests: Res<Assets<ObjectManifest>>,
+    mut burning: Query<(
+        Entity,
+        &Name,
+        &mut BurnTimer,
+        Option<&Object>,
+        Option<&FamilyOwner>,
+    )>,
+    mut families: Query<(&mut Budget, &mut TransactionLog)>,
+) {
+    for (entity, name, mut timer, object, owner) in &mut burning {
+        timer.tick(time.delta());
+        if !timer.finished() {
+            continue;
+        }
+
+        info!("`{entity}` burned down");
+        let loss = match object {
+            Some(object) => asset_server
+                .get_handle(&**object)
+                .and_then(|handle| manifests.get(&handle))
+                .map(|manifest| manifest.price)
+                .unwrap_or_default(),
+            None => WALL_LOSS,
+        };
+
+        // Entities placed without a family, like in the city editor, have no owner to charge.
+        if let Some((mut budget, mut log)) = owner.and_then(|owner| families.get_mut(**owner).ok())
+        {
+            budget.spend(&mut log, TransactionKind::Damage, name.as_str(), loss);
+        }
+
+        commands.spawn(GameEvent::new(
+            EventCategory::Hazards,
+            format!("{name} burned down, the damage is {loss}"),
+        ));
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Returns the position of the burning entity in city coordinates.
+fn fire_position(transform: Option<&Transform>, segment: Option<&Segment>) -> Vec2 {
+    match (segment, transform) {
+        (Some(segment), _) => segment.start.lerp(segment.end, 0.5),
+        (None, Some(transform)) => transform.translation.xz(),
+        (None, None) => Vec2::ZERO,
+    }
+}
+
+/// Base intensity of [`FireLight`].
+const FIRE_INTENSITY: f32 = 200_000.0;
+
+/// Chance to catch fire on every ignition check while an actor uses the object.
+///
+/// Scaled by [`UsedObject::ignition_scale`]. Inserted from object manifests.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(Flammable)]
+pub(crate) struct IgnitionSource(f64);
+
+/// Marks an object that can catch fire from nearby burning entities.
+///
+/// Walls are always flammable. Inserted from object manifests.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub(crate) struct Flammable;
+
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+#[require(BurnTimer)]
+pub(crate) struct OnFire;
+
+/// Time until the burning entity is destroyed.
+///
+/// Ticked only on server.
+#[derive(Component, Deref, DerefMut)]
+struct BurnTimer(Timer);
+
+impl Default for BurnTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(60.0, TimerMode::Once))
+    }
+}
+
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Fire light")),
+    PointLight(|| PointLight {
+        color: Color::srgb(1.0, 0.5, 0.1),
+        intensity: FIRE_INTENSITY,
+        ..Default::default()
+    }),
+)]
+struct FireLight;
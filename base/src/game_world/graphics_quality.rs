@@ -0,0 +1,136 @@
+use bevy::{
+    core_pipeline::bloom::Bloom,
+    ecs::system::EntityCommands,
+    pbr::{
+        CascadeShadowConfigBuilder, DirectionalLightShadowMap, ScreenSpaceAmbientOcclusion,
+        ScreenSpaceAmbientOcclusionQualityLevel,
+    },
+    prelude::*,
+};
+
+use super::player_camera::PlayerCamera;
+use crate::settings::{GraphicsQuality, Settings, SettingsApply};
+
+/// Configures shadows and post-processing according to [`GraphicsQuality`] from settings.
+///
+/// Reconfigures existing cameras and lights when settings are applied.
+pub(super) struct GraphicsQualityPlugin;
+
+impl Plugin for GraphicsQualityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(init_camera)
+            .add_observer(init_light)
+            .add_observer(apply);
+    }
+}
+
+fn init_camera(
+    trigger: Trigger<OnAdd, PlayerCamera>,
+    mut commands: Commands,
+    settings: Res<Settings>,
+) {
+    let preset = QualityPreset::new(settings.video.quality);
+    preset.configure_camera(&mut commands.entity(trigger.entity()));
+}
+
+fn init_light(
+    trigger: Trigger<OnAdd, DirectionalLight>,
+    mut commands: Commands,
+    mut shadow_map: ResMut<DirectionalLightShadowMap>,
+    settings: Res<Settings>,
+) {
+    let preset = QualityPreset::new(settings.video.quality);
+    shadow_map.size = preset.shadow_map_size;
+    preset.configure_light(&mut commands.entity(trigger.entity()));
+}
+
+fn apply(
+    _trigger: Trigger<SettingsApply>,
+    mut commands: Commands,
+    mut shadow_map: ResMut<DirectionalLightShadowMap>,
+    settings: Res<Settings>,
+    cameras: Query<Entity, With<PlayerCamera>>,
+    lights: Query<Entity, With<DirectionalLight>>,
+) {
+    let quality = settings.video.quality;
+    debug!("applying {quality:?} graphics quality");
+
+    let preset = QualityPreset::new(quality);
+    shadow_map.size = preset.shadow_map_size;
+    for entity in &cameras {
+        preset.configure_camera(&mut commands.entity(entity));
+    }
+    for entity in &lights {
+        preset.configure_light(&mut commands.entity(entity));
+    }
+}
+
+struct QualityPreset {
+    /// Resolution of each shadow cascade.
+    shadow_map_size: usize,
+    num_cascades: usize,
+    /// Ambient occlusion level, disabled if `None`.
+    ssao: Option<ScreenSpaceAmbientOcclusionQualityLevel>,
+    bloom: bool,
+}
+
+impl QualityPreset {
+    fn new(quality: GraphicsQuality) -> Self {
+        match quality {
+            GraphicsQuality::Low => Self {
+                shadow_map_size: 1024,
+                num_cascades: 1,
+                ssao: None,
+                bloom: false,
+            },
+            GraphicsQuality::Medium => Self {
+                shadow_map_size: 2048,
+                num_cascades: 2,
+                ssao: Some(ScreenSpaceAmbientOcclusionQualityLevel::Low),
+                bloom: true,
+            },
+            GraphicsQuality::High => Self {
+                shadow_map_size: 2048,
+                num_cascades: 4,
+                ssao: Some(ScreenSpaceAmbientOcclusionQualityLevel::High),
+                bloom: true,
+            },
+            GraphicsQuality::Ultra => Self {
+                shadow_map_size: 4096,
+                num_cascades: 4,
+                ssao: Some(ScreenSpaceAmbientOcclusionQualityLevel::Ultra),
+                bloom: true,
+            },
+        }
+    }
+
+    fn configure_camera(&self, camera: &mut EntityCommands) {
+        match self.ssao {
+            Some(quality_level) => {
+                camera.insert(ScreenSpaceAmbientOcclusion {
+                    quality_level,
+                    ..Default::default()
+                });
+            }
+            None => {
+                camera.remove::<ScreenSpaceAmbientOcclusion>();
+            }
+        }
+
+        if self.bloom {
+            camera.insert(Bloom::NATURAL);
+        } else {
+            camera.remove::<Bloom>();
+        }
+    }
+
+    fn configure_light(&self, light: &mut EntityCommands) {
+        light.insert(
+            CascadeShadowConfigBuilder {
+                num_cascades: self.num_cascades,
+                ..Default::default()
+            }
+            .build(),
+        );
+    }
+}
@@ -0,0 +1,351 @@
+use avian3d::prelude::*;
+use bevy::{
+    ecs::entity::{EntityHashMap, EntityHashSet},
+    math::bounding::{Aabb3d, RayCast3d},
+    picking::backend::prelude::*,
+    prelude::*,
+    render::view::RenderLayers,
+    utils::HashMap,
+};
+
+use crate::settings::{PickingBackend, Settings};
+
+/// Picking backend that looks up colliders in a uniform grid on the XZ plane.
+///
+/// Cells are walked along the pointer ray starting from the camera, and the walk stops
+/// at the first cell behind the nearest hit. The grid is updated only for colliders
+/// that moved, so static furniture and walls cost nothing between frames.
+///
+/// Emits the same [`PointerHits`] as the physics backend, selected by [`PickingBackend`].
+pub(super) struct GridPickingPlugin;
+
+impl Plugin for GridPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PickingGrid>()
+            .add_observer(remove)
+            .add_systems(
+                PreUpdate,
+                (update, update_hits)
+                    .chain()
+                    .in_set(PickSet::Backend)
+                    .run_if(grid_backend),
+            );
+    }
+}
+
+/// Size of a grid cell in meters.
+const CELL_SIZE: f32 = 4.0;
+
+/// Colliders that overlap more cells are checked for every ray instead.
+///
+/// Keeps terrain and roads from filling the grid.
+const MAX_CELLS: f32 = 64.0;
+
+/// Maximum distance from the camera at which entities can be picked.
+const MAX_DISTANCE: f32 = 1000.0;
+
+fn grid_backend(settings: Res<Settings>) -> bool {
+    settings.developer.picking_backend == PickingBackend::Grid
+}
+
+/// Picks up changes made while the backend was disabled too,
+/// since change detection is relative to the last run of the system.
+fn update(
+    mut grid: ResMut<PickingGrid>,
+    colliders: Query<(Entity, &ColliderAabb), Changed<ColliderAabb>>,
+) {
+    for (entity, aabb) in &colliders {
+        grid.insert(entity, aabb);
+    }
+}
+
+fn remove(trigger: Trigger<OnRemove, Collider>, mut grid: ResMut<PickingGrid>) {
+    grid.remove(trigger.entity());
+}
+
+fn update_hits(
+    mut tested: Local<EntityHashSet>,
+    mut hits: EventWriter<PointerHits>,
+    grid: Res<PickingGrid>,
+    ray_map: Res<RayMap>,
+    cameras: Query<(&Camera, Option<&RenderLayers>)>,
+    colliders: Query<(
+        &Collider,
+        &ColliderAabb,
+        &Position,
+        &Rotation,
+        Option<&RenderLayers>,
+        Option<&PickingBehavior>,
+    )>,
+) {
+    for (&ray_id, &ray) in ray_map.map() {
+        let Ok((camera, camera_layers)) = cameras.get(ray_id.camera) else {
+            continue;
+        };
+        let camera_layers = camera_layers.cloned().unwrap_or_default();
+
+        tested.clear();
+        let mut nearest = None;
+        let mut max_distance = MAX_DISTANCE;
+        let large = [(0.0, grid.large.as_slice())].into_iter();
+        let cells = CellWalk::new(ray).filter_map(|(cell, entry)| {
+            grid.cells
+                .get(&cell)
+                .map(|entities| (entry, entities.as_slice()))
+        });
+        for (entry, entities) in large.chain(cells) {
+            if entry > max_distance {
+                break;
+            }
+
+            for &entity in entities {
+                if !tested.insert(entity) {
+                    // Already checked in a previous cell.
+                    continue;
+                }
+                let Ok((collider, aabb, position, rotation, layers, behavior)) =
+                    colliders.get(entity)
+                else {
+                    continue;
+                };
+                if behavior.is_some_and(|behavior| !behavior.is_hoverable) {
+                    continue;
+                }
+                if !camera_layers.intersects(&layers.cloned().unwrap_or_default()) {
+                    continue;
+                }
+
+                let bounds = Aabb3d {
+                    min: aabb.min.into(),
+                    max: aabb.max.into(),
+                };
+                if RayCast3d::from_ray(ray, max_distance)
+                    .aabb_intersection_at(&bounds)
+                    .is_none()
+                {
+                    continue;
+                }
+
+                if let Some((distance, normal)) = collider.cast_ray(
+                    position.0,
+                    *rotation,
+                    ray.origin,
+                    *ray.direction,
+                    max_distance,
+                    true,
+                ) {
+                    nearest = Some((entity, distance, normal));
+                    max_distance = distance;
+                }
+            }
+        }
+
+        if let Some((entity, distance, normal)) = nearest {
+            let hit = HitData::new(
+                ray_id.camera,
+                distance,
+                Some(ray.get_point(distance)),
+                Some(normal),
+            );
+            hits.send(PointerHits::new(
+                ray_id.pointer,
+                vec![(entity, hit)],
+                camera.order as f32,
+            ));
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+struct PickingGrid {
+    cells: HashMap<IVec2, Vec<Entity>>,
+    /// Colliders that overlap more than [`MAX_CELLS`].
+    large: Vec<Entity>,
+    placements: EntityHashMap<CellPlacement>,
+}
+
+impl PickingGrid {
+    fn insert(&mut self, entity: Entity, aabb: &ColliderAabb) {
+        let min = cell(aabb.min);
+        let max = cell(aabb.max);
+        let area = ((aabb.max - aabb.min).xz() / CELL_SIZE + 1.0).element_product();
+        // Also catches infinite bounds.
+        let placement = if area <= MAX_CELLS {
+            CellPlacement::Cells { min, max }
+        } else {
+            CellPlacement::Large
+        };
+
+        if self.placements.get(&entity) == Some(&placement) {
+            return;
+        }
+        self.remove(entity);
+
+        match placement {
+            CellPlacement::Cells { min, max } => {
+                for x in min.x..=max.x {
+                    for z in min.y..=max.y {
+                        self.cells.entry(IVec2::new(x, z)).or_default().push(entity);
+                    }
+                }
+            }
+            CellPlacement::Large => self.large.push(entity),
+        }
+        self.placements.insert(entity, placement);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let Some(placement) = self.placements.remove(&entity) else {
+            return;
+        };
+
+        match placement {
+            CellPlacement::Cells { min, max } => {
+                for x in min.x..=max.x {
+                    for z in min.y..=max.y {
+                        let cell = IVec2::new(x, z);
+                        let Some(entities) = self.cells.get_mut(&cell) else {
+                            continue;
+                        };
+                        entities.retain(|&cell_entity| cell_entity != entity);
+                        if entities.is_empty() {
+                            self.cells.remove(&cell);
+                        }
+                    }
+                }
+            }
+            CellPlacement::Large => self.large.retain(|&large_entity| large_entity != entity),
+        }
+    }
+}
+
+fn cell(point: Vec3) -> IVec2 {
+    (point.xz() / CELL_SIZE).floor().as_ivec2()
+}
+
+/// Inclusive range of cells occupied by a collider.
+#[derive(Clone, Copy, PartialEq)]
+enum CellPlacement {
+    Cells { min: IVec2, max: IVec2 },
+    Large,
+}
+
+/// Iterates over cells crossed by a ray in order along with the distance at which the ray enters them.
+///
+/// Never ends, the caller is expected to stop at the needed distance.
+struct CellWalk {
+    cell: IVec2,
+    step: IVec2,
+    /// Distance along the ray to the next cell boundary on each axis.
+    next: Vec2,
+    /// Distance along the ray to cross a whole cell on each axis.
+    delta: Vec2,
+    entry: f32,
+}
+
+impl CellWalk {
+    fn new(ray: Ray3d) -> Self {
+        let origin = ray.origin.xz() / CELL_SIZE;
+        let direction = ray.direction.xz();
+        let cell = origin.floor().as_ivec2();
+
+        let axis = |origin: f32, cell: i32, direction: f32| {
+            if direction == 0.0 {
+                // Parallel to the boundaries.
+                return (0, f32::INFINITY, f32::INFINITY);
+            }
+            let delta = CELL_SIZE / direction.abs();
+            if direction > 0.0 {
+                (1, (cell as f32 + 1.0 - origin) * delta, delta)
+            } else {
+                (-1, (origin - cell as f32) * delta, delta)
+            }
+        };
+        let (step_x, next_x, delta_x) = axis(origin.x, cell.x, direction.x);
+        let (step_z, next_z, delta_z) = axis(origin.y, cell.y, direction.y);
+
+        Self {
+            cell,
+            step: IVec2::new(step_x, step_z),
+            next: Vec2::new(next_x, next_z),
+            delta: Vec2::new(delta_x, delta_z),
+            entry: 0.0,
+        }
+    }
+}
+
+impl Iterator for CellWalk {
+    type Item = (IVec2, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = (self.cell, self.entry);
+        if self.next.x < self.next.y {
+            self.cell.x += self.step.x;
+            self.entry = self.next.x;
+            self.next.x += self.delta.x;
+        } else {
+            self.cell.y += self.step.y;
+            self.entry = self.next.y;
+            self.next.y += self.delta.y;
+        }
+
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walk() {
+        let ray = Ray3d::new(Vec3::new(1.0, 10.0, 1.0), Dir3::X);
+        let walk: Vec<_> = CellWalk::new(ray).take(3).collect();
+        assert_eq!(
+            walk,
+            [
+                (IVec2::ZERO, 0.0),
+                (IVec2::new(1, 0), 3.0),
+                (IVec2::new(2, 0), 7.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn vertical_walk() {
+        let ray = Ray3d::new(Vec3::new(-1.0, 10.0, 1.0), Dir3::NEG_Y);
+        let mut walk = CellWalk::new(ray);
+        assert_eq!(walk.next(), Some((IVec2::new(-1, 0), 0.0)));
+        let (_, entry) = walk.next().unwrap();
+        assert!(entry > MAX_DISTANCE);
+    }
+
+    #[test]
+    fn placement() {
+        let mut grid = PickingGrid::default();
+        let entity = Entity::from_raw(0);
+        grid.insert(
+            entity,
+            &ColliderAabb {
+                min: Vec3::new(3.0, 0.0, 3.0),
+                max: Vec3::new(5.0, 1.0, 5.0),
+            },
+        );
+        assert_eq!(grid.cells.len(), 4);
+        assert!(grid.large.is_empty());
+
+        grid.insert(
+            entity,
+            &ColliderAabb {
+                min: Vec3::splat(-100.0),
+                max: Vec3::splat(100.0),
+            },
+        );
+        assert!(grid.cells.is_empty());
+        assert_eq!(grid.large, [entity]);
+
+        grid.remove(entity);
+        assert!(grid.large.is_empty());
+        assert!(grid.placements.is_empty());
+    }
+}
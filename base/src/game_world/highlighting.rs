@@ -4,9 +4,11 @@ use bevy_mod_outline::{InheritOutline, OutlineVolume};
 
 use super::{
     city::CityMode,
+    cursor_mode::CursorMode,
     family::{building::BuildingMode, FamilyMode},
     Layer,
 };
+use crate::settings::{OutlinePalette, Settings};
 
 pub(super) struct HighlightingPlugin;
 
@@ -23,7 +25,8 @@ impl Plugin for HighlightingPlugin {
             .add_systems(OnEnter(FamilyMode::Life), highlight_actors_and_objects)
             .add_systems(OnExit(BuildingMode::Objects), disable_highlighting)
             .add_systems(OnExit(CityMode::Objects), disable_highlighting)
-            .add_systems(OnExit(FamilyMode::Life), disable_highlighting);
+            .add_systems(OnExit(FamilyMode::Life), disable_highlighting)
+            .add_systems(PostUpdate, update_selection);
     }
 }
 
@@ -37,9 +40,15 @@ fn highlight_actors_and_objects(mut highlighting: ResMut<Highlighting>) {
     debug!("enabling highlighting for `{:?}`", highlighting.mask);
 }
 
-fn disable_highlighting(mut highlighting: ResMut<Highlighting>) {
+fn disable_highlighting(
+    mut highlighting: ResMut<Highlighting>,
+    mut cursor_mode: ResMut<CursorMode>,
+) {
     debug!("disabling highlighting");
     highlighting.mask = LayerMask::NONE;
+    if *cursor_mode == CursorMode::Interact {
+        *cursor_mode = CursorMode::Default;
+    }
 }
 
 /// Initializes scene children with [`InheritOutlineBundle`] to let toggle only top-level entity.
@@ -59,13 +68,57 @@ fn init_scene(
     }
 }
 
+/// Updates outlines for all entities whose selection changed this frame.
+fn update_selection(
+    settings: Res<Settings>,
+    highlighting: Res<Highlighting>,
+    mut deselected: RemovedComponents<Selected>,
+    selected: Query<Entity, Added<Selected>>,
+    mut volumes: Query<(&mut OutlineVolume, Has<Selected>)>,
+) {
+    let palette = settings.accessibility.outline_palette;
+    for entity in deselected.read().chain(&selected) {
+        let Ok((mut outline, selected)) = volumes.get_mut(entity) else {
+            continue;
+        };
+
+        debug!("updating selection outline for `{entity}`");
+        let hovered = highlighting.last_hovered == Some(entity);
+        apply_outline(&mut outline, palette, hovered, selected);
+    }
+}
+
+/// Sets outline for combined hover and selection state.
+///
+/// Selection has its own color, hovering a selected entity makes its outline wider.
+fn apply_outline(
+    outline: &mut OutlineVolume,
+    palette: OutlinePalette,
+    hovered: bool,
+    selected: bool,
+) {
+    outline.visible = hovered || selected;
+    outline.colour = if selected {
+        palette.selection_color()
+    } else {
+        palette.color()
+    };
+    outline.width = if hovered && selected {
+        HOVERED_SELECTION_WIDTH
+    } else {
+        HIGHLIGHTING_VOLUME.width
+    };
+}
+
 fn show(
     trigger: Trigger<Pointer<Over>>,
+    settings: Res<Settings>,
     mut highlighting: ResMut<Highlighting>,
+    mut cursor_mode: ResMut<CursorMode>,
     disabler: Query<(), With<HighlightDisabler>>,
-    mut volumes: Query<(&mut OutlineVolume, &CollisionLayers)>,
+    mut volumes: Query<(&mut OutlineVolume, &CollisionLayers, Has<Selected>)>,
 ) {
-    let Ok((mut outline, layers)) = volumes.get_mut(trigger.entity()) else {
+    let Ok((mut outline, layers, selected)) = volumes.get_mut(trigger.entity()) else {
         return;
     };
 
@@ -80,23 +133,33 @@ fn show(
     highlighting.last_hovered = Some(trigger.entity());
     if disabler.is_empty() {
         debug!("showing highlighting for `{}`", trigger.entity());
-        outline.visible = true;
+        let palette = settings.accessibility.outline_palette;
+        apply_outline(&mut outline, palette, true, selected);
+        if *cursor_mode == CursorMode::Default {
+            *cursor_mode = CursorMode::Interact;
+        }
     }
 }
 
 fn hide(
     trigger: Trigger<Pointer<Out>>,
-    mut volumes: Query<&mut OutlineVolume>,
+    settings: Res<Settings>,
+    mut volumes: Query<(&mut OutlineVolume, Has<Selected>)>,
     mut highlighting: ResMut<Highlighting>,
+    mut cursor_mode: ResMut<CursorMode>,
 ) {
-    let Ok(mut outline) = volumes.get_mut(trigger.entity()) else {
+    let Ok((mut outline, selected)) = volumes.get_mut(trigger.entity()) else {
         return;
     };
 
     highlighting.last_hovered = None;
+    if *cursor_mode == CursorMode::Interact {
+        *cursor_mode = CursorMode::Default;
+    }
     if outline.visible {
         debug!("hiding highlighting for `{}`", trigger.entity());
-        outline.visible = false;
+        let palette = settings.accessibility.outline_palette;
+        apply_outline(&mut outline, palette, false, selected);
     }
 }
 
@@ -136,6 +199,9 @@ pub(super) const HIGHLIGHTING_VOLUME: OutlineVolume = OutlineVolume {
     width: 3.0,
 };
 
+/// Outline width for a hovered entity that is also selected.
+const HOVERED_SELECTION_WIDTH: f32 = 5.0;
+
 #[derive(Resource)]
 struct Highlighting {
     mask: LayerMask,
@@ -154,3 +220,9 @@ impl Default for Highlighting {
 /// Highlighting will be disabled if any entity with this component is present.
 #[derive(Component, Default)]
 pub(super) struct HighlightDisabler;
+
+/// Outlines an entity with the selection color until removed.
+///
+/// Can be inserted on multiple entities at once, like a group of actors.
+#[derive(Component)]
+pub(crate) struct Selected;
@@ -0,0 +1,153 @@
+use bevy::{asset::RecursiveDependencyLoadState, prelude::*};
+use vleue_navigator::prelude::*;
+
+use super::{
+    city::{ActiveCity, CityNavMesh},
+    WorldState,
+};
+
+/// Shows [`WorldState::Loading`] between world menu and city or family
+/// until assets and navmesh of the active city are ready.
+pub(super) struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoadingTracker>()
+            .add_observer(start)
+            .add_systems(
+                OnEnter(WorldState::Loading),
+                track_city
+                    .never_param_warn()
+                    .after(super::city::activate_by_actor),
+            )
+            .add_systems(
+                Update,
+                (update_tracker, finish)
+                    .chain()
+                    .run_if(in_state(WorldState::Loading)),
+            )
+            .add_systems(OnExit(WorldState::Loading), cleanup);
+    }
+}
+
+fn start(trigger: Trigger<LoadingStart>, mut commands: Commands) {
+    info!("loading {:?}", trigger.0);
+    commands.insert_resource(LoadingTarget(trigger.0));
+    commands.set_state(WorldState::Loading);
+}
+
+fn track_city(
+    mut tracker: ResMut<LoadingTracker>,
+    active_city: Single<(Entity, &CityNavMesh), With<ActiveCity>>,
+    children: Query<&Children>,
+    scenes: Query<&SceneRoot>,
+    meshes: Query<&Mesh3d>,
+    materials: Query<&MeshMaterial3d<StandardMaterial>>,
+) {
+    let (city_entity, navmesh) = *active_city;
+    debug!("tracking loading for city `{city_entity}`");
+
+    tracker.add_navmesh(**navmesh);
+    for entity in children.iter_descendants(city_entity) {
+        if let Ok(scene) = scenes.get(entity) {
+            tracker.add_handle(scene.0.clone());
+        }
+        if let Ok(mesh) = meshes.get(entity) {
+            tracker.add_handle(mesh.0.clone());
+        }
+        if let Ok(material) = materials.get(entity) {
+            tracker.add_handle(material.0.clone());
+        }
+    }
+}
+
+fn update_tracker(
+    mut tracker: ResMut<LoadingTracker>,
+    asset_server: Res<AssetServer>,
+    navmeshes: Query<&NavMeshStatus>,
+) {
+    let pending = tracker.pending();
+    let tracker_mut = tracker.bypass_change_detection();
+    tracker_mut.handles.retain(|handle| {
+        matches!(
+            asset_server.get_recursive_dependency_load_state(handle.id()),
+            Some(RecursiveDependencyLoadState::NotLoaded | RecursiveDependencyLoadState::Loading)
+        )
+    });
+    tracker_mut.navmeshes.retain(|&entity| {
+        navmeshes
+            .get(entity)
+            .is_ok_and(|status| matches!(status, NavMeshStatus::Building))
+    });
+
+    if tracker.pending() != pending {
+        debug!("{} items left to load", tracker.pending());
+        tracker.set_changed();
+    }
+}
+
+fn finish(
+    mut commands: Commands,
+    tracker: Res<LoadingTracker>,
+    target: Option<Res<LoadingTarget>>,
+) {
+    if tracker.pending() != 0 {
+        return;
+    }
+
+    // Could be entered without the event, fallback to the world.
+    let state = target.map(|target| target.0).unwrap_or_default();
+    info!("finishing loading for {state:?}");
+    commands.set_state(state);
+}
+
+fn cleanup(mut commands: Commands, mut tracker: ResMut<LoadingTracker>) {
+    commands.remove_resource::<LoadingTarget>();
+    *tracker = Default::default();
+}
+
+/// Event that switches to [`WorldState::Loading`] and then to the specified state after loading.
+#[derive(Event)]
+pub struct LoadingStart(pub WorldState);
+
+/// State to switch after loading.
+#[derive(Resource)]
+struct LoadingTarget(WorldState);
+
+/// Aggregates assets and navmeshes that need to be loaded before leaving [`WorldState::Loading`].
+#[derive(Resource, Default)]
+pub struct LoadingTracker {
+    handles: Vec<UntypedHandle>,
+    navmeshes: Vec<Entity>,
+    total: usize,
+}
+
+impl LoadingTracker {
+    /// Waits for the asset and all its dependencies.
+    ///
+    /// Failed assets are considered loaded.
+    pub fn add_handle(&mut self, handle: impl Into<UntypedHandle>) {
+        self.handles.push(handle.into());
+        self.total += 1;
+    }
+
+    /// Waits until the navmesh on the entity finishes building.
+    pub fn add_navmesh(&mut self, entity: Entity) {
+        self.navmeshes.push(entity);
+        self.total += 1;
+    }
+
+    /// Returns the number of items that are still loading.
+    pub fn pending(&self) -> usize {
+        self.handles.len() + self.navmeshes.len()
+    }
+
+    /// Returns progress from 0.0 to 1.0.
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+
+        (self.total - self.pending()) as f32 / self.total as f32
+    }
+}
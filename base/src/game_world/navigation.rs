@@ -1,4 +1,5 @@
 pub(super) mod following;
+pub mod nav_tiles;
 pub(super) mod path_debug;
 
 use bevy::prelude::*;
@@ -7,16 +8,26 @@ use path_debug::PathDebugPlugin;
 use serde::{Deserialize, Serialize};
 use vleue_navigator::prelude::*;
 
-use crate::game_world::city::CityNavMesh;
+use crate::game_world::{
+    actor::ACTOR_RADIUS,
+    city::{
+        road::{Road, RoadData},
+        CityNavMesh,
+    },
+    family::building::basement::{self, Basement},
+    segment::Segment,
+};
 use following::FollowingPlugin;
+use nav_tiles::NavTilesPlugin;
 
 pub(super) struct NavigationPlugin;
 
 impl Plugin for NavigationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((FollowingPlugin, PathDebugPlugin))
+        app.add_plugins((FollowingPlugin, NavTilesPlugin, PathDebugPlugin))
             .register_type::<Navigation>()
             .register_type::<NavDestination>()
+            .register_type::<Obstacle>()
             .replicate::<Navigation>()
             .replicate::<NavDestination>()
             .replicate::<NavPath>()
@@ -36,6 +47,7 @@ fn update_paths(
     mut navmeshes: ResMut<Assets<NavMesh>>,
     city_navmeshes: Query<(&ManagedNavMesh, &Parent, &NavMeshStatus), Changed<NavMeshStatus>>,
     children: Query<&Children>,
+    basements: Query<&Basement>,
     mut agents: Query<(
         Entity,
         &Transform,
@@ -54,6 +66,10 @@ fn update_paths(
         };
 
         let children = children.get(**parent).unwrap();
+        let basements: Vec<_> = basements
+            .iter_many(children)
+            .map(|basement| basement.0)
+            .collect();
         let mut iter = agents.iter_many_mut(children);
         while let Some((entity, transform, mut dest, mut path, mut path_index)) = iter.fetch_next()
         {
@@ -63,8 +79,10 @@ fn update_paths(
 
             if let Some(transformed) = navmesh.transformed_path(transform.translation, endpoint) {
                 debug!("recalculating path for `{entity}`");
+                path.0.clear();
                 path.0.push(transform.translation);
                 path.0.extend(transformed.path);
+                basement::follow_floors(&mut path.0, &basements);
                 path_index.0 = 0;
             } else {
                 debug!("cancelling destination for `{entity}`");
@@ -78,6 +96,7 @@ fn generate_paths(
     mut navmeshes: ResMut<Assets<NavMesh>>,
     cities: Query<&CityNavMesh>,
     city_navmeshes: Query<&ManagedNavMesh>,
+    basements: Query<(&Parent, &Basement)>,
     mut agents: Query<
         (
             Entity,
@@ -113,6 +132,13 @@ fn generate_paths(
             debug!("calculating path for `{entity}`");
             path.0.push(transform.translation);
             path.0.extend(transformed.path);
+
+            let basements: Vec<_> = basements
+                .iter()
+                .filter(|(basement_parent, _)| **basement_parent == **parent)
+                .map(|(_, basement)| basement.0)
+                .collect();
+            basement::follow_floors(&mut path.0, &basements);
         } else {
             debug!("refusing destination for `{entity}`");
             **dest = None;
@@ -120,10 +146,15 @@ fn generate_paths(
     }
 }
 
+/// Speed multiplier for agents that walk on roads.
+const ROAD_SPEED_FACTOR: f32 = 1.5;
+
 fn navigate(
     time: Res<Time>,
+    roads: Query<(&Parent, &Segment, &RoadData), With<Road>>,
     mut agents: Query<(
         Entity,
+        &Parent,
         &Navigation,
         &NavPath,
         &mut NavPathIndex,
@@ -131,16 +162,41 @@ fn navigate(
         &mut Transform,
     )>,
 ) {
-    for (entity, &navigation, path, mut path_index, mut dest, mut transform) in &mut agents {
+    let positions: Vec<_> = agents
+        .iter()
+        .map(|(entity, parent, .., transform)| (entity, **parent, transform.translation))
+        .collect();
+
+    for (entity, parent, &navigation, path, mut path_index, mut dest, mut transform) in &mut agents
+    {
         if dest.is_none() || path.is_empty() {
             continue;
         }
 
+        let mut navigation = navigation;
+        let position = transform.translation.xz();
+        let on_road = roads.iter().any(|(road_parent, segment, road_data)| {
+            **road_parent == **parent
+                && segment.closest_point(position).distance(position) < road_data.half_width
+        });
+        if on_road {
+            navigation.speed *= ROAD_SPEED_FACTOR;
+        }
+
+        let neighbors = positions
+            .iter()
+            .filter(|&&(other_entity, other_parent, _)| {
+                other_entity != entity && other_parent == **parent
+            })
+            .map(|&(.., translation)| translation);
+        let avoidance = avoidance_direction(transform.translation, neighbors);
+
         let target_index = **path_index + 1;
         if let Some(passed_points) = move_agent(
             &mut transform,
             navigation,
             &path[target_index..],
+            avoidance,
             time.delta_secs(),
         ) {
             if passed_points != 0 {
@@ -159,9 +215,32 @@ fn navigate(
 }
 
 /// Marks an entity with [`Collider`] as a navigation mesh affector.
-#[derive(Component, Default)]
+///
+/// Navigation tiles under it will be rebuilt on its insertion, removal or movement.
+/// Can be specified in object manifests to make placed objects non-walkable.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
 pub struct Obstacle;
 
+/// Distance at which agents start to steer away from each other.
+const AVOIDANCE_DISTANCE: f32 = ACTOR_RADIUS * 3.0;
+
+/// Calculates a direction that pushes the agent away from nearby agents.
+///
+/// Closer agents contribute more. Vertical axis is ignored.
+fn avoidance_direction(translation: Vec3, neighbors: impl Iterator<Item = Vec3>) -> Vec3 {
+    let mut direction = Vec3::ZERO;
+    for neighbor in neighbors {
+        let disp = (translation - neighbor).with_y(0.0);
+        let distance = disp.length();
+        if distance < AVOIDANCE_DISTANCE && distance > f32::EPSILON {
+            direction += disp / distance * (1.0 - distance / AVOIDANCE_DISTANCE);
+        }
+    }
+
+    direction
+}
+
 /// Moves the agent along a path.
 ///
 /// The path should contain only the remaining points to reach.
@@ -169,12 +248,15 @@ pub struct Obstacle;
 /// Skips points that actor have projected past to prevent jitter
 /// when multiple points are near each other.
 ///
+/// Movement direction is biased by `avoidance` to steer around other agents.
+///
 /// Returns the number of points passed.
 /// If the path is completed, returns [`None`].
-fn move_agent(
+pub(super) fn move_agent(
     transform: &mut Transform,
     navigation: Navigation,
     path: &[Vec3],
+    avoidance: Vec3,
     delta: f32,
 ) -> Option<usize> {
     let movement_step = navigation.speed * delta;
@@ -193,7 +275,8 @@ fn move_agent(
     let disp = target_point - transform.translation;
     let target_rotation = transform.looking_to(disp, Vec3::Y).rotation;
     const ROTATION_SPEED: f32 = 10.0;
-    transform.translation += disp.normalize() * movement_step;
+    let direction = (disp.normalize() + avoidance).normalize_or(disp.normalize());
+    transform.translation += direction * movement_step;
     transform.rotation = transform
         .rotation
         .slerp(target_rotation, ROTATION_SPEED * delta);
@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+    utils::HashSet,
+};
+use vleue_navigator::prelude::*;
+
+use super::Obstacle;
+use crate::game_world::{actor::ACTOR_RADIUS, city::CityNavMesh};
+
+/// Tracks navigation tiles affected by obstacle changes and rebuilds navmeshes only when they get dirty.
+///
+/// Rebuilds are coalesced: while a navmesh is building, new dirty tiles are accumulated
+/// for the next rebuild. Baking itself happens asynchronously inside the navmesh updater.
+pub(super) struct NavTilesPlugin;
+
+impl Plugin for NavTilesPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(DIRTY_TILES).with_suffix(" tiles"))
+            .register_diagnostic(Diagnostic::new(REBUILDS))
+            .register_diagnostic(Diagnostic::new(BUILD_TIME).with_suffix(" ms"))
+            .add_observer(mark_removed)
+            .add_systems(
+                PostUpdate,
+                (mark_changed, request_rebuilds, measure_builds).chain(),
+            );
+    }
+}
+
+/// Number of dirty tiles that caused the last rebuild.
+pub const DIRTY_TILES: DiagnosticPath = DiagnosticPath::const_new("navigation/dirty_tiles");
+
+/// Total number of navmesh rebuilds.
+pub const REBUILDS: DiagnosticPath = DiagnosticPath::const_new("navigation/rebuilds");
+
+/// Time between the rebuild request and the built navmesh.
+pub const BUILD_TIME: DiagnosticPath = DiagnosticPath::const_new("navigation/build_time");
+
+/// Size of a navigation tile side.
+const TILE_SIZE: f32 = 10.0;
+
+fn mark_changed(
+    mut commands: Commands,
+    cities: Query<(&GlobalTransform, &CityNavMesh)>,
+    mut navmeshes: Query<&mut NavTiles>,
+    obstacles: Query<
+        (Entity, &Parent, &ColliderAabb, Option<&NavFootprint>),
+        (With<Obstacle>, Or<(Changed<ColliderAabb>, Added<Obstacle>)>),
+    >,
+) {
+    for (entity, parent, aabb, footprint) in &obstacles {
+        if aabb.min.cmpgt(aabb.max).any() {
+            // Collider is not generated yet.
+            continue;
+        }
+        let Ok((city_transform, navmesh_entity)) = cities.get(**parent) else {
+            continue;
+        };
+        let Ok(mut tiles) = navmeshes.get_mut(**navmesh_entity) else {
+            continue;
+        };
+
+        let city_offset = city_transform.translation().xz();
+        let rect = Rect::from_corners(aabb.min.xz() - city_offset, aabb.max.xz() - city_offset);
+        if footprint.is_some_and(|footprint| footprint.0 == rect) {
+            continue;
+        }
+
+        trace!("marking tiles dirty for obstacle `{entity}`");
+        if let Some(footprint) = footprint {
+            tiles.mark(footprint.0);
+        }
+        tiles.mark(rect);
+        commands.entity(entity).insert(NavFootprint(rect));
+    }
+}
+
+fn mark_removed(
+    trigger: Trigger<OnRemove, Obstacle>,
+    cities: Query<&CityNavMesh>,
+    mut navmeshes: Query<&mut NavTiles>,
+    obstacles: Query<(&Parent, &NavFootprint)>,
+) {
+    let Ok((parent, footprint)) = obstacles.get(trigger.entity()) else {
+        return;
+    };
+    let Ok(navmesh_entity) = cities.get(**parent) else {
+        return;
+    };
+    if let Ok(mut tiles) = navmeshes.get_mut(**navmesh_entity) {
+        trace!(
+            "marking tiles dirty for removed obstacle `{}`",
+            trigger.entity()
+        );
+        tiles.mark(footprint.0);
+    }
+}
+
+fn request_rebuilds(
+    mut diagnostics: Diagnostics,
+    time: Res<Time<Real>>,
+    mut navmeshes: Query<(
+        Entity,
+        &mut NavTiles,
+        &mut NavMeshUpdateMode,
+        &NavMeshStatus,
+    )>,
+) {
+    for (entity, mut tiles, mut update_mode, status) in &mut navmeshes {
+        if tiles.dirty.is_empty() || matches!(status, NavMeshStatus::Building) {
+            continue;
+        }
+
+        debug!(
+            "rebuilding navmesh `{entity}` for {} dirty tiles",
+            tiles.dirty.len()
+        );
+        diagnostics.add_measurement(&DIRTY_TILES, || tiles.dirty.len() as f64);
+        tiles.dirty.clear();
+        tiles.build_start = Some(time.elapsed());
+        *update_mode = NavMeshUpdateMode::OnDemand(true);
+    }
+}
+
+fn measure_builds(
+    mut diagnostics: Diagnostics,
+    mut rebuilds: Local<u32>,
+    time: Res<Time<Real>>,
+    mut navmeshes: Query<(&mut NavTiles, &NavMeshStatus), Changed<NavMeshStatus>>,
+) {
+    for (mut tiles, status) in &mut navmeshes {
+        if !matches!(status, NavMeshStatus::Built) {
+            continue;
+        }
+        let Some(build_start) = tiles.build_start.take() else {
+            continue;
+        };
+
+        *rebuilds += 1;
+        let build_time = time.elapsed() - build_start;
+        diagnostics.add_measurement(&REBUILDS, || *rebuilds as f64);
+        diagnostics.add_measurement(&BUILD_TIME, || build_time.as_secs_f64() * 1000.0);
+    }
+}
+
+/// Dirty tiles of a navmesh in city coordinates.
+#[derive(Component, Default)]
+pub(crate) struct NavTiles {
+    dirty: HashSet<IVec2>,
+    build_start: Option<Duration>,
+}
+
+impl NavTiles {
+    /// Marks all tiles under the area as dirty.
+    ///
+    /// The area is inflated by the agent radius since it affects the mesh around obstacles.
+    fn mark(&mut self, rect: Rect) {
+        let rect = rect.inflate(ACTOR_RADIUS);
+        let min = (rect.min / TILE_SIZE).floor().as_ivec2();
+        let max = (rect.max / TILE_SIZE).floor().as_ivec2();
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                self.dirty.insert(IVec2::new(x, y));
+            }
+        }
+    }
+}
+
+/// Last area that an obstacle occupied in city coordinates.
+///
+/// Used to mark the previous tiles as dirty when the obstacle moves or disappears.
+#[derive(Component)]
+struct NavFootprint(Rect);
@@ -1,5 +1,8 @@
+mod batching;
 pub(crate) mod door;
+pub(crate) mod light;
 pub mod placing_object;
+pub(crate) mod slots;
 pub(crate) mod wall_mount;
 
 use avian3d::prelude::*;
@@ -13,28 +16,47 @@ use bevy_replicon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    city::{City, HALF_CITY_SIZE},
+    city::HALF_CITY_SIZE,
     commands_history::{
         CommandConfirmation, CommandId, CommandRequest, ConfirmableCommand, EntityRecorder,
         PendingCommand,
     },
+    family::{Budget, FamilyOwner, TransactionKind, TransactionLog},
     highlighting::HIGHLIGHTING_VOLUME,
+    players::Players,
 };
-use crate::{asset::manifest::object_manifest::ObjectManifest, game_world::Layer};
+use crate::{
+    asset::manifest::object_manifest::{ObjectCategory, ObjectManifest},
+    game_world::Layer,
+};
+use batching::BatchingPlugin;
 use door::DoorPlugin;
+use light::LightPlugin;
 use placing_object::PlacingObjectPlugin;
+use slots::{ObjectSlot, ObjectSlots, SlotsPlugin, Slotted};
 use wall_mount::WallMountPlugin;
 
 pub(super) struct ObjectPlugin;
 
 impl Plugin for ObjectPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((DoorPlugin, PlacingObjectPlugin, WallMountPlugin))
-            .register_type::<Object>()
-            .replicate_group::<(Object, Transform)>()
-            .add_mapped_client_trigger::<CommandRequest<ObjectCommand>>(ChannelKind::Unordered)
-            .add_observer(init)
-            .add_observer(apply_command);
+        app.add_plugins((
+            BatchingPlugin,
+            DoorPlugin,
+            LightPlugin,
+            PlacingObjectPlugin,
+            SlotsPlugin,
+            WallMountPlugin,
+        ))
+        .register_type::<Object>()
+        .replicate_group::<(Object, Transform)>()
+        .add_mapped_client_trigger::<CommandRequest<ObjectCommand>>(ChannelKind::Unordered)
+        .add_observer(init)
+        .add_observer(apply_command)
+        .add_systems(
+            Update,
+            reload.run_if(on_event::<AssetEvent<ObjectManifest>>),
+        );
     }
 }
 
@@ -62,6 +84,8 @@ fn init(
         .unwrap_or_else(|| panic!("'{:?}' should be loaded", &**object));
 
     *name = Name::new(manifest.general.name.clone());
+    // Objects with the same scene share mesh and material handles and get merged into batches.
+    // Don't mutate these materials in place, replace handles for individual objects instead.
     scene_root.0 = asset_server.load(manifest.scene.clone());
 
     let mut entity = commands.entity(trigger.entity());
@@ -73,12 +97,62 @@ fn init(
     }
 }
 
+/// Applies changes from modified manifests to already placed objects.
+fn reload(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<ObjectManifest>>,
+    asset_server: Res<AssetServer>,
+    manifests: Res<Assets<ObjectManifest>>,
+    mut objects: Query<(Entity, &Object, &mut Name, &mut SceneRoot)>,
+) {
+    for &event in asset_events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        let Some(manifest_path) = asset_server.get_path(id) else {
+            continue;
+        };
+        let manifest = manifests
+            .get(id)
+            .expect("modified manifest should be loaded");
+
+        for (entity, object, mut name, mut scene_root) in &mut objects {
+            if **object != manifest_path {
+                continue;
+            }
+
+            debug!("reloading object '{manifest_path}' for `{entity}`");
+            *name = Name::new(manifest.general.name.clone());
+            let scene_handle = asset_server.load(manifest.scene.clone());
+            if scene_root.0 != scene_handle {
+                scene_root.0 = scene_handle;
+            }
+
+            let mut entity = commands.entity(entity);
+            for component in &manifest.components {
+                entity.insert_reflect(component.clone_value());
+            }
+        }
+    }
+}
+
 fn apply_command(
     trigger: Trigger<FromClient<CommandRequest<ObjectCommand>>>,
     mut commands: Commands,
-    mut objects: Query<&mut Transform, Without<City>>,
+    asset_server: Res<AssetServer>,
+    manifests: Res<Assets<ObjectManifest>>,
+    mut objects: Query<(&mut Transform, &Object, Option<&FamilyOwner>)>,
+    mut families: Query<(&mut Budget, &mut TransactionLog)>,
+    owners: Query<&ObjectSlots>,
+    slotted: Query<(Entity, &Parent, &Slotted)>,
+    parents: Query<&Parent>,
+    players: Res<Players>,
 ) {
-    // TODO: validate if command can be applied.
+    if !players.can_build(trigger.client_id) {
+        error!("`{:?}` is not allowed to build", trigger.client_id);
+        return;
+    }
+
     let mut confirmation = CommandConfirmation::new(trigger.event.id);
     match &trigger.event.command {
         ObjectCommand::Buy {
@@ -86,37 +160,152 @@ fn apply_command(
             city_entity,
             translation,
             rotation,
+            slot,
         } => {
-            if translation.y.abs() > HALF_CITY_SIZE {
-                error!("received translation {translation} with 'y' outside of city size");
+            if !inside_city(*translation) {
+                error!("received translation {translation} outside of city size");
+                return;
+            }
+
+            let Some(manifest) = asset_server
+                .get_handle(manifest_path)
+                .and_then(|handle| manifests.get(&handle))
+            else {
+                error!("received unknown object {manifest_path:?}");
                 return;
+            };
+            if manifest.category == ObjectCategory::Food {
+                error!("object {manifest_path:?} can't be bought");
+                return;
+            }
+
+            let slot_placement = match *slot {
+                Some(slot) => {
+                    let Some(point) = slot_point(slot, None, &owners, &slotted) else {
+                        return;
+                    };
+                    Some((slot, point))
+                }
+                None => None,
+            };
+
+            // Objects are free when placed by players without a family, like in the city editor.
+            let family_entity = players.family(trigger.client_id);
+            let family = family_entity.and_then(|entity| families.get_mut(entity).ok());
+            if let Some((mut budget, mut log)) = family {
+                if **budget < manifest.price {
+                    error!(
+                        "`{:?}` can't afford object {manifest_path:?}",
+                        trigger.client_id
+                    );
+                    return;
+                }
+                budget.spend(
+                    &mut log,
+                    TransactionKind::Purchase,
+                    manifest.general.name.clone(),
+                    manifest.price,
+                );
             }
 
             info!("`{:?}` buys object {manifest_path:?}", trigger.client_id);
-            commands.entity(*city_entity).with_children(|parent| {
-                let transform = Transform::from_translation(*translation).with_rotation(*rotation);
-                let entity = parent
-                    .spawn((Object(manifest_path.clone()), transform))
-                    .id();
-                confirmation.entity = Some(entity);
-            });
+            let object = Object(manifest_path.clone());
+            if let Some((slot, point)) = slot_placement {
+                commands.entity(slot.entity).with_children(|parent| {
+                    let transform = Transform::from_translation(point).with_rotation(*rotation);
+                    let entity = parent.spawn((object, transform, Slotted(slot.index))).id();
+                    confirmation.entity = Some(entity);
+                });
+            } else {
+                commands.entity(*city_entity).with_children(|parent| {
+                    let transform =
+                        Transform::from_translation(*translation).with_rotation(*rotation);
+                    let entity = parent.spawn((object, transform)).id();
+                    confirmation.entity = Some(entity);
+                });
+            }
+            if let (Some(entity), Some(family_entity)) = (confirmation.entity, family_entity) {
+                commands.entity(entity).insert(FamilyOwner(family_entity));
+            }
         }
         ObjectCommand::Move {
             entity,
             translation,
             rotation,
-        } => match objects.get_mut(*entity) {
-            Ok(mut transform) => {
-                info!("`{:?}` moves object `{entity}`", trigger.client_id);
+            slot,
+        } => {
+            let mut transform = match objects.get_mut(*entity) {
+                Ok((transform, ..)) => transform,
+                Err(e) => {
+                    error!("unable to move object `{entity}`: {e}");
+                    return;
+                }
+            };
+
+            // Confirm even invalid moves to despawn the ghost on client,
+            // the reverse command will be a no-op since the object stays in place.
+            info!("`{:?}` moves object `{entity}`", trigger.client_id);
+            if let Some(slot) = *slot {
+                if slot.entity == *entity
+                    || parents
+                        .iter_ancestors(slot.entity)
+                        .any(|parent_entity| parent_entity == *entity)
+                {
+                    error!("`{entity}` can't be placed into its own slot");
+                } else if let Some(point) = slot_point(slot, Some(*entity), &owners, &slotted) {
+                    transform.translation = point;
+                    transform.rotation = *rotation;
+                    commands
+                        .entity(*entity)
+                        .set_parent(slot.entity)
+                        .insert(Slotted(slot.index));
+                }
+            } else if !inside_city(*translation) {
+                error!("received translation {translation} outside of city size");
+            } else {
                 transform.translation = *translation;
                 transform.rotation = *rotation;
+                if let Ok((_, parent, _)) = slotted.get(*entity) {
+                    // Move from the slot owner back to the city.
+                    let city_entity = **parents.get(**parent).unwrap();
+                    commands
+                        .entity(*entity)
+                        .set_parent(city_entity)
+                        .remove::<Slotted>();
+                }
             }
-            Err(e) => {
-                error!("unable to move object `{entity}`: {e}");
+        }
+        ObjectCommand::Sell { entity } => {
+            let Ok((_, object, owner)) = objects.get(*entity) else {
+                error!("unable to sell `{entity}` since it's not an object");
                 return;
+            };
+
+            // Only objects bought by the family are refunded, objects placed for free are just removed.
+            if let Some(&owner) = owner {
+                if players.family(trigger.client_id) != Some(*owner) {
+                    error!(
+                        "`{:?}` can't sell `{entity}` owned by another family",
+                        trigger.client_id
+                    );
+                    return;
+                }
+
+                let manifest = asset_server
+                    .get_handle(&**object)
+                    .and_then(|handle| manifests.get(&handle));
+                if let (Some(manifest), Ok((mut budget, mut log))) =
+                    (manifest, families.get_mut(*owner))
+                {
+                    budget.earn(
+                        &mut log,
+                        TransactionKind::Sale,
+                        manifest.general.name.clone(),
+                        manifest.price,
+                    );
+                }
             }
-        },
-        ObjectCommand::Sell { entity } => {
+
             info!("`{:?}` sells object `{entity}`", trigger.client_id);
             commands.entity(*entity).despawn_recursive();
         }
@@ -128,6 +317,41 @@ fn apply_command(
     });
 }
 
+fn inside_city(translation: Vec3) -> bool {
+    translation.abs().max_element() <= HALF_CITY_SIZE
+}
+
+/// Returns slot position in the owner space if the slot exists and free.
+///
+/// `moving_entity` is ignored when checking if the slot is occupied.
+fn slot_point(
+    slot: ObjectSlot,
+    moving_entity: Option<Entity>,
+    owners: &Query<&ObjectSlots>,
+    slotted: &Query<(Entity, &Parent, &Slotted)>,
+) -> Option<Vec3> {
+    let Ok(slots) = owners.get(slot.entity) else {
+        error!("`{}` doesn't have slots", slot.entity);
+        return None;
+    };
+    let Some(&point) = slots.get(slot.index) else {
+        error!("`{}` doesn't have slot {}", slot.entity, slot.index);
+        return None;
+    };
+    let occupied = slotted.iter().any(|(entity, parent, slotted)| {
+        **parent == slot.entity && **slotted == slot.index && Some(entity) != moving_entity
+    });
+    if occupied {
+        error!(
+            "slot {} of `{}` is already occupied",
+            slot.index, slot.entity
+        );
+        return None;
+    }
+
+    Some(point)
+}
+
 /// Contains path to the object info.
 #[derive(Clone, Component, Debug, Default, Reflect, Serialize, Deserialize, Deref)]
 #[reflect(Component)]
@@ -140,7 +364,13 @@ fn apply_command(
     OutlineVolume(|| HIGHLIGHTING_VOLUME),
     CollisionLayers(|| CollisionLayers::new(
         Layer::Object,
-        [Layer::PlacingObject, Layer::Wall, Layer::PlacingWall],
+        [
+            Layer::PlacingObject,
+            Layer::Wall,
+            Layer::PlacingWall,
+            Layer::Fence,
+            Layer::PlacingFence,
+        ],
     ))
 )]
 pub(crate) struct Object(pub(crate) AssetPath<'static>);
@@ -151,12 +381,18 @@ enum ObjectCommand {
         manifest_path: AssetPath<'static>,
         city_entity: Entity,
         translation: Vec3,
+        /// Relative to the slot owner if the slot is set.
         rotation: Quat,
+        /// If set, the object will be placed into this slot ignoring translation.
+        slot: Option<ObjectSlot>,
     },
     Move {
         entity: Entity,
         translation: Vec3,
+        /// Relative to the slot owner if the slot is set.
         rotation: Quat,
+        /// If set, the object will be placed into this slot ignoring translation.
+        slot: Option<ObjectSlot>,
     },
     Sell {
         entity: Entity,
@@ -176,24 +412,31 @@ impl PendingCommand for ObjectCommand {
                 entity: Entity::PLACEHOLDER,
             },
             Self::Move { entity, .. } => {
-                let transform = world.get::<Transform>(entity).unwrap();
+                let transform = *world.get::<Transform>(entity).unwrap();
                 Self::Move {
                     entity,
                     translation: transform.translation,
                     rotation: transform.rotation,
+                    slot: current_slot(world, entity),
                 }
             }
             Self::Sell { entity } => {
                 recorder.record(entity);
+                let slot = current_slot(world, entity);
                 let entity = world.entity(entity);
                 let manifest_path = entity.get::<Object>().unwrap().0.clone();
-                let parent = entity.get::<Parent>().unwrap();
+                let parent = **entity.get::<Parent>().unwrap();
                 let transform = entity.get::<Transform>().unwrap();
+                let city_entity = match slot {
+                    Some(_) => **world.get::<Parent>(parent).unwrap(),
+                    None => parent,
+                };
                 Self::Buy {
                     manifest_path,
-                    city_entity: **parent,
+                    city_entity,
                     translation: transform.translation,
                     rotation: transform.rotation,
+                    slot,
                 }
             }
         };
@@ -204,6 +447,15 @@ impl PendingCommand for ObjectCommand {
     }
 }
 
+fn current_slot(world: &World, entity: Entity) -> Option<ObjectSlot> {
+    let slotted = world.get::<Slotted>(entity)?;
+    let parent = world.get::<Parent>(entity).unwrap();
+    Some(ObjectSlot {
+        entity: **parent,
+        index: **slotted,
+    })
+}
+
 impl ConfirmableCommand for ObjectCommand {
     fn confirm(
         mut self: Box<Self>,
@@ -224,9 +476,31 @@ impl ConfirmableCommand for ObjectCommand {
 impl MapEntities for ObjectCommand {
     fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
         match self {
-            Self::Buy { .. } => (),
-            Self::Move { entity, .. } => *entity = entity_mapper.map_entity(*entity),
+            Self::Buy { slot, .. } => {
+                if let Some(slot) = slot {
+                    slot.entity = entity_mapper.map_entity(slot.entity);
+                }
+            }
+            Self::Move { entity, slot, .. } => {
+                *entity = entity_mapper.map_entity(*entity);
+                if let Some(slot) = slot {
+                    slot.entity = entity_mapper.map_entity(slot.entity);
+                }
+            }
             Self::Sell { entity } => *entity = entity_mapper.map_entity(*entity),
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn city_bounds() {
+        assert!(inside_city(Vec3::ZERO));
+        assert!(inside_city(Vec3::new(HALF_CITY_SIZE, 0.0, -HALF_CITY_SIZE)));
+        assert!(!inside_city(Vec3::new(HALF_CITY_SIZE + 0.1, 0.0, 0.0)));
+        assert!(!inside_city(Vec3::new(0.0, 0.0, -HALF_CITY_SIZE - 0.1)));
+    }
+}
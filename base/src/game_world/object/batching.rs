@@ -0,0 +1,250 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, MeshVertexAttributeId},
+        render_resource::PrimitiveTopology,
+    },
+    scene::SceneInstanceReady,
+    utils::{Entry, HashMap, HashSet},
+};
+use bevy_mod_outline::OutlineVolume;
+
+use super::{slots::Slotted, Object};
+use crate::game_world::city::City;
+
+/// Merges meshes of placed objects that share the same scene to reduce draw calls.
+///
+/// Objects are taken out of their batch when moved or hidden, like while being picked up.
+/// Outlined objects show their own meshes on top of the batch since outlines are drawn from them.
+pub(super) struct BatchingPlugin;
+
+impl Plugin for BatchingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(init).add_systems(
+            PostUpdate,
+            (update_batches, update_outlined)
+                .chain()
+                .after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Minimum number of identical objects in a city to merge them.
+const MIN_BATCH_SIZE: usize = 8;
+
+/// Marks objects as batchable after their scene is spawned.
+///
+/// Animated objects are skipped because merged meshes can't be animated.
+fn init(
+    trigger: Trigger<SceneInstanceReady>,
+    mut commands: Commands,
+    objects: Query<(), With<Object>>,
+    children: Query<&Children>,
+    players: Query<(), With<AnimationPlayer>>,
+) {
+    if objects.get(trigger.entity()).is_err() {
+        return;
+    }
+
+    // The scene could be reloaded with new meshes, so take the object out of its batch.
+    let mut entity = commands.entity(trigger.entity());
+    entity.remove::<Batched>();
+
+    if children
+        .iter_descendants(trigger.entity())
+        .any(|child_entity| players.get(child_entity).is_ok())
+    {
+        debug!("skipping batching for animated `{}`", trigger.entity());
+        entity.remove::<Batchable>();
+    } else {
+        entity.insert(Batchable);
+    }
+}
+
+/// Rebuilds batches whose objects were added, removed, moved or hidden.
+fn update_batches(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut removed_batched: RemovedComponents<Batched>,
+    changed_objects: Query<
+        (Entity, &Parent, &SceneRoot),
+        (
+            With<Batchable>,
+            Or<(
+                Added<Batchable>,
+                Changed<Parent>,
+                Changed<Visibility>,
+                Changed<GlobalTransform>,
+            )>,
+        ),
+    >,
+    objects: Query<(Entity, &Parent, &SceneRoot, &Visibility), (With<Batchable>, Without<Slotted>)>,
+    outlines: Query<&OutlineVolume>,
+    batched: Query<&Batched>,
+    batches: Query<(Entity, &Parent, &ObjectBatch)>,
+    cities: Query<&GlobalTransform, With<City>>,
+    children: Query<&Children>,
+    scene_meshes: Query<(&Mesh3d, &MeshMaterial3d<StandardMaterial>, &GlobalTransform)>,
+    mut mesh_visibility: Query<&mut Visibility, Without<Batchable>>,
+) {
+    let mut dirty = HashSet::default();
+    for entity in removed_batched
+        .read()
+        .chain(changed_objects.iter().map(|(entity, ..)| entity))
+    {
+        for (_, parent, batch) in &batches {
+            if batch.members.contains(&entity) {
+                dirty.insert((**parent, batch.scene));
+            }
+        }
+    }
+    for (_, parent, scene_root) in &changed_objects {
+        dirty.insert((**parent, scene_root.id()));
+    }
+
+    for (city_entity, scene) in dirty {
+        let Ok(city_transform) = cities.get(city_entity) else {
+            continue;
+        };
+
+        let mut old_members = Vec::new();
+        if let Some((batch_entity, _, batch)) = batches
+            .iter()
+            .find(|(_, parent, batch)| ***parent == city_entity && batch.scene == scene)
+        {
+            commands.entity(batch_entity).despawn_recursive();
+            old_members.extend_from_slice(&batch.members);
+        }
+
+        let mut members: Vec<_> = objects
+            .iter()
+            .filter(|&(_, parent, scene_root, &visibility)| {
+                **parent == city_entity
+                    && scene_root.id() == scene
+                    && visibility != Visibility::Hidden
+            })
+            .map(|(entity, ..)| entity)
+            .collect();
+        if members.len() < MIN_BATCH_SIZE {
+            members.clear();
+        }
+
+        for &entity in old_members
+            .iter()
+            .filter(|entity| !members.contains(entity))
+        {
+            let Ok(batched) = batched.get(entity) else {
+                continue;
+            };
+            trace!("taking `{entity}` out of its batch");
+            let mut iter = mesh_visibility.iter_many_mut(&batched.mesh_entities);
+            while let Some(mut visibility) = iter.fetch_next() {
+                *visibility = Visibility::Inherited;
+            }
+            commands.entity(entity).remove::<Batched>();
+        }
+
+        if members.is_empty() {
+            continue;
+        }
+
+        debug!("batching {} objects in `{city_entity}`", members.len());
+        let city_affine = city_transform.affine().inverse();
+        let mut merged_meshes = HashMap::<_, (Handle<StandardMaterial>, Mesh)>::default();
+        for &entity in &members {
+            let mut mesh_entities = Vec::new();
+            for child_entity in children.iter_descendants(entity) {
+                let Ok((mesh_handle, material, transform)) = scene_meshes.get(child_entity) else {
+                    continue;
+                };
+                let Some(mesh) = meshes.get(mesh_handle) else {
+                    continue;
+                };
+                if mesh.primitive_topology() != PrimitiveTopology::TriangleList
+                    || mesh.indices().is_none()
+                {
+                    continue;
+                }
+
+                let relative = Transform::from_matrix((city_affine * transform.affine()).into());
+                let mesh = mesh.clone().transformed_by(relative);
+                let attributes: Vec<MeshVertexAttributeId> = mesh
+                    .attributes()
+                    .map(|(attribute, _)| attribute.id)
+                    .collect();
+                match merged_meshes.entry((material.id(), attributes)) {
+                    Entry::Occupied(mut entry) => entry.get_mut().1.merge(&mesh),
+                    Entry::Vacant(entry) => {
+                        entry.insert((material.0.clone(), with_u32_indices(mesh)));
+                    }
+                }
+                mesh_entities.push(child_entity);
+            }
+
+            let outlined = outlines.get(entity).is_ok_and(|outline| outline.visible);
+            if !outlined {
+                let mut iter = mesh_visibility.iter_many_mut(&mesh_entities);
+                while let Some(mut visibility) = iter.fetch_next() {
+                    *visibility = Visibility::Hidden;
+                }
+            }
+            commands.entity(entity).insert(Batched { mesh_entities });
+        }
+
+        commands.entity(city_entity).with_children(|parent| {
+            parent
+                .spawn(ObjectBatch { scene, members })
+                .with_children(|parent| {
+                    for (material, mesh) in merged_meshes.into_values() {
+                        parent.spawn((Mesh3d(meshes.add(mesh)), MeshMaterial3d(material)));
+                    }
+                });
+        });
+    }
+}
+
+/// Shows own meshes of batched objects while they are outlined.
+fn update_outlined(
+    objects: Query<(&Batched, &OutlineVolume), Changed<OutlineVolume>>,
+    mut mesh_visibility: Query<&mut Visibility, Without<Batchable>>,
+) {
+    for (batched, outline) in &objects {
+        let mut iter = mesh_visibility.iter_many_mut(&batched.mesh_entities);
+        while let Some(mut visibility) = iter.fetch_next() {
+            *visibility = if outline.visible {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+/// Converts indices to 32 bits to let the mesh grow beyond [`u16`] vertices on merging.
+fn with_u32_indices(mut mesh: Mesh) -> Mesh {
+    if let Some(Indices::U16(indices)) = mesh.indices() {
+        let indices = indices.iter().map(|&index| index.into()).collect();
+        mesh.insert_indices(Indices::U32(indices));
+    }
+    mesh
+}
+
+/// Object with a spawned static scene that can be merged into a batch.
+#[derive(Component)]
+struct Batchable;
+
+/// Object whose meshes are hidden because they are drawn as part of an [`ObjectBatch`].
+#[derive(Component)]
+struct Batched {
+    mesh_entities: Vec<Entity>,
+}
+
+/// Merged meshes of objects with the same scene inside a city.
+///
+/// Children contain a mesh for each material.
+#[derive(Component)]
+#[require(Name(|| Name::new("Object batch")), Transform, Visibility)]
+struct ObjectBatch {
+    scene: AssetId<Scene>,
+    members: Vec<Entity>,
+}
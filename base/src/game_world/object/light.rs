@@ -0,0 +1,145 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::placing_object::PlacingObject;
+use crate::game_world::Layer;
+
+pub(super) struct LightPlugin;
+
+impl Plugin for LightPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ObjectLight>()
+            .register_type::<LightOff>()
+            .replicate::<LightOff>()
+            .add_observer(init)
+            .add_observer(turn_off)
+            .add_observer(turn_on);
+    }
+}
+
+/// Height above object origins from which walls between lights are searched.
+const WALL_RAY_HEIGHT: f32 = 1.0;
+
+/// Maximum distance between lights in the same room.
+const ROOM_DISTANCE: f32 = 15.0;
+
+fn init(
+    trigger: Trigger<OnAdd, ObjectLight>,
+    mut commands: Commands,
+    objects: Query<(&ObjectLight, Has<LightOff>), Without<PlacingObject>>,
+) {
+    let Ok((light, off)) = objects.get(trigger.entity()) else {
+        return;
+    };
+
+    debug!("spawning light for `{}`", trigger.entity());
+    let visibility = if off {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn((
+            LightSource,
+            PointLight {
+                intensity: light.intensity,
+                range: light.range,
+                shadows_enabled: true,
+                ..Default::default()
+            },
+            Transform::from_translation(light.offset),
+            visibility,
+        ));
+    });
+}
+
+fn turn_off(
+    trigger: Trigger<OnAdd, LightOff>,
+    children: Query<&Children>,
+    mut sources: Query<&mut Visibility, With<LightSource>>,
+) {
+    debug!("turning off `{}`", trigger.entity());
+    let Ok(children) = children.get(trigger.entity()) else {
+        return;
+    };
+    let mut iter = sources.iter_many_mut(children);
+    while let Some(mut visibility) = iter.fetch_next() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn turn_on(
+    trigger: Trigger<OnRemove, LightOff>,
+    children: Query<&Children>,
+    mut sources: Query<&mut Visibility, With<LightSource>>,
+) {
+    debug!("turning on `{}`", trigger.entity());
+    let Ok(children) = children.get(trigger.entity()) else {
+        return;
+    };
+    let mut iter = sources.iter_many_mut(children);
+    while let Some(mut visibility) = iter.fetch_next() {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+/// Returns lights in the same room as the given light, including itself.
+///
+/// There are no rooms, so lights are considered to be in the same room
+/// when they are close and not separated by a wall.
+pub(crate) fn room_lights(
+    spatial_query: &SpatialQuery,
+    lights: &Query<(Entity, &GlobalTransform), (With<ObjectLight>, Without<PlacingObject>)>,
+    light_entity: Entity,
+) -> Vec<Entity> {
+    let Ok((_, transform)) = lights.get(light_entity) else {
+        return Vec::new();
+    };
+
+    let origin = transform.translation() + Vec3::Y * WALL_RAY_HEIGHT;
+    let filter = SpatialQueryFilter::from_mask(Layer::Wall);
+    lights
+        .iter()
+        .filter(|&(entity, other_transform)| {
+            if entity == light_entity {
+                return true;
+            }
+
+            let target = other_transform.translation() + Vec3::Y * WALL_RAY_HEIGHT;
+            let Ok((direction, distance)) = Dir3::new_and_length(target - origin) else {
+                return true;
+            };
+
+            distance <= ROOM_DISTANCE
+                && spatial_query
+                    .cast_ray(origin, direction, distance, true, &filter)
+                    .is_none()
+        })
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+/// Point light emitted by an object.
+///
+/// Inserted from object manifests.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub(crate) struct ObjectLight {
+    intensity: f32,
+    range: f32,
+
+    /// Light position in object space.
+    offset: Vec3,
+}
+
+/// Marks an object whose light is switched off.
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub(crate) struct LightOff;
+
+/// Light spawned for [`ObjectLight`].
+#[derive(Component)]
+#[require(Name(|| Name::new("Object light")))]
+struct LightSource;
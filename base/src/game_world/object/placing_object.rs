@@ -1,9 +1,12 @@
+pub(crate) mod placement_rules;
 pub(crate) mod side_snap;
+pub(crate) mod slot_snap;
 pub(crate) mod wall_snap;
 
 use std::{
     f32::consts::{FRAC_PI_2, FRAC_PI_4, PI},
     fmt::Debug,
+    iter,
 };
 
 use avian3d::prelude::*;
@@ -13,23 +16,31 @@ use bevy::{
     prelude::*,
 };
 use bevy_enhanced_input::prelude::*;
+use bevy_mod_outline::OutlineVolume;
 
 use crate::{
     alpha_color::{self, AlphaColor},
     asset::manifest::object_manifest::ObjectManifest,
+    audio::{Sound, SoundPlay},
     game_world::{
         city::CityMode,
         commands_history::{CommandsHistory, PendingDespawn},
+        cursor_mode::PlacingCursor,
         family::building::BuildingMode,
         highlighting::HighlightDisabler,
-        object::{Object, ObjectCommand},
+        object::{
+            slots::{ObjectSlot, Slotted},
+            Object, ObjectCommand,
+        },
         player_camera::{CameraCaster, PlayerCamera},
         Layer,
     },
     ghost::Ghost,
     settings::Settings,
 };
+use placement_rules::{PlacementRules, PlacementRulesPlugin};
 use side_snap::SideSnapPlugin;
+use slot_snap::{PlacingSlot, SlotSnap, SlotSnapPlugin};
 use wall_snap::WallSnapPlugin;
 
 pub(super) struct PlacingObjectPlugin;
@@ -38,17 +49,32 @@ impl Plugin for PlacingObjectPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(WallSnapPlugin)
             .add_plugins(SideSnapPlugin)
+            .add_plugins(SlotSnapPlugin)
+            .add_plugins(PlacementRulesPlugin)
             .add_input_context::<PlacingObject>()
             .add_observer(pick)
             .add_observer(init)
             .add_observer(rotate)
+            .add_observer(enable_free_placement)
+            .add_observer(disable_free_placement)
+            .add_observer(clear_overlaps)
+            .add_observer(show_overlap)
+            .add_observer(hide_overlap)
             .add_observer(sell)
             .add_observer(cancel.never_param_warn())
             .add_observer(confirm)
             .add_systems(
                 Update,
-                apply_position
-                    .never_param_warn()
+                (
+                    apply_position.never_param_warn(),
+                    update_collision.never_param_warn(),
+                    update_overlaps.never_param_warn(),
+                    conform_to_ground
+                        .never_param_warn()
+                        .after(wall_snap::snap)
+                        .after(SideSnapPlugin::snap)
+                        .after(slot_snap::snap),
+                )
                     .run_if(in_state(CityMode::Objects).or(in_state(BuildingMode::Objects))),
             )
             .add_systems(
@@ -66,7 +92,8 @@ fn pick(
     city_mode: Option<Res<State<CityMode>>>,
     building_mode: Option<Res<State<BuildingMode>>>,
     mut commands: Commands,
-    objects: Query<(Entity, &Parent), With<Object>>,
+    objects: Query<(Entity, &Parent, Has<Slotted>), With<Object>>,
+    parents: Query<&Parent>,
     placing_objects: Query<(), With<PlacingObject>>,
 ) {
     if trigger.button != PointerButton::Primary {
@@ -80,13 +107,20 @@ fn pick(
     if !placing_objects.is_empty() {
         return;
     }
-    let Ok((object_entity, parent)) = objects.get(trigger.entity()) else {
+    let Ok((object_entity, parent, slotted)) = objects.get(trigger.entity()) else {
         return;
     };
     trigger.propagate(false);
 
+    // Slotted objects are children of the slot owner, but placing happens in city space.
+    let city_entity = if slotted {
+        **parents.get(**parent).unwrap()
+    } else {
+        **parent
+    };
+
     info!("picking object `{object_entity}`");
-    commands.entity(**parent).with_children(|parent| {
+    commands.entity(city_entity).with_children(|parent| {
         parent.spawn(PlacingObject::Moving(object_entity));
     });
 }
@@ -108,7 +142,8 @@ fn init(
         ),
         Without<PlayerCamera>,
     >,
-    objects: Query<(&Object, &Transform), Without<PlacingObject>>,
+    objects: Query<(&Object, &Transform, &Parent, Has<Slotted>), Without<PlacingObject>>,
+    owners: Query<&Transform, Without<PlacingObject>>,
 ) {
     let (&placing_object, mut scene_root, mut state, mut transform) =
         placing_objects.get_mut(trigger.entity()).unwrap();
@@ -130,9 +165,15 @@ fn init(
             (manifest, Vec3::ZERO, rotation)
         }
         PlacingObject::Moving(object_entity) => {
-            let (object, &transform) = objects
+            let (object, &transform, parent, slotted) = objects
                 .get(object_entity)
                 .expect("moving object should reference a valid object");
+            let transform = if slotted {
+                let owner_transform = owners.get(**parent).unwrap();
+                owner_transform.mul_transform(transform)
+            } else {
+                transform
+            };
 
             let manifest_handle = asset_server
                 .get_handle(&**object)
@@ -180,6 +221,19 @@ fn rotate(
     );
 }
 
+fn enable_free_placement(trigger: Trigger<Started<FreeObjectPlacement>>, mut commands: Commands) {
+    debug!("enabling free placement");
+    commands.entity(trigger.entity()).insert(FreePlacement);
+}
+
+fn disable_free_placement(
+    trigger: Trigger<Completed<FreeObjectPlacement>>,
+    mut commands: Commands,
+) {
+    debug!("disabling free placement");
+    commands.entity(trigger.entity()).remove::<FreePlacement>();
+}
+
 fn sell(
     trigger: Trigger<Completed<SellObject>>,
     mut commands: Commands,
@@ -213,15 +267,26 @@ fn confirm(
         &Transform,
         &PlacingObject,
         &PlacingObjectState,
-        &CollidingEntities,
+        Option<&PlacingSlot>,
     )>,
+    owners: Query<&Transform, Without<PlacingObject>>,
 ) {
-    let (parent, translation, &placing_object, state, colliding_entities) = *placing_object;
+    let (parent, translation, &placing_object, state, placing_slot) = *placing_object;
 
-    if !state.allowed_place || !colliding_entities.is_empty() {
+    if !state.can_place() {
         return;
     }
 
+    // Rotation inside a slot is stored relative to the slot owner.
+    let slot = placing_slot.and_then(|slot| **slot);
+    let rotation = match slot {
+        Some(ObjectSlot { entity, .. }) => {
+            let owner_transform = owners.get(entity).unwrap();
+            owner_transform.rotation.inverse() * translation.rotation
+        }
+        None => translation.rotation,
+    };
+
     let command_id = match placing_object {
         PlacingObject::Spawning(id) => {
             let manifest_path = asset_server
@@ -231,13 +296,15 @@ fn confirm(
                 manifest_path: manifest_path.into_owned(),
                 city_entity: **parent,
                 translation: translation.translation,
-                rotation: translation.rotation,
+                rotation,
+                slot,
             })
         }
         PlacingObject::Moving(entity) => history.push_pending(ObjectCommand::Move {
             entity,
             translation: translation.translation,
-            rotation: translation.rotation,
+            rotation,
+            slot,
         }),
     };
 
@@ -245,6 +312,10 @@ fn confirm(
         .entity(trigger.entity())
         .insert(PendingDespawn { command_id })
         .remove::<(PlacingObject, PlacingObjectState)>();
+    commands.trigger_targets(
+        SoundPlay::new(Sound::PlaceObject).with_translation(translation.translation),
+        **parent,
+    );
 
     info!("confirming `{placing_object:?}`");
 }
@@ -259,14 +330,178 @@ fn apply_position(
     }
 }
 
-fn update_alpha(
+/// Updates collision violation.
+///
+/// For objects with [`SlotSnap`] it's updated during snapping.
+fn update_collision(
     placing_object: Single<
-        (&mut AlphaColor, &PlacingObjectState, &CollidingEntities),
-        Or<(Changed<CollidingEntities>, Changed<PlacingObjectState>)>,
+        (&mut PlacingObjectState, &CollidingEntities),
+        (Changed<CollidingEntities>, Without<SlotSnap>),
     >,
 ) {
-    let (mut alpha, state, colliding_entities) = placing_object.into_inner();
-    if state.allowed_place && colliding_entities.is_empty() {
+    let (mut state, colliding_entities) = placing_object.into_inner();
+    state.set_violated(
+        PlacementViolation::Collision,
+        !colliding_entities.is_empty(),
+    );
+}
+
+/// Marks objects that intersect with the placing object.
+fn update_overlaps(
+    mut commands: Commands,
+    placing_object: Single<&CollidingEntities, (With<PlacingObject>, Changed<CollidingEntities>)>,
+    parents: Query<&Parent>,
+    objects: Query<(), With<Object>>,
+    overlapping: Query<Entity, With<Overlapping>>,
+) {
+    let mut overlapped = Vec::new();
+    for &entity in placing_object.iter() {
+        // Colliders can be placed on children.
+        if let Some(object_entity) = iter::once(entity)
+            .chain(parents.iter_ancestors(entity))
+            .find(|&entity| objects.get(entity).is_ok())
+        {
+            overlapped.push(object_entity);
+        }
+    }
+
+    for entity in &overlapping {
+        if !overlapped.contains(&entity) {
+            commands.entity(entity).remove::<Overlapping>();
+        }
+    }
+    for entity in overlapped {
+        if !overlapping.contains(entity) {
+            commands.entity(entity).insert(Overlapping);
+        }
+    }
+}
+
+fn clear_overlaps(
+    _trigger: Trigger<OnRemove, PlacingObject>,
+    mut commands: Commands,
+    overlapping: Query<Entity, With<Overlapping>>,
+) {
+    for entity in &overlapping {
+        commands.entity(entity).remove::<Overlapping>();
+    }
+}
+
+fn show_overlap(trigger: Trigger<OnAdd, Overlapping>, mut volumes: Query<&mut OutlineVolume>) {
+    if let Ok(mut outline) = volumes.get_mut(trigger.entity()) {
+        debug!("showing overlap for `{}`", trigger.entity());
+        outline.visible = true;
+        outline.colour = RED.into();
+    }
+}
+
+fn hide_overlap(trigger: Trigger<OnRemove, Overlapping>, mut volumes: Query<&mut OutlineVolume>) {
+    if let Ok(mut outline) = volumes.get_mut(trigger.entity()) {
+        debug!("hiding overlap for `{}`", trigger.entity());
+        outline.visible = false;
+    }
+}
+
+/// Places the object on top of the ground under its footprint.
+///
+/// Disallows placing if the ground is too steep or missing when required by [`PlacementRules`].
+fn conform_to_ground(
+    spatial_query: SpatialQuery,
+    cities: Query<&GlobalTransform>,
+    placing_object: Single<(
+        &mut Transform,
+        &mut PlacingObjectState,
+        &Parent,
+        &ColliderAabb,
+        Option<&PlacementRules>,
+        Option<&PlacingSlot>,
+    )>,
+) {
+    let (mut transform, mut state, parent, aabb, rules, placing_slot) = placing_object.into_inner();
+    if placing_slot.is_some_and(|slot| slot.is_some()) {
+        // Placed on top of another object.
+        let state = state.bypass_change_detection();
+        let changed = state.set_violated(PlacementViolation::SteepGround, false)
+            | state.set_violated(PlacementViolation::RequiresFloor, false);
+        if changed {
+            state.set_changed();
+        }
+        return;
+    }
+    if aabb.min.cmpgt(aabb.max).any() {
+        // Collider is not generated yet.
+        return;
+    }
+
+    let city_transform = cities.get(**parent).unwrap();
+    let origin_height = aabb.max.y + GROUND_SAMPLE_HEIGHT;
+    let center = aabb.center();
+    let filter = SpatialQueryFilter::from_mask(Layer::Ground);
+
+    let mut max_height = None;
+    let mut max_slope = 0.0_f32;
+    let mut missed = false;
+    for (x, z) in [
+        (center.x, center.z),
+        (aabb.min.x, aabb.min.z),
+        (aabb.min.x, aabb.max.z),
+        (aabb.max.x, aabb.min.z),
+        (aabb.max.x, aabb.max.z),
+    ] {
+        let origin = Vec3::new(x, origin_height, z);
+        if let Some(hit) = spatial_query.cast_ray(
+            origin,
+            Dir3::NEG_Y,
+            GROUND_SAMPLE_HEIGHT * 2.0 + aabb.size().y,
+            true,
+            &filter,
+        ) {
+            let point = origin - Vec3::Y * hit.distance;
+            let height = city_transform.affine().inverse().transform_point3(point).y;
+            max_height = Some(max_height.map_or(height, |max: f32| max.max(height)));
+            max_slope = max_slope.max(hit.normal.angle_between(Vec3::Y));
+        } else {
+            missed = true;
+        }
+    }
+
+    let no_floor = missed && rules.is_some_and(|rules| rules.requires_floor);
+    if state
+        .bypass_change_detection()
+        .set_violated(PlacementViolation::RequiresFloor, no_floor)
+    {
+        debug!("changing missing floor to `{no_floor}`");
+        state.set_changed();
+    }
+
+    let Some(height) = max_height else {
+        return;
+    };
+
+    // Use the highest point to avoid sinking into the ground.
+    transform.translation.y = height;
+
+    let steep = max_slope > MAX_GROUND_SLOPE;
+    if state
+        .bypass_change_detection()
+        .set_violated(PlacementViolation::SteepGround, steep)
+    {
+        debug!("changing steep ground to `{steep}`");
+        state.set_changed();
+    }
+}
+
+/// Height above the object from which the ground is sampled.
+const GROUND_SAMPLE_HEIGHT: f32 = 1.0;
+
+/// Maximum ground slope in radians on which objects can be placed.
+const MAX_GROUND_SLOPE: f32 = 0.35;
+
+fn update_alpha(
+    placing_object: Single<(&mut AlphaColor, &PlacingObjectState), Changed<PlacingObjectState>>,
+) {
+    let (mut alpha, state) = placing_object.into_inner();
+    if state.can_place() {
         **alpha = WHITE.into();
     } else {
         **alpha = RED.into();
@@ -282,6 +517,7 @@ fn update_alpha(
     StateScoped::<BuildingMode>(|| StateScoped(BuildingMode::Objects)),
     StateScoped::<CityMode>(|| StateScoped(CityMode::Objects)),
     HighlightDisabler,
+    PlacingCursor,
     AlphaColor(|| AlphaColor(WHITE.into())),
     SceneRoot,
     RigidBody(|| RigidBody::Kinematic),
@@ -293,6 +529,8 @@ fn update_alpha(
             Layer::PlacingObject,
             Layer::Wall,
             Layer::PlacingWall,
+            Layer::Fence,
+            Layer::PlacingFence,
         ],
     )),
 )]
@@ -314,14 +552,18 @@ impl InputContext for PlacingObject {
                 negative: &settings.keyboard.rotate_right,
             },
             MouseButton::Right,
-            GamepadButton::West,
+            &settings.gamepad.rotate_object,
         ));
         ctx.bind::<SellObject>()
-            .to((&settings.keyboard.delete, GamepadButton::North));
+            .to((&settings.keyboard.delete, &settings.gamepad.delete));
         ctx.bind::<CancelObject>()
-            .to((KeyCode::Escape, GamepadButton::East));
+            .to((KeyCode::Escape, &settings.gamepad.cancel));
         ctx.bind::<ConfirmObject>()
-            .to((MouseButton::Left, GamepadButton::South));
+            .to((MouseButton::Left, &settings.gamepad.confirm));
+        ctx.bind::<FreeObjectPlacement>().to((
+            &settings.keyboard.free_placement,
+            &settings.gamepad.free_placement,
+        ));
 
         ctx
     }
@@ -343,28 +585,88 @@ struct CancelObject;
 #[input_action(output = bool)]
 struct ConfirmObject;
 
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct FreeObjectPlacement;
+
+/// Disables optional snapping for the placing object while the free placement action is held.
+///
+/// Snapping that is required for the object, like doors in walls, still applies.
+#[derive(Component)]
+struct FreePlacement;
+
+/// Marks objects that intersect with the placing object to outline them.
+#[derive(Component)]
+struct Overlapping;
+
 #[derive(Component, Default, Deref, DerefMut)]
 pub struct ObjectRotationLimit(Option<f32>);
 
 /// Controls if an object can be placed.
 ///
 /// Stored as a separate component to avoid triggering change detection to update the object material.
-#[derive(Component)]
-struct PlacingObjectState {
+#[derive(Component, Default)]
+pub struct PlacingObjectState {
     /// An offset between cursor position on first creation and object origin.
     cursor_offset: Vec3,
 
-    /// Additional object condition for placing.
+    /// Rules that the current position breaks.
     ///
     /// For example, a door can be placed only on a wall. Controlled by other plugins.
-    allowed_place: bool,
+    violations: Vec<PlacementViolation>,
+}
+
+impl PlacingObjectState {
+    fn can_place(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Returns the first broken rule, if any.
+    pub fn violation(&self) -> Option<PlacementViolation> {
+        self.violations.first().copied()
+    }
+
+    /// Marks the rule as broken or satisfied.
+    ///
+    /// Returns `true` if the state changed.
+    fn set_violated(&mut self, violation: PlacementViolation, violated: bool) -> bool {
+        let index = self.violations.iter().position(|&v| v == violation);
+        match (index, violated) {
+            (None, true) => self.violations.push(violation),
+            (Some(index), false) => {
+                self.violations.remove(index);
+            }
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+/// Reason why an object can't be placed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlacementViolation {
+    Collision,
+    SteepGround,
+    RequiresWall,
+    RequiresSlot,
+    RequiresFloor,
+    OutdoorOnly,
+    RequiresCeiling,
+    Clearance,
 }
 
-impl Default for PlacingObjectState {
-    fn default() -> Self {
-        Self {
-            cursor_offset: Default::default(),
-            allowed_place: true,
+impl PlacementViolation {
+    pub fn text(self) -> &'static str {
+        match self {
+            PlacementViolation::Collision => "Overlaps with something",
+            PlacementViolation::SteepGround => "The ground is too steep",
+            PlacementViolation::RequiresWall => "Should be placed on a wall",
+            PlacementViolation::RequiresSlot => "Should be placed on a surface",
+            PlacementViolation::RequiresFloor => "Needs ground under the whole object",
+            PlacementViolation::OutdoorOnly => "Can be placed only outdoors",
+            PlacementViolation::RequiresCeiling => "Should be placed under a ceiling",
+            PlacementViolation::Clearance => "Needs free space in front",
         }
     }
 }
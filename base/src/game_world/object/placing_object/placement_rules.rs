@@ -0,0 +1,118 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use super::{PlacementViolation, PlacingObject, PlacingObjectState};
+use crate::game_world::{
+    city::CityMode,
+    family::building::{wall, BuildingMode},
+    Layer,
+};
+
+pub(super) struct PlacementRulesPlugin;
+
+impl Plugin for PlacementRulesPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PlacementRules>().add_systems(
+            Update,
+            check
+                .never_param_warn()
+                .after(super::conform_to_ground)
+                .run_if(in_state(CityMode::Objects).or(in_state(BuildingMode::Objects))),
+        );
+    }
+}
+
+/// Height of the area checked by [`PlacementRules::clearance`].
+const CLEARANCE_HEIGHT: f32 = 1.0;
+
+fn check(
+    spatial_query: SpatialQuery,
+    parents: Query<&Parent>,
+    placing_object: Single<(
+        &GlobalTransform,
+        &mut PlacingObjectState,
+        &PlacingObject,
+        &PlacementRules,
+    )>,
+) {
+    let (transform, mut state, &placing_object, rules) = placing_object.into_inner();
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+
+    if rules.outdoor_only {
+        let indoor = wall::is_indoors(&spatial_query, translation);
+        if state
+            .bypass_change_detection()
+            .set_violated(PlacementViolation::OutdoorOnly, indoor)
+        {
+            debug!("changing indoor placement to `{indoor}`");
+            state.set_changed();
+        }
+    }
+
+    if rules.ceiling_mount {
+        let indoor = wall::is_indoors(&spatial_query, translation);
+        if state
+            .bypass_change_detection()
+            .set_violated(PlacementViolation::RequiresCeiling, !indoor)
+        {
+            debug!("changing ceiling placement to `{indoor}`");
+            state.set_changed();
+        }
+    }
+
+    if let Some(area) = rules.clearance {
+        let center = area.center();
+        let position =
+            translation + rotation * Vec3::new(center.x, CLEARANCE_HEIGHT / 2.0, center.y);
+        let collider = Collider::cuboid(area.width(), CLEARANCE_HEIGHT, area.height());
+        let filter = SpatialQueryFilter::from_mask([Layer::Object, Layer::Wall, Layer::Fence]);
+        let blocked = spatial_query
+            .shape_intersections(&collider, position, rotation, &filter)
+            .into_iter()
+            .any(|entity| match placing_object {
+                // Ignore the original object while moving it.
+                PlacingObject::Moving(object_entity) => {
+                    entity != object_entity
+                        && parents
+                            .iter_ancestors(entity)
+                            .all(|parent_entity| parent_entity != object_entity)
+                }
+                PlacingObject::Spawning(_) => true,
+            });
+        if state
+            .bypass_change_detection()
+            .set_violated(PlacementViolation::Clearance, blocked)
+        {
+            debug!("changing blocked clearance to `{blocked}`");
+            state.set_changed();
+        }
+    }
+}
+
+/// Constraints for placing an object.
+///
+/// Wall requirement is controlled by [`WallSnap`](super::wall_snap::WallSnap).
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component, Default)]
+pub(crate) struct PlacementRules {
+    /// Requires ground under the whole object, so it can't hang over pools or basements.
+    #[reflect(default)]
+    pub(super) requires_floor: bool,
+
+    /// Disallows placing between walls.
+    #[reflect(default)]
+    outdoor_only: bool,
+
+    /// Allows placing only between walls, for objects hanging from the ceiling.
+    ///
+    /// There are no ceilings yet, so the object's model is expected to be at the wall height
+    /// and a place surrounded by walls is considered to be covered.
+    #[reflect(default)]
+    ceiling_mount: bool,
+
+    /// Area on the XZ plane in object space that should be free of objects and walls.
+    ///
+    /// Usually in front of the object, such as space for watching a TV.
+    #[reflect(default)]
+    clearance: Option<Rect>,
+}
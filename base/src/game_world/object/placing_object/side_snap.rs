@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use super::PlacingObject;
+use super::{FreePlacement, PlacingObject};
 use crate::game_world::{city::CityMode, family::building::BuildingMode};
 
 pub(super) struct SideSnapPlugin;
@@ -58,8 +58,11 @@ impl SideSnapPlugin {
         }
     }
 
-    fn snap(
-        placing_object: Single<(&mut Transform, &SideSnap), With<PlacingObject>>,
+    pub(super) fn snap(
+        placing_object: Single<
+            (&mut Transform, &SideSnap),
+            (With<PlacingObject>, Without<FreePlacement>),
+        >,
         objects: Query<
             (&SideSnap, &Transform, &SideSnapNodes, &Visibility),
             Without<PlacingObject>,
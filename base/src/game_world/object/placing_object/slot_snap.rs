@@ -0,0 +1,153 @@
+use std::iter;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use super::{FreePlacement, PlacementViolation, PlacingObject, PlacingObjectState};
+use crate::game_world::{
+    city::CityMode,
+    family::building::BuildingMode,
+    object::{
+        slots::{ObjectSlot, ObjectSlots, Slotted},
+        Object,
+    },
+};
+
+pub(super) struct SlotSnapPlugin;
+
+impl Plugin for SlotSnapPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SlotSnap>()
+            .add_observer(init_placing)
+            .add_systems(
+                Update,
+                snap.never_param_warn()
+                    .after(super::apply_position)
+                    .run_if(in_state(CityMode::Objects).or(in_state(BuildingMode::Objects))),
+            );
+    }
+}
+
+/// Maximum horizontal distance from the cursor to a slot for snapping.
+const SNAP_DELTA: f32 = 0.3;
+
+fn init_placing(
+    trigger: Trigger<OnAdd, SlotSnap>,
+    mut placing_objects: Query<(&mut PlacingObjectState, &SlotSnap)>,
+) {
+    let Ok((mut state, snap)) = placing_objects.get_mut(trigger.entity()) else {
+        return;
+    };
+    if snap.required {
+        debug!("disabling placing until slotted");
+        state.set_violated(PlacementViolation::RequiresSlot, true);
+    }
+}
+
+/// Snaps the placing object to the nearest free slot.
+///
+/// Collisions with the slot owner are ignored.
+pub(super) fn snap(
+    placing_object: Single<(
+        &mut Transform,
+        &mut PlacingObjectState,
+        &mut PlacingSlot,
+        &PlacingObject,
+        &CollidingEntities,
+        &SlotSnap,
+        Has<FreePlacement>,
+    )>,
+    owners: Query<(Entity, &Transform, &ObjectSlots, &Visibility), Without<PlacingObject>>,
+    slotted: Query<(Entity, &Parent, &Slotted)>,
+    parents: Query<&Parent>,
+    objects: Query<(), With<Object>>,
+) {
+    let (
+        mut transform,
+        mut state,
+        mut placing_slot,
+        &placing_object,
+        colliding_entities,
+        snap,
+        free_placement,
+    ) = placing_object.into_inner();
+    let moving_entity = match placing_object {
+        PlacingObject::Spawning(_) => None,
+        PlacingObject::Moving(entity) => Some(entity),
+    };
+
+    // Optional slots are ignored during free placement.
+    let owners = owners.iter().filter(|_| !free_placement || snap.required);
+    let mut nearest: Option<(ObjectSlot, Vec3, f32)> = None;
+    for (owner_entity, owner_transform, slots, &visibility) in owners {
+        if visibility == Visibility::Hidden || Some(owner_entity) == moving_entity {
+            continue;
+        }
+
+        for (index, &point) in slots.iter().enumerate() {
+            let occupied = slotted.iter().any(|(entity, parent, slotted)| {
+                **parent == owner_entity && **slotted == index && Some(entity) != moving_entity
+            });
+            if occupied {
+                continue;
+            }
+
+            let position = owner_transform.transform_point(point);
+            let distance = position.xz().distance(transform.translation.xz());
+            if distance <= SNAP_DELTA && nearest.is_none_or(|(.., min)| distance < min) {
+                let slot = ObjectSlot {
+                    entity: owner_entity,
+                    index,
+                };
+                nearest = Some((slot, position, distance));
+            }
+        }
+    }
+
+    let slot = nearest.map(|(slot, position, _)| {
+        trace!("snapping to `{slot:?}`");
+        transform.translation = position;
+        slot
+    });
+    if placing_slot.0 != slot {
+        debug!("changing slot to `{slot:?}`");
+        placing_slot.0 = slot;
+    }
+
+    let colliding = colliding_entities.iter().any(|&entity| {
+        let Some(slot) = slot else {
+            return true;
+        };
+
+        // Find the object to which the collider belongs.
+        let object_entity = iter::once(entity)
+            .chain(parents.iter_ancestors(entity))
+            .find(|&entity| objects.get(entity).is_ok());
+        object_entity != Some(slot.entity)
+    });
+
+    let mut changed = state
+        .bypass_change_detection()
+        .set_violated(PlacementViolation::Collision, colliding);
+    if snap.required {
+        changed |= state
+            .bypass_change_detection()
+            .set_violated(PlacementViolation::RequiresSlot, slot.is_none());
+    }
+    if changed {
+        state.set_changed();
+    }
+}
+
+/// Enables placing objects into [`ObjectSlots`] of other objects.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+#[require(PlacingSlot)]
+pub(crate) struct SlotSnap {
+    /// Requires an object to be placed into a slot.
+    required: bool,
+}
+
+/// Slot to which the placing object is currently snapped.
+#[derive(Component, Default, Deref)]
+pub(super) struct PlacingSlot(Option<ObjectSlot>);
@@ -2,7 +2,7 @@ use std::f32::consts::PI;
 
 use bevy::prelude::*;
 
-use super::{ObjectRotationLimit, PlacingObjectState};
+use super::{FreePlacement, ObjectRotationLimit, PlacementViolation, PlacingObjectState};
 use crate::game_world::{
     city::CityMode,
     family::building::{
@@ -34,29 +34,33 @@ fn init_placing(
     let (mut placing_object, snap) = placing_objects.get_mut(trigger.entity()).unwrap();
     if snap.required() {
         debug!("disabling placing until snapped");
-        placing_object.allowed_place = false;
+        placing_object.set_violated(PlacementViolation::RequiresWall, true);
     }
 }
 
-fn snap(
+pub(super) fn snap(
     placing_object: Single<
         (
             &mut Transform,
             &mut PlacingObjectState,
             &mut ObjectRotationLimit,
             &WallSnap,
+            Has<FreePlacement>,
         ),
         Without<Wall>,
     >,
     walls: Query<(&Segment, &Transform), With<Wall>>,
 ) {
     const SNAP_DELTA: f32 = 1.0;
-    let (mut object_transform, mut state, mut rotation_limit, snap) = placing_object.into_inner();
+    let (mut object_transform, mut state, mut rotation_limit, snap, free_placement) =
+        placing_object.into_inner();
     let object_point = object_transform.translation.xz();
-    if let Some((wall, wall_transform, wall_point)) = walls
+    let nearest_wall = walls
         .iter()
         .map(|(wall, transform)| (wall, transform, wall.closest_point(object_point)))
-        .find(|(.., point)| point.distance(object_point) <= SNAP_DELTA)
+        .find(|(.., point)| point.distance(object_point) <= SNAP_DELTA);
+    if let Some((wall, wall_transform, wall_point)) =
+        nearest_wall.filter(|_| !free_placement || snap.required())
     {
         trace!("snapping to wall");
         const GAP: f32 = 0.03; // A small gap between the object and wall to avoid collision.
@@ -76,14 +80,14 @@ fn snap(
             **rotation_limit = Some(PI);
             if snap.required() {
                 debug!("allowing placing");
-                state.allowed_place = true;
+                state.set_violated(PlacementViolation::RequiresWall, false);
             }
         }
     } else if rotation_limit.is_some() {
         **rotation_limit = None;
         if snap.required() {
             debug!("disallowing placing");
-            state.allowed_place = false;
+            state.set_violated(PlacementViolation::RequiresWall, true);
         }
     }
 }
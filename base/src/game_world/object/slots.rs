@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Registers slots that let surfaces hold smaller objects.
+pub(super) struct SlotsPlugin;
+
+impl Plugin for SlotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ObjectSlots>()
+            .register_type::<Slotted>()
+            .replicate::<Slotted>();
+    }
+}
+
+/// Points in object space where smaller objects can be placed, like spots on a table.
+#[derive(Component, Reflect, Clone, Default, Deref)]
+#[reflect(Component)]
+pub(crate) struct ObjectSlots(Vec<Vec3>);
+
+/// Index of the slot occupied by the object.
+///
+/// Slotted objects are children of the object that owns the slot,
+/// so they follow it when it moves.
+#[derive(Component, Reflect, Clone, Copy, Debug, Deref, Deserialize, Serialize, PartialEq)]
+#[reflect(Component)]
+pub(crate) struct Slotted(pub(crate) usize);
+
+/// Slot of a specific object.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub(crate) struct ObjectSlot {
+    /// Object that owns the slot.
+    pub(crate) entity: Entity,
+    pub(crate) index: usize,
+}
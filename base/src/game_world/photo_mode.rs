@@ -0,0 +1,383 @@
+use std::{
+    f32::consts::FRAC_PI_2,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{
+    core_pipeline::bloom::Bloom,
+    pbr::ScreenSpaceAmbientOcclusion,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        renderer::RenderDevice,
+        view::screenshot::{save_to_disk, Screenshot, ScreenshotCaptured},
+    },
+    window::PrimaryWindow,
+};
+use bevy_enhanced_input::prelude::*;
+use bevy_replicon::prelude::*;
+
+use super::{
+    player_camera::PlayerCamera,
+    time_speed::{TimeSpeed, TimeSpeedRequest},
+};
+use crate::{game_paths::GamePaths, settings::Settings};
+
+pub(super) struct PhotoModePlugin;
+
+impl Plugin for PhotoModePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_input_context::<PhotoMode>()
+            .add_observer(enter)
+            .add_observer(exit)
+            .add_observer(fly)
+            .add_observer(look)
+            .add_observer(change_fov)
+            .add_observer(roll)
+            .add_observer(capture);
+    }
+}
+
+/// Pauses the game only in single player to avoid freezing the world for other players.
+fn enter(
+    _trigger: Trigger<Started<EnterPhotoMode>>,
+    mut commands: Commands,
+    time_speed: Res<TimeSpeed>,
+    server: Res<RepliconServer>,
+    client: Res<RepliconClient>,
+    camera_entity: Single<Entity, (With<PlayerCamera>, Without<PhotoMode>)>,
+) {
+    info!("entering photo mode");
+    let mut resume_speed = None;
+    if !server.is_running() && client.is_disconnected() && *time_speed != TimeSpeed::Paused {
+        debug!("pausing game for photo mode");
+        resume_speed = Some(*time_speed);
+        commands.client_trigger(TimeSpeedRequest(TimeSpeed::Paused));
+    }
+    commands
+        .entity(*camera_entity)
+        .insert(PhotoMode { resume_speed });
+}
+
+fn exit(
+    _trigger: Trigger<Started<ExitPhotoMode>>,
+    mut commands: Commands,
+    camera: Single<(Entity, &PhotoMode, &mut Projection)>,
+) {
+    info!("exiting photo mode");
+    let (camera_entity, photo_mode, mut projection) = camera.into_inner();
+    if let Projection::Perspective(perspective) = &mut *projection {
+        perspective.fov = PerspectiveProjection::default().fov;
+    }
+    if let Some(time_speed) = photo_mode.resume_speed {
+        debug!("resuming game with `{time_speed:?}`");
+        commands.client_trigger(TimeSpeedRequest(time_speed));
+    }
+    commands.entity(camera_entity).remove::<PhotoMode>();
+}
+
+fn fly(
+    trigger: Trigger<Fired<PhotoMove>>,
+    time: Res<Time<Real>>,
+    mut transform: Single<&mut Transform, With<PhotoMode>>,
+) {
+    // Movement consists of X and -Z components, so swap Y and Z with negation.
+    let mut movement = trigger.value.extend(0.0).xzy();
+    movement.z = -movement.z;
+
+    let rotation = transform.rotation;
+    transform.translation += rotation * movement * FLY_SPEED * time.delta_secs();
+}
+
+fn look(
+    trigger: Trigger<Fired<PhotoLook>>,
+    mut transform: Single<&mut Transform, With<PhotoMode>>,
+) {
+    let (yaw, pitch, roll) = transform.rotation.to_euler(EulerRot::YXZ);
+    let yaw = yaw + trigger.value.x;
+    let pitch = (pitch + trigger.value.y).clamp(-FRAC_PI_2 + 0.01, FRAC_PI_2 - 0.01);
+    transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
+}
+
+fn change_fov(
+    trigger: Trigger<Fired<PhotoFov>>,
+    mut projection: Single<&mut Projection, With<PhotoMode>>,
+) {
+    if let Projection::Perspective(perspective) = &mut **projection {
+        perspective.fov = (perspective.fov - trigger.value * 0.05)
+            .clamp(MIN_FOV.to_radians(), MAX_FOV.to_radians());
+    }
+}
+
+fn roll(
+    trigger: Trigger<Fired<PhotoRoll>>,
+    time: Res<Time<Real>>,
+    mut transform: Single<&mut Transform, With<PhotoMode>>,
+) {
+    transform.rotate_local_z(trigger.value * ROLL_SPEED * time.delta_secs());
+}
+
+fn capture(
+    _trigger: Trigger<Started<CapturePhoto>>,
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    game_paths: Res<GamePaths>,
+    settings: Res<Settings>,
+    render_device: Res<RenderDevice>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<
+        (
+            &GlobalTransform,
+            &Projection,
+            &EnvironmentMapLight,
+            Option<&Bloom>,
+            Option<&ScreenSpaceAmbientOcclusion>,
+        ),
+        With<PhotoMode>,
+    >,
+) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = game_paths
+        .screenshots
+        .join(format!("photo_{timestamp}.png"));
+
+    let size = window.physical_size();
+    let max_factor = render_device.limits().max_texture_dimension_2d / size.max_element().max(1);
+    let factor = settings.video.photo_supersampling.factor().min(max_factor);
+    if factor <= 1 {
+        info!("capturing photo to {path:?}");
+        commands
+            .spawn(Screenshot::primary_window())
+            .observe(save_to_disk(path));
+        return;
+    }
+
+    info!("capturing photo to {path:?} with {factor}x supersampling");
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size.x * factor,
+            height: size.y * factor,
+            ..Default::default()
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    let image_handle = images.add(image);
+
+    // Renders the view once more into the scaled image instead of resizing the window.
+    let (&transform, projection, env_light, bloom, ssao) = *camera;
+    let mut entity = commands.spawn((
+        PhotoCamera,
+        transform.compute_transform(),
+        projection.clone(),
+        env_light.clone(),
+        Camera {
+            hdr: true,
+            target: RenderTarget::Image(image_handle.clone()),
+            ..Default::default()
+        },
+    ));
+    if let Some(bloom) = bloom {
+        entity.insert(bloom.clone());
+    }
+    if let Some(ssao) = ssao {
+        entity.insert(ssao.clone());
+    }
+    let camera_entity = entity.id();
+
+    commands.spawn(Screenshot::image(image_handle)).observe(
+        move |trigger: Trigger<ScreenshotCaptured>, mut commands: Commands| {
+            commands.entity(camera_entity).despawn();
+            let image = downsample(&trigger.0, factor);
+            match image.try_into_dynamic() {
+                Ok(dyn_image) => match dyn_image.to_rgb8().save(&path) {
+                    Ok(()) => info!("photo saved to {path:?}"),
+                    Err(e) => error!("unable to save photo to {path:?}: {e}"),
+                },
+                Err(e) => error!("unable to convert photo: {e}"),
+            }
+        },
+    );
+}
+
+/// Reduces the size of an 8-bit sRGB image by averaging each `factor`x`factor` block.
+///
+/// Color channels are averaged in linear space to keep the brightness of edges.
+fn downsample(image: &Image, factor: u32) -> Image {
+    const CHANNELS: usize = 4;
+
+    let to_linear: Vec<f32> = (0..=u8::MAX)
+        .map(|value| Srgba::gamma_function(value as f32 / u8::MAX as f32))
+        .collect();
+
+    let src_width = image.width() as usize;
+    let width = image.width() / factor;
+    let height = image.height() / factor;
+    let factor = factor as usize;
+    let block_area = (factor * factor) as f32;
+    let mut data = Vec::with_capacity(width as usize * height as usize * CHANNELS);
+    for y in 0..height as usize {
+        for x in 0..width as usize {
+            let mut sum = [0.0; CHANNELS];
+            for block_y in 0..factor {
+                let row = (y * factor + block_y) * src_width;
+                for block_x in 0..factor {
+                    let index = (row + x * factor + block_x) * CHANNELS;
+                    let pixel = &image.data[index..index + CHANNELS];
+                    for (channel, (sum, &value)) in sum.iter_mut().zip(pixel).enumerate() {
+                        *sum += if channel < 3 {
+                            to_linear[value as usize]
+                        } else {
+                            value as f32 / u8::MAX as f32
+                        };
+                    }
+                }
+            }
+            for (channel, sum) in sum.into_iter().enumerate() {
+                let mut value = sum / block_area;
+                if channel < 3 {
+                    value = Srgba::gamma_function_inverse(value);
+                }
+                data.push((value * u8::MAX as f32).round() as u8);
+            }
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            ..Default::default()
+        },
+        TextureDimension::D2,
+        data,
+        image.texture_descriptor.format,
+        RenderAssetUsages::default(),
+    )
+}
+
+const FLY_SPEED: f32 = 5.0;
+const ROLL_SPEED: f32 = 1.0;
+const MIN_FOV: f32 = 10.0;
+const MAX_FOV: f32 = 120.0;
+
+/// Free camera mode for taking screenshots.
+///
+/// Inserted on the [`PlayerCamera`]. Pauses single player game and hides the UI while present.
+#[derive(Component)]
+pub struct PhotoMode {
+    /// Speed to restore on exit if the game was paused by the mode.
+    resume_speed: Option<TimeSpeed>,
+}
+
+impl InputContext for PhotoMode {
+    const PRIORITY: isize = 1;
+
+    fn context_instance(world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        let settings = world.resource::<Settings>();
+
+        ctx.bind::<ExitPhotoMode>().to((
+            &settings.keyboard.photo_mode,
+            KeyCode::Escape,
+            &settings.gamepad.cancel,
+        ));
+        ctx.bind::<CapturePhoto>()
+            .to((KeyCode::Space, &settings.gamepad.confirm));
+        ctx.bind::<EnablePhotoLook>().to(MouseButton::Right);
+        ctx.bind::<PhotoMove>()
+            .to((
+                Cardinal {
+                    north: &settings.keyboard.camera_forward,
+                    east: &settings.keyboard.camera_left,
+                    south: &settings.keyboard.camera_backward,
+                    west: &settings.keyboard.camera_right,
+                },
+                GamepadStick::Left,
+            ))
+            .with_modifiers(DeadZone::default());
+        ctx.bind::<PhotoLook>()
+            .to((
+                Input::mouse_motion()
+                    .with_modifiers((Negate::all(), Scale::splat(0.003)))
+                    .with_conditions(Chord::<EnablePhotoLook>::default()),
+                GamepadStick::Right.with_modifiers_each(Scale::splat(0.03)),
+            ))
+            .with_modifiers(DeadZone::default());
+        ctx.bind::<PhotoFov>().to((
+            Bidirectional {
+                positive: &settings.keyboard.zoom_in,
+                negative: &settings.keyboard.zoom_out,
+            },
+            Bidirectional {
+                positive: GamepadAxis::RightZ,
+                negative: GamepadAxis::LeftZ,
+            }
+            .with_modifiers_each(Scale::splat(0.1)),
+            Input::mouse_wheel().with_modifiers(SwizzleAxis::YXZ),
+        ));
+        ctx.bind::<PhotoRoll>().to((
+            Bidirectional {
+                positive: &settings.keyboard.rotate_left,
+                negative: &settings.keyboard.rotate_right,
+            },
+            Bidirectional {
+                positive: GamepadButton::LeftTrigger,
+                negative: GamepadButton::RightTrigger,
+            },
+        ));
+
+        ctx
+    }
+}
+
+/// Temporary camera that renders a supersampled photo from the [`PhotoMode`] view.
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Photo camera")),
+    Camera3d,
+    Msaa(|| Msaa::Off)
+)]
+struct PhotoCamera;
+
+/// Bound in [`PlayerCamera`] context since [`PhotoMode`] context exists only in photo mode.
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(super) struct EnterPhotoMode;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct ExitPhotoMode;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct CapturePhoto;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct EnablePhotoLook;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = Vec2)]
+struct PhotoMove;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = Vec2)]
+struct PhotoLook;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = f32)]
+struct PhotoFov;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = f32)]
+struct PhotoRoll;
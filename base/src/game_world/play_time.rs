@@ -0,0 +1,75 @@
+use std::{mem, time::Duration};
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::players::Players;
+use crate::core::GameState;
+
+/// Counts real time spent in the world and in each family.
+///
+/// The world time is stored only in the save header, family time
+/// is serialized together with the family.
+pub(super) struct PlayTimePlugin;
+
+impl Plugin for PlayTimePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PlayTime>()
+            .replicate::<PlayTime>()
+            .add_systems(OnEnter(GameState::InGame), init)
+            .add_systems(
+                Update,
+                tick.run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup);
+    }
+}
+
+const FAMILY_STEP: Duration = Duration::from_secs(1);
+
+/// Starts counting from zero unless the value was restored from the save header.
+fn init(mut commands: Commands) {
+    commands.init_resource::<WorldPlayTime>();
+}
+
+/// Advances the world time and the time of families claimed by players.
+///
+/// Families are updated once per [`FAMILY_STEP`] to avoid replicating them every frame.
+fn tick(
+    mut elapsed: Local<Duration>,
+    time: Res<Time<Real>>,
+    players: Res<Players>,
+    mut world_play_time: ResMut<WorldPlayTime>,
+    mut families: Query<&mut PlayTime>,
+) {
+    world_play_time.0 += time.delta();
+
+    *elapsed += time.delta();
+    if *elapsed < FAMILY_STEP {
+        return;
+    }
+    let step = mem::take(&mut *elapsed);
+
+    let claimed = players
+        .iter()
+        .filter_map(|(_, player)| player.family_entity);
+    let mut iter = families.iter_many_mut(claimed);
+    while let Some(mut play_time) = iter.fetch_next() {
+        play_time.0 += step;
+    }
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<WorldPlayTime>();
+}
+
+/// Total time the world was played, including previous sessions.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct WorldPlayTime(pub Duration);
+
+/// Total time the family was played by any player.
+#[derive(Component, Clone, Copy, Default, Deref, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct PlayTime(Duration);
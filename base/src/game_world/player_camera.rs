@@ -1,8 +1,9 @@
 use std::f32::consts::{FRAC_PI_2, PI};
 
+use avian3d::prelude::*;
 use bevy::{
     asset::AssetPath, core_pipeline::experimental::taa::TemporalAntiAliasing,
-    ecs::system::SystemParam, pbr::ScreenSpaceAmbientOcclusion, prelude::*,
+    ecs::system::SystemParam, prelude::*,
 };
 use bevy_enhanced_input::prelude::*;
 use num_enum::IntoPrimitive;
@@ -11,7 +12,12 @@ use strum::EnumIter;
 use crate::{
     asset::collection::{AssetCollection, Collection},
     common_conditions::in_any_state,
-    game_world::WorldState,
+    game_world::{
+        actor::SelectedActor,
+        photo_mode::{EnterPhotoMode, PhotoMode},
+        time_speed::{PauseTime, SetFastSpeed, SetFasterSpeed, SetNormalSpeed, SetUltraSpeed},
+        Layer, WorldState,
+    },
     settings::Settings,
 };
 
@@ -25,24 +31,38 @@ impl Plugin for PlayerCameraPlugin {
             .add_observer(pan)
             .add_observer(zoom)
             .add_observer(rotate)
+            .add_observer(toggle_follow)
+            .add_observer(focus)
             .add_systems(
                 Update,
-                apply_transform.run_if(in_any_state([
-                    WorldState::FamilyEditor,
-                    WorldState::City,
-                    WorldState::Family,
-                ])),
+                (
+                    edge_scroll,
+                    follow.never_param_warn(),
+                    apply_transform,
+                    avoid_walls.never_param_warn(),
+                )
+                    .chain()
+                    .run_if(in_any_state([
+                        WorldState::FamilyEditor,
+                        WorldState::City,
+                        WorldState::Family,
+                    ])),
             );
     }
 }
 
+/// Also starts at the selected actor, if any, since cameras are respawned on city switch.
 fn init(
     trigger: Trigger<OnAdd, PlayerCamera>,
-    mut cameras: Query<&mut EnvironmentMapLight>,
+    mut cameras: Query<(&mut EnvironmentMapLight, &mut OrbitOrigin)>,
     environment_map: Res<Collection<EnvironmentMap>>,
+    actors: Query<&Transform, With<SelectedActor>>,
 ) {
     debug!("initializing player camera");
-    let mut env_light = cameras.get_mut(trigger.entity()).unwrap();
+    let (mut env_light, mut orbit_origin) = cameras.get_mut(trigger.entity()).unwrap();
+    if let Ok(transform) = actors.get_single() {
+        **orbit_origin = transform.translation;
+    }
     env_light.diffuse_map = environment_map.handle(EnvironmentMap::Diffuse);
     env_light.specular_map = environment_map.handle(EnvironmentMap::Specular);
     env_light.intensity = 800.0;
@@ -50,32 +70,110 @@ fn init(
 
 fn pan(
     trigger: Trigger<Fired<PanCamera>>,
+    mut commands: Commands,
     world_state: Res<State<WorldState>>,
-    camera: Single<(&mut OrbitOrigin, &Transform, &SpringArm)>,
+    camera: Single<(
+        Entity,
+        &mut OrbitOrigin,
+        &Transform,
+        &SpringArm,
+        Has<CameraFollow>,
+    )>,
 ) {
     if *world_state == WorldState::FamilyEditor {
         return;
     }
 
-    // Calculate direction without camera's tilt.
+    let (camera_entity, mut orbit_origin, transform, spring_arm, following) = camera.into_inner();
+    if following {
+        info!("stopping following actor due to panning");
+        commands.entity(camera_entity).remove::<CameraFollow>();
+    }
+
+    **orbit_origin += pan_offset(transform, trigger.value, **spring_arm);
+}
+
+fn edge_scroll(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    world_state: Res<State<WorldState>>,
+    window: Single<&Window>,
+    camera: Single<(&mut OrbitOrigin, &Transform, &SpringArm)>,
+) {
+    if !settings.camera.edge_scrolling || *world_state == WorldState::FamilyEditor {
+        return;
+    }
+    if !window.focused {
+        return;
+    }
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
+    };
+
+    let margin = settings.camera.edge_margin;
+    let mut direction = Vec2::ZERO;
+    if cursor_pos.x < margin {
+        direction.x -= 1.0;
+    } else if cursor_pos.x > window.width() - margin {
+        direction.x += 1.0;
+    }
+    // Cursor Y grows downwards.
+    if cursor_pos.y < margin {
+        direction.y += 1.0;
+    } else if cursor_pos.y > window.height() - margin {
+        direction.y -= 1.0;
+    }
+
+    if direction == Vec2::ZERO {
+        return;
+    }
+
     let (mut orbit_origin, transform, spring_arm) = camera.into_inner();
+    let movement = direction.normalize() * settings.camera.edge_speed * EDGE_SCROLL_SPEED;
+    **orbit_origin += pan_offset(transform, movement * time.delta_secs(), **spring_arm);
+}
+
+/// Converts 2D movement into camera origin offset.
+fn pan_offset(transform: &Transform, movement: Vec2, spring_arm: f32) -> Vec3 {
+    // Calculate direction without camera's tilt.
     let forward = transform.forward();
     let camera_dir = Vec3::new(forward.x, 0.0, forward.z).normalize();
     let rotation = Quat::from_rotation_arc(Vec3::NEG_Z, camera_dir);
 
     // Movement consists of X and -Z components, so swap Y and Z with negation.
-    let mut movement = trigger.value.extend(0.0).xzy();
+    let mut movement = movement.extend(0.0).xzy();
     movement.z = -movement.z;
 
     // Make speed dependent on camera distance.
-    let arm_multiplier = **spring_arm * 0.02;
+    let arm_multiplier = spring_arm * 0.02;
 
-    **orbit_origin += rotation * movement * arm_multiplier;
+    rotation * movement * arm_multiplier
 }
 
-fn zoom(trigger: Trigger<Fired<ZoomCamera>>, mut spring_arm: Single<&mut SpringArm>) {
+/// Zooms towards the cursor's ground intersection.
+///
+/// Scales the camera arm and origin around the point under the cursor,
+/// so this point stays at the same screen position.
+fn zoom(
+    trigger: Trigger<Fired<ZoomCamera>>,
+    world_state: Res<State<WorldState>>,
+    camera_caster: CameraCaster,
+    camera: Single<(&mut OrbitOrigin, &mut SpringArm)>,
+) {
+    let (mut orbit_origin, mut spring_arm) = camera.into_inner();
+
     // Limit to prevent clipping into the ground.
-    ***spring_arm = (***spring_arm - trigger.value).max(0.2);
+    let new_arm = (**spring_arm - trigger.value).max(0.2);
+    let factor = new_arm / **spring_arm;
+    **spring_arm = new_arm;
+
+    if *world_state == WorldState::FamilyEditor {
+        return;
+    }
+
+    if let Some(point) = camera_caster.intersect_ground() {
+        **orbit_origin = point + (**orbit_origin - point) * factor;
+    }
 }
 
 fn rotate(
@@ -94,12 +192,90 @@ fn rotate(
     rotation.y = rotation.y.clamp(min_y, max_y);
 }
 
-fn apply_transform(camera: Single<(&mut Transform, &OrbitOrigin, &OrbitRotation, &SpringArm)>) {
+fn toggle_follow(
+    _trigger: Trigger<Started<ToggleFollow>>,
+    mut commands: Commands,
+    world_state: Res<State<WorldState>>,
+    camera: Single<(Entity, Has<CameraFollow>), With<PlayerCamera>>,
+) {
+    if *world_state != WorldState::Family {
+        return;
+    }
+
+    let (camera_entity, following) = *camera;
+    if following {
+        info!("stopping following actor");
+        commands.entity(camera_entity).remove::<CameraFollow>();
+    } else {
+        info!("starting following actor");
+        commands.entity(camera_entity).insert(CameraFollow);
+    }
+}
+
+fn focus(trigger: Trigger<CameraFocus>, mut orbit_origin: Single<&mut OrbitOrigin>) {
+    debug!("focusing camera on `{}`", trigger.0);
+    ***orbit_origin = trigger.0;
+}
+
+fn follow(
+    time: Res<Time>,
+    mut orbit_origin: Single<&mut OrbitOrigin, With<CameraFollow>>,
+    settings: Res<Settings>,
+    actor_transform: Single<&Transform, With<SelectedActor>>,
+) {
+    let target = actor_transform.translation;
+    if settings.accessibility.reduced_motion {
+        ***orbit_origin = target;
+    } else {
+        orbit_origin.smooth_nudge(&target, FOLLOW_DECAY_RATE, time.delta_secs());
+    }
+}
+
+fn apply_transform(
+    camera: Single<(&mut Transform, &OrbitOrigin, &OrbitRotation, &SpringArm), Without<PhotoMode>>,
+) {
     let (mut transform, orbit_origin, orbit_rotation, spring_arm) = camera.into_inner();
     transform.translation = orbit_rotation.sphere_pos() * **spring_arm + **orbit_origin;
     transform.look_at(**orbit_origin, Vec3::Y);
 }
 
+/// Brings the camera closer to the followed actor if there is a wall in between.
+fn avoid_walls(
+    spatial_query: SpatialQuery,
+    cities: Query<&GlobalTransform>,
+    camera: Single<
+        (&mut Transform, &Parent, &OrbitOrigin),
+        (With<CameraFollow>, Without<PhotoMode>),
+    >,
+) {
+    let (mut transform, parent, orbit_origin) = camera.into_inner();
+    let city_transform = cities.get(**parent).unwrap();
+    let origin = **orbit_origin + Vec3::Y * FOLLOW_HEIGHT;
+    let Ok((direction, distance)) = Dir3::new_and_length(transform.translation - origin) else {
+        return;
+    };
+
+    let (_, city_rotation, _) = city_transform.to_scale_rotation_translation();
+    if let Some(hit) = spatial_query.cast_ray(
+        city_transform.transform_point(origin),
+        city_rotation * direction,
+        distance,
+        true,
+        &SpatialQueryFilter::from_mask(Layer::Wall),
+    ) {
+        transform.translation = origin + direction * hit.distance.max(0.2);
+    }
+}
+
+/// Base edge scrolling speed, multiplied by the speed from settings.
+const EDGE_SCROLL_SPEED: f32 = 50.0;
+
+/// How fast the camera catches up with the followed actor.
+const FOLLOW_DECAY_RATE: f32 = 5.0;
+
+/// Height above the followed actor's origin from which walls are checked.
+const FOLLOW_HEIGHT: f32 = 1.0;
+
 #[derive(Component)]
 #[require(
     OrbitOrigin,
@@ -111,9 +287,9 @@ fn apply_transform(camera: Single<(&mut Transform, &OrbitOrigin, &OrbitRotation,
     Camera(|| Camera { hdr: true, ..Default::default() }),
     TemporalAntiAliasing,
     EnvironmentMapLight,
-    ScreenSpaceAmbientOcclusion
+    SpatialListener
 )]
-pub(super) struct PlayerCamera;
+pub struct PlayerCamera;
 
 impl InputContext for PlayerCamera {
     fn context_instance(world: &World, _entity: Entity) -> ContextInstance {
@@ -121,8 +297,22 @@ impl InputContext for PlayerCamera {
         let settings = world.resource::<Settings>();
 
         ctx.bind::<EnableCameraRotation>().to(MouseButton::Middle);
+        ctx.bind::<ToggleFollow>().to((
+            &settings.keyboard.follow_actor,
+            &settings.gamepad.follow_actor,
+        ));
+        ctx.bind::<EnterPhotoMode>()
+            .to((&settings.keyboard.photo_mode, &settings.gamepad.photo_mode));
         ctx.bind::<EnablePanCamera>()
-            .to((MouseButton::Right, GamepadButton::East));
+            .to((MouseButton::Right, &settings.gamepad.pan_camera));
+        ctx.bind::<PauseTime>().to(&settings.keyboard.pause);
+        ctx.bind::<SetNormalSpeed>()
+            .to(&settings.keyboard.normal_speed);
+        ctx.bind::<SetFastSpeed>().to(&settings.keyboard.fast_speed);
+        ctx.bind::<SetFasterSpeed>()
+            .to(&settings.keyboard.faster_speed);
+        ctx.bind::<SetUltraSpeed>()
+            .to(&settings.keyboard.ultra_speed);
 
         ctx.bind::<PanCamera>()
             .to((
@@ -145,11 +335,7 @@ impl InputContext for PlayerCamera {
                     ))
                     .with_conditions(Chord::<EnablePanCamera>::default()),
             ))
-            .with_modifiers((
-                DeadZone::default(),
-                Scale::splat(0.7),
-                SmoothNudge::default(),
-            ));
+            .with_modifiers((DeadZone::default(), Scale::splat(0.7)));
 
         ctx.bind::<RotateCamera>()
             .to((
@@ -163,22 +349,29 @@ impl InputContext for PlayerCamera {
                     .with_modifiers(Scale::splat(0.08))
                     .with_conditions(Chord::<EnableCameraRotation>::default()),
             ))
-            .with_modifiers((Scale::splat(0.05), SmoothNudge::default()));
-
-        ctx.bind::<ZoomCamera>()
-            .to((
-                Bidirectional {
-                    positive: &settings.keyboard.zoom_in,
-                    negative: &settings.keyboard.zoom_out,
-                },
-                Bidirectional {
-                    positive: GamepadAxis::RightZ,
-                    negative: GamepadAxis::LeftZ,
-                }
-                .with_modifiers_each(Scale::splat(0.1)),
-                Input::mouse_wheel().with_modifiers(SwizzleAxis::YXZ),
-            ))
-            .with_modifiers(SmoothNudge::default());
+            .with_modifiers(Scale::splat(0.05));
+
+        ctx.bind::<ZoomCamera>().to((
+            Bidirectional {
+                positive: &settings.keyboard.zoom_in,
+                negative: &settings.keyboard.zoom_out,
+            },
+            Bidirectional {
+                positive: GamepadAxis::RightZ,
+                negative: GamepadAxis::LeftZ,
+            }
+            .with_modifiers_each(Scale::splat(0.1)),
+            Input::mouse_wheel().with_modifiers(SwizzleAxis::YXZ),
+        ));
+
+        if !settings.accessibility.reduced_motion {
+            ctx.bind::<PanCamera>()
+                .with_modifiers(SmoothNudge::default());
+            ctx.bind::<RotateCamera>()
+                .with_modifiers(SmoothNudge::default());
+            ctx.bind::<ZoomCamera>()
+                .with_modifiers(SmoothNudge::default());
+        }
 
         ctx
     }
@@ -204,6 +397,10 @@ struct EnableCameraRotation;
 #[input_action(output = bool)]
 struct EnablePanCamera;
 
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct ToggleFollow;
+
 #[derive(Clone, Copy, Debug, EnumIter, IntoPrimitive)]
 #[repr(usize)]
 enum EnvironmentMap {
@@ -224,6 +421,14 @@ impl AssetCollection for EnvironmentMap {
     }
 }
 
+/// Moves the camera origin to the specified point in city coordinates.
+#[derive(Event)]
+pub(super) struct CameraFocus(pub(super) Vec3);
+
+/// Makes the camera follow [`SelectedActor`].
+#[derive(Component)]
+struct CameraFollow;
+
 /// The origin of a camera.
 #[derive(Component, Default, Deref, DerefMut)]
 struct OrbitOrigin(Vec3);
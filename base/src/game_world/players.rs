@@ -0,0 +1,517 @@
+use std::mem;
+
+use bevy::{ecs::entity::MapEntities, prelude::*, utils::HashMap};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+use super::{
+    family::{Family, SelectedFamily},
+    WorldState,
+};
+use crate::{core::GameState, notification::Notification};
+
+/// Tracks connected clients, their families and permissions on server.
+pub(super) struct PlayersPlugin;
+
+impl Plugin for PlayersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Players>()
+            .init_resource::<LocalPermission>()
+            .add_client_trigger::<FamilyClaim>(ChannelKind::Ordered)
+            .add_client_trigger::<FamilyRelease>(ChannelKind::Ordered)
+            .add_client_trigger::<SpectateRequest>(ChannelKind::Ordered)
+            .add_mapped_server_trigger::<ClaimedFamilies>(ChannelKind::Ordered)
+            .add_server_trigger::<PermissionChanged>(ChannelKind::Ordered)
+            .add_server_trigger::<ClaimRejected>(ChannelKind::Ordered)
+            .add_observer(send_claim)
+            .add_observer(send_release)
+            .add_observer(claim)
+            .add_observer(release)
+            .add_observer(spectate)
+            .add_observer(mark_claimed)
+            .add_observer(leave_family)
+            .add_observer(receive_permission)
+            .add_systems(
+                PreUpdate,
+                (
+                    update_players
+                        .after(ServerSet::Receive)
+                        .run_if(server_running),
+                    request_spectating
+                        .after(ClientSet::Receive)
+                        .run_if(client_just_connected.and(resource_exists::<JoinAsSpectator>)),
+                ),
+            )
+            .add_systems(
+                OnEnter(GameState::InGame),
+                add_host.run_if(server_or_singleplayer),
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup);
+    }
+}
+
+fn send_claim(trigger: Trigger<OnAdd, SelectedFamily>, mut commands: Commands) {
+    debug!("claiming family `{}`", trigger.entity());
+    commands.client_trigger_targets(FamilyClaim, trigger.entity());
+}
+
+fn send_release(trigger: Trigger<OnRemove, SelectedFamily>, mut commands: Commands) {
+    debug!("releasing family `{}`", trigger.entity());
+    commands.client_trigger_targets(FamilyRelease, trigger.entity());
+}
+
+fn claim(
+    trigger: Trigger<FromClient<FamilyClaim>>,
+    mut commands: Commands,
+    mut players: ResMut<Players>,
+    families: Query<(), With<Family>>,
+) {
+    let family_entity = trigger.entity();
+    if !players.can_play(trigger.client_id) {
+        error!(
+            "`{:?}` is not allowed to claim family `{family_entity}`",
+            trigger.client_id
+        );
+        reject_claim(&mut commands, trigger.client_id);
+        return;
+    }
+    if families.get(family_entity).is_err() {
+        error!(
+            "`{:?}` tried to claim invalid family `{family_entity}`",
+            trigger.client_id
+        );
+        reject_claim(&mut commands, trigger.client_id);
+        return;
+    }
+    if let Some(owner_id) = players.owner(family_entity) {
+        if owner_id != trigger.client_id {
+            error!(
+                "`{:?}` tried to claim family `{family_entity}` that already claimed by `{owner_id:?}`",
+                trigger.client_id
+            );
+            reject_claim(&mut commands, trigger.client_id);
+            return;
+        }
+    }
+    let Some(player) = players.0.get_mut(&trigger.client_id) else {
+        error!("received claim from unknown `{:?}`", trigger.client_id);
+        return;
+    };
+
+    info!("`{:?}` claims family `{family_entity}`", trigger.client_id);
+    player.family_entity = Some(family_entity);
+    if !player.owned_families.contains(&family_entity) {
+        player.owned_families.push(family_entity);
+    }
+    players.broadcast_claims(&mut commands);
+}
+
+fn reject_claim(commands: &mut Commands, client_id: ClientId) {
+    commands.server_trigger(ToClients {
+        mode: SendMode::Direct(client_id),
+        event: ClaimRejected,
+    });
+}
+
+/// Leaves the family if the server refused the claim.
+fn leave_family(
+    _trigger: Trigger<ClaimRejected>,
+    mut commands: Commands,
+    world_state: Option<Res<State<WorldState>>>,
+) {
+    if world_state.is_some_and(|state| *state == WorldState::Family) {
+        info!("leaving family after rejected claim");
+        commands.trigger(Notification::error("Unable to play for this family"));
+        commands.set_state(WorldState::World);
+    }
+}
+
+fn release(
+    trigger: Trigger<FromClient<FamilyRelease>>,
+    mut commands: Commands,
+    mut players: ResMut<Players>,
+) {
+    let Some(player) = players.0.get_mut(&trigger.client_id) else {
+        error!("received release from unknown `{:?}`", trigger.client_id);
+        return;
+    };
+    if player.family_entity != Some(trigger.entity()) {
+        debug!(
+            "ignoring release of family `{}` not claimed by `{:?}`",
+            trigger.entity(),
+            trigger.client_id
+        );
+        return;
+    }
+
+    info!(
+        "`{:?}` releases family `{}`",
+        trigger.client_id,
+        trigger.entity()
+    );
+    player.family_entity = None;
+    players.broadcast_claims(&mut commands);
+}
+
+fn request_spectating(mut commands: Commands) {
+    info!("requesting spectator mode");
+    commands.remove_resource::<JoinAsSpectator>();
+    commands.client_trigger(SpectateRequest);
+}
+
+/// Turns the player into a spectator.
+///
+/// Lowering own permission is always allowed.
+fn spectate(
+    trigger: Trigger<FromClient<SpectateRequest>>,
+    mut commands: Commands,
+    mut players: ResMut<Players>,
+) {
+    let Some(player) = players.0.get_mut(&trigger.client_id) else {
+        error!(
+            "received spectate request from unknown `{:?}`",
+            trigger.client_id
+        );
+        return;
+    };
+
+    info!("`{:?}` becomes a spectator", trigger.client_id);
+    player.family_entity = None;
+    let released = !mem::take(&mut player.owned_families).is_empty();
+    players.set_permission(&mut commands, trigger.client_id, Permission::Spectator);
+    if released {
+        players.broadcast_claims(&mut commands);
+    }
+}
+
+fn receive_permission(
+    trigger: Trigger<PermissionChanged>,
+    mut commands: Commands,
+    mut local_permission: ResMut<LocalPermission>,
+) {
+    if **local_permission == trigger.0 {
+        return;
+    }
+
+    info!("received `{}` permission", trigger.0);
+    commands.trigger(Notification::info(format!(
+        "Your permission changed to {}",
+        trigger.0
+    )));
+    **local_permission = trigger.0;
+}
+
+fn mark_claimed(
+    trigger: Trigger<ClaimedFamilies>,
+    mut commands: Commands,
+    families: Query<(Entity, Has<ClaimedFamily>, Has<OwnedFamily>), With<Family>>,
+) {
+    for (family_entity, marked, owned) in &families {
+        let claimed = trigger.others.contains(&family_entity);
+        if claimed && !marked {
+            debug!("marking family `{family_entity}` as claimed");
+            commands.entity(family_entity).insert(ClaimedFamily);
+        } else if !claimed && marked {
+            debug!("marking family `{family_entity}` as free");
+            commands.entity(family_entity).remove::<ClaimedFamily>();
+        }
+
+        let own = trigger.own.contains(&family_entity);
+        if own && !owned {
+            debug!("marking family `{family_entity}` as owned");
+            commands.entity(family_entity).insert(OwnedFamily);
+        } else if !own && owned {
+            commands.entity(family_entity).remove::<OwnedFamily>();
+        }
+    }
+}
+
+fn update_players(
+    mut commands: Commands,
+    mut server_events: EventReader<ServerEvent>,
+    mut players: ResMut<Players>,
+) {
+    for event in server_events.read() {
+        match event {
+            ServerEvent::ClientConnected { client_id } => {
+                info!("adding player for `{client_id:?}`");
+                commands.trigger(Notification::info("Player connected"));
+                players
+                    .0
+                    .insert(*client_id, Player::new(Permission::default()));
+                commands.server_trigger(ToClients {
+                    mode: SendMode::Direct(*client_id),
+                    event: players.claims_for(*client_id),
+                });
+                commands.server_trigger(ToClients {
+                    mode: SendMode::Direct(*client_id),
+                    event: PermissionChanged(Permission::default()),
+                });
+            }
+            ServerEvent::ClientDisconnected { client_id, reason } => {
+                info!("removing player for `{client_id:?}`: {reason}");
+                commands.trigger(Notification::warning(format!(
+                    "Player disconnected: {reason}"
+                )));
+                if players
+                    .0
+                    .remove(client_id)
+                    .is_some_and(|player| !player.owned_families.is_empty())
+                {
+                    players.broadcast_claims(&mut commands);
+                }
+            }
+        }
+    }
+}
+
+fn add_host(mut players: ResMut<Players>, mut local_permission: ResMut<LocalPermission>) {
+    debug!("adding host player");
+    players
+        .0
+        .insert(ClientId::SERVER, Player::new(Permission::Host));
+    **local_permission = Permission::Host;
+}
+
+fn cleanup(mut players: ResMut<Players>, mut local_permission: ResMut<LocalPermission>) {
+    players.0.clear();
+    *local_permission = Default::default();
+}
+
+/// Connected players, available only on server.
+#[derive(Resource, Default)]
+pub struct Players(HashMap<ClientId, Player>);
+
+impl Players {
+    pub fn iter(&self) -> impl Iterator<Item = (&ClientId, &Player)> {
+        self.0.iter()
+    }
+
+    /// Changes permission of the player and notifies the client about it.
+    ///
+    /// Used to promote spectators, so they can claim a family.
+    /// Returns `false` if the player is not connected.
+    pub fn set_permission(
+        &mut self,
+        commands: &mut Commands,
+        client_id: ClientId,
+        permission: Permission,
+    ) -> bool {
+        let Some(player) = self.0.get_mut(&client_id) else {
+            return false;
+        };
+
+        player.permission = permission;
+        if client_id != ClientId::SERVER {
+            commands.server_trigger(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: PermissionChanged(permission),
+            });
+        }
+        true
+    }
+
+    /// Returns `true` if the client is allowed to control families.
+    pub fn can_play(&self, client_id: ClientId) -> bool {
+        self.0
+            .get(&client_id)
+            .is_some_and(|player| player.permission.can_play())
+    }
+
+    /// Returns `true` if the client is allowed to modify buildings and objects.
+    pub fn can_build(&self, client_id: ClientId) -> bool {
+        self.0
+            .get(&client_id)
+            .is_some_and(|player| player.permission.can_build())
+    }
+
+    /// Returns `true` if the client runs the server.
+    pub fn is_host(&self, client_id: ClientId) -> bool {
+        self.0
+            .get(&client_id)
+            .is_some_and(|player| player.permission == Permission::Host)
+    }
+
+    /// Returns `true` if the client owns the family and can control its members.
+    pub fn owns(&self, client_id: ClientId, family_entity: Entity) -> bool {
+        self.0
+            .get(&client_id)
+            .is_some_and(|player| player.owned_families.contains(&family_entity))
+    }
+
+    /// Returns the family claimed by the client.
+    pub fn family(&self, client_id: ClientId) -> Option<Entity> {
+        self.0
+            .get(&client_id)
+            .and_then(|player| player.family_entity)
+    }
+
+    /// Removes a deleted family from all players.
+    pub fn forget_family(&mut self, commands: &mut Commands, family_entity: Entity) {
+        let mut changed = false;
+        for player in self.0.values_mut() {
+            if player.family_entity == Some(family_entity) {
+                player.family_entity = None;
+            }
+            if let Some(index) = player
+                .owned_families
+                .iter()
+                .position(|&entity| entity == family_entity)
+            {
+                player.owned_families.remove(index);
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.broadcast_claims(commands);
+        }
+    }
+
+    fn owner(&self, family_entity: Entity) -> Option<ClientId> {
+        self.0
+            .iter()
+            .find(|(_, player)| player.owned_families.contains(&family_entity))
+            .map(|(&client_id, _)| client_id)
+    }
+
+    /// Splits owned families into the client's own and the ones owned by others.
+    fn claims_for(&self, client_id: ClientId) -> ClaimedFamilies {
+        let mut claims = ClaimedFamilies::default();
+        for (&id, player) in &self.0 {
+            if id == client_id {
+                claims.own.extend(&player.owned_families);
+            } else {
+                claims.others.extend(&player.owned_families);
+            }
+        }
+
+        claims
+    }
+
+    fn broadcast_claims(&self, commands: &mut Commands) {
+        for &client_id in self.0.keys() {
+            commands.server_trigger(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: self.claims_for(client_id),
+            });
+        }
+    }
+}
+
+pub struct Player {
+    pub permission: Permission,
+    /// Currently controlled family.
+    pub family_entity: Option<Entity>,
+    /// Families played during the session, can't be claimed by other players.
+    ///
+    /// Includes [`Self::family_entity`].
+    pub owned_families: Vec<Entity>,
+}
+
+impl Player {
+    fn new(permission: Permission) -> Self {
+        Self {
+            permission,
+            family_entity: None,
+            owned_families: Vec::new(),
+        }
+    }
+}
+
+/// What the player is allowed to do, ordered from the least privileged.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Display,
+    EnumString,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum Permission {
+    /// Can only watch the world without a family.
+    Spectator,
+    /// Can only play for a family.
+    Guest,
+    /// Can also build and buy objects.
+    #[default]
+    Builder,
+    /// Player who runs the server.
+    Host,
+}
+
+impl Permission {
+    pub fn can_play(self) -> bool {
+        self >= Self::Guest
+    }
+
+    pub fn can_build(self) -> bool {
+        self >= Self::Builder
+    }
+}
+
+/// Marks a family as owned by another player.
+///
+/// Updated on clients from [`ClaimedFamilies`].
+#[derive(Component)]
+pub struct ClaimedFamily;
+
+/// Marks a family as owned by the local player.
+///
+/// The player can switch between owned families without leaving the family mode.
+/// Updated on clients from [`ClaimedFamilies`].
+#[derive(Component)]
+pub struct OwnedFamily;
+
+#[derive(Deserialize, Event, Serialize)]
+struct FamilyClaim;
+
+#[derive(Deserialize, Event, Serialize)]
+struct FamilyRelease;
+
+#[derive(Deserialize, Event, Serialize)]
+struct SpectateRequest;
+
+#[derive(Deserialize, Event, Serialize)]
+struct PermissionChanged(Permission);
+
+/// Sent to a client when the server refused its [`FamilyClaim`].
+#[derive(Deserialize, Event, Serialize)]
+struct ClaimRejected;
+
+/// Permission of the local player, updated from the server.
+///
+/// Used on clients to hide actions that the server would reject.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct LocalPermission(Permission);
+
+/// Requests [`Permission::Spectator`] after connecting to the server.
+///
+/// Should be inserted before the connection.
+#[derive(Resource)]
+pub struct JoinAsSpectator;
+
+/// All families owned by players, sent to each client separately.
+#[derive(Default, Deserialize, Event, Serialize)]
+struct ClaimedFamilies {
+    /// Families owned by the receiving client.
+    own: Vec<Entity>,
+    /// Families owned by other players.
+    others: Vec<Entity>,
+}
+
+impl MapEntities for ClaimedFamilies {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        for entity in self.own.iter_mut().chain(&mut self.others) {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::{self, Display, Formatter};
+
+use bevy::{
+    prelude::*,
+    reflect::{ReflectRef, VariantField},
+    utils::HashSet,
+};
+
+/// Drops scene entities with entity references that don't resolve inside the scene.
+///
+/// Dropping an entity may break references of other entities (like children of a dropped city),
+/// so the check repeats until the scene becomes consistent.
+/// Returns all found problems in the order of discovery.
+pub(super) fn drop_broken_entities(scene: &mut DynamicScene) -> Vec<BrokenReference> {
+    let mut broken = Vec::new();
+    loop {
+        let scene_entities: HashSet<_> =
+            scene.entities.iter().map(|entity| entity.entity).collect();
+        let count = broken.len();
+        scene.entities.retain(|dynamic_entity| {
+            for component in &dynamic_entity.components {
+                let mut references = Vec::new();
+                collect_entities(&**component, &mut references);
+                if let Some(&target) = references.iter().find(|&&target| {
+                    target != Entity::PLACEHOLDER && !scene_entities.contains(&target)
+                }) {
+                    broken.push(BrokenReference {
+                        entity: dynamic_entity.entity,
+                        component: component
+                            .get_represented_type_info()
+                            .map(|info| info.type_path_table().short_path())
+                            .unwrap_or_else(|| component.reflect_short_type_path())
+                            .to_string(),
+                        target,
+                    });
+                    return false;
+                }
+            }
+            true
+        });
+
+        if broken.len() == count {
+            return broken;
+        }
+    }
+}
+
+/// Recursively collects all [`Entity`] values from a reflected value.
+fn collect_entities(value: &dyn PartialReflect, entities: &mut Vec<Entity>) {
+    if let Some(&entity) = value.try_downcast_ref::<Entity>() {
+        entities.push(entity);
+        return;
+    }
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field, entities);
+            }
+        }
+        ReflectRef::TupleStruct(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field, entities);
+            }
+        }
+        ReflectRef::Tuple(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field, entities);
+            }
+        }
+        ReflectRef::List(value) => {
+            for item in value.iter() {
+                collect_entities(item, entities);
+            }
+        }
+        ReflectRef::Array(value) => {
+            for item in value.iter() {
+                collect_entities(item, entities);
+            }
+        }
+        ReflectRef::Map(value) => {
+            for (key, item) in value.iter() {
+                collect_entities(key, entities);
+                collect_entities(item, entities);
+            }
+        }
+        ReflectRef::Set(value) => {
+            for item in value.iter() {
+                collect_entities(item, entities);
+            }
+        }
+        ReflectRef::Enum(value) => {
+            for field in value.iter_fields() {
+                let field = match field {
+                    VariantField::Struct(_, field) => field,
+                    VariantField::Tuple(field) => field,
+                };
+                collect_entities(field, entities);
+            }
+        }
+        ReflectRef::Opaque(_) => (),
+    }
+}
+
+/// Entity reference from a saved component that points outside of the scene.
+pub(super) struct BrokenReference {
+    entity: Entity,
+    component: String,
+    target: Entity,
+}
+
+impl Display for BrokenReference {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "`{}` on entity `{}` references missing entity `{}`",
+            self.component, self.entity, self.target
+        )
+    }
+}
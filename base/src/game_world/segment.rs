@@ -146,7 +146,7 @@ fn disconnect_all(
     taken_connections
 }
 
-#[derive(Component, Clone, Copy, Default, Deserialize, Reflect, Serialize)]
+#[derive(Component, Clone, Copy, Default, Deserialize, PartialEq, Reflect, Serialize)]
 #[reflect(Component)]
 #[require(SegmentConnections)]
 pub(crate) struct Segment {
@@ -332,7 +332,7 @@ impl Sub<Vec2> for Segment {
 }
 
 /// Dynamically updated component with precalculated connected entities for each segment point.
-#[derive(Component, Default)]
+#[derive(Clone, Component, Default)]
 pub(crate) struct SegmentConnections {
     start: Vec<SegmentConnection>,
     end: Vec<SegmentConnection>,
@@ -360,6 +360,16 @@ impl SegmentConnections {
             .min_by(|a, b| a.abs().partial_cmp(&b.abs()).unwrap_or(Ordering::Equal))
     }
 
+    /// Returns entities connected to the point with their connected point kinds.
+    pub(super) fn connected(
+        &self,
+        point_kind: PointKind,
+    ) -> impl Iterator<Item = (Entity, PointKind)> + '_ {
+        self.get(point_kind)
+            .iter()
+            .map(|connection| (connection.entity, connection.kind))
+    }
+
     /// Returns iterator over segments that with unified direction based on point type.
     fn get_unified(&self, point_kind: PointKind) -> impl Iterator<Item = Segment> + '_ {
         self.get(point_kind)
@@ -387,6 +397,7 @@ impl SegmentConnections {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct SegmentConnection {
     entity: Entity,
     segment: Segment,
@@ -5,7 +5,7 @@ use bevy_enhanced_input::prelude::*;
 
 use super::{CameraCaster, PointKind, Segment, SegmentConnections};
 use crate::{
-    game_world::{city::CityMode, family::building::BuildingMode},
+    game_world::{city::CityMode, cursor_mode::PlacingCursor, family::building::BuildingMode},
     settings::Settings,
 };
 
@@ -17,9 +17,11 @@ impl Plugin for PlacingSegmentPlugin {
             .add_observer(cancel)
             .add_systems(
                 Update,
-                update_position
-                    .never_param_warn()
-                    .run_if(in_state(BuildingMode::Walls).or(in_state(CityMode::Roads))),
+                update_position.never_param_warn().run_if(
+                    in_state(BuildingMode::Walls)
+                        .or(in_state(BuildingMode::Fences))
+                        .or(in_state(CityMode::Roads)),
+                ),
             );
     }
 }
@@ -160,7 +162,7 @@ fn round_angle(
 }
 
 #[derive(Component, Clone, Copy)]
-#[require(Segment)]
+#[require(Segment, PlacingCursor)]
 pub(crate) struct PlacingSegment {
     pub(crate) point_kind: PointKind,
     pub(crate) snap_offset: f32,
@@ -174,18 +176,18 @@ impl InputContext for PlacingSegment {
         let settings = world.resource::<Settings>();
 
         ctx.bind::<DeleteSegment>()
-            .to((&settings.keyboard.delete, GamepadButton::North));
+            .to((&settings.keyboard.delete, &settings.gamepad.delete));
         ctx.bind::<CancelSegment>()
-            .to((KeyCode::Escape, GamepadButton::East));
+            .to((KeyCode::Escape, &settings.gamepad.cancel));
         ctx.bind::<ConfirmSegment>()
-            .to((MouseButton::Left, GamepadButton::South));
+            .to((MouseButton::Left, &settings.gamepad.confirm));
         ctx.bind::<FreeSegmentPlacement>().to((
             &settings.keyboard.free_placement,
-            GamepadButton::LeftTrigger2,
+            &settings.gamepad.free_placement,
         ));
         ctx.bind::<OrdinalSegmentPlacement>().to((
             &settings.keyboard.ordinal_placement,
-            GamepadButton::RightTrigger2,
+            &settings.gamepad.ordinal_placement,
         ));
 
         ctx
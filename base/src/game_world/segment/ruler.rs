@@ -38,7 +38,10 @@ impl Plugin for RulerPlugin {
                     ..Default::default()
                 },
             )
-            .add_systems(PostUpdate, draw.run_if(in_state(BuildingMode::Walls)));
+            .add_systems(
+                PostUpdate,
+                draw.run_if(in_state(BuildingMode::Walls).or(in_state(BuildingMode::Fences))),
+            );
     }
 }
 
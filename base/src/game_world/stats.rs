@@ -0,0 +1,101 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    actor::{needs::Need, Actor},
+    family::{Budget, FamilyMembers},
+    SimulationSet,
+};
+
+/// Periodically samples family and city values for charts.
+pub(super) struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<FamilyStats>()
+            .register_type::<CityStats>()
+            .replicate::<FamilyStats>()
+            .replicate::<CityStats>()
+            .add_systems(FixedUpdate, record.in_set(SimulationSet::Economy));
+    }
+}
+
+/// Game time between samples.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Maximum number of samples in a [`Series`].
+const SERIES_CAPACITY: usize = 120;
+
+fn record(
+    mut elapsed: Local<Duration>,
+    time: Res<Time>,
+    mut families: Query<(&mut FamilyStats, &Budget, &FamilyMembers)>,
+    mut cities: Query<(&mut CityStats, Option<&Children>)>,
+    members: Query<&Children, With<Actor>>,
+    needs: Query<&Need>,
+    actors: Query<(), With<Actor>>,
+) {
+    *elapsed += time.delta();
+    if *elapsed < SAMPLE_INTERVAL {
+        return;
+    }
+    *elapsed = Duration::ZERO;
+
+    debug!("recording stats");
+    for (mut stats, budget, family_members) in &mut families {
+        let mut sum = 0.0;
+        let mut count = 0;
+        for children in members.iter_many(family_members.iter()) {
+            for need in needs.iter_many(children) {
+                sum += need.0;
+                count += 1;
+            }
+        }
+
+        stats.budget.push(**budget as f32);
+        if count != 0 {
+            stats.needs.push(sum / count as f32);
+        }
+    }
+
+    for (mut stats, children) in &mut cities {
+        let population = children
+            .into_iter()
+            .flatten()
+            .filter(|&&entity| actors.get(entity).is_ok())
+            .count();
+        stats.population.push(population as f32);
+    }
+}
+
+/// Values sampled over time for a family.
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct FamilyStats {
+    pub budget: Series,
+    /// Average value of all needs of all members.
+    pub needs: Series,
+}
+
+/// Values sampled over time for a city.
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct CityStats {
+    pub population: Series,
+}
+
+/// Ring buffer of the last [`SERIES_CAPACITY`] samples, from oldest to newest.
+#[derive(Clone, Default, Deref, Deserialize, Reflect, Serialize)]
+pub struct Series(VecDeque<f32>);
+
+impl Series {
+    fn push(&mut self, value: f32) {
+        if self.0.len() == SERIES_CAPACITY {
+            self.0.pop_front();
+        }
+        self.0.push_back(value);
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+use super::players::Players;
+use crate::core::GameState;
+
+/// Synchronizes simulation speed between server and clients.
+///
+/// Any player can change the speed, the server applies it and broadcasts to everyone.
+pub(super) struct TimeSpeedPlugin;
+
+impl Plugin for TimeSpeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimeSpeed>()
+            .add_client_trigger::<TimeSpeedRequest>(ChannelKind::Ordered)
+            .add_server_trigger::<TimeSpeedChanged>(ChannelKind::Ordered)
+            .add_observer(request::<PauseTime>)
+            .add_observer(request::<SetNormalSpeed>)
+            .add_observer(request::<SetFastSpeed>)
+            .add_observer(request::<SetFasterSpeed>)
+            .add_observer(request::<SetUltraSpeed>)
+            .add_observer(apply_request)
+            .add_observer(receive)
+            .add_systems(
+                PreUpdate,
+                send_to_connected
+                    .after(ServerSet::Receive)
+                    .run_if(server_running),
+            )
+            .add_systems(PreUpdate, update_time.run_if(resource_changed::<TimeSpeed>))
+            .add_systems(OnExit(GameState::InGame), cleanup);
+    }
+}
+
+fn request<A: SpeedAction>(_trigger: Trigger<Started<A>>, mut commands: Commands) {
+    debug!("requesting `{:?}` time speed", A::SPEED);
+    commands.client_trigger(TimeSpeedRequest(A::SPEED));
+}
+
+fn apply_request(
+    trigger: Trigger<FromClient<TimeSpeedRequest>>,
+    mut commands: Commands,
+    mut time_speed: ResMut<TimeSpeed>,
+    players: Res<Players>,
+) {
+    if !players.can_play(trigger.client_id) {
+        error!(
+            "`{:?}` is not allowed to change time speed",
+            trigger.client_id
+        );
+        return;
+    }
+
+    let speed = trigger.event.0;
+    if *time_speed == speed {
+        return;
+    }
+
+    info!(
+        "`{:?}` changes time speed to `{speed:?}`",
+        trigger.client_id
+    );
+    *time_speed = speed;
+    commands.server_trigger(ToClients {
+        mode: SendMode::Broadcast,
+        event: TimeSpeedChanged(speed),
+    });
+}
+
+/// Applies speed on clients.
+///
+/// In singleplayer the speed is already set by [`apply_request`].
+fn receive(trigger: Trigger<TimeSpeedChanged>, mut time_speed: ResMut<TimeSpeed>) {
+    if *time_speed != trigger.0 {
+        debug!("received `{:?}` time speed", trigger.0);
+        *time_speed = trigger.0;
+    }
+}
+
+fn send_to_connected(
+    mut commands: Commands,
+    mut server_events: EventReader<ServerEvent>,
+    time_speed: Res<TimeSpeed>,
+) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientConnected { client_id } = event {
+            commands.server_trigger(ToClients {
+                mode: SendMode::Direct(*client_id),
+                event: TimeSpeedChanged(*time_speed),
+            });
+        }
+    }
+}
+
+/// Applies speed to the virtual time.
+///
+/// Fixed timestep is driven by the virtual time, so it scales too.
+/// The UI doesn't depend on the virtual time and stays responsive while paused.
+fn update_time(time_speed: Res<TimeSpeed>, mut time: ResMut<Time<Virtual>>) {
+    debug!("applying `{:?}` time speed", *time_speed);
+    match time_speed.relative_speed() {
+        Some(speed) => {
+            time.set_relative_speed(speed);
+            time.unpause();
+        }
+        None => time.pause(),
+    }
+}
+
+fn cleanup(mut time_speed: ResMut<TimeSpeed>) {
+    *time_speed = Default::default();
+}
+
+/// Current simulation speed.
+///
+/// Available on both server and clients, but changed only via [`TimeSpeedRequest`].
+#[derive(
+    Resource, Clone, Copy, Debug, Default, Deserialize, EnumIter, Eq, PartialEq, Serialize,
+)]
+pub enum TimeSpeed {
+    Paused,
+    #[default]
+    Normal,
+    Fast,
+    Faster,
+    Ultra,
+}
+
+impl TimeSpeed {
+    /// Returns `None` if paused.
+    fn relative_speed(self) -> Option<f32> {
+        match self {
+            Self::Paused => None,
+            Self::Normal => Some(1.0),
+            Self::Fast => Some(2.0),
+            Self::Faster => Some(3.0),
+            Self::Ultra => Some(10.0),
+        }
+    }
+
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Self::Paused => "⏸",
+            Self::Normal => "▶",
+            Self::Fast => "⏩",
+            Self::Faster => "⏭",
+            Self::Ultra => "🚀",
+        }
+    }
+}
+
+/// Asks the server to change [`TimeSpeed`].
+#[derive(Deserialize, Event, Serialize)]
+pub struct TimeSpeedRequest(pub TimeSpeed);
+
+#[derive(Deserialize, Event, Serialize)]
+struct TimeSpeedChanged(TimeSpeed);
+
+trait SpeedAction: InputAction {
+    const SPEED: TimeSpeed;
+}
+
+/// Bound in [`PlayerCamera`](super::player_camera::PlayerCamera) context to be available in all modes.
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(super) struct PauseTime;
+
+impl SpeedAction for PauseTime {
+    const SPEED: TimeSpeed = TimeSpeed::Paused;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(super) struct SetNormalSpeed;
+
+impl SpeedAction for SetNormalSpeed {
+    const SPEED: TimeSpeed = TimeSpeed::Normal;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(super) struct SetFastSpeed;
+
+impl SpeedAction for SetFastSpeed {
+    const SPEED: TimeSpeed = TimeSpeed::Fast;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(super) struct SetFasterSpeed;
+
+impl SpeedAction for SetFasterSpeed {
+    const SPEED: TimeSpeed = TimeSpeed::Faster;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(super) struct SetUltraSpeed;
+
+impl SpeedAction for SetUltraSpeed {
+    const SPEED: TimeSpeed = TimeSpeed::Ultra;
+}
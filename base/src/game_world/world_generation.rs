@@ -0,0 +1,93 @@
+mod city_generation;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{city::City, difficulty::Difficulty, family::Budget, WorldName};
+use crate::core::GameState;
+use city_generation::{CityGenerationPlugin, GenerateCity};
+
+/// Creates new worlds from [`WorldCreate`].
+pub(super) struct WorldGenerationPlugin;
+
+impl Plugin for WorldGenerationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(CityGenerationPlugin)
+            .register_type::<WorldSeed>()
+            .register_type::<StartingBudget>()
+            .replicate::<WorldSeed>()
+            .replicate::<StartingBudget>()
+            .add_observer(create);
+    }
+}
+
+fn create(trigger: Trigger<WorldCreate>, mut commands: Commands) {
+    let options = &trigger.0;
+    info!(
+        "creating world '{}' with `{}` difficulty and seed {}",
+        options.name, options.difficulty, options.seed
+    );
+
+    commands.spawn(options.difficulty);
+    commands.spawn(WorldSeed(options.seed));
+    commands.spawn(StartingBudget(options.starting_budget));
+    if options.starter_city {
+        debug!("spawning starter city");
+        commands.spawn((
+            City,
+            Name::new("Starter city"),
+            GenerateCity { seed: options.seed },
+        ));
+    }
+
+    commands.insert_resource(WorldName(options.name.clone()));
+    commands.set_state(GameState::InGame);
+}
+
+/// Starts a new world with the given options.
+#[derive(Event)]
+pub struct WorldCreate(pub WorldCreateOptions);
+
+/// Options chosen when creating a new world.
+#[derive(Clone)]
+pub struct WorldCreateOptions {
+    pub name: String,
+    pub seed: u32,
+    pub starting_budget: u32,
+    pub difficulty: Difficulty,
+    /// Generates a city with streets and houses in advance.
+    pub starter_city: bool,
+}
+
+impl Default for WorldCreateOptions {
+    fn default() -> Self {
+        Self {
+            name: "New world".to_string(),
+            seed: rand::random(),
+            starting_budget: *Budget::default(),
+            difficulty: Default::default(),
+            starter_city: true,
+        }
+    }
+}
+
+/// Seed from which the world content was generated.
+#[derive(Component, Clone, Copy, Debug, Deref, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("World seed")),
+    Replicated,
+    StateScoped<GameState>(|| StateScoped(GameState::InGame)),
+)]
+pub struct WorldSeed(pub u32);
+
+/// Budget assigned to newly created families.
+#[derive(Component, Clone, Copy, Debug, Deref, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("Starting budget")),
+    Replicated,
+    StateScoped<GameState>(|| StateScoped(GameState::InGame)),
+)]
+pub struct StartingBudget(pub u32);
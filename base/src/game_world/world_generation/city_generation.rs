@@ -0,0 +1,353 @@
+use std::f32::consts::TAU;
+
+use bevy::{asset::AssetPath, prelude::*};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    asset::manifest::{
+        fence_manifest::FenceManifest, object_manifest::ObjectManifest, road_manifest::RoadManifest,
+    },
+    game_world::{
+        city::{self, road::Road},
+        family::building::{
+            fence::{Fence, Gate},
+            wall::Wall,
+        },
+        object::Object,
+        segment::Segment,
+    },
+};
+
+/// Fills cities marked with [`GenerateCity`] with streets, lots, starter houses and decorations.
+pub(super) struct CityGenerationPlugin;
+
+impl Plugin for CityGenerationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(generate);
+    }
+}
+
+const ROAD: &str = "base/roads/road_007/road_007.road.ron";
+const FENCE: &str = "base/fences/picket/picket.fence.ron";
+const DOOR: &str = "base/objects/doors/classic_door/classic_door.object.ron";
+const DECORATIONS: [&str; 3] = [
+    "base/objects/foliage/simple_bush/simple_bush.object.ron",
+    "base/objects/rocks/small_stone/small_stone.object.ron",
+    "base/objects/rocks/medium_stone/medium_stone.object.ron",
+];
+
+/// Half length of the main street along X.
+const STREET_HALF_LENGTH: f32 = 80.0;
+
+/// Length of streets crossing the main street ends on each side.
+const CROSS_STREET_LENGTH: f32 = 50.0;
+
+/// Distance from street center lines to lots.
+const LOT_SETBACK: f32 = 6.0;
+
+const LOT_GAP: f32 = 2.0;
+const MIN_LOT_WIDTH: f32 = 12.0;
+const MAX_LOT_WIDTH: f32 = 24.0;
+const MIN_LOT_DEPTH: f32 = 16.0;
+const MAX_LOT_DEPTH: f32 = 28.0;
+const GATE_WIDTH: f32 = 2.0;
+
+/// Distance from the lot front to the house front.
+const HOUSE_SETBACK: f32 = 4.0;
+
+/// Minimum free space between a house and lot fences.
+const HOUSE_MARGIN: f32 = 2.0;
+
+const STARTER_HOUSES: usize = 3;
+
+/// How many times to try to place decorations, some attempts are rejected.
+const DECORATION_ATTEMPTS: usize = 60;
+
+/// Half size of the area around the streets for decorations.
+const DECORATION_HALF_SIZE: f32 = STREET_HALF_LENGTH + 20.0;
+
+/// Bundled starter house layouts.
+const BLUEPRINTS: [Blueprint; 2] = [
+    Blueprint {
+        size: Vec2::new(8.0, 6.0),
+        walls: &[
+            (Vec2::new(-4.0, 0.0), Vec2::new(4.0, 0.0)),
+            (Vec2::new(4.0, 0.0), Vec2::new(4.0, 6.0)),
+            (Vec2::new(4.0, 6.0), Vec2::new(-4.0, 6.0)),
+            (Vec2::new(-4.0, 6.0), Vec2::new(-4.0, 0.0)),
+        ],
+        door: Vec2::new(0.0, 0.0),
+    },
+    Blueprint {
+        size: Vec2::new(10.0, 8.0),
+        walls: &[
+            (Vec2::new(-5.0, 0.0), Vec2::new(5.0, 0.0)),
+            (Vec2::new(5.0, 0.0), Vec2::new(5.0, 8.0)),
+            (Vec2::new(5.0, 8.0), Vec2::new(0.0, 8.0)),
+            (Vec2::new(0.0, 8.0), Vec2::new(-5.0, 8.0)),
+            (Vec2::new(-5.0, 8.0), Vec2::new(-5.0, 0.0)),
+            (Vec2::new(0.0, 8.0), Vec2::new(0.0, 4.0)),
+        ],
+        door: Vec2::new(2.5, 0.0),
+    },
+];
+
+fn generate(
+    trigger: Trigger<OnAdd, GenerateCity>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    cities: Query<&GenerateCity>,
+) {
+    let generate_city = *cities.get(trigger.entity()).unwrap();
+    info!(
+        "generating city `{}` from seed {}",
+        trigger.entity(),
+        generate_city.seed
+    );
+
+    let mut rng = StdRng::seed_from_u64(generate_city.seed.into());
+    let lots = lay_out_lots(&mut rng);
+
+    let road = manifest_path::<RoadManifest>(&asset_server, ROAD);
+    let fence = manifest_path::<FenceManifest>(&asset_server, FENCE);
+    let door = manifest_path::<ObjectManifest>(&asset_server, DOOR);
+    let decorations: Vec<_> = DECORATIONS
+        .into_iter()
+        .filter_map(|path| manifest_path::<ObjectManifest>(&asset_server, path))
+        .collect();
+
+    commands
+        .entity(trigger.entity())
+        .remove::<GenerateCity>()
+        .with_children(|parent| {
+            if let Some(road) = road {
+                spawn_streets(parent, road);
+            }
+
+            for lot in &lots {
+                parent.spawn(city::Lot {
+                    rect: lot.rect(),
+                    entrance: lot.entrance(),
+                });
+                if let Some(fence) = &fence {
+                    spawn_fences(parent, fence, lot);
+                }
+            }
+
+            let mut houses = 0;
+            for lot in &lots {
+                if houses == STARTER_HOUSES {
+                    break;
+                }
+
+                let blueprint = &BLUEPRINTS[rng.gen_range(0..BLUEPRINTS.len())];
+                if lot.fits(blueprint) {
+                    spawn_house(parent, door.as_ref(), lot, blueprint);
+                    houses += 1;
+                }
+            }
+
+            if !decorations.is_empty() {
+                spawn_decorations(parent, &mut rng, &decorations, &lots);
+            }
+        });
+}
+
+/// Returns the path if the manifest is available.
+///
+/// Bundled assets can be removed by the player, so generation skips missing ones.
+fn manifest_path<A: Asset>(
+    asset_server: &AssetServer,
+    path: &'static str,
+) -> Option<AssetPath<'static>> {
+    if asset_server.get_handle::<A>(path).is_none() {
+        warn!("'{path}' is missing, skipping it for city generation");
+        return None;
+    }
+
+    Some(path.into())
+}
+
+/// Places lots of random sizes along both sides of the main street.
+fn lay_out_lots(rng: &mut impl Rng) -> Vec<Lot> {
+    let mut lots = Vec::new();
+    for side in [1.0, -1.0] {
+        let mut x = -STREET_HALF_LENGTH + LOT_SETBACK;
+        loop {
+            let width = rng.gen_range(MIN_LOT_WIDTH..=MAX_LOT_WIDTH);
+            if x + width > STREET_HALF_LENGTH - LOT_SETBACK {
+                break;
+            }
+
+            let depth = rng.gen_range(MIN_LOT_DEPTH..=MAX_LOT_DEPTH);
+            lots.push(Lot {
+                min_x: x,
+                max_x: x + width,
+                front: side * LOT_SETBACK,
+                back: side * (LOT_SETBACK + depth),
+            });
+            x += width + LOT_GAP;
+        }
+    }
+
+    lots
+}
+
+fn spawn_streets(parent: &mut ChildBuilder, road: AssetPath<'static>) {
+    let west = Vec2::new(-STREET_HALF_LENGTH, 0.0);
+    let east = Vec2::new(STREET_HALF_LENGTH, 0.0);
+    let mut segments = vec![Segment::new(west, east)];
+
+    // Split cross streets at the main street to connect them.
+    for end in [west, east] {
+        for side in [1.0, -1.0] {
+            let cross_end = end + Vec2::Y * side * CROSS_STREET_LENGTH;
+            segments.push(Segment::new(end, cross_end));
+        }
+    }
+
+    for segment in segments {
+        parent.spawn((Road(road.clone()), segment));
+    }
+}
+
+fn spawn_fences(parent: &mut ChildBuilder, fence: &AssetPath<'static>, lot: &Lot) {
+    let front_left = Vec2::new(lot.min_x, lot.front);
+    let front_right = Vec2::new(lot.max_x, lot.front);
+    let back_right = Vec2::new(lot.max_x, lot.back);
+    let back_left = Vec2::new(lot.min_x, lot.back);
+
+    let center = (lot.min_x + lot.max_x) / 2.0;
+    let gate_left = Vec2::new(center - GATE_WIDTH / 2.0, lot.front);
+    let gate_right = Vec2::new(center + GATE_WIDTH / 2.0, lot.front);
+
+    for segment in [
+        Segment::new(front_left, gate_left),
+        Segment::new(gate_right, front_right),
+        Segment::new(front_right, back_right),
+        Segment::new(back_right, back_left),
+        Segment::new(back_left, front_left),
+    ] {
+        parent.spawn((Fence(fence.clone()), segment));
+    }
+    parent.spawn((
+        Fence(fence.clone()),
+        Gate,
+        Segment::new(gate_left, gate_right),
+    ));
+}
+
+fn spawn_house(
+    parent: &mut ChildBuilder,
+    door: Option<&AssetPath<'static>>,
+    lot: &Lot,
+    blueprint: &Blueprint,
+) {
+    let side = lot.front.signum();
+    let origin = Vec2::new(
+        (lot.min_x + lot.max_x) / 2.0,
+        lot.front + side * HOUSE_SETBACK,
+    );
+    let to_lot = |point: Vec2| origin + Vec2::new(point.x, side * point.y);
+
+    for &(start, end) in blueprint.walls {
+        parent.spawn((Wall, Segment::new(to_lot(start), to_lot(end))));
+    }
+
+    if let Some(door) = door {
+        // The door is always placed on the first wall.
+        let (start, end) = blueprint.walls[0];
+        let displacement = to_lot(end) - to_lot(start);
+        let translation = to_lot(blueprint.door);
+        parent.spawn((
+            Object(door.clone()),
+            Transform::from_xyz(translation.x, 0.0, translation.y)
+                .with_rotation(Quat::from_rotation_y(-displacement.to_angle())),
+        ));
+    }
+}
+
+/// Scatters decorations outside of streets and lots.
+fn spawn_decorations(
+    parent: &mut ChildBuilder,
+    rng: &mut impl Rng,
+    decorations: &[AssetPath<'static>],
+    lots: &[Lot],
+) {
+    for _ in 0..DECORATION_ATTEMPTS {
+        let point = Vec2::new(
+            rng.gen_range(-DECORATION_HALF_SIZE..=DECORATION_HALF_SIZE),
+            rng.gen_range(-DECORATION_HALF_SIZE..=DECORATION_HALF_SIZE),
+        );
+        let rotation = Quat::from_rotation_y(rng.gen_range(0.0..TAU));
+        let decoration = &decorations[rng.gen_range(0..decorations.len())];
+
+        let near_main_street =
+            point.x.abs() <= STREET_HALF_LENGTH + LOT_SETBACK && point.y.abs() <= LOT_SETBACK;
+        let near_cross_street = (point.x.abs() - STREET_HALF_LENGTH).abs() <= LOT_SETBACK
+            && point.y.abs() <= CROSS_STREET_LENGTH + LOT_SETBACK;
+        if near_main_street || near_cross_street || lots.iter().any(|lot| lot.contains(point)) {
+            continue;
+        }
+
+        parent.spawn((
+            Object(decoration.clone()),
+            Transform::from_xyz(point.x, 0.0, point.y).with_rotation(rotation),
+        ));
+    }
+}
+
+/// Requests generation of the city content from the seed.
+///
+/// Removed after the generation.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct GenerateCity {
+    pub(crate) seed: u32,
+}
+
+/// Fenced area along the main street.
+struct Lot {
+    min_x: f32,
+    max_x: f32,
+    /// Z coordinate of the side facing the street.
+    front: f32,
+    /// Z coordinate of the opposite side.
+    back: f32,
+}
+
+impl Lot {
+    fn fits(&self, blueprint: &Blueprint) -> bool {
+        let width = self.max_x - self.min_x;
+        let depth = (self.back - self.front).abs();
+        width >= blueprint.size.x + 2.0 * HOUSE_MARGIN
+            && depth >= blueprint.size.y + HOUSE_SETBACK + HOUSE_MARGIN
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        self.rect().contains(point)
+    }
+
+    fn rect(&self) -> Rect {
+        Rect::new(self.min_x, self.front, self.max_x, self.back)
+    }
+
+    /// Returns a point between the gate and the house front.
+    fn entrance(&self) -> Vec2 {
+        let side = self.front.signum();
+        Vec2::new(
+            (self.min_x + self.max_x) / 2.0,
+            self.front + side * HOUSE_SETBACK / 2.0,
+        )
+    }
+}
+
+/// Wall layout of a house.
+///
+/// Points are relative to the middle of the house front,
+/// with Y pointing away from the street.
+struct Blueprint {
+    size: Vec2,
+    walls: &'static [(Vec2, Vec2)],
+    /// Door position on the first wall.
+    door: Vec2,
+}
@@ -0,0 +1,280 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{bail, ensure, Context, Result};
+use bevy::{ecs::system::SystemParam, prelude::*, scene::ron};
+use serde::{Deserialize, Serialize};
+
+use crate::game_paths::{write_atomic, GamePaths};
+
+/// Operations on worlds saved to disk.
+///
+/// Each world consists of the scene file, optional header, timeline and preview files
+/// and a directory with named snapshots, all of them are named after [`super::WorldName`].
+#[derive(SystemParam)]
+pub struct WorldManagement<'w> {
+    game_paths: Res<'w, GamePaths>,
+}
+
+impl WorldManagement<'_> {
+    /// Returns all saved worlds, the most recently played first.
+    pub fn worlds(&self) -> Result<Vec<WorldInfo>> {
+        let mut worlds: Vec<_> = self
+            .game_paths
+            .get_world_names()?
+            .into_iter()
+            .map(|name| {
+                let world_path = self.game_paths.world_path(&name);
+                let last_played = fs::metadata(&world_path)
+                    .and_then(|metadata| metadata.modified())
+                    .inspect_err(|e| error!("unable to read {world_path:?} metadata: {e}"))
+                    .ok();
+                let preview_path = self.game_paths.world_preview_path(&name);
+                let header = read_header(&self.game_paths.world_header_path(&name));
+                WorldInfo {
+                    preview: preview_path.exists().then_some(preview_path),
+                    name,
+                    last_played,
+                    header,
+                }
+            })
+            .collect();
+
+        worlds.sort_by(|a, b| b.last_played.cmp(&a.last_played));
+
+        Ok(worlds)
+    }
+
+    /// Returns named snapshots of a world, the most recently saved first.
+    pub fn snapshots(&self, world_name: &str) -> Result<Vec<SnapshotInfo>> {
+        let mut snapshots: Vec<_> = self
+            .game_paths
+            .get_snapshot_names(world_name)?
+            .into_iter()
+            .map(|name| {
+                let snapshot_path = self.game_paths.snapshot_path(world_name, &name);
+                let saved = fs::metadata(&snapshot_path)
+                    .and_then(|metadata| metadata.modified())
+                    .inspect_err(|e| error!("unable to read {snapshot_path:?} metadata: {e}"))
+                    .ok();
+                let header = read_header(&self.game_paths.snapshot_header_path(world_name, &name));
+                SnapshotInfo {
+                    name,
+                    saved,
+                    header,
+                }
+            })
+            .collect();
+
+        snapshots.sort_by(|a, b| b.saved.cmp(&a.saved));
+
+        Ok(snapshots)
+    }
+
+    pub fn rename(&self, name: &str, new_name: &str) -> Result<()> {
+        self.ensure_available(new_name)?;
+
+        info!("renaming world '{name}' to '{new_name}'");
+        for (path, new_path) in self.world_files(name, new_name) {
+            if path.exists() {
+                fs::rename(&path, &new_path)
+                    .with_context(|| format!("unable to move {path:?} to {new_path:?}"))?;
+            }
+        }
+
+        let snapshots_dir = self.game_paths.snapshots_dir(name);
+        if snapshots_dir.exists() {
+            let new_dir = self.game_paths.snapshots_dir(new_name);
+            fs::rename(&snapshots_dir, &new_dir)
+                .with_context(|| format!("unable to move {snapshots_dir:?} to {new_dir:?}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Copies the world under a free name and returns the new name.
+    pub fn duplicate(&self, name: &str) -> Result<String> {
+        let new_name = (1..)
+            .map(|index| match index {
+                1 => format!("{name} (copy)"),
+                _ => format!("{name} (copy {index})"),
+            })
+            .find(|new_name| !self.game_paths.world_path(new_name).exists())
+            .expect("iterator should be infinite");
+
+        info!("duplicating world '{name}' as '{new_name}'");
+        for (path, new_path) in self.world_files(name, &new_name) {
+            if path.exists() {
+                fs::copy(&path, &new_path)
+                    .with_context(|| format!("unable to copy {path:?} to {new_path:?}"))?;
+            }
+        }
+
+        let snapshot_names = self.game_paths.get_snapshot_names(name)?;
+        if !snapshot_names.is_empty() {
+            let new_dir = self.game_paths.snapshots_dir(&new_name);
+            fs::create_dir_all(&new_dir)
+                .with_context(|| format!("unable to create {new_dir:?}"))?;
+            for snapshot_name in snapshot_names {
+                for (path, new_path) in [
+                    (
+                        self.game_paths.snapshot_path(name, &snapshot_name),
+                        self.game_paths.snapshot_path(&new_name, &snapshot_name),
+                    ),
+                    (
+                        self.game_paths.snapshot_header_path(name, &snapshot_name),
+                        self.game_paths
+                            .snapshot_header_path(&new_name, &snapshot_name),
+                    ),
+                ] {
+                    if path.exists() {
+                        fs::copy(&path, &new_path)
+                            .with_context(|| format!("unable to copy {path:?} to {new_path:?}"))?;
+                    }
+                }
+            }
+        }
+
+        Ok(new_name)
+    }
+
+    pub fn delete(&self, name: &str) -> Result<()> {
+        info!("deleting world '{name}'");
+        let world_path = self.game_paths.world_path(name);
+        fs::remove_file(&world_path).with_context(|| format!("unable to remove {world_path:?}"))?;
+
+        for path in [
+            self.game_paths.world_header_path(name),
+            self.game_paths.timeline_path(name),
+            self.game_paths.world_preview_path(name),
+        ] {
+            if path.exists() {
+                fs::remove_file(&path).with_context(|| format!("unable to remove {path:?}"))?;
+            }
+        }
+
+        let snapshots_dir = self.game_paths.snapshots_dir(name);
+        if snapshots_dir.exists() {
+            fs::remove_dir_all(&snapshots_dir)
+                .with_context(|| format!("unable to remove {snapshots_dir:?}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error if the name can't be used for a snapshot.
+    ///
+    /// Unlike worlds, existing snapshots can be overwritten.
+    pub fn ensure_valid_snapshot(&self, name: &str) -> Result<()> {
+        ensure!(!name.trim().is_empty(), "snapshot name can't be empty");
+        if name.contains(['/', '\\']) {
+            bail!("snapshot name can't contain slashes");
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error if the name can't be used for a new world.
+    fn ensure_available(&self, name: &str) -> Result<()> {
+        ensure!(!name.trim().is_empty(), "world name can't be empty");
+        if name.contains(['/', '\\']) {
+            bail!("world name can't contain slashes");
+        }
+        ensure!(
+            !self.game_paths.world_path(name).exists(),
+            "world '{name}' already exists"
+        );
+
+        Ok(())
+    }
+
+    /// Returns pairs of world files for the current and the new name.
+    fn world_files(&self, name: &str, new_name: &str) -> [(PathBuf, PathBuf); 4] {
+        [
+            (
+                self.game_paths.world_path(name),
+                self.game_paths.world_path(new_name),
+            ),
+            (
+                self.game_paths.world_header_path(name),
+                self.game_paths.world_header_path(new_name),
+            ),
+            (
+                self.game_paths.timeline_path(name),
+                self.game_paths.timeline_path(new_name),
+            ),
+            (
+                self.game_paths.world_preview_path(name),
+                self.game_paths.world_preview_path(new_name),
+            ),
+        ]
+    }
+}
+
+/// Metadata of a saved world.
+pub struct WorldInfo {
+    pub name: String,
+    /// Time of the last save.
+    pub last_played: Option<SystemTime>,
+    /// Screenshot captured on the last save.
+    pub preview: Option<PathBuf>,
+    /// Missing for worlds saved before headers were introduced.
+    pub header: Option<SaveHeader>,
+}
+
+/// Metadata of a named world snapshot.
+pub struct SnapshotInfo {
+    pub name: String,
+    /// Time when the snapshot was saved.
+    pub saved: Option<SystemTime>,
+    pub header: Option<SaveHeader>,
+}
+
+/// Metadata written next to each saved world or snapshot.
+///
+/// Small enough to be read for listings without deserializing the world.
+/// The thumbnail is stored as a separate image because it's captured
+/// asynchronously after the save.
+#[derive(Deserialize, Serialize)]
+pub struct SaveHeader {
+    /// Version of the game that wrote the save.
+    pub version: String,
+    /// Total time the world was played.
+    pub play_time: Duration,
+    /// Names of families with their total play time.
+    pub families: Vec<(String, Duration)>,
+}
+
+impl SaveHeader {
+    pub(crate) fn new(play_time: Duration, families: Vec<(String, Duration)>) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            play_time,
+            families,
+        }
+    }
+
+    pub(crate) fn read(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).with_context(|| format!("unable to read {path:?}"))?;
+        let header = ron::from_str(&text).with_context(|| format!("unable to parse {path:?}"))?;
+
+        Ok(header)
+    }
+
+    pub(crate) fn write(&self, path: &Path) -> Result<()> {
+        let text = ron::ser::to_string_pretty(self, Default::default())?;
+        write_atomic(path, text).with_context(|| format!("unable to write {path:?}"))
+    }
+}
+
+/// Reads header if it exists, logging errors since headers are optional.
+fn read_header(path: &Path) -> Option<SaveHeader> {
+    if !path.exists() {
+        return None;
+    }
+
+    SaveHeader::read(path).inspect_err(|e| error!("{e:#}")).ok()
+}
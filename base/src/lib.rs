@@ -1,26 +1,44 @@
+pub mod achievements;
 mod alpha_color;
 pub mod asset;
+pub mod audio;
 mod combined_scene_collider;
 pub mod common_conditions;
 pub mod core;
 mod dynamic_mesh;
+mod entity_audit;
 pub mod error_message;
 pub mod game_paths;
 pub mod game_world;
 mod ghost;
+pub mod log_files;
 pub mod network;
+pub mod notification;
+pub mod profiling;
+mod scripting;
 pub mod settings;
+pub mod tutorial;
 
 use bevy::{app::PluginGroupBuilder, prelude::*};
 
+use achievements::AchievementsPlugin;
 use alpha_color::AlphaColorPlugin;
 use asset::AssetPlugin;
+use audio::AudioPlugin;
 use combined_scene_collider::SceneColliderConstructorPlugin;
 use core::CorePlugin;
+use entity_audit::EntityAuditPlugin;
 use game_paths::GamePathsPlugin;
 use game_world::GameWorldPlugin;
 use ghost::GhostPlugin;
+use network::{
+    discovery::DiscoveryPlugin, migration::MigrationPlugin, replay_log::ReplayLogPlugin,
+    token_server::TokenServerPlugin,
+};
+use profiling::ProfilingPlugin;
+use scripting::ScriptingPlugin;
 use settings::SettingsPlugin;
+use tutorial::TutorialPlugin;
 
 pub struct CorePlugins;
 
@@ -28,6 +46,7 @@ impl PluginGroup for CorePlugins {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
             .add(AssetPlugin)
+            .add(AudioPlugin)
             .add(CorePlugin)
             .add(AlphaColorPlugin)
             .add(SceneColliderConstructorPlugin)
@@ -35,5 +54,14 @@ impl PluginGroup for CorePlugins {
             .add(GamePathsPlugin)
             .add(SettingsPlugin)
             .add(GhostPlugin)
+            .add(DiscoveryPlugin)
+            .add(MigrationPlugin)
+            .add(ReplayLogPlugin)
+            .add(TokenServerPlugin)
+            .add(ScriptingPlugin)
+            .add(TutorialPlugin)
+            .add(AchievementsPlugin)
+            .add(ProfilingPlugin)
+            .add(EntityAuditPlugin)
     }
 }
@@ -0,0 +1,146 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use bevy::{
+    log::{
+        tracing_subscriber::{fmt, Layer},
+        BoxedLayer, Level,
+    },
+    prelude::*,
+    scene::ron,
+};
+
+use crate::{
+    game_paths::{GamePaths, LOG_EXTENSION},
+    settings::Settings,
+};
+
+/// Size in bytes after which the log switches to the next file.
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Number of log files kept across sessions, older files are removed on startup.
+const MAX_FILES: usize = 20;
+
+/// Creates a layer that duplicates logs into [`GamePaths::logs`] in compact format.
+///
+/// Lets players attach logs to bug reports. Should be passed as `custom_layer`
+/// to [`LogPlugin`](bevy::log::LogPlugin). Inserts [`SessionLog`] if the file was created.
+pub fn file_layer(app: &mut App) -> Option<BoxedLayer> {
+    let logs = app.world().resource::<GamePaths>().logs.clone();
+    let session = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    // The logger is not initialized yet, so errors are printed directly.
+    let writer = RotatingWriter::new(logs, session)
+        .inspect_err(|e| eprintln!("{e:#}"))
+        .ok()?;
+    if let Err(e) = remove_old(app.world().resource::<GamePaths>()) {
+        eprintln!("{e:#}");
+    }
+
+    app.insert_resource(SessionLog(writer.path.clone()));
+
+    Some(
+        fmt::layer()
+            .compact()
+            .with_ansi(false)
+            .with_writer(Mutex::new(writer))
+            .boxed(),
+    )
+}
+
+/// Reads [`DeveloperSettings::log_level`](crate::settings::DeveloperSettings::log_level)
+/// directly from the settings file.
+///
+/// Logging is initialized before [`Settings`] are loaded, so parsing errors are ignored here
+/// and reported on load.
+pub fn configured_level(game_paths: &GamePaths) -> Level {
+    fs::read_to_string(&game_paths.settings)
+        .ok()
+        .and_then(|content| ron::from_str::<Settings>(&content).ok())
+        .map(|settings| settings.developer.log_level)
+        .unwrap_or_default()
+        .into()
+}
+
+fn remove_old(game_paths: &GamePaths) -> Result<()> {
+    let paths = game_paths.get_log_paths()?;
+    let count = paths.len().saturating_sub(MAX_FILES);
+    for path in &paths[..count] {
+        fs::remove_file(path).with_context(|| format!("unable to remove {path:?}"))?;
+    }
+
+    Ok(())
+}
+
+fn log_path(logs: &Path, session: u64, part: usize) -> PathBuf {
+    let mut path = logs.join(format!("{session}-{part}"));
+    path.set_extension(LOG_EXTENSION);
+    path
+}
+
+/// Path to the first log file of the current session.
+///
+/// Next parts are created next to it with an increased suffix.
+#[derive(Resource, Deref)]
+pub struct SessionLog(PathBuf);
+
+/// Writes into a new file once the current one exceeds [`MAX_FILE_SIZE`].
+struct RotatingWriter {
+    logs: PathBuf,
+    session: u64,
+    part: usize,
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+impl RotatingWriter {
+    fn new(logs: PathBuf, session: u64) -> Result<Self> {
+        let path = log_path(&logs, session, 0);
+        let file = File::create(&path).with_context(|| format!("unable to create {path:?}"))?;
+
+        Ok(Self {
+            logs,
+            session,
+            part: 0,
+            path,
+            file,
+            written: 0,
+        })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.part += 1;
+        self.path = log_path(&self.logs, self.session, self.part);
+        self.file = File::create(&self.path)?;
+        self.written = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for RotatingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written >= MAX_FILE_SIZE {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.written += written as u64;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
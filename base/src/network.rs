@@ -1,3 +1,8 @@
+pub mod discovery;
+pub mod migration;
+pub mod replay_log;
+pub mod token_server;
+
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
     time::SystemTime,
@@ -10,41 +15,83 @@ use bevy_replicon_renet::netcode::{
     ServerConfig,
 };
 
+use token_server::TokenServer;
+
 pub const DEFAULT_PORT: u16 = 4761;
 const PROTOCOL_ID: u64 = 7;
+const MAX_CLIENTS: usize = 8;
+
+/// Password of the current session.
+///
+/// Kept to host or rejoin the session after host migration.
+#[derive(Default, Resource)]
+pub struct SessionPassword(pub String);
 
-pub fn create_server(port: u16) -> Result<NetcodeServerTransport> {
+/// Creates a server transport and a token server that authorizes clients for it.
+///
+/// Connect tokens are signed with a random key, so clients can join only
+/// with tokens from the returned [`TokenServer`].
+pub fn create_server(port: u16, password: &str) -> Result<(NetcodeServerTransport, TokenServer)> {
     info!("creating server transport for port {port}");
 
     let current_time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
-    let public_addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port);
-    let socket = UdpSocket::bind(public_addr)?;
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?;
+    let private_key = rand::random();
     let server_config = ServerConfig {
         current_time,
-        max_clients: 1,
+        max_clients: MAX_CLIENTS,
         protocol_id: PROTOCOL_ID,
-        authentication: ServerAuthentication::Unsecure,
-        public_addresses: vec![public_addr],
+        authentication: ServerAuthentication::Secure { private_key },
+        public_addresses: public_addresses(port),
     };
     let transport = NetcodeServerTransport::new(server_config, socket)?;
+    let token_server = TokenServer::new(port, private_key, password.to_string())?;
 
-    Ok(transport)
+    Ok((transport, token_server))
 }
 
-pub fn create_client(ip: IpAddr, port: u16) -> Result<NetcodeClientTransport> {
+/// Creates a client transport with a connect token requested from the server.
+///
+/// Returns an error if the server rejected the password.
+pub fn create_client(ip: IpAddr, port: u16, password: &str) -> Result<NetcodeClientTransport> {
     info!("creating client transport for {ip}:{port}");
 
+    let connect_token = token_server::request_token(SocketAddr::new(ip, port), password)?;
     let current_time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
-    let client_id = current_time.as_millis() as u64;
-    let server_addr = SocketAddr::new(ip, port);
-    let socket = UdpSocket::bind((ip, 0))?;
-    let authentication = ClientAuthentication::Unsecure {
-        client_id,
-        protocol_id: PROTOCOL_ID,
-        server_addr,
-        user_data: None,
-    };
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    let authentication = ClientAuthentication::Secure { connect_token };
     let transport = NetcodeClientTransport::new(current_time, authentication, socket)?;
 
     Ok(transport)
 }
+
+/// Returns addresses of all network interfaces with the specified port.
+///
+/// Connect tokens are accepted only if they were generated for one of these addresses,
+/// so clients can join using any address of the machine.
+fn public_addresses(port: u16) -> Vec<SocketAddr> {
+    match if_addrs::get_if_addrs() {
+        Ok(interfaces) => interfaces
+            .into_iter()
+            .map(|interface| {
+                debug!("accepting connections via {}", interface.ip());
+                SocketAddr::new(interface.ip(), port)
+            })
+            .collect(),
+        Err(e) => {
+            error!("unable to list network interfaces, accepting only local connections: {e}");
+            vec![SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port)]
+        }
+    }
+}
+
+/// Returns the address of the machine in the local network.
+///
+/// Connecting a UDP socket doesn't send anything, it only picks the outgoing interface.
+fn local_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(8, 8, 8, 8), 80)).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    debug!("detected local address {ip}");
+    Some(ip)
+}
@@ -0,0 +1,230 @@
+use std::{
+    io::ErrorKind,
+    net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use bevy_replicon_renet::netcode::NetcodeServerTransport;
+use serde::{Deserialize, Serialize};
+
+use super::{DEFAULT_PORT, MAX_CLIENTS, PROTOCOL_ID};
+use crate::{
+    core::GameState,
+    game_world::{players::Players, WorldName},
+};
+
+/// Announces running servers via UDP broadcast and collects announcements into [`LanServers`].
+pub(crate) struct DiscoveryPlugin;
+
+impl Plugin for DiscoveryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                announce
+                    .pipe(disable_on_error)
+                    .run_if(server_running)
+                    .run_if(not(resource_exists::<AnnouncementFailed>))
+                    .run_if(in_state(GameState::InGame)),
+                receive.run_if(resource_exists::<LanServers>),
+            ),
+        )
+        .add_systems(OnExit(GameState::InGame), cleanup);
+    }
+}
+
+/// Port on which servers are announced.
+const DISCOVERY_PORT: u16 = DEFAULT_PORT + 1;
+
+/// How often the server announces itself.
+const ANNOUNCE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Time after which a server that stopped announcing is removed from [`LanServers`].
+const SERVER_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Large enough for any world name.
+const MAX_ANNOUNCEMENT_SIZE: usize = 1024;
+
+fn announce(
+    mut socket: Local<Option<UdpSocket>>,
+    mut elapsed: Local<Duration>,
+    time: Res<Time<Real>>,
+    world_name: Res<WorldName>,
+    transport: Res<NetcodeServerTransport>,
+    players: Res<Players>,
+) -> Result<()> {
+    *elapsed += time.delta();
+    if *elapsed < ANNOUNCE_INTERVAL {
+        return Ok(());
+    }
+    *elapsed = Duration::ZERO;
+
+    let socket = match &mut *socket {
+        Some(socket) => socket,
+        None => {
+            debug!("creating announcement socket");
+            let new_socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+                .context("unable to create announcement socket")?;
+            new_socket.set_broadcast(true)?;
+            new_socket.set_nonblocking(true)?;
+            socket.insert(new_socket)
+        }
+    };
+
+    let port = transport
+        .addresses()
+        .first()
+        .map(SocketAddr::port)
+        .unwrap_or(DEFAULT_PORT);
+    let announcement = Announcement {
+        protocol_id: PROTOCOL_ID,
+        world_name: world_name.0.clone(),
+        port,
+        players: players.iter().count(),
+        max_players: MAX_CLIENTS,
+    };
+    let bytes = bincode::serialize(&announcement).expect("announcement should be serialized");
+
+    trace!("announcing '{}' on port {port}", announcement.world_name);
+    socket
+        .send_to(&bytes, (Ipv4Addr::BROADCAST, DISCOVERY_PORT))
+        .context("unable to send server announcement")?;
+
+    Ok(())
+}
+
+/// Stops announcing after the first failure.
+///
+/// Hosts without a broadcast route would fail on each announcement.
+/// Discovery is optional, so the error is only logged.
+fn disable_on_error(In(result): In<Result<()>>, mut commands: Commands) {
+    if let Err(e) = result {
+        error!("disabling server announcements: {e:#}");
+        commands.insert_resource(AnnouncementFailed);
+    }
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<AnnouncementFailed>();
+}
+
+fn receive(time: Res<Time<Real>>, mut lan_servers: ResMut<LanServers>) {
+    let now = time.elapsed();
+    let mut changed = false;
+    let mut buffer = [0; MAX_ANNOUNCEMENT_SIZE];
+    loop {
+        let (len, sender_addr) = match lan_servers.socket.recv_from(&mut buffer) {
+            Ok(received) => received,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+            Err(e) => {
+                error!("unable to receive server announcement: {e}");
+                break;
+            }
+        };
+
+        let Ok(announcement) = bincode::deserialize::<Announcement>(&buffer[..len]) else {
+            debug!("ignoring invalid announcement from {sender_addr}");
+            continue;
+        };
+        if announcement.protocol_id != PROTOCOL_ID {
+            debug!("ignoring announcement with different protocol from {sender_addr}");
+            continue;
+        }
+
+        let server = LanServer {
+            ip: sender_addr.ip(),
+            port: announcement.port,
+            world_name: announcement.world_name,
+            players: announcement.players,
+            max_players: announcement.max_players,
+            last_seen: now,
+        };
+        let servers = &mut lan_servers.bypass_change_detection().servers;
+        match servers
+            .iter_mut()
+            .find(|known| known.ip == server.ip && known.port == server.port)
+        {
+            Some(known) => {
+                changed |= known.world_name != server.world_name
+                    || known.players != server.players
+                    || known.max_players != server.max_players;
+                *known = server;
+            }
+            None => {
+                debug!("discovered '{}' at {}", server.world_name, server.addr());
+                servers.push(server);
+                changed = true;
+            }
+        }
+    }
+
+    let servers = &mut lan_servers.bypass_change_detection().servers;
+    let len = servers.len();
+    servers.retain(|server| now - server.last_seen < SERVER_TIMEOUT);
+    if servers.len() != len {
+        debug!("removing {} timed out servers", len - servers.len());
+        changed = true;
+    }
+
+    if changed {
+        lan_servers.set_changed();
+    }
+}
+
+/// Disables announcements until the end of the session.
+#[derive(Resource)]
+struct AnnouncementFailed;
+
+/// Servers discovered on the local network.
+///
+/// Insert to start listening for announcements and remove to stop.
+#[derive(Resource)]
+pub struct LanServers {
+    socket: UdpSocket,
+    servers: Vec<LanServer>,
+}
+
+impl LanServers {
+    pub fn new() -> Result<Self> {
+        info!("listening for server announcements on port {DISCOVERY_PORT}");
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, DISCOVERY_PORT))
+            .context("unable to listen for server announcements")?;
+        socket.set_nonblocking(true)?;
+
+        Ok(Self {
+            socket,
+            servers: Default::default(),
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LanServer> {
+        self.servers.iter()
+    }
+}
+
+pub struct LanServer {
+    pub ip: IpAddr,
+    pub port: u16,
+    pub world_name: String,
+    pub players: usize,
+    pub max_players: usize,
+    last_seen: Duration,
+}
+
+impl LanServer {
+    pub fn addr(&self) -> SocketAddr {
+        SocketAddr::new(self.ip, self.port)
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct Announcement {
+    protocol_id: u64,
+    world_name: String,
+    port: u16,
+    players: usize,
+    max_players: usize,
+}
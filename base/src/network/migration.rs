@@ -0,0 +1,316 @@
+use std::{mem, net::SocketAddr, time::Duration};
+
+use anyhow::{Context, Result};
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use bevy_replicon_renet::{
+    netcode::{NetcodeClientTransport, NetcodeServerTransport},
+    renet::{ConnectionConfig, RenetClient, RenetServer},
+    RenetChannelsExt,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{token_server::TokenServer, SessionPassword};
+use crate::{
+    core::GameState,
+    error_message::{error_message, ErrorMessage},
+    game_world::{self, actor::Actor, WorldName},
+};
+
+/// Hands the session over to one of the clients when the host leaves.
+///
+/// The host sends the world snapshot to the client with the lowest ID, which restarts as
+/// the new server on the same port. Other clients reconnect to it automatically.
+pub(crate) struct MigrationPlugin;
+
+impl Plugin for MigrationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SessionPassword>()
+            .add_server_trigger::<HostSnapshot>(ChannelKind::Ordered)
+            .add_server_trigger::<HostChange>(ChannelKind::Ordered)
+            .add_observer(hand_over)
+            .add_observer(take_over)
+            .add_observer(follow)
+            .add_systems(
+                Update,
+                (
+                    stop_server.run_if(resource_exists::<Handover>),
+                    resume
+                        .pipe(error_message)
+                        .run_if(resource_exists::<Migration>)
+                        .run_if(in_state(GameState::Menu)),
+                ),
+            );
+    }
+}
+
+/// How long the old host waits for clients to receive the handover.
+const HANDOVER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Delay before reconnecting to give the new host time to start the server.
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// Interval between attempts to start the server or reconnect to it.
+///
+/// The port could still be occupied if the old host runs on the same machine.
+const RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time after which the migration is abandoned.
+const MIGRATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn hand_over(
+    _trigger: Trigger<HostLeave>,
+    mut commands: Commands,
+    world: &World,
+    server: Res<RepliconServer>,
+    clients: Res<ConnectedClients>,
+    world_name: Res<WorldName>,
+    transport: Option<Res<NetcodeServerTransport>>,
+    actors: Query<Entity, With<Actor>>,
+) {
+    if !server.is_running() {
+        return;
+    }
+    let Some(transport) = transport else {
+        return;
+    };
+
+    commands.init_resource::<Handover>();
+
+    let Some(new_host) = clients
+        .iter()
+        .map(|client| client.id())
+        .min_by_key(|id| id.get())
+    else {
+        debug!("no clients to hand the session over");
+        return;
+    };
+    let Some(port) = transport.addresses().first().map(SocketAddr::port) else {
+        return;
+    };
+    let Some(mut ip) = transport.client_addr(new_host.get()).map(|addr| addr.ip()) else {
+        error!("unable to get address of `{new_host:?}`");
+        return;
+    };
+    if ip.is_loopback() {
+        // The new host runs on the same machine, use an address reachable from the network.
+        if let Some(local_ip) = super::local_ip() {
+            ip = local_ip;
+        }
+    }
+
+    info!("handing the session over to `{new_host:?}` at {ip}");
+    commands.server_trigger(ToClients {
+        mode: SendMode::Direct(new_host),
+        event: HostSnapshot {
+            world_name: world_name.0.clone(),
+            port,
+            scene: game_world::serialize_world(world, actors.iter()),
+        },
+    });
+    commands.server_trigger(ToClients {
+        mode: SendMode::BroadcastExcept(new_host),
+        event: HostChange {
+            addr: SocketAddr::new(ip, port),
+        },
+    });
+}
+
+/// Stops the server after all clients left or the timeout.
+///
+/// Keeps the server running after the host left to deliver the handover.
+fn stop_server(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    clients: Res<ConnectedClients>,
+    mut handover: ResMut<Handover>,
+) {
+    handover.elapsed += time.delta();
+    if !clients.is_empty() && handover.elapsed < HANDOVER_TIMEOUT {
+        return;
+    }
+
+    info!("stopping server after handover");
+    commands.remove_resource::<Handover>();
+    commands.remove_resource::<RenetServer>();
+    commands.remove_resource::<NetcodeServerTransport>();
+    commands.remove_resource::<TokenServer>();
+}
+
+fn take_over(
+    mut trigger: Trigger<HostSnapshot>,
+    mut commands: Commands,
+    server: Res<RepliconServer>,
+    transport: Option<ResMut<NetcodeClientTransport>>,
+) {
+    if server.is_running() {
+        return;
+    }
+
+    info!("becoming the new host");
+    let snapshot = trigger.event_mut();
+    leave_session(&mut commands, transport);
+    commands.insert_resource(Migration {
+        elapsed: Duration::ZERO,
+        attempts: 0,
+        kind: MigrationKind::Host {
+            world_name: mem::take(&mut snapshot.world_name),
+            port: snapshot.port,
+            scene: mem::take(&mut snapshot.scene),
+        },
+    });
+}
+
+fn follow(
+    trigger: Trigger<HostChange>,
+    mut commands: Commands,
+    server: Res<RepliconServer>,
+    transport: Option<ResMut<NetcodeClientTransport>>,
+) {
+    // Broadcast also triggers locally on the old host.
+    if server.is_running() {
+        return;
+    }
+
+    info!("host changed to {}", trigger.addr);
+    leave_session(&mut commands, transport);
+    commands.insert_resource(Migration {
+        elapsed: Duration::ZERO,
+        attempts: 0,
+        kind: MigrationKind::Client { addr: trigger.addr },
+    });
+}
+
+fn resume(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut scene_spawner: ResMut<SceneSpawner>,
+    mut scenes: ResMut<Assets<DynamicScene>>,
+    mut migration: ResMut<Migration>,
+    network_channels: Res<RepliconChannels>,
+    password: Res<SessionPassword>,
+    registry: Res<AppTypeRegistry>,
+) -> Result<()> {
+    let migration = &mut *migration;
+    migration.elapsed += time.delta();
+    let config = ConnectionConfig {
+        server_channels_config: network_channels.get_server_configs(),
+        client_channels_config: network_channels.get_client_configs(),
+        ..Default::default()
+    };
+
+    match &mut migration.kind {
+        MigrationKind::Host {
+            world_name,
+            port,
+            scene,
+        } => {
+            if migration.elapsed < RETRY_INTERVAL * migration.attempts {
+                return Ok(());
+            }
+            migration.attempts += 1;
+
+            let (transport, token_server) = match super::create_server(*port, &password.0) {
+                Ok(server) => server,
+                Err(e) if migration.elapsed < MIGRATION_TIMEOUT => {
+                    debug!("unable to start server, retrying: {e}");
+                    return Ok(());
+                }
+                Err(e) => {
+                    commands.remove_resource::<Migration>();
+                    return Err(e.context("unable to host the session"));
+                }
+            };
+
+            info!("hosting '{world_name}' after migration");
+            let scene = game_world::deserialize_world(scene, &registry.read());
+            let world_name = mem::take(world_name);
+            commands.remove_resource::<Migration>();
+            let scene = scene.context("unable to deserialize received world")?;
+
+            commands.insert_resource(RenetServer::new(config));
+            commands.insert_resource(transport);
+            commands.insert_resource(token_server);
+            commands.insert_resource(WorldName(world_name));
+            scene_spawner.spawn_dynamic(scenes.add(scene));
+            commands.set_state(GameState::InGame);
+        }
+        MigrationKind::Client { addr } => {
+            if migration.elapsed < RECONNECT_DELAY + RETRY_INTERVAL * migration.attempts {
+                return Ok(());
+            }
+            migration.attempts += 1;
+
+            info!("reconnecting to {addr}");
+            let transport = match super::create_client(addr.ip(), addr.port(), &password.0) {
+                Ok(transport) => transport,
+                Err(e) if migration.elapsed < RECONNECT_DELAY + MIGRATION_TIMEOUT => {
+                    debug!("unable to reconnect, retrying: {e}");
+                    return Ok(());
+                }
+                Err(e) => {
+                    commands.remove_resource::<Migration>();
+                    return Err(e.context("unable to reconnect to the new host"));
+                }
+            };
+
+            commands.remove_resource::<Migration>();
+            commands.insert_resource(RenetClient::new(config));
+            commands.insert_resource(transport);
+        }
+    }
+
+    Ok(())
+}
+
+/// Disconnects from the current server without reporting it as an error.
+fn leave_session(commands: &mut Commands, transport: Option<ResMut<NetcodeClientTransport>>) {
+    if let Some(mut transport) = transport {
+        transport.disconnect();
+    }
+    commands.remove_resource::<RenetClient>();
+    commands.remove_resource::<NetcodeClientTransport>();
+    commands.set_state(GameState::Menu);
+}
+
+/// Triggered on the host before leaving the game to hand the session over.
+#[derive(Event)]
+pub struct HostLeave;
+
+#[derive(Default, Resource)]
+struct Handover {
+    elapsed: Duration,
+}
+
+/// Pending restoration of the session after the host left.
+#[derive(Resource)]
+struct Migration {
+    elapsed: Duration,
+    attempts: u32,
+    kind: MigrationKind,
+}
+
+enum MigrationKind {
+    Host {
+        world_name: String,
+        port: u16,
+        scene: Vec<u8>,
+    },
+    Client {
+        addr: SocketAddr,
+    },
+}
+
+/// World snapshot sent to the client that becomes the new host.
+#[derive(Deserialize, Event, Serialize)]
+struct HostSnapshot {
+    world_name: String,
+    port: u16,
+    scene: Vec<u8>,
+}
+
+/// Address of the new host sent to the rest of the clients.
+#[derive(Deserialize, Event, Serialize)]
+struct HostChange {
+    addr: SocketAddr,
+}
@@ -0,0 +1,265 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use bevy::{prelude::*, scene::ron};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::GameState,
+    error_message::error_message,
+    game_paths::{write_atomic, GamePaths},
+    game_world::WorldName,
+    notification::Notification,
+    settings::Settings,
+};
+
+/// Records network messages on server to reproduce bugs.
+///
+/// Messages are captured on the transport level, so all triggers and events are covered
+/// without registering them individually. A dump can be replayed into a fresh headless world
+/// with [`Replay`].
+pub(crate) struct ReplayLogPlugin;
+
+impl Plugin for ReplayLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayLog>()
+            .add_observer(dump.pipe(error_message))
+            .add_systems(OnEnter(GameState::InGame), reset)
+            .add_systems(
+                PreUpdate,
+                (
+                    replay.run_if(resource_exists::<Replay>.and(in_state(GameState::InGame))),
+                    record_received.run_if(server_running.and(recording_enabled)),
+                )
+                    .chain()
+                    .after(ServerSet::ReceivePackets)
+                    .before(ServerSet::Receive),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    record_sent.run_if(server_running.and(recording_enabled)),
+                    discard_sent.run_if(resource_exists::<Replay>),
+                )
+                    .chain()
+                    .after(ServerSet::Send)
+                    .before(ServerSet::SendPackets),
+            );
+    }
+}
+
+/// Maximum number of stored messages in each direction.
+const MAX_MESSAGES: usize = 10_000;
+
+fn recording_enabled(settings: Res<Settings>) -> bool {
+    settings.developer.network_recording
+}
+
+fn reset(mut log: ResMut<ReplayLog>) {
+    *log = Default::default();
+}
+
+fn record_received(
+    mut server: ResMut<RepliconServer>,
+    mut log: ResMut<ReplayLog>,
+    channels: Res<RepliconChannels>,
+    tick: Res<RepliconTick>,
+) {
+    for channel_id in 0..channels.client_channels().len() as u8 {
+        let messages: Vec<_> = server.receive(channel_id).collect();
+        for (client_id, message) in messages {
+            log.push_received(LoggedMessage {
+                tick: tick.get(),
+                client_id,
+                channel_id,
+                message: message.to_vec(),
+            });
+            server.insert_received(client_id, channel_id, message);
+        }
+    }
+}
+
+fn record_sent(
+    mut server: ResMut<RepliconServer>,
+    mut log: ResMut<ReplayLog>,
+    tick: Res<RepliconTick>,
+) {
+    let messages: Vec<_> = server.drain_sent().collect();
+    for (client_id, channel_id, message) in messages {
+        log.push_sent(LoggedMessage {
+            tick: tick.get(),
+            client_id,
+            channel_id,
+            message: message.to_vec(),
+        });
+        server.send(client_id, channel_id, message);
+    }
+}
+
+fn dump(
+    _trigger: Trigger<ReplayLogDump>,
+    mut commands: Commands,
+    log: Res<ReplayLog>,
+    game_paths: Res<GamePaths>,
+    world_name: Option<Res<WorldName>>,
+) -> Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let path = game_paths.logs.join(format!("replay-{timestamp}.ron"));
+    info!(
+        "dumping {} received and {} sent messages to {path:?}",
+        log.received.len(),
+        log.sent.len()
+    );
+
+    let replay = Replay {
+        world_name: world_name.map(|name| name.0.clone()).unwrap_or_default(),
+        log: log.clone(),
+        ticks: None,
+    };
+    let content = ron::ser::to_string(&replay).context("unable to serialize network messages")?;
+    write_atomic(&path, content).with_context(|| format!("unable to write {path:?}"))?;
+
+    commands.trigger(Notification::info(format!(
+        "Network messages saved to {}",
+        path.display()
+    )));
+
+    Ok(())
+}
+
+/// Feeds recorded client messages into the server at the same ticks relative to the start.
+///
+/// Clients from the dump are connected on the first run, but there is no real transport.
+fn replay(
+    mut commands: Commands,
+    mut server_events: EventWriter<ServerEvent>,
+    mut server: ResMut<RepliconServer>,
+    mut replay: ResMut<Replay>,
+    tick: Res<RepliconTick>,
+) {
+    if replay.log.received.is_empty() {
+        return;
+    }
+
+    let (first_tick, start_tick) = match replay.ticks {
+        Some(ticks) => ticks,
+        None => {
+            info!(
+                "replaying {} messages into '{}'",
+                replay.log.received.len(),
+                replay.world_name
+            );
+            if replay.log.truncated {
+                warn!("older messages were dropped during recording, the replay may diverge");
+            }
+
+            server.set_running(true);
+            let mut client_ids: Vec<_> = replay
+                .log
+                .received
+                .iter()
+                .map(|message| message.client_id)
+                .collect();
+            client_ids.sort_by_key(|client_id| client_id.get());
+            client_ids.dedup();
+            for client_id in client_ids {
+                server_events.send(ServerEvent::ClientConnected { client_id });
+            }
+
+            let first_tick = replay.log.received[0].tick;
+            replay.ticks = Some((first_tick, tick.get()));
+            (first_tick, tick.get())
+        }
+    };
+
+    while let Some(message) = replay.log.received.front() {
+        if message.tick - first_tick > tick.get() - start_tick {
+            break;
+        }
+
+        let message = replay.log.received.pop_front().unwrap();
+        server.insert_received(message.client_id, message.channel_id, message.message);
+    }
+
+    if replay.log.received.is_empty() {
+        info!("replay finished");
+        commands.trigger(Notification::info("Replay finished"));
+    }
+}
+
+/// Drops messages to replayed clients since they have no transport.
+fn discard_sent(mut server: ResMut<RepliconServer>) {
+    server.drain_sent().for_each(drop);
+}
+
+/// Ring buffer with network messages of the current session.
+///
+/// Cleared on entering the game, so the dump can be replayed from the world save.
+#[derive(Resource, Clone, Default, Deserialize, Serialize)]
+struct ReplayLog {
+    received: VecDeque<LoggedMessage>,
+    sent: VecDeque<LoggedMessage>,
+    /// Set if older received messages were dropped.
+    truncated: bool,
+}
+
+impl ReplayLog {
+    fn push_received(&mut self, message: LoggedMessage) {
+        if self.received.len() == MAX_MESSAGES {
+            self.received.pop_front();
+            self.truncated = true;
+        }
+        self.received.push_back(message);
+    }
+
+    fn push_sent(&mut self, message: LoggedMessage) {
+        if self.sent.len() == MAX_MESSAGES {
+            self.sent.pop_front();
+        }
+        self.sent.push_back(message);
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+struct LoggedMessage {
+    tick: u32,
+    client_id: ClientId,
+    channel_id: u8,
+    message: Vec<u8>,
+}
+
+/// Writes recorded network messages into [`GamePaths::logs`].
+#[derive(Event)]
+pub struct ReplayLogDump;
+
+/// Dump of network messages that is replayed while present.
+#[derive(Resource, Deserialize, Serialize)]
+pub struct Replay {
+    world_name: String,
+    log: ReplayLog,
+    /// First recorded tick and the tick at which the replay started.
+    #[serde(skip)]
+    ticks: Option<(u32, u32)>,
+}
+
+impl Replay {
+    pub fn read(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("unable to read {path:?}"))?;
+        ron::from_str(&content).with_context(|| format!("unable to parse {path:?}"))
+    }
+
+    /// Name of the world in which messages were recorded.
+    pub fn world_name(&self) -> &str {
+        &self.world_name
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{bail, Context, Result};
+use bevy::prelude::*;
+use bevy_replicon_renet::netcode::ConnectToken;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::PROTOCOL_ID;
+
+/// Hands out connect tokens to clients that know the session password.
+///
+/// Tokens are signed with a key that is generated for each session and never leaves the server,
+/// so clients can't create them on their own. The password is never sent: the client proves
+/// that it knows it by hashing it with a random challenge from the server.
+pub(crate) struct TokenServerPlugin;
+
+impl Plugin for TokenServerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, serve.run_if(resource_exists::<TokenServer>));
+    }
+}
+
+/// How long a generated connect token is valid, in seconds.
+const TOKEN_EXPIRE_SECS: u64 = 30;
+
+/// Time without packets after which the connection is closed, in seconds.
+const TIMEOUT_SECS: i32 = 15;
+
+/// Time after which an unfinished token request is dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+const CHALLENGE_BYTES: usize = 32;
+const PROOF_BYTES: usize = 32;
+
+/// Protocol ID followed by the password proof.
+const REQUEST_BYTES: usize = size_of::<u64>() + PROOF_BYTES;
+
+fn serve(time: Res<Time<Real>>, mut token_server: ResMut<TokenServer>) {
+    let now = time.elapsed();
+    let token_server = &mut *token_server;
+    loop {
+        let (stream, addr) = match token_server.listener.accept() {
+            Ok(accepted) => accepted,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+            Err(e) => {
+                error!("unable to accept token request: {e}");
+                break;
+            }
+        };
+
+        match PendingRequest::new(stream, addr, now) {
+            Ok(request) => {
+                debug!("received token request from {addr}");
+                token_server.requests.push(request);
+            }
+            Err(e) => debug!("unable to send challenge to {addr}: {e}"),
+        }
+    }
+
+    token_server.requests.retain_mut(|request| {
+        if now - request.received > REQUEST_TIMEOUT {
+            debug!("dropping timed out token request from {}", request.addr);
+            return false;
+        }
+
+        match request.read() {
+            Ok(false) => true,
+            Ok(true) => {
+                if let Err(e) = token_server.issuer.reply(request) {
+                    error!(
+                        "unable to reply to token request from {}: {e:#}",
+                        request.addr
+                    );
+                }
+                false
+            }
+            Err(e) => {
+                debug!("dropping token request from {}: {e}", request.addr);
+                false
+            }
+        }
+    });
+}
+
+/// Requests a connect token from the [`TokenServer`] at the specified address.
+pub(super) fn request_token(addr: SocketAddr, password: &str) -> Result<ConnectToken> {
+    debug!("requesting connect token from {addr}");
+    let mut stream =
+        TcpStream::connect_timeout(&addr, REQUEST_TIMEOUT).context("the server is unreachable")?;
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+
+    let mut challenge = [0; CHALLENGE_BYTES];
+    stream
+        .read_exact(&mut challenge)
+        .context("unable to receive challenge")?;
+
+    let mut request = Vec::with_capacity(REQUEST_BYTES);
+    request.extend_from_slice(&PROTOCOL_ID.to_le_bytes());
+    request.extend_from_slice(&password_proof(&challenge, password));
+    stream
+        .write_all(&request)
+        .context("unable to send token request")?;
+
+    let response = bincode::deserialize_from(&mut stream).context("unable to receive token")?;
+    match response {
+        TokenResponse::Accepted(bytes) => {
+            let token = ConnectToken::read(&mut bytes.as_slice())?;
+            Ok(token)
+        }
+        TokenResponse::IncorrectPassword => bail!("the password is incorrect"),
+        TokenResponse::ProtocolMismatch => {
+            bail!("the server runs an incompatible version of the game")
+        }
+    }
+}
+
+/// Hashes the password with the challenge, so the proof can't be reused for another request.
+fn password_proof(challenge: &[u8; CHALLENGE_BYTES], password: &str) -> [u8; PROOF_BYTES] {
+    let mut hasher = Sha256::new();
+    hasher.update(PROTOCOL_ID.to_le_bytes());
+    hasher.update(challenge);
+    hasher.update(password.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Accepts token requests over TCP on the same port as the server transport.
+///
+/// Should be removed together with the server transport.
+#[derive(Resource)]
+pub struct TokenServer {
+    listener: TcpListener,
+    issuer: TokenIssuer,
+    requests: Vec<PendingRequest>,
+}
+
+impl TokenServer {
+    pub(super) fn new(port: u16, private_key: [u8; 32], password: String) -> Result<Self> {
+        info!("creating token server for port {port}");
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        listener.set_nonblocking(true)?;
+
+        Ok(Self {
+            listener,
+            issuer: TokenIssuer {
+                port,
+                private_key,
+                password,
+            },
+            requests: Default::default(),
+        })
+    }
+}
+
+struct TokenIssuer {
+    port: u16,
+    private_key: [u8; 32],
+    password: String,
+}
+
+impl TokenIssuer {
+    fn reply(&self, request: &mut PendingRequest) -> Result<()> {
+        let (protocol_id, proof) = request.buffer.split_at(size_of::<u64>());
+        let protocol_id = u64::from_le_bytes(
+            protocol_id
+                .try_into()
+                .expect("request should start with the protocol ID"),
+        );
+        let response = if protocol_id != PROTOCOL_ID {
+            debug!(
+                "rejecting token request from {} with different protocol",
+                request.addr
+            );
+            TokenResponse::ProtocolMismatch
+        } else if proof != password_proof(&request.challenge, &self.password) {
+            warn!(
+                "rejecting token request from {} with incorrect password",
+                request.addr
+            );
+            TokenResponse::IncorrectPassword
+        } else {
+            let client_id = rand::thread_rng().gen_range(1..u64::MAX);
+            // Issue the token for the address by which the client reached the server.
+            let server_addr = SocketAddr::new(request.stream.local_addr()?.ip(), self.port);
+            let current_time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
+            let token = ConnectToken::generate(
+                current_time,
+                PROTOCOL_ID,
+                TOKEN_EXPIRE_SECS,
+                client_id,
+                TIMEOUT_SECS,
+                vec![server_addr],
+                None,
+                &self.private_key,
+            )?;
+            let mut bytes = Vec::new();
+            token.write(&mut bytes)?;
+
+            info!(
+                "issuing connect token for client {client_id} to {}",
+                request.addr
+            );
+            TokenResponse::Accepted(bytes)
+        };
+
+        // Response fits into the socket buffer, so it's written at once.
+        bincode::serialize_into(&mut request.stream, &response)?;
+
+        Ok(())
+    }
+}
+
+/// Client that received a challenge and hasn't sent the full request yet.
+struct PendingRequest {
+    stream: TcpStream,
+    addr: SocketAddr,
+    challenge: [u8; CHALLENGE_BYTES],
+    buffer: Vec<u8>,
+    received: Duration,
+}
+
+impl PendingRequest {
+    fn new(mut stream: TcpStream, addr: SocketAddr, received: Duration) -> io::Result<Self> {
+        let challenge = rand::random();
+        stream.write_all(&challenge)?;
+        stream.set_nonblocking(true)?;
+
+        Ok(Self {
+            stream,
+            addr,
+            challenge,
+            buffer: Vec::with_capacity(REQUEST_BYTES),
+            received,
+        })
+    }
+
+    /// Reads the available part of the request.
+    ///
+    /// Returns `true` if the request is complete.
+    fn read(&mut self) -> io::Result<bool> {
+        let mut bytes = [0; REQUEST_BYTES];
+        let remaining = REQUEST_BYTES - self.buffer.len();
+        match self.stream.read(&mut bytes[..remaining]) {
+            Ok(0) => Err(ErrorKind::UnexpectedEof.into()),
+            Ok(len) => {
+                self.buffer.extend_from_slice(&bytes[..len]);
+                Ok(self.buffer.len() == REQUEST_BYTES)
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+enum TokenResponse {
+    /// Serialized [`ConnectToken`].
+    Accepted(Vec<u8>),
+    IncorrectPassword,
+    ProtocolMismatch,
+}
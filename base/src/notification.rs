@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+/// Short message displayed to the player for a few seconds.
+///
+/// Should be sent via [`Commands::trigger`].
+#[derive(Event, Clone)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub message: String,
+}
+
+impl Notification {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            kind: NotificationKind::Info,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            kind: NotificationKind::Warning,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            kind: NotificationKind::Error,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotificationKind {
+    Info,
+    Warning,
+    Error,
+}
@@ -0,0 +1,64 @@
+use bevy::{
+    diagnostic::{
+        Diagnostic, DiagnosticPath, Diagnostics, FrameTimeDiagnosticsPlugin, RegisterDiagnostic,
+    },
+    prelude::*,
+    utils::Instant,
+};
+use bevy_replicon::prelude::*;
+
+/// Registers timings of heavy systems for the in-game profiler.
+///
+/// Systems are already traced by Bevy when the `trace` feature is enabled,
+/// these diagnostics are always available and also cover work done in background tasks.
+pub(super) struct ProfilingPlugin;
+
+impl Plugin for ProfilingPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+
+        app.init_resource::<SendStart>()
+            .register_diagnostic(Diagnostic::new(WALL_MESHING).with_suffix(" ms"))
+            .register_diagnostic(Diagnostic::new(PREVIEW_RENDERING).with_suffix(" ms"))
+            .register_diagnostic(Diagnostic::new(REPLICATION_SEND).with_suffix(" ms"))
+            .add_systems(
+                PostUpdate,
+                (
+                    start_send.before(ServerSet::Send),
+                    finish_send.after(ServerSet::Send),
+                )
+                    .run_if(server_running),
+            );
+    }
+}
+
+/// Time to generate a single wall mesh.
+pub const WALL_MESHING: DiagnosticPath = DiagnosticPath::const_new("profiling/wall_meshing");
+
+/// Time between a preview request and the rendered image.
+pub const PREVIEW_RENDERING: DiagnosticPath =
+    DiagnosticPath::const_new("profiling/preview_rendering");
+
+/// Time spent on collecting and sending replication messages.
+pub const REPLICATION_SEND: DiagnosticPath =
+    DiagnosticPath::const_new("profiling/replication_send");
+
+/// Records time elapsed since `start` in milliseconds.
+pub fn measure(diagnostics: &mut Diagnostics, path: &DiagnosticPath, start: Instant) {
+    diagnostics.add_measurement(path, || start.elapsed().as_secs_f64() * 1000.0);
+}
+
+fn start_send(mut send_start: ResMut<SendStart>) {
+    **send_start = Some(Instant::now());
+}
+
+fn finish_send(mut diagnostics: Diagnostics, mut send_start: ResMut<SendStart>) {
+    if let Some(start) = send_start.take() {
+        measure(&mut diagnostics, &REPLICATION_SEND, start);
+    }
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct SendStart(Option<Instant>);
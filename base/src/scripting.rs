@@ -0,0 +1,370 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use bevy::{
+    asset::{io::Reader, AssetLoader, AssetPath, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+use rhai::{
+    module_resolvers::DummyModuleResolver, Array, CallFnOptions, Dynamic, Engine, EvalAltResult,
+    Scope, AST,
+};
+
+use crate::{
+    asset::{
+        self,
+        manifest::{MapPaths, ReflectMapPaths},
+    },
+    game_world::actor::outfit::OutfitKind,
+};
+
+/// Loads and runs object scripts written in Rhai.
+///
+/// A script defines `interactions()` that returns names of available interactions
+/// and `perform(interaction)` that describes the result using methods on `this`:
+///
+/// - `change_need(name, delta)` changes the actor need with the specified name, up to [`MAX_NEED_DELTA`].
+/// - `play_animation(path)` plays the animation clip on the actor.
+/// - `spawn_effect(path, duration)` spawns the scene on the object for the specified number of seconds,
+///   up to [`MAX_EFFECT_DURATION`].
+/// - `change_outfit(kind)` dresses the actor in the outfit (`"everyday"` or `"sleep"`) until the task finishes.
+///
+/// Paths are relative to the script. Scripts can't import modules, have no access to anything else
+/// and executed only on server.
+pub(super) struct ScriptingPlugin;
+
+impl Plugin for ScriptingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ObjectScript>()
+            .init_asset::<Script>()
+            .init_asset_loader::<ScriptLoader>()
+            .init_resource::<ScriptEngine>()
+            .add_observer(load);
+    }
+}
+
+fn load(
+    trigger: Trigger<OnInsert, ObjectScript>,
+    asset_server: Res<AssetServer>,
+    mut objects: Query<&mut ObjectScript>,
+) {
+    let mut object_script = objects.get_mut(trigger.entity()).unwrap();
+    debug!(
+        "loading script '{}' for `{}`",
+        object_script.path,
+        trigger.entity()
+    );
+    object_script.handle = asset_server.load(object_script.path.clone());
+}
+
+/// Maximum number of operations for a single function call.
+///
+/// Prevents scripts from freezing the server with infinite loops.
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// Maximum change of a need for a single `change_need` call.
+const MAX_NEED_DELTA: f32 = 50.0;
+
+/// Maximum lifetime of a scene spawned by `spawn_effect` in seconds.
+const MAX_EFFECT_DURATION: f32 = 60.0;
+
+fn create_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(16)
+        .set_max_string_size(1024)
+        .set_max_array_size(256)
+        .set_max_map_size(256)
+        .set_module_resolver(DummyModuleResolver::new())
+        .on_print(|text| info!("script: {text}"))
+        .on_debug(|text, _, pos| debug!("script at {pos}: {text}"));
+
+    engine
+        .register_type_with_name::<ScriptContext>("Context")
+        .register_fn("change_need", ScriptContext::change_need)
+        .register_fn("play_animation", ScriptContext::play_animation)
+        .register_fn("spawn_effect", ScriptContext::spawn_effect)
+        .register_fn("change_outfit", ScriptContext::change_outfit);
+
+    engine
+}
+
+#[derive(Resource, Deref)]
+pub(crate) struct ScriptEngine(Engine);
+
+impl Default for ScriptEngine {
+    fn default() -> Self {
+        Self(create_engine())
+    }
+}
+
+impl ScriptEngine {
+    /// Runs `perform` from the script and returns requested commands.
+    pub(crate) fn perform(&self, script: &Script, interaction: &str) -> Result<Vec<ScriptCommand>> {
+        let mut context = Dynamic::from(ScriptContext::default());
+        let options = CallFnOptions::new().bind_this_ptr(&mut context);
+        self.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            &script.ast,
+            "perform",
+            (interaction.to_string(),),
+        )
+        .with_context(|| format!("unable to perform '{interaction}'"))?;
+
+        let mut commands = context.cast::<ScriptContext>().0;
+        for command in &mut commands {
+            match command {
+                ScriptCommand::PlayAnimation(path) => {
+                    asset::change_parent_dir(path, &script.dir);
+                }
+                ScriptCommand::SpawnEffect { scene, .. } => {
+                    asset::change_parent_dir(scene, &script.dir);
+                }
+                ScriptCommand::ChangeNeed { .. } | ScriptCommand::ChangeOutfit(_) => (),
+            }
+        }
+
+        Ok(commands)
+    }
+}
+
+/// Available to scripts as `this` inside `perform`.
+#[derive(Clone, Default)]
+struct ScriptContext(Vec<ScriptCommand>);
+
+impl ScriptContext {
+    fn change_need(&mut self, name: &str, delta: f64) -> Result<(), Box<EvalAltResult>> {
+        if !delta.is_finite() {
+            return Err(format!("'{delta}' is not a valid need delta").into());
+        }
+        self.0.push(ScriptCommand::ChangeNeed {
+            name: name.to_string(),
+            delta: (delta as f32).clamp(-MAX_NEED_DELTA, MAX_NEED_DELTA),
+        });
+        Ok(())
+    }
+
+    fn play_animation(&mut self, path: &str) {
+        self.0
+            .push(ScriptCommand::PlayAnimation(path.to_string().into()));
+    }
+
+    fn spawn_effect(&mut self, path: &str, duration: f64) -> Result<(), Box<EvalAltResult>> {
+        if !duration.is_finite() || duration <= 0.0 {
+            return Err(format!("'{duration}' is not a valid effect duration").into());
+        }
+        self.0.push(ScriptCommand::SpawnEffect {
+            scene: path.to_string().into(),
+            duration: (duration as f32).min(MAX_EFFECT_DURATION),
+        });
+        Ok(())
+    }
+
+    fn change_outfit(&mut self, kind: &str) -> Result<(), Box<EvalAltResult>> {
+        let kind = kind
+            .parse::<OutfitKind>()
+            .map_err(|_| format!("'{kind}' is not a valid outfit"))?;
+        self.0.push(ScriptCommand::ChangeOutfit(kind));
+        Ok(())
+    }
+}
+
+/// Action requested by a script.
+#[derive(Clone)]
+pub(crate) enum ScriptCommand {
+    ChangeNeed {
+        name: String,
+        delta: f32,
+    },
+    PlayAnimation(AssetPath<'static>),
+    SpawnEffect {
+        scene: AssetPath<'static>,
+        duration: f32,
+    },
+    ChangeOutfit(OutfitKind),
+}
+
+struct ScriptLoader(Engine);
+
+impl Default for ScriptLoader {
+    fn default() -> Self {
+        Self(create_engine())
+    }
+}
+
+impl AssetLoader for ScriptLoader {
+    type Asset = Script;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut string = String::new();
+        reader.read_to_string(&mut string).await?;
+
+        let ast = self.0.compile(&string)?;
+        let interactions: Array = self
+            .0
+            .call_fn(&mut Scope::new(), &ast, "interactions", ())
+            .context("unable to get interactions")?;
+        let interactions = interactions
+            .into_iter()
+            .map(Dynamic::into_string)
+            .collect::<Result<_, _>>()
+            .map_err(|type_name| anyhow!("interactions should be strings, got `{type_name}`"))?;
+
+        Ok(Script {
+            ast,
+            interactions,
+            dir: load_context
+                .path()
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rhai"]
+    }
+}
+
+#[derive(Asset, TypePath)]
+pub(crate) struct Script {
+    ast: AST,
+    pub(crate) interactions: Vec<String>,
+
+    /// Directory of the script to resolve relative paths.
+    dir: PathBuf,
+}
+
+/// Attaches a script with custom interactions to an object.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, MapPaths)]
+pub(crate) struct ObjectScript {
+    path: AssetPath<'static>,
+
+    #[reflect(ignore)]
+    pub(crate) handle: Handle<Script>,
+}
+
+impl MapPaths for ObjectScript {
+    fn map_paths(&mut self, dir: &Path) {
+        asset::change_parent_dir(&mut self.path, dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import() -> Result<()> {
+        let engine = create_engine();
+        let ast = engine.compile(
+            r#"
+            import "other" as other;
+            fn interactions() { [] }
+            "#,
+        )?;
+
+        let result = engine.call_fn::<Array>(&mut Scope::new(), &ast, "interactions", ());
+        assert!(
+            matches!(
+                result.map_err(|e| *e),
+                Err(EvalAltResult::ErrorModuleNotFound(..))
+            ),
+            "imports should be rejected"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn operations_limit() -> Result<()> {
+        let engine = create_engine();
+        let ast = engine.compile("fn interactions() { loop {} }")?;
+
+        let result = engine.call_fn::<Array>(&mut Scope::new(), &ast, "interactions", ());
+        assert!(
+            matches!(
+                result.map_err(|e| *e),
+                Err(EvalAltResult::ErrorTooManyOperations(_))
+            ),
+            "infinite loops should be interrupted"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn need_delta() -> Result<()> {
+        let engine = ScriptEngine::default();
+        let script = Script {
+            ast: engine.compile(
+                r#"
+                fn perform(interaction) {
+                    this.change_need("Hunger", 1000.0);
+                    this.change_need("Fun", -1000.0);
+                }
+                "#,
+            )?,
+            interactions: Vec::new(),
+            dir: PathBuf::new(),
+        };
+
+        let commands = engine.perform(&script, "Eat")?;
+        let deltas: Vec<_> = commands
+            .into_iter()
+            .filter_map(|command| match command {
+                ScriptCommand::ChangeNeed { delta, .. } => Some(delta),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(deltas, [MAX_NEED_DELTA, -MAX_NEED_DELTA]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn effect_duration() -> Result<()> {
+        let engine = ScriptEngine::default();
+        let script = Script {
+            ast: engine.compile(
+                r#"
+                fn perform(interaction) {
+                    if interaction == "Long" {
+                        this.spawn_effect("effect.gltf", 1000.0);
+                    } else {
+                        this.spawn_effect("effect.gltf", -1.0);
+                    }
+                }
+                "#,
+            )?,
+            interactions: Vec::new(),
+            dir: PathBuf::new(),
+        };
+
+        let commands = engine.perform(&script, "Long")?;
+        let durations: Vec<_> = commands
+            .into_iter()
+            .filter_map(|command| match command {
+                ScriptCommand::SpawnEffect { duration, .. } => Some(duration),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(durations, [MAX_EFFECT_DURATION]);
+        assert!(
+            engine.perform(&script, "Negative").is_err(),
+            "negative duration should be rejected"
+        );
+
+        Ok(())
+    }
+}
@@ -3,14 +3,24 @@ use std::{fs, path::Path};
 use anyhow::{Context, Result};
 use avian3d::prelude::*;
 use bevy::{
-    color::palettes::css::DARK_RED, pbr::wireframe::WireframeConfig, prelude::*, scene::ron,
-    window::WindowMode,
+    audio::Volume,
+    color::palettes::css::DARK_RED,
+    log::Level,
+    pbr::wireframe::WireframeConfig,
+    prelude::*,
+    scene::ron,
+    window::{PresentMode, WindowMode},
 };
 use bevy_enhanced_input::prelude::*;
 use serde::{Deserialize, Serialize};
+use strum::EnumIter;
 use vleue_navigator::prelude::*;
 
-use super::{error_message::error_message, game_paths::GamePaths};
+use super::{
+    error_message::error_message,
+    game_paths::{backup_corrupted, write_atomic, GamePaths},
+    tutorial::TutorialStep,
+};
 
 pub(super) struct SettingsPlugin;
 
@@ -25,18 +35,24 @@ fn load(
     mut commands: Commands,
     mut config_store: ResMut<GizmoConfigStore>,
     mut wireframe_config: ResMut<WireframeConfig>,
+    mut global_volume: ResMut<GlobalVolume>,
     game_paths: Res<GamePaths>,
-    mut window: Single<&mut Window>,
+    window: Option<Single<&mut Window>>,
 ) {
     info!("loading settings");
 
-    let settings = Settings::read(&game_paths.settings).unwrap_or_default();
+    let settings = Settings::read(&game_paths.settings).unwrap_or_else(|e| {
+        error!("{e:#}");
+        Default::default()
+    });
 
+    let mut window = window.map(Single::into_inner);
     apply_settings(
         &mut commands,
         &mut config_store,
         &mut wireframe_config,
-        &mut window,
+        &mut global_volume,
+        window.as_deref_mut(),
         &settings,
     );
 
@@ -48,17 +64,20 @@ fn apply(
     mut commands: Commands,
     mut config_store: ResMut<GizmoConfigStore>,
     mut wireframe_config: ResMut<WireframeConfig>,
+    mut global_volume: ResMut<GlobalVolume>,
     settings: Res<Settings>,
     game_paths: Res<GamePaths>,
-    mut window: Single<&mut Window>,
+    window: Option<Single<&mut Window>>,
 ) -> Result<()> {
     info!("applying settings");
 
+    let mut window = window.map(Single::into_inner);
     apply_settings(
         &mut commands,
         &mut config_store,
         &mut wireframe_config,
-        &mut window,
+        &mut global_volume,
+        window.as_deref_mut(),
         &settings,
     );
 
@@ -69,17 +88,37 @@ fn apply_settings(
     commands: &mut Commands,
     config_store: &mut GizmoConfigStore,
     wireframe_config: &mut WireframeConfig,
-    window: &mut Window,
+    global_volume: &mut GlobalVolume,
+    window: Option<&mut Window>,
     settings: &Settings,
 ) {
-    if settings.video.fullscreen {
-        window.mode = WindowMode::Fullscreen(MonitorSelection::Current);
-    } else {
-        window.mode = WindowMode::Windowed;
+    // Window is missing in headless mode.
+    if let Some(window) = window {
+        let video = &settings.video;
+        window.mode = match video.display_mode {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+            DisplayMode::Fullscreen => WindowMode::SizedFullscreen(MonitorSelection::Current),
+        };
+        window
+            .resolution
+            .set(video.resolution.x as f32, video.resolution.y as f32);
+        window.present_mode = if video.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        };
     }
 
+    commands.insert_resource(UiScale(settings.accessibility.ui_scale));
+
+    global_volume.volume = Volume::new(settings.audio.master);
     wireframe_config.global = settings.developer.wireframe;
     config_store.config_mut::<PhysicsGizmos>().0.enabled = settings.developer.colliders;
+    // Disabled backend requires markers that are never inserted.
+    commands.insert_resource(PhysicsPickingSettings {
+        require_markers: settings.developer.picking_backend != PickingBackend::Physics,
+    });
     if settings.developer.nav_mesh {
         commands.insert_resource(NavMeshesDebug(DARK_RED.into()))
     } else {
@@ -97,21 +136,33 @@ pub struct SettingsApply;
 #[serde(default)]
 pub struct Settings {
     pub video: VideoSettings,
+    pub audio: AudioSettings,
+    pub camera: CameraSettings,
+    pub gameplay: GameplaySettings,
+    pub accessibility: AccessibilitySettings,
     pub keyboard: KeyboardSettings,
+    pub gamepad: GamepadSettings,
+    pub mods: ModSettings,
     pub developer: DeveloperSettings,
 }
 
 impl Settings {
     /// Creates [`Settings`] from the application settings file.
     /// Will be initialed with defaults if the file does not exist.
+    /// A file that fails to parse is moved aside to keep it from being overwritten.
     fn read(file_name: &Path) -> Result<Settings> {
         info!("reading settings from {file_name:?}");
 
-        match fs::read_to_string(file_name) {
-            Ok(content) => ron::from_str::<Settings>(&content)
-                .with_context(|| format!("unable to read settings from {file_name:?}")),
-            Err(_) => Ok(Settings::default()),
-        }
+        let Ok(content) = fs::read_to_string(file_name) else {
+            return Ok(Settings::default());
+        };
+
+        ron::from_str::<Settings>(&content).or_else(|e| {
+            let backup_path = backup_corrupted(file_name)?;
+            Err(e).with_context(|| {
+                format!("unable to read settings from {file_name:?}, moved to {backup_path:?}")
+            })
+        })
     }
 
     /// Saves settings on disk under.
@@ -130,16 +181,336 @@ impl Settings {
         fs::create_dir_all(parent_folder)
             .with_context(|| format!("unable to create {parent_folder:?}"))?;
 
-        fs::write(file_name, content)
+        write_atomic(file_name, content)
             .with_context(|| format!("unable to write settings to {file_name:?}"))
     }
 }
 
-#[derive(Clone, Default, Deserialize, Reflect, Serialize)]
+#[derive(Clone, Deserialize, Reflect, Serialize)]
 #[serde(default)]
 pub struct VideoSettings {
-    /// TODO: Replace with combobox for all window modes.
-    pub fullscreen: bool,
+    pub display_mode: DisplayMode,
+    /// Window size in physical pixels.
+    ///
+    /// Ignored in borderless mode, which always matches the monitor.
+    pub resolution: UVec2,
+    pub vsync: bool,
+    pub quality: GraphicsQuality,
+    pub photo_supersampling: Supersampling,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            display_mode: Default::default(),
+            resolution: UVec2::new(1280, 720),
+            vsync: true,
+            quality: Default::default(),
+            photo_supersampling: Default::default(),
+        }
+    }
+}
+
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize,
+)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub fn text(self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "Windowed",
+            DisplayMode::Borderless => "Borderless",
+            DisplayMode::Fullscreen => "Fullscreen",
+        }
+    }
+}
+
+/// Preset for shadows and post-processing on game cameras.
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize,
+)]
+pub enum GraphicsQuality {
+    Low,
+    Medium,
+    #[default]
+    High,
+    Ultra,
+}
+
+impl GraphicsQuality {
+    pub fn text(self) -> &'static str {
+        match self {
+            GraphicsQuality::Low => "Low",
+            GraphicsQuality::Medium => "Medium",
+            GraphicsQuality::High => "High",
+            GraphicsQuality::Ultra => "Ultra",
+        }
+    }
+}
+
+/// Resolution multiplier for photos, which are downsampled back to the window size.
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize,
+)]
+pub enum Supersampling {
+    #[default]
+    Off,
+    X2,
+    X4,
+}
+
+impl Supersampling {
+    pub fn factor(self) -> u32 {
+        match self {
+            Supersampling::Off => 1,
+            Supersampling::X2 => 2,
+            Supersampling::X4 => 4,
+        }
+    }
+
+    pub fn text(self) -> &'static str {
+        match self {
+            Supersampling::Off => "Off",
+            Supersampling::X2 => "2x",
+            Supersampling::X4 => "4x",
+        }
+    }
+}
+
+/// Volumes in range from 0 to 1.
+#[derive(Clone, Deserialize, Reflect, Serialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    /// Multiplier for all other volumes.
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub ui: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 0.8,
+            music: 0.6,
+            sfx: 1.0,
+            ui: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Reflect, Serialize)]
+#[serde(default)]
+pub struct CameraSettings {
+    /// Move the camera when the cursor is near the window edges.
+    pub edge_scrolling: bool,
+    /// Distance from the window edges in logical pixels that triggers scrolling.
+    pub edge_margin: f32,
+    /// Scrolling speed multiplier.
+    pub edge_speed: f32,
+    pub wall_view: WallView,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            edge_scrolling: false,
+            edge_margin: 10.0,
+            edge_speed: 1.0,
+            wall_view: Default::default(),
+        }
+    }
+}
+
+/// Controls which walls are displayed as cut.
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize,
+)]
+pub enum WallView {
+    /// All walls are cut.
+    Down,
+    /// Only walls between the camera and its focus are cut.
+    #[default]
+    Auto,
+    /// Walls are never cut.
+    Up,
+}
+
+impl WallView {
+    pub fn text(self) -> &'static str {
+        match self {
+            WallView::Down => "Walls down",
+            WallView::Auto => "Walls cutaway",
+            WallView::Up => "Walls up",
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Reflect, Serialize)]
+#[serde(default)]
+pub struct GameplaySettings {
+    /// Pause single player game when a need of a family member drops to zero.
+    pub pause_on_depleted_need: bool,
+
+    /// Pause single player game when an object or a wall catches fire.
+    pub pause_on_fire: bool,
+
+    /// Interval in seconds of game time between city time-lapse frames.
+    ///
+    /// Set to 0 to disable recording.
+    pub time_lapse_interval: f32,
+
+    /// Name of the file from `base/names` without extension used to generate actor names.
+    pub names: String,
+
+    /// Current step of the tutorial, `None` if it was completed or skipped.
+    pub tutorial_step: Option<TutorialStep>,
+
+    /// When to display actor names above their heads.
+    pub nameplates: NameplateMode,
+
+    /// Display speech bubbles and critical needs above actors.
+    pub actor_indicators: bool,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        Self {
+            pause_on_depleted_need: true,
+            pause_on_fire: true,
+            time_lapse_interval: 60.0,
+            names: "en".to_string(),
+            tutorial_step: Some(Default::default()),
+            nameplates: Default::default(),
+            actor_indicators: true,
+        }
+    }
+}
+
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize,
+)]
+pub enum NameplateMode {
+    Always,
+    /// Only for the actor under cursor.
+    #[default]
+    Hover,
+    Never,
+}
+
+impl NameplateMode {
+    pub fn text(self) -> &'static str {
+        match self {
+            NameplateMode::Always => "Names always",
+            NameplateMode::Hover => "Names on hover",
+            NameplateMode::Never => "Names hidden",
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Reflect, Serialize)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    /// Multiplier for the size of all UI elements.
+    pub ui_scale: f32,
+    pub text_size: TextSize,
+    /// Name of the theme file from `base/themes` without extension.
+    pub theme: String,
+    pub outline_palette: OutlinePalette,
+    /// Disable camera smoothing.
+    pub reduced_motion: bool,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            text_size: Default::default(),
+            theme: "default".to_string(),
+            outline_palette: Default::default(),
+            reduced_motion: false,
+        }
+    }
+}
+
+/// Multiplier for font sizes from the theme.
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize,
+)]
+pub enum TextSize {
+    #[default]
+    Normal,
+    Large,
+    Larger,
+}
+
+impl TextSize {
+    pub fn text(self) -> &'static str {
+        match self {
+            TextSize::Normal => "Normal",
+            TextSize::Large => "Large",
+            TextSize::Larger => "Larger",
+        }
+    }
+
+    pub fn scale(self) -> f32 {
+        match self {
+            TextSize::Normal => 1.0,
+            TextSize::Large => 1.25,
+            TextSize::Larger => 1.5,
+        }
+    }
+}
+
+/// Color of outlines for hovered actors and objects.
+///
+/// Colorblind palettes are picked from colors that remain distinguishable
+/// for the corresponding type of color vision deficiency.
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize,
+)]
+pub enum OutlinePalette {
+    #[default]
+    Default,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl OutlinePalette {
+    pub fn text(self) -> &'static str {
+        match self {
+            OutlinePalette::Default => "Default",
+            OutlinePalette::Deuteranopia => "Deuteranopia",
+            OutlinePalette::Protanopia => "Protanopia",
+            OutlinePalette::Tritanopia => "Tritanopia",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            OutlinePalette::Default => Color::srgba(1.0, 1.0, 1.0, 0.3),
+            OutlinePalette::Deuteranopia => Color::srgb(0.9, 0.6, 0.0),
+            OutlinePalette::Protanopia => Color::srgb(0.35, 0.7, 0.9),
+            OutlinePalette::Tritanopia => Color::srgb(0.8, 0.475, 0.655),
+        }
+    }
+
+    /// Color for selected entities, distinguishable from [`Self::color`].
+    pub fn selection_color(self) -> Color {
+        match self {
+            OutlinePalette::Default => Color::srgb(1.0, 0.85, 0.2),
+            OutlinePalette::Deuteranopia => Color::srgb(0.0, 0.45, 0.7),
+            OutlinePalette::Protanopia => Color::srgb(0.95, 0.9, 0.25),
+            OutlinePalette::Tritanopia => Color::srgb(0.0, 0.6, 0.5),
+        }
+    }
 }
 
 #[derive(Clone, Deserialize, Reflect, Serialize)]
@@ -153,9 +524,16 @@ pub struct KeyboardSettings {
     pub rotate_right: Vec<Input>,
     pub zoom_in: Vec<Input>,
     pub zoom_out: Vec<Input>,
+    pub follow_actor: Vec<Input>,
+    pub photo_mode: Vec<Input>,
     pub delete: Vec<Input>,
     pub free_placement: Vec<Input>,
     pub ordinal_placement: Vec<Input>,
+    pub pause: Vec<Input>,
+    pub normal_speed: Vec<Input>,
+    pub fast_speed: Vec<Input>,
+    pub faster_speed: Vec<Input>,
+    pub ultra_speed: Vec<Input>,
 }
 
 impl KeyboardSettings {
@@ -168,8 +546,15 @@ impl KeyboardSettings {
         self.rotate_right.clear();
         self.zoom_in.clear();
         self.zoom_out.clear();
+        self.follow_actor.clear();
+        self.photo_mode.clear();
         self.delete.clear();
         self.free_placement.clear();
+        self.pause.clear();
+        self.normal_speed.clear();
+        self.fast_speed.clear();
+        self.faster_speed.clear();
+        self.ultra_speed.clear();
     }
 }
 
@@ -184,13 +569,91 @@ impl Default for KeyboardSettings {
             rotate_right: vec![KeyCode::Period.into()],
             zoom_in: vec![KeyCode::Equal.into(), KeyCode::NumpadAdd.into()],
             zoom_out: vec![KeyCode::Minus.into(), KeyCode::NumpadSubtract.into()],
+            follow_actor: vec![KeyCode::KeyF.into()],
+            photo_mode: vec![KeyCode::KeyP.into()],
             delete: vec![KeyCode::Delete.into(), KeyCode::Backspace.into()],
             free_placement: vec![KeyCode::AltLeft.into(), KeyCode::AltRight.into()],
             ordinal_placement: vec![KeyCode::ShiftLeft.into(), KeyCode::ShiftRight.into()],
+            pause: vec![KeyCode::Digit0.into(), KeyCode::Pause.into()],
+            normal_speed: vec![KeyCode::Digit1.into()],
+            fast_speed: vec![KeyCode::Digit2.into()],
+            faster_speed: vec![KeyCode::Digit3.into()],
+            ultra_speed: vec![KeyCode::Digit4.into()],
+        }
+    }
+}
+
+/// Gamepad buttons for actions.
+///
+/// Sticks and triggers axes are not configurable.
+#[derive(Clone, Deserialize, Reflect, Serialize)]
+#[serde(default)]
+pub struct GamepadSettings {
+    pub confirm: Vec<Input>,
+    pub cancel: Vec<Input>,
+    pub pan_camera: Vec<Input>,
+    pub follow_actor: Vec<Input>,
+    pub photo_mode: Vec<Input>,
+    pub rotate_object: Vec<Input>,
+    pub delete: Vec<Input>,
+    pub free_placement: Vec<Input>,
+    pub ordinal_placement: Vec<Input>,
+    pub undo: Vec<Input>,
+    pub redo: Vec<Input>,
+    pub menu: Vec<Input>,
+}
+
+impl GamepadSettings {
+    pub fn clear(&mut self) {
+        self.confirm.clear();
+        self.cancel.clear();
+        self.pan_camera.clear();
+        self.follow_actor.clear();
+        self.photo_mode.clear();
+        self.rotate_object.clear();
+        self.delete.clear();
+        self.free_placement.clear();
+        self.ordinal_placement.clear();
+        self.undo.clear();
+        self.redo.clear();
+        self.menu.clear();
+    }
+}
+
+impl Default for GamepadSettings {
+    fn default() -> Self {
+        Self {
+            confirm: vec![GamepadButton::South.into()],
+            cancel: vec![GamepadButton::East.into()],
+            pan_camera: vec![GamepadButton::East.into()],
+            follow_actor: vec![GamepadButton::RightThumb.into()],
+            photo_mode: vec![GamepadButton::Select.into()],
+            rotate_object: vec![GamepadButton::West.into()],
+            delete: vec![GamepadButton::North.into()],
+            free_placement: vec![GamepadButton::LeftTrigger2.into()],
+            ordinal_placement: vec![GamepadButton::RightTrigger2.into()],
+            undo: vec![GamepadButton::LeftTrigger.into()],
+            redo: vec![GamepadButton::RightTrigger.into()],
+            menu: vec![GamepadButton::Start.into()],
         }
     }
 }
 
+#[derive(Clone, Default, Deserialize, Reflect, Serialize)]
+#[serde(default)]
+pub struct ModSettings {
+    /// Names of mods that shouldn't be loaded.
+    ///
+    /// Stores disabled mods to enable newly installed mods by default.
+    pub disabled: Vec<String>,
+}
+
+impl ModSettings {
+    pub fn enabled(&self, mod_name: &str) -> bool {
+        !self.disabled.iter().any(|name| name == mod_name)
+    }
+}
+
 #[derive(Clone, Default, Deserialize, Reflect, Serialize)]
 #[serde(default)]
 pub struct DeveloperSettings {
@@ -199,4 +662,108 @@ pub struct DeveloperSettings {
     pub colliders: bool,
     pub paths: bool,
     pub nav_mesh: bool,
+    pub network_stats: bool,
+    pub profiler: bool,
+    /// Display the world inspector if the game is built with it.
+    ///
+    /// In multiplayer requires host permission.
+    pub world_inspector: bool,
+    /// Pause single player game while the world inspector is displayed.
+    pub pause_on_inspector: bool,
+    pub inspector_filter: InspectorFilter,
+    /// Record network messages on server to dump and replay them.
+    pub network_recording: bool,
+    /// Report entities left after returning to the main menu.
+    pub entity_audit: bool,
+    /// Show tools to teleport actors, change their needs and complete tasks.
+    ///
+    /// In multiplayer requires host permission.
+    pub debug_tools: bool,
+    /// Minimum level of messages written to logs.
+    ///
+    /// Applied on the next launch since the logger is initialized before settings.
+    pub log_level: LogLevel,
+    pub picking_backend: PickingBackend,
+}
+
+/// Entities listed in the world inspector.
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize,
+)]
+pub enum InspectorFilter {
+    #[default]
+    All,
+    Actors,
+    Walls,
+    Needs,
+}
+
+impl InspectorFilter {
+    pub fn text(self) -> &'static str {
+        match self {
+            InspectorFilter::All => "All entities",
+            InspectorFilter::Actors => "Actors",
+            InspectorFilter::Walls => "Walls",
+            InspectorFilter::Needs => "Needs",
+        }
+    }
+}
+
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize,
+)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn text(self) -> &'static str {
+        match self {
+            LogLevel::Error => "Errors",
+            LogLevel::Warn => "Warnings",
+            LogLevel::Info => "Info",
+            LogLevel::Debug => "Debug",
+            LogLevel::Trace => "Trace",
+        }
+    }
+}
+
+/// Source of cursor hits for world entities.
+///
+/// Both backends emit the same pointer events.
+#[derive(
+    Clone, Component, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize,
+)]
+pub enum PickingBackend {
+    /// Ray casts through the physics query pipeline.
+    #[default]
+    Physics,
+    /// Walks a grid of collider bounds along the ray, updated only for moved colliders.
+    Grid,
+}
+
+impl PickingBackend {
+    pub fn text(self) -> &'static str {
+        match self {
+            PickingBackend::Physics => "Physics",
+            PickingBackend::Grid => "Grid",
+        }
+    }
+}
+
+impl From<LogLevel> for Level {
+    fn from(value: LogLevel) -> Self {
+        match value {
+            LogLevel::Error => Level::ERROR,
+            LogLevel::Warn => Level::WARN,
+            LogLevel::Info => Level::INFO,
+            LogLevel::Debug => Level::DEBUG,
+            LogLevel::Trace => Level::TRACE,
+        }
+    }
 }
@@ -0,0 +1,181 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::{
+    asset::manifest::object_manifest::{ObjectCategory, ObjectManifest},
+    game_world::{
+        actor::needs::{Hunger, Need},
+        family::{building::wall::Wall, Family, FamilyMode},
+        object::Object,
+        WorldState,
+    },
+    settings::{Settings, SettingsApply},
+};
+
+/// Guides new players through the basic actions.
+///
+/// Steps are completed by observing the corresponding game events.
+/// Progress is stored in gameplay settings to survive restarts.
+pub(super) struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(skip)
+            .add_observer(replay)
+            .add_observer(complete_family_creation)
+            .add_observer(complete_wall_building)
+            .add_observer(complete_furniture_placing)
+            .add_systems(
+                Update,
+                complete_hunger_satisfying
+                    .run_if(in_state(WorldState::Family))
+                    .run_if(on_step(TutorialStep::SatisfyHunger)),
+            );
+    }
+}
+
+fn skip(_trigger: Trigger<TutorialSkip>, mut commands: Commands, mut settings: ResMut<Settings>) {
+    info!("skipping tutorial");
+    settings.gameplay.tutorial_step = None;
+    commands.trigger(SettingsApply);
+}
+
+fn replay(
+    _trigger: Trigger<TutorialReplay>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+) {
+    info!("restarting tutorial");
+    settings.gameplay.tutorial_step = Some(Default::default());
+    commands.trigger(SettingsApply);
+}
+
+fn complete_family_creation(
+    _trigger: Trigger<OnAdd, Family>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+    world_state: Option<Res<State<WorldState>>>,
+) {
+    // Ignore families spawned on world loading.
+    if world_state.is_some_and(|state| *state.get() == WorldState::FamilyEditor) {
+        advance(&mut commands, &mut settings, TutorialStep::CreateFamily);
+    }
+}
+
+fn complete_wall_building(
+    _trigger: Trigger<OnAdd, Wall>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+    family_mode: Option<Res<State<FamilyMode>>>,
+) {
+    if family_mode.is_some_and(|mode| *mode.get() == FamilyMode::Building) {
+        advance(&mut commands, &mut settings, TutorialStep::BuildWall);
+    }
+}
+
+fn complete_furniture_placing(
+    trigger: Trigger<OnAdd, Object>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+    asset_server: Res<AssetServer>,
+    manifests: Res<Assets<ObjectManifest>>,
+    family_mode: Option<Res<State<FamilyMode>>>,
+    objects: Query<&Object>,
+) {
+    if !family_mode.is_some_and(|mode| *mode.get() == FamilyMode::Building) {
+        return;
+    }
+
+    let object = objects.get(trigger.entity()).unwrap();
+    let is_furniture = asset_server
+        .get_handle(&**object)
+        .and_then(|handle| manifests.get(&handle))
+        .is_some_and(|manifest| manifest.category == ObjectCategory::Furniture);
+    if is_furniture {
+        advance(&mut commands, &mut settings, TutorialStep::PlaceFurniture);
+    }
+}
+
+/// Completes the step when hunger of any actor increases.
+fn complete_hunger_satisfying(
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+    mut last_values: Local<EntityHashMap<f32>>,
+    needs: Query<(Entity, &Need), (With<Hunger>, Changed<Need>)>,
+) {
+    for (entity, need) in &needs {
+        if let Some(last_value) = last_values.insert(entity, need.0) {
+            if need.0 > last_value {
+                advance(&mut commands, &mut settings, TutorialStep::SatisfyHunger);
+                last_values.clear();
+                return;
+            }
+        }
+    }
+}
+
+/// Moves the tutorial to the next step if the specified step is active.
+fn advance(commands: &mut Commands, settings: &mut ResMut<Settings>, step: TutorialStep) {
+    if settings.gameplay.tutorial_step != Some(step) {
+        return;
+    }
+
+    info!("completing tutorial step `{step:?}`");
+    settings.gameplay.tutorial_step = step.next();
+    commands.trigger(SettingsApply);
+}
+
+fn on_step(step: TutorialStep) -> impl Fn(Res<Settings>) -> bool {
+    move |settings| settings.gameplay.tutorial_step == Some(step)
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, EnumIter, Eq, Hash, PartialEq, Reflect, Serialize,
+)]
+pub enum TutorialStep {
+    #[default]
+    CreateFamily,
+    BuildWall,
+    PlaceFurniture,
+    SatisfyHunger,
+}
+
+impl TutorialStep {
+    pub fn text(self) -> &'static str {
+        match self {
+            TutorialStep::CreateFamily => {
+                "Create a family in the world menu, edit its members and place it into a city."
+            }
+            TutorialStep::BuildWall => {
+                "Switch to the building mode, select walls and draw a wall on the lot."
+            }
+            TutorialStep::PlaceFurniture => {
+                "Open the furniture category in the building mode and place a bed or any other piece of furniture."
+            }
+            TutorialStep::SatisfyHunger => {
+                "Check the needs of the actor, click on an object and pick an interaction that satisfies hunger."
+            }
+        }
+    }
+
+    /// Returns the step number starting from 1.
+    pub fn number(self) -> usize {
+        Self::iter()
+            .position(|step| step == self)
+            .expect("step should be iterable")
+            + 1
+    }
+
+    fn next(self) -> Option<Self> {
+        Self::iter().skip_while(|&step| step != self).nth(1)
+    }
+}
+
+/// Finishes the tutorial without completing the remaining steps.
+#[derive(Event)]
+pub struct TutorialSkip;
+
+/// Starts the tutorial from the first step.
+#[derive(Event)]
+pub struct TutorialReplay;
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use project_harmonia_base::game_world::WorldState;
+use project_harmonia_base::{core::GameState, game_world::WorldState};
 
 /// Spawn a dedicated camera for UI when we don't use 3D camera.
 pub(super) struct Camera2dPlugin;
@@ -8,9 +8,11 @@ pub(super) struct Camera2dPlugin;
 impl Plugin for Camera2dPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, spawn)
+            .add_systems(OnExit(GameState::Menu), enable)
             .add_systems(OnExit(WorldState::FamilyEditor), enable)
             .add_systems(OnExit(WorldState::Family), enable)
             .add_systems(OnExit(WorldState::City), enable)
+            .add_systems(OnEnter(GameState::Menu), disable)
             .add_systems(OnEnter(WorldState::FamilyEditor), disable)
             .add_systems(OnEnter(WorldState::Family), disable)
             .add_systems(OnEnter(WorldState::City), disable);
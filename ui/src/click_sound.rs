@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use project_harmonia_base::audio::{Sound, SoundPlay};
+use project_harmonia_widgets::button::ButtonKind;
+
+/// Plays a sound when any button is clicked.
+pub(super) struct ClickSoundPlugin;
+
+impl Plugin for ClickSoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(play);
+    }
+}
+
+/// Clicks propagate from text to buttons, so only the button entity is matched.
+fn play(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    buttons: Query<(), With<ButtonKind>>,
+) {
+    if trigger.button == PointerButton::Primary && buttons.get(trigger.entity()).is_ok() {
+        commands.trigger(SoundPlay::new(Sound::Click));
+    }
+}
@@ -0,0 +1,322 @@
+use std::cmp::Reverse;
+
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use bevy_replicon::prelude::*;
+use bevy_simple_text_input::{TextInputSubmitEvent, TextInputValue};
+use project_harmonia_base::{
+    game_world::{
+        city::CityMode,
+        commands_history::CommandsHistory,
+        family::{building::BuildingMode, FamilyMode},
+        WorldState,
+    },
+    network::replay_log::ReplayLogDump,
+    settings::Settings,
+};
+use project_harmonia_widgets::{
+    button::{ButtonKind, Toggled},
+    dialog::Dialog,
+    text_edit::TextEdit,
+    theme::Theme,
+};
+
+use crate::menu::settings_menu::SettingsMenuOpen;
+
+/// Searches and executes commands available in the current context by name.
+///
+/// Opened with Ctrl + P.
+pub(super) struct CommandPalettePlugin;
+
+impl Plugin for CommandPalettePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_input_context::<PaletteShortcut>()
+            .add_systems(Startup, setup)
+            .add_observer(open)
+            .add_observer(execute.never_param_warn())
+            .add_systems(
+                Update,
+                (
+                    update_results.never_param_warn(),
+                    submit.never_param_warn(),
+                    close.never_param_warn(),
+                ),
+            );
+    }
+}
+
+/// Maximum number of displayed matches.
+const MAX_RESULTS: usize = 8;
+
+fn setup(mut commands: Commands) {
+    commands.spawn(PaletteShortcut);
+}
+
+fn open(
+    _trigger: Trigger<Started<OpenCommandPalette>>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    settings: Res<Settings>,
+    server: Res<RepliconServer>,
+    world_state: Option<Res<State<WorldState>>>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    palettes: Query<(), With<CommandPalette>>,
+    family_modes: Query<(Entity, &FamilyMode), With<Toggled>>,
+    building_modes: Query<(Entity, &BuildingMode), With<Toggled>>,
+    city_modes: Query<(Entity, &CityMode), With<Toggled>>,
+) {
+    if !palettes.is_empty() {
+        return;
+    }
+
+    // Modes are switched via their tab buttons to keep HUD in sync.
+    let mut entries = Vec::new();
+    for (entity, mode) in &family_modes {
+        entries.push(PaletteEntry::new(
+            format!("Family mode: {mode:?}"),
+            PaletteCommand::Toggle(entity),
+        ));
+    }
+    for (entity, mode) in &building_modes {
+        entries.push(PaletteEntry::new(
+            format!("Building mode: {mode:?}"),
+            PaletteCommand::Toggle(entity),
+        ));
+    }
+    for (entity, mode) in &city_modes {
+        entries.push(PaletteEntry::new(
+            format!("City mode: {mode:?}"),
+            PaletteCommand::Toggle(entity),
+        ));
+    }
+    if world_state.is_some() {
+        entries.push(PaletteEntry::new("Undo", PaletteCommand::Undo));
+        entries.push(PaletteEntry::new("Redo", PaletteCommand::Redo));
+    }
+    if server.is_running() && settings.developer.network_recording {
+        entries.push(PaletteEntry::new(
+            "Dump network messages",
+            PaletteCommand::DumpReplayLog,
+        ));
+    }
+    entries.push(PaletteEntry::new("Settings", PaletteCommand::Settings));
+
+    info!("opening command palette with {} entries", entries.len());
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn(CommandPalette(entries))
+            .with_children(|parent| {
+                parent
+                    .spawn((
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            padding: theme.padding.normal,
+                            row_gap: theme.gap.normal,
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((PaletteEdit, TextInputValue::default()));
+                        parent.spawn((
+                            PaletteResults,
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: theme.gap.normal,
+                                ..Default::default()
+                            },
+                        ));
+                    });
+            })
+            .observe(click_outside);
+    });
+}
+
+fn update_results(
+    mut commands: Commands,
+    palette: Single<&CommandPalette>,
+    query: Single<&TextInputValue, (Changed<TextInputValue>, With<PaletteEdit>)>,
+    results_entity: Single<Entity, With<PaletteResults>>,
+) {
+    let mut matches: Vec<_> = palette
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            fuzzy_score(&query.0, &entry.label).map(|score| (index, score))
+        })
+        .collect();
+    matches.sort_by_key(|&(_, score)| Reverse(score));
+
+    debug!("found {} matches for '{}'", matches.len(), query.0);
+    commands
+        .entity(*results_entity)
+        .despawn_descendants()
+        .with_children(|parent| {
+            for &(index, _) in matches.iter().take(MAX_RESULTS) {
+                parent
+                    .spawn((PaletteResult(palette[index].command), ButtonKind::Normal))
+                    .with_child(Text::new(palette[index].label.clone()))
+                    .observe(click_result);
+            }
+        });
+}
+
+fn click_result(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    results: Query<&PaletteResult>,
+) {
+    let result = results.get(trigger.entity()).unwrap();
+    commands.trigger(**result);
+}
+
+/// Executes the best match on Enter.
+fn submit(
+    mut commands: Commands,
+    mut submit_events: EventReader<TextInputSubmitEvent>,
+    edit_entity: Single<Entity, With<PaletteEdit>>,
+    results_children: Single<&Children, With<PaletteResults>>,
+    results: Query<&PaletteResult>,
+) {
+    for event in submit_events.read() {
+        if event.entity != *edit_entity {
+            continue;
+        }
+
+        if let Some(result) = results.iter_many(*results_children).next() {
+            commands.trigger(**result);
+        }
+    }
+}
+
+fn click_outside(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    palette_entity: Single<Entity, With<CommandPalette>>,
+) {
+    if trigger.target == *palette_entity {
+        info!("closing command palette");
+        commands.entity(*palette_entity).despawn_recursive();
+    }
+}
+
+fn close(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    palette_entity: Single<Entity, With<CommandPalette>>,
+) {
+    // Keyboard actions are disabled while typing.
+    if keys.just_pressed(KeyCode::Escape) {
+        info!("closing command palette");
+        commands.entity(*palette_entity).despawn_recursive();
+    }
+}
+
+fn execute(
+    trigger: Trigger<PaletteCommand>,
+    mut commands: Commands,
+    mut history: CommandsHistory,
+    mut buttons: Query<&mut Toggled>,
+    palette_entity: Single<Entity, With<CommandPalette>>,
+) {
+    info!("executing `{:?}` from command palette", *trigger);
+    commands.entity(*palette_entity).despawn_recursive();
+    match *trigger {
+        PaletteCommand::Toggle(button_entity) => {
+            if let Ok(mut toggled) = buttons.get_mut(button_entity) {
+                **toggled = true;
+            }
+        }
+        PaletteCommand::Undo => history.undo(),
+        PaletteCommand::Redo => history.redo(),
+        PaletteCommand::DumpReplayLog => commands.trigger(ReplayLogDump),
+        PaletteCommand::Settings => commands.trigger(SettingsMenuOpen),
+    }
+}
+
+/// Returns how well `query` matches `text`, or `None` if it doesn't match.
+///
+/// All query characters must appear in the text in order, ignoring case.
+/// Consecutive matches and matches at word starts are scored higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let mut score = 0;
+    let mut chars = text.chars().enumerate();
+    let mut last_match = None;
+    for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+        let (index, _) = chars
+            .by_ref()
+            .find(|(_, c)| c.eq_ignore_ascii_case(&query_char))?;
+
+        score += 1;
+        if last_match.is_some_and(|last| last + 1 == index) {
+            score += 2;
+        }
+        if index == 0
+            || text
+                .chars()
+                .nth(index - 1)
+                .is_some_and(|c| !c.is_alphanumeric())
+        {
+            score += 2;
+        }
+        last_match = Some(index);
+    }
+
+    Some(score)
+}
+
+struct PaletteEntry {
+    label: String,
+    command: PaletteCommand,
+}
+
+impl PaletteEntry {
+    fn new(label: impl Into<String>, command: PaletteCommand) -> Self {
+        Self {
+            label: label.into(),
+            command,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Event)]
+enum PaletteCommand {
+    /// Toggles a tab button.
+    Toggle(Entity),
+    Undo,
+    Redo,
+    DumpReplayLog,
+    Settings,
+}
+
+/// Available entries, collected on opening.
+#[derive(Component, Deref)]
+#[require(Name(|| Name::new("Command palette")), Dialog)]
+struct CommandPalette(Vec<PaletteEntry>);
+
+#[derive(Component)]
+#[require(TextEdit)]
+struct PaletteEdit;
+
+#[derive(Component)]
+struct PaletteResults;
+
+#[derive(Component, Deref)]
+struct PaletteResult(PaletteCommand);
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Command palette shortcut")))]
+struct PaletteShortcut;
+
+impl InputContext for PaletteShortcut {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<OpenCommandPalette>()
+            .to(KeyCode::KeyP.with_mod_keys(ModKeys::CONTROL));
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct OpenCommandPalette;
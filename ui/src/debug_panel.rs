@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+use project_harmonia_base::{
+    game_world::{
+        actor::{
+            debug_tools::{self, NeedSet, TaskComplete},
+            needs::{Need, NeedGlyph},
+            task::ActiveTask,
+            SelectedActor,
+        },
+        players::LocalPermission,
+        WorldState,
+    },
+    settings::Settings,
+};
+use project_harmonia_widgets::{
+    button::ButtonKind,
+    label::LabelKind,
+    slider::{Slider, SliderChanged, SliderLabel, SliderRange},
+    theme::Theme,
+};
+
+/// Displays actor debug tools from developer settings.
+///
+/// Contains sliders to change needs of the selected actor
+/// and a button to complete its active task.
+pub(super) struct DebugPanelPlugin;
+
+impl Plugin for DebugPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(reload).add_systems(
+            Update,
+            (toggle.never_param_warn(), update_sliders)
+                .chain()
+                .run_if(in_state(WorldState::Family)),
+        );
+    }
+}
+
+const NEED_RANGE: SliderRange = SliderRange {
+    min: 0.0,
+    max: 100.0,
+    step: 1.0,
+};
+
+fn toggle(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    settings: Res<Settings>,
+    client: Res<RepliconClient>,
+    local_permission: Res<LocalPermission>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    actor_children: Single<&Children, With<SelectedActor>>,
+    panel_entity: Option<Single<Entity, With<DebugPanel>>>,
+    needs: Query<(Entity, &NeedGlyph, &Need)>,
+) {
+    let allowed = debug_tools::debug_tools_allowed(&settings, &client, &local_permission);
+    match (allowed, panel_entity) {
+        (true, None) => {
+            debug!("showing debug panel");
+            commands.entity(*root_entity).with_children(|parent| {
+                parent
+                    .spawn((
+                        DebugPanel,
+                        StateScoped(WorldState::Family),
+                        Node {
+                            position_type: PositionType::Absolute,
+                            top: Val::Percent(25.0),
+                            left: Val::Px(0.0),
+                            display: Display::Grid,
+                            grid_template_columns: vec![GridTrack::auto(); 2],
+                            column_gap: theme.gap.normal,
+                            row_gap: theme.gap.normal,
+                            padding: theme.padding.normal,
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        for (need_entity, glyph, need) in needs.iter_many(*actor_children) {
+                            parent.spawn((LabelKind::Symbol, Text::new(glyph.0)));
+                            parent
+                                .spawn((
+                                    NeedSlider(need_entity),
+                                    Slider(need.0),
+                                    NEED_RANGE,
+                                    SliderLabel,
+                                ))
+                                .observe(set_need);
+                        }
+                        parent
+                            .spawn((
+                                ButtonKind::Normal,
+                                Node {
+                                    grid_column: GridPlacement::span(2),
+                                    ..Default::default()
+                                },
+                            ))
+                            .with_child(Text::new("Complete task"))
+                            .observe(complete_task);
+                    });
+            });
+        }
+        (false, Some(panel_entity)) => {
+            debug!("hiding debug panel");
+            commands.entity(*panel_entity).despawn_recursive();
+        }
+        _ => (),
+    }
+}
+
+/// Respawns the panel for the newly selected actor.
+fn reload(
+    _trigger: Trigger<OnAdd, SelectedActor>,
+    mut commands: Commands,
+    panel_entity: Option<Single<Entity, With<DebugPanel>>>,
+) {
+    if let Some(panel_entity) = panel_entity {
+        debug!("reloading debug panel");
+        commands.entity(*panel_entity).despawn_recursive();
+    }
+}
+
+fn update_sliders(
+    needs: Query<&Need, Changed<Need>>,
+    mut sliders: Query<(&mut Slider, &NeedSlider)>,
+) {
+    for (mut slider, need_slider) in &mut sliders {
+        if let Ok(need) = needs.get(need_slider.0) {
+            slider.set_if_neq(Slider(need.0));
+        }
+    }
+}
+
+fn set_need(
+    trigger: Trigger<SliderChanged>,
+    mut commands: Commands,
+    sliders: Query<&NeedSlider>,
+    needs: Query<&Need>,
+) {
+    let need_slider = sliders.get(trigger.entity()).unwrap();
+    let Ok(need) = needs.get(need_slider.0) else {
+        return;
+    };
+
+    // Ignore changes that come from `update_sliders`.
+    if need.0 != trigger.event().0 {
+        info!("setting need `{}` to {}", need_slider.0, trigger.event().0);
+        commands.client_trigger_targets(NeedSet(trigger.event().0), need_slider.0);
+    }
+}
+
+fn complete_task(
+    _trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    actor_children: Single<&Children, With<SelectedActor>>,
+    tasks: Query<Entity, With<ActiveTask>>,
+) {
+    if let Some(task_entity) = tasks.iter_many(*actor_children).next() {
+        info!("completing task `{task_entity}`");
+        commands.client_trigger_targets(TaskComplete, task_entity);
+    } else {
+        debug!("no active task to complete");
+    }
+}
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Debug panel")))]
+struct DebugPanel;
+
+/// Need entity controlled by the slider.
+#[derive(Component)]
+struct NeedSlider(Entity);
@@ -1,6 +1,15 @@
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use arboard::Clipboard;
 use bevy::prelude::*;
 
-use project_harmonia_base::error_message::ErrorMessage;
+use project_harmonia_base::{
+    error_message::{error_message, ErrorMessage},
+    game_paths::GamePaths,
+    log_files::SessionLog,
+    notification::Notification,
+};
 use project_harmonia_widgets::{
     button::ButtonKind, dialog::Dialog, label::LabelKind, theme::Theme,
 };
@@ -13,39 +22,116 @@ impl Plugin for ErrorDialogPlugin {
     }
 }
 
+#[cfg(target_os = "windows")]
+const OPEN_COMMAND: &str = "explorer";
+#[cfg(target_os = "macos")]
+const OPEN_COMMAND: &str = "open";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const OPEN_COMMAND: &str = "xdg-open";
+
 fn show(
     trigger: Trigger<ErrorMessage>,
     mut commands: Commands,
     theme: Res<Theme>,
+    game_paths: Res<GamePaths>,
+    session_log: Option<Res<SessionLog>>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
 ) {
     info!("showing error dialog");
+    let log_path = session_log
+        .as_deref()
+        .map_or(&game_paths.logs, |path| &**path);
     commands.entity(*root_entity).with_children(|parent| {
-        parent
-            .spawn(Dialog)
-            .with_children(|parent| {
-                parent
-                    .spawn((
-                        Node {
-                            flex_direction: FlexDirection::Column,
-                            justify_content: JustifyContent::Center,
-                            align_items: AlignItems::Center,
-                            padding: theme.padding.normal,
-                            row_gap: theme.gap.normal,
+        parent.spawn(ErrorDialog).with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        padding: theme.padding.normal,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                    theme.panel_background,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((LabelKind::Normal, Text::new(&**trigger)));
+                    parent.spawn((
+                        LabelKind::Small,
+                        Text::new(format!(
+                            "Details are in the log file: {}",
+                            log_path.display()
+                        )),
+                    ));
+                    parent
+                        .spawn(Node {
+                            column_gap: theme.gap.normal,
                             ..Default::default()
-                        },
-                        theme.panel_background,
-                    ))
-                    .with_children(|parent| {
-                        parent.spawn((LabelKind::Normal, Text::new(&**trigger)));
-                        parent.spawn(ButtonKind::Normal).with_child(Text::new("Ok"));
-                    });
-            })
-            .observe(close);
+                        })
+                        .with_children(|parent| {
+                            parent
+                                .spawn(ButtonKind::Normal)
+                                .with_child(Text::new("Copy log path"))
+                                .observe(copy_log_path.pipe(error_message));
+                            parent
+                                .spawn(ButtonKind::Normal)
+                                .with_child(Text::new("Open logs"))
+                                .observe(open_logs.pipe(error_message));
+                            parent
+                                .spawn(ButtonKind::Normal)
+                                .with_child(Text::new("Ok"))
+                                .observe(close);
+                        });
+                });
+        });
     });
 }
 
-fn close(trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+fn copy_log_path(
+    _trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    game_paths: Res<GamePaths>,
+    session_log: Option<Res<SessionLog>>,
+) -> Result<()> {
+    let log_path = session_log
+        .as_deref()
+        .map_or(&game_paths.logs, |path| &**path);
+    info!("copying {log_path:?} to clipboard");
+    Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(log_path.to_string_lossy()))
+        .context("unable to copy log path")?;
+
+    commands.trigger(Notification::info("Log path copied"));
+
+    Ok(())
+}
+
+fn open_logs(_trigger: Trigger<Pointer<Click>>, game_paths: Res<GamePaths>) -> Result<()> {
+    info!("opening {:?}", game_paths.logs);
+    Command::new(OPEN_COMMAND)
+        .arg(&game_paths.logs)
+        .spawn()
+        .with_context(|| format!("unable to open {:?}", game_paths.logs))?;
+
+    Ok(())
+}
+
+fn close(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    parents: Query<&Parent>,
+    dialogs: Query<(), With<ErrorDialog>>,
+) {
+    let dialog_entity = parents
+        .iter_ancestors(trigger.entity())
+        .find(|&entity| dialogs.get(entity).is_ok())
+        .expect("button should be spawned inside error dialog");
+
     info!("closing error dialog");
-    commands.entity(trigger.entity()).despawn_recursive();
+    commands.entity(dialog_entity).despawn_recursive();
 }
+
+#[derive(Component)]
+#[require(Dialog)]
+struct ErrorDialog;
@@ -1,6 +1,8 @@
 mod city_hud;
 mod family_hud;
 mod objects_node;
+mod speed_node;
+mod stats_menu;
 pub(super) mod task_menu;
 mod tools_node;
 
@@ -9,6 +11,8 @@ use bevy::prelude::*;
 use city_hud::CityHudPlugin;
 use family_hud::FamilyHudPlugin;
 use objects_node::ObjectsNodePlugin;
+use speed_node::SpeedNodePlugin;
+use stats_menu::StatsMenuPlugin;
 use task_menu::TaskMenuPlugin;
 use tools_node::ToolsNodePlugin;
 
@@ -20,6 +24,8 @@ impl Plugin for HudPlugin {
             CityHudPlugin,
             ObjectsNodePlugin,
             FamilyHudPlugin,
+            SpeedNodePlugin,
+            StatsMenuPlugin,
             TaskMenuPlugin,
             ToolsNodePlugin,
         ));
@@ -1,12 +1,10 @@
 mod roads_node;
+mod time_lapse_node;
 
 use bevy::prelude::*;
 use project_harmonia_base::{
-    asset::manifest::{
-        object_manifest::{ObjectCategory, ObjectManifest},
-        road_manifest::RoadManifest,
-    },
-    game_world::{city::CityMode, WorldState},
+    asset::manifest::{object_manifest::ObjectCategory, road_manifest::RoadManifest},
+    game_world::{city::CityMode, players::LocalPermission, WorldState},
 };
 use project_harmonia_widgets::{
     button::{ButtonKind, TabContent, Toggled},
@@ -14,14 +12,15 @@ use project_harmonia_widgets::{
 };
 use strum::IntoEnumIterator;
 
-use crate::hud::{objects_node, tools_node};
+use crate::hud::{objects_node, stats_menu::StatsMenuOpen, tools_node};
 use roads_node::RoadsNodePlugin;
+use time_lapse_node::TimeLapseNodePlugin;
 
 pub(super) struct CityHudPlugin;
 
 impl Plugin for CityHudPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(RoadsNodePlugin)
+        app.add_plugins((RoadsNodePlugin, TimeLapseNodePlugin))
             .add_systems(OnEnter(WorldState::City), setup)
             .add_systems(Update, set_city_mode.run_if(in_state(WorldState::City)));
     }
@@ -32,8 +31,8 @@ fn setup(
     mut tab_commands: Commands,
     theme: Res<Theme>,
     asset_server: Res<AssetServer>,
-    object_manifests: Res<Assets<ObjectManifest>>,
     road_manifests: Res<Assets<RoadManifest>>,
+    local_permission: Res<LocalPermission>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
 ) {
     debug!("showing city HUD");
@@ -63,7 +62,9 @@ fn setup(
                     ))
                     .id();
 
-                for mode in CityMode::iter() {
+                // Players without building permission can only look around.
+                let modes = CityMode::iter().filter(|_| local_permission.can_build());
+                for mode in modes {
                     let content_entity = parent
                         .spawn((
                             Node {
@@ -78,9 +79,7 @@ fn setup(
                             CityMode::Objects => {
                                 objects_node::setup(
                                     parent,
-                                    &mut tab_commands,
                                     &theme,
-                                    &object_manifests,
                                     ObjectCategory::CITY_CATEGORIES,
                                 );
                             }
@@ -91,6 +90,7 @@ fn setup(
                                 &theme,
                                 &road_manifests,
                             ),
+                            CityMode::TimeLapse => time_lapse_node::setup(parent),
                         })
                         .id();
 
@@ -104,10 +104,20 @@ fn setup(
                         .with_child(Text::new(mode.glyph()))
                         .set_parent(tabs_entity);
                 }
+
+                tab_commands
+                    .spawn(ButtonKind::Symbol)
+                    .with_child(Text::new("📈"))
+                    .set_parent(tabs_entity)
+                    .observe(open_stats);
             });
     });
 }
 
+fn open_stats(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(StatsMenuOpen);
+}
+
 fn set_city_mode(
     mut commands: Commands,
     buttons: Query<(Ref<Toggled>, &CityMode), Changed<Toggled>>,
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+
+use project_harmonia_base::game_world::city::{
+    time_lapse::{Timeline, TimelinePlayback},
+    CityMode,
+};
+use project_harmonia_widgets::{button::ButtonKind, label::LabelKind};
+
+pub(super) struct TimeLapseNodePlugin;
+
+impl Plugin for TimeLapseNodePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            update_label
+                .never_param_warn()
+                .run_if(in_state(CityMode::TimeLapse))
+                .run_if(resource_exists_and_changed::<TimelinePlayback>),
+        );
+    }
+}
+
+pub(super) fn setup(parent: &mut ChildBuilder) {
+    parent.spawn((FrameLabel, LabelKind::Normal, Text::default()));
+    for button in [
+        PlaybackButton::First,
+        PlaybackButton::Previous,
+        PlaybackButton::Play,
+        PlaybackButton::Next,
+        PlaybackButton::Last,
+    ] {
+        parent
+            .spawn((button, ButtonKind::Symbol))
+            .with_child(Text::new(button.glyph()))
+            .observe(click);
+    }
+}
+
+fn click(
+    trigger: Trigger<Pointer<Click>>,
+    timeline: Res<Timeline>,
+    mut playback: ResMut<TimelinePlayback>,
+    buttons: Query<&PlaybackButton>,
+) {
+    let last_frame = timeline.len().saturating_sub(1);
+    let button = *buttons.get(trigger.entity()).unwrap();
+    debug!("clicking `{button:?}` playback button");
+    match button {
+        PlaybackButton::First => playback.frame = 0,
+        PlaybackButton::Previous => playback.frame = playback.frame.saturating_sub(1),
+        PlaybackButton::Play => {
+            if !playback.playing && playback.frame == last_frame {
+                playback.frame = 0;
+            }
+            playback.playing = !playback.playing;
+        }
+        PlaybackButton::Next => playback.frame = (playback.frame + 1).min(last_frame),
+        PlaybackButton::Last => playback.frame = last_frame,
+    }
+}
+
+fn update_label(
+    timeline: Res<Timeline>,
+    playback: Res<TimelinePlayback>,
+    mut text: Single<&mut Text, With<FrameLabel>>,
+) {
+    text.0 = if timeline.is_empty() {
+        "Nothing recorded yet".to_string()
+    } else {
+        format!("{} / {}", playback.frame + 1, timeline.len())
+    };
+}
+
+#[derive(Component)]
+struct FrameLabel;
+
+#[derive(Clone, Copy, Component, Debug)]
+enum PlaybackButton {
+    First,
+    Previous,
+    Play,
+    Next,
+    Last,
+}
+
+impl PlaybackButton {
+    fn glyph(self) -> &'static str {
+        match self {
+            Self::First => "⏮",
+            Self::Previous => "⏪",
+            Self::Play => "⏯",
+            Self::Next => "⏩",
+            Self::Last => "⏭",
+        }
+    }
+}
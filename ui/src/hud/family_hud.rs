@@ -1,4 +1,5 @@
 mod building_hud;
+mod events_node;
 mod info_node;
 mod members_node;
 mod portrait_node;
@@ -6,13 +7,14 @@ mod tasks_node;
 
 use bevy::prelude::*;
 use project_harmonia_base::{
-    asset::manifest::object_manifest::ObjectManifest,
+    asset::manifest::fence_manifest::FenceManifest,
     game_world::{
         actor::{
             task::{ActiveTask, Task},
             SelectedActor,
         },
-        family::{self, Budget, FamilyMembers, FamilyMode, SelectedFamily},
+        family::{Budget, FamilyMembers, FamilyMode, SelectedFamily},
+        time_speed::TimeSpeed,
         WorldState,
     },
 };
@@ -22,8 +24,11 @@ use project_harmonia_widgets::{
 };
 use strum::IntoEnumIterator;
 
+use super::speed_node;
 use building_hud::BuildingHudPlugin;
+use events_node::EventsNodePlugin;
 use info_node::InfoNodePlugin;
+use members_node::MembersNodePlugin;
 use portrait_node::PortraitNodePlugin;
 use tasks_node::TasksNodePlugin;
 
@@ -34,28 +39,42 @@ impl Plugin for FamilyHudPlugin {
         app.add_plugins((
             TasksNodePlugin,
             InfoNodePlugin,
+            MembersNodePlugin,
             PortraitNodePlugin,
             BuildingHudPlugin,
+            EventsNodePlugin,
         ))
-        .add_systems(OnEnter(WorldState::Family), setup.after(family::select));
+        .add_observer(setup);
     }
 }
 
+/// Spawns HUD for the selected family.
+///
+/// Respawns it when the player switches to another family.
 fn setup(
+    _trigger: Trigger<OnAdd, SelectedFamily>,
     mut commands: Commands,
     mut tab_commands: Commands,
     theme: Res<Theme>,
-    object_manifests: Res<Assets<ObjectManifest>>,
+    fence_manifests: Res<Assets<FenceManifest>>,
+    time_speed: Res<TimeSpeed>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
     actor_children: Single<&Children, With<SelectedActor>>,
     selected_family: Single<(&Budget, &FamilyMembers), With<SelectedFamily>>,
     selected_entity: Single<Entity, With<SelectedActor>>,
     tasks: Query<(Entity, Has<ActiveTask>), With<Task>>,
+    huds: Query<Entity, With<FamilyHud>>,
 ) {
+    for hud_entity in &huds {
+        debug!("despawning previous family hud");
+        commands.entity(hud_entity).despawn_recursive();
+    }
+
     debug!("showing family hud");
     commands.entity(*root_entity).with_children(|parent| {
         parent
             .spawn((
+                FamilyHud,
                 PickingBehavior::IGNORE,
                 StateScoped(WorldState::Family),
                 Node {
@@ -99,7 +118,7 @@ fn setup(
                                 parent,
                                 &mut tab_commands,
                                 &theme,
-                                &object_manifests,
+                                &fence_manifests,
                             ),
                         })
                         .id();
@@ -115,6 +134,8 @@ fn setup(
                         .set_parent(tabs_entity)
                         .observe(set_family_mode);
                 }
+
+                speed_node::setup(parent, &theme, *time_speed);
             });
     });
 }
@@ -128,3 +149,6 @@ fn set_family_mode(
     info!("changing family mode to `{mode:?}`");
     commands.set_state(mode);
 }
+
+#[derive(Component)]
+struct FamilyHud;
@@ -1,24 +1,34 @@
+mod fences_node;
 mod walls_node;
 
 use bevy::prelude::*;
 use project_harmonia_base::{
-    asset::manifest::object_manifest::{ObjectCategory, ObjectManifest},
-    game_world::family::{building::BuildingMode, FamilyMode},
+    asset::manifest::{fence_manifest::FenceManifest, object_manifest::ObjectCategory},
+    game_world::family::{
+        building::{walkthrough::WalkthroughStart, BuildingMode},
+        FamilyMode,
+    },
+    tutorial::TutorialStep,
 };
 use project_harmonia_widgets::{
     button::{ButtonKind, TabContent, Toggled},
+    label::LabelKind,
     theme::Theme,
 };
 use strum::IntoEnumIterator;
 
-use crate::hud::{objects_node, tools_node};
+use crate::{
+    hud::{objects_node, tools_node},
+    tutorial::TutorialTarget,
+};
+use fences_node::FencesNodePlugin;
 use walls_node::WallsNodePlugin;
 
 pub(super) struct BuildingHudPlugin;
 
 impl Plugin for BuildingHudPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(WallsNodePlugin)
+        app.add_plugins((FencesNodePlugin, WallsNodePlugin))
             .add_systems(OnEnter(FamilyMode::Building), sync_building_mode);
     }
 }
@@ -39,7 +49,7 @@ pub(super) fn setup(
     parent: &mut ChildBuilder,
     tab_commands: &mut Commands,
     theme: &Theme,
-    object_manifests: &Assets<ObjectManifest>,
+    fence_manifests: &Assets<FenceManifest>,
 ) {
     tools_node::setup(parent, theme);
 
@@ -68,29 +78,51 @@ pub(super) fn setup(
             ))
             .with_children(|parent| match mode {
                 BuildingMode::Objects => {
-                    objects_node::setup(
-                        parent,
-                        tab_commands,
-                        theme,
-                        object_manifests,
-                        ObjectCategory::FAMILY_CATEGORIES,
-                    );
+                    objects_node::setup(parent, theme, ObjectCategory::FAMILY_CATEGORIES);
                 }
                 BuildingMode::Walls => walls_node::setup(parent),
+                BuildingMode::Fences => fences_node::setup(parent, theme, fence_manifests),
+                BuildingMode::Pools => {
+                    parent.spawn((
+                        LabelKind::Small,
+                        Text::new("Drag to dig a pool, click a pool to resize or fill it"),
+                    ));
+                }
+                BuildingMode::Basements => {
+                    parent.spawn((
+                        LabelKind::Small,
+                        Text::new(
+                            "Drag to dig a basement with stairs, click a basement to resize or fill it",
+                        ),
+                    ));
+                }
             })
             .id();
 
-        tab_commands
-            .spawn((
-                mode,
-                ButtonKind::Symbol,
-                TabContent(content_entity),
-                Toggled(mode == Default::default()),
-            ))
+        let mut tab_entity = tab_commands.spawn((
+            mode,
+            ButtonKind::Symbol,
+            TabContent(content_entity),
+            Toggled(mode == Default::default()),
+        ));
+        if mode == BuildingMode::Walls {
+            tab_entity.insert(TutorialTarget(TutorialStep::BuildWall));
+        }
+        tab_entity
             .with_child(Text::new(mode.glyph()))
             .set_parent(tabs_entity)
             .observe(set_building_mode);
     }
+
+    tab_commands
+        .spawn(ButtonKind::Normal)
+        .with_child(Text::new("Walkthrough"))
+        .set_parent(tabs_entity)
+        .observe(start_walkthrough);
+}
+
+fn start_walkthrough(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(WalkthroughStart);
 }
 
 fn set_building_mode(
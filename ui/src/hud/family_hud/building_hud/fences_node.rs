@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use project_harmonia_base::{
+    asset::manifest::fence_manifest::FenceManifest,
+    game_world::family::building::{
+        fence::{placing_fence::SpawnFenceId, FenceTool},
+        BuildingMode,
+    },
+};
+use project_harmonia_widgets::{
+    button::{ButtonKind, ExclusiveButton, Toggled},
+    label::LabelKind,
+    popup::Popup,
+    theme::Theme,
+};
+use strum::IntoEnumIterator;
+
+pub(super) struct FencesNodePlugin;
+
+impl Plugin for FencesNodePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(BuildingMode::Fences), sync_fence_tool)
+            .add_systems(
+                Update,
+                (select, show_popup, set_fence_tool).run_if(in_state(BuildingMode::Fences)),
+            );
+    }
+}
+
+fn select(mut commands: Commands, buttons: Query<(&Toggled, &FenceButton), Changed<Toggled>>) {
+    for (toggled, fence_button) in &buttons {
+        if toggled.0 {
+            debug!("selecting fence `{:?}` for creation", fence_button.0);
+            commands.insert_resource(SpawnFenceId(fence_button.0));
+        }
+    }
+}
+
+fn show_popup(
+    mut commands: Commands,
+    manifests: Res<Assets<FenceManifest>>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    buttons: Query<(Entity, &Interaction, &FenceButton), Changed<Interaction>>,
+) {
+    for (button_entity, &interaction, &fence_button) in &buttons {
+        if interaction != Interaction::Hovered {
+            continue;
+        }
+
+        let manifest = manifests.get(*fence_button).unwrap();
+        info!("showing popup for fence '{}'", manifest.general.name);
+        commands.entity(*root_entity).with_children(|parent| {
+            parent
+                .spawn(Popup { button_entity })
+                .with_children(|parent| {
+                    parent
+                        .spawn((
+                            LabelKind::Normal,
+                            Text::new(manifest.general.name.clone() + "\n\n"),
+                        ))
+                        .with_child((
+                            LabelKind::Small,
+                            TextSpan::new(format!(
+                                "{}\n{}",
+                                manifest.general.license, manifest.general.author,
+                            )),
+                        ));
+                });
+        });
+    }
+}
+
+fn set_fence_tool(
+    mut commands: Commands,
+    buttons: Query<(Ref<Toggled>, &FenceTool), Changed<Toggled>>,
+) {
+    for (toggled, &mode) in &buttons {
+        if toggled.0 && !toggled.is_added() {
+            info!("changing fence tool to `{mode:?}`");
+            commands.set_state(mode);
+        }
+    }
+}
+
+/// Sets tool to the last selected.
+///
+/// Needed because on swithicng tab the tool resets, but selected button doesn't.
+fn sync_fence_tool(mut commands: Commands, buttons: Query<(&Toggled, &FenceTool)>) {
+    for (toggled, &mode) in &buttons {
+        if toggled.0 {
+            debug!("syncing fence tool to `{mode:?}`");
+            commands.set_state(mode);
+        }
+    }
+}
+
+pub(super) fn setup(parent: &mut ChildBuilder, theme: &Theme, manifests: &Assets<FenceManifest>) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for tool in FenceTool::iter() {
+                parent
+                    .spawn((
+                        tool,
+                        ButtonKind::Symbol,
+                        ExclusiveButton,
+                        Toggled(tool == Default::default()),
+                    ))
+                    .with_child(Text::new(tool.glyph()));
+            }
+        });
+
+    // Gates are assembled from the same modules, so the selection is shared between tools.
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: theme.gap.normal,
+            padding: theme.padding.normal,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for (id, manifest) in manifests.iter() {
+                parent
+                    .spawn(FenceButton(id))
+                    .with_child(Text::new(manifest.general.name.clone()));
+            }
+        });
+}
+
+#[derive(Component, Clone, Copy, Deref)]
+#[require(
+    Name(|| Name::new("Fence button")),
+    ButtonKind(|| ButtonKind::Normal),
+    ExclusiveButton
+)]
+struct FenceButton(AssetId<FenceManifest>);
@@ -0,0 +1,162 @@
+use std::{cmp::Reverse, time::Duration};
+
+use bevy::prelude::*;
+use project_harmonia_base::game_world::{
+    event_log::{self, EventCategory, GameEvent},
+    WorldState,
+};
+use project_harmonia_widgets::{
+    button::{ButtonKind, Toggled},
+    label::LabelKind,
+    scroll_view::ScrollView,
+    theme::Theme,
+    tooltip::Tooltip,
+};
+use strum::IntoEnumIterator;
+
+pub(super) struct EventsNodePlugin;
+
+impl Plugin for EventsNodePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            update_entries
+                .never_param_warn()
+                .run_if(in_state(WorldState::Family)),
+        );
+    }
+}
+
+/// Interval to refresh displayed event ages.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Maximum number of displayed events.
+const MAX_ENTRIES: usize = 50;
+
+/// Rebuilds the list on events or filters change.
+fn update_entries(
+    mut commands: Commands,
+    mut elapsed: Local<Duration>,
+    mut removed_events: RemovedComponents<GameEvent>,
+    time: Res<Time<Real>>,
+    event_list: Single<(Entity, Ref<EventList>)>,
+    filters: Query<(Ref<Toggled>, &CategoryFilter)>,
+    events: Query<Ref<GameEvent>>,
+) {
+    *elapsed += time.delta();
+    let (list_entity, event_list) = event_list.into_inner();
+    let changed = event_list.is_added()
+        || removed_events.read().count() != 0
+        || filters.iter().any(|(toggled, _)| toggled.is_changed())
+        || events.iter().any(|event| event.is_added());
+    if !changed && *elapsed < REFRESH_INTERVAL {
+        return;
+    }
+    *elapsed = Duration::ZERO;
+
+    let mut visible: Vec<_> = events
+        .iter()
+        .filter(|event| {
+            filters
+                .iter()
+                .any(|(toggled, filter)| toggled.0 && filter.0 == event.category)
+        })
+        .collect();
+    visible.sort_by_key(|event| Reverse(event.timestamp));
+
+    trace!("displaying {} events", visible.len());
+    let now = event_log::unix_timestamp();
+    commands
+        .entity(list_entity)
+        .despawn_descendants()
+        .with_children(|parent| {
+            if visible.is_empty() {
+                parent.spawn((
+                    LabelKind::Small,
+                    Text::new("No events"),
+                    Node {
+                        grid_column: GridPlacement::span(3),
+                        ..Default::default()
+                    },
+                ));
+            }
+
+            for event in visible.into_iter().take(MAX_ENTRIES) {
+                parent.spawn((LabelKind::Symbol, Text::new(event.category.glyph())));
+                parent.spawn((LabelKind::Small, Text::new(event.message.clone())));
+                parent.spawn((
+                    LabelKind::Small,
+                    Text::new(format_age(now.saturating_sub(event.timestamp))),
+                ));
+            }
+        });
+}
+
+/// Formats seconds as a short relative time.
+fn format_age(secs: u64) -> String {
+    match secs {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{} min ago", secs / 60),
+        3600..=86399 => format!("{} h ago", secs / 3600),
+        _ => format!("{} d ago", secs / 86400),
+    }
+}
+
+/// Spawns the node and returns its entity.
+pub(super) fn setup(parent: &mut ChildBuilder, theme: &Theme) -> Entity {
+    parent
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                width: Val::Px(400.0),
+                padding: theme.padding.normal,
+                row_gap: theme.gap.normal,
+                ..Default::default()
+            },
+            theme.panel_background,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for category in EventCategory::iter() {
+                        parent
+                            .spawn((
+                                CategoryFilter(category),
+                                ButtonKind::Symbol,
+                                Toggled(true),
+                                Tooltip(category.name().into()),
+                            ))
+                            .with_child(Text::new(category.glyph()));
+                    }
+                });
+
+            parent.spawn((
+                EventList,
+                ScrollView,
+                Node {
+                    display: Display::Grid,
+                    max_height: Val::Px(300.0),
+                    column_gap: theme.gap.normal,
+                    row_gap: theme.gap.normal,
+                    grid_template_columns: vec![
+                        GridTrack::auto(),
+                        GridTrack::flex(1.0),
+                        GridTrack::auto(),
+                    ],
+                    ..Default::default()
+                },
+            ));
+        })
+        .id()
+}
+
+/// Shows events of the category when toggled.
+#[derive(Component)]
+struct CategoryFilter(EventCategory);
+
+#[derive(Component)]
+struct EventList;
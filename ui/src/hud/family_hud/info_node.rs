@@ -1,30 +1,41 @@
 use bevy::prelude::*;
-use project_harmonia_base::game_world::{
-    actor::{
-        needs::{Need, NeedGlyph},
-        SelectedActor,
+use project_harmonia_base::{
+    game_world::{
+        actor::{
+            needs::{Need, NeedForecast, NeedGlyph, CRITICAL_NEED},
+            SelectedActor,
+        },
+        WorldState,
     },
-    WorldState,
+    tutorial::TutorialStep,
 };
 use project_harmonia_widgets::{
     button::{ButtonKind, TabContent, Toggled},
     label::LabelKind,
-    progress_bar::ProgressBar,
+    progress_bar::{ProgressBar, ProgressMarker, ProgressThresholds},
     theme::Theme,
 };
 use strum::{EnumIter, IntoEnumIterator};
 
+use super::events_node;
+use crate::tutorial::TutorialTarget;
+
 pub(super) struct InfoNodePlugin;
 
 impl Plugin for InfoNodePlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(cleanup_need_bars).add_systems(
             Update,
-            update_need_bars.run_if(in_state(WorldState::Family)),
+            (update_need_bars, update_forecasts)
+                .chain()
+                .run_if(in_state(WorldState::Family)),
         );
     }
 }
 
+/// Need value below which the bar is highlighted as a warning.
+const WARNING_NEED: f32 = 50.0;
+
 fn update_need_bars(
     mut commands: Commands,
     selected_actor: Single<(&Children, Ref<SelectedActor>)>,
@@ -56,12 +67,28 @@ fn update_need_bars(
             trace!("creating bar with `{need:?}` for `{entity}`");
             commands.entity(tab_content.0).with_children(|parent| {
                 parent.spawn((LabelKind::Symbol, Text::new(glyph.0)));
-                parent.spawn((BarNeed(entity), ProgressBar(need.0)));
+                parent.spawn((
+                    BarNeed(entity),
+                    ProgressBar(need.0),
+                    ProgressThresholds {
+                        warning: WARNING_NEED,
+                        critical: CRITICAL_NEED,
+                    },
+                    ProgressMarker::default(),
+                ));
             });
         }
     }
 }
 
+/// Moves forecast markers to the predicted need values after one in-game hour.
+fn update_forecasts(forecast: NeedForecast, mut markers: Query<(&mut ProgressMarker, &BarNeed)>) {
+    for (mut marker, bar_need) in &mut markers {
+        let value = forecast.in_hour(bar_need.0);
+        marker.set_if_neq(ProgressMarker(value));
+    }
+}
+
 fn cleanup_need_bars(
     trigger: Trigger<OnRemove, Need>,
     mut commands: Commands,
@@ -119,15 +146,19 @@ pub(super) fn setup(parent: &mut ChildBuilder, tab_commands: &mut Commands, them
                             theme.panel_background,
                         ))
                         .id(),
+                    InfoTab::Events => events_node::setup(parent, theme),
                 };
 
-                tab_commands
-                    .spawn((
-                        tab,
-                        ButtonKind::Symbol,
-                        TabContent(content_entity),
-                        Toggled(index == 0),
-                    ))
+                let mut tab_entity = tab_commands.spawn((
+                    tab,
+                    ButtonKind::Symbol,
+                    TabContent(content_entity),
+                    Toggled(index == 0),
+                ));
+                if tab == InfoTab::Needs {
+                    tab_entity.insert(TutorialTarget(TutorialStep::SatisfyHunger));
+                }
+                tab_entity
                     .with_child(Text::new(tab.glyph()))
                     .set_parent(tabs_entity);
             }
@@ -141,6 +172,7 @@ struct BarNeed(Entity);
 enum InfoTab {
     Skills,
     Needs,
+    Events,
 }
 
 impl InfoTab {
@@ -148,6 +180,7 @@ impl InfoTab {
         match self {
             InfoTab::Skills => "💡",
             InfoTab::Needs => "📈",
+            InfoTab::Events => "📰",
         }
     }
 }
@@ -1,5 +1,8 @@
 use bevy::prelude::*;
-use project_harmonia_base::game_world::{actor::SelectedActor, family::FamilyMembers};
+use project_harmonia_base::game_world::{
+    actor::{GroupedActor, SelectedActor},
+    family::FamilyMembers,
+};
 use project_harmonia_widgets::{
     button::{ButtonKind, ExclusiveButton, Toggled},
     theme::Theme,
@@ -7,6 +10,14 @@ use project_harmonia_widgets::{
 
 use crate::preview::Preview;
 
+pub(super) struct MembersNodePlugin;
+
+impl Plugin for MembersNodePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(show_grouped).add_observer(hide_grouped);
+    }
+}
+
 pub(super) fn setup(
     parent: &mut ChildBuilder,
     theme: &Theme,
@@ -27,17 +38,81 @@ pub(super) fn setup(
             for &entity in members.iter() {
                 parent
                     .spawn((
+                        MemberButton(entity),
                         ButtonKind::Image,
                         ExclusiveButton,
                         Toggled(entity == active_entity),
                     ))
                     .with_child(Preview::Actor(entity))
-                    .observe(
-                        move |_trigger: Trigger<Pointer<Click>>, mut commands: Commands, selected_entity: Single<Entity, With<SelectedActor>>| {
-                            commands.entity(*selected_entity).remove::<SelectedActor>();
-                            commands.entity(entity).insert(SelectedActor);
-                        },
-                    );
+                    .observe(select);
             }
         });
 }
+
+/// Selects the clicked member or adds it to the group if shift is held.
+fn select(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut buttons: Query<(&MemberButton, &mut Toggled)>,
+    selected_entity: Single<Entity, With<SelectedActor>>,
+    grouped_actors: Query<Entity, With<GroupedActor>>,
+) {
+    let (&member_button, mut toggled) = buttons.get_mut(trigger.entity()).unwrap();
+    if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        // Keep the toggle only for the selected member.
+        **toggled = *member_button == *selected_entity;
+        if *member_button == *selected_entity {
+            return;
+        }
+
+        if grouped_actors.get(*member_button).is_ok() {
+            info!("removing `{}` from group", *member_button);
+            commands.entity(*member_button).remove::<GroupedActor>();
+        } else {
+            info!("adding `{}` to group", *member_button);
+            commands.entity(*member_button).insert(GroupedActor);
+        }
+    } else {
+        for actor_entity in &grouped_actors {
+            commands.entity(actor_entity).remove::<GroupedActor>();
+        }
+        commands.entity(*selected_entity).remove::<SelectedActor>();
+        commands.entity(*member_button).insert(SelectedActor);
+    }
+}
+
+fn show_grouped(
+    trigger: Trigger<OnAdd, GroupedActor>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    buttons: Query<(Entity, &MemberButton)>,
+) {
+    if let Some((button_entity, _)) = buttons
+        .iter()
+        .find(|(_, member_button)| ***member_button == trigger.entity())
+    {
+        commands.entity(button_entity).insert(Outline::new(
+            Val::Px(2.0),
+            Val::ZERO,
+            theme.button.pressed_background.0,
+        ));
+    }
+}
+
+fn hide_grouped(
+    trigger: Trigger<OnRemove, GroupedActor>,
+    mut commands: Commands,
+    buttons: Query<(Entity, &MemberButton)>,
+) {
+    if let Some((button_entity, _)) = buttons
+        .iter()
+        .find(|(_, member_button)| ***member_button == trigger.entity())
+    {
+        commands.entity(button_entity).remove::<Outline>();
+    }
+}
+
+/// Family member associated with the portrait button.
+#[derive(Clone, Copy, Component, Deref)]
+struct MemberButton(Entity);
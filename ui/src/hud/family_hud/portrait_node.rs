@@ -1,9 +1,19 @@
 use bevy::prelude::*;
 use project_harmonia_base::game_world::{
-    family::{Budget, SelectedFamily},
+    city::{season::Calendar, weather::Weather, ActiveCity},
+    family::{Budget, Family, FamilyMode, FamilySwitch, SelectedFamily, TransactionLog},
+    players::ClaimedFamily,
     WorldState,
 };
-use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+use project_harmonia_widgets::{
+    button::ButtonKind,
+    label::LabelKind,
+    popup::{ManualClose, Popup},
+    theme::Theme,
+    tooltip::Tooltip,
+};
+
+use crate::hud::stats_menu::StatsMenuOpen;
 
 pub(super) struct PortraitNodePlugin;
 
@@ -11,38 +21,274 @@ impl Plugin for PortraitNodePlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            update_budget
+            (
+                animate_budget,
+                update_transactions,
+                update_season,
+                update_weather,
+            )
                 .never_param_warn()
                 .run_if(in_state(WorldState::Family)),
         );
     }
 }
 
-fn update_budget(
-    current_budget: Single<&Budget, (With<SelectedFamily>, Changed<Budget>)>,
-    mut budget_label: Single<&mut Text, With<BudgetLabel>>,
+/// Fraction of the remaining difference between the displayed and the actual budget
+/// that is rolled per second.
+const BUDGET_ROLL_SPEED: f32 = 4.0;
+
+/// Rolls the displayed budget towards the actual value.
+fn animate_budget(
+    time: Res<Time>,
+    current_budget: Single<(&Budget, Ref<SelectedFamily>)>,
+    budget_label: Single<(&mut Text, &mut BudgetLabel)>,
+) {
+    let (budget, selected_family) = current_budget.into_inner();
+    let (mut text, mut label) = budget_label.into_inner();
+    let target = **budget as f32;
+    if selected_family.is_added() {
+        label.displayed = target;
+    } else if label.displayed == target {
+        return;
+    } else {
+        let difference = target - label.displayed;
+        let step = (difference.abs() * BUDGET_ROLL_SPEED * time.delta_secs()).max(1.0);
+        if step >= difference.abs() {
+            debug!("changing budget to `{budget:?}`");
+            label.displayed = target;
+        } else {
+            label.displayed += step * difference.signum();
+        }
+    }
+
+    **text = (label.displayed.round() as u32).to_string();
+}
+
+fn toggle_transactions(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    log: Single<&TransactionLog, With<SelectedFamily>>,
+    popup_entity: Option<Single<Entity, With<TransactionsPopup>>>,
+) {
+    if let Some(popup_entity) = popup_entity {
+        info!("closing transactions");
+        commands.entity(*popup_entity).despawn_recursive();
+        return;
+    }
+
+    info!("showing transactions");
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                TransactionsPopup,
+                Popup {
+                    button_entity: trigger.entity(),
+                },
+                StateScoped(FamilyMode::Life),
+            ))
+            .with_children(|parent| setup_transactions(parent, &theme, &log));
+    });
+}
+
+fn toggle_families(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    families: Query<
+        (Entity, &Name),
+        (
+            With<Family>,
+            Without<ClaimedFamily>,
+            Without<SelectedFamily>,
+        ),
+    >,
+    popup_entity: Option<Single<Entity, With<FamiliesPopup>>>,
+) {
+    if let Some(popup_entity) = popup_entity {
+        info!("closing families");
+        commands.entity(*popup_entity).despawn_recursive();
+        return;
+    }
+
+    info!("showing families");
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                FamiliesPopup,
+                Popup {
+                    button_entity: trigger.entity(),
+                },
+                StateScoped(FamilyMode::Life),
+            ))
+            .with_children(|parent| {
+                if families.is_empty() {
+                    parent.spawn((LabelKind::Normal, Text::new("No other families")));
+                    return;
+                }
+
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        for (family_entity, name) in &families {
+                            parent
+                                .spawn((ButtonKind::Normal, FamilyButton(family_entity)))
+                                .with_child(Text::new(name.as_str()))
+                                .observe(switch_family);
+                        }
+                    });
+            });
+    });
+}
+
+fn switch_family(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    buttons: Query<&FamilyButton>,
+    popup_entity: Single<Entity, With<FamiliesPopup>>,
+) {
+    let family_button = buttons.get(trigger.entity()).unwrap();
+    commands.entity(*popup_entity).despawn_recursive();
+    commands.trigger(FamilySwitch(family_button.0));
+}
+
+fn update_transactions(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    log: Single<Ref<TransactionLog>, With<SelectedFamily>>,
+    popup_entity: Single<Entity, With<TransactionsPopup>>,
+) {
+    if !log.is_changed() {
+        return;
+    }
+
+    debug!("updating transactions");
+    commands
+        .entity(*popup_entity)
+        .despawn_descendants()
+        .with_children(|parent| setup_transactions(parent, &theme, &log));
+}
+
+fn setup_transactions(parent: &mut ChildBuilder, theme: &Theme, log: &TransactionLog) {
+    if log.is_empty() {
+        parent.spawn((LabelKind::Normal, Text::new("No transactions yet")));
+        return;
+    }
+
+    for transaction in log.iter().rev() {
+        parent
+            .spawn(Node {
+                column_gap: theme.gap.normal,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                parent.spawn((LabelKind::Symbol, Text::new(transaction.kind.glyph())));
+                parent.spawn((LabelKind::Normal, Text::new(&transaction.description)));
+                parent.spawn((
+                    LabelKind::Normal,
+                    Text::new(format!("{:+}", transaction.amount)),
+                ));
+            });
+    }
+}
+
+fn update_season(
+    calendar: Single<Ref<Calendar>, With<ActiveCity>>,
+    season_label: Single<(Ref<SeasonLabel>, &mut Text, &mut Tooltip)>,
 ) {
-    debug!("changing budget to `{:?}`", **current_budget);
-    ***budget_label = current_budget.to_string();
+    let (label, mut text, mut tooltip) = season_label.into_inner();
+    if !calendar.is_changed() && !label.is_added() {
+        return;
+    }
+
+    let season = calendar.season();
+    debug!("changing season to `{season:?}`");
+    **text = season.glyph().to_string();
+    tooltip.0 = format!("{}, day {}", season.name(), calendar.day_of_season());
+}
+
+fn update_weather(
+    weather: Single<Ref<Weather>, With<ActiveCity>>,
+    weather_label: Single<(Ref<WeatherLabel>, &mut Text, &mut Tooltip)>,
+) {
+    let (label, mut text, mut tooltip) = weather_label.into_inner();
+    if !weather.is_changed() && !label.is_added() {
+        return;
+    }
+
+    debug!("changing weather to `{:?}`", **weather);
+    **text = weather.glyph().to_string();
+    tooltip.0 = weather.name().to_string();
 }
 
 pub(super) fn setup(parent: &mut ChildBuilder, theme: &Theme, budget: Budget) {
     parent
         .spawn((
             Node {
-                width: Val::Px(180.0),
-                height: Val::Px(30.0),
+                width: Val::Px(300.0),
                 align_self: AlignSelf::FlexEnd,
                 align_items: AlignItems::Center,
+                column_gap: theme.gap.normal,
                 ..Default::default()
             },
             theme.panel_background,
         ))
         .with_children(|parent| {
-            parent.spawn((BudgetLabel, Text::new(budget.to_string())));
+            parent
+                .spawn(ButtonKind::Symbol)
+                .with_child(Text::new("📈"))
+                .observe(open_stats);
+            parent
+                .spawn(ButtonKind::Symbol)
+                .with_child(Text::new("🔄"))
+                .observe(toggle_families);
+            parent
+                .spawn(ButtonKind::Normal)
+                .with_child((
+                    BudgetLabel {
+                        displayed: *budget as f32,
+                    },
+                    Text::new(budget.to_string()),
+                ))
+                .observe(toggle_transactions);
+            parent.spawn(SeasonLabel);
+            parent.spawn(WeatherLabel);
         });
 }
 
+fn open_stats(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(StatsMenuOpen);
+}
+
+#[derive(Component)]
+struct BudgetLabel {
+    /// Currently displayed value, rolls towards the actual budget.
+    displayed: f32,
+}
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Transactions popup")), ManualClose)]
+struct TransactionsPopup;
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Families popup")), ManualClose)]
+struct FamiliesPopup;
+
+/// Family to switch to.
+#[derive(Component)]
+struct FamilyButton(Entity);
+
+#[derive(Component)]
+#[require(LabelKind(|| LabelKind::Symbol), Text, Tooltip(|| Tooltip(String::new())))]
+struct SeasonLabel;
+
 #[derive(Component)]
-#[require(LabelKind(|| LabelKind::Normal))]
-struct BudgetLabel;
+#[require(LabelKind(|| LabelKind::Symbol), Text, Tooltip(|| Tooltip(String::new())))]
+struct WeatherLabel;
@@ -1,6 +1,10 @@
+use std::cmp::Reverse;
+
 use bevy::prelude::*;
+use bevy_simple_text_input::TextInputValue;
+use strum::{EnumIter, IntoEnumIterator};
 
-use crate::preview::Preview;
+use crate::{preview::Preview, tutorial::TutorialTarget};
 use project_harmonia_base::{
     asset::manifest::object_manifest::{ObjectCategory, ObjectManifest},
     game_world::{
@@ -8,12 +12,15 @@ use project_harmonia_base::{
         family::FamilyMode,
         object::placing_object::PlacingObject,
     },
+    tutorial::TutorialStep,
 };
 use project_harmonia_widgets::{
-    button::{ButtonKind, ExclusiveButton, TabContent, Toggled},
+    button::{ButtonKind, Disabled, ExclusiveButton, Toggled},
+    dropdown::{Dropdown, SelectionChanged},
     label::LabelKind,
-    popup::Popup,
+    text_edit::ManualActivation,
     theme::Theme,
+    tooltip::Tooltip,
 };
 
 pub(super) struct ObjectsNodePlugin;
@@ -22,87 +29,97 @@ impl Plugin for ObjectsNodePlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(untoggle).add_systems(
             Update,
-            (show_popup, reload_buttons)
+            (
+                update_search.never_param_warn(),
+                update_grid.never_param_warn(),
+            )
+                .chain()
                 .run_if(in_state(CityMode::Objects).or(in_state(FamilyMode::Building))),
         );
     }
 }
 
-fn show_popup(
-    mut commands: Commands,
-    manifests: Res<Assets<ObjectManifest>>,
-    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
-    buttons: Query<(Entity, &Interaction, &ObjectButton), Changed<Interaction>>,
-) {
-    for (button_entity, &interaction, &button) in &buttons {
-        if interaction != Interaction::Hovered {
-            continue;
-        }
+/// Number of object buttons on a single page, fits 3 rows.
+const PAGE_SIZE: usize = 24;
+const COLUMNS: u16 = 8;
 
-        let manifest = manifests.get(*button).unwrap();
-        info!("showing popup for object '{}'", manifest.general.name);
-        commands.entity(*root_entity).with_children(|parent| {
-            parent
-                .spawn(Popup { button_entity })
-                .with_children(|parent| {
-                    parent
-                        .spawn((
-                            LabelKind::Normal,
-                            Text::new(manifest.general.name.clone() + "\n\n"),
-                        ))
-                        .with_child((
-                            LabelKind::Small,
-                            TextSpan::new(format!(
-                                "{}\n{}",
-                                manifest.general.license, manifest.general.author,
-                            )),
-                        ));
-                });
-        });
+fn update_search(
+    mut browser: Single<&mut ObjectBrowser>,
+    search: Single<&TextInputValue, (Changed<TextInputValue>, With<ObjectSearch>)>,
+) {
+    let search = search.0.trim().to_lowercase();
+    if browser.search != search {
+        debug!("searching objects for '{search}'");
+        browser.search = search;
+        browser.page = 0;
     }
 }
 
-fn reload_buttons(
+/// Displays the current page of objects that match the browser filters.
+fn update_grid(
     mut commands: Commands,
     mut change_events: EventReader<AssetEvent<ObjectManifest>>,
     manifests: Res<Assets<ObjectManifest>>,
-    buttons: Query<(Entity, &ObjectButton)>,
-    categories: Query<(&ObjectCategory, &TabContent)>,
+    mut browser: Single<&mut ObjectBrowser>,
+    grid_entity: Single<Entity, With<ObjectGrid>>,
+    mut page_text: Single<&mut Text, With<PageText>>,
+    placing_object: Option<Single<(Entity, &PlacingObject)>>,
+    page_buttons: Query<(Entity, &PageButton)>,
 ) {
-    for &event in change_events.read() {
-        let AssetEvent::Modified { id } = event else {
-            continue;
-        };
-
-        debug!("recreating button for asset {id}");
+    let manifests_changed = change_events.read().count() != 0;
+    if !browser.is_changed() && !manifests_changed {
+        return;
+    }
 
-        // Fully remove the button because category may change.
-        for (entity, &button) in &buttons {
-            if id == *button {
-                commands.entity(entity).despawn_recursive();
-                break;
-            }
-        }
+    let mut objects: Vec<_> = manifests
+        .iter()
+        .filter(|(_, manifest)| browser.matches(manifest))
+        .collect();
+    browser.sort.apply(&mut objects);
 
-        let manifest = manifests
-            .get(id)
-            .expect("manifest should always come from file");
+    let page_count = objects.len().div_ceil(PAGE_SIZE).max(1);
+    let page = browser.page.min(page_count - 1);
+    if browser.page != page {
+        browser.bypass_change_detection().page = page;
+    }
 
-        let tab_content = categories.iter().find_map(|(&category, &tab_content)| {
-            if category == manifest.category {
-                Some(tab_content)
-            } else {
-                None
+    debug!("showing page {page} of {} objects", objects.len());
+    let placing = placing_object.and_then(|placing_object| match *placing_object {
+        (entity, &PlacingObject::Spawning(id)) => Some((entity, id)),
+        (_, PlacingObject::Moving(_)) => None,
+    });
+    let mut placing_button = None;
+    commands
+        .entity(*grid_entity)
+        .despawn_descendants()
+        .with_children(|parent| {
+            for (id, manifest) in objects.into_iter().skip(page * PAGE_SIZE).take(PAGE_SIZE) {
+                let toggled = placing.is_some_and(|(_, placing_id)| placing_id == id);
+                let button_entity = parent
+                    .spawn((ObjectButton(id), Toggled(toggled), object_tooltip(manifest)))
+                    .with_child(Preview::Object(id))
+                    .observe(start_placing)
+                    .id();
+                if toggled {
+                    placing_button = Some(button_entity);
+                }
             }
         });
 
-        if let Some(tab_content) = tab_content {
-            commands.entity(tab_content.0).with_children(|parent| {
-                parent
-                    .spawn(ObjectButton(id))
-                    .with_child(Preview::Object(id))
-                    .observe(start_placing);
-            });
+    if let (Some((placing_entity, _)), Some(button_entity)) = (placing, placing_button) {
+        // Button was recreated, update the reference.
+        commands
+            .entity(placing_entity)
+            .insert(PlacingObjectButton(button_entity));
+    }
+
+    page_text.0 = format!("{}/{page_count}", page + 1);
+    for (entity, &direction) in &page_buttons {
+        let target = page.checked_add_signed(*direction as isize);
+        if target.is_some_and(|target| target < page_count) {
+            commands.entity(entity).remove::<Disabled>();
+        } else {
+            commands.entity(entity).insert(Disabled);
         }
     }
 }
@@ -125,53 +142,144 @@ fn untoggle(
 
 pub(super) fn setup(
     parent: &mut ChildBuilder,
-    tab_commands: &mut Commands,
     theme: &Theme,
-    manifests: &Assets<ObjectManifest>,
-    categories: &[ObjectCategory],
+    categories: &'static [ObjectCategory],
 ) {
-    let tabs_entity = parent
-        .spawn(Node {
-            flex_direction: FlexDirection::Column,
-            ..Default::default()
-        })
-        .id();
-
-    for (index, &category) in categories.iter().enumerate() {
-        let content_entity = parent
-            .spawn(Node {
-                display: Display::Grid,
+    parent
+        .spawn((
+            ObjectBrowser::new(categories),
+            Node {
                 column_gap: theme.gap.normal,
-                row_gap: theme.gap.normal,
-                padding: theme.padding.normal,
-                grid_template_columns: vec![GridTrack::auto(); 8],
                 ..Default::default()
-            })
-            .with_children(|parent| {
-                for (id, _) in manifests
-                    .iter()
-                    .filter(|(_, manifest)| manifest.category == category)
-                {
+            },
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
                     parent
-                        .spawn(ObjectButton(id))
-                        .with_child(Preview::Object(id))
-                        .observe(start_placing);
-                }
-            })
-            .id();
-
-        tab_commands
-            .spawn((
-                category,
-                ButtonKind::Symbol,
-                TabContent(content_entity),
-                Toggled(index == 0),
-            ))
-            .with_child(Text::new(category.glyph()))
-            .set_parent(tabs_entity);
+                        .spawn((
+                            CategoryChip(None),
+                            ButtonKind::Symbol,
+                            Toggled(true),
+                            Tooltip("All".into()),
+                        ))
+                        .with_child(Text::new("*"))
+                        .observe(select_category);
+                    for &category in categories {
+                        let mut chip_entity = parent.spawn((
+                            CategoryChip(Some(category)),
+                            ButtonKind::Symbol,
+                            Tooltip(category.text().into()),
+                        ));
+                        if category == ObjectCategory::Furniture {
+                            chip_entity.insert(TutorialTarget(TutorialStep::PlaceFurniture));
+                        }
+                        chip_entity
+                            .with_child(Text::new(category.glyph()))
+                            .observe(select_category);
+                    }
+                });
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(Node {
+                            column_gap: theme.gap.normal,
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(ObjectSearch);
+                            parent
+                                .spawn(Dropdown::from_enum(ObjectSort::default(), ObjectSort::text))
+                                .observe(select_sort);
+                        });
+
+                    parent.spawn((
+                        ObjectGrid,
+                        Node {
+                            display: Display::Grid,
+                            column_gap: theme.gap.normal,
+                            row_gap: theme.gap.normal,
+                            padding: theme.padding.normal,
+                            grid_template_columns: vec![GridTrack::auto(); COLUMNS.into()],
+                            ..Default::default()
+                        },
+                    ));
+
+                    parent
+                        .spawn(Node {
+                            column_gap: theme.gap.normal,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent
+                                .spawn((PageButton(-1), ButtonKind::Symbol))
+                                .with_child(Text::new("⬅"))
+                                .observe(switch_page);
+                            parent.spawn((PageText, LabelKind::Normal, Text::default()));
+                            parent
+                                .spawn((PageButton(1), ButtonKind::Symbol))
+                                .with_child(Text::new("➡"))
+                                .observe(switch_page);
+                        });
+                });
+        });
+}
+
+fn select_category(
+    trigger: Trigger<Pointer<Click>>,
+    mut browser: Single<&mut ObjectBrowser>,
+    chips: Query<&CategoryChip>,
+) {
+    let chip = *chips.get(trigger.entity()).unwrap();
+    debug!(
+        "filtering objects by {:?}",
+        chip.0.map(ObjectCategory::text)
+    );
+    browser.category = chip.0;
+    browser.page = 0;
+}
+
+fn select_sort(
+    trigger: Trigger<SelectionChanged>,
+    mut browser: Single<&mut ObjectBrowser>,
+    dropdowns: Query<&Dropdown>,
+) {
+    let dropdown = dropdowns.get(trigger.entity()).unwrap();
+    if let Some(sort) = dropdown.selected_enum() {
+        debug!("sorting objects by {}", ObjectSort::text(sort));
+        browser.sort = sort;
     }
 }
 
+fn switch_page(
+    trigger: Trigger<Pointer<Click>>,
+    mut browser: Single<&mut ObjectBrowser>,
+    buttons: Query<&PageButton>,
+) {
+    let direction = **buttons.get(trigger.entity()).unwrap();
+    // Upper bound will be clamped on update.
+    browser.page = browser.page.saturating_add_signed(direction as isize);
+}
+
+fn object_tooltip(manifest: &ObjectManifest) -> Tooltip {
+    Tooltip(format!(
+        "{}\n${}\n\n{}\n{}",
+        manifest.general.name, manifest.price, manifest.general.license, manifest.general.author,
+    ))
+}
+
 fn start_placing(
     trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
@@ -195,6 +303,90 @@ fn start_placing(
     });
 }
 
+/// Filters and pagination for objects.
+#[derive(Component)]
+struct ObjectBrowser {
+    /// Categories available in the current mode.
+    categories: &'static [ObjectCategory],
+    /// Selected category, all available categories if `None`.
+    category: Option<ObjectCategory>,
+    /// Lowercase text to search in names.
+    search: String,
+    sort: ObjectSort,
+    page: usize,
+}
+
+impl ObjectBrowser {
+    fn new(categories: &'static [ObjectCategory]) -> Self {
+        Self {
+            categories,
+            category: None,
+            search: Default::default(),
+            sort: Default::default(),
+            page: 0,
+        }
+    }
+
+    fn matches(&self, manifest: &ObjectManifest) -> bool {
+        let category_matches = match self.category {
+            Some(category) => category == manifest.category,
+            None => self.categories.contains(&manifest.category),
+        };
+
+        category_matches && manifest.general.name.to_lowercase().contains(&self.search)
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, EnumIter)]
+enum ObjectSort {
+    #[default]
+    Name,
+    PriceAscending,
+    PriceDescending,
+}
+
+impl ObjectSort {
+    fn text(self) -> &'static str {
+        match self {
+            ObjectSort::Name => "Name",
+            ObjectSort::PriceAscending => "Cheapest",
+            ObjectSort::PriceDescending => "Most expensive",
+        }
+    }
+
+    fn apply(self, objects: &mut [(AssetId<ObjectManifest>, &ObjectManifest)]) {
+        match self {
+            ObjectSort::Name => {
+                objects.sort_by(|(_, a), (_, b)| a.general.name.cmp(&b.general.name))
+            }
+            ObjectSort::PriceAscending => objects.sort_by_key(|(_, manifest)| manifest.price),
+            ObjectSort::PriceDescending => {
+                objects.sort_by_key(|(_, manifest)| Reverse(manifest.price))
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+#[require(ManualActivation)]
+struct ObjectSearch;
+
+/// Filters objects by category or shows all of them if `None`.
+#[derive(Component, Clone, Copy)]
+#[require(ExclusiveButton)]
+struct CategoryChip(Option<ObjectCategory>);
+
+#[derive(Component)]
+#[require(Node)]
+struct ObjectGrid;
+
+#[derive(Component)]
+struct PageText;
+
+/// Switches the page in the stored direction.
+#[derive(Component, Clone, Copy, Deref)]
+struct PageButton(i8);
+
 #[derive(Component, Clone, Copy, Deref)]
 #[require(ButtonKind(|| ButtonKind::Image), ExclusiveButton)]
 struct ObjectButton(AssetId<ObjectManifest>);
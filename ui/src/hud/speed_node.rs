@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+use project_harmonia_base::game_world::time_speed::{TimeSpeed, TimeSpeedRequest};
+use project_harmonia_widgets::{
+    button::{ButtonKind, ExclusiveButton, Toggled},
+    theme::Theme,
+};
+use strum::IntoEnumIterator;
+
+pub(super) struct SpeedNodePlugin;
+
+impl Plugin for SpeedNodePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                request_speed,
+                sync_speed.run_if(resource_changed::<TimeSpeed>),
+            ),
+        );
+    }
+}
+
+fn request_speed(
+    mut commands: Commands,
+    time_speed: Res<TimeSpeed>,
+    buttons: Query<(Ref<Toggled>, &SpeedButton), Changed<Toggled>>,
+) {
+    for (toggled, &speed_button) in &buttons {
+        if toggled.0 && !toggled.is_added() && *time_speed != *speed_button {
+            info!("requesting `{:?}` time speed", *speed_button);
+            commands.client_trigger(TimeSpeedRequest(*speed_button));
+        }
+    }
+}
+
+/// Toggles the button for speed changed by hotkeys or other players.
+fn sync_speed(time_speed: Res<TimeSpeed>, mut buttons: Query<(&mut Toggled, &SpeedButton)>) {
+    for (mut toggled, &speed_button) in &mut buttons {
+        if *speed_button == *time_speed && !toggled.0 {
+            debug!("syncing speed button to `{:?}`", *time_speed);
+            toggled.0 = true;
+        }
+    }
+}
+
+pub(super) fn setup(parent: &mut ChildBuilder, theme: &Theme, time_speed: TimeSpeed) {
+    parent
+        .spawn((
+            Name::new("Speed node"),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(50.0),
+                bottom: Val::Px(0.0),
+                padding: theme.padding.normal,
+                ..Default::default()
+            },
+            theme.panel_background,
+        ))
+        .with_children(|parent| {
+            for speed in TimeSpeed::iter() {
+                parent
+                    .spawn((SpeedButton(speed), Toggled(speed == time_speed)))
+                    .with_child(Text::new(speed.glyph()));
+            }
+        });
+}
+
+#[derive(Component, Clone, Copy, Deref)]
+#[require(ButtonKind(|| ButtonKind::Symbol), ExclusiveButton)]
+struct SpeedButton(TimeSpeed);
@@ -0,0 +1,191 @@
+use bevy::prelude::*;
+use strum::{EnumIter, IntoEnumIterator};
+
+use project_harmonia_base::{
+    core::GameState,
+    game_world::{
+        city::ActiveCity,
+        family::SelectedFamily,
+        stats::{CityStats, FamilyStats, Series},
+    },
+};
+use project_harmonia_widgets::{
+    button::ButtonKind, label::LabelKind, line_chart::LineChart, theme::Theme,
+};
+
+pub(super) struct StatsMenuPlugin;
+
+impl Plugin for StatsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(setup)
+            .add_systems(Update, update_charts.never_param_warn());
+    }
+}
+
+fn setup(
+    _trigger: Trigger<StatsMenuOpen>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    family_stats: Option<Single<&FamilyStats, With<SelectedFamily>>>,
+    city_stats: Option<Single<&CityStats, With<ActiveCity>>>,
+) {
+    info!("opening stats menu");
+    let family_stats = family_stats.as_deref().copied();
+    let city_stats = city_stats.as_deref().copied();
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                StatsMenu,
+                Node {
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    padding: theme.padding.global,
+                    row_gap: theme.gap.normal,
+                    ..Default::default()
+                },
+                theme.background_color,
+            ))
+            .with_children(|parent| {
+                parent.spawn((LabelKind::Large, Text::new("Statistics")));
+                parent
+                    .spawn(Node {
+                        display: Display::Grid,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        column_gap: theme.gap.large,
+                        row_gap: theme.gap.normal,
+                        align_items: AlignItems::Center,
+                        grid_template_columns: vec![
+                            GridTrack::auto(),
+                            GridTrack::px(250.0),
+                            GridTrack::flex(1.0),
+                        ],
+                        grid_auto_rows: vec![GridTrack::px(120.0)],
+                        align_content: AlignContent::FlexStart,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        for stat in Stat::iter() {
+                            let Some(series) = stat.series(family_stats, city_stats) else {
+                                continue;
+                            };
+
+                            parent.spawn((LabelKind::Normal, Text::new(stat.name())));
+                            parent.spawn((stat, LabelKind::Small, Text::new(summary(series))));
+                            parent.spawn((
+                                stat,
+                                LineChart(series.iter().copied().collect()),
+                                Node {
+                                    height: Val::Percent(100.0),
+                                    ..Default::default()
+                                },
+                            ));
+                        }
+                    });
+
+                parent
+                    .spawn(Node {
+                        width: Val::Percent(100.0),
+                        justify_content: JustifyContent::End,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent
+                            .spawn(ButtonKind::Normal)
+                            .with_child(Text::new("Close"))
+                            .observe(close);
+                    });
+            });
+    });
+}
+
+fn update_charts(
+    _menu_entity: Single<Entity, With<StatsMenu>>,
+    family_stats: Option<Single<Ref<FamilyStats>, With<SelectedFamily>>>,
+    city_stats: Option<Single<Ref<CityStats>, With<ActiveCity>>>,
+    mut charts: Query<(&Stat, &mut LineChart)>,
+    mut labels: Query<(&Stat, &mut Text)>,
+) {
+    let family_changed = family_stats
+        .as_ref()
+        .is_some_and(|stats| stats.is_changed());
+    let city_changed = city_stats.as_ref().is_some_and(|stats| stats.is_changed());
+    if !family_changed && !city_changed {
+        return;
+    }
+
+    debug!("updating stats charts");
+    let family_stats = family_stats.as_deref().map(|stats| &**stats);
+    let city_stats = city_stats.as_deref().map(|stats| &**stats);
+    for (&stat, mut chart) in &mut charts {
+        if let Some(series) = stat.series(family_stats, city_stats) {
+            chart.0 = series.iter().copied().collect();
+        }
+    }
+    for (&stat, mut text) in &mut labels {
+        if let Some(series) = stat.series(family_stats, city_stats) {
+            text.0 = summary(series);
+        }
+    }
+}
+
+/// Formats the latest value with its range.
+fn summary(series: &Series) -> String {
+    let Some(&last) = series.back() else {
+        return "No data yet".to_string();
+    };
+
+    let min = series.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = series.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    format!("{last:.0} (min {min:.0}, max {max:.0})")
+}
+
+fn close(
+    _trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    menu_entity: Single<Entity, With<StatsMenu>>,
+) {
+    info!("closing stats menu");
+    commands.entity(*menu_entity).despawn_recursive();
+}
+
+/// Creates a statistics menu node for the selected family and the active city.
+#[derive(Event)]
+pub(super) struct StatsMenuOpen;
+
+#[derive(Component)]
+#[require(StateScoped::<GameState>(|| StateScoped(GameState::InGame)))]
+struct StatsMenu;
+
+#[derive(Clone, Component, Copy, EnumIter)]
+enum Stat {
+    Budget,
+    Needs,
+    Population,
+}
+
+impl Stat {
+    fn name(self) -> &'static str {
+        match self {
+            Stat::Budget => "Budget",
+            Stat::Needs => "Average needs",
+            Stat::Population => "Population",
+        }
+    }
+
+    /// Returns the series if its owner is available.
+    fn series<'a>(
+        self,
+        family_stats: Option<&'a FamilyStats>,
+        city_stats: Option<&'a CityStats>,
+    ) -> Option<&'a Series> {
+        match self {
+            Stat::Budget => family_stats.map(|stats| &stats.budget),
+            Stat::Needs => family_stats.map(|stats| &stats.needs),
+            Stat::Population => city_stats.map(|stats| &stats.population),
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
 
-use project_harmonia_base::game_world::{
-    actor::task::{AvailableTasks, TaskSelect},
-    family::FamilyMode,
+use project_harmonia_base::{
+    game_world::{
+        actor::task::{AvailableTasks, TaskSelect},
+        family::FamilyMode,
+    },
+    settings::Settings,
 };
 use project_harmonia_widgets::{button::ButtonKind, label::LabelKind, theme::Theme};
 
@@ -107,10 +110,11 @@ struct TaskMenu;
 impl InputContext for TaskMenu {
     const PRIORITY: isize = 1;
 
-    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+    fn context_instance(world: &World, _entity: Entity) -> ContextInstance {
         let mut ctx = ContextInstance::default();
+        let settings = world.resource::<Settings>();
         ctx.bind::<CloseTaskMenu>()
-            .to((KeyCode::Escape, GamepadButton::East));
+            .to((KeyCode::Escape, &settings.gamepad.cancel));
         ctx
     }
 }
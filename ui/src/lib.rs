@@ -1,18 +1,48 @@
 mod camera_2d;
+mod click_sound;
+mod command_palette;
+mod debug_panel;
 mod error_dialog;
 mod hud;
+mod loading_screen;
 mod menu;
+mod nameplates;
+mod nav_mesh_stats;
+mod network_stats;
+mod notifications;
+mod pause_dialog;
+mod placement_hint;
 mod preview;
+mod profiler;
 mod root;
+mod shortcuts_overlay;
+mod text_input_priority;
+mod theme_settings;
+mod tutorial;
 
 use bevy::{app::PluginGroupBuilder, prelude::*};
 
 use camera_2d::Camera2dPlugin;
+use click_sound::ClickSoundPlugin;
+use command_palette::CommandPalettePlugin;
+use debug_panel::DebugPanelPlugin;
 use error_dialog::ErrorDialogPlugin;
 use hud::HudPlugin;
+use loading_screen::LoadingScreenPlugin;
 use menu::MenuPlugin;
+use nameplates::NameplatesPlugin;
+use nav_mesh_stats::NavMeshStatsPlugin;
+use network_stats::NetworkStatsPlugin;
+use notifications::NotificationsPlugin;
+use pause_dialog::PauseDialogPlugin;
+use placement_hint::PlacementHintPlugin;
 use preview::PreviewPlugin;
+use profiler::ProfilerPlugin;
 use root::RootPlugin;
+use shortcuts_overlay::ShortcutsOverlayPlugin;
+use text_input_priority::TextInputPriorityPlugin;
+use theme_settings::ThemeSettingsPlugin;
+use tutorial::TutorialPlugin;
 
 pub struct UiPlugins;
 
@@ -20,10 +50,25 @@ impl PluginGroup for UiPlugins {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
             .add(Camera2dPlugin)
+            .add(ClickSoundPlugin)
+            .add(CommandPalettePlugin)
+            .add(DebugPanelPlugin)
             .add(MenuPlugin)
             .add(ErrorDialogPlugin)
+            .add(NameplatesPlugin)
+            .add(PauseDialogPlugin)
+            .add(PlacementHintPlugin)
+            .add(NavMeshStatsPlugin)
+            .add(NetworkStatsPlugin)
+            .add(NotificationsPlugin)
             .add(HudPlugin)
+            .add(LoadingScreenPlugin)
             .add(PreviewPlugin)
+            .add(ProfilerPlugin)
             .add(RootPlugin)
+            .add(ShortcutsOverlayPlugin)
+            .add(TextInputPriorityPlugin)
+            .add(ThemeSettingsPlugin)
+            .add(TutorialPlugin)
     }
 }
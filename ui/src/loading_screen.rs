@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use project_harmonia_base::game_world::{loading::LoadingTracker, WorldState};
+use project_harmonia_widgets::{label::LabelKind, progress_bar::ProgressBar, theme::Theme};
+
+/// Displays progress from [`LoadingTracker`] while in [`WorldState::Loading`].
+pub(super) struct LoadingScreenPlugin;
+
+impl Plugin for LoadingScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(WorldState::Loading), setup)
+            .add_systems(
+                Update,
+                update_progress
+                    .never_param_warn()
+                    .run_if(in_state(WorldState::Loading))
+                    .run_if(resource_changed::<LoadingTracker>),
+            );
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+) {
+    info!("showing loading screen");
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                StateScoped(WorldState::Loading),
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: theme.gap.normal,
+                    ..Default::default()
+                },
+                theme.background_color,
+            ))
+            .with_children(|parent| {
+                parent.spawn((LabelKind::Large, Text::new("Loading...")));
+                parent.spawn((
+                    LoadingProgress,
+                    ProgressBar(0.0),
+                    Node {
+                        width: Val::Px(300.0),
+                        height: Val::Px(20.0),
+                        ..Default::default()
+                    },
+                ));
+            });
+    });
+}
+
+fn update_progress(
+    tracker: Res<LoadingTracker>,
+    mut progress_bar: Single<&mut ProgressBar, With<LoadingProgress>>,
+) {
+    progress_bar.0 = tracker.progress() * 100.0;
+}
+
+#[derive(Component)]
+struct LoadingProgress;
@@ -1,17 +1,21 @@
+mod achievements_menu;
 mod connection_dialog;
 mod editor_menu;
 mod ingame_menu;
 mod main_menu;
-mod settings_menu;
+mod menu_background;
+pub(super) mod settings_menu;
 mod world_browser;
 mod world_menu;
 
 use bevy::prelude::*;
 
+use achievements_menu::AchievementsMenuPlugin;
 use connection_dialog::ConnectionDialogPlugin;
 use editor_menu::EditorMenuPlugin;
 use ingame_menu::InGameMenuPlugin;
 use main_menu::MainMenuPlugin;
+use menu_background::MenuBackgroundPlugin;
 use project_harmonia_base::core::GameState;
 use settings_menu::SettingsMenuPlugin;
 use world_browser::WorldBrowserPlugin;
@@ -24,10 +28,12 @@ impl Plugin for MenuPlugin {
         app.add_sub_state::<MenuState>()
             .enable_state_scoped_entities::<MenuState>()
             .add_plugins((
+                AchievementsMenuPlugin,
                 ConnectionDialogPlugin,
                 EditorMenuPlugin,
                 InGameMenuPlugin,
                 MainMenuPlugin,
+                MenuBackgroundPlugin,
                 SettingsMenuPlugin,
                 WorldBrowserPlugin,
                 WorldMenuPlugin,
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use strum::IntoEnumIterator;
+
+use project_harmonia_base::achievements::{Achievement, Achievements};
+use project_harmonia_widgets::{
+    button::ButtonKind, label::LabelKind, progress_bar::ProgressBar, scroll_view::ScrollView,
+    theme::Theme,
+};
+
+pub(super) struct AchievementsMenuPlugin;
+
+impl Plugin for AchievementsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(setup);
+    }
+}
+
+fn setup(
+    _trigger: Trigger<AchievementsMenuOpen>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    achievements: Res<Achievements>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+) {
+    info!("opening achievements menu");
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                AchievementsMenu,
+                Node {
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    padding: theme.padding.global,
+                    row_gap: theme.gap.normal,
+                    ..Default::default()
+                },
+                theme.background_color,
+            ))
+            .with_children(|parent| {
+                parent.spawn((LabelKind::Large, Text::new("Achievements")));
+                parent
+                    .spawn((
+                        ScrollView,
+                        Node {
+                            display: Display::Grid,
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            column_gap: theme.gap.normal,
+                            row_gap: theme.gap.normal,
+                            align_items: AlignItems::Center,
+                            grid_template_columns: vec![
+                                GridTrack::auto(),
+                                GridTrack::auto(),
+                                GridTrack::flex(1.0),
+                                GridTrack::px(200.0),
+                                GridTrack::auto(),
+                            ],
+                            align_content: AlignContent::FlexStart,
+                            ..Default::default()
+                        },
+                    ))
+                    .with_children(|parent| {
+                        for achievement in Achievement::iter() {
+                            setup_achievement_row(parent, &achievements, achievement);
+                        }
+                    });
+
+                parent
+                    .spawn(Node {
+                        width: Val::Percent(100.0),
+                        justify_content: JustifyContent::End,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent
+                            .spawn(ButtonKind::Normal)
+                            .with_child(Text::new("Close"))
+                            .observe(close);
+                    });
+            });
+    });
+}
+
+fn setup_achievement_row(
+    parent: &mut ChildBuilder,
+    achievements: &Achievements,
+    achievement: Achievement,
+) {
+    let unlocked = achievements.is_unlocked(achievement);
+    let count = achievements
+        .count(achievement.counter())
+        .min(achievement.goal());
+
+    parent.spawn((LabelKind::Symbol, Text::new(achievement.glyph())));
+    parent.spawn((LabelKind::Normal, Text::new(achievement.name())));
+    parent.spawn((LabelKind::Small, Text::new(achievement.description())));
+    parent.spawn(ProgressBar(
+        count as f32 / achievement.goal() as f32 * 100.0,
+    ));
+    let status = if unlocked {
+        "✔".to_string()
+    } else {
+        format!("{count}/{}", achievement.goal())
+    };
+    parent.spawn((LabelKind::Normal, Text::new(status)));
+}
+
+fn close(
+    _trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    menu_entity: Single<Entity, With<AchievementsMenu>>,
+) {
+    info!("closing achievements menu");
+    commands.entity(*menu_entity).despawn_recursive();
+}
+
+/// Creates an achievements menu node.
+#[derive(Event)]
+pub(super) struct AchievementsMenuOpen;
+
+#[derive(Component)]
+struct AchievementsMenu;
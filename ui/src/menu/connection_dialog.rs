@@ -1,7 +1,11 @@
 use bevy::prelude::*;
 use bevy_replicon::prelude::*;
-use bevy_replicon_renet::renet::RenetClient;
+use bevy_replicon_renet::{
+    netcode::{NetcodeClientTransport, NetcodeDisconnectReason},
+    renet::RenetClient,
+};
 
+use project_harmonia_base::error_message::ErrorMessage;
 use project_harmonia_widgets::{
     button::ButtonKind, dialog::Dialog, label::LabelKind, theme::Theme,
 };
@@ -14,6 +18,7 @@ impl Plugin for ConnectionDialogPlugin {
             Update,
             (
                 show.run_if(client_started_connecting),
+                show_error.run_if(client_just_disconnected),
                 close
                     // Dialog may not be created if the connection happens instantly.
                     .never_param_warn()
@@ -59,6 +64,26 @@ fn cancel(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
     commands.remove_resource::<RenetClient>();
 }
 
+/// Reports the reason if the client was disconnected by the transport.
+fn show_error(mut commands: Commands, transport: Option<Res<NetcodeClientTransport>>) {
+    let Some(reason) = transport.and_then(|transport| transport.disconnect_reason()) else {
+        return;
+    };
+
+    error!("disconnected from server: {reason}");
+    let message = match reason {
+        NetcodeDisconnectReason::ConnectionRequestTimedOut
+        | NetcodeDisconnectReason::ConnectTokenExpired => {
+            "Unable to connect: the server is unreachable.".to_string()
+        }
+        NetcodeDisconnectReason::ConnectionDenied => {
+            "The server denied the connection.".to_string()
+        }
+        reason => format!("Disconnected from the server: {reason}."),
+    };
+    commands.trigger(ErrorMessage::new(message));
+}
+
 fn close(mut commands: Commands, dialog_entity: Single<Entity, With<ConnectionDialog>>) {
     info!("closing connection dialog");
     commands.entity(*dialog_entity).despawn_recursive();
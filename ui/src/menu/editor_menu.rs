@@ -1,27 +1,40 @@
-use std::mem;
+use std::{iter, mem};
 
-use bevy::prelude::*;
+use bevy::{asset::AssetPath, prelude::*};
 use bevy_replicon::prelude::*;
 use bevy_simple_text_input::TextInputValue;
-
-use crate::preview::{Preview, PreviewProcessed};
-use project_harmonia_base::game_world::{
-    city::City,
-    family::{
-        editor::{
-            EditorActor, EditorFamily, EditorFamilyReset, EditorFirstName, EditorLastName,
-            EditorSelectedActor, EditorSex, FamilyScene,
+use strum::IntoEnumIterator;
+
+use crate::preview::{self, Preview, PreviewProcessed};
+use project_harmonia_base::{
+    asset::manifest::clothing_manifest::{BodySlot, ClothingManifest},
+    game_paths::GamePaths,
+    game_world::{
+        actor::outfit::{ActiveOutfit, OutfitKind, Outfits},
+        city::City,
+        family::{
+            editor::{
+                EditorActor, EditorFamily, EditorFamilyRandomize, EditorFamilyReset,
+                EditorFirstName, EditorLastName, EditorSelectedActor, EditorSex, FamilyScene,
+            },
+            gallery::{
+                package::{PackageExport, PackageImport},
+                GalleryChanged, GalleryDelete, GalleryLoad, GallerySave,
+            },
+            Crest, FamilyAppearance, FamilyColor, FamilyCreate,
         },
-        FamilyCreate,
+        WorldState,
     },
-    WorldState,
 };
 use project_harmonia_widgets::{
     button::{ButtonKind, ExclusiveButton, Toggled},
-    dialog::Dialog,
+    dialog::{ConfirmationDialog, Dialog, DialogConfirmed},
+    dropdown::{Dropdown, SelectionChanged},
     label::LabelKind,
+    tab_view::{Tab, TabView},
     text_edit::TextEdit,
     theme::Theme,
+    tooltip::Tooltip,
 };
 
 pub(super) struct EditorMenuPlugin;
@@ -30,14 +43,16 @@ impl Plugin for EditorMenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(create_actor_buttons)
             .add_observer(remove_actor_buttons)
-            .add_observer(display_actor_data)
+            .add_observer(reload_gallery)
             .add_systems(OnEnter(WorldState::FamilyEditor), setup)
             .add_systems(
                 Update,
                 (
+                    display_actor_data.never_param_warn(),
                     apply_first_name.never_param_warn(),
                     apply_last_name.never_param_warn(),
                     update_previews,
+                    update_outfit_dropdowns.never_param_warn(),
                 )
                     .run_if(in_state(WorldState::FamilyEditor)),
             );
@@ -47,6 +62,10 @@ impl Plugin for EditorMenuPlugin {
 fn setup(
     mut commands: Commands,
     theme: Res<Theme>,
+    asset_server: Res<AssetServer>,
+    clothing_manifests: Res<Assets<ClothingManifest>>,
+    game_paths: Res<GamePaths>,
+    mut images: ResMut<Assets<Image>>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
 ) {
     info!("entering family editor");
@@ -61,7 +80,14 @@ fn setup(
                 },
             ))
             .with_children(|parent| {
-                setup_personality_node(parent, &theme);
+                setup_actor_node(
+                    parent,
+                    &theme,
+                    &asset_server,
+                    &clothing_manifests,
+                    &game_paths,
+                    &mut images,
+                );
                 setup_actors_node(parent, &theme);
                 setup_family_menu_buttons(parent, &theme);
             });
@@ -113,23 +139,39 @@ fn remove_actor_buttons(
     }
 }
 
-// Updates UI with parameters of the current actor.
+/// Updates UI with parameters of the current actor.
+///
+/// Runs when the actor is switched or its parameters are changed outside of the UI.
 fn display_actor_data(
-    trigger: Trigger<OnAdd, EditorSelectedActor>,
-    actors: Query<(&EditorSex, &EditorFirstName, &EditorLastName)>,
+    actor: Single<(
+        Ref<EditorSex>,
+        Ref<EditorFirstName>,
+        Ref<EditorLastName>,
+        Ref<EditorSelectedActor>,
+    )>,
     mut sex_buttons: Query<(&mut Toggled, &EditorSex), Without<ActorButton>>,
-    mut first_name_edits: Query<&mut TextInputValue, With<FirstNameEdit>>,
-    mut last_name_edits: Query<&mut TextInputValue, (With<LastNameEdit>, Without<FirstNameEdit>)>,
+    mut first_name_edit: Single<&mut TextInputValue, With<FirstNameEdit>>,
+    mut last_name_edit: Single<&mut TextInputValue, (With<LastNameEdit>, Without<FirstNameEdit>)>,
 ) {
-    let (&actor_sex, first_name, last_name) = actors.get(trigger.entity()).unwrap();
-    first_name_edits.single_mut().0.clone_from(first_name);
-    last_name_edits.single_mut().0.clone_from(last_name);
+    let (actor_sex, first_name, last_name, selected) = actor.into_inner();
+
+    // Compare to avoid resetting the text that is currently typed.
+    if (first_name.is_changed() || selected.is_added()) && first_name_edit.0 != first_name.0 {
+        first_name_edit.0.clone_from(&first_name);
+    }
+    if (last_name.is_changed() || selected.is_added()) && last_name_edit.0 != last_name.0 {
+        last_name_edit.0.clone_from(&last_name);
+    }
 
-    let (mut sex_toggled, ..) = sex_buttons
-        .iter_mut()
-        .find(|(_, &sex)| sex == actor_sex)
-        .expect("sex buttons should be spawned for each variant");
-    sex_toggled.0 = true;
+    if actor_sex.is_changed() || selected.is_added() {
+        let (mut sex_toggled, ..) = sex_buttons
+            .iter_mut()
+            .find(|(_, &sex)| sex == *actor_sex)
+            .expect("sex buttons should be spawned for each variant");
+        if !sex_toggled.0 {
+            sex_toggled.0 = true;
+        }
+    }
 }
 
 fn apply_first_name(
@@ -179,11 +221,18 @@ fn update_previews(
     }
 }
 
-fn setup_personality_node(parent: &mut ChildBuilder, theme: &Theme) {
+fn setup_actor_node(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    asset_server: &AssetServer,
+    clothing_manifests: &Assets<ClothingManifest>,
+    game_paths: &GamePaths,
+    images: &mut Assets<Image>,
+) {
     parent
         .spawn((
+            TabView,
             Node {
-                flex_direction: FlexDirection::Column,
                 position_type: PositionType::Absolute,
                 padding: theme.padding.normal,
                 row_gap: theme.gap.normal,
@@ -193,38 +242,200 @@ fn setup_personality_node(parent: &mut ChildBuilder, theme: &Theme) {
         ))
         .with_children(|parent| {
             parent
-                .spawn((Node {
-                    display: Display::Grid,
-                    column_gap: theme.gap.normal,
-                    row_gap: theme.gap.normal,
-                    grid_template_columns: vec![GridTrack::auto(); 2],
-                    ..Default::default()
-                },))
+                .spawn((
+                    Tab("Personality".to_string()),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                ))
+                .with_children(|parent| setup_personality_tab(parent, theme));
+            parent
+                .spawn((
+                    Tab("Outfit".to_string()),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                ))
                 .with_children(|parent| {
-                    parent.spawn((LabelKind::Normal, Text::new("First name")));
-                    parent.spawn(FirstNameEdit);
-                    parent.spawn((LabelKind::Normal, Text::new("Last name")));
-                    parent.spawn(LastNameEdit);
+                    setup_outfit_tab(parent, theme, asset_server, clothing_manifests)
                 });
+            parent
+                .spawn((
+                    Tab("Gallery".to_string()),
+                    GalleryNode,
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                ))
+                .with_children(|parent| setup_gallery_entries(parent, theme, game_paths, images));
+        });
+}
+
+fn setup_personality_tab(parent: &mut ChildBuilder, theme: &Theme) {
+    parent
+        .spawn((Node {
+            display: Display::Grid,
+            column_gap: theme.gap.normal,
+            row_gap: theme.gap.normal,
+            grid_template_columns: vec![GridTrack::auto(); 2],
+            ..Default::default()
+        },))
+        .with_children(|parent| {
+            parent.spawn((LabelKind::Normal, Text::new("First name")));
+            parent.spawn(FirstNameEdit);
+            parent.spawn((LabelKind::Normal, Text::new("Last name")));
+            parent.spawn(LastNameEdit);
+        });
 
-            parent.spawn(Node::default()).with_children(|parent| {
+    parent.spawn(Node::default()).with_children(|parent| {
+        parent
+            .spawn((
+                EditorSex::Male,
+                ButtonKind::Normal,
+                ExclusiveButton,
+                Toggled(true),
+            ))
+            .with_child(Text::new("Male"))
+            .observe(apply_sex);
+        parent
+            .spawn((EditorSex::Female, ButtonKind::Normal, ExclusiveButton))
+            .with_child(Text::new("Female"))
+            .observe(apply_sex);
+    });
+}
+
+fn setup_outfit_tab(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    asset_server: &AssetServer,
+    clothing_manifests: &Assets<ClothingManifest>,
+) {
+    parent
+        .spawn((
+            OutfitKindDropdown,
+            Dropdown::from_enum(OutfitKind::default(), OutfitKind::text),
+        ))
+        .observe(select_outfit_kind);
+
+    parent
+        .spawn(Node {
+            display: Display::Grid,
+            column_gap: theme.gap.normal,
+            row_gap: theme.gap.normal,
+            grid_template_columns: vec![GridTrack::auto(); 2],
+            align_items: AlignItems::Center,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for slot in BodySlot::iter() {
+                let mut items: Vec<_> = clothing_manifests
+                    .iter()
+                    .filter(|(_, manifest)| manifest.slots.contains(&slot))
+                    .filter_map(|(id, manifest)| {
+                        let path = asset_server.get_path(id)?.into_owned();
+                        Some((path, manifest.general.name.as_str()))
+                    })
+                    .collect();
+                items.sort_by_key(|&(_, name)| name);
+
+                let options = iter::once("None").chain(items.iter().map(|&(_, name)| name));
+                parent.spawn((LabelKind::Normal, Text::new(slot.text())));
                 parent
                     .spawn((
-                        EditorSex::Male,
-                        ButtonKind::Normal,
-                        ExclusiveButton,
-                        Toggled(true),
+                        SlotDropdown {
+                            slot,
+                            items: items.iter().map(|(path, _)| path.clone()).collect(),
+                        },
+                        Dropdown::new(options, 0),
                     ))
-                    .with_child(Text::new("Male"))
-                    .observe(apply_sex);
-                parent
-                    .spawn((EditorSex::Female, ButtonKind::Normal, ExclusiveButton))
-                    .with_child(Text::new("Female"))
-                    .observe(apply_sex);
-            });
+                    .observe(select_clothing);
+            }
         });
 }
 
+fn select_outfit_kind(
+    trigger: Trigger<SelectionChanged>,
+    dropdowns: Query<&Dropdown>,
+    mut active_outfit: Single<&mut ActiveOutfit, With<EditorSelectedActor>>,
+) {
+    let dropdown = dropdowns.get(trigger.entity()).unwrap();
+    let kind = dropdown
+        .selected_enum()
+        .expect("dropdown should be created from outfit kinds");
+    info!("switching to {kind:?} outfit");
+    active_outfit.0 = kind;
+}
+
+fn select_clothing(
+    trigger: Trigger<SelectionChanged>,
+    asset_server: Res<AssetServer>,
+    clothing_manifests: Res<Assets<ClothingManifest>>,
+    dropdowns: Query<&SlotDropdown>,
+    actor: Single<(&mut Outfits, &ActiveOutfit), With<EditorSelectedActor>>,
+) {
+    let slot_dropdown = dropdowns.get(trigger.entity()).unwrap();
+    let (mut outfits, active_outfit) = actor.into_inner();
+    let outfit = outfits.get_mut(**active_outfit);
+
+    // The first option is reserved for an empty slot.
+    match trigger
+        .0
+        .checked_sub(1)
+        .and_then(|index| slot_dropdown.items.get(index))
+    {
+        Some(item_path) => {
+            let manifest = asset_server
+                .get_handle(item_path)
+                .and_then(|handle| clothing_manifests.get(&handle))
+                .expect("clothing manifests should be preloaded");
+            info!("putting on '{item_path}'");
+            outfit.put_on(item_path.clone(), &manifest.slots);
+        }
+        None => {
+            info!("taking off {:?}", slot_dropdown.slot);
+            outfit.take_off(slot_dropdown.slot);
+        }
+    }
+}
+
+/// Shows the outfit of the selected actor.
+///
+/// Changes to other slots are displayed too since items can cover multiple slots.
+fn update_outfit_dropdowns(
+    actor: Single<(Ref<Outfits>, Ref<ActiveOutfit>, Ref<EditorSelectedActor>)>,
+    mut kind_dropdown: Single<&mut Dropdown, With<OutfitKindDropdown>>,
+    mut slot_dropdowns: Query<(&SlotDropdown, &mut Dropdown), Without<OutfitKindDropdown>>,
+) {
+    let (outfits, active_outfit, selected) = actor.into_inner();
+    if !outfits.is_changed() && !active_outfit.is_changed() && !selected.is_added() {
+        return;
+    }
+
+    let kind_index = OutfitKind::iter()
+        .position(|kind| kind == **active_outfit)
+        .unwrap_or_default();
+    if kind_dropdown.selected != kind_index {
+        kind_dropdown.selected = kind_index;
+    }
+
+    let outfit = outfits.get(**active_outfit);
+    for (slot_dropdown, mut dropdown) in &mut slot_dropdowns {
+        let index = outfit
+            .item(slot_dropdown.slot)
+            .and_then(|path| slot_dropdown.items.iter().position(|item| item == path))
+            .map_or(0, |index| index + 1);
+        if dropdown.selected != index {
+            dropdown.selected = index;
+        }
+    }
+}
+
 fn apply_sex(
     trigger: Trigger<Pointer<Click>>,
     mut actor_sex: Single<&mut EditorSex, With<EditorSelectedActor>>,
@@ -235,6 +446,154 @@ fn apply_sex(
     **actor_sex = button_sex;
 }
 
+fn setup_gallery_entries(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    game_paths: &GamePaths,
+    images: &mut Assets<Image>,
+) {
+    parent
+        .spawn(ButtonKind::Normal)
+        .with_child(Text::new("Import packages"))
+        .observe(import_packages);
+
+    let names = match game_paths.get_family_names() {
+        Ok(names) => names,
+        Err(e) => {
+            error!("unable to read gallery: {e:#}");
+            return;
+        }
+    };
+
+    if names.is_empty() {
+        parent.spawn((LabelKind::Normal, Text::new("No saved families")));
+        return;
+    }
+
+    for name in names {
+        parent
+            .spawn(Node {
+                align_items: AlignItems::Center,
+                column_gap: theme.gap.normal,
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                let preview_path = game_paths.family_preview_path(&name);
+                let image = preview_path
+                    .exists()
+                    .then(|| preview::load_image(&preview_path))
+                    .and_then(|result| {
+                        result
+                            .inspect_err(|e| error!("unable to load family preview: {e:#}"))
+                            .ok()
+                    });
+                if let Some(image) = image {
+                    parent.spawn((
+                        ImageNode::new(images.add(image)),
+                        Node {
+                            width: theme.button.image.image_width,
+                            height: theme.button.image.image_height,
+                            ..Default::default()
+                        },
+                    ));
+                } else {
+                    parent.spawn((LabelKind::Symbol, Text::new("👪")));
+                }
+                parent.spawn((LabelKind::Normal, Text::new(name.clone())));
+                parent
+                    .spawn((GalleryButton(name.clone()), ButtonKind::Normal))
+                    .with_child(Text::new("Load"))
+                    .observe(load_family);
+                parent
+                    .spawn((
+                        GalleryButton(name.clone()),
+                        ButtonKind::Symbol,
+                        Tooltip("Export package".to_string()),
+                    ))
+                    .with_child(Text::new("📦"))
+                    .observe(export_family);
+                parent
+                    .spawn((
+                        GalleryButton(name),
+                        ButtonKind::Symbol,
+                        Tooltip("Delete".to_string()),
+                    ))
+                    .with_child(Text::new("🗑"))
+                    .observe(delete_family);
+            });
+    }
+}
+
+fn reload_gallery(
+    _trigger: Trigger<GalleryChanged>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    game_paths: Res<GamePaths>,
+    mut images: ResMut<Assets<Image>>,
+    gallery_entity: Single<Entity, With<GalleryNode>>,
+) {
+    debug!("reloading gallery");
+    commands
+        .entity(*gallery_entity)
+        .despawn_descendants()
+        .with_children(|parent| setup_gallery_entries(parent, &theme, &game_paths, &mut images));
+}
+
+fn load_family(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    buttons: Query<&GalleryButton>,
+) {
+    let gallery_button = buttons.get(trigger.entity()).unwrap();
+    commands.trigger(GalleryLoad(gallery_button.0.clone()));
+}
+
+fn export_family(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    buttons: Query<&GalleryButton>,
+) {
+    let gallery_button = buttons.get(trigger.entity()).unwrap();
+    commands.trigger(PackageExport(gallery_button.0.clone()));
+}
+
+fn import_packages(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(PackageImport);
+}
+
+fn delete_family(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    buttons: Query<&GalleryButton>,
+) {
+    let gallery_button = buttons.get(trigger.entity()).unwrap();
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                gallery_button.clone(),
+                ConfirmationDialog::new(
+                    "Delete family",
+                    format!(
+                        "Are you sure you want to delete family {} from the gallery?",
+                        gallery_button.0
+                    ),
+                )
+                .with_confirm_text("Delete"),
+            ))
+            .observe(confirm_delete);
+    });
+}
+
+fn confirm_delete(
+    trigger: Trigger<DialogConfirmed>,
+    mut commands: Commands,
+    dialogs: Query<&GalleryButton>,
+) {
+    let gallery_button = dialogs.get(trigger.entity()).unwrap();
+    commands.trigger(GalleryDelete(gallery_button.0.clone()));
+}
+
 fn setup_actors_node(parent: &mut ChildBuilder, theme: &Theme) {
     parent
         .spawn((
@@ -260,6 +619,10 @@ fn setup_actors_node(parent: &mut ChildBuilder, theme: &Theme) {
                 .spawn(ButtonKind::Symbol)
                 .with_child(Text::new("➕"))
                 .observe(add_actor);
+            parent
+                .spawn((ButtonKind::Symbol, Tooltip("Randomize".to_string())))
+                .with_child(Text::new("🎲"))
+                .observe(randomize);
         });
 }
 
@@ -278,6 +641,10 @@ fn add_actor(
     });
 }
 
+fn randomize(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(EditorFamilyRandomize);
+}
+
 fn setup_family_menu_buttons(parent: &mut ChildBuilder, theme: &Theme) {
     parent
         .spawn(Node {
@@ -305,9 +672,10 @@ fn confirm_family(
     mut commands: Commands,
     theme: Res<Theme>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    appearance: Single<&FamilyAppearance, With<EditorFamily>>,
 ) {
     commands.entity(*root_entity).with_children(|parent| {
-        setup_save_family_dialog(parent, &theme);
+        setup_save_family_dialog(parent, &theme, **appearance);
     });
 }
 
@@ -315,7 +683,11 @@ fn cancel_family(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
     commands.set_state(WorldState::World);
 }
 
-fn setup_save_family_dialog(parent: &mut ChildBuilder, theme: &Theme) {
+fn setup_save_family_dialog(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    appearance: FamilyAppearance,
+) {
     info!("showing save family dialog");
     parent.spawn(Dialog).with_children(|parent| {
         parent
@@ -338,6 +710,37 @@ fn setup_save_family_dialog(parent: &mut ChildBuilder, theme: &Theme) {
                     TextEdit,
                     TextInputValue("New family".to_string()),
                 ));
+                parent
+                    .spawn(Node {
+                        column_gap: theme.gap.normal,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        for color in FamilyColor::iter() {
+                            parent
+                                .spawn((ColorButton(color), Toggled(color == appearance.color)))
+                                .with_child((
+                                    Node {
+                                        width: Val::Percent(100.0),
+                                        height: Val::Percent(100.0),
+                                        ..Default::default()
+                                    },
+                                    BackgroundColor(color.color()),
+                                ));
+                        }
+                    });
+                parent
+                    .spawn(Node {
+                        column_gap: theme.gap.normal,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        for crest in Crest::iter() {
+                            parent
+                                .spawn((CrestButton(crest), Toggled(crest == appearance.crest)))
+                                .with_child(Text::new(crest.glyph()));
+                        }
+                    });
                 parent
                     .spawn(Node {
                         column_gap: theme.gap.normal,
@@ -365,8 +768,24 @@ fn save_family(
     family_name: Single<&TextInputValue, With<FamilyNameEdit>>,
     dialog_entity: Single<Entity, With<Dialog>>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    mut family_appearance: Single<&mut FamilyAppearance, With<EditorFamily>>,
+    color_buttons: Query<(&Toggled, &ColorButton)>,
+    crest_buttons: Query<(&Toggled, &CrestButton)>,
 ) {
-    commands.insert_resource(FamilyScene::new(family_name.0.clone()));
+    if let Some(color) = color_buttons
+        .iter()
+        .find_map(|(toggled, button)| toggled.then_some(button.0))
+    {
+        family_appearance.color = color;
+    }
+    if let Some(crest) = crest_buttons
+        .iter()
+        .find_map(|(toggled, button)| toggled.then_some(button.0))
+    {
+        family_appearance.crest = crest;
+    }
+
+    commands.insert_resource(FamilyScene::new(family_name.0.clone(), **family_appearance));
     commands.entity(*root_entity).with_children(|parent| {
         setup_place_family_dialog(parent, &theme, &cities);
     });
@@ -439,6 +858,10 @@ fn setup_place_family_dialog(
                             ..Default::default()
                         })
                         .with_children(|parent| {
+                            parent
+                                .spawn(ButtonKind::Normal)
+                                .with_child(Text::new("Save to gallery"))
+                                .observe(save_to_gallery);
                             parent
                                 .spawn(ButtonKind::Normal)
                                 .with_child(Text::new("Create new"))
@@ -489,6 +912,34 @@ fn place(
     commands.trigger(EditorFamilyReset);
 }
 
+/// Saves the family with the preview of its first actor as a thumbnail.
+fn save_to_gallery(
+    _trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    game_paths: Res<GamePaths>,
+    family_scene: Res<FamilyScene>,
+    images: Res<Assets<Image>>,
+    actors_children: Single<&Children, With<ActorsNode>>,
+    buttons: Query<&Children, With<ActorButton>>,
+    previews: Query<&ImageNode, With<PreviewProcessed>>,
+) {
+    commands.trigger(GallerySave);
+
+    let Some(image_node) = buttons
+        .iter_many(*actors_children)
+        .next()
+        .and_then(|children| previews.iter_many(children).next())
+    else {
+        return;
+    };
+    let Some(image) = images.get(&image_node.image) else {
+        return;
+    };
+
+    let path = game_paths.family_preview_path(&family_scene.name);
+    preview::save_to_disk(&mut commands, image_node.image.clone(), image.size(), path);
+}
+
 fn create_new(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
@@ -531,6 +982,33 @@ struct ActorButton(Entity);
 #[derive(Component)]
 struct FamilyNameEdit;
 
+#[derive(Component, Clone, Copy)]
+#[require(ButtonKind(|| ButtonKind::Symbol), ExclusiveButton)]
+struct ColorButton(FamilyColor);
+
+#[derive(Component, Clone, Copy)]
+#[require(ButtonKind(|| ButtonKind::Symbol), ExclusiveButton)]
+struct CrestButton(Crest);
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Gallery node")))]
+struct GalleryNode;
+
+/// Stores the name of a family from the gallery.
+#[derive(Component, Clone)]
+struct GalleryButton(String);
+
+#[derive(Component)]
+struct OutfitKindDropdown;
+
+/// Selects clothing for a body slot of the current outfit.
+#[derive(Component)]
+struct SlotDropdown {
+    slot: BodySlot,
+    /// Manifest paths for options after "None".
+    items: Vec<AssetPath<'static>>,
+}
+
 #[derive(Component)]
 #[require(Name(|| Name::new("Place city button")), ButtonKind(|| ButtonKind::Normal))]
 struct PlaceCityButton {
@@ -1,14 +1,29 @@
+use anyhow::{Context, Result};
 use bevy::{app::AppExit, prelude::*};
 use bevy_enhanced_input::prelude::*;
+use bevy_replicon::prelude::*;
+use bevy_simple_text_input::TextInputValue;
 use project_harmonia_base::{
     core::GameState,
-    game_world::{GameSave, WorldState},
+    error_message::error_message,
+    game_world::{
+        time_speed::{TimeSpeed, TimeSpeedRequest},
+        world_management::WorldManagement,
+        GameSave, SnapshotSave, WorldName, WorldState,
+    },
+    network::migration::HostLeave,
+    settings::Settings,
 };
 use project_harmonia_widgets::{
-    button::ButtonKind, dialog::Dialog, label::LabelKind, theme::Theme,
+    button::ButtonKind,
+    dialog::{ConfirmationDialog, Dialog, DialogConfirmed},
+    label::LabelKind,
+    scroll_view::ScrollView,
+    text_edit::TextEdit,
+    theme::Theme,
 };
 
-use super::settings_menu::SettingsMenuOpen;
+use super::settings_menu::{SettingsMenu, SettingsMenuOpen};
 
 pub(super) struct InGameMenuPlugin;
 
@@ -21,18 +36,35 @@ impl Plugin for InGameMenuPlugin {
     }
 }
 
+/// Maximum height of the existing snapshots list in the save dialog.
+const SNAPSHOTS_HEIGHT: f32 = 300.0;
+
 fn toggle(
     _trigger: Trigger<Started<ToggleIngameMenu>>,
     mut commands: Commands,
-    menu: Single<(Entity, &Parent, &mut Visibility), With<IngameMenu>>,
+    time_speed: Res<TimeSpeed>,
+    server: Res<RepliconServer>,
+    client: Res<RepliconClient>,
+    menu: Single<(Entity, &Parent, &mut IngameMenu, &mut Visibility)>,
+    settings_menus: Query<(), With<SettingsMenu>>,
 ) {
-    let (entity, parent, mut visibility) = menu.into_inner();
+    if !settings_menus.is_empty() {
+        debug!("ignoring menu toggle while settings are open");
+        return;
+    }
+
+    let (entity, parent, mut menu, mut visibility) = menu.into_inner();
     match *visibility {
         Visibility::Inherited => {
             info!("closing in-game menu");
+            menu.resume(&mut commands);
             *visibility = Visibility::Hidden;
         }
         Visibility::Hidden => {
+            // In multiplayer the world keeps running for other players.
+            if !server.is_running() && client.is_disconnected() {
+                menu.pause(&mut commands, *time_speed);
+            }
             *visibility = Visibility::Inherited;
             info!("showing in-game menu");
         }
@@ -51,7 +83,11 @@ fn setup(
 ) {
     commands.entity(*root_entity).with_children(|parent| {
         parent
-            .spawn((IngameMenu, Visibility::Hidden, StateScoped(**world_state)))
+            .spawn((
+                IngameMenu::default(),
+                Visibility::Hidden,
+                StateScoped(**world_state),
+            ))
             .with_children(|parent| {
                 parent
                     .spawn((
@@ -76,21 +112,25 @@ fn setup(
                             .spawn(ButtonKind::Normal)
                             .with_child(Text::new("Save"))
                             .observe(save);
+                        parent
+                            .spawn(ButtonKind::Normal)
+                            .with_child(Text::new("Save as"))
+                            .observe(save_as.pipe(error_message));
                         parent
                             .spawn(ButtonKind::Normal)
                             .with_child(Text::new("Settings"))
                             .observe(open_settings);
                         parent
                             .spawn(ButtonKind::Normal)
-                            .with_child(Text::new("World"))
+                            .with_child(Text::new("Exit to world menu"))
                             .observe(open_world);
                         parent
                             .spawn(ButtonKind::Normal)
-                            .with_child(Text::new("Main menu"))
+                            .with_child(Text::new("Exit to main menu"))
                             .observe(exit_to_main_menu);
                         parent
                             .spawn(ButtonKind::Normal)
-                            .with_child(Text::new("Exit game"))
+                            .with_child(Text::new("Exit to desktop"))
                             .observe(exit_game);
                     });
             });
@@ -99,27 +139,178 @@ fn setup(
 
 fn resume(
     _trigger: Trigger<Pointer<Click>>,
-    mut menu_visibility: Single<&mut Visibility, With<IngameMenu>>,
+    mut commands: Commands,
+    menu: Single<(&mut IngameMenu, &mut Visibility)>,
 ) {
     info!("closing in-game menu");
-    **menu_visibility = Visibility::Hidden;
+    let (mut menu, mut visibility) = menu.into_inner();
+    menu.resume(&mut commands);
+    *visibility = Visibility::Hidden;
 }
 
 fn save(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
-    mut menu_visibility: Single<&mut Visibility, With<IngameMenu>>,
+    menu: Single<(&mut IngameMenu, &mut Visibility)>,
 ) {
     info!("closing in-game menu");
     commands.trigger(GameSave);
-    **menu_visibility = Visibility::Hidden;
+    let (mut menu, mut visibility) = menu.into_inner();
+    menu.resume(&mut commands);
+    *visibility = Visibility::Hidden;
+}
+
+fn save_as(
+    _trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    world_name: Res<WorldName>,
+    world_management: WorldManagement,
+    menu_entity: Single<Entity, With<IngameMenu>>,
+) -> Result<()> {
+    let snapshots = world_management
+        .snapshots(&world_name.0)
+        .context("unable to read world snapshots")?;
+
+    info!("showing save dialog");
+    commands.entity(*menu_entity).with_children(|parent| {
+        parent.spawn(SaveAsDialog).with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        padding: theme.padding.normal,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                    theme.panel_background,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((LabelKind::Normal, Text::new("Save as")));
+                    if !snapshots.is_empty() {
+                        parent
+                            .spawn((
+                                ScrollView,
+                                Node {
+                                    flex_direction: FlexDirection::Column,
+                                    max_height: Val::Px(SNAPSHOTS_HEIGHT),
+                                    row_gap: theme.gap.normal,
+                                    ..Default::default()
+                                },
+                            ))
+                            .with_children(|parent| {
+                                for snapshot in snapshots {
+                                    parent
+                                        .spawn((
+                                            ButtonKind::Normal,
+                                            SnapshotButton(snapshot.name.clone()),
+                                        ))
+                                        .with_child(Text::new(snapshot.name))
+                                        .observe(pick_snapshot);
+                                }
+                            });
+                    }
+                    parent.spawn(SnapshotNameEdit);
+                    parent
+                        .spawn(Node {
+                            column_gap: theme.gap.normal,
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent
+                                .spawn(ButtonKind::Normal)
+                                .with_child(Text::new("Save"))
+                                .observe(confirm_save_as.pipe(error_message));
+                            parent
+                                .spawn(ButtonKind::Normal)
+                                .with_child(Text::new("Cancel"))
+                                .observe(cancel_save_as);
+                        });
+                });
+        });
+    });
+
+    Ok(())
+}
+
+/// Copies the name of an existing snapshot into [`SnapshotNameEdit`].
+fn pick_snapshot(
+    trigger: Trigger<Pointer<Click>>,
+    buttons: Query<&SnapshotButton>,
+    mut name_edit: Single<&mut TextInputValue, With<SnapshotNameEdit>>,
+) {
+    let snapshot_button = buttons.get(trigger.entity()).unwrap();
+    name_edit.0 = snapshot_button.0.clone();
+}
+
+fn confirm_save_as(
+    _trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    world_name: Res<WorldName>,
+    world_management: WorldManagement,
+    dialog_entity: Single<Entity, With<SaveAsDialog>>,
+    menu_entity: Single<Entity, With<IngameMenu>>,
+    name_edit: Single<&TextInputValue, With<SnapshotNameEdit>>,
+) -> Result<()> {
+    let name = name_edit.0.trim();
+    world_management.ensure_valid_snapshot(name)?;
+
+    let exists = world_management
+        .snapshots(&world_name.0)?
+        .iter()
+        .any(|snapshot| snapshot.name == name);
+    if exists {
+        info!("asking to overwrite snapshot '{name}'");
+        commands.entity(*menu_entity).with_children(|parent| {
+            parent
+                .spawn(
+                    ConfirmationDialog::new(
+                        "Overwrite snapshot",
+                        format!("Snapshot {name} already exists. Overwrite it?"),
+                    )
+                    .with_confirm_text("Overwrite"),
+                )
+                .observe(confirm_overwrite);
+        });
+    } else {
+        commands.trigger(SnapshotSave(name.to_string()));
+        commands.entity(*dialog_entity).despawn_recursive();
+    }
+
+    Ok(())
+}
+
+fn confirm_overwrite(
+    _trigger: Trigger<DialogConfirmed>,
+    mut commands: Commands,
+    dialog_entity: Single<Entity, With<SaveAsDialog>>,
+    name_edit: Single<&TextInputValue, With<SnapshotNameEdit>>,
+) {
+    commands.trigger(SnapshotSave(name_edit.0.trim().to_string()));
+    commands.entity(*dialog_entity).despawn_recursive();
+}
+
+fn cancel_save_as(
+    _trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    dialog_entity: Single<Entity, With<SaveAsDialog>>,
+) {
+    info!("cancelling save");
+    commands.entity(*dialog_entity).despawn_recursive();
 }
 
 fn open_settings(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
     commands.trigger(SettingsMenuOpen);
 }
 
-fn open_world(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+fn open_world(
+    _trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut menu: Single<&mut IngameMenu>,
+) {
+    menu.resume(&mut commands);
     commands.set_state(WorldState::World);
 }
 
@@ -194,7 +385,10 @@ fn save_and_exit(
 ) {
     commands.trigger(GameSave);
     match *exit_dialog {
-        ExitDialog::MainMenu => commands.set_state(GameState::Menu),
+        ExitDialog::MainMenu => {
+            commands.trigger(HostLeave);
+            commands.set_state(GameState::Menu);
+        }
         ExitDialog::Game => {
             info!("exiting game");
             exit_events.send_default();
@@ -209,7 +403,10 @@ fn exit_without_saving(
     exit_dialog: Single<&ExitDialog>,
 ) {
     match *exit_dialog {
-        ExitDialog::MainMenu => commands.set_state(GameState::Menu),
+        ExitDialog::MainMenu => {
+            commands.trigger(HostLeave);
+            commands.set_state(GameState::Menu);
+        }
         ExitDialog::Game => {
             info!("exiting game");
             exit_events.send_default();
@@ -226,17 +423,38 @@ fn cancel_exit(
     commands.entity(*dialog_entity).despawn_recursive();
 }
 
-#[derive(Component)]
+#[derive(Component, Default)]
 #[require(Name(|| Name::new("Ingame menu")), Dialog)]
-struct IngameMenu;
+struct IngameMenu {
+    /// Speed to restore on close if the menu paused the game.
+    resume_speed: Option<TimeSpeed>,
+}
+
+impl IngameMenu {
+    fn pause(&mut self, commands: &mut Commands, time_speed: TimeSpeed) {
+        if time_speed != TimeSpeed::Paused {
+            info!("pausing game while the menu is open");
+            self.resume_speed = Some(time_speed);
+            commands.client_trigger(TimeSpeedRequest(TimeSpeed::Paused));
+        }
+    }
+
+    fn resume(&mut self, commands: &mut Commands) {
+        if let Some(time_speed) = self.resume_speed.take() {
+            info!("resuming game with `{time_speed:?}`");
+            commands.client_trigger(TimeSpeedRequest(time_speed));
+        }
+    }
+}
 
 impl InputContext for IngameMenu {
     const PRIORITY: isize = -1;
 
-    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+    fn context_instance(world: &World, _entity: Entity) -> ContextInstance {
         let mut ctx = ContextInstance::default();
+        let settings = world.resource::<Settings>();
         ctx.bind::<ToggleIngameMenu>()
-            .to((KeyCode::Escape, GamepadButton::Start));
+            .to((KeyCode::Escape, &settings.gamepad.menu));
         ctx
     }
 }
@@ -245,6 +463,18 @@ impl InputContext for IngameMenu {
 #[input_action(output = bool)]
 struct ToggleIngameMenu;
 
+#[derive(Component)]
+#[require(Name(|| Name::new("Save as dialog")), Dialog)]
+struct SaveAsDialog;
+
+#[derive(Component)]
+#[require(TextEdit)]
+struct SnapshotNameEdit;
+
+/// Existing snapshot that can be overwritten.
+#[derive(Component)]
+struct SnapshotButton(String);
+
 #[derive(Component, Clone, Copy)]
 #[require(Name(|| Name::new("Exit dialog")), Dialog)]
 enum ExitDialog {
@@ -1,6 +1,6 @@
 use bevy::{app::AppExit, prelude::*};
 
-use super::{settings_menu::SettingsMenuOpen, MenuState};
+use super::{achievements_menu::AchievementsMenuOpen, settings_menu::SettingsMenuOpen, MenuState};
 use project_harmonia_widgets::{button::ButtonKind, theme::Theme};
 
 pub(super) struct MainMenuPlugin;
@@ -41,6 +41,10 @@ fn setup(
                     .spawn(ButtonKind::Large)
                     .with_child(Text::new("Settings"))
                     .observe(open_settings);
+                parent
+                    .spawn(ButtonKind::Large)
+                    .with_child(Text::new("Achievements"))
+                    .observe(open_achievements);
 
                 parent
                     .spawn(ButtonKind::Large)
@@ -58,6 +62,10 @@ fn open_settings(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
     commands.trigger(SettingsMenuOpen);
 }
 
+fn open_achievements(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(AchievementsMenuOpen);
+}
+
 fn exit(_trigger: Trigger<Pointer<Click>>, mut exit_events: EventWriter<AppExit>) {
     info!("exiting game");
     exit_events.send_default();
@@ -0,0 +1,116 @@
+use std::f32::consts::TAU;
+
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+
+use project_harmonia_base::core::GameState;
+
+/// Displays a small showcase scene behind menus with a slowly orbiting camera.
+///
+/// Everything is scoped to [`GameState::Menu`].
+pub(super) struct MenuBackgroundPlugin;
+
+impl Plugin for MenuBackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Menu), setup)
+            .add_systems(Update, orbit.run_if(in_state(GameState::Menu)));
+    }
+}
+
+const GROUND_SIZE: f32 = 20.0;
+
+/// Angular speed in radians per second.
+const ORBIT_SPEED: f32 = 0.05;
+const ORBIT_RADIUS: f32 = 7.0;
+const ORBIT_HEIGHT: f32 = 3.0;
+
+/// Scenes placed in the showcase with their positions and rotations around Y.
+const SCENES: [(&str, Vec3, f32); 5] = [
+    (
+        "base/objects/outdoor_furniture/comfortable_bench/comfortable_bench.gltf#Scene0",
+        Vec3::new(0.0, 0.0, -1.0),
+        0.0,
+    ),
+    (
+        "base/objects/outdoor_furniture/simple_bench/simple_bench.gltf#Scene0",
+        Vec3::new(2.5, 0.0, 0.5),
+        -1.2,
+    ),
+    (
+        "base/objects/foliage/simple_bush/simple_bush.gltf#Scene0",
+        Vec3::new(-2.0, 0.0, -1.5),
+        0.4,
+    ),
+    (
+        "base/objects/foliage/simple_bush/simple_bush.gltf#Scene0",
+        Vec3::new(1.5, 0.0, -2.5),
+        2.1,
+    ),
+    (
+        "base/objects/rocks/medium_stone/medium_stone.gltf#Scene0",
+        Vec3::new(-1.5, 0.0, 1.5),
+        0.8,
+    ),
+];
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, mut meshes: ResMut<Assets<Mesh>>) {
+    info!("spawning menu background");
+
+    let mut mesh = Plane3d::default()
+        .mesh()
+        .size(GROUND_SIZE, GROUND_SIZE)
+        .build();
+    if let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0) {
+        // Adjust UVs to tile the texture properly.
+        for point in uvs {
+            for value in point {
+                *value *= GROUND_SIZE;
+            }
+        }
+    }
+
+    commands
+        .spawn((
+            Name::new("Menu background"),
+            StateScoped(GameState::Menu),
+            Transform::default(),
+            Visibility::default(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Mesh3d(meshes.add(mesh)),
+                MeshMaterial3d::<StandardMaterial>(
+                    asset_server.load("base/ground/spring_grass/spring_glass.ron"),
+                ),
+            ));
+            for (path, translation, angle) in SCENES {
+                parent.spawn((
+                    SceneRoot(asset_server.load(path)),
+                    Transform::from_translation(translation)
+                        .with_rotation(Quat::from_rotation_y(angle)),
+                ));
+            }
+            parent.spawn((
+                DirectionalLight {
+                    shadows_enabled: true,
+                    ..Default::default()
+                },
+                Transform::from_xyz(4.0, 7.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ));
+            parent.spawn(MenuCamera::default());
+        });
+}
+
+fn orbit(time: Res<Time>, mut cameras: Query<(&mut MenuCamera, &mut Transform)>) {
+    for (mut camera, mut transform) in &mut cameras {
+        camera.angle = (camera.angle + ORBIT_SPEED * time.delta_secs()) % TAU;
+        let (sin, cos) = camera.angle.sin_cos();
+        *transform = Transform::from_xyz(ORBIT_RADIUS * sin, ORBIT_HEIGHT, ORBIT_RADIUS * cos)
+            .looking_at(Vec3::Y * 0.5, Vec3::Y);
+    }
+}
+
+#[derive(Component, Default)]
+#[require(Name(|| Name::new("Menu camera")), Camera3d)]
+struct MenuCamera {
+    angle: f32,
+}
@@ -1,24 +1,39 @@
 use std::fmt::Write;
 
 use bevy::{
-    input::{common_conditions::*, keyboard::KeyboardInput, mouse::MouseButtonInput, ButtonState},
+    asset::LoadedFolder,
+    input::{
+        common_conditions::*, gamepad::GamepadButtonStateChangedEvent, keyboard::KeyboardInput,
+        mouse::MouseButtonInput, ButtonState,
+    },
     prelude::*,
     reflect::GetPath,
 };
 use bevy_enhanced_input::prelude::*;
 use strum::{EnumIter, IntoEnumIterator};
 
-use project_harmonia_base::settings::{
-    DeveloperSettings, KeyboardSettings, Settings, SettingsApply, VideoSettings,
+use project_harmonia_base::{
+    game_paths::GamePaths,
+    settings::{
+        AccessibilitySettings, AudioSettings, CameraSettings, DeveloperSettings, DisplayMode,
+        GamepadSettings, GameplaySettings, GraphicsQuality, InspectorFilter, KeyboardSettings,
+        LogLevel, ModSettings, NameplateMode, OutlinePalette, PickingBackend, Settings,
+        SettingsApply, Supersampling, TextSize, VideoSettings, WallView,
+    },
+    tutorial::TutorialReplay,
 };
 use project_harmonia_widgets::{
-    button::{ButtonKind, TabContent, Toggled},
+    button::{ButtonKind, ExclusiveButton, TabContent, Toggled},
     checkbox::Checkbox,
     dialog::Dialog,
+    dropdown::Dropdown,
     label::LabelKind,
+    slider::{Slider, SliderLabel, SliderRange},
     theme::Theme,
 };
 
+use crate::theme_settings::ThemeFolder;
+
 pub(super) struct SettingsMenuPlugin;
 
 impl Plugin for SettingsMenuPlugin {
@@ -46,9 +61,13 @@ fn setup(
     mut tab_commands: Commands,
     settings: Res<Settings>,
     theme: Res<Theme>,
+    game_paths: Res<GamePaths>,
+    folders: Res<Assets<LoadedFolder>>,
+    theme_folder: Res<ThemeFolder>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
 ) {
     info!("opening setting menu");
+    let theme_names = theme_folder.names(&folders);
     commands.entity(*root_entity).with_children(|parent| {
         parent
             .spawn((
@@ -75,9 +94,26 @@ fn setup(
                 for tab in SettingsTab::iter() {
                     let content_entity = match tab {
                         SettingsTab::Video => setup_video_tab(parent, &theme, &settings.video),
+                        SettingsTab::Audio => setup_audio_tab(parent, &theme, &settings.audio),
+                        SettingsTab::Camera => setup_camera_tab(parent, &theme, &settings.camera),
+                        SettingsTab::Gameplay => {
+                            setup_gameplay_tab(parent, &theme, &settings.gameplay)
+                        }
+                        SettingsTab::Accessibility => setup_accessibility_tab(
+                            parent,
+                            &theme,
+                            &settings.accessibility,
+                            &theme_names,
+                        ),
                         SettingsTab::Keyboard => {
                             setup_keyboard_tab(parent, &theme, &settings.keyboard)
                         }
+                        SettingsTab::Gamepad => {
+                            setup_gamepad_tab(parent, &theme, &settings.gamepad)
+                        }
+                        SettingsTab::Mods => {
+                            setup_mods_tab(parent, &theme, &game_paths, &settings.mods)
+                        }
                         SettingsTab::Developer => {
                             setup_developer_tab(parent, &theme, &settings.developer)
                         }
@@ -130,7 +166,152 @@ macro_rules! settings_field {
 #[derive(Component, Clone, Copy)]
 struct SettingsField(&'static str);
 
+/// Resolutions available in the video tab.
+///
+/// The current resolution is also displayed if it's not in the list.
+const RESOLUTIONS: [UVec2; 6] = [
+    UVec2::new(1280, 720),
+    UVec2::new(1366, 768),
+    UVec2::new(1600, 900),
+    UVec2::new(1920, 1080),
+    UVec2::new(2560, 1440),
+    UVec2::new(3840, 2160),
+];
+
 fn setup_video_tab(parent: &mut ChildBuilder, theme: &Theme, video: &VideoSettings) -> Entity {
+    let mut resolutions = RESOLUTIONS.to_vec();
+    if !resolutions.contains(&video.resolution) {
+        resolutions.push(video.resolution);
+    }
+
+    parent
+        .spawn(Node {
+            display: Display::Grid,
+            padding: theme.padding.normal,
+            column_gap: theme.gap.normal,
+            row_gap: theme.gap.normal,
+            grid_template_columns: vec![GridTrack::auto(); 2],
+            align_items: AlignItems::Center,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn((LabelKind::Normal, Text::new("Display mode")));
+            parent.spawn((
+                DisplayModeDropdown,
+                Dropdown::from_enum(video.display_mode, DisplayMode::text),
+            ));
+
+            parent.spawn((LabelKind::Normal, Text::new("Resolution")));
+            let selected = resolutions
+                .iter()
+                .position(|&resolution| resolution == video.resolution)
+                .unwrap_or_default();
+            let options = resolutions
+                .iter()
+                .map(|resolution| format!("{}x{}", resolution.x, resolution.y))
+                .collect::<Vec<_>>();
+            parent.spawn((
+                ResolutionDropdown(resolutions),
+                Dropdown::new(options, selected),
+            ));
+
+            parent.spawn((LabelKind::Normal, Text::new("Graphics quality")));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for quality in GraphicsQuality::iter() {
+                        parent
+                            .spawn((
+                                quality,
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(quality == video.quality),
+                            ))
+                            .with_child(Text::new(quality.text()));
+                    }
+                });
+
+            parent.spawn((LabelKind::Normal, Text::new("Photo supersampling")));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for supersampling in Supersampling::iter() {
+                        parent
+                            .spawn((
+                                supersampling,
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(supersampling == video.photo_supersampling),
+                            ))
+                            .with_child(Text::new(supersampling.text()));
+                    }
+                });
+
+            parent.spawn(Node::default());
+            parent
+                .spawn((Checkbox(video.vsync), settings_field!(video.vsync)))
+                .with_child(Text::new("Vertical sync"));
+        })
+        .id()
+}
+
+const VOLUME_RANGE: SliderRange = SliderRange {
+    min: 0.0,
+    max: 1.0,
+    step: 0.05,
+};
+
+fn setup_audio_tab(parent: &mut ChildBuilder, theme: &Theme, audio: &AudioSettings) -> Entity {
+    parent
+        .spawn(Node {
+            display: Display::Grid,
+            padding: theme.padding.normal,
+            column_gap: theme.gap.normal,
+            row_gap: theme.gap.normal,
+            grid_template_columns: vec![GridTrack::auto(); 2],
+            align_items: AlignItems::Center,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn((LabelKind::Normal, Text::new("Master")));
+            parent.spawn((
+                Slider(audio.master),
+                VOLUME_RANGE,
+                SliderLabel,
+                settings_field!(audio.master),
+            ));
+            parent.spawn((LabelKind::Normal, Text::new("Music")));
+            parent.spawn((
+                Slider(audio.music),
+                VOLUME_RANGE,
+                SliderLabel,
+                settings_field!(audio.music),
+            ));
+            parent.spawn((LabelKind::Normal, Text::new("Effects")));
+            parent.spawn((
+                Slider(audio.sfx),
+                VOLUME_RANGE,
+                SliderLabel,
+                settings_field!(audio.sfx),
+            ));
+            parent.spawn((LabelKind::Normal, Text::new("Interface")));
+            parent.spawn((
+                Slider(audio.ui),
+                VOLUME_RANGE,
+                SliderLabel,
+                settings_field!(audio.ui),
+            ));
+        })
+        .id()
+}
+
+fn setup_camera_tab(parent: &mut ChildBuilder, theme: &Theme, camera: &CameraSettings) -> Entity {
     parent
         .spawn(Node {
             padding: theme.padding.normal,
@@ -141,14 +322,204 @@ fn setup_video_tab(parent: &mut ChildBuilder, theme: &Theme, video: &VideoSettin
         .with_children(|parent| {
             parent
                 .spawn((
-                    Checkbox(video.fullscreen),
-                    settings_field!(video.fullscreen),
+                    Checkbox(camera.edge_scrolling),
+                    settings_field!(camera.edge_scrolling),
                 ))
-                .with_child(Text::new("Fullscreen"));
+                .with_child(Text::new("Edge scrolling"));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for wall_view in WallView::iter() {
+                        parent
+                            .spawn((
+                                wall_view,
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(wall_view == camera.wall_view),
+                            ))
+                            .with_child(Text::new(wall_view.text()));
+                    }
+                });
         })
         .id()
 }
 
+const UI_SCALES: [f32; 4] = [0.75, 1.0, 1.25, 1.5];
+
+fn setup_accessibility_tab(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    accessibility: &AccessibilitySettings,
+    theme_names: &[String],
+) -> Entity {
+    parent
+        .spawn(Node {
+            display: Display::Grid,
+            padding: theme.padding.normal,
+            column_gap: theme.gap.normal,
+            row_gap: theme.gap.normal,
+            grid_template_columns: vec![GridTrack::auto(); 2],
+            align_items: AlignItems::Center,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn((LabelKind::Normal, Text::new("Interface scale")));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for ui_scale in UI_SCALES {
+                        parent
+                            .spawn((
+                                UiScaleButton(ui_scale),
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(ui_scale == accessibility.ui_scale),
+                            ))
+                            .with_child(Text::new(format!("{:.0}%", ui_scale * 100.0)));
+                    }
+                });
+
+            parent.spawn((LabelKind::Normal, Text::new("Theme")));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    flex_wrap: FlexWrap::Wrap,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for name in theme_names {
+                        parent
+                            .spawn((
+                                ThemeButton(name.clone()),
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(*name == accessibility.theme),
+                            ))
+                            .with_child(Text::new(name.clone()));
+                    }
+                });
+
+            parent.spawn((LabelKind::Normal, Text::new("Text size")));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for text_size in TextSize::iter() {
+                        parent
+                            .spawn((
+                                text_size,
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(text_size == accessibility.text_size),
+                            ))
+                            .with_child(Text::new(text_size.text()));
+                    }
+                });
+
+            parent.spawn((LabelKind::Normal, Text::new("Outline colors")));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for palette in OutlinePalette::iter() {
+                        parent
+                            .spawn((
+                                palette,
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(palette == accessibility.outline_palette),
+                            ))
+                            .with_child(Text::new(palette.text()));
+                    }
+                });
+
+            parent.spawn(Node::default());
+            parent
+                .spawn((
+                    Checkbox(accessibility.reduced_motion),
+                    settings_field!(accessibility.reduced_motion),
+                ))
+                .with_child(Text::new("Reduce camera motion"));
+        })
+        .id()
+}
+
+fn setup_gameplay_tab(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    gameplay: &GameplaySettings,
+) -> Entity {
+    parent
+        .spawn(Node {
+            padding: theme.padding.normal,
+            row_gap: theme.gap.normal,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Checkbox(gameplay.pause_on_depleted_need),
+                    settings_field!(gameplay.pause_on_depleted_need),
+                ))
+                .with_child(Text::new("Pause when a need is depleted"));
+            parent
+                .spawn((
+                    Checkbox(gameplay.pause_on_fire),
+                    settings_field!(gameplay.pause_on_fire),
+                ))
+                .with_child(Text::new("Pause when a fire starts"));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for mode in NameplateMode::iter() {
+                        parent
+                            .spawn((
+                                mode,
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(mode == gameplay.nameplates),
+                            ))
+                            .with_child(Text::new(mode.text()));
+                    }
+                });
+            parent
+                .spawn((
+                    Checkbox(gameplay.actor_indicators),
+                    settings_field!(gameplay.actor_indicators),
+                ))
+                .with_child(Text::new("Show speech and critical needs above actors"));
+            parent
+                .spawn((
+                    ButtonKind::Normal,
+                    Node {
+                        align_self: AlignSelf::FlexStart,
+                        ..Default::default()
+                    },
+                ))
+                .with_child(Text::new("Replay tutorial"))
+                .observe(replay_tutorial);
+        })
+        .id()
+}
+
+fn replay_tutorial(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(TutorialReplay);
+}
+
 /// Number of input columns.
 const INPUTS_PER_ACTION: usize = 3;
 
@@ -222,6 +593,20 @@ fn setup_keyboard_tab(
                 &keyboard.zoom_out,
                 settings_field!(keyboard.zoom_out),
             );
+            setup_action_row(
+                parent,
+                theme,
+                "Follow actor",
+                &keyboard.follow_actor,
+                settings_field!(keyboard.follow_actor),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Photo mode",
+                &keyboard.photo_mode,
+                settings_field!(keyboard.photo_mode),
+            );
             setup_action_row(
                 parent,
                 theme,
@@ -243,6 +628,143 @@ fn setup_keyboard_tab(
                 &keyboard.ordinal_placement,
                 settings_field!(keyboard.ordinal_placement),
             );
+            setup_action_row(
+                parent,
+                theme,
+                "Pause",
+                &keyboard.pause,
+                settings_field!(keyboard.pause),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Normal speed",
+                &keyboard.normal_speed,
+                settings_field!(keyboard.normal_speed),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Fast speed",
+                &keyboard.fast_speed,
+                settings_field!(keyboard.fast_speed),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Faster speed",
+                &keyboard.faster_speed,
+                settings_field!(keyboard.faster_speed),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Ultra speed",
+                &keyboard.ultra_speed,
+                settings_field!(keyboard.ultra_speed),
+            );
+        })
+        .id()
+}
+
+fn setup_gamepad_tab(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    gamepad: &GamepadSettings,
+) -> Entity {
+    parent
+        .spawn(Node {
+            display: Display::Grid,
+            column_gap: theme.gap.normal,
+            row_gap: theme.gap.normal,
+            grid_template_columns: vec![GridTrack::auto(); INPUTS_PER_ACTION + 1],
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            setup_action_row(
+                parent,
+                theme,
+                "Confirm",
+                &gamepad.confirm,
+                settings_field!(gamepad.confirm),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Cancel",
+                &gamepad.cancel,
+                settings_field!(gamepad.cancel),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Pan camera",
+                &gamepad.pan_camera,
+                settings_field!(gamepad.pan_camera),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Follow actor",
+                &gamepad.follow_actor,
+                settings_field!(gamepad.follow_actor),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Photo mode",
+                &gamepad.photo_mode,
+                settings_field!(gamepad.photo_mode),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Rotate object",
+                &gamepad.rotate_object,
+                settings_field!(gamepad.rotate_object),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Delete object",
+                &gamepad.delete,
+                settings_field!(gamepad.delete),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Free placement",
+                &gamepad.free_placement,
+                settings_field!(gamepad.free_placement),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Ordinal placement",
+                &gamepad.ordinal_placement,
+                settings_field!(gamepad.ordinal_placement),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Undo",
+                &gamepad.undo,
+                settings_field!(gamepad.undo),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Redo",
+                &gamepad.redo,
+                settings_field!(gamepad.redo),
+            );
+            setup_action_row(
+                parent,
+                theme,
+                "Menu",
+                &gamepad.menu,
+                settings_field!(gamepad.menu),
+            );
         })
         .id()
 }
@@ -341,6 +863,7 @@ fn bind(
     mut commands: Commands,
     mut key_events: EventReader<KeyboardInput>,
     mut mouse_button_events: EventReader<MouseButtonInput>,
+    mut gamepad_button_events: EventReader<GamepadButtonStateChangedEvent>,
     theme: Res<Theme>,
     dialog: Single<(Entity, &BindingDialog)>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
@@ -354,8 +877,12 @@ fn bind(
         .read()
         .filter(|event| event.state == ButtonState::Pressed)
         .map(|event| event.button.into());
+    let gamepad_buttons = gamepad_button_events
+        .read()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .map(|event| event.button.into());
 
-    let Some(input) = keys.chain(mouse_buttons).next() else {
+    let Some(input) = keys.chain(mouse_buttons).chain(gamepad_buttons).next() else {
         return;
     };
 
@@ -455,6 +982,47 @@ fn cancel_replace_binding(
     commands.entity(*dialog_entity).despawn_recursive();
 }
 
+fn setup_mods_tab(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    game_paths: &GamePaths,
+    mods: &ModSettings,
+) -> Entity {
+    let mod_names = game_paths
+        .get_mod_names()
+        .map_err(|e| error!("unable to get mod names: {e}"))
+        .unwrap_or_default();
+
+    parent
+        .spawn(Node {
+            padding: theme.padding.normal,
+            row_gap: theme.gap.normal,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            if mod_names.is_empty() {
+                parent.spawn((
+                    LabelKind::Normal,
+                    Text::new(format!("No mods installed in {:?}", game_paths.mods)),
+                ));
+            }
+            for mod_name in mod_names {
+                parent
+                    .spawn((
+                        Checkbox(mods.enabled(&mod_name)),
+                        ModCheckbox(mod_name.clone()),
+                    ))
+                    .with_child(Text::new(mod_name));
+            }
+            parent.spawn((
+                LabelKind::Small,
+                Text::new("Changes will be applied after restart"),
+            ));
+        })
+        .id()
+}
+
 fn setup_developer_tab(
     parent: &mut ChildBuilder,
     theme: &Theme,
@@ -495,6 +1063,104 @@ fn setup_developer_tab(
                     settings_field!(developer.nav_mesh),
                 ))
                 .with_child(Text::new("Display navigation mesh"));
+            parent
+                .spawn((
+                    Checkbox(developer.network_stats),
+                    settings_field!(developer.network_stats),
+                ))
+                .with_child(Text::new("Display network statistics"));
+            parent
+                .spawn((
+                    Checkbox(developer.network_recording),
+                    settings_field!(developer.network_recording),
+                ))
+                .with_child(Text::new("Record network messages"));
+            parent
+                .spawn((
+                    Checkbox(developer.profiler),
+                    settings_field!(developer.profiler),
+                ))
+                .with_child(Text::new("Display profiler"));
+            parent
+                .spawn((
+                    Checkbox(developer.world_inspector),
+                    settings_field!(developer.world_inspector),
+                ))
+                .with_child(Text::new("Display world inspector"));
+            parent
+                .spawn((
+                    Checkbox(developer.pause_on_inspector),
+                    settings_field!(developer.pause_on_inspector),
+                ))
+                .with_child(Text::new("Pause while inspecting"));
+            parent
+                .spawn((
+                    Checkbox(developer.entity_audit),
+                    settings_field!(developer.entity_audit),
+                ))
+                .with_child(Text::new("Report leaked entities"));
+            parent
+                .spawn((
+                    Checkbox(developer.debug_tools),
+                    settings_field!(developer.debug_tools),
+                ))
+                .with_child(Text::new("Actor debug tools"));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for filter in InspectorFilter::iter() {
+                        parent
+                            .spawn((
+                                filter,
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(filter == developer.inspector_filter),
+                            ))
+                            .with_child(Text::new(filter.text()));
+                    }
+                });
+            parent.spawn((
+                LabelKind::Normal,
+                Text::new("Log level (applied on restart)"),
+            ));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for level in LogLevel::iter() {
+                        parent
+                            .spawn((
+                                level,
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(level == developer.log_level),
+                            ))
+                            .with_child(Text::new(level.text()));
+                    }
+                });
+            parent.spawn((LabelKind::Normal, Text::new("Picking")));
+            parent
+                .spawn(Node {
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for backend in PickingBackend::iter() {
+                        parent
+                            .spawn((
+                                backend,
+                                ButtonKind::Normal,
+                                ExclusiveButton,
+                                Toggled(backend == developer.picking_backend),
+                            ))
+                            .with_child(Text::new(backend.text()));
+                    }
+                });
         })
         .id()
 }
@@ -506,16 +1172,89 @@ fn confirm(
     menu_entity: Single<Entity, With<SettingsMenu>>,
     buttons: Query<(&InputButton, &SettingsField)>,
     checkboxes: Query<(&Checkbox, &SettingsField)>,
+    sliders: Query<(&Slider, &SettingsField)>,
+    mod_checkboxes: Query<(&Checkbox, &ModCheckbox)>,
+    (wall_views, nameplate_modes, inspector_filters, log_levels, picking_backends): (
+        Query<(&Toggled, &WallView)>,
+        Query<(&Toggled, &NameplateMode)>,
+        Query<(&Toggled, &InspectorFilter)>,
+        Query<(&Toggled, &LogLevel)>,
+        Query<(&Toggled, &PickingBackend)>,
+    ),
+    display_mode_dropdown: Single<&Dropdown, With<DisplayModeDropdown>>,
+    resolution_dropdown: Single<(&Dropdown, &ResolutionDropdown)>,
+    ui_scales: Query<(&Toggled, &UiScaleButton)>,
+    (qualities, supersamplings): (
+        Query<(&Toggled, &GraphicsQuality)>,
+        Query<(&Toggled, &Supersampling)>,
+    ),
+    text_sizes: Query<(&Toggled, &TextSize)>,
+    palettes: Query<(&Toggled, &OutlinePalette)>,
+    theme_buttons: Query<(&Toggled, &ThemeButton)>,
 ) {
     info!("confirming settings");
 
+    if let Some(display_mode) = display_mode_dropdown.selected_enum() {
+        settings.video.display_mode = display_mode;
+    }
+    let (dropdown, resolutions) = *resolution_dropdown;
+    if let Some(&resolution) = resolutions.get(dropdown.selected) {
+        settings.video.resolution = resolution;
+    }
+    if let Some((_, ui_scale)) = ui_scales.iter().find(|(toggled, _)| ***toggled) {
+        settings.accessibility.ui_scale = ui_scale.0;
+    }
+    if let Some((_, &quality)) = qualities.iter().find(|(toggled, _)| ***toggled) {
+        settings.video.quality = quality;
+    }
+    if let Some((_, &supersampling)) = supersamplings.iter().find(|(toggled, _)| ***toggled) {
+        settings.video.photo_supersampling = supersampling;
+    }
+    if let Some((_, &text_size)) = text_sizes.iter().find(|(toggled, _)| ***toggled) {
+        settings.accessibility.text_size = text_size;
+    }
+    if let Some((_, &palette)) = palettes.iter().find(|(toggled, _)| ***toggled) {
+        settings.accessibility.outline_palette = palette;
+    }
+    if let Some((_, theme_button)) = theme_buttons.iter().find(|(toggled, _)| ***toggled) {
+        settings.accessibility.theme = theme_button.0.clone();
+    }
+
+    if let Some((_, &wall_view)) = wall_views.iter().find(|(toggled, _)| ***toggled) {
+        settings.camera.wall_view = wall_view;
+    }
+    if let Some((_, &mode)) = nameplate_modes.iter().find(|(toggled, _)| ***toggled) {
+        settings.gameplay.nameplates = mode;
+    }
+    if let Some((_, &filter)) = inspector_filters.iter().find(|(toggled, _)| ***toggled) {
+        settings.developer.inspector_filter = filter;
+    }
+    if let Some((_, &level)) = log_levels.iter().find(|(toggled, _)| ***toggled) {
+        settings.developer.log_level = level;
+    }
+    if let Some((_, &backend)) = picking_backends.iter().find(|(toggled, _)| ***toggled) {
+        settings.developer.picking_backend = backend;
+    }
+
     for (checkbox, field) in &checkboxes {
         let field_value = settings
             .path_mut::<bool>(field.0)
             .expect("fields with checkboxes should be stored as bools");
         *field_value = checkbox.0;
     }
+    for (slider, field) in &sliders {
+        let field_value = settings
+            .path_mut::<f32>(field.0)
+            .expect("fields with sliders should be stored as floats");
+        *field_value = slider.0;
+    }
+    settings.mods.disabled = mod_checkboxes
+        .iter()
+        .filter(|(checkbox, _)| !checkbox.0)
+        .map(|(_, mod_checkbox)| mod_checkbox.0.clone())
+        .collect();
     settings.keyboard.clear();
+    settings.gamepad.clear();
     for (button, field) in &buttons {
         if let Some(input) = button.input {
             let field_value = settings
@@ -556,16 +1295,22 @@ fn update_button_text(
 
 // Creates a settings menu node.
 #[derive(Event)]
-pub(super) struct SettingsMenuOpen;
+pub(crate) struct SettingsMenuOpen;
 
 #[derive(Component)]
-struct SettingsMenu;
+pub(super) struct SettingsMenu;
 
 #[derive(Default, EnumIter, PartialEq, Clone, Copy)]
 enum SettingsTab {
     #[default]
     Video,
+    Audio,
+    Camera,
+    Gameplay,
+    Accessibility,
     Keyboard,
+    Gamepad,
+    Mods,
     Developer,
 }
 
@@ -573,12 +1318,37 @@ impl SettingsTab {
     fn text(self) -> &'static str {
         match self {
             SettingsTab::Video => "Video",
+            SettingsTab::Audio => "Audio",
+            SettingsTab::Camera => "Camera",
+            SettingsTab::Gameplay => "Gameplay",
+            SettingsTab::Accessibility => "Accessibility",
             SettingsTab::Keyboard => "Keyboard",
+            SettingsTab::Gamepad => "Gamepad",
+            SettingsTab::Mods => "Mods",
             SettingsTab::Developer => "Developer",
         }
     }
 }
 
+#[derive(Component)]
+struct DisplayModeDropdown;
+
+/// Window resolutions listed in the dropdown in the same order.
+#[derive(Component, Deref)]
+struct ResolutionDropdown(Vec<UVec2>);
+
+/// Name of the theme selected by the button.
+#[derive(Component)]
+struct ThemeButton(String);
+
+/// UI scale selected by the button.
+#[derive(Component)]
+struct UiScaleButton(f32);
+
+/// Stores name of the mod toggled by the checkbox.
+#[derive(Component)]
+struct ModCheckbox(String);
+
 /// Stores information about button mapping.
 #[derive(Component)]
 #[require(Name(|| Name::new("Mapping button")), ButtonKind(|| ButtonKind::Normal))]
@@ -1,4 +1,7 @@
-use std::{fs, net::Ipv4Addr};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::{Duration, SystemTime},
+};
 
 use anyhow::{Context, Result};
 use bevy::prelude::*;
@@ -7,32 +10,65 @@ use bevy_replicon_renet::{
     renet::{ConnectionConfig, RenetClient, RenetServer},
     RenetChannelsExt,
 };
-use bevy_simple_text_input::TextInputValue;
+use bevy_simple_text_input::{TextInputSettings, TextInputValue};
+use strum::IntoEnumIterator;
 
 use super::MenuState;
+use crate::preview;
 use project_harmonia_base::{
-    core::GameState,
     error_message::error_message,
-    game_paths::GamePaths,
-    game_world::{GameLoad, WorldName},
-    network::{self, DEFAULT_PORT},
+    game_world::{
+        difficulty::Difficulty,
+        players::JoinAsSpectator,
+        world_generation::{WorldCreate, WorldCreateOptions},
+        world_management::{WorldInfo, WorldManagement},
+        GameLoad, SnapshotLoad, WorldName,
+    },
+    network::{
+        self,
+        discovery::{LanServer, LanServers},
+        SessionPassword, DEFAULT_PORT,
+    },
 };
 use project_harmonia_widgets::{
-    button::ButtonKind, dialog::Dialog, label::LabelKind, text_edit::TextEdit, theme::Theme,
+    button::{ButtonKind, ExclusiveButton, Toggled},
+    checkbox::Checkbox,
+    dialog::{ConfirmationDialog, Dialog, DialogConfirmed},
+    label::LabelKind,
+    numeric_edit::NumericEdit,
+    scroll_view::ScrollView,
+    text_edit::TextEdit,
+    theme::Theme,
 };
 
 pub(super) struct WorldBrowserPlugin;
 
 impl Plugin for WorldBrowserPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(MenuState::WorldBrowser), setup);
+        app.add_observer(refresh.pipe(error_message))
+            .add_systems(OnEnter(MenuState::WorldBrowser), (setup, start_discovery))
+            .add_systems(OnExit(MenuState::WorldBrowser), stop_discovery)
+            .add_systems(
+                Update,
+                update_lan_list
+                    .never_param_warn()
+                    .run_if(resource_exists_and_changed::<LanServers>),
+            );
     }
 }
 
+/// Width of world preview images in pixels.
+const PREVIEW_WIDTH: f32 = 160.0;
+
+/// Maximum height of the snapshots list in the load dialog.
+const SNAPSHOTS_HEIGHT: f32 = 300.0;
+
+/// Change of the starting budget per spin button click.
+const BUDGET_STEP: u32 = 1000;
+
 fn setup(
     mut commands: Commands,
     theme: Res<Theme>,
-    game_paths: Res<GamePaths>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
 ) {
     info!("entering world browser");
@@ -52,8 +88,10 @@ fn setup(
             ))
             .with_children(|parent| {
                 parent.spawn((LabelKind::Large, Text::new("World browser")));
-                parent
-                    .spawn(Node {
+                parent.spawn((
+                    WorldList,
+                    ScrollView,
+                    Node {
                         width: Val::Percent(100.0),
                         height: Val::Percent(100.0),
                         flex_direction: FlexDirection::Column,
@@ -62,16 +100,21 @@ fn setup(
                         padding: theme.padding.normal,
                         row_gap: theme.gap.normal,
                         ..Default::default()
-                    })
-                    .with_children(|parent| {
-                        let world_names = game_paths
-                            .get_world_names()
-                            .map_err(|e| error!("unable to get world names: {e}"))
-                            .unwrap_or_default();
-                        for name in world_names {
-                            setup_world_node(parent, &theme, name);
-                        }
-                    });
+                    },
+                ));
+
+                parent.spawn((LabelKind::Normal, Text::new("LAN games")));
+                parent.spawn((
+                    LanList,
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: theme.padding.normal,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                ));
 
                 parent
                     .spawn(Node {
@@ -100,9 +143,40 @@ fn setup(
                     });
             });
     });
+
+    // Trigger after spawning the browser to display possible errors on top of it.
+    commands.trigger(RefreshWorlds);
 }
 
-fn setup_world_node(parent: &mut ChildBuilder, theme: &Theme, label: impl Into<String>) {
+fn refresh(
+    _trigger: Trigger<RefreshWorlds>,
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    theme: Res<Theme>,
+    world_management: WorldManagement,
+    list_entity: Single<Entity, With<WorldList>>,
+) -> Result<()> {
+    commands.entity(*list_entity).despawn_descendants();
+    let worlds = world_management
+        .worlds()
+        .context("unable to read saved worlds")?;
+
+    debug!("listing {} worlds", worlds.len());
+    commands.entity(*list_entity).with_children(|parent| {
+        for world in worlds {
+            setup_world_node(parent, &theme, &mut images, world);
+        }
+    });
+
+    Ok(())
+}
+
+fn setup_world_node(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    images: &mut Assets<Image>,
+    world: WorldInfo,
+) {
     parent
         .spawn((
             Node {
@@ -113,8 +187,24 @@ fn setup_world_node(parent: &mut ChildBuilder, theme: &Theme, label: impl Into<S
             theme.panel_background,
         ))
         .with_children(|parent| {
+            let image = world.preview.and_then(|path| {
+                preview::load_image(&path)
+                    .inspect_err(|e| error!("unable to load world preview: {e:#}"))
+                    .ok()
+            });
+            if let Some(image) = image {
+                parent.spawn((
+                    ImageNode::new(images.add(image)),
+                    Node {
+                        width: Val::Px(PREVIEW_WIDTH),
+                        height: Val::Px(PREVIEW_WIDTH * 9.0 / 16.0),
+                        ..Default::default()
+                    },
+                ));
+            }
+
             let node_entity = parent.parent_entity();
-            let label_entity = parent.spawn((LabelKind::Large, Text::new(label))).id();
+            let label_entity = parent.spawn((LabelKind::Large, Text::new(world.name))).id();
             let world_node = WorldNode {
                 label_entity,
                 node_entity,
@@ -124,9 +214,33 @@ fn setup_world_node(parent: &mut ChildBuilder, theme: &Theme, label: impl Into<S
                 .spawn(Node {
                     width: Val::Percent(100.0),
                     height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: theme.gap.normal,
                     ..Default::default()
                 })
-                .add_child(label_entity);
+                .add_child(label_entity)
+                .with_children(|parent| {
+                    parent.spawn((
+                        LabelKind::Small,
+                        Text::new(last_played_text(world.last_played)),
+                    ));
+                    if let Some(header) = world.header {
+                        parent.spawn((
+                            LabelKind::Small,
+                            Text::new(format!("Play time: {}", play_time_text(header.play_time))),
+                        ));
+                        for (name, play_time) in header.families {
+                            parent.spawn((
+                                LabelKind::Small,
+                                Text::new(format!("{name}: {}", play_time_text(play_time))),
+                            ));
+                        }
+                        parent.spawn((
+                            LabelKind::Small,
+                            Text::new(format!("Version {}", header.version)),
+                        ));
+                    }
+                });
             parent
                 .spawn(Node {
                     flex_direction: FlexDirection::Column,
@@ -142,6 +256,18 @@ fn setup_world_node(parent: &mut ChildBuilder, theme: &Theme, label: impl Into<S
                         .spawn((ButtonKind::Normal, world_node))
                         .with_child(Text::new("Host"))
                         .observe(host);
+                    parent
+                        .spawn((ButtonKind::Normal, world_node))
+                        .with_child(Text::new("Load"))
+                        .observe(show_snapshots.pipe(error_message));
+                    parent
+                        .spawn((ButtonKind::Normal, world_node))
+                        .with_child(Text::new("Rename"))
+                        .observe(rename);
+                    parent
+                        .spawn((ButtonKind::Normal, world_node))
+                        .with_child(Text::new("Duplicate"))
+                        .observe(duplicate.pipe(error_message));
                     parent
                         .spawn((ButtonKind::Normal, world_node))
                         .with_child(Text::new("Remove"))
@@ -150,6 +276,109 @@ fn setup_world_node(parent: &mut ChildBuilder, theme: &Theme, label: impl Into<S
         });
 }
 
+fn last_played_text(last_played: Option<SystemTime>) -> String {
+    match elapsed_text(last_played) {
+        Some(elapsed) => format!("Last played {elapsed}"),
+        None => "Last played: unknown".to_string(),
+    }
+}
+
+fn play_time_text(play_time: Duration) -> String {
+    let minutes = play_time.as_secs() / 60;
+    let hours = minutes / 60;
+    if hours > 0 {
+        format!("{hours} h {} min", minutes % 60)
+    } else {
+        format!("{minutes} min")
+    }
+}
+
+fn elapsed_text(time: Option<SystemTime>) -> Option<String> {
+    let elapsed = time.and_then(|time| time.elapsed().ok())?;
+
+    let minutes = elapsed.as_secs() / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+    let text = if days > 0 {
+        format!("{days} d ago")
+    } else if hours > 0 {
+        format!("{hours} h ago")
+    } else if minutes > 0 {
+        format!("{minutes} min ago")
+    } else {
+        "just now".to_string()
+    };
+
+    Some(text)
+}
+
+/// Starts listening for LAN games.
+///
+/// Failure is not critical, only one game instance on the machine can listen.
+fn start_discovery(mut commands: Commands) {
+    match LanServers::new() {
+        Ok(lan_servers) => commands.insert_resource(lan_servers),
+        Err(e) => warn!("LAN games are unavailable: {e:#}"),
+    }
+}
+
+fn stop_discovery(mut commands: Commands) {
+    commands.remove_resource::<LanServers>();
+}
+
+fn update_lan_list(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    lan_servers: Res<LanServers>,
+    list_entity: Single<Entity, With<LanList>>,
+) {
+    debug!("updating LAN games");
+    commands
+        .entity(*list_entity)
+        .despawn_descendants()
+        .with_children(|parent| {
+            for server in lan_servers.iter() {
+                setup_lan_node(parent, &theme, server);
+            }
+        });
+}
+
+fn setup_lan_node(parent: &mut ChildBuilder, theme: &Theme, server: &LanServer) {
+    parent
+        .spawn((
+            Node {
+                padding: theme.padding.normal,
+                column_gap: theme.gap.normal,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            theme.panel_background,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LabelKind::Large,
+                Text::new(&server.world_name),
+                Node {
+                    width: Val::Percent(100.0),
+                    ..Default::default()
+                },
+            ));
+            parent.spawn((
+                LabelKind::Normal,
+                Text::new(format!(
+                    "{} ({}/{})",
+                    server.addr(),
+                    server.players,
+                    server.max_players
+                )),
+            ));
+            parent
+                .spawn((ButtonKind::Normal, LanServerAddr(server.addr())))
+                .with_child(Text::new("Join"))
+                .observe(join_lan);
+        });
+}
+
 fn play(
     trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
@@ -201,15 +430,24 @@ fn host(
 
                     parent
                         .spawn(Node {
+                            display: Display::Grid,
                             column_gap: theme.gap.normal,
-                            justify_content: JustifyContent::Center,
+                            row_gap: theme.gap.normal,
+                            grid_template_columns: vec![GridTrack::auto(); 2],
                             ..Default::default()
                         })
                         .with_children(|parent| {
                             parent.spawn((LabelKind::Normal, Text::new("Port:")));
-                            parent.spawn((PortEdit, TextInputValue(DEFAULT_PORT.to_string())));
+                            parent.spawn((PortEdit, NumericEdit::new(DEFAULT_PORT)));
+
+                            parent.spawn((LabelKind::Normal, Text::new("Password:")));
+                            parent.spawn(PasswordEdit);
                         });
 
+                    parent
+                        .spawn((SpectatorCheckbox, Checkbox(false)))
+                        .with_child(Text::new("Join as spectator"));
+
                     parent
                         .spawn(Node {
                             column_gap: theme.gap.normal,
@@ -235,7 +473,8 @@ fn confirm_host(
     mut commands: Commands,
     network_channels: Res<RepliconChannels>,
     dialog: Single<(Entity, &WorldNode), With<Dialog>>,
-    port: Single<&TextInputValue, With<PortEdit>>,
+    port: Single<&NumericEdit<u16>, With<PortEdit>>,
+    password: Single<&TextInputValue, With<PasswordEdit>>,
     labels: Query<&Text>,
 ) -> Result<()> {
     let (dialog_entity, world_node) = *dialog;
@@ -245,10 +484,13 @@ fn confirm_host(
         client_channels_config: network_channels.get_client_configs(),
         ..Default::default()
     });
-    let transport = network::create_server(port.0.parse()?).context("unable to create server")?;
+    let (transport, token_server) =
+        network::create_server(port.value, &password.0).context("unable to create server")?;
 
     commands.insert_resource(server);
     commands.insert_resource(transport);
+    commands.insert_resource(token_server);
+    commands.insert_resource(SessionPassword(password.0.clone()));
 
     let world_name = labels
         .get(world_node.label_entity)
@@ -269,21 +511,25 @@ fn cancel_host(
     commands.entity(*dialog_entity).despawn_recursive();
 }
 
-fn remove(
+fn show_snapshots(
     trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
     theme: Res<Theme>,
+    world_management: WorldManagement,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
     buttons: Query<&WorldNode>,
     labels: Query<&Text>,
-) {
+) -> Result<()> {
     let &world_node = buttons.get(trigger.entity()).unwrap();
     let world_name = labels
         .get(world_node.label_entity)
         .expect("world label should contain text");
+    let snapshots = world_management
+        .snapshots(world_name)
+        .with_context(|| format!("unable to read snapshots of world {}", &**world_name))?;
 
     commands.entity(*root_entity).with_children(|parent| {
-        info!("showing remove dialog");
+        info!("showing snapshots dialog");
         parent.spawn((Dialog, world_node)).with_children(|parent| {
             parent
                 .spawn((
@@ -298,14 +544,126 @@ fn remove(
                     theme.panel_background,
                 ))
                 .with_children(|parent| {
-                    parent.spawn((
-                        LabelKind::Normal,
-                        Text::new(format!(
-                            "Are you sure you want to remove world {}?",
-                            &**world_name
-                        )),
-                    ));
+                    parent.spawn((LabelKind::Normal, Text::new("Load snapshot")));
+                    if snapshots.is_empty() {
+                        parent.spawn((LabelKind::Small, Text::new("No saved snapshots")));
+                    }
+                    parent
+                        .spawn((
+                            ScrollView,
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                max_height: Val::Px(SNAPSHOTS_HEIGHT),
+                                row_gap: theme.gap.normal,
+                                ..Default::default()
+                            },
+                        ))
+                        .with_children(|parent| {
+                            for snapshot in snapshots {
+                                parent
+                                    .spawn(Node {
+                                        column_gap: theme.gap.normal,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    })
+                                    .with_children(|parent| {
+                                        let saved = elapsed_text(snapshot.saved)
+                                            .unwrap_or_else(|| "unknown".to_string());
+                                        let details = match snapshot.header {
+                                            Some(header) => format!(
+                                                "Saved {saved}, played {}",
+                                                play_time_text(header.play_time)
+                                            ),
+                                            None => format!("Saved {saved}"),
+                                        };
+                                        parent
+                                            .spawn(Node {
+                                                width: Val::Percent(100.0),
+                                                flex_direction: FlexDirection::Column,
+                                                ..Default::default()
+                                            })
+                                            .with_child((
+                                                LabelKind::Normal,
+                                                Text::new(snapshot.name.clone()),
+                                            ))
+                                            .with_child((LabelKind::Small, Text::new(details)));
+                                        parent
+                                            .spawn((
+                                                ButtonKind::Normal,
+                                                SnapshotButton(snapshot.name),
+                                            ))
+                                            .with_child(Text::new("Load"))
+                                            .observe(load_snapshot);
+                                    });
+                            }
+                        });
+                    parent
+                        .spawn(ButtonKind::Normal)
+                        .with_child(Text::new("Cancel"))
+                        .observe(cancel_load);
+                });
+        });
+    });
+
+    Ok(())
+}
+
+fn load_snapshot(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    dialog: Single<&WorldNode, With<Dialog>>,
+    buttons: Query<&SnapshotButton>,
+    labels: Query<&Text>,
+) {
+    let snapshot_button = buttons.get(trigger.entity()).unwrap();
+    let world_name = labels
+        .get(dialog.label_entity)
+        .expect("world label should contain text");
+
+    commands.insert_resource(WorldName(world_name.0.clone()));
+    commands.trigger(SnapshotLoad(snapshot_button.0.clone()));
+}
+
+fn cancel_load(
+    _trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    dialog_entity: Single<Entity, With<Dialog>>,
+) {
+    info!("cancelling load");
+    commands.entity(*dialog_entity).despawn_recursive();
+}
+
+fn rename(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    buttons: Query<&WorldNode>,
+    labels: Query<&Text>,
+) {
+    let &world_node = buttons.get(trigger.entity()).unwrap();
+    let world_name = labels
+        .get(world_node.label_entity)
+        .expect("world label should contain text");
 
+    commands.entity(*root_entity).with_children(|parent| {
+        info!("showing rename dialog");
+        parent.spawn((Dialog, world_node)).with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        padding: theme.padding.normal,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                    theme.panel_background,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((LabelKind::Normal, Text::new("Rename world")));
+                    parent.spawn((WorldNameEdit, TextInputValue(world_name.0.clone())));
                     parent
                         .spawn(Node {
                             column_gap: theme.gap.normal,
@@ -314,48 +672,112 @@ fn remove(
                         .with_children(|parent| {
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Remove"))
-                                .observe(confirm_remove.pipe(error_message));
+                                .with_child(Text::new("Rename"))
+                                .observe(confirm_rename.pipe(error_message));
                             parent
                                 .spawn(ButtonKind::Normal)
                                 .with_child(Text::new("Cancel"))
-                                .observe(cancel_remove);
+                                .observe(cancel_rename);
                         });
                 });
         });
     });
 }
 
-fn confirm_remove(
+fn confirm_rename(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
-    game_paths: Res<GamePaths>,
-    dialogs: Single<(Entity, &WorldNode), With<Dialog>>,
+    world_management: WorldManagement,
+    dialog: Single<(Entity, &WorldNode), With<Dialog>>,
+    name_edit: Single<&TextInputValue, With<WorldNameEdit>>,
     labels: Query<&Text>,
 ) -> Result<()> {
-    let (dialog_entity, world_node) = *dialogs;
-
+    let (dialog_entity, world_node) = *dialog;
     let world_name = labels
         .get(world_node.label_entity)
         .expect("world label should contain text");
-    let world_path = game_paths.world_path(world_name);
-    fs::remove_file(&world_path).with_context(|| format!("unable to remove {world_path:?}"))?;
 
-    commands.entity(world_node.node_entity).despawn_recursive();
+    // Keep the dialog open on error to let the user pick another name.
+    world_management.rename(world_name, &name_edit.0)?;
+
     commands.entity(dialog_entity).despawn_recursive();
+    commands.trigger(RefreshWorlds);
 
     Ok(())
 }
 
-fn cancel_remove(
+fn cancel_rename(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
     dialog_entity: Single<Entity, With<Dialog>>,
 ) {
-    info!("cancelling removal");
+    info!("cancelling rename");
     commands.entity(*dialog_entity).despawn_recursive();
 }
 
+fn duplicate(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    world_management: WorldManagement,
+    buttons: Query<&WorldNode>,
+    labels: Query<&Text>,
+) -> Result<()> {
+    let world_node = buttons.get(trigger.entity()).unwrap();
+    let world_name = labels
+        .get(world_node.label_entity)
+        .expect("world label should contain text");
+
+    world_management.duplicate(world_name)?;
+    commands.trigger(RefreshWorlds);
+
+    Ok(())
+}
+
+fn remove(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    buttons: Query<&WorldNode>,
+    labels: Query<&Text>,
+) {
+    let &world_node = buttons.get(trigger.entity()).unwrap();
+    let world_name = labels
+        .get(world_node.label_entity)
+        .expect("world label should contain text");
+
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                world_node,
+                ConfirmationDialog::new(
+                    "Remove world",
+                    format!("Are you sure you want to remove world {}?", &**world_name),
+                )
+                .with_confirm_text("Remove"),
+            ))
+            .observe(confirm_remove.pipe(error_message));
+    });
+}
+
+fn confirm_remove(
+    trigger: Trigger<DialogConfirmed>,
+    mut commands: Commands,
+    world_management: WorldManagement,
+    dialogs: Query<&WorldNode>,
+    labels: Query<&Text>,
+) -> Result<()> {
+    let world_node = dialogs.get(trigger.entity()).unwrap();
+
+    let world_name = labels
+        .get(world_node.label_entity)
+        .expect("world label should contain text");
+    world_management.delete(world_name)?;
+
+    commands.entity(world_node.node_entity).despawn_recursive();
+
+    Ok(())
+}
+
 fn create(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
@@ -378,8 +800,45 @@ fn create(
                     theme.panel_background,
                 ))
                 .with_children(|parent| {
+                    let options = WorldCreateOptions::default();
                     parent.spawn((LabelKind::Normal, Text::new("Create world")));
-                    parent.spawn((TextEdit, TextInputValue("New world".to_string())));
+                    parent.spawn((WorldNameEdit, TextInputValue(options.name)));
+                    parent
+                        .spawn(Node {
+                            display: Display::Grid,
+                            column_gap: theme.gap.normal,
+                            row_gap: theme.gap.normal,
+                            grid_template_columns: vec![GridTrack::auto(); 2],
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((LabelKind::Normal, Text::new("Seed:")));
+                            parent.spawn((SeedEdit, NumericEdit::new(options.seed)));
+
+                            parent.spawn((LabelKind::Normal, Text::new("Starting budget:")));
+                            parent.spawn((
+                                BudgetEdit,
+                                NumericEdit::new(options.starting_budget).with_step(BUDGET_STEP),
+                            ));
+                        });
+                    parent
+                        .spawn(Node {
+                            column_gap: theme.gap.normal,
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            for difficulty in Difficulty::iter() {
+                                parent
+                                    .spawn((
+                                        DifficultyButton(difficulty),
+                                        Toggled(difficulty == options.difficulty),
+                                    ))
+                                    .with_child(Text::new(difficulty.to_string()));
+                            }
+                        });
+                    parent
+                        .spawn((StarterCityCheckbox, Checkbox(options.starter_city)))
+                        .with_child(Text::new("Generate starter city"));
                     parent
                         .spawn(Node {
                             column_gap: theme.gap.normal,
@@ -403,11 +862,25 @@ fn create(
 fn confirm_create(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
-    world_name: Single<&TextInputValue>,
+    world_name: Single<&TextInputValue, With<WorldNameEdit>>,
+    seed: Single<&NumericEdit<u32>, With<SeedEdit>>,
+    budget: Single<&NumericEdit<u32>, With<BudgetEdit>>,
+    starter_city_checkbox: Single<&Checkbox, With<StarterCityCheckbox>>,
     dialog_entity: Single<Entity, With<Dialog>>,
+    buttons: Query<(&Toggled, &DifficultyButton)>,
 ) {
-    commands.insert_resource(WorldName(world_name.0.clone()));
-    commands.set_state(GameState::InGame);
+    let difficulty = buttons
+        .iter()
+        .find_map(|(toggled, button)| toggled.then_some(button.0))
+        .unwrap_or_default();
+
+    commands.trigger(WorldCreate(WorldCreateOptions {
+        name: world_name.0.clone(),
+        seed: seed.value,
+        starting_budget: budget.value,
+        difficulty,
+        starter_city: starter_city_checkbox.0,
+    }));
     commands.entity(*dialog_entity).despawn_recursive();
 }
 
@@ -426,7 +899,34 @@ fn join(
     theme: Res<Theme>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
 ) {
-    commands.entity(*root_entity).with_children(|parent| {
+    spawn_join_dialog(
+        &mut commands,
+        &theme,
+        *root_entity,
+        Ipv4Addr::LOCALHOST.into(),
+        DEFAULT_PORT,
+    );
+}
+
+fn join_lan(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    buttons: Query<&LanServerAddr>,
+) {
+    let addr = buttons.get(trigger.entity()).unwrap();
+    spawn_join_dialog(&mut commands, &theme, *root_entity, addr.ip(), addr.port());
+}
+
+fn spawn_join_dialog(
+    commands: &mut Commands,
+    theme: &Theme,
+    root_entity: Entity,
+    ip: IpAddr,
+    port: u16,
+) {
+    commands.entity(root_entity).with_children(|parent| {
         info!("showing join dialog");
         parent.spawn(Dialog).with_children(|parent| {
             parent
@@ -454,10 +954,13 @@ fn join(
                         })
                         .with_children(|parent| {
                             parent.spawn((LabelKind::Normal, Text::new("IP:")));
-                            parent.spawn((IpEdit, TextInputValue(Ipv4Addr::LOCALHOST.to_string())));
+                            parent.spawn((IpEdit, TextInputValue(ip.to_string())));
 
                             parent.spawn((LabelKind::Normal, Text::new("Port:")));
-                            parent.spawn((PortEdit, TextInputValue(DEFAULT_PORT.to_string())));
+                            parent.spawn((PortEdit, NumericEdit::new(port)));
+
+                            parent.spawn((LabelKind::Normal, Text::new("Password:")));
+                            parent.spawn(PasswordEdit);
                         });
 
                     parent
@@ -484,8 +987,10 @@ fn confirm_join(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
     network_channels: Res<RepliconChannels>,
-    port: Single<&TextInputValue, With<PortEdit>>,
+    port: Single<&NumericEdit<u16>, With<PortEdit>>,
     ip: Single<&TextInputValue, With<IpEdit>>,
+    password: Single<&TextInputValue, With<PasswordEdit>>,
+    spectator_checkbox: Single<&Checkbox, With<SpectatorCheckbox>>,
     dialog_entity: Single<Entity, With<Dialog>>,
 ) -> Result<()> {
     let client = RenetClient::new(ConnectionConfig {
@@ -493,11 +998,15 @@ fn confirm_join(
         client_channels_config: network_channels.get_client_configs(),
         ..Default::default()
     });
-    let transport = network::create_client(port.0.parse()?, ip.0.parse()?)
+    let transport = network::create_client(ip.0.parse()?, port.value, &password.0)
         .context("unable to create connection")?;
 
     commands.insert_resource(client);
     commands.insert_resource(transport);
+    commands.insert_resource(SessionPassword(password.0.clone()));
+    if spectator_checkbox.0 {
+        commands.insert_resource(JoinAsSpectator);
+    }
     commands.entity(*dialog_entity).despawn_recursive(); // Despawn only on transport creation.
 
     Ok(())
@@ -516,6 +1025,18 @@ fn back(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
     commands.set_state(MenuState::MainMenu);
 }
 
+/// Requests re-reading saved worlds into [`WorldList`].
+#[derive(Event)]
+struct RefreshWorlds;
+
+/// List of worlds from [`WorldManagement`].
+#[derive(Component)]
+struct WorldList;
+
+#[derive(Component)]
+#[require(TextEdit)]
+struct WorldNameEdit;
+
 /// Associated world node entities.
 #[derive(Clone, Component, Copy)]
 struct WorldNode {
@@ -523,10 +1044,46 @@ struct WorldNode {
     node_entity: Entity,
 }
 
+/// Named snapshot of a world.
+#[derive(Component)]
+struct SnapshotButton(String);
+
+/// List of servers from [`LanServers`].
+#[derive(Component)]
+struct LanList;
+
+#[derive(Component, Deref)]
+struct LanServerAddr(SocketAddr);
+
 #[derive(Component)]
-#[require(TextEdit)]
 struct PortEdit;
 
 #[derive(Component)]
 #[require(TextEdit)]
 struct IpEdit;
+
+#[derive(Component)]
+#[require(
+    TextEdit,
+    TextInputSettings(|| TextInputSettings {
+        mask_character: Some('*'),
+        retain_on_submit: true,
+    })
+)]
+struct PasswordEdit;
+
+#[derive(Component)]
+struct SpectatorCheckbox;
+
+#[derive(Component)]
+struct SeedEdit;
+
+#[derive(Component)]
+struct BudgetEdit;
+
+#[derive(Component)]
+struct StarterCityCheckbox;
+
+#[derive(Component, Clone, Copy)]
+#[require(ButtonKind(|| ButtonKind::Normal), ExclusiveButton)]
+struct DifficultyButton(Difficulty);
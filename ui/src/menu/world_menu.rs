@@ -1,23 +1,31 @@
-use std::mem;
+use std::{iter, mem};
 
 use bevy::prelude::*;
 use bevy_replicon::prelude::*;
 use bevy_simple_text_input::TextInputValue;
 
+use crate::tutorial::TutorialTarget;
 use project_harmonia_base::{
     core::GameState,
     error_message::ErrorMessage,
     game_world::{
         actor::SelectedActor,
-        city::{ActiveCity, City},
-        family::{Family, FamilyDelete, FamilyMembers},
+        city::{ActiveCity, City, LotKind},
+        family::{npc::Npc, Family, FamilyAppearance, FamilyDelete, FamilyMembers},
+        loading::LoadingStart,
+        players::{ClaimedFamily, LocalPermission},
         WorldName, WorldState,
     },
+    tutorial::TutorialStep,
 };
 use project_harmonia_widgets::{
-    button::{ButtonKind, TabContent, Toggled},
-    dialog::Dialog,
+    button::{ButtonKind, Disabled},
+    checkbox::Checkbox,
+    click::DoubleClick,
+    dialog::{ConfirmationDialog, Dialog, DialogConfirmed},
     label::LabelKind,
+    scroll_view::ScrollView,
+    tab_view::{ActiveTab, Tab, TabView},
     text_edit::TextEdit,
     theme::Theme,
 };
@@ -31,17 +39,24 @@ impl Plugin for WorldMenuPlugin {
             .add_observer(remove_entity_nodes::<City>)
             .add_observer(create_family_nodes)
             .add_observer(create_city_nodes)
-            .add_systems(OnEnter(WorldState::World), setup);
+            .add_observer(init_play_button)
+            .add_observer(play_family_on_double_click)
+            .add_observer(disable_play_button)
+            .add_observer(enable_play_button)
+            .add_systems(OnEnter(WorldState::World), setup)
+            .add_systems(
+                Update,
+                update_play_buttons.run_if(resource_changed::<LocalPermission>),
+            );
     }
 }
 
 fn setup(
     mut commands: Commands,
-    mut tab_commands: Commands,
     theme: Res<Theme>,
     world_name: Res<WorldName>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
-    families: Query<(Entity, &Name), With<Family>>,
+    families: Query<(Entity, &Name, &FamilyAppearance), (With<Family>, Without<Npc>)>,
     cities: Query<(Entity, &Name), With<City>>,
 ) {
     commands.entity(*root_entity).with_children(|parent| {
@@ -62,61 +77,61 @@ fn setup(
             .with_children(|parent| {
                 parent.spawn((LabelKind::Large, Text::new(world_name.0.clone())));
 
-                let tabs_entity = parent
-                    .spawn(Node {
-                        justify_content: JustifyContent::Center,
-                        ..Default::default()
-                    })
-                    .id();
-
-                for tab in WorldTab::iter() {
-                    let content_entity = parent
-                        .spawn(Node {
+                parent
+                    .spawn((
+                        TabView,
+                        Node {
                             width: Val::Percent(100.0),
                             height: Val::Percent(100.0),
-                            flex_direction: FlexDirection::Column,
-                            align_items: AlignItems::Center,
-                            justify_content: JustifyContent::FlexStart,
-                            padding: theme.padding.normal,
-                            row_gap: theme.gap.normal,
                             ..Default::default()
-                        })
-                        .with_children(|parent| match tab {
-                            WorldTab::Families => {
-                                for (entity, name) in &families {
-                                    setup_entity_node(
-                                        setup_family_buttons,
-                                        parent,
-                                        &theme,
-                                        entity,
-                                        name,
-                                    );
-                                }
-                            }
-                            WorldTab::Cities => {
-                                for (entity, name) in &cities {
-                                    setup_entity_node(
-                                        setup_city_buttons,
-                                        parent,
-                                        &theme,
-                                        entity,
-                                        name,
-                                    );
-                                }
-                            }
-                        })
-                        .id();
-
-                    tab_commands
-                        .spawn((
-                            tab,
-                            ButtonKind::Normal,
-                            TabContent(content_entity),
-                            Toggled(tab == Default::default()),
-                        ))
-                        .with_child(Text::new(tab.text()))
-                        .set_parent(tabs_entity);
-                }
+                        },
+                    ))
+                    .with_children(|parent| {
+                        for tab in WorldTab::iter() {
+                            parent
+                                .spawn((
+                                    tab,
+                                    Tab(tab.text().to_string()),
+                                    ScrollView,
+                                    Node {
+                                        width: Val::Percent(100.0),
+                                        height: Val::Percent(100.0),
+                                        flex_direction: FlexDirection::Column,
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::FlexStart,
+                                        padding: theme.padding.normal,
+                                        row_gap: theme.gap.normal,
+                                        ..Default::default()
+                                    },
+                                ))
+                                .with_children(|parent| match tab {
+                                    WorldTab::Families => {
+                                        for (entity, name, &appearance) in &families {
+                                            setup_entity_node(
+                                                setup_family_buttons,
+                                                parent,
+                                                &theme,
+                                                entity,
+                                                Some(appearance),
+                                                name,
+                                            );
+                                        }
+                                    }
+                                    WorldTab::Cities => {
+                                        for (entity, name) in &cities {
+                                            setup_entity_node(
+                                                setup_city_buttons,
+                                                parent,
+                                                &theme,
+                                                entity,
+                                                None,
+                                                name,
+                                            );
+                                        }
+                                    }
+                                });
+                        }
+                    });
 
                 parent
                     .spawn(Node {
@@ -134,7 +149,10 @@ fn setup(
                             ..Default::default()
                         });
                         parent
-                            .spawn(ButtonKind::Normal)
+                            .spawn((
+                                ButtonKind::Normal,
+                                TutorialTarget(TutorialStep::CreateFamily),
+                            ))
                             .with_child(Text::new("Create"))
                             .observe(create);
                     });
@@ -147,8 +165,16 @@ fn setup_entity_node(
     parent: &mut ChildBuilder,
     theme: &Theme,
     entity: Entity,
+    appearance: Option<FamilyAppearance>,
     label: impl Into<String>,
 ) {
+    let (border, border_color) = match appearance {
+        Some(appearance) => (
+            UiRect::all(Val::Px(2.0)),
+            BorderColor(appearance.color.color()),
+        ),
+        None => Default::default(),
+    };
     parent
         .spawn((
             WorldEntity(entity),
@@ -156,8 +182,10 @@ fn setup_entity_node(
             Node {
                 padding: theme.padding.normal,
                 column_gap: theme.gap.normal,
+                border,
                 ..Default::default()
             },
+            border_color,
             theme.panel_background,
         ))
         .with_children(|parent| {
@@ -165,9 +193,23 @@ fn setup_entity_node(
                 .spawn(Node {
                     width: Val::Percent(100.0),
                     height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    column_gap: theme.gap.normal,
                     ..Default::default()
                 })
                 .with_children(|parent| {
+                    if let Some(appearance) = appearance {
+                        // Not a label to keep the color from being overridden by the theme.
+                        parent.spawn((
+                            Text::new(appearance.crest.glyph()),
+                            TextFont {
+                                font: theme.label.symbol.font.clone(),
+                                font_size: theme.label.symbol.font_size,
+                                ..Default::default()
+                            },
+                            TextColor(appearance.color.color()),
+                        ));
+                    }
                     parent.spawn((LabelKind::Large, Text::new(label)));
                 });
             parent
@@ -184,7 +226,7 @@ fn setup_entity_node(
 
 fn setup_family_buttons(parent: &mut ChildBuilder, world_entity: WorldEntity) {
     parent
-        .spawn((ButtonKind::Normal, world_entity))
+        .spawn((PlayButton, world_entity))
         .with_child(Text::new("Play"))
         .observe(play_family);
     parent
@@ -205,24 +247,148 @@ fn play_family(
     let members = families
         .get(world_entity)
         .expect("world entity node should reference a family");
+
+    start_playing(&mut commands, world_entity, members);
+}
+
+fn play_family_on_double_click(
+    trigger: Trigger<DoubleClick>,
+    mut commands: Commands,
+    local_permission: Res<LocalPermission>,
+    nodes: Query<&WorldEntity, With<WorldNode>>,
+    families: Query<&FamilyMembers, Without<ClaimedFamily>>,
+    parents: Query<&Parent>,
+    buttons: Query<(), With<Button>>,
+) {
+    let Ok(&world_entity) = nodes.get(trigger.entity()) else {
+        return;
+    };
+    if !local_permission.can_play() {
+        return;
+    }
+
+    // Ignore fast clicks on node buttons.
+    if iter::once(trigger.target)
+        .chain(parents.iter_ancestors(trigger.target))
+        .take_while(|&entity| entity != trigger.entity())
+        .any(|entity| buttons.get(entity).is_ok())
+    {
+        return;
+    }
+
+    if let Ok(members) = families.get(*world_entity) {
+        start_playing(&mut commands, *world_entity, members);
+    }
+}
+
+fn start_playing(commands: &mut Commands, family_entity: Entity, members: &FamilyMembers) {
     let actor_entity = *members
         .first()
         .expect("family always have at least one member");
 
-    info!("starting playing for family `{world_entity}`");
+    info!("starting playing for family `{family_entity}`");
     commands.entity(actor_entity).insert(SelectedActor);
-    commands.set_state(WorldState::Family);
+    commands.trigger(LoadingStart(WorldState::Family));
+}
+
+fn init_play_button(
+    trigger: Trigger<OnAdd, PlayButton>,
+    mut commands: Commands,
+    local_permission: Res<LocalPermission>,
+    buttons: Query<&WorldEntity>,
+    families: Query<(), With<ClaimedFamily>>,
+) {
+    let world_entity = **buttons.get(trigger.entity()).unwrap();
+    if !local_permission.can_play() || families.get(world_entity).is_ok() {
+        commands.entity(trigger.entity()).insert(Disabled);
+    }
+}
+
+/// Disables playing for families controlled by other players.
+fn disable_play_button(
+    trigger: Trigger<OnAdd, ClaimedFamily>,
+    mut commands: Commands,
+    buttons: Query<(Entity, &WorldEntity), With<PlayButton>>,
+) {
+    if let Some((button_entity, _)) = buttons
+        .iter()
+        .find(|(_, &world_entity)| *world_entity == trigger.entity())
+    {
+        debug!("disabling play for claimed family `{}`", trigger.entity());
+        commands.entity(button_entity).insert(Disabled);
+    }
+}
+
+fn enable_play_button(
+    trigger: Trigger<OnRemove, ClaimedFamily>,
+    mut commands: Commands,
+    local_permission: Res<LocalPermission>,
+    buttons: Query<(Entity, &WorldEntity), With<PlayButton>>,
+) {
+    if !local_permission.can_play() {
+        return;
+    }
+
+    if let Some((button_entity, _)) = buttons
+        .iter()
+        .find(|(_, &world_entity)| *world_entity == trigger.entity())
+    {
+        debug!("enabling play for released family `{}`", trigger.entity());
+        commands.entity(button_entity).remove::<Disabled>();
+    }
+}
+
+/// Disables playing for spectators and enables it back after promotion.
+fn update_play_buttons(
+    mut commands: Commands,
+    local_permission: Res<LocalPermission>,
+    buttons: Query<(Entity, &WorldEntity), With<PlayButton>>,
+    families: Query<(), With<ClaimedFamily>>,
+) {
+    for (button_entity, &world_entity) in &buttons {
+        if !local_permission.can_play() || families.get(*world_entity).is_ok() {
+            commands.entity(button_entity).insert(Disabled);
+        } else {
+            commands.entity(button_entity).remove::<Disabled>();
+        }
+    }
 }
 
 fn delete_family(
     trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
     buttons: Query<&WorldEntity>,
+    names: Query<&Name>,
 ) {
-    let world_entity = **buttons
+    let world_entity = *buttons
         .get(trigger.entity())
         .expect("family button should reference world entity node");
+    let name = names
+        .get(*world_entity)
+        .map(Name::as_str)
+        .unwrap_or_default();
 
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                world_entity,
+                ConfirmationDialog::new(
+                    "Delete family",
+                    format!("Are you sure you want to delete family {name}?"),
+                )
+                .with_confirm_text("Delete"),
+            ))
+            .observe(confirm_family_delete);
+    });
+}
+
+fn confirm_family_delete(
+    trigger: Trigger<DialogConfirmed>,
+    mut commands: Commands,
+    dialogs: Query<&WorldEntity>,
+) {
+    let world_entity = **dialogs.get(trigger.entity()).unwrap();
     info!("deleting family `{world_entity}`");
     commands.client_trigger_targets(FamilyDelete, world_entity);
 }
@@ -249,17 +415,44 @@ fn edit_city(
 
     info!("starting editing city `{world_entity}`");
     commands.entity(world_entity).insert(ActiveCity);
-    commands.set_state(WorldState::City);
+    commands.trigger(LoadingStart(WorldState::City));
 }
 
 fn delete_city(
     trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
     buttons: Query<&WorldEntity>,
+    names: Query<&Name>,
 ) {
-    let world_entity = **buttons
+    let world_entity = *buttons
         .get(trigger.entity())
         .expect("city button should reference world entity node");
+    let name = names
+        .get(*world_entity)
+        .map(Name::as_str)
+        .unwrap_or_default();
+
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                world_entity,
+                ConfirmationDialog::new(
+                    "Delete city",
+                    format!("Are you sure you want to delete city {name}?"),
+                )
+                .with_confirm_text("Delete"),
+            ))
+            .observe(confirm_city_delete);
+    });
+}
+
+fn confirm_city_delete(
+    trigger: Trigger<DialogConfirmed>,
+    mut commands: Commands,
+    dialogs: Query<&WorldEntity>,
+) {
+    let world_entity = **dialogs.get(trigger.entity()).unwrap();
 
     // TODO: use event for despawn, otherwise client will despawn the city locally.
     info!("deleting city `{world_entity}`");
@@ -288,6 +481,9 @@ fn setup_create_city_dialog(parent: &mut ChildBuilder, theme: &Theme) {
                     TextEdit,
                     TextInputValue("New city".to_string()),
                 ));
+                parent
+                    .spawn((CommunityCheckbox, Checkbox(false)))
+                    .with_child(Text::new("Community lot"));
                 parent
                     .spawn(Node {
                         column_gap: theme.gap.normal,
@@ -311,10 +507,17 @@ fn confirm_city_creation(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
     mut city_name: Single<&mut TextInputValue, With<CityNameEdit>>,
+    community_checkbox: Single<&Checkbox, With<CommunityCheckbox>>,
     dialog_entity: Single<Entity, With<Dialog>>,
 ) {
-    info!("creating new city");
-    commands.spawn((City, Name::new(mem::take(&mut city_name.0))));
+    let lot_kind = if community_checkbox.0 {
+        LotKind::Community
+    } else {
+        LotKind::Residential
+    };
+
+    info!("creating new `{lot_kind:?}` city");
+    commands.spawn((City, lot_kind, Name::new(mem::take(&mut city_name.0))));
     commands.entity(*dialog_entity).despawn_recursive();
 }
 
@@ -335,13 +538,10 @@ fn create(
     mut commands: Commands,
     theme: Res<Theme>,
     root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
-    tabs: Query<(&Toggled, &WorldTab)>,
+    current_tab: Single<&WorldTab, With<ActiveTab>>,
     cities: Query<(), With<City>>,
 ) {
-    let current_tab = tabs
-        .iter()
-        .find_map(|(toggled, world_tab)| toggled.0.then_some(world_tab))
-        .expect("one tab should always be active");
+    let current_tab = **current_tab;
 
     info!("starting creation for `{current_tab:?}`");
     match current_tab {
@@ -364,19 +564,29 @@ fn create_family_nodes(
     trigger: Trigger<OnAdd, Family>,
     mut commands: Commands,
     theme: Res<Theme>,
-    families: Query<&Name>,
-    tabs: Query<(&TabContent, &WorldTab)>,
+    families: Query<(&Name, &FamilyAppearance, Has<Npc>)>,
+    tabs: Query<(Entity, &WorldTab)>,
     nodes: Query<&WorldEntity, With<WorldNode>>,
 ) {
-    let Some((tab_content, _)) = tabs.iter().find(|(_, &tab)| tab == WorldTab::Families) else {
+    let Some((tab_entity, _)) = tabs.iter().find(|(_, &tab)| tab == WorldTab::Families) else {
         return;
     };
 
-    let name = families.get(trigger.entity()).unwrap();
+    let (name, &appearance, npc) = families.get(trigger.entity()).unwrap();
+    if npc {
+        return;
+    }
     if nodes.iter().all(|&entity| *entity != trigger.entity()) {
         debug!("creating button for family '{name}'");
-        commands.entity(**tab_content).with_children(|parent| {
-            setup_entity_node(setup_family_buttons, parent, &theme, trigger.entity(), name);
+        commands.entity(tab_entity).with_children(|parent| {
+            setup_entity_node(
+                setup_family_buttons,
+                parent,
+                &theme,
+                trigger.entity(),
+                Some(appearance),
+                name,
+            );
         });
     }
 }
@@ -386,18 +596,25 @@ fn create_city_nodes(
     mut commands: Commands,
     theme: Res<Theme>,
     cities: Query<&Name>,
-    tabs: Query<(&TabContent, &WorldTab)>,
+    tabs: Query<(Entity, &WorldTab)>,
     nodes: Query<&WorldEntity, With<WorldNode>>,
 ) {
-    let Some((tab_content, _)) = tabs.iter().find(|(_, &tab)| tab == WorldTab::Cities) else {
+    let Some((tab_entity, _)) = tabs.iter().find(|(_, &tab)| tab == WorldTab::Cities) else {
         return;
     };
 
     let name = cities.get(trigger.entity()).unwrap();
     if nodes.iter().all(|&entity| *entity != trigger.entity()) {
         debug!("creating button for city '{name}'");
-        commands.entity(**tab_content).with_children(|parent| {
-            setup_entity_node(setup_city_buttons, parent, &theme, trigger.entity(), name);
+        commands.entity(tab_entity).with_children(|parent| {
+            setup_entity_node(
+                setup_city_buttons,
+                parent,
+                &theme,
+                trigger.entity(),
+                None,
+                name,
+            );
         });
     }
 }
@@ -445,3 +662,10 @@ struct WorldNode;
 
 #[derive(Component)]
 struct CityNameEdit;
+
+#[derive(Component)]
+struct CommunityCheckbox;
+
+#[derive(Component)]
+#[require(ButtonKind(|| ButtonKind::Normal))]
+struct PlayButton;
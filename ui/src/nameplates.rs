@@ -0,0 +1,313 @@
+use std::f32::consts::TAU;
+
+use bevy::{prelude::*, ui::UiSystem};
+use project_harmonia_base::{
+    game_world::{
+        actor::{
+            needs::{Need, NeedGlyph},
+            task::{ActiveTask, Conversation},
+            Actor, FirstName, LastName, ACTOR_HEIGHT,
+        },
+        family::FamilyAppearance,
+        player_camera::PlayerCamera,
+    },
+    settings::{NameplateMode, Settings},
+};
+use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+
+/// Displays names with family crests, speech bubbles and critical needs above actors.
+pub(super) struct NameplatesPlugin;
+
+impl Plugin for NameplatesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HoveredActor>()
+            .add_observer(spawn)
+            .add_observer(despawn)
+            .add_observer(hover)
+            .add_observer(unhover)
+            .add_systems(
+                PostUpdate,
+                (
+                    update_names,
+                    update_crests,
+                    update_icons,
+                    update_positions.never_param_warn(),
+                )
+                    .chain()
+                    .before(UiSystem::Layout),
+            );
+    }
+}
+
+/// Distance between the actor's head and its nameplate.
+const OFFSET: f32 = 0.3;
+
+/// Flashes per second of critical need icons.
+const FLASH_FREQUENCY: f32 = 1.5;
+
+const SPEECH_GLYPH: &str = "💬";
+
+fn spawn(
+    trigger: Trigger<OnAdd, Actor>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+) {
+    debug!("spawning nameplate for `{}`", trigger.entity());
+    commands.entity(*root_entity).with_children(|parent| {
+        let mut nameplate = parent.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            // Keep below HUD.
+            ZIndex(-1),
+            PickingBehavior::IGNORE,
+            Visibility::Hidden,
+        ));
+
+        let mut speech_entity = Entity::PLACEHOLDER;
+        let mut needs_entity = Entity::PLACEHOLDER;
+        let mut title_entity = Entity::PLACEHOLDER;
+        let mut crest_entity = Entity::PLACEHOLDER;
+        let mut name_entity = Entity::PLACEHOLDER;
+        nameplate.with_children(|parent| {
+            parent
+                .spawn((Node::default(), PickingBehavior::IGNORE))
+                .with_children(|parent| {
+                    speech_entity = parent
+                        .spawn((
+                            LabelKind::Symbol,
+                            Text::new(SPEECH_GLYPH),
+                            PickingBehavior::IGNORE,
+                        ))
+                        .id();
+                    needs_entity = parent
+                        .spawn((LabelKind::Symbol, Text::default(), PickingBehavior::IGNORE))
+                        .id();
+                });
+            title_entity = parent
+                .spawn((
+                    Node {
+                        align_items: AlignItems::Center,
+                        column_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                    PickingBehavior::IGNORE,
+                ))
+                .with_children(|parent| {
+                    // Not a label to keep the family color from being overridden by the theme.
+                    crest_entity = parent
+                        .spawn((
+                            Text::default(),
+                            TextFont {
+                                font: theme.label.symbol.font.clone(),
+                                font_size: theme.label.small.font_size,
+                                ..Default::default()
+                            },
+                            PickingBehavior::IGNORE,
+                        ))
+                        .id();
+                    name_entity = parent
+                        .spawn((LabelKind::Small, Text::default(), PickingBehavior::IGNORE))
+                        .id();
+                })
+                .id();
+        });
+
+        nameplate.insert(Nameplate {
+            actor_entity: trigger.entity(),
+            speech_entity,
+            needs_entity,
+            title_entity,
+            crest_entity,
+            name_entity,
+        });
+    });
+}
+
+fn despawn(
+    trigger: Trigger<OnRemove, Actor>,
+    mut commands: Commands,
+    nameplates: Query<(Entity, &Nameplate)>,
+) {
+    if let Some((entity, _)) = nameplates
+        .iter()
+        .find(|(_, nameplate)| nameplate.actor_entity == trigger.entity())
+    {
+        debug!("despawning nameplate for `{}`", trigger.entity());
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn hover(
+    trigger: Trigger<Pointer<Over>>,
+    mut hovered_actor: ResMut<HoveredActor>,
+    actors: Query<(), With<Actor>>,
+) {
+    if actors.get(trigger.entity()).is_ok() {
+        **hovered_actor = Some(trigger.entity());
+    }
+}
+
+fn unhover(trigger: Trigger<Pointer<Out>>, mut hovered_actor: ResMut<HoveredActor>) {
+    if **hovered_actor == Some(trigger.entity()) {
+        **hovered_actor = None;
+    }
+}
+
+fn update_names(
+    actors: Query<(&FirstName, &LastName), Or<(Changed<FirstName>, Changed<LastName>)>>,
+    nameplates: Query<&Nameplate>,
+    mut texts: Query<&mut Text>,
+) {
+    for nameplate in &nameplates {
+        if let Ok((first_name, last_name)) = actors.get(nameplate.actor_entity) {
+            let mut text = texts
+                .get_mut(nameplate.name_entity)
+                .expect("nameplate should have a name text");
+            text.0 = format!("{} {}", first_name.0, last_name.0);
+        }
+    }
+}
+
+fn update_crests(
+    actors: Query<Ref<Actor>>,
+    families: Query<Ref<FamilyAppearance>>,
+    nameplates: Query<&Nameplate>,
+    mut texts: Query<(&mut Text, &mut TextColor)>,
+) {
+    for nameplate in &nameplates {
+        let Ok(actor) = actors.get(nameplate.actor_entity) else {
+            continue;
+        };
+        let Ok(appearance) = families.get(actor.family_entity) else {
+            continue;
+        };
+        if !actor.is_changed() && !appearance.is_changed() {
+            continue;
+        }
+
+        let (mut text, mut color) = texts
+            .get_mut(nameplate.crest_entity)
+            .expect("nameplate should have a crest text");
+        text.0 = appearance.crest.glyph().into();
+        color.0 = appearance.color.color();
+    }
+}
+
+fn update_icons(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    hovered_actor: Res<HoveredActor>,
+    actors: Query<&Children>,
+    needs: Query<(&Need, &NeedGlyph)>,
+    conversations: Query<(), (With<Conversation>, With<ActiveTask>)>,
+    nameplates: Query<(Entity, &Nameplate)>,
+    mut nodes: Query<&mut Node>,
+    mut texts: Query<(&mut Text, &mut TextColor)>,
+) {
+    let alpha = if settings.accessibility.reduced_motion {
+        1.0
+    } else {
+        0.5 + 0.5 * (time.elapsed_secs() * FLASH_FREQUENCY * TAU).cos()
+    };
+
+    for (nameplate_entity, nameplate) in &nameplates {
+        let children = actors
+            .get(nameplate.actor_entity)
+            .map(|children| &**children)
+            .unwrap_or_default();
+
+        let indicators = settings.gameplay.actor_indicators;
+        let speaking = indicators && conversations.iter_many(children).next().is_some();
+        let critical_glyphs: String = if indicators {
+            needs
+                .iter_many(children)
+                .filter(|(need, _)| need.is_critical())
+                .map(|(_, glyph)| glyph.0)
+                .collect()
+        } else {
+            Default::default()
+        };
+        let show_name = match settings.gameplay.nameplates {
+            NameplateMode::Always => true,
+            NameplateMode::Hover => **hovered_actor == Some(nameplate.actor_entity),
+            NameplateMode::Never => false,
+        };
+
+        let (mut text, mut color) = texts
+            .get_mut(nameplate.needs_entity)
+            .expect("nameplate should have a needs text");
+        if text.0 != critical_glyphs {
+            text.0 = critical_glyphs;
+        }
+        color.0.set_alpha(alpha);
+
+        for (entity, displayed) in [
+            (nameplate.speech_entity, speaking),
+            (nameplate.needs_entity, !text.0.is_empty()),
+            (nameplate.title_entity, show_name),
+            (
+                nameplate_entity,
+                speaking || !text.0.is_empty() || show_name,
+            ),
+        ] {
+            let mut node = nodes.get_mut(entity).unwrap();
+            let display = if displayed {
+                Display::Flex
+            } else {
+                Display::None
+            };
+            if node.display != display {
+                node.display = display;
+            }
+        }
+    }
+}
+
+/// Places nameplates above actors in screen space.
+///
+/// Uses transforms and node sizes from the last frame.
+fn update_positions(
+    ui_scale: Res<UiScale>,
+    camera: Single<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    actors: Query<(&GlobalTransform, &InheritedVisibility)>,
+    mut nameplates: Query<(&mut Node, &mut Visibility, &ComputedNode, &Nameplate)>,
+) {
+    let (camera, camera_transform) = *camera;
+    for (mut node, mut visibility, computed_node, nameplate) in &mut nameplates {
+        let Ok((transform, inherited_visibility)) = actors.get(nameplate.actor_entity) else {
+            continue;
+        };
+
+        let position = transform.translation() + Vec3::Y * (ACTOR_HEIGHT + OFFSET);
+        match camera.world_to_viewport(camera_transform, position) {
+            Ok(viewport_position) if inherited_visibility.get() => {
+                let size = computed_node.size() * computed_node.inverse_scale_factor();
+                node.left = Val::Px(viewport_position.x / ui_scale.0 - size.x / 2.0);
+                node.top = Val::Px(viewport_position.y / ui_scale.0 - size.y);
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            _ => {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct Nameplate {
+    actor_entity: Entity,
+    speech_entity: Entity,
+    needs_entity: Entity,
+    title_entity: Entity,
+    crest_entity: Entity,
+    name_entity: Entity,
+}
+
+/// Actor under cursor, used for [`NameplateMode::Hover`].
+#[derive(Resource, Default, Deref, DerefMut)]
+struct HoveredActor(Option<Entity>);
@@ -0,0 +1,88 @@
+use bevy::{
+    diagnostic::{DiagnosticPath, DiagnosticsStore},
+    prelude::*,
+};
+
+use project_harmonia_base::{game_world::navigation::nav_tiles, settings::Settings};
+use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+
+/// Displays navmesh rebuild statistics.
+///
+/// Shown together with the navmesh debug rendering from developer settings.
+pub(super) struct NavMeshStatsPlugin;
+
+impl Plugin for NavMeshStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                toggle.never_param_warn(),
+                update_texts.run_if(any_with_component::<NavMeshStats>),
+            )
+                .chain(),
+        );
+    }
+}
+
+const STATS: [(DiagnosticPath, &str); 3] = [
+    (nav_tiles::REBUILDS, "Navmesh rebuilds"),
+    (nav_tiles::DIRTY_TILES, "Dirty tiles"),
+    (nav_tiles::BUILD_TIME, "Build time"),
+];
+
+fn toggle(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    settings: Res<Settings>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    stats_entity: Option<Single<Entity, With<NavMeshStats>>>,
+) {
+    match (settings.developer.nav_mesh, stats_entity) {
+        (true, None) => {
+            debug!("showing navmesh stats");
+            commands.entity(*root_entity).with_children(|parent| {
+                parent
+                    .spawn((
+                        NavMeshStats,
+                        Node {
+                            position_type: PositionType::Absolute,
+                            bottom: Val::Px(0.0),
+                            right: Val::Px(0.0),
+                            flex_direction: FlexDirection::Column,
+                            padding: theme.padding.normal,
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        for (index, (_, name)) in STATS.iter().enumerate() {
+                            parent.spawn((LabelKind::Small, StatText(index), Text::new(*name)));
+                        }
+                    });
+            });
+        }
+        (false, Some(stats_entity)) => {
+            debug!("hiding navmesh stats");
+            commands.entity(*stats_entity).despawn_recursive();
+        }
+        _ => (),
+    }
+}
+
+fn update_texts(store: Res<DiagnosticsStore>, mut texts: Query<(&mut Text, &StatText)>) {
+    for (mut text, stat_text) in &mut texts {
+        let (path, name) = &STATS[**stat_text];
+        if let Some(diagnostic) = store.get(path) {
+            if let Some(value) = diagnostic.value() {
+                text.0 = format!("{name}: {value:.1}{}", diagnostic.suffix);
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct NavMeshStats;
+
+/// Index of the displayed stat from [`STATS`].
+#[derive(Component, Deref)]
+struct StatText(usize);
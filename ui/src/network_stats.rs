@@ -0,0 +1,194 @@
+use bevy::{
+    diagnostic::{DiagnosticPath, DiagnosticsStore},
+    prelude::*,
+};
+use bevy_replicon::{client::diagnostics::ClientDiagnosticsPlugin, prelude::*};
+
+use project_harmonia_base::settings::Settings;
+use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+
+/// Displays replication statistics for connected clients.
+///
+/// Toggled from developer settings.
+pub(super) struct NetworkStatsPlugin;
+
+impl Plugin for NetworkStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                toggle.never_param_warn(),
+                (update_texts, update_graphs).run_if(any_with_component::<NetworkStats>),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Number of bars in each graph.
+const GRAPH_LEN: usize = 60;
+
+const GRAPH_WIDTH: f32 = 180.0;
+const GRAPH_HEIGHT: f32 = 30.0;
+
+const STATS: [Stat; 7] = [
+    Stat {
+        path: ClientDiagnosticsPlugin::RTT,
+        name: "RTT",
+        unit: "ms",
+        scale: 1000.0,
+    },
+    Stat {
+        path: ClientDiagnosticsPlugin::PACKET_LOSS,
+        name: "Packet loss",
+        unit: "%",
+        scale: 100.0,
+    },
+    Stat {
+        path: ClientDiagnosticsPlugin::RECEIVED_BPS,
+        name: "Received",
+        unit: "KiB/s",
+        scale: 1.0 / 1024.0,
+    },
+    Stat {
+        path: ClientDiagnosticsPlugin::SENT_BPS,
+        name: "Sent",
+        unit: "KiB/s",
+        scale: 1.0 / 1024.0,
+    },
+    Stat {
+        path: ClientDiagnosticsPlugin::REPLICATION_BYTES,
+        name: "Replication",
+        unit: "B/tick",
+        scale: 1.0,
+    },
+    Stat {
+        path: ClientDiagnosticsPlugin::ENTITY_CHANGES,
+        name: "Changed entities",
+        unit: "per tick",
+        scale: 1.0,
+    },
+    Stat {
+        path: ClientDiagnosticsPlugin::DESPAWNS,
+        name: "Despawns",
+        unit: "per tick",
+        scale: 1.0,
+    },
+];
+
+/// Spawns or despawns the overlay depending on settings and connection status.
+fn toggle(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    settings: Res<Settings>,
+    client: Res<RepliconClient>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    stats_entity: Option<Single<Entity, With<NetworkStats>>>,
+) {
+    let enabled = settings.developer.network_stats && client.is_connected();
+    match (enabled, stats_entity) {
+        (true, None) => {
+            debug!("showing network stats");
+            commands.entity(*root_entity).with_children(|parent| {
+                parent
+                    .spawn((
+                        NetworkStats,
+                        Node {
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(0.0),
+                            right: Val::Px(0.0),
+                            flex_direction: FlexDirection::Column,
+                            padding: theme.padding.normal,
+                            row_gap: theme.gap.normal,
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        for (index, stat) in STATS.iter().enumerate() {
+                            parent.spawn((LabelKind::Small, StatText(index), Text::new(stat.name)));
+                            parent
+                                .spawn((
+                                    StatGraph(index),
+                                    Node {
+                                        width: Val::Px(GRAPH_WIDTH),
+                                        height: Val::Px(GRAPH_HEIGHT),
+                                        align_items: AlignItems::End,
+                                        ..Default::default()
+                                    },
+                                    theme.background_color,
+                                ))
+                                .with_children(|parent| {
+                                    for _ in 0..GRAPH_LEN {
+                                        parent.spawn((
+                                            Node {
+                                                width: Val::Percent(100.0 / GRAPH_LEN as f32),
+                                                height: Val::Percent(0.0),
+                                                ..Default::default()
+                                            },
+                                            BackgroundColor(theme.label.normal.color.0),
+                                        ));
+                                    }
+                                });
+                        }
+                    });
+            });
+        }
+        (false, Some(stats_entity)) => {
+            debug!("hiding network stats");
+            commands.entity(*stats_entity).despawn_recursive();
+        }
+        _ => (),
+    }
+}
+
+fn update_texts(store: Res<DiagnosticsStore>, mut texts: Query<(&mut Text, &StatText)>) {
+    for (mut text, stat_text) in &mut texts {
+        let stat = &STATS[**stat_text];
+        if let Some(value) = store
+            .get(&stat.path)
+            .and_then(|diagnostic| diagnostic.smoothed())
+        {
+            text.0 = format!("{}: {:.1} {}", stat.name, value * stat.scale, stat.unit);
+        }
+    }
+}
+
+/// Scales bars to the maximum value in the diagnostic history.
+fn update_graphs(
+    store: Res<DiagnosticsStore>,
+    graphs: Query<(&StatGraph, &Children)>,
+    mut bars: Query<&mut Node>,
+) {
+    for (graph, children) in &graphs {
+        let Some(diagnostic) = store.get(&STATS[**graph].path) else {
+            continue;
+        };
+
+        let max = diagnostic.values().copied().fold(f64::EPSILON, f64::max);
+        let mut values = diagnostic.values().rev();
+        let mut iter = bars.iter_many_mut(children.iter().rev());
+        while let Some(mut node) = iter.fetch_next() {
+            let value = values.next().copied().unwrap_or_default();
+            node.height = Val::Percent((value / max * 100.0) as f32);
+        }
+    }
+}
+
+struct Stat {
+    path: DiagnosticPath,
+    name: &'static str,
+    unit: &'static str,
+    scale: f64,
+}
+
+#[derive(Component)]
+struct NetworkStats;
+
+/// Index of the displayed stat from [`STATS`].
+#[derive(Component, Deref)]
+struct StatText(usize);
+
+/// Index of the graphed stat from [`STATS`].
+#[derive(Component, Deref)]
+struct StatGraph(usize);
@@ -0,0 +1,172 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+
+use project_harmonia_base::notification::{Notification, NotificationKind};
+use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+
+/// Displays [`Notification`]s as toasts in the corner of the screen.
+///
+/// Shows a limited number of toasts at once, others wait in the queue.
+pub(super) struct NotificationsPlugin;
+
+impl Plugin for NotificationsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NotificationQueue>()
+            .add_observer(enqueue)
+            .add_systems(Update, (show, animate).chain())
+            .add_systems(PostUpdate, restyle.run_if(resource_changed::<Theme>));
+    }
+}
+
+const MAX_VISIBLE: usize = 3;
+const DISPLAY_DURATION: Duration = Duration::from_secs(5);
+const SLIDE_DURATION: f32 = 0.3;
+
+/// Offset from which toasts slide in, in logical pixels.
+const SLIDE_DISTANCE: f32 = 350.0;
+
+fn enqueue(trigger: Trigger<Notification>, mut queue: ResMut<NotificationQueue>) {
+    debug!("queuing notification: {}", trigger.message);
+    queue.push_back(trigger.event().clone());
+}
+
+fn show(
+    mut commands: Commands,
+    mut queue: ResMut<NotificationQueue>,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    area_entity: Option<Single<Entity, With<NotificationArea>>>,
+    toasts: Query<(), With<Toast>>,
+) {
+    if queue.is_empty() {
+        return;
+    }
+
+    let area_entity = match area_entity {
+        Some(area_entity) => *area_entity,
+        None => {
+            debug!("spawning notification area");
+            commands
+                .spawn((
+                    NotificationArea,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(0.0),
+                        right: Val::Px(0.0),
+                        flex_direction: FlexDirection::Column,
+                        padding: theme.padding.global,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                    GlobalZIndex(i32::MAX - 1),
+                    PickingBehavior::IGNORE,
+                ))
+                .set_parent(*root_entity)
+                .id()
+        }
+    };
+
+    let count = MAX_VISIBLE
+        .saturating_sub(toasts.iter().count())
+        .min(queue.len());
+    for notification in queue.drain(..count) {
+        debug!("showing notification: {}", notification.message);
+        commands.entity(area_entity).with_children(|parent| {
+            parent
+                .spawn((
+                    Toast::new(notification.kind),
+                    Node {
+                        width: theme.notification.width,
+                        padding: theme.notification.padding,
+                        border: theme.notification.border,
+                        left: Val::Px(SLIDE_DISTANCE),
+                        ..Default::default()
+                    },
+                    theme.notification.background_color,
+                    border_color(notification.kind, &theme),
+                ))
+                .with_child((LabelKind::Normal, Text::new(notification.message)))
+                .observe(dismiss);
+        });
+    }
+}
+
+/// Slides toasts in and despawns them after the display duration.
+fn animate(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toasts: Query<(Entity, &mut Toast, &mut Node)>,
+) {
+    for (entity, mut toast, mut node) in &mut toasts {
+        toast.timer.tick(time.delta());
+        if toast.timer.finished() {
+            debug!("dismissing expired notification `{entity}`");
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let progress = (toast.timer.elapsed_secs() / SLIDE_DURATION).min(1.0);
+        let left = Val::Px(SLIDE_DISTANCE * (1.0 - progress).powi(2));
+        if node.left != left {
+            node.left = left;
+        }
+    }
+}
+
+fn dismiss(trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    debug!("dismissing notification `{}`", trigger.entity());
+    commands.entity(trigger.entity()).despawn_recursive();
+}
+
+fn restyle(
+    theme: Res<Theme>,
+    mut areas: Query<&mut Node, With<NotificationArea>>,
+    mut toasts: Query<
+        (&Toast, &mut Node, &mut BackgroundColor, &mut BorderColor),
+        Without<NotificationArea>,
+    >,
+) {
+    for mut node in &mut areas {
+        node.padding = theme.padding.global;
+        node.row_gap = theme.gap.normal;
+    }
+
+    for (toast, mut node, mut background, mut border) in &mut toasts {
+        node.width = theme.notification.width;
+        node.padding = theme.notification.padding;
+        node.border = theme.notification.border;
+        *background = theme.notification.background_color;
+        *border = border_color(toast.kind, &theme);
+    }
+}
+
+fn border_color(kind: NotificationKind, theme: &Theme) -> BorderColor {
+    match kind {
+        NotificationKind::Info => theme.notification.info_border,
+        NotificationKind::Warning => theme.notification.warning_border,
+        NotificationKind::Error => theme.notification.error_border,
+    }
+}
+
+/// Notifications that wait until there is space for them.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct NotificationQueue(VecDeque<Notification>);
+
+#[derive(Component)]
+struct NotificationArea;
+
+#[derive(Component)]
+struct Toast {
+    kind: NotificationKind,
+    timer: Timer,
+}
+
+impl Toast {
+    fn new(kind: NotificationKind) -> Self {
+        Self {
+            kind,
+            timer: Timer::new(DISPLAY_DURATION, TimerMode::Once),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+use bevy_replicon::prelude::*;
+use project_harmonia_base::game_world::{
+    auto_pause::AutoPaused,
+    time_speed::{TimeSpeed, TimeSpeedRequest},
+    WorldState,
+};
+use project_harmonia_widgets::{
+    button::ButtonKind, dialog::Dialog, label::LabelKind, theme::Theme,
+};
+
+pub(super) struct PauseDialogPlugin;
+
+impl Plugin for PauseDialogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(show);
+    }
+}
+
+fn show(
+    trigger: Trigger<AutoPaused>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+) {
+    info!("showing pause dialog");
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((Dialog, StateScoped(WorldState::Family)))
+            .with_children(|parent| {
+                let dialog_entity = parent.parent_entity();
+                parent
+                    .spawn((
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            padding: theme.padding.normal,
+                            row_gap: theme.gap.normal,
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((LabelKind::Large, Text::new("Game paused")));
+                        parent.spawn((LabelKind::Normal, Text::new(&**trigger)));
+                        parent
+                            .spawn((
+                                ButtonKind::Normal,
+                                ResumeButton {
+                                    dialog_entity,
+                                    time_speed: trigger.resume_speed,
+                                },
+                            ))
+                            .with_child(Text::new("Resume"))
+                            .observe(resume);
+                    });
+            });
+    });
+}
+
+fn resume(trigger: Trigger<Pointer<Click>>, mut commands: Commands, buttons: Query<&ResumeButton>) {
+    let button = buttons.get(trigger.entity()).unwrap();
+    info!("resuming game with `{:?}`", button.time_speed);
+    commands.client_trigger(TimeSpeedRequest(button.time_speed));
+    commands.entity(button.dialog_entity).despawn_recursive();
+}
+
+#[derive(Component)]
+struct ResumeButton {
+    dialog_entity: Entity,
+    /// Speed that was active before the pause.
+    time_speed: TimeSpeed,
+}
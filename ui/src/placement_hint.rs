@@ -0,0 +1,84 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use project_harmonia_base::game_world::object::placing_object::PlacingObjectState;
+use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+
+/// Displays near the cursor why the placing object can't be placed.
+pub(super) struct PlacementHintPlugin;
+
+impl Plugin for PlacementHintPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(spawn)
+            .add_observer(despawn)
+            .add_systems(Update, update.never_param_warn());
+    }
+}
+
+fn spawn(
+    trigger: Trigger<OnAdd, PlacingObjectState>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+) {
+    debug!("spawning placement hint for `{}`", trigger.entity());
+    commands.entity(*root_entity).with_children(|parent| {
+        parent.spawn((
+            PlacementHint(trigger.entity()),
+            LabelKind::Small,
+            Text::default(),
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                max_width: theme.tooltip.max_width,
+                padding: theme.tooltip.padding,
+                ..Default::default()
+            },
+            GlobalZIndex(i32::MAX),
+            PickingBehavior::IGNORE,
+            theme.tooltip.background_color,
+        ));
+    });
+}
+
+fn despawn(
+    trigger: Trigger<OnRemove, PlacingObjectState>,
+    mut commands: Commands,
+    hints: Query<(Entity, &PlacementHint)>,
+) {
+    if let Some((entity, _)) = hints.iter().find(|(_, hint)| hint.0 == trigger.entity()) {
+        debug!("despawning placement hint for `{}`", trigger.entity());
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn update(
+    theme: Res<Theme>,
+    ui_scale: Res<UiScale>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    placing_objects: Query<&PlacingObjectState>,
+    mut hints: Query<(&mut Node, &mut Text, &PlacementHint)>,
+) {
+    for (mut node, mut text, hint) in &mut hints {
+        let violation = placing_objects
+            .get(hint.0)
+            .ok()
+            .and_then(PlacingObjectState::violation);
+        let (Some(violation), Some(cursor_position)) = (violation, window.cursor_position()) else {
+            if node.display != Display::None {
+                node.display = Display::None;
+            }
+            continue;
+        };
+
+        if text.0 != violation.text() {
+            text.0 = violation.text().to_string();
+        }
+        let position = cursor_position / ui_scale.0 + theme.tooltip.cursor_offset;
+        node.display = Display::Flex;
+        node.left = Val::Px(position.x);
+        node.top = Val::Px(position.y);
+    }
+}
+
+/// Hint for the placing object entity.
+#[derive(Component)]
+struct PlacementHint(Entity);
@@ -1,30 +1,63 @@
-use std::f32::consts::PI;
+use std::{
+    collections::VecDeque,
+    f32::consts::PI,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
 
+use anyhow::{Context, Result};
 use bevy::{
-    asset::RecursiveDependencyLoadState,
+    asset::{AssetPath, RecursiveDependencyLoadState},
+    diagnostic::Diagnostics,
+    image::{CompressedImageFormats, ImageSampler, ImageType},
     pbr::wireframe::NoWireframe,
     prelude::*,
     render::{
         camera::RenderTarget,
-        render_resource::{Extent3d, TextureUsages},
+        gpu_readback::{Readback, ReadbackComplete},
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        renderer::RenderDevice,
         view::{NoFrustumCulling, RenderLayers},
     },
     scene,
+    tasks::{block_on, futures_lite::future, IoTaskPool, Task},
+    utils::{HashMap, Instant},
 };
 
-use project_harmonia_base::asset::manifest::object_manifest::ObjectManifest;
+use project_harmonia_base::{
+    asset::manifest::object_manifest::ObjectManifest,
+    game_paths::GamePaths,
+    game_world::actor::outfit::{ActiveOutfit, Outfits},
+    profiling,
+};
 
 pub(super) struct PreviewPlugin;
 
 impl Plugin for PreviewPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<PreviewState>()
+            .init_resource::<PreviewQueue>()
+            .init_resource::<PreviewCache>()
+            .add_observer(enqueue::<OnAdd, Preview>)
+            .add_observer(enqueue::<OnRemove, PreviewProcessed>)
             .add_systems(Startup, setup)
             .add_systems(
                 OnEnter(PreviewState::Inactive),
                 despawn_scene.never_param_warn(),
             )
             .add_systems(OnEnter(PreviewState::Rendering), render)
+            .add_systems(
+                Update,
+                (
+                    invalidate.run_if(
+                        on_event::<AssetEvent<ObjectManifest>>.or(on_event::<AssetEvent<Scene>>),
+                    ),
+                    invalidate_actors,
+                    finish_loading,
+                ),
+            )
             .add_systems(
                 SpawnScene,
                 (
@@ -48,47 +81,144 @@ fn setup(mut commands: Commands) {
     ));
 }
 
+/// Queues previews that were added or need to be regenerated.
+fn enqueue<E: Event, C: Component>(trigger: Trigger<E, C>, mut queue: ResMut<PreviewQueue>) {
+    if !queue.contains(&trigger.entity()) {
+        queue.push_back(trigger.entity());
+    }
+}
+
+/// Takes requests from the queue in order until one needs rendering.
+///
+/// Object previews are taken from the cache or loaded from disk when available.
 fn wait_for_request(
     mut commands: Commands,
+    mut queue: ResMut<PreviewQueue>,
+    mut cache: ResMut<PreviewCache>,
     asset_server: Res<AssetServer>,
+    game_paths: Res<GamePaths>,
     manifests: Res<Assets<ObjectManifest>>,
     camera_entity: Single<Entity, With<PreviewCamera>>,
-    previews: Query<(Entity, &Preview, Has<CalculatedClip>), Without<PreviewProcessed>>,
-    actors: Query<&SceneRoot>,
+    mut previews: Query<(&Preview, &mut ImageNode, Has<CalculatedClip>), Without<PreviewProcessed>>,
+    actors: Query<(&SceneRoot, Option<&Outfits>, Option<&ActiveOutfit>)>,
 ) {
-    // Check for `CalculatedClip` to make sure that the preview node is visible.
-    if let Some((preview_entity, &preview, ..)) = previews.iter().find(|&(.., c)| !c) {
-        let (translation, scene_root) = match preview {
+    let mut index = 0;
+    while let Some(&preview_entity) = queue.get(index) {
+        let Ok((&preview, mut image_node, clipped)) = previews.get_mut(preview_entity) else {
+            // Despawned or already processed.
+            queue.remove(index);
+            continue;
+        };
+
+        // Check for `CalculatedClip` to make sure that the preview node is visible.
+        if clipped {
+            index += 1;
+            continue;
+        }
+        queue.remove(index);
+
+        let mut outfit = None;
+        let (translation, scene_root, manifest_path) = match preview {
             Preview::Actor(entity) => {
                 debug!("generating preview for actor `{entity}`");
 
-                let scene_root = actors
+                let (scene_root, outfits, active_outfit) = actors
                     .get(entity)
                     .expect("actor for preview should have a scene handle");
+                if let (Some(outfits), Some(&active_outfit)) = (outfits, active_outfit) {
+                    outfit = Some((outfits.clone(), active_outfit));
+                }
 
-                (Vec3::new(0.0, -1.67, -0.42), scene_root.clone())
+                (Vec3::new(0.0, -1.67, -0.42), scene_root.clone(), None)
             }
             Preview::Object(id) => {
                 let manifest = manifests.get(id).expect("manifests should be preloaded");
+                let manifest_path = asset_server
+                    .get_path(id)
+                    .expect("manifests should be loaded from files")
+                    .into_owned();
+
+                if let Some(image_handle) = cache.get(&manifest_path) {
+                    debug!("using cached preview for '{manifest_path}'");
+                    image_node.image = image_handle;
+                    commands.entity(preview_entity).insert(PreviewProcessed);
+                    continue;
+                }
+
+                let path = game_paths.preview_path(&manifest_path);
+                if path.exists() {
+                    debug!("loading preview for '{manifest_path}' from {path:?}");
+                    let task = IoTaskPool::get().spawn(async move { load_image(&path) });
+                    commands.entity(preview_entity).insert((
+                        PreviewProcessed,
+                        PreviewLoading {
+                            manifest_path,
+                            task,
+                        },
+                    ));
+                    continue;
+                }
 
                 debug!("generating preview for object '{:?}'", manifest.scene);
 
                 let scene_handle = asset_server.load(manifest.scene.clone()).into();
 
-                (manifest.preview_translation, scene_handle)
+                (
+                    manifest.preview_translation,
+                    scene_handle,
+                    Some(manifest_path),
+                )
             }
         };
 
         commands.entity(preview_entity).insert(PreviewProcessed);
         commands.entity(*camera_entity).with_children(|parent| {
-            parent.spawn((
+            let mut scene = parent.spawn((
                 PreviewTarget(preview_entity),
+                PreviewStart(Instant::now()),
                 scene_root,
                 Transform::from_translation(translation).with_rotation(Quat::from_rotation_y(PI)), // Rotate towards camera.
             ));
+            if let Some(manifest_path) = manifest_path {
+                scene.insert(PreviewManifest(manifest_path));
+            }
+            if let Some(outfit) = outfit {
+                // Clothing pieces will be spawned and bound to the skeleton like for actors.
+                scene.insert(outfit);
+            }
         });
 
         commands.set_state(PreviewState::LoadingAsset);
+        return;
+    }
+}
+
+/// Applies previews loaded from disk.
+fn finish_loading(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut cache: ResMut<PreviewCache>,
+    mut previews: Query<(Entity, &mut PreviewLoading, &mut ImageNode)>,
+) {
+    for (preview_entity, mut loading, mut image_node) in &mut previews {
+        let Some(result) = block_on(future::poll_once(&mut loading.task)) else {
+            continue;
+        };
+
+        commands.entity(preview_entity).remove::<PreviewLoading>();
+        match result {
+            Ok(image) => {
+                debug!("loaded preview for '{}'", loading.manifest_path);
+                let image_handle = images.add(image);
+                cache.insert(loading.manifest_path.clone(), image_handle.clone());
+                image_node.image = image_handle;
+            }
+            Err(e) => {
+                error!("unable to load preview: {e:#}");
+                // The broken file is removed, so the preview will be rendered instead.
+                commands.entity(preview_entity).remove::<PreviewProcessed>();
+            }
+        }
     }
 }
 
@@ -101,10 +231,25 @@ fn wait_for_loading(
     targets: Query<&Node>,
     children: Query<&Children>,
     meshes: Query<Entity, With<Mesh3d>>,
+    pieces: Query<&SceneRoot, Without<PreviewTarget>>,
 ) {
     let (scene_entity, preview_target, scene_handle) = *preview_scene;
     match asset_server.recursive_dependency_load_state(&**scene_handle) {
         RecursiveDependencyLoadState::Loaded => {
+            // Wait for clothing pieces too.
+            let pieces_loaded = pieces
+                .iter_many(children.iter_descendants(scene_entity))
+                .all(|piece_handle| {
+                    !matches!(
+                        asset_server.recursive_dependency_load_state(&**piece_handle),
+                        RecursiveDependencyLoadState::NotLoaded
+                            | RecursiveDependencyLoadState::Loading
+                    )
+                });
+            if !pieces_loaded {
+                return;
+            }
+
             debug!("asset for preview was successfully loaded");
 
             let Ok(node) = targets.get(preview_target.0) else {
@@ -118,7 +263,9 @@ fn wait_for_loading(
             };
 
             let mut image = Image::default();
-            image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+            // Copying is needed to read back object previews for storing on disk.
+            image.texture_descriptor.usage |=
+                TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC;
             image.resize(Extent3d {
                 width: width as u32,
                 height: height as u32,
@@ -149,36 +296,229 @@ fn wait_for_loading(
     }
 }
 
+/// Requests new previews for objects whose manifests or scenes were modified.
+///
+/// Also removes their cached images and thumbnails.
+fn invalidate(
+    mut commands: Commands,
+    mut manifest_events: EventReader<AssetEvent<ObjectManifest>>,
+    mut scene_events: EventReader<AssetEvent<Scene>>,
+    mut cache: ResMut<PreviewCache>,
+    asset_server: Res<AssetServer>,
+    game_paths: Res<GamePaths>,
+    manifests: Res<Assets<ObjectManifest>>,
+    previews: Query<(Entity, &Preview), With<PreviewProcessed>>,
+) {
+    let mut modified: Vec<_> = manifest_events
+        .read()
+        .filter_map(|event| match *event {
+            AssetEvent::Modified { id } => Some(id),
+            _ => None,
+        })
+        .collect();
+
+    for event in scene_events.read() {
+        let AssetEvent::Modified { id } = *event else {
+            continue;
+        };
+        let Some(scene_path) = asset_server.get_path(id) else {
+            continue;
+        };
+        modified.extend(
+            manifests
+                .iter()
+                .filter(|(_, manifest)| manifest.scene == scene_path)
+                .map(|(id, _)| id),
+        );
+    }
+
+    for &id in &modified {
+        let Some(manifest_path) = asset_server.get_path(id).map(AssetPath::into_owned) else {
+            continue;
+        };
+        cache.remove(&manifest_path);
+
+        let path = game_paths.preview_path(&manifest_path);
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != ErrorKind::NotFound {
+                error!("unable to remove {path:?}: {e}");
+            }
+        }
+    }
+
+    for (preview_entity, preview) in &previews {
+        if let Preview::Object(id) = *preview {
+            if modified.contains(&id) {
+                debug!("regenerating preview for `{preview_entity}`");
+                commands
+                    .entity(preview_entity)
+                    .remove::<(PreviewProcessed, PreviewLoading)>();
+            }
+        }
+    }
+}
+
+/// Requests new previews for actors whose appearance changed.
+fn invalidate_actors(
+    mut commands: Commands,
+    changed_actors: Query<(), Or<(Changed<SceneRoot>, Changed<Outfits>, Changed<ActiveOutfit>)>>,
+    previews: Query<(Entity, &Preview), With<PreviewProcessed>>,
+) {
+    for (preview_entity, preview) in &previews {
+        if let Preview::Actor(actor_entity) = *preview {
+            if changed_actors.contains(actor_entity) {
+                debug!("regenerating preview for `{preview_entity}`");
+                commands.entity(preview_entity).remove::<PreviewProcessed>();
+            }
+        }
+    }
+}
+
 /// Waits one frame for components like [`NoWireframe`] to take effect.
 fn render(mut commands: Commands) {
     debug!("finishing rendering");
     commands.set_state(PreviewState::Inactive);
 }
 
+/// Assigns the rendered image and caches object previews.
+///
+/// Object previews are also read back from GPU to store them on disk.
 fn despawn_scene(
     mut commands: Commands,
+    mut diagnostics: Diagnostics,
+    mut cache: ResMut<PreviewCache>,
+    images: Res<Assets<Image>>,
+    game_paths: Res<GamePaths>,
     mut preview_camera: Single<&mut Camera, With<PreviewCamera>>,
-    preview_scene: Single<(Entity, &PreviewTarget)>,
+    preview_scene: Single<(
+        Entity,
+        &PreviewTarget,
+        &PreviewStart,
+        Option<&PreviewManifest>,
+    )>,
     mut targets: Query<&mut ImageNode>,
 ) {
     preview_camera.is_active = false;
 
-    let (entity, preview_target) = *preview_scene;
+    let (entity, preview_target, preview_start, manifest) = *preview_scene;
+    let RenderTarget::Image(image_handle) = &preview_camera.target else {
+        panic!("preview camera should render only to images");
+    };
+
     if let Ok(mut target_handle) = targets.get_mut(**preview_target) {
-        let RenderTarget::Image(image_handle) = &preview_camera.target else {
-            panic!("preview camera should render only to images");
-        };
         target_handle.image = image_handle.clone();
         debug!("preview is ready");
+        profiling::measure(
+            &mut diagnostics,
+            &profiling::PREVIEW_RENDERING,
+            **preview_start,
+        );
     } else {
         info!("preview target is no longer valid");
     }
 
+    if let Some(manifest) = manifest {
+        cache.insert(manifest.0.clone(), image_handle.clone());
+
+        let image = images
+            .get(image_handle)
+            .expect("rendered preview should be stored");
+        let path = game_paths.preview_path(&manifest.0);
+        save_to_disk(&mut commands, image_handle.clone(), image.size(), path);
+    }
+
     commands.entity(entity).despawn_recursive();
 }
 
+/// Reads back a rendered preview from GPU and writes it as PNG.
+///
+/// The image should be a render target from [`Preview`] processing.
+pub(crate) fn save_to_disk(
+    commands: &mut Commands,
+    image_handle: Handle<Image>,
+    size: UVec2,
+    path: PathBuf,
+) {
+    commands
+        .spawn(Readback::texture(image_handle))
+        .observe(write_readback(path, size));
+}
+
+/// Returns an observer that writes the read back preview as PNG and despawns the readback entity.
+fn write_readback(path: PathBuf, size: UVec2) -> impl FnMut(Trigger<ReadbackComplete>, Commands) {
+    move |trigger, mut commands| {
+        commands.entity(trigger.entity()).despawn();
+
+        let path = path.clone();
+        let mut data = trigger.event().0.clone();
+        let row_bytes = size.x as usize * 4;
+        let padded_row_bytes = RenderDevice::align_copy_bytes_per_row(row_bytes);
+        if padded_row_bytes != row_bytes && data.len() == padded_row_bytes * size.y as usize {
+            data = data
+                .chunks(padded_row_bytes)
+                .flat_map(|row| &row[..row_bytes])
+                .copied()
+                .collect();
+        }
+
+        IoTaskPool::get()
+            .spawn(async move {
+                let _span = info_span!("preview_saving").entered();
+                debug!("saving preview to {path:?}");
+                if let Err(e) = save_image(&path, size, data) {
+                    error!("unable to save preview: {e:#}");
+                }
+            })
+            .detach();
+    }
+}
+
+fn save_image(path: &Path, size: UVec2, data: Vec<u8>) -> Result<()> {
+    let image = Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let dynamic_image = image
+        .try_into_dynamic()
+        .context("unable to convert preview")?;
+    dynamic_image
+        .save(path)
+        .with_context(|| format!("unable to write {path:?}"))?;
+
+    Ok(())
+}
+
+/// Reads a thumbnail from disk and removes it if it's broken.
+pub(crate) fn load_image(path: &Path) -> Result<Image> {
+    let buffer = fs::read(path).with_context(|| format!("unable to read {path:?}"))?;
+    Image::from_buffer(
+        &buffer,
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        RenderAssetUsages::default(),
+    )
+    .with_context(|| format!("unable to decode {path:?}"))
+    .inspect_err(|_| {
+        if let Err(e) = fs::remove_file(path) {
+            error!("unable to remove {path:?}: {e}");
+        }
+    })
+}
+
 const PREVIEW_RENDER_LAYER: RenderLayers = RenderLayers::layer(1);
 
+/// Maximum number of object previews kept in [`PreviewCache`].
+const CACHE_CAPACITY: usize = 256;
+
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, States)]
 enum PreviewState {
     #[default]
@@ -205,7 +545,8 @@ struct PreviewCamera;
 /// Specifies preview that should be generated for specific actor in the world or for an object by its manifest.
 ///
 /// Generated image handle will be written to the image handle on this entity.
-/// Preview generation happens only if UI element entity is visible.
+/// Requests are processed in order, but only for visible UI elements.
+/// Object previews are cached in memory and on disk.
 /// Processed entities will be marked with [`PreviewProcessed`].
 #[derive(Clone, Component, Copy)]
 #[require(ImageNode)]
@@ -214,10 +555,73 @@ pub(crate) enum Preview {
     Object(AssetId<ObjectManifest>),
 }
 
+/// Entities with [`Preview`] that are waiting for processing, in request order.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct PreviewQueue(VecDeque<Entity>);
+
+/// Rendered object previews keyed by manifest paths.
+///
+/// Evicts the least recently used image when the capacity is exceeded.
+#[derive(Resource, Default)]
+struct PreviewCache {
+    images: HashMap<AssetPath<'static>, Handle<Image>>,
+    /// Paths from the least to the most recently used.
+    order: VecDeque<AssetPath<'static>>,
+}
+
+impl PreviewCache {
+    fn get(&mut self, path: &AssetPath<'static>) -> Option<Handle<Image>> {
+        let handle = self.images.get(path)?.clone();
+        self.touch(path);
+        Some(handle)
+    }
+
+    fn insert(&mut self, path: AssetPath<'static>, handle: Handle<Image>) {
+        if self.images.insert(path.clone(), handle).is_some() {
+            self.touch(&path);
+            return;
+        }
+
+        self.order.push_back(path);
+        if self.order.len() > CACHE_CAPACITY {
+            if let Some(evicted) = self.order.pop_front() {
+                debug!("evicting preview for '{evicted}'");
+                self.images.remove(&evicted);
+            }
+        }
+    }
+
+    fn remove(&mut self, path: &AssetPath<'static>) {
+        if self.images.remove(path).is_some() {
+            self.order.retain(|cached| cached != path);
+        }
+    }
+
+    fn touch(&mut self, path: &AssetPath<'static>) {
+        if let Some(index) = self.order.iter().position(|cached| cached == path) {
+            let path = self.order.remove(index).unwrap();
+            self.order.push_back(path);
+        }
+    }
+}
+
 /// Marks entity with [`Preview`] as processed end excludes it from preview generation.
 #[derive(Component)]
 pub(super) struct PreviewProcessed;
 
+/// Thumbnail that is being loaded from disk for a [`Preview`].
+#[derive(Component)]
+struct PreviewLoading {
+    manifest_path: AssetPath<'static>,
+    task: Task<Result<Image>>,
+}
+
+/// Manifest path of the object on the preview scene.
+///
+/// Present only for object previews, which are cached.
+#[derive(Component)]
+struct PreviewManifest(AssetPath<'static>);
+
 /// Points to the entity for which the preview will be generated.
 #[derive(Component, Deref, Clone, Copy)]
 #[require(
@@ -225,3 +629,7 @@ pub(super) struct PreviewProcessed;
     SceneRoot,
 )]
 struct PreviewTarget(Entity);
+
+/// Time when the preview was requested.
+#[derive(Component, Deref)]
+struct PreviewStart(Instant);
@@ -0,0 +1,158 @@
+use bevy::{
+    diagnostic::{DiagnosticPath, DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+
+use project_harmonia_base::{game_world::navigation::nav_tiles, profiling, settings::Settings};
+use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+
+/// Displays timings of heavy systems and a frame time histogram.
+///
+/// Toggled from developer settings.
+pub(super) struct ProfilerPlugin;
+
+impl Plugin for ProfilerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                toggle.never_param_warn(),
+                (update_texts, update_histogram).run_if(any_with_component::<Profiler>),
+            )
+                .chain(),
+        );
+    }
+}
+
+const TIMINGS: [(DiagnosticPath, &str); 5] = [
+    (FrameTimeDiagnosticsPlugin::FRAME_TIME, "Frame"),
+    (profiling::WALL_MESHING, "Wall meshing"),
+    (profiling::PREVIEW_RENDERING, "Preview rendering"),
+    (nav_tiles::BUILD_TIME, "Navmesh build"),
+    (profiling::REPLICATION_SEND, "Replication send"),
+];
+
+/// Upper bounds of histogram buckets in milliseconds.
+///
+/// The last bucket collects everything above.
+const BUCKETS: [f64; 5] = [8.3, 16.7, 33.3, 66.7, f64::INFINITY];
+
+const HISTOGRAM_WIDTH: f32 = 180.0;
+const BAR_HEIGHT: f32 = 10.0;
+
+fn toggle(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    settings: Res<Settings>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    profiler_entity: Option<Single<Entity, With<Profiler>>>,
+) {
+    match (settings.developer.profiler, profiler_entity) {
+        (true, None) => {
+            debug!("showing profiler");
+            commands.entity(*root_entity).with_children(|parent| {
+                parent
+                    .spawn((
+                        Profiler,
+                        Node {
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(0.0),
+                            left: Val::Px(0.0),
+                            flex_direction: FlexDirection::Column,
+                            padding: theme.padding.normal,
+                            row_gap: theme.gap.normal,
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        for (index, (_, name)) in TIMINGS.iter().enumerate() {
+                            parent.spawn((LabelKind::Small, TimingText(index), Text::new(*name)));
+                        }
+                        let mut lower_bound = 0.0;
+                        for (index, &upper_bound) in BUCKETS.iter().enumerate() {
+                            let label = if upper_bound.is_finite() {
+                                format!("{lower_bound:.0}-{upper_bound:.0} ms")
+                            } else {
+                                format!("> {lower_bound:.0} ms")
+                            };
+                            lower_bound = upper_bound;
+
+                            parent
+                                .spawn(Node {
+                                    align_items: AlignItems::Center,
+                                    column_gap: theme.gap.normal,
+                                    ..Default::default()
+                                })
+                                .with_children(|parent| {
+                                    parent.spawn((LabelKind::Small, Text::new(label)));
+                                    parent
+                                        .spawn((
+                                            Node {
+                                                width: Val::Px(HISTOGRAM_WIDTH),
+                                                height: Val::Px(BAR_HEIGHT),
+                                                ..Default::default()
+                                            },
+                                            theme.background_color,
+                                        ))
+                                        .with_child((
+                                            HistogramBar(index),
+                                            Node {
+                                                width: Val::Percent(0.0),
+                                                ..Default::default()
+                                            },
+                                            BackgroundColor(theme.label.normal.color.0),
+                                        ));
+                                });
+                        }
+                    });
+            });
+        }
+        (false, Some(profiler_entity)) => {
+            debug!("hiding profiler");
+            commands.entity(*profiler_entity).despawn_recursive();
+        }
+        _ => (),
+    }
+}
+
+fn update_texts(store: Res<DiagnosticsStore>, mut texts: Query<(&mut Text, &TimingText)>) {
+    for (mut text, timing_text) in &mut texts {
+        let (path, name) = &TIMINGS[**timing_text];
+        if let Some(value) = store.get(path).and_then(|diagnostic| diagnostic.smoothed()) {
+            text.0 = format!("{name}: {value:.2} ms");
+        }
+    }
+}
+
+/// Distributes frame times from the diagnostic history into buckets.
+fn update_histogram(store: Res<DiagnosticsStore>, mut bars: Query<(&mut Node, &HistogramBar)>) {
+    let Some(diagnostic) = store.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) else {
+        return;
+    };
+
+    let mut counts = [0; BUCKETS.len()];
+    for &value in diagnostic.values() {
+        let index = BUCKETS
+            .iter()
+            .position(|&upper_bound| value <= upper_bound)
+            .unwrap_or(BUCKETS.len() - 1);
+        counts[index] += 1;
+    }
+
+    let total = counts.iter().sum::<u32>().max(1);
+    for (mut node, bar) in &mut bars {
+        node.width = Val::Percent(counts[**bar] as f32 / total as f32 * 100.0);
+    }
+}
+
+#[derive(Component)]
+struct Profiler;
+
+/// Index of the displayed timing from [`TIMINGS`].
+#[derive(Component, Deref)]
+struct TimingText(usize);
+
+/// Index of the bucket from [`BUCKETS`].
+#[derive(Component, Deref)]
+struct HistogramBar(usize);
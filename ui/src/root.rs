@@ -1,10 +1,13 @@
 use bevy::prelude::*;
+use project_harmonia_base::game_world::photo_mode::PhotoMode;
 
 pub(super) struct RootPlugin;
 
 impl Plugin for RootPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn);
+        app.add_observer(hide)
+            .add_observer(show)
+            .add_systems(Startup, spawn);
     }
 }
 
@@ -21,3 +24,19 @@ fn spawn(mut commands: Commands) {
         },
     ));
 }
+
+fn hide(
+    _trigger: Trigger<OnAdd, PhotoMode>,
+    mut root_visibility: Single<&mut Visibility, (With<Node>, Without<Parent>)>,
+) {
+    debug!("hiding UI for photo mode");
+    **root_visibility = Visibility::Hidden;
+}
+
+fn show(
+    _trigger: Trigger<OnRemove, PhotoMode>,
+    mut root_visibility: Single<&mut Visibility, (With<Node>, Without<Parent>)>,
+) {
+    debug!("showing UI after photo mode");
+    **root_visibility = Visibility::Inherited;
+}
@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use project_harmonia_base::{
+    game_world::{cursor_mode::CursorMode, WorldState},
+    settings::Settings,
+};
+use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+
+/// Lists key bindings available in the current context while F1 is held.
+pub(super) struct ShortcutsOverlayPlugin;
+
+impl Plugin for ShortcutsOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_input_context::<ShortcutsOverlay>()
+            .add_systems(Startup, setup)
+            .add_observer(show)
+            .add_observer(hide);
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(ShortcutsOverlay);
+}
+
+fn show(
+    _trigger: Trigger<Started<ShowShortcuts>>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    settings: Res<Settings>,
+    cursor_mode: Res<CursorMode>,
+    world_state: Option<Res<State<WorldState>>>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+) {
+    debug!("showing shortcuts");
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                ShortcutsNode,
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                PickingBehavior::IGNORE,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn((
+                        Node {
+                            display: Display::Grid,
+                            column_gap: theme.gap.normal,
+                            row_gap: theme.gap.normal,
+                            padding: theme.padding.normal,
+                            grid_template_columns: vec![GridTrack::auto(); 2],
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            LabelKind::Large,
+                            Text::new("General"),
+                            Node {
+                                grid_column: GridPlacement::span(2),
+                                ..Default::default()
+                            },
+                        ));
+                        parent.spawn((LabelKind::Normal, Text::new("Command palette")));
+                        parent.spawn((LabelKind::Normal, Text::new("Ctrl + P")));
+                        parent.spawn((LabelKind::Normal, Text::new("Shortcuts")));
+                        parent.spawn((LabelKind::Normal, Text::new("F1 (hold)")));
+
+                        let keyboard = &settings.keyboard;
+                        let in_world = world_state.is_some();
+                        let in_family =
+                            world_state.is_some_and(|state| **state == WorldState::Family);
+                        let placing = *cursor_mode == CursorMode::Placing;
+                        let sections: [(_, _, &[(_, &[Input])]); 3] = [
+                            (
+                                "Camera",
+                                in_world,
+                                &[
+                                    ("Forward", &keyboard.camera_forward),
+                                    ("Left", &keyboard.camera_left),
+                                    ("Backward", &keyboard.camera_backward),
+                                    ("Right", &keyboard.camera_right),
+                                    ("Rotate left", &keyboard.rotate_left),
+                                    ("Rotate right", &keyboard.rotate_right),
+                                    ("Zoom in", &keyboard.zoom_in),
+                                    ("Zoom out", &keyboard.zoom_out),
+                                    ("Follow actor", &keyboard.follow_actor),
+                                    ("Photo mode", &keyboard.photo_mode),
+                                ],
+                            ),
+                            (
+                                "Time",
+                                in_family,
+                                &[
+                                    ("Pause", &keyboard.pause),
+                                    ("Normal speed", &keyboard.normal_speed),
+                                    ("Fast speed", &keyboard.fast_speed),
+                                    ("Faster speed", &keyboard.faster_speed),
+                                    ("Ultra speed", &keyboard.ultra_speed),
+                                ],
+                            ),
+                            (
+                                "Placing",
+                                placing,
+                                &[
+                                    ("Delete", &keyboard.delete),
+                                    ("Free placement", &keyboard.free_placement),
+                                    ("Ordinal placement", &keyboard.ordinal_placement),
+                                ],
+                            ),
+                        ];
+
+                        for (title, active, bindings) in sections {
+                            if !active {
+                                continue;
+                            }
+
+                            parent.spawn((
+                                LabelKind::Large,
+                                Text::new(title),
+                                Node {
+                                    grid_column: GridPlacement::span(2),
+                                    ..Default::default()
+                                },
+                            ));
+                            for &(name, inputs) in bindings {
+                                let text = if inputs.is_empty() {
+                                    "Unassigned".to_string()
+                                } else {
+                                    inputs
+                                        .iter()
+                                        .map(ToString::to_string)
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                };
+                                parent.spawn((LabelKind::Normal, Text::new(name)));
+                                parent.spawn((LabelKind::Normal, Text::new(text)));
+                            }
+                        }
+                    });
+            });
+    });
+}
+
+fn hide(
+    _trigger: Trigger<Completed<ShowShortcuts>>,
+    mut commands: Commands,
+    shortcuts_entity: Option<Single<Entity, With<ShortcutsNode>>>,
+) {
+    if let Some(shortcuts_entity) = shortcuts_entity {
+        debug!("hiding shortcuts");
+        commands.entity(*shortcuts_entity).despawn_recursive();
+    }
+}
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Shortcuts overlay")))]
+struct ShortcutsOverlay;
+
+impl InputContext for ShortcutsOverlay {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<ShowShortcuts>().to(KeyCode::F1);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct ShowShortcuts;
+
+#[derive(Component)]
+struct ShortcutsNode;
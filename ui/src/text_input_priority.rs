@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use bevy_simple_text_input::TextInputInactive;
+use project_harmonia_widgets::text_edit::TextEdit;
+
+/// Prevents keyboard actions from being triggered while typing.
+///
+/// Actions are already separated by contexts on mode-specific entities,
+/// but text inputs aren't actions, so typing a name could pan the camera or sell an object.
+pub(super) struct TextInputPriorityPlugin;
+
+impl Plugin for TextInputPriorityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, update_sources.before(EnhancedInputSystem));
+    }
+}
+
+fn update_sources(
+    mut action_sources: ResMut<ActionSources>,
+    text_edits: Query<(&TextInputInactive, &InheritedVisibility), With<TextEdit>>,
+) {
+    let typing = text_edits
+        .iter()
+        .any(|(inactive, visibility)| !inactive.0 && visibility.get());
+    if action_sources.keyboard == typing {
+        debug!("setting keyboard actions to `{}`", !typing);
+        action_sources.keyboard = !typing;
+    }
+}
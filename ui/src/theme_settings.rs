@@ -0,0 +1,115 @@
+use bevy::{asset::LoadedFolder, prelude::*};
+
+use project_harmonia_base::settings::{Settings, SettingsApply};
+use project_harmonia_widgets::theme::Theme;
+
+/// Replaces [`Theme`] with the one selected in accessibility settings and scales its text.
+///
+/// Themes are loaded from [`THEMES_DIR`] and re-applied when their files change.
+pub(super) struct ThemeSettingsPlugin;
+
+impl Plugin for ThemeSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(apply)
+            .add_systems(Startup, load)
+            .add_systems(PostStartup, init)
+            .add_systems(Update, replace);
+    }
+}
+
+const THEMES_DIR: &str = "base/themes";
+const THEME_EXTENSION: &str = ".theme.ron";
+
+fn load(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ThemeFolder(asset_server.load_folder(THEMES_DIR)));
+}
+
+fn init(
+    mut commands: Commands,
+    mut theme: ResMut<Theme>,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+) {
+    let accessibility = &settings.accessibility;
+    debug!("selecting theme '{}'", accessibility.theme);
+    commands.insert_resource(SelectedTheme(
+        asset_server.load(theme_path(&accessibility.theme)),
+    ));
+    theme.set_text_scale(accessibility.text_size.scale());
+}
+
+fn apply(
+    _trigger: Trigger<SettingsApply>,
+    mut theme: ResMut<Theme>,
+    mut selected_theme: ResMut<SelectedTheme>,
+    asset_server: Res<AssetServer>,
+    themes: Res<Assets<Theme>>,
+    settings: Res<Settings>,
+) {
+    let accessibility = &settings.accessibility;
+    let handle = asset_server.load(theme_path(&accessibility.theme));
+    if handle != **selected_theme {
+        debug!("selecting theme '{}'", accessibility.theme);
+        **selected_theme = handle;
+        // Already loaded themes won't emit load events.
+        if let Some(new_theme) = themes.get(&**selected_theme) {
+            *theme = new_theme.clone();
+        }
+    }
+
+    debug!("applying {:?} text size", accessibility.text_size);
+    theme.set_text_scale(accessibility.text_size.scale());
+}
+
+fn replace(
+    mut asset_events: EventReader<AssetEvent<Theme>>,
+    mut theme: ResMut<Theme>,
+    themes: Res<Assets<Theme>>,
+    settings: Res<Settings>,
+    selected_theme: Res<SelectedTheme>,
+) {
+    for &event in asset_events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event {
+            if id != selected_theme.id() {
+                continue;
+            }
+
+            debug!("replacing theme with '{}'", settings.accessibility.theme);
+            *theme = themes
+                .get(id)
+                .expect("loaded theme should be available")
+                .clone();
+            theme.set_text_scale(settings.accessibility.text_size.scale());
+        }
+    }
+}
+
+fn theme_path(name: &str) -> String {
+    format!("{THEMES_DIR}/{name}{THEME_EXTENSION}")
+}
+
+/// Keeps all available themes loaded.
+#[derive(Resource)]
+pub(crate) struct ThemeFolder(Handle<LoadedFolder>);
+
+impl ThemeFolder {
+    /// Returns names of loaded themes without the extension.
+    pub(crate) fn names(&self, folders: &Assets<LoadedFolder>) -> Vec<String> {
+        let Some(folder) = folders.get(&self.0) else {
+            return Default::default();
+        };
+
+        folder
+            .handles
+            .iter()
+            .filter_map(|handle| handle.path())
+            .filter_map(|path| path.path().file_name())
+            .filter_map(|file_name| file_name.to_str())
+            .filter_map(|file_name| file_name.strip_suffix(THEME_EXTENSION))
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+#[derive(Resource, Deref, DerefMut)]
+struct SelectedTheme(Handle<Theme>);
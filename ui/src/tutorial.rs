@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+
+use project_harmonia_base::{
+    core::GameState,
+    settings::Settings,
+    tutorial::{TutorialSkip, TutorialStep},
+};
+use project_harmonia_widgets::{button::ButtonKind, label::LabelKind, theme::Theme};
+use strum::IntoEnumIterator;
+
+/// Displays the current tutorial step and points to its [`TutorialTarget`].
+pub(super) struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::InGame), sync_panel)
+            .add_systems(
+                Update,
+                (
+                    sync_panel.run_if(resource_changed::<Settings>),
+                    move_arrow.never_param_warn(),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// Vertical amplitude of the arrow bobbing in logical pixels.
+const ARROW_BOB: f32 = 6.0;
+
+/// Spawns, updates or despawns the panel according to the current step.
+fn sync_panel(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    settings: Res<Settings>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    panel_entity: Option<Single<Entity, With<TutorialPanel>>>,
+    arrow_entity: Option<Single<Entity, With<TutorialArrow>>>,
+    mut title: Option<Single<&mut Text, With<TutorialTitle>>>,
+    mut description: Option<Single<&mut Text, (With<TutorialDescription>, Without<TutorialTitle>)>>,
+) {
+    let Some(step) = settings.gameplay.tutorial_step else {
+        if let Some(panel_entity) = panel_entity {
+            debug!("hiding tutorial");
+            commands.entity(*panel_entity).despawn_recursive();
+        }
+        if let Some(arrow_entity) = arrow_entity {
+            commands.entity(*arrow_entity).despawn_recursive();
+        }
+        return;
+    };
+
+    if let (Some(title), Some(description)) = (&mut title, &mut description) {
+        title.0 = step_title(step);
+        description.0 = step.text().to_string();
+        return;
+    }
+
+    debug!("showing tutorial");
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                TutorialPanel,
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(30.0),
+                    width: Val::Px(300.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: theme.padding.normal,
+                    row_gap: theme.gap.normal,
+                    ..Default::default()
+                },
+                theme.panel_background,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    TutorialTitle,
+                    LabelKind::Normal,
+                    Text::new(step_title(step)),
+                ));
+                parent.spawn((
+                    TutorialDescription,
+                    LabelKind::Small,
+                    Text::new(step.text()),
+                ));
+                parent
+                    .spawn((
+                        ButtonKind::Normal,
+                        Node {
+                            align_self: AlignSelf::FlexEnd,
+                            ..Default::default()
+                        },
+                    ))
+                    .with_child(Text::new("Skip"))
+                    .observe(skip);
+            });
+
+        parent.spawn((
+            TutorialArrow,
+            LabelKind::Symbol,
+            Text::new("⬇"),
+            Visibility::Hidden,
+        ));
+    });
+}
+
+fn step_title(step: TutorialStep) -> String {
+    format!(
+        "Tutorial {}/{}",
+        step.number(),
+        TutorialStep::iter().count()
+    )
+}
+
+fn skip(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(TutorialSkip);
+}
+
+/// Places the arrow above the visible target of the current step.
+fn move_arrow(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    arrow: Single<(&mut Node, &mut Visibility, &ComputedNode), With<TutorialArrow>>,
+    targets: Query<(
+        &TutorialTarget,
+        &ComputedNode,
+        &GlobalTransform,
+        &InheritedVisibility,
+    )>,
+) {
+    let (mut arrow_node, mut arrow_visibility, arrow_computed) = arrow.into_inner();
+    let target = targets
+        .iter()
+        .find(|(target, computed_node, _, visibility)| {
+            Some(target.0) == settings.gameplay.tutorial_step
+                && visibility.get()
+                && computed_node.size() != Vec2::ZERO
+        });
+
+    let Some((_, computed_node, transform, _)) = target else {
+        arrow_visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    // UI transforms and sizes are in physical pixels.
+    let scale = computed_node.inverse_scale_factor();
+    let center = transform.translation().truncate() * scale;
+    let half_height = computed_node.size().y / 2.0 * scale;
+    let arrow_size = arrow_computed.size() * arrow_computed.inverse_scale_factor();
+    let bob = time.elapsed_secs().sin() * ARROW_BOB;
+
+    arrow_node.position_type = PositionType::Absolute;
+    arrow_node.left = Val::Px(center.x - arrow_size.x / 2.0);
+    arrow_node.top = Val::Px(center.y - half_height - arrow_size.y - ARROW_BOB + bob);
+    arrow_visibility.set_if_neq(Visibility::Inherited);
+}
+
+/// Widget that the arrow points to while the step is active.
+///
+/// If multiple targets exist for a step, the first visible one is used.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct TutorialTarget(pub(crate) TutorialStep);
+
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Tutorial panel")),
+    StateScoped::<GameState>(|| StateScoped(GameState::InGame)),
+)]
+struct TutorialPanel;
+
+#[derive(Component)]
+struct TutorialTitle;
+
+#[derive(Component)]
+struct TutorialDescription;
+
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Tutorial arrow")),
+    Node,
+    GlobalZIndex(|| GlobalZIndex(i32::MAX - 2)),
+    PickingBehavior(|| PickingBehavior::IGNORE),
+    StateScoped::<GameState>(|| StateScoped(GameState::InGame)),
+)]
+struct TutorialArrow;
@@ -10,9 +10,12 @@ impl Plugin for ButtonPlugin {
             .add_observer(theme_image)
             .add_observer(theme_text)
             .add_observer(toggle)
+            .add_observer(disable)
+            .add_observer(enable)
             .add_systems(
                 PostUpdate,
                 (
+                    restyle.run_if(resource_changed::<Theme>),
                     update_background,
                     (ensure_single_toggle, switch_tabs).chain(),
                 ),
@@ -25,28 +28,22 @@ fn theme(
     theme: Res<Theme>,
     mut buttons: Query<(&ButtonKind, &mut Node)>,
 ) {
-    let (button_kind, mut node) = buttons.get_mut(trigger.entity()).unwrap();
+    let (&button_kind, mut node) = buttons.get_mut(trigger.entity()).unwrap();
 
     node.justify_content = JustifyContent::Center;
     node.align_items = AlignItems::Center;
-    match button_kind {
-        ButtonKind::Normal => {
-            node.width = theme.button.normal.width;
-            node.height = theme.button.normal.height;
-        }
-        ButtonKind::Large => {
-            node.width = theme.button.large.width;
-            node.height = theme.button.large.height;
-        }
-        ButtonKind::Symbol => {
-            node.width = theme.button.symbol.width;
-            node.height = theme.button.symbol.height;
-        }
-        ButtonKind::Image => {
-            node.width = theme.button.image.width;
-            node.height = theme.button.image.height;
-        }
-    }
+    set_size(&mut node, &theme, button_kind);
+}
+
+fn set_size(node: &mut Node, theme: &Theme, button_kind: ButtonKind) {
+    let (width, height) = match button_kind {
+        ButtonKind::Normal => (theme.button.normal.width, theme.button.normal.height),
+        ButtonKind::Large => (theme.button.large.width, theme.button.large.height),
+        ButtonKind::Symbol => (theme.button.symbol.width, theme.button.symbol.height),
+        ButtonKind::Image => (theme.button.image.width, theme.button.image.height),
+    };
+    node.width = width;
+    node.height = height;
 }
 
 // TODO 0.16: Access hierarchy in the main theme trigger.
@@ -81,10 +78,19 @@ fn theme_text(
         return;
     };
 
-    let Ok(button_kind) = buttons.get(**parent) else {
+    let Ok(&button_kind) = buttons.get(**parent) else {
         return;
     };
 
+    set_text_style(&mut font, &mut color, &theme, button_kind);
+}
+
+fn set_text_style(
+    font: &mut TextFont,
+    color: &mut TextColor,
+    theme: &Theme,
+    button_kind: ButtonKind,
+) {
     let button_theme = match button_kind {
         ButtonKind::Normal => &theme.button.normal,
         ButtonKind::Large => &theme.button.large,
@@ -97,6 +103,48 @@ fn theme_text(
     *color = button_theme.color;
 }
 
+/// Applies the changed theme to all existing buttons.
+fn restyle(
+    theme: Res<Theme>,
+    mut buttons: Query<
+        (
+            Option<&ButtonKind>,
+            &Interaction,
+            Option<&Toggled>,
+            Has<Disabled>,
+            &mut Node,
+            &mut BackgroundColor,
+        ),
+        With<Button>,
+    >,
+    mut text: Query<(&Parent, &mut TextFont, &mut TextColor)>,
+    mut images: Query<(&Parent, &mut Node), (With<ImageNode>, Without<Button>)>,
+) {
+    for (button_kind, &interaction, toggled, disabled, mut node, mut background) in &mut buttons {
+        if let Some(&button_kind) = button_kind {
+            set_size(&mut node, &theme, button_kind);
+        }
+        *background = if disabled {
+            theme.button.disabled_background
+        } else {
+            background_color(&theme, interaction, toggled)
+        };
+    }
+
+    for (parent, mut font, mut color) in &mut text {
+        if let Ok((Some(&button_kind), ..)) = buttons.get(**parent) {
+            set_text_style(&mut font, &mut color, &theme, button_kind);
+        }
+    }
+
+    for (parent, mut node) in &mut images {
+        if let Ok((Some(ButtonKind::Image), ..)) = buttons.get(**parent) {
+            node.width = theme.button.image.image_width;
+            node.height = theme.button.image.image_height;
+        }
+    }
+}
+
 fn toggle(
     trigger: Trigger<Pointer<Click>>,
     mut buttons: Query<(&mut Toggled, Has<ExclusiveButton>)>,
@@ -113,22 +161,59 @@ fn update_background(
     theme: Res<Theme>,
     mut buttons: Query<
         (&Interaction, &mut BackgroundColor, Option<&Toggled>),
-        (Or<(Changed<Interaction>, Changed<Toggled>)>, With<Button>),
+        (
+            Or<(Changed<Interaction>, Changed<Toggled>)>,
+            With<Button>,
+            Without<Disabled>,
+        ),
     >,
 ) {
     for (&interaction, mut background, toggled) in &mut buttons {
-        let toggled = toggled.map(|toggled| toggled.0).unwrap_or_default();
-        *background = match (interaction, toggled) {
-            (Interaction::Pressed, _) | (Interaction::None, true) => {
-                theme.button.pressed_background
-            }
-            (Interaction::Hovered, true) => theme.button.hovered_pressed_background,
-            (Interaction::Hovered, false) => theme.button.hovered_background,
-            (Interaction::None, false) => theme.button.normal_background,
-        };
+        *background = background_color(&theme, interaction, toggled);
+    }
+}
+
+fn background_color(
+    theme: &Theme,
+    interaction: Interaction,
+    toggled: Option<&Toggled>,
+) -> BackgroundColor {
+    let toggled = toggled.map(|toggled| toggled.0).unwrap_or_default();
+    match (interaction, toggled) {
+        (Interaction::Pressed, _) | (Interaction::None, true) => theme.button.pressed_background,
+        (Interaction::Hovered, true) => theme.button.hovered_pressed_background,
+        (Interaction::Hovered, false) => theme.button.hovered_background,
+        (Interaction::None, false) => theme.button.normal_background,
     }
 }
 
+fn disable(
+    trigger: Trigger<OnAdd, Disabled>,
+    theme: Res<Theme>,
+    mut buttons: Query<(&mut BackgroundColor, &mut PickingBehavior)>,
+) {
+    let (mut background, mut picking) = buttons.get_mut(trigger.entity()).unwrap();
+    *background = theme.button.disabled_background;
+    picking.is_hoverable = false;
+}
+
+fn enable(
+    trigger: Trigger<OnRemove, Disabled>,
+    theme: Res<Theme>,
+    mut buttons: Query<(&mut BackgroundColor, &mut PickingBehavior, Option<&Toggled>)>,
+) {
+    let Ok((mut background, mut picking, toggled)) = buttons.get_mut(trigger.entity()) else {
+        return;
+    };
+
+    *background = if toggled.is_some_and(|toggled| **toggled) {
+        theme.button.pressed_background
+    } else {
+        theme.button.normal_background
+    };
+    picking.is_hoverable = true;
+}
+
 fn ensure_single_toggle(
     mut query_cache: Local<Vec<Entity>>,
     mut buttons: Query<(Entity, &mut Toggled), With<ExclusiveButton>>,
@@ -191,6 +276,11 @@ pub enum ButtonKind {
     Image,
 }
 
+/// Greys out the button and ignores clicks while present.
+#[derive(Component)]
+#[require(PickingBehavior)]
+pub struct Disabled;
+
 /// Makes button behave like tab by changing visibility of the stored entity depending on the value of [`Toggled`].
 #[derive(Component, Clone, Copy, Deref)]
 #[require(ExclusiveButton)]
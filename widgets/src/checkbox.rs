@@ -9,7 +9,10 @@ impl Plugin for CheckboxPlugin {
         app.add_observer(init)
             .add_observer(toggle)
             .add_observer(theme_text)
-            .add_systems(PostUpdate, update_tick);
+            .add_systems(
+                PostUpdate,
+                (update_tick, restyle.run_if(resource_changed::<Theme>)),
+            );
     }
 }
 
@@ -58,6 +61,41 @@ fn theme_text(
     *color = theme.label.normal.color;
 }
 
+fn restyle(
+    theme: Res<Theme>,
+    mut checkboxes: Query<(&mut Node, &Children), With<Checkbox>>,
+    mut buttons: Query<(&mut Node, Option<&Children>), (With<Button>, Without<Checkbox>)>,
+    mut ticks: Query<(&mut Node, &mut BackgroundColor), (Without<Button>, Without<Checkbox>)>,
+    mut text: Query<(&mut TextFont, &mut TextColor)>,
+) {
+    for (mut node, children) in &mut checkboxes {
+        node.column_gap = theme.checkbox.column_gap;
+
+        let mut iter = text.iter_many_mut(children);
+        while let Some((mut font, mut color)) = iter.fetch_next() {
+            font.font = theme.label.normal.font.clone();
+            font.font_size = theme.label.normal.font_size;
+            *color = theme.label.normal.color;
+        }
+
+        let mut iter = buttons.iter_many_mut(children);
+        while let Some((mut node, button_children)) = iter.fetch_next() {
+            node.width = theme.checkbox.button_width;
+            node.height = theme.checkbox.button_height;
+
+            let Some(button_children) = button_children else {
+                continue;
+            };
+            let mut iter = ticks.iter_many_mut(button_children);
+            while let Some((mut node, mut tick_color)) = iter.fetch_next() {
+                node.width = theme.checkbox.tick_width;
+                node.height = theme.checkbox.tick_height;
+                *tick_color = theme.checkbox.tick_color;
+            }
+        }
+    }
+}
+
 fn toggle(trigger: Trigger<Pointer<Click>>, mut checkboxes: Query<&mut Checkbox>) {
     if let Ok(mut checkbox) = checkboxes.get_mut(trigger.entity()) {
         checkbox.0 = !checkbox.0;
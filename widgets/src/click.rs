@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// Emits [`DoubleClick`] on top of picking clicks.
+pub(super) struct ClickPlugin;
+
+impl Plugin for ClickPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LastClick>().add_observer(detect_double);
+    }
+}
+
+/// Maximum delay between two clicks to count them as a double click.
+const DOUBLE_CLICK_DELAY: Duration = Duration::from_millis(400);
+
+fn detect_double(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut last_click: ResMut<LastClick>,
+) {
+    // Clicks propagate to parents, react only on the original target.
+    if trigger.entity() != trigger.target || trigger.button != PointerButton::Primary {
+        return;
+    }
+
+    let now = time.elapsed();
+    match last_click.take() {
+        Some((entity, time)) if entity == trigger.target && now - time <= DOUBLE_CLICK_DELAY => {
+            debug!("registering double click on `{entity}`");
+            commands.trigger_targets(DoubleClick { target: entity }, entity);
+        }
+        _ => **last_click = Some((trigger.target, now)),
+    }
+}
+
+/// Two primary clicks on the same entity in a short succession.
+///
+/// Triggered on the clicked entity and propagates to its parents like picking events.
+/// Both clicks are still emitted as regular [`Pointer<Click>`].
+#[derive(Clone, Component, Copy)]
+pub struct DoubleClick {
+    /// Originally clicked entity.
+    pub target: Entity,
+}
+
+impl Event for DoubleClick {
+    type Traversal = &'static Parent;
+
+    const AUTO_PROPAGATE: bool = true;
+}
+
+/// Target and time of the last click that wasn't a part of a double click.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct LastClick(Option<(Entity, Duration)>);
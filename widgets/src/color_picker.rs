@@ -0,0 +1,219 @@
+use bevy::prelude::*;
+use bevy_simple_text_input::{TextInputInactive, TextInputValue};
+
+use super::{
+    label::LabelKind,
+    slider::{Slider, SliderRange},
+    text_edit::TextEdit,
+    theme::Theme,
+};
+
+pub(super) struct ColorPickerPlugin;
+
+impl Plugin for ColorPickerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(init).add_systems(
+            PostUpdate,
+            (
+                (read_sliders, read_hex, update_widgets).chain(),
+                restyle.run_if(resource_changed::<Theme>),
+            ),
+        );
+    }
+}
+
+fn init(
+    trigger: Trigger<OnAdd, ColorPicker>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    mut pickers: Query<(&ColorPicker, &mut Node)>,
+) {
+    let (picker, mut node) = pickers.get_mut(trigger.entity()).unwrap();
+    node.flex_direction = FlexDirection::Column;
+    node.row_gap = theme.gap.normal;
+
+    let picker_entity = trigger.entity();
+    let color = picker.0;
+    commands.entity(picker_entity).with_children(|parent| {
+        parent.spawn((
+            PickerSwatch(picker_entity),
+            Node {
+                width: theme.color_picker.swatch_width,
+                height: theme.color_picker.swatch_height,
+                ..Default::default()
+            },
+            BackgroundColor(color.into()),
+        ));
+
+        parent
+            .spawn(Node {
+                display: Display::Grid,
+                column_gap: theme.gap.normal,
+                row_gap: theme.gap.normal,
+                grid_template_columns: vec![GridTrack::auto(); 2],
+                align_items: AlignItems::Center,
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                for channel in [HsvChannel::Hue, HsvChannel::Saturation, HsvChannel::Value] {
+                    parent.spawn((LabelKind::Normal, Text::new(channel.text())));
+                    parent.spawn((
+                        PickerSlider {
+                            picker_entity,
+                            channel,
+                        },
+                        Slider(channel.get(color)),
+                        channel.range(),
+                    ));
+                }
+            });
+
+        parent.spawn((PickerHex(picker_entity), TextInputValue(hex(color))));
+    });
+}
+
+fn restyle(theme: Res<Theme>, mut swatches: Query<&mut Node, With<PickerSwatch>>) {
+    for mut node in &mut swatches {
+        node.width = theme.color_picker.swatch_width;
+        node.height = theme.color_picker.swatch_height;
+    }
+}
+
+fn read_sliders(
+    sliders: Query<(&Slider, &PickerSlider), Changed<Slider>>,
+    mut pickers: Query<&mut ColorPicker>,
+) {
+    for (slider, picker_slider) in &sliders {
+        let Ok(mut picker) = pickers.get_mut(picker_slider.picker_entity) else {
+            continue;
+        };
+
+        let mut color = picker.0;
+        picker_slider.channel.set(&mut color, slider.0);
+        if picker.0 != color {
+            picker.0 = color;
+        }
+    }
+}
+
+fn read_hex(
+    hex_edits: Query<(&TextInputValue, &PickerHex), Changed<TextInputValue>>,
+    mut pickers: Query<&mut ColorPicker>,
+) {
+    for (text, hex_edit) in &hex_edits {
+        let Ok(mut picker) = pickers.get_mut(**hex_edit) else {
+            continue;
+        };
+        let Ok(srgba) = Srgba::hex(&text.0) else {
+            continue;
+        };
+
+        let color = Hsva::from(srgba);
+        if hex(picker.0) != hex(color) {
+            picker.0 = color;
+        }
+    }
+}
+
+fn update_widgets(
+    mut commands: Commands,
+    pickers: Query<(Entity, Ref<ColorPicker>)>,
+    mut swatches: Query<(&PickerSwatch, &mut BackgroundColor)>,
+    mut sliders: Query<(&PickerSlider, &mut Slider)>,
+    mut hex_edits: Query<(&PickerHex, &mut TextInputValue, &TextInputInactive)>,
+) {
+    for (picker_entity, picker) in &pickers {
+        if !picker.is_changed() || picker.is_added() {
+            continue;
+        }
+
+        for (swatch, mut background) in &mut swatches {
+            if **swatch == picker_entity {
+                background.0 = picker.0.into();
+            }
+        }
+
+        for (picker_slider, mut slider) in &mut sliders {
+            if picker_slider.picker_entity == picker_entity {
+                slider.set_if_neq(Slider(picker_slider.channel.get(picker.0)));
+            }
+        }
+
+        for (hex_edit, mut text, inactive) in &mut hex_edits {
+            // Don't interrupt typing.
+            if **hex_edit == picker_entity && inactive.0 {
+                text.0 = hex(picker.0);
+            }
+        }
+
+        debug!("picking color for `{picker_entity}`");
+        commands.trigger_targets(ColorPicked(picker.0.into()), picker_entity);
+    }
+}
+
+fn hex(color: Hsva) -> String {
+    Srgba::from(color).to_hex()
+}
+
+/// Allows to pick a color using sliders for HSV channels or by entering hex code.
+#[derive(Component)]
+#[require(Node)]
+pub struct ColorPicker(pub Hsva);
+
+/// Triggered on [`ColorPicker`] when its color changes after spawn.
+#[derive(Event, Clone, Copy)]
+pub struct ColorPicked(pub Color);
+
+#[derive(Clone, Copy)]
+enum HsvChannel {
+    Hue,
+    Saturation,
+    Value,
+}
+
+impl HsvChannel {
+    fn text(self) -> &'static str {
+        match self {
+            HsvChannel::Hue => "Hue",
+            HsvChannel::Saturation => "Saturation",
+            HsvChannel::Value => "Value",
+        }
+    }
+
+    fn range(self) -> SliderRange {
+        match self {
+            HsvChannel::Hue => SliderRange::new(0.0, 360.0),
+            HsvChannel::Saturation | HsvChannel::Value => SliderRange::default(),
+        }
+    }
+
+    fn get(self, color: Hsva) -> f32 {
+        match self {
+            HsvChannel::Hue => color.hue,
+            HsvChannel::Saturation => color.saturation,
+            HsvChannel::Value => color.value,
+        }
+    }
+
+    fn set(self, color: &mut Hsva, value: f32) {
+        match self {
+            HsvChannel::Hue => color.hue = value,
+            HsvChannel::Saturation => color.saturation = value,
+            HsvChannel::Value => color.value = value,
+        }
+    }
+}
+
+#[derive(Component)]
+struct PickerSlider {
+    picker_entity: Entity,
+    channel: HsvChannel,
+}
+
+/// Displays the color of the picker.
+#[derive(Component, Deref)]
+struct PickerSwatch(Entity);
+
+#[derive(Component, Deref)]
+#[require(TextEdit)]
+struct PickerHex(Entity);
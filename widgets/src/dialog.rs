@@ -1,12 +1,14 @@
 use bevy::{prelude::*, ui::FocusPolicy};
 
-use crate::theme::Theme;
+use crate::{button::ButtonKind, label::LabelKind, theme::Theme};
 
 pub(super) struct DialogPlugin;
 
 impl Plugin for DialogPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(init);
+        app.add_observer(init)
+            .add_observer(init_confirmation)
+            .add_systems(PostUpdate, restyle.run_if(resource_changed::<Theme>));
     }
 }
 
@@ -19,6 +21,90 @@ fn init(
     *background_color = theme.modal_background;
 }
 
+fn init_confirmation(
+    trigger: Trigger<OnAdd, ConfirmationDialog>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    dialogs: Query<&ConfirmationDialog>,
+) {
+    let dialog = dialogs.get(trigger.entity()).unwrap();
+    debug!("showing confirmation dialog `{}`", trigger.entity());
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent
+            .spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    padding: theme.padding.normal,
+                    row_gap: theme.gap.normal,
+                    ..Default::default()
+                },
+                theme.panel_background,
+            ))
+            .with_children(|parent| {
+                parent.spawn((LabelKind::Large, Text::new(dialog.title.clone())));
+                parent.spawn((LabelKind::Normal, Text::new(dialog.message.clone())));
+                parent
+                    .spawn(Node {
+                        column_gap: theme.gap.normal,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent
+                            .spawn(ButtonKind::Normal)
+                            .with_child(Text::new(dialog.confirm_text.clone()))
+                            .observe(confirm);
+                        parent
+                            .spawn(ButtonKind::Normal)
+                            .with_child(Text::new("Cancel"))
+                            .observe(cancel);
+                    });
+            });
+    });
+}
+
+fn confirm(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    parents: Query<&Parent>,
+    dialogs: Query<(), With<ConfirmationDialog>>,
+) {
+    let dialog_entity = find_dialog(trigger.entity(), &parents, &dialogs);
+    info!("confirming `{dialog_entity}`");
+    commands.trigger_targets(DialogConfirmed, dialog_entity);
+    commands.entity(dialog_entity).despawn_recursive();
+}
+
+fn cancel(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    parents: Query<&Parent>,
+    dialogs: Query<(), With<ConfirmationDialog>>,
+) {
+    let dialog_entity = find_dialog(trigger.entity(), &parents, &dialogs);
+    info!("cancelling `{dialog_entity}`");
+    commands.trigger_targets(DialogCancelled, dialog_entity);
+    commands.entity(dialog_entity).despawn_recursive();
+}
+
+fn find_dialog(
+    button_entity: Entity,
+    parents: &Query<&Parent>,
+    dialogs: &Query<(), With<ConfirmationDialog>>,
+) -> Entity {
+    parents
+        .iter_ancestors(button_entity)
+        .find(|&entity| dialogs.get(entity).is_ok())
+        .expect("buttons should be spawned inside confirmation dialog")
+}
+
+fn restyle(theme: Res<Theme>, mut dialogs: Query<&mut BackgroundColor, With<Dialog>>) {
+    for mut background_color in &mut dialogs {
+        *background_color = theme.modal_background;
+    }
+}
+
 #[derive(Component, Default)]
 #[require(
     Node(|| Node {
@@ -32,3 +118,40 @@ fn init(
     FocusPolicy(|| FocusPolicy::Block),
 )]
 pub struct Dialog;
+
+/// Modal dialog that asks to confirm an action.
+///
+/// Triggers [`DialogConfirmed`] or [`DialogCancelled`] on itself and despawns
+/// after pressing one of its buttons. Spawn it with components that describe
+/// the action to access them from observers.
+#[derive(Component)]
+#[require(Dialog)]
+pub struct ConfirmationDialog {
+    pub title: String,
+    pub message: String,
+    /// Text for the confirmation button.
+    pub confirm_text: String,
+}
+
+impl ConfirmationDialog {
+    pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            confirm_text: "Confirm".to_string(),
+        }
+    }
+
+    pub fn with_confirm_text(mut self, text: impl Into<String>) -> Self {
+        self.confirm_text = text.into();
+        self
+    }
+}
+
+/// Triggered on [`ConfirmationDialog`] when the user confirms the action.
+#[derive(Event, Clone, Copy)]
+pub struct DialogConfirmed;
+
+/// Triggered on [`ConfirmationDialog`] when the user cancels the action.
+#[derive(Event, Clone, Copy)]
+pub struct DialogCancelled;
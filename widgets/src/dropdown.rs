@@ -0,0 +1,261 @@
+use bevy::{prelude::*, ui::RelativeCursorPosition};
+use strum::IntoEnumIterator;
+
+use super::{
+    button::{ButtonKind, ExclusiveButton, Toggled},
+    navigation::Focused,
+    popup::{ManualClose, Popup},
+};
+
+pub(super) struct DropdownPlugin;
+
+impl Plugin for DropdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(init)
+            .add_observer(toggle_list)
+            .add_observer(select)
+            .add_systems(Update, (close, navigate))
+            .add_systems(PostUpdate, update_text);
+    }
+}
+
+fn init(trigger: Trigger<OnAdd, Dropdown>, mut commands: Commands) {
+    // Will be updated automatically on `Dropdown` insertion.
+    commands
+        .entity(trigger.entity())
+        .with_child(Text::default());
+}
+
+fn update_text(
+    dropdowns: Query<(&Dropdown, &Children), Or<(Changed<Dropdown>, Changed<Children>)>>,
+    mut text: Query<&mut Text>,
+) {
+    for (dropdown, children) in &dropdowns {
+        let mut iter = text.iter_many_mut(children);
+        let mut text = iter.fetch_next().expect("dropdown should have child text");
+        text.0 = dropdown.selected_text().unwrap_or_default().to_string();
+    }
+}
+
+/// Opens the list of options on click or closes it if it's already opened.
+fn toggle_list(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    dropdowns: Query<&Dropdown>,
+    popups: Query<(Entity, &Popup), With<DropdownList>>,
+) {
+    let Ok(dropdown) = dropdowns.get(trigger.entity()) else {
+        return;
+    };
+
+    if let Some((popup_entity, _)) = popups
+        .iter()
+        .find(|(_, popup)| popup.button_entity == trigger.entity())
+    {
+        debug!("closing options for `{}`", trigger.entity());
+        commands.entity(popup_entity).despawn_recursive();
+        return;
+    }
+
+    debug!("showing options for `{}`", trigger.entity());
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                DropdownList,
+                Popup {
+                    button_entity: trigger.entity(),
+                },
+            ))
+            .with_children(|parent| {
+                for (index, option) in dropdown.options.iter().enumerate() {
+                    parent
+                        .spawn((
+                            DropdownOption(index),
+                            ButtonKind::Normal,
+                            ExclusiveButton,
+                            Toggled(index == dropdown.selected),
+                        ))
+                        .with_child(Text::new(option.clone()));
+                }
+            });
+    });
+}
+
+fn select(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    options: Query<(&DropdownOption, &Parent)>,
+    popups: Query<&Popup>,
+    mut dropdowns: Query<&mut Dropdown>,
+) {
+    let Ok((&option, parent)) = options.get(trigger.entity()) else {
+        return;
+    };
+
+    let popup = popups
+        .get(**parent)
+        .expect("options should be spawned inside popup");
+    let mut dropdown = dropdowns
+        .get_mut(popup.button_entity)
+        .expect("popup should point to a dropdown");
+
+    apply_selection(&mut commands, &mut dropdown, popup.button_entity, *option);
+    commands.entity(**parent).despawn_recursive();
+}
+
+fn apply_selection(
+    commands: &mut Commands,
+    dropdown: &mut Dropdown,
+    dropdown_entity: Entity,
+    index: usize,
+) {
+    if dropdown.selected != index {
+        debug!("selecting option {index} for `{dropdown_entity}`");
+        dropdown.selected = index;
+        commands.trigger_targets(SelectionChanged(index), dropdown_entity);
+    }
+}
+
+/// Closes the list when clicking outside of it.
+fn close(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    popup: Option<Single<(Entity, &Popup, &RelativeCursorPosition), With<DropdownList>>>,
+    buttons: Query<&Interaction>,
+) {
+    let Some(popup) = popup else {
+        return;
+    };
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let (popup_entity, popup, cursor_position) = *popup;
+    // Clicks on the dropdown itself are handled by its observer.
+    let button_interaction = buttons.get(popup.button_entity).ok();
+    if !cursor_position.mouse_over() && button_interaction == Some(&Interaction::None) {
+        debug!("closing options for `{}`", popup.button_entity);
+        commands.entity(popup_entity).despawn_recursive();
+    }
+}
+
+/// Moves focus between options of the opened list with arrows.
+///
+/// Confirms the focused option with Enter and closes the list with Escape.
+fn navigate(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    popup: Option<Single<(Entity, &Popup, &Children), With<DropdownList>>>,
+    options: Query<(Entity, &DropdownOption, Has<Focused>)>,
+    mut dropdowns: Query<&mut Dropdown>,
+) {
+    let Some(popup) = popup else {
+        return;
+    };
+    let (popup_entity, popup, children) = *popup;
+
+    if keys.just_pressed(KeyCode::Escape) {
+        debug!("closing options for `{}`", popup.button_entity);
+        commands.entity(popup_entity).despawn_recursive();
+        return;
+    }
+
+    let options: Vec<_> = options.iter_many(children).collect();
+    let focused_index = options.iter().position(|&(.., focused)| focused);
+
+    if keys.just_pressed(KeyCode::Enter) {
+        if let Some(index) = focused_index {
+            let (_, &option, _) = options[index];
+            let mut dropdown = dropdowns
+                .get_mut(popup.button_entity)
+                .expect("popup should point to a dropdown");
+            apply_selection(&mut commands, &mut dropdown, popup.button_entity, *option);
+            commands.entity(popup_entity).despawn_recursive();
+        }
+        return;
+    }
+
+    let offset = if keys.just_pressed(KeyCode::ArrowDown) {
+        1
+    } else if keys.just_pressed(KeyCode::ArrowUp) {
+        -1
+    } else {
+        return;
+    };
+
+    let next_index = match focused_index {
+        Some(index) => index.saturating_add_signed(offset).min(options.len() - 1),
+        None => 0,
+    };
+    if let Some(index) = focused_index {
+        let (entity, ..) = options[index];
+        commands.entity(entity).remove::<Focused>();
+    }
+    if let Some(&(entity, ..)) = options.get(next_index) {
+        commands.entity(entity).insert(Focused);
+    }
+}
+
+/// Button that opens a list of options to pick one of them.
+///
+/// The button text shows the selected option.
+#[derive(Component)]
+#[require(ButtonKind(|| ButtonKind::Normal))]
+pub struct Dropdown {
+    pub options: Vec<String>,
+    pub selected: usize,
+}
+
+impl Dropdown {
+    pub fn new(options: impl IntoIterator<Item = impl Into<String>>, selected: usize) -> Self {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            selected,
+        }
+    }
+
+    /// Creates a dropdown with all enum variants as options.
+    pub fn from_enum<T: IntoEnumIterator + PartialEq>(
+        selected: T,
+        text: impl Fn(T) -> &'static str,
+    ) -> Self {
+        let mut selected_index = 0;
+        let options = T::iter()
+            .enumerate()
+            .map(|(index, value)| {
+                if value == selected {
+                    selected_index = index;
+                }
+                text(value)
+            })
+            .collect::<Vec<_>>();
+
+        Self::new(options, selected_index)
+    }
+
+    /// Returns the selected variant for dropdowns created with [`Self::from_enum`].
+    pub fn selected_enum<T: IntoEnumIterator>(&self) -> Option<T> {
+        T::iter().nth(self.selected)
+    }
+
+    pub fn selected_text(&self) -> Option<&str> {
+        self.options.get(self.selected).map(String::as_str)
+    }
+}
+
+/// Triggered on [`Dropdown`] when the user picks a different option.
+///
+/// Contains the index of the selected option.
+#[derive(Event, Clone, Copy)]
+pub struct SelectionChanged(pub usize);
+
+/// Popup with options for [`Dropdown`].
+///
+/// Unlike hover popups, stays open until an option is selected or the user clicks outside.
+#[derive(Component)]
+#[require(RelativeCursorPosition, ManualClose)]
+struct DropdownList;
+
+#[derive(Component, Clone, Copy, Deref)]
+struct DropdownOption(usize);
@@ -6,7 +6,8 @@ pub(super) struct LabelPlugin;
 
 impl Plugin for LabelPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(theme);
+        app.add_observer(theme)
+            .add_systems(PostUpdate, restyle.run_if(resource_changed::<Theme>));
     }
 }
 
@@ -19,6 +20,21 @@ fn theme(
         .get_mut(trigger.entity())
         .expect("labels should be spawned with text or span");
 
+    set_style(&mut text_font, &mut text_color, &theme, label_kind);
+}
+
+fn restyle(theme: Res<Theme>, mut labels: Query<(&LabelKind, &mut TextFont, &mut TextColor)>) {
+    for (label_kind, mut text_font, mut text_color) in &mut labels {
+        set_style(&mut text_font, &mut text_color, &theme, label_kind);
+    }
+}
+
+fn set_style(
+    text_font: &mut TextFont,
+    text_color: &mut TextColor,
+    theme: &Theme,
+    label_kind: &LabelKind,
+) {
     let label_theme = match label_kind {
         LabelKind::Small => &theme.label.small,
         LabelKind::Normal => &theme.label.normal,
@@ -1,22 +1,42 @@
 pub mod button;
 pub mod checkbox;
+pub mod click;
+pub mod color_picker;
 pub mod dialog;
+pub mod dropdown;
 pub mod label;
+pub mod line_chart;
+pub mod navigation;
+pub mod numeric_edit;
 pub mod popup;
 pub mod progress_bar;
+pub mod scroll_view;
+pub mod slider;
+pub mod tab_view;
 pub mod text_edit;
 pub mod theme;
+pub mod tooltip;
 
 use bevy::prelude::*;
 
 use button::ButtonPlugin;
 use checkbox::CheckboxPlugin;
+use click::ClickPlugin;
+use color_picker::ColorPickerPlugin;
 use dialog::DialogPlugin;
+use dropdown::DropdownPlugin;
 use label::LabelPlugin;
+use line_chart::LineChartPlugin;
+use navigation::NavigationPlugin;
+use numeric_edit::NumericEditPlugin;
 use popup::PopupPlugin;
 use progress_bar::ProgressBarPlugin;
+use scroll_view::ScrollViewPlugin;
+use slider::SliderPlugin;
+use tab_view::TabViewPlugin;
 use text_edit::TextEditPlugin;
 use theme::ThemePlugin;
+use tooltip::TooltipPlugin;
 
 pub struct WidgetsPlugin;
 
@@ -25,12 +45,24 @@ impl Plugin for WidgetsPlugin {
         app.add_plugins((
             ButtonPlugin,
             DialogPlugin,
+            DropdownPlugin,
             LabelPlugin,
+            LineChartPlugin,
+            NavigationPlugin,
+            NumericEditPlugin,
             CheckboxPlugin,
+            ColorPickerPlugin,
             PopupPlugin,
             ProgressBarPlugin,
+            ScrollViewPlugin,
+        ))
+        .add_plugins((
+            SliderPlugin,
+            TabViewPlugin,
             TextEditPlugin,
             ThemePlugin,
+            TooltipPlugin,
+            ClickPlugin,
         ));
     }
 }
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use super::theme::Theme;
+
+pub(super) struct LineChartPlugin;
+
+impl Plugin for LineChartPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(init).add_systems(
+            PostUpdate,
+            (update_points, restyle.run_if(resource_changed::<Theme>)),
+        );
+    }
+}
+
+fn init(
+    trigger: Trigger<OnAdd, LineChart>,
+    theme: Res<Theme>,
+    mut charts: Query<&mut BackgroundColor>,
+) {
+    let mut background_color = charts.get_mut(trigger.entity()).unwrap();
+    *background_color = theme.line_chart.background_color;
+}
+
+fn restyle(
+    theme: Res<Theme>,
+    mut charts: Query<&mut BackgroundColor, (With<LineChart>, Without<ChartPoint>)>,
+    mut points: Query<(&mut Node, &mut BackgroundColor), With<ChartPoint>>,
+) {
+    for mut background_color in &mut charts {
+        *background_color = theme.line_chart.background_color;
+    }
+    for (mut node, mut point_color) in &mut points {
+        set_point_size(&mut node, theme.line_chart.point_size);
+        *point_color = theme.line_chart.point_color;
+    }
+}
+
+/// Respawns points with values normalized between minimum and maximum.
+fn update_points(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    charts: Query<(Entity, &LineChart), Changed<LineChart>>,
+) {
+    for (chart_entity, chart) in &charts {
+        let min = chart.min();
+        let range = chart.max() - min;
+        let last_index = chart.0.len().saturating_sub(1);
+
+        commands
+            .entity(chart_entity)
+            .despawn_descendants()
+            .with_children(|parent| {
+                for (index, &value) in chart.0.iter().enumerate() {
+                    let x = if last_index == 0 {
+                        0.5
+                    } else {
+                        index as f32 / last_index as f32
+                    };
+                    let y = if range == 0.0 {
+                        0.5
+                    } else {
+                        (value - min) / range
+                    };
+
+                    let mut node = Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Percent(x * 100.0),
+                        bottom: Val::Percent(y * 100.0),
+                        ..Default::default()
+                    };
+                    set_point_size(&mut node, theme.line_chart.point_size);
+                    parent.spawn((ChartPoint, node, theme.line_chart.point_color));
+                }
+            });
+    }
+}
+
+fn set_point_size(node: &mut Node, size: f32) {
+    node.width = Val::Px(size);
+    node.height = Val::Px(size);
+    node.margin.left = Val::Px(-size / 2.0);
+    node.margin.bottom = Val::Px(-size / 2.0);
+}
+
+/// Plots values from left to right as points scaled to the node size.
+#[derive(Component, Default)]
+#[require(Node, BackgroundColor)]
+pub struct LineChart(pub Vec<f32>);
+
+impl LineChart {
+    /// Returns the smallest value or 0 if empty.
+    pub fn min(&self) -> f32 {
+        self.0.iter().copied().reduce(f32::min).unwrap_or_default()
+    }
+
+    /// Returns the largest value or 0 if empty.
+    pub fn max(&self) -> f32 {
+        self.0.iter().copied().reduce(f32::max).unwrap_or_default()
+    }
+}
+
+#[derive(Component)]
+#[require(PickingBehavior(|| PickingBehavior::IGNORE))]
+struct ChartPoint;
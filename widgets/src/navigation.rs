@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+use bevy::{
+    math::FloatOrd,
+    picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    },
+    prelude::*,
+    render::camera::NormalizedRenderTarget,
+    window::{PrimaryWindow, WindowRef},
+};
+
+use super::{
+    button::Disabled,
+    slider::{Slider, SliderRange},
+    theme::Theme,
+};
+
+/// Moves focus between interactive nodes with gamepad D-pad.
+///
+/// South button clicks the focused node and left or right on D-pad adjusts focused sliders.
+/// Focus is dropped when the cursor moves.
+pub(super) struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(show_outline)
+            .add_observer(hide_outline)
+            .add_systems(Update, (drop_focus, move_focus, click).chain());
+    }
+}
+
+fn show_outline(trigger: Trigger<OnAdd, Focused>, mut commands: Commands, theme: Res<Theme>) {
+    commands
+        .entity(trigger.entity())
+        .insert(theme.focus_outline);
+}
+
+fn hide_outline(trigger: Trigger<OnRemove, Focused>, mut commands: Commands) {
+    commands.entity(trigger.entity()).remove::<Outline>();
+}
+
+fn drop_focus(
+    mut commands: Commands,
+    mut cursor_events: EventReader<CursorMoved>,
+    focused_entity: Option<Single<Entity, With<Focused>>>,
+) {
+    if cursor_events.read().count() == 0 {
+        return;
+    }
+
+    if let Some(focused_entity) = focused_entity {
+        debug!("dropping focus from `{}`", *focused_entity);
+        commands.entity(*focused_entity).remove::<Focused>();
+    }
+}
+
+const DIRECTIONS: [(GamepadButton, Vec2); 4] = [
+    (GamepadButton::DPadUp, Vec2::NEG_Y),
+    (GamepadButton::DPadDown, Vec2::Y),
+    (GamepadButton::DPadLeft, Vec2::NEG_X),
+    (GamepadButton::DPadRight, Vec2::X),
+];
+
+/// Fraction of the range to change continuous sliders per D-pad press.
+const SLIDER_STEP: f32 = 0.1;
+
+fn move_focus(
+    mut commands: Commands,
+    gamepads: Query<&Gamepad>,
+    focused: Option<
+        Single<
+            (
+                Entity,
+                &GlobalTransform,
+                Option<(&mut Slider, &SliderRange)>,
+            ),
+            With<Focused>,
+        >,
+    >,
+    nodes: Query<
+        (
+            Entity,
+            &ComputedNode,
+            &GlobalTransform,
+            &InheritedVisibility,
+        ),
+        (With<Interaction>, Without<Disabled>),
+    >,
+) {
+    let Some(direction) = gamepads.iter().find_map(|gamepad| {
+        DIRECTIONS
+            .iter()
+            .find(|(button, _)| gamepad.just_pressed(*button))
+            .map(|&(_, direction)| direction)
+    }) else {
+        return;
+    };
+
+    let visible_nodes = nodes.iter().filter(|(_, node, _, visibility)| {
+        // Nodes inside hidden tabs have zero size.
+        visibility.get() && node.size() != Vec2::ZERO
+    });
+
+    let Some(focused) = focused else {
+        if let Some((entity, ..)) = visible_nodes.min_by_key(|(.., transform, _)| {
+            let translation = transform.translation();
+            FloatOrd(translation.x + translation.y)
+        }) {
+            debug!("focusing `{entity}`");
+            commands.entity(entity).insert(Focused);
+        }
+        return;
+    };
+
+    let (focused_entity, focused_transform, slider) = focused.into_inner();
+    if let Some((mut slider, range)) = slider {
+        if direction.x != 0.0 {
+            slider.0 = range.step_by(slider.0, direction.x, SLIDER_STEP);
+            return;
+        }
+    }
+
+    let origin = focused_transform.translation().truncate();
+    let next = visible_nodes
+        .filter(|&(entity, ..)| entity != focused_entity)
+        .filter_map(|(entity, _, transform, _)| {
+            let offset = transform.translation().truncate() - origin;
+            let distance = offset.dot(direction);
+            if distance <= 0.0 {
+                return None;
+            }
+
+            // Prefer nodes that are aligned with the direction.
+            let deviation = (offset - direction * distance).length();
+            Some((entity, distance + deviation * 2.0))
+        })
+        .min_by_key(|&(_, score)| FloatOrd(score));
+
+    if let Some((entity, _)) = next {
+        debug!("moving focus from `{focused_entity}` to `{entity}`");
+        commands.entity(focused_entity).remove::<Focused>();
+        commands.entity(entity).insert(Focused);
+    }
+}
+
+/// Triggers the same event as a mouse click at the center of the focused node.
+fn click(
+    mut commands: Commands,
+    gamepads: Query<&Gamepad>,
+    window_entity: Option<Single<Entity, With<PrimaryWindow>>>,
+    focused: Option<Single<(Entity, &ComputedNode, &GlobalTransform), With<Focused>>>,
+) {
+    if !gamepads
+        .iter()
+        .any(|gamepad| gamepad.just_pressed(GamepadButton::South))
+    {
+        return;
+    }
+    let (Some(window_entity), Some(focused)) = (window_entity, focused) else {
+        return;
+    };
+
+    let (entity, node, transform) = *focused;
+    let window_ref = WindowRef::Primary
+        .normalize(Some(*window_entity))
+        .expect("primary window should always be normalized");
+    let location = Location {
+        target: NormalizedRenderTarget::Window(window_ref),
+        position: transform.translation().truncate() * node.inverse_scale_factor(),
+    };
+    let event = Click {
+        button: PointerButton::Primary,
+        hit: HitData::new(Entity::PLACEHOLDER, 0.0, None, None),
+        duration: Duration::ZERO,
+    };
+
+    debug!("clicking focused `{entity}`");
+    commands.trigger_targets(
+        Pointer::new(PointerId::Mouse, location, entity, event),
+        entity,
+    );
+}
+
+/// Marks an interactive node selected with gamepad.
+///
+/// Only one node can be focused at a time.
+#[derive(Component)]
+pub struct Focused;
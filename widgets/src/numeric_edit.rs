@@ -0,0 +1,270 @@
+use std::{fmt::Display, str::FromStr};
+
+use bevy::prelude::*;
+use bevy_simple_text_input::{TextInputCursorPos, TextInputInactive, TextInputValue};
+
+use super::{button::ButtonKind, text_edit::TextEdit, theme::Theme};
+
+pub(super) struct NumericEditPlugin;
+
+impl Plugin for NumericEditPlugin {
+    fn build(&self, app: &mut App) {
+        add_numeric_edit::<u16>(app);
+        add_numeric_edit::<u32>(app);
+        add_numeric_edit::<i32>(app);
+        add_numeric_edit::<f32>(app);
+    }
+}
+
+fn add_numeric_edit<T: Numeric>(app: &mut App) {
+    app.add_observer(init::<T>).add_systems(
+        PostUpdate,
+        (
+            parse::<T>,
+            sync_text::<T>,
+            normalize::<T>,
+            trigger_changed::<T>,
+        )
+            .chain(),
+    );
+}
+
+fn init<T: Numeric>(
+    trigger: Trigger<OnAdd, NumericEdit<T>>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    mut edits: Query<(&NumericEdit<T>, &mut Node)>,
+) {
+    let (edit, mut node) = edits.get_mut(trigger.entity()).unwrap();
+    node.align_items = AlignItems::Center;
+    node.column_gap = theme.gap.normal;
+
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent
+            .spawn((SpinButton(-1), ButtonKind::Symbol))
+            .with_child(Text::new("-"))
+            .observe(spin::<T>);
+        parent.spawn((NumericText, TextInputValue(edit.value.to_string())));
+        parent
+            .spawn((SpinButton(1), ButtonKind::Symbol))
+            .with_child(Text::new("+"))
+            .observe(spin::<T>);
+    });
+}
+
+fn spin<T: Numeric>(
+    trigger: Trigger<Pointer<Click>>,
+    buttons: Query<(&SpinButton, &Parent)>,
+    mut edits: Query<(&mut NumericEdit<T>, &Children)>,
+    mut texts: Query<&mut TextInputValue, With<NumericText>>,
+) {
+    let (&direction, parent) = buttons.get(trigger.entity()).unwrap();
+    let (mut edit, children) = edits
+        .get_mut(**parent)
+        .expect("spin buttons should be spawned inside numeric edit");
+
+    let value = if *direction > 0 {
+        edit.value.saturating_add(edit.step)
+    } else {
+        edit.value.saturating_sub(edit.step)
+    };
+    let value = edit.clamp(value);
+    if value != edit.value {
+        edit.value = value;
+        let mut iter = texts.iter_many_mut(children);
+        let mut text = iter
+            .fetch_next()
+            .expect("numeric edit should have child text");
+        text.0 = value.to_string();
+    }
+}
+
+/// Removes invalid characters and updates the value from the text.
+fn parse<T: Numeric>(
+    mut texts: Query<
+        (&mut TextInputValue, &mut TextInputCursorPos, &Parent),
+        (With<NumericText>, Changed<TextInputValue>),
+    >,
+    mut edits: Query<&mut NumericEdit<T>>,
+) {
+    for (mut text, mut cursor_pos, parent) in &mut texts {
+        let Ok(mut edit) = edits.get_mut(**parent) else {
+            continue;
+        };
+
+        if text.0.chars().any(|c| !T::is_valid_char(c)) {
+            text.0.retain(T::is_valid_char);
+            cursor_pos.0 = cursor_pos.0.min(text.0.len());
+        }
+
+        if let Ok(value) = text.0.parse::<T>() {
+            let value = edit.clamp(value);
+            if edit.value != value {
+                edit.value = value;
+            }
+        }
+    }
+}
+
+/// Updates the text after value changes from code.
+///
+/// Skips the text that is currently edited to avoid interrupting the input.
+fn sync_text<T: Numeric>(
+    edits: Query<(&NumericEdit<T>, &Children), Changed<NumericEdit<T>>>,
+    mut texts: Query<(&mut TextInputValue, &TextInputInactive), With<NumericText>>,
+) {
+    for (edit, children) in &edits {
+        let mut iter = texts.iter_many_mut(children);
+        while let Some((mut text, inactive)) = iter.fetch_next() {
+            if inactive.0 && text.0.parse::<T>().ok() != Some(edit.value) {
+                text.0 = edit.value.to_string();
+            }
+        }
+    }
+}
+
+/// Replaces the text with the clamped value when the edit loses focus.
+fn normalize<T: Numeric>(
+    mut texts: Query<
+        (&mut TextInputValue, &TextInputInactive, &Parent),
+        (With<NumericText>, Changed<TextInputInactive>),
+    >,
+    edits: Query<&NumericEdit<T>>,
+) {
+    for (mut text, inactive, parent) in &mut texts {
+        let Ok(edit) = edits.get(**parent) else {
+            continue;
+        };
+
+        let value = edit.value.to_string();
+        if inactive.0 && text.0 != value {
+            text.0 = value;
+        }
+    }
+}
+
+fn trigger_changed<T: Numeric>(
+    mut commands: Commands,
+    edits: Query<(Entity, Ref<NumericEdit<T>>)>,
+) {
+    for (entity, edit) in &edits {
+        if edit.is_changed() && !edit.is_added() {
+            commands.trigger_targets(ValueChanged(edit.value), entity);
+        }
+    }
+}
+
+/// Text edit for numbers with buttons to increment or decrement the value.
+///
+/// Only characters valid for `T` can be typed.
+#[derive(Component, Clone, Copy)]
+#[require(Node)]
+pub struct NumericEdit<T: Numeric> {
+    pub value: T,
+    pub min: T,
+    pub max: T,
+    /// Value change for spin buttons.
+    pub step: T,
+}
+
+impl<T: Numeric> NumericEdit<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            min: T::MIN,
+            max: T::MAX,
+            step: T::ONE,
+        }
+    }
+
+    pub fn with_range(mut self, min: T, max: T) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    pub fn with_step(mut self, step: T) -> Self {
+        self.step = step;
+        self
+    }
+
+    fn clamp(&self, value: T) -> T {
+        if value < self.min {
+            self.min
+        } else if value > self.max {
+            self.max
+        } else {
+            value
+        }
+    }
+}
+
+/// Triggered on [`NumericEdit`] when its value changes after spawn.
+#[derive(Event, Clone, Copy)]
+pub struct ValueChanged<T: Numeric>(pub T);
+
+/// Number type supported by [`NumericEdit`].
+pub trait Numeric: FromStr + Display + PartialOrd + Copy + Send + Sync + 'static {
+    const MIN: Self;
+    const MAX: Self;
+    const ONE: Self;
+
+    fn saturating_add(self, other: Self) -> Self;
+    fn saturating_sub(self, other: Self) -> Self;
+
+    /// Returns `true` if the character can be a part of a number.
+    fn is_valid_char(c: char) -> bool;
+}
+
+macro_rules! impl_integer {
+    ($type:ty, [$($extra_char:literal),*]) => {
+        impl Numeric for $type {
+            const MIN: Self = <$type>::MIN;
+            const MAX: Self = <$type>::MAX;
+            const ONE: Self = 1;
+
+            fn saturating_add(self, other: Self) -> Self {
+                <$type>::saturating_add(self, other)
+            }
+
+            fn saturating_sub(self, other: Self) -> Self {
+                <$type>::saturating_sub(self, other)
+            }
+
+            fn is_valid_char(c: char) -> bool {
+                c.is_ascii_digit() || [$($extra_char),*].contains(&c)
+            }
+        }
+    };
+}
+
+impl_integer!(u16, []);
+impl_integer!(u32, []);
+impl_integer!(i32, ['-']);
+
+impl Numeric for f32 {
+    const MIN: Self = f32::MIN;
+    const MAX: Self = f32::MAX;
+    const ONE: Self = 1.0;
+
+    fn saturating_add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn saturating_sub(self, other: Self) -> Self {
+        self - other
+    }
+
+    fn is_valid_char(c: char) -> bool {
+        c.is_ascii_digit() || c == '-' || c == '.'
+    }
+}
+
+/// Text edit inside [`NumericEdit`].
+#[derive(Component)]
+#[require(TextEdit)]
+struct NumericText;
+
+/// Changes [`NumericEdit`] value by its step in the stored direction.
+#[derive(Component, Clone, Copy, Deref)]
+struct SpinButton(i8);
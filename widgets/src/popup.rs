@@ -6,7 +6,10 @@ pub(super) struct PopupPlugin;
 
 impl Plugin for PopupPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(init).add_systems(PostUpdate, close);
+        app.add_observer(init).add_systems(
+            PostUpdate,
+            (close, restyle.run_if(resource_changed::<Theme>)),
+        );
     }
 }
 
@@ -36,7 +39,18 @@ fn init(
     *background = theme.popup_background;
 }
 
-fn close(mut commands: Commands, popups: Query<(Entity, &Popup)>, buttons: Query<&Interaction>) {
+fn restyle(theme: Res<Theme>, mut popups: Query<(&mut Node, &mut BackgroundColor), With<Popup>>) {
+    for (mut node, mut background) in &mut popups {
+        node.padding = theme.padding.normal;
+        *background = theme.popup_background;
+    }
+}
+
+fn close(
+    mut commands: Commands,
+    popups: Query<(Entity, &Popup), Without<ManualClose>>,
+    buttons: Query<&Interaction>,
+) {
     for (entity, popup) in &popups {
         match buttons.get(popup.button_entity) {
             Ok(Interaction::Hovered) | Ok(Interaction::Pressed) => (),
@@ -45,8 +59,15 @@ fn close(mut commands: Commands, popups: Query<(Entity, &Popup)>, buttons: Query
     }
 }
 
+/// Node displayed above the button.
+///
+/// Despawns automatically when the button is no longer hovered.
 #[derive(Component)]
 #[require(Node)]
 pub struct Popup {
     pub button_entity: Entity,
 }
+
+/// Disables automatic despawn of [`Popup`].
+#[derive(Component, Default)]
+pub struct ManualClose;
@@ -7,7 +7,15 @@ pub(super) struct ProgressBarPlugin;
 impl Plugin for ProgressBarPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(init)
-            .add_systems(PostUpdate, update_progress);
+            .add_observer(init_marker)
+            .add_systems(
+                PostUpdate,
+                (
+                    update_progress,
+                    update_marker,
+                    restyle.run_if(resource_changed::<Theme>),
+                ),
+            );
     }
 }
 
@@ -15,29 +23,131 @@ fn init(
     trigger: Trigger<OnAdd, ProgressBar>,
     mut commands: Commands,
     theme: Res<Theme>,
-    mut progress_bars: Query<&mut BackgroundColor>,
+    mut progress_bars: Query<(
+        &mut BackgroundColor,
+        &ProgressBar,
+        Option<&ProgressThresholds>,
+    )>,
 ) {
-    let mut background_color = progress_bars.get_mut(trigger.entity()).unwrap();
+    let (mut background_color, progress_bar, thresholds) =
+        progress_bars.get_mut(trigger.entity()).unwrap();
     *background_color = theme.progress_bar.background_color;
 
-    commands
-        .entity(trigger.entity())
-        .with_child((Node::default(), theme.progress_bar.fill_color));
+    commands.entity(trigger.entity()).with_child((
+        FillNode,
+        Node::default(),
+        fill_color(&theme, progress_bar.0, thresholds),
+    ));
+}
+
+fn init_marker(trigger: Trigger<OnAdd, ProgressMarker>, mut commands: Commands, theme: Res<Theme>) {
+    commands.entity(trigger.entity()).with_child((
+        MarkerNode,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(theme.progress_bar.marker_width),
+            height: Val::Percent(100.0),
+            ..Default::default()
+        },
+        theme.progress_bar.marker_color,
+    ));
+}
+
+fn restyle(
+    theme: Res<Theme>,
+    mut progress_bars: Query<
+        (
+            &mut BackgroundColor,
+            &ProgressBar,
+            Option<&ProgressThresholds>,
+            &Children,
+        ),
+        (Without<FillNode>, Without<MarkerNode>),
+    >,
+    mut fill_nodes: Query<&mut BackgroundColor, (With<FillNode>, Without<MarkerNode>)>,
+    mut marker_nodes: Query<(&mut BackgroundColor, &mut Node), With<MarkerNode>>,
+) {
+    for (mut background_color, progress_bar, thresholds, children) in &mut progress_bars {
+        *background_color = theme.progress_bar.background_color;
+        let mut iter = fill_nodes.iter_many_mut(children);
+        while let Some(mut color) = iter.fetch_next() {
+            *color = fill_color(&theme, progress_bar.0, thresholds);
+        }
+        let mut iter = marker_nodes.iter_many_mut(children);
+        while let Some((mut color, mut node)) = iter.fetch_next() {
+            *color = theme.progress_bar.marker_color;
+            node.width = Val::Px(theme.progress_bar.marker_width);
+        }
+    }
 }
 
 fn update_progress(
-    progress_bars: Query<(&ProgressBar, &Children), Changed<ProgressBar>>,
-    mut fill_nodes: Query<&mut Node>,
+    theme: Res<Theme>,
+    progress_bars: Query<
+        (&ProgressBar, Option<&ProgressThresholds>, &Children),
+        Or<(Changed<ProgressBar>, Changed<ProgressThresholds>)>,
+    >,
+    mut fill_nodes: Query<(&mut Node, &mut BackgroundColor), With<FillNode>>,
 ) {
-    for (progress_bar, children) in &progress_bars {
+    for (progress_bar, thresholds, children) in &progress_bars {
         let mut iter = fill_nodes.iter_many_mut(children);
-        let mut style = iter
+        let (mut style, mut color) = iter
             .fetch_next()
             .expect("progress bar should have child fill node");
         style.width = Val::Percent(progress_bar.0);
+        *color = fill_color(&theme, progress_bar.0, thresholds);
     }
 }
 
+fn update_marker(
+    progress_bars: Query<(&ProgressMarker, &Children), Changed<ProgressMarker>>,
+    mut marker_nodes: Query<&mut Node, With<MarkerNode>>,
+) {
+    for (marker, children) in &progress_bars {
+        let mut iter = marker_nodes.iter_many_mut(children);
+        while let Some(mut style) = iter.fetch_next() {
+            if let Some(value) = marker.0 {
+                style.display = Display::Flex;
+                style.left = Val::Percent(value.clamp(0.0, 100.0));
+            } else {
+                style.display = Display::None;
+            }
+        }
+    }
+}
+
+fn fill_color(
+    theme: &Theme,
+    value: f32,
+    thresholds: Option<&ProgressThresholds>,
+) -> BackgroundColor {
+    match thresholds {
+        Some(thresholds) if value < thresholds.critical => theme.progress_bar.critical_color,
+        Some(thresholds) if value < thresholds.warning => theme.progress_bar.warning_color,
+        _ => theme.progress_bar.fill_color,
+    }
+}
+
+/// Progress in percent.
 #[derive(Component)]
 #[require(Node)]
 pub struct ProgressBar(pub f32);
+
+/// Colors the fill of [`ProgressBar`] when its value drops below the thresholds.
+#[derive(Component, Clone, Copy)]
+pub struct ProgressThresholds {
+    pub warning: f32,
+    pub critical: f32,
+}
+
+/// Displays a vertical line on [`ProgressBar`] at the specified value.
+///
+/// Hidden if [`None`].
+#[derive(Component, Default, PartialEq)]
+pub struct ProgressMarker(pub Option<f32>);
+
+#[derive(Component)]
+struct FillNode;
+
+#[derive(Component)]
+struct MarkerNode;
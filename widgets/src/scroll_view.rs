@@ -0,0 +1,178 @@
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    ui::RelativeCursorPosition,
+};
+
+use super::theme::Theme;
+
+pub(super) struct ScrollViewPlugin;
+
+impl Plugin for ScrollViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(init)
+            .add_observer(drag)
+            .add_systems(Update, (scroll, update_thumb).chain())
+            .add_systems(PostUpdate, restyle.run_if(resource_changed::<Theme>));
+    }
+}
+
+fn init(
+    trigger: Trigger<OnAdd, ScrollView>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    mut views: Query<&mut Node>,
+) {
+    let mut node = views.get_mut(trigger.entity()).unwrap();
+    node.overflow = Overflow::scroll_y();
+
+    commands.entity(trigger.entity()).with_child((
+        ScrollThumb,
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::ZERO,
+            width: theme.scroll_view.thumb_width,
+            display: Display::None,
+            ..Default::default()
+        },
+        theme.scroll_view.thumb_color,
+    ));
+}
+
+fn restyle(
+    theme: Res<Theme>,
+    mut thumbs: Query<(&mut Node, &mut BackgroundColor), With<ScrollThumb>>,
+) {
+    for (mut node, mut background_color) in &mut thumbs {
+        node.width = theme.scroll_view.thumb_width;
+        *background_color = theme.scroll_view.thumb_color;
+    }
+}
+
+/// Height of a single line for mouse wheels that scroll by lines.
+const LINE_HEIGHT: f32 = 20.0;
+
+fn scroll(
+    mut wheel_events: EventReader<MouseWheel>,
+    mut views: Query<(&mut ScrollPosition, &RelativeCursorPosition), With<ScrollView>>,
+) {
+    for event in wheel_events.read() {
+        let delta = match event.unit {
+            MouseScrollUnit::Line => event.y * LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+
+        for (mut scroll_position, cursor_position) in &mut views {
+            if cursor_position.mouse_over() {
+                scroll_position.offset_y = (scroll_position.offset_y - delta).max(0.0);
+            }
+        }
+    }
+}
+
+/// Scrolls the content by dragging it or the thumb.
+///
+/// Dragging the thumb moves the content in the opposite direction
+/// proportionally to the content size.
+fn drag(
+    mut trigger: Trigger<Pointer<Drag>>,
+    mut views: Query<(&mut ScrollPosition, &ScrollContent)>,
+    thumbs: Query<(), With<ScrollThumb>>,
+) {
+    let Ok((mut scroll_position, content)) = views.get_mut(trigger.entity()) else {
+        return;
+    };
+    trigger.propagate(false);
+
+    let delta = trigger.event().delta.y;
+    let offset = if thumbs.get(trigger.event().target).is_ok() {
+        delta * content.ratio()
+    } else {
+        -delta
+    };
+    scroll_position.offset_y = (scroll_position.offset_y + offset).max(0.0);
+}
+
+/// Calculates content size and places the thumb according to the scroll position.
+///
+/// Uses layout from the previous frame.
+fn update_thumb(
+    mut views: Query<
+        (
+            &mut ScrollContent,
+            &ScrollPosition,
+            &ComputedNode,
+            &GlobalTransform,
+            &Children,
+        ),
+        With<ScrollView>,
+    >,
+    children: Query<(&ComputedNode, &GlobalTransform), Without<ScrollThumb>>,
+    mut thumbs: Query<&mut Node, With<ScrollThumb>>,
+) {
+    for (mut content, scroll_position, node, transform, view_children) in &mut views {
+        let scale = node.inverse_scale_factor();
+        let visible_height = node.size().y * scale;
+        let top = transform.translation().y * scale - visible_height / 2.0;
+        let bottom = children
+            .iter_many(view_children)
+            .map(|(node, transform)| (transform.translation().y + node.size().y / 2.0) * scale)
+            .fold(top, f32::max);
+
+        let height = bottom - top + scroll_position.offset_y;
+        content.set_if_neq(ScrollContent {
+            height,
+            visible_height,
+        });
+
+        let mut iter = thumbs.iter_many_mut(view_children);
+        let mut thumb_node = iter
+            .fetch_next()
+            .expect("scroll view should have child thumb");
+
+        let mut new_node = thumb_node.clone();
+        if height > visible_height {
+            let thumb_height = visible_height * visible_height / height;
+            let progress = (scroll_position.offset_y / (height - visible_height)).clamp(0.0, 1.0);
+            // Children of a scrolled node are offset, including the thumb.
+            let top = scroll_position.offset_y + progress * (visible_height - thumb_height);
+
+            new_node.display = Display::Flex;
+            new_node.height = Val::Px(thumb_height);
+            new_node.top = Val::Px(top);
+        } else {
+            new_node.display = Display::None;
+        }
+        thumb_node.set_if_neq(new_node);
+    }
+}
+
+/// Vertical container that clips overflowing children and scrolls them.
+///
+/// Content can be scrolled with mouse wheel or by dragging the content or the thumb.
+/// Limit the height of the node to make it scrollable.
+#[derive(Component)]
+#[require(Node, ScrollPosition, RelativeCursorPosition, ScrollContent)]
+pub struct ScrollView;
+
+/// Marks the scrollbar thumb inside [`ScrollView`].
+#[derive(Component)]
+struct ScrollThumb;
+
+/// Content size of [`ScrollView`] in logical pixels.
+#[derive(Component, Default, PartialEq, Clone, Copy)]
+struct ScrollContent {
+    height: f32,
+    visible_height: f32,
+}
+
+impl ScrollContent {
+    /// Returns how much the content moves per pixel of the thumb movement.
+    fn ratio(self) -> f32 {
+        if self.visible_height > 0.0 {
+            self.height / self.visible_height
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+
+use super::{label::LabelKind, theme::Theme};
+
+pub(super) struct SliderPlugin;
+
+impl Plugin for SliderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(init)
+            .add_observer(init_label)
+            .add_observer(press)
+            .add_observer(drag)
+            .add_systems(
+                PostUpdate,
+                (
+                    trigger_changed,
+                    update_handle,
+                    update_label,
+                    restyle.run_if(resource_changed::<Theme>),
+                ),
+            );
+    }
+}
+
+fn init(
+    trigger: Trigger<OnAdd, Slider>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    mut sliders: Query<(&mut Node, &mut BackgroundColor)>,
+) {
+    let (mut node, mut background_color) = sliders.get_mut(trigger.entity()).unwrap();
+    node.width = theme.slider.width;
+    node.height = theme.slider.height;
+    node.align_items = AlignItems::Center;
+    *background_color = theme.slider.background_color;
+
+    commands.entity(trigger.entity()).with_child((
+        SliderHandle,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(theme.slider.handle_width),
+            height: theme.slider.handle_height,
+            margin: UiRect::left(Val::Px(-theme.slider.handle_width / 2.0)),
+            ..Default::default()
+        },
+        theme.slider.handle_color,
+    ));
+}
+
+fn init_label(trigger: Trigger<OnAdd, SliderLabel>, mut commands: Commands, theme: Res<Theme>) {
+    commands.entity(trigger.entity()).with_child((
+        SliderLabelText,
+        LabelKind::Small,
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(100.0),
+            margin: UiRect::left(theme.slider.label_margin),
+            ..Default::default()
+        },
+    ));
+}
+
+fn restyle(
+    theme: Res<Theme>,
+    mut sliders: Query<(&mut Node, &mut BackgroundColor), With<Slider>>,
+    mut handles: Query<(&mut Node, &mut BackgroundColor), (With<SliderHandle>, Without<Slider>)>,
+    mut labels: Query<
+        &mut Node,
+        (
+            With<SliderLabelText>,
+            Without<Slider>,
+            Without<SliderHandle>,
+        ),
+    >,
+) {
+    for (mut node, mut background_color) in &mut sliders {
+        node.width = theme.slider.width;
+        node.height = theme.slider.height;
+        *background_color = theme.slider.background_color;
+    }
+
+    for (mut node, mut handle_color) in &mut handles {
+        node.width = Val::Px(theme.slider.handle_width);
+        node.height = theme.slider.handle_height;
+        node.margin = UiRect::left(Val::Px(-theme.slider.handle_width / 2.0));
+        *handle_color = theme.slider.handle_color;
+    }
+
+    for mut node in &mut labels {
+        node.margin = UiRect::left(theme.slider.label_margin);
+    }
+}
+
+fn press(
+    trigger: Trigger<Pointer<Down>>,
+    mut sliders: Query<(&mut Slider, &SliderRange, &ComputedNode, &GlobalTransform)>,
+) {
+    if let Ok((mut slider, range, node, transform)) = sliders.get_mut(trigger.entity()) {
+        let fraction = pointer_fraction(node, transform, trigger.pointer_location.position);
+        slider.set_if_neq(Slider(range.value(fraction)));
+    }
+}
+
+fn drag(
+    mut trigger: Trigger<Pointer<Drag>>,
+    mut sliders: Query<(&mut Slider, &SliderRange, &ComputedNode, &GlobalTransform)>,
+) {
+    if let Ok((mut slider, range, node, transform)) = sliders.get_mut(trigger.entity()) {
+        // Prevent parent scroll views from scrolling.
+        trigger.propagate(false);
+        let fraction = pointer_fraction(node, transform, trigger.pointer_location.position);
+        slider.set_if_neq(Slider(range.value(fraction)));
+    }
+}
+
+/// Converts pointer position in logical pixels into fraction of the slider width.
+fn pointer_fraction(node: &ComputedNode, transform: &GlobalTransform, position: Vec2) -> f32 {
+    let width = node.size().x * node.inverse_scale_factor();
+    let center = transform.translation().x * node.inverse_scale_factor();
+    let left = center - width / 2.0;
+    ((position.x - left) / width).clamp(0.0, 1.0)
+}
+
+fn trigger_changed(mut commands: Commands, sliders: Query<(Entity, Ref<Slider>)>) {
+    for (entity, slider) in &sliders {
+        if slider.is_changed() && !slider.is_added() {
+            commands.trigger_targets(SliderChanged(slider.0), entity);
+        }
+    }
+}
+
+fn update_handle(
+    sliders: Query<(&Slider, &SliderRange, &Children), Or<(Changed<Slider>, Changed<SliderRange>)>>,
+    mut handles: Query<&mut Node, With<SliderHandle>>,
+) {
+    for (slider, range, children) in &sliders {
+        let mut iter = handles.iter_many_mut(children);
+        let mut node = iter.fetch_next().expect("slider should have child handle");
+        node.left = Val::Percent(range.fraction(slider.0) * 100.0);
+    }
+}
+
+fn update_label(
+    sliders: Query<
+        (&Slider, &SliderRange, &Children),
+        (
+            With<SliderLabel>,
+            Or<(Changed<Slider>, Changed<SliderRange>, Changed<Children>)>,
+        ),
+    >,
+    mut labels: Query<&mut Text, With<SliderLabelText>>,
+) {
+    for (slider, range, children) in &sliders {
+        let mut iter = labels.iter_many_mut(children);
+        if let Some(mut text) = iter.fetch_next() {
+            text.0 = format!("{:.*}", range.precision(), slider.0);
+        }
+    }
+}
+
+/// Horizontal slider with value inside [`SliderRange`].
+///
+/// Value can be set by clicking or dragging.
+#[derive(Component, Clone, Copy, PartialEq)]
+#[require(Node, Interaction, SliderRange)]
+pub struct Slider(pub f32);
+
+/// Allowed values for [`Slider`].
+///
+/// Defaults to a continuous range from 0 to 1.
+#[derive(Component, Clone, Copy)]
+pub struct SliderRange {
+    pub min: f32,
+    pub max: f32,
+    /// Value increment, `0.0` means continuous.
+    pub step: f32,
+}
+
+impl SliderRange {
+    pub fn new(min: f32, max: f32) -> Self {
+        Self {
+            min,
+            max,
+            step: 0.0,
+        }
+    }
+
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Converts fraction of the slider width into a value snapped to the step.
+    pub fn value(&self, fraction: f32) -> f32 {
+        let mut value = self.min + fraction * (self.max - self.min);
+        if self.step > 0.0 {
+            value = self.min + ((value - self.min) / self.step).round() * self.step;
+        }
+        value.clamp(self.min, self.max)
+    }
+
+    /// Converts value into fraction of the slider width.
+    pub fn fraction(&self, value: f32) -> f32 {
+        let length = self.max - self.min;
+        if length <= 0.0 {
+            return 0.0;
+        }
+        ((value - self.min) / length).clamp(0.0, 1.0)
+    }
+
+    /// Moves the value by the number of steps.
+    ///
+    /// Continuous ranges use `fallback_fraction` of the range length as a step.
+    pub fn step_by(&self, value: f32, steps: f32, fallback_fraction: f32) -> f32 {
+        let step = if self.step > 0.0 {
+            self.step
+        } else {
+            fallback_fraction * (self.max - self.min)
+        };
+        self.value(self.fraction(value + steps * step))
+    }
+
+    /// Returns the number of decimal places to display values.
+    fn precision(&self) -> usize {
+        if self.step > 0.0 {
+            (-self.step.log10()).ceil().max(0.0) as usize
+        } else {
+            2
+        }
+    }
+}
+
+impl Default for SliderRange {
+    fn default() -> Self {
+        Self::new(0.0, 1.0)
+    }
+}
+
+/// Displays the value of [`Slider`] to the right of it.
+#[derive(Component)]
+pub struct SliderLabel;
+
+/// Triggered on [`Slider`] when its value changes after spawn.
+#[derive(Event)]
+pub struct SliderChanged(pub f32);
+
+#[derive(Component)]
+struct SliderHandle;
+
+#[derive(Component)]
+struct SliderLabelText;
@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+
+use super::button::{ButtonKind, TabContent, Toggled};
+
+pub(super) struct TabViewPlugin;
+
+impl Plugin for TabViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(init)
+            .add_observer(remove_button)
+            .add_systems(Update, add_buttons)
+            .add_systems(PostUpdate, update_active);
+    }
+}
+
+fn init(trigger: Trigger<OnAdd, TabView>, mut commands: Commands, mut views: Query<&mut Node>) {
+    let mut node = views.get_mut(trigger.entity()).unwrap();
+    node.flex_direction = FlexDirection::Column;
+    node.align_items = AlignItems::Center;
+
+    let bar_entity = commands
+        .spawn((
+            TabBar,
+            Node {
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+        ))
+        .id();
+
+    // Insert at the beginning because tabs could be already added.
+    commands
+        .entity(trigger.entity())
+        .insert_children(0, &[bar_entity]);
+}
+
+/// Creates a button in the tab bar for each new tab.
+///
+/// The first tab of a view becomes active.
+fn add_buttons(
+    mut commands: Commands,
+    views: Query<&Children, (With<TabView>, Changed<Children>)>,
+    bars: Query<(), With<TabBar>>,
+    tabs: Query<(Entity, &Tab, Option<&TabButton>)>,
+) {
+    for children in &views {
+        let Some(&bar_entity) = children.iter().find(|&&entity| bars.get(entity).is_ok()) else {
+            continue;
+        };
+
+        let mut has_buttons = tabs
+            .iter_many(children)
+            .any(|(.., button)| button.is_some());
+        for (tab_entity, tab, button) in tabs.iter_many(children) {
+            if button.is_some() {
+                continue;
+            }
+
+            debug!("adding button for tab `{tab_entity}`");
+            let button_entity = commands
+                .spawn((
+                    TabViewButton,
+                    ButtonKind::Normal,
+                    TabContent(tab_entity),
+                    Toggled(!has_buttons),
+                ))
+                .with_child(Text::new(tab.0.clone()))
+                .set_parent(bar_entity)
+                .id();
+            commands.entity(tab_entity).insert(TabButton(button_entity));
+            has_buttons = true;
+        }
+    }
+}
+
+fn remove_button(trigger: Trigger<OnRemove, Tab>, mut commands: Commands, tabs: Query<&TabButton>) {
+    if let Ok(button) = tabs.get(trigger.entity()) {
+        if let Some(entity) = commands.get_entity(**button) {
+            entity.despawn_recursive();
+        }
+    }
+}
+
+fn update_active(
+    mut commands: Commands,
+    buttons: Query<(Ref<Toggled>, &TabContent, &Parent), With<TabViewButton>>,
+    parents: Query<&Parent>,
+) {
+    for (toggled, &tab_content, bar_parent) in &buttons {
+        if !toggled.is_changed() {
+            continue;
+        }
+
+        if !**toggled {
+            commands.entity(*tab_content).remove::<ActiveTab>();
+            continue;
+        }
+
+        commands.entity(*tab_content).insert(ActiveTab);
+        if !toggled.is_added() {
+            let view_entity = **parents
+                .get(**bar_parent)
+                .expect("tab bar should be inside a view");
+            debug!("switching to tab `{}` in `{view_entity}`", *tab_content);
+            commands.trigger_targets(TabChanged(*tab_content), view_entity);
+        }
+    }
+}
+
+/// Container that displays one of its [`Tab`] children at a time.
+///
+/// Adds a bar with a button for each tab to switch between them.
+#[derive(Component)]
+#[require(Node)]
+pub struct TabView;
+
+/// Content node inside [`TabView`] with the label for its button.
+#[derive(Component)]
+#[require(Node)]
+pub struct Tab(pub String);
+
+/// Marks the currently displayed [`Tab`].
+#[derive(Component)]
+pub struct ActiveTab;
+
+/// Triggered on [`TabView`] when the user switches to another tab.
+///
+/// Contains the entity of the new active [`Tab`].
+#[derive(Event, Clone, Copy)]
+pub struct TabChanged(pub Entity);
+
+#[derive(Component)]
+struct TabBar;
+
+#[derive(Component)]
+struct TabViewButton;
+
+/// Button that switches to the tab.
+#[derive(Component, Deref)]
+struct TabButton(Entity);
@@ -1,18 +1,44 @@
-use bevy::prelude::*;
+use std::ops::Range;
+
+use arboard::Clipboard;
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        InputSystem,
+    },
+    prelude::*,
+    text::{ComputedTextBlock, TextLayoutInfo},
+    ui::{RelativeCursorPosition, UiSystem},
+    window::{Ime, PrimaryWindow},
+};
 use bevy_simple_text_input::{
-    TextInput, TextInputCursorPos, TextInputInactive, TextInputTextColor, TextInputTextFont,
-    TextInputValue,
+    TextInput, TextInputCursorPos, TextInputInactive, TextInputSettings, TextInputSubmitEvent,
+    TextInputTextColor, TextInputTextFont, TextInputValue,
 };
 
 use super::theme::Theme;
 
-/// Adds focus functionality to `bevy_simple_text_input`.
+/// Adds focus, selection, clipboard and IME functionality to `bevy_simple_text_input`.
 pub(super) struct TextEditPlugin;
 
 impl Plugin for TextEditPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(theme)
-            .add_systems(PostUpdate, update_border_colors);
+        app.init_non_send_resource::<SystemClipboard>()
+            .add_observer(theme)
+            .add_systems(PreUpdate, (edit, commit_ime).chain().after(InputSystem))
+            .add_systems(Update, deactivate)
+            .add_systems(
+                PostUpdate,
+                (
+                    restyle.run_if(resource_changed::<Theme>),
+                    update_border_colors,
+                    clear_selection,
+                    update_ime,
+                    update_selection
+                        .after(UiSystem::PostLayout)
+                        .after(TransformSystem::TransformPropagate),
+                ),
+            );
     }
 }
 
@@ -30,6 +56,7 @@ fn theme(
         &TextInputValue,
     )>,
     other_edits: Query<(), With<TextEdit>>,
+    manual_edits: Query<(), With<ManualActivation>>,
 ) {
     let (
         mut node,
@@ -41,6 +68,41 @@ fn theme(
         text,
     ) = text.get_mut(trigger.entity()).unwrap();
 
+    set_style(
+        &mut node,
+        &mut background,
+        &mut text_color,
+        &mut text_font,
+        &theme,
+    );
+    cursor_pos.0 = text.0.chars().count();
+
+    // Activate if the input is single.
+    // TODO 0.16: iterate only onver neighbors when hierarchy will be available.
+    inactive.0 = manual_edits.get(trigger.entity()).is_ok() || other_edits.get_single().is_err();
+    commands
+        .entity(trigger.entity())
+        .observe(activate)
+        .with_child((
+            SelectionHighlight,
+            Node {
+                position_type: PositionType::Absolute,
+                display: Display::None,
+                ..Default::default()
+            },
+            ZIndex(-1),
+            PickingBehavior::IGNORE,
+            theme.text_edit.selection_color,
+        ));
+}
+
+fn set_style(
+    node: &mut Node,
+    background: &mut BackgroundColor,
+    text_color: &mut TextInputTextColor,
+    text_font: &mut TextInputTextFont,
+    theme: &Theme,
+) {
     node.min_width = theme.text_edit.min_width;
     node.border = theme.text_edit.border;
     node.padding = theme.text_edit.padding;
@@ -48,12 +110,43 @@ fn theme(
     text_font.0.font = theme.text_edit.font.clone();
     text_font.0.font_size = theme.text_edit.font_size;
     text_color.0 = theme.text_edit.text_color;
-    cursor_pos.0 = text.0.len();
+}
 
-    // Activate if the input is single.
-    // TODO 0.16: iterate only onver neighbors when hierarchy will be available.
-    inactive.0 = other_edits.get_single().is_err();
-    commands.entity(trigger.entity()).observe(activate);
+fn restyle(
+    theme: Res<Theme>,
+    mut highlights: Query<&mut BackgroundColor, (With<SelectionHighlight>, Without<TextEdit>)>,
+    mut text_edits: Query<
+        (
+            &mut Node,
+            &mut BackgroundColor,
+            &mut TextInputTextColor,
+            &mut TextInputTextFont,
+            &TextInputInactive,
+            &mut BorderColor,
+        ),
+        With<TextEdit>,
+    >,
+) {
+    for (mut node, mut background, mut text_color, mut text_font, inactive, mut border_color) in
+        &mut text_edits
+    {
+        set_style(
+            &mut node,
+            &mut background,
+            &mut text_color,
+            &mut text_font,
+            &theme,
+        );
+        *border_color = if inactive.0 {
+            theme.text_edit.inactive_border
+        } else {
+            theme.text_edit.active_border
+        };
+    }
+
+    for mut background in &mut highlights {
+        *background = theme.text_edit.selection_color;
+    }
 }
 
 fn activate(trigger: Trigger<Pointer<Click>>, mut text_inputs: Query<&mut TextInputInactive>) {
@@ -68,6 +161,29 @@ fn activate(trigger: Trigger<Pointer<Click>>, mut text_inputs: Query<&mut TextIn
     inactive.0 = false;
 }
 
+/// Deactivates edits with [`ManualActivation`] on click outside or Escape.
+fn deactivate(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut text_edits: Query<
+        (Entity, &mut TextInputInactive, &RelativeCursorPosition),
+        With<ManualActivation>,
+    >,
+) {
+    let clicked = mouse.just_pressed(MouseButton::Left);
+    let escaped = keys.just_pressed(KeyCode::Escape);
+    if !clicked && !escaped {
+        return;
+    }
+
+    for (entity, mut inactive, cursor_position) in &mut text_edits {
+        if !inactive.0 && (escaped || !cursor_position.mouse_over()) {
+            debug!("deactivating `{entity}`");
+            inactive.0 = true;
+        }
+    }
+}
+
 fn update_border_colors(
     theme: Res<Theme>,
     mut text_inputs: Query<(&TextInputInactive, &mut BorderColor), Changed<TextInputInactive>>,
@@ -81,6 +197,459 @@ fn update_border_colors(
     }
 }
 
+/// Handles keyboard input for the active text edit.
+///
+/// Takes keyboard events that the edit consumed to prevent `bevy_simple_text_input`
+/// from processing them again. Other events are sent back.
+fn edit(
+    mut keyboard_events: ResMut<Events<KeyboardInput>>,
+    mut submit_events: EventWriter<TextInputSubmitEvent>,
+    mut clipboard: NonSendMut<SystemClipboard>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut text_edits: Query<
+        (
+            Entity,
+            &mut TextInputValue,
+            &mut TextInputCursorPos,
+            &mut SelectionAnchor,
+            &TextInputSettings,
+            &TextInputInactive,
+        ),
+        With<TextEdit>,
+    >,
+) {
+    let Some((entity, mut text, mut cursor_pos, mut anchor, settings, _)) =
+        text_edits.iter_mut().find(|(.., inactive)| !inactive.0)
+    else {
+        return;
+    };
+    if keyboard_events.is_empty() {
+        return;
+    }
+
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let ctrl = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+
+    let mut state = EditState {
+        text: text.0.clone(),
+        cursor: cursor_pos.0,
+        anchor: anchor.0,
+    };
+    let mut unhandled = Vec::new();
+    let events: Vec<_> = keyboard_events.drain().collect();
+    for event in events {
+        if !event.state.is_pressed() {
+            unhandled.push(event);
+            continue;
+        }
+
+        match (&event.logical_key, event.key_code) {
+            (_, KeyCode::ArrowLeft) => {
+                let pos = match state.selection() {
+                    _ if ctrl => state.prev_word(),
+                    Some(selection) if !shift => selection.start,
+                    _ => state.cursor.saturating_sub(1),
+                };
+                state.move_cursor(pos, shift);
+            }
+            (_, KeyCode::ArrowRight) => {
+                let pos = match state.selection() {
+                    _ if ctrl => state.next_word(),
+                    Some(selection) if !shift => selection.end,
+                    _ => (state.cursor + 1).min(state.len()),
+                };
+                state.move_cursor(pos, shift);
+            }
+            (_, KeyCode::Home) => state.move_cursor(0, shift),
+            (_, KeyCode::End) => state.move_cursor(state.len(), shift),
+            (_, KeyCode::Backspace) => {
+                if state.selection().is_none() {
+                    let pos = if ctrl {
+                        state.prev_word()
+                    } else {
+                        state.cursor.saturating_sub(1)
+                    };
+                    state.move_cursor(pos, true);
+                }
+                state.insert("");
+            }
+            (_, KeyCode::Delete) => {
+                if state.selection().is_none() {
+                    let pos = if ctrl {
+                        state.next_word()
+                    } else {
+                        (state.cursor + 1).min(state.len())
+                    };
+                    state.move_cursor(pos, true);
+                }
+                state.insert("");
+            }
+            (_, KeyCode::Enter | KeyCode::NumpadEnter) => {
+                debug!("submitting `{entity}`");
+                submit_events.send(TextInputSubmitEvent {
+                    entity,
+                    value: state.text.clone(),
+                });
+                if !settings.retain_on_submit {
+                    state = Default::default();
+                }
+            }
+            (_, KeyCode::KeyA) if ctrl => {
+                state.anchor = Some(0);
+                state.cursor = state.len();
+            }
+            (_, KeyCode::KeyC | KeyCode::KeyX) if ctrl => {
+                // Don't leak hidden text like passwords.
+                if settings.mask_character.is_some() {
+                    continue;
+                }
+                let Some(selected) = state.selected_text() else {
+                    continue;
+                };
+                if let Some(clipboard) = &mut clipboard.0 {
+                    if let Err(e) = clipboard.set_text(selected) {
+                        error!("unable to copy text: {e}");
+                        continue;
+                    }
+                }
+                if event.key_code == KeyCode::KeyX {
+                    state.insert("");
+                }
+            }
+            (_, KeyCode::KeyV) if ctrl => {
+                let Some(clipboard) = &mut clipboard.0 else {
+                    continue;
+                };
+                match clipboard.get_text() {
+                    Ok(pasted) => {
+                        // Edits are single-line.
+                        let pasted: String = pasted.chars().filter(|c| !c.is_control()).collect();
+                        state.insert(&pasted);
+                    }
+                    Err(e) => error!("unable to paste text: {e}"),
+                }
+            }
+            (Key::Space, _) => state.insert(" "),
+            (Key::Character(chars), _) if !ctrl => state.insert(chars),
+            _ => unhandled.push(event),
+        }
+    }
+
+    for event in unhandled {
+        keyboard_events.send(event);
+    }
+
+    if text.0 != state.text {
+        text.0 = state.text;
+    }
+    if cursor_pos.0 != state.cursor {
+        cursor_pos.0 = state.cursor;
+    }
+    anchor.set_if_neq(SelectionAnchor(state.anchor));
+}
+
+/// Inserts text composed with an input method into the active text edit.
+fn commit_ime(
+    mut ime_events: EventReader<Ime>,
+    mut text_edits: Query<
+        (
+            &mut TextInputValue,
+            &mut TextInputCursorPos,
+            &mut SelectionAnchor,
+            &TextInputInactive,
+        ),
+        With<TextEdit>,
+    >,
+) {
+    for event in ime_events.read() {
+        let Ime::Commit { value, .. } = event else {
+            continue;
+        };
+        let Some((mut text, mut cursor_pos, mut anchor, _)) =
+            text_edits.iter_mut().find(|(.., inactive)| !inactive.0)
+        else {
+            continue;
+        };
+
+        let mut state = EditState {
+            text: text.0.clone(),
+            cursor: cursor_pos.0,
+            anchor: anchor.0,
+        };
+        state.insert(value);
+        text.0 = state.text;
+        cursor_pos.0 = state.cursor;
+        anchor.0 = state.anchor;
+    }
+}
+
+/// Enables input methods only while a text edit is active and places the candidate window under it.
+fn update_ime(
+    ui_scale: Res<UiScale>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+    text_edits: Query<(&TextInputInactive, &ComputedNode, &GlobalTransform), With<TextEdit>>,
+) {
+    let active_edit = text_edits.iter().find(|(inactive, ..)| !inactive.0);
+    let ime_enabled = active_edit.is_some();
+    if window.ime_enabled != ime_enabled {
+        debug!("setting IME to {ime_enabled}");
+        window.ime_enabled = ime_enabled;
+    }
+
+    if let Some((_, node, transform)) = active_edit {
+        let bottom_left = transform.translation().xy() + node.size() * Vec2::new(-0.5, 0.5);
+        let position = bottom_left * node.inverse_scale_factor() * ui_scale.0;
+        if window.ime_position != position {
+            window.ime_position = position;
+        }
+    }
+}
+
+/// Removes the selection from deactivated edits and keeps it in bounds after text changes from code.
+fn clear_selection(
+    mut text_edits: Query<
+        (&TextInputValue, &TextInputInactive, &mut SelectionAnchor),
+        Or<(Changed<TextInputValue>, Changed<TextInputInactive>)>,
+    >,
+) {
+    for (text, inactive, mut anchor) in &mut text_edits {
+        let Some(pos) = anchor.0 else {
+            continue;
+        };
+        if inactive.0 || pos > text.0.chars().count() {
+            anchor.0 = None;
+        }
+    }
+}
+
+/// Places the highlight under selected characters.
+///
+/// Uses the layout of the displayed text, which also contains the cursor character.
+fn update_selection(
+    text_edits: Query<
+        (
+            Entity,
+            &TextInputValue,
+            &TextInputCursorPos,
+            &SelectionAnchor,
+            &ComputedNode,
+            &GlobalTransform,
+        ),
+        With<TextEdit>,
+    >,
+    children: Query<&Children>,
+    layouts: Query<(
+        &TextLayoutInfo,
+        &ComputedTextBlock,
+        &ComputedNode,
+        &GlobalTransform,
+        &ViewVisibility,
+    )>,
+    spans: Query<AnyOf<(&Text, &TextSpan)>>,
+    mut highlights: Query<&mut Node, With<SelectionHighlight>>,
+) {
+    for (entity, text, cursor_pos, anchor, edit_node, edit_transform) in &text_edits {
+        let mut iter = highlights.iter_many_mut(children.get(entity).into_iter().flatten());
+        let Some(mut highlight) = iter.fetch_next() else {
+            continue;
+        };
+
+        let Some(anchor) = anchor.0.filter(|&anchor| anchor != cursor_pos.0) else {
+            highlight.display = Display::None;
+            continue;
+        };
+        let selection = anchor.min(cursor_pos.0)..anchor.max(cursor_pos.0);
+
+        let Some((layout, text_block, text_node, text_transform)) = children
+            .iter_descendants(entity)
+            .filter_map(|entity| layouts.get(entity).ok())
+            .find(|&(.., visibility)| visibility.get())
+            .map(|(layout, text_block, node, transform, _)| (layout, text_block, node, transform))
+        else {
+            continue;
+        };
+
+        // Glyphs are indexed by span, collect span offsets to map them to characters.
+        let mut displayed = String::new();
+        let mut span_offsets = Vec::new();
+        for text_entity in text_block.entities() {
+            span_offsets.push(displayed.len());
+            let Ok((text, span)) = spans.get(text_entity.entity) else {
+                continue;
+            };
+            if let Some(value) = text.map(|text| &text.0).or(span.map(|span| &span.0)) {
+                displayed += value;
+            }
+        }
+        let cursor_chars = displayed
+            .chars()
+            .count()
+            .saturating_sub(text.0.chars().count());
+
+        let mut min_x = f32::MAX;
+        let mut max_x = f32::MIN;
+        for glyph in &layout.glyphs {
+            let Some(prefix) = span_offsets
+                .get(glyph.span_index)
+                .and_then(|offset| displayed.get(..offset + glyph.byte_index))
+            else {
+                continue;
+            };
+            let char_index = prefix.chars().count();
+            let char_index = if char_index < cursor_pos.0 {
+                char_index
+            } else if char_index < cursor_pos.0 + cursor_chars {
+                continue;
+            } else {
+                char_index - cursor_chars
+            };
+
+            if selection.contains(&char_index) {
+                min_x = min_x.min(glyph.position.x - glyph.size.x / 2.0);
+                max_x = max_x.max(glyph.position.x + glyph.size.x / 2.0);
+            }
+        }
+        if min_x > max_x {
+            highlight.display = Display::None;
+            continue;
+        }
+
+        // Convert from text to edit coordinates.
+        let text_min = text_transform.translation().xy() - text_node.size() / 2.0;
+        let edit_min = edit_transform.translation().xy() - edit_node.size() / 2.0;
+        let offset = text_min - edit_min;
+        let border = edit_node.border();
+        let scale = edit_node.inverse_scale_factor();
+
+        highlight.display = Display::Flex;
+        highlight.left = Val::Px((offset.x + min_x - border.left) * scale);
+        highlight.top = Val::Px((offset.y - border.top) * scale);
+        highlight.width = Val::Px((max_x - min_x) * scale);
+        highlight.height = Val::Px(text_node.size().y * scale);
+    }
+}
+
 #[derive(Component, Default)]
-#[require(TextInput, TextInputCursorPos)]
+#[require(TextInput, TextInputCursorPos, SelectionAnchor)]
 pub struct TextEdit;
+
+/// Keeps [`TextEdit`] inactive on spawn even if it's the only edit.
+///
+/// Deactivates the edit on click outside or Escape. Useful for edits outside dialogs,
+/// where typing shouldn't be captured until the user clicks on them.
+#[derive(Component, Default)]
+#[require(TextEdit, RelativeCursorPosition)]
+pub struct ManualActivation;
+
+/// Position where the selection starts.
+///
+/// Selection spans from this position to the cursor.
+#[derive(Component, Default, Clone, Copy, PartialEq)]
+struct SelectionAnchor(Option<usize>);
+
+#[derive(Component)]
+struct SelectionHighlight;
+
+/// System clipboard for copy and paste.
+///
+/// Could be unavailable, for example, on headless systems.
+struct SystemClipboard(Option<Clipboard>);
+
+impl Default for SystemClipboard {
+    fn default() -> Self {
+        let clipboard = Clipboard::new()
+            .inspect_err(|e| error!("unable to access clipboard: {e}"))
+            .ok();
+        Self(clipboard)
+    }
+}
+
+/// Text with a cursor and selection in characters.
+#[derive(Default)]
+struct EditState {
+    text: String,
+    cursor: usize,
+    anchor: Option<usize>,
+}
+
+impl EditState {
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Returns the selected character range if it's not empty.
+    fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        let range = anchor.min(self.cursor)..anchor.max(self.cursor);
+        (!range.is_empty()).then_some(range)
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        let selection = self.selection()?;
+        let text = self
+            .text
+            .chars()
+            .skip(selection.start)
+            .take(selection.len())
+            .collect();
+        Some(text)
+    }
+
+    /// Moves the cursor, extending the selection if `select` is set or dropping it otherwise.
+    fn move_cursor(&mut self, pos: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = pos;
+    }
+
+    /// Replaces the selection with the text or inserts it at the cursor.
+    fn insert(&mut self, text: &str) {
+        let range = self.selection().unwrap_or(self.cursor..self.cursor);
+        let start = self.byte_index(range.start);
+        let end = self.byte_index(range.end);
+        self.text.replace_range(start..end, text);
+        self.cursor = range.start + text.chars().count();
+        self.anchor = None;
+    }
+
+    /// Returns the start of the word before the cursor.
+    fn prev_word(&self) -> usize {
+        let chars: Vec<_> = self.text.chars().take(self.cursor).collect();
+        let mut pos = chars.len();
+        while pos > 0 && !chars[pos - 1].is_alphanumeric() {
+            pos -= 1;
+        }
+        while pos > 0 && chars[pos - 1].is_alphanumeric() {
+            pos -= 1;
+        }
+        pos
+    }
+
+    /// Returns the end of the word after the cursor.
+    fn next_word(&self) -> usize {
+        let mut chars = self.text.chars().skip(self.cursor).peekable();
+        let mut pos = self.cursor;
+        while chars.next_if(|c| !c.is_alphanumeric()).is_some() {
+            pos += 1;
+        }
+        while chars.next_if(|c| c.is_alphanumeric()).is_some() {
+            pos += 1;
+        }
+        pos
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.text.len(), |(index, _)| index)
+    }
+}
@@ -1,11 +1,24 @@
-use bevy::prelude::*;
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    scene::ron,
+};
+use serde::{de, Deserialize, Deserializer};
 
+/// Provides the default theme and loads themes from `.theme.ron` files.
+///
+/// Replacing or modifying the [`Theme`] resource restyles already spawned widgets.
 pub(super) struct ThemePlugin;
 
 impl Plugin for ThemePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Theme>()
-            .add_systems(Startup, set_clear_color);
+            .init_asset::<Theme>()
+            .init_asset_loader::<ThemeLoader>()
+            .add_systems(
+                PostUpdate,
+                set_clear_color.run_if(resource_changed::<Theme>),
+            );
     }
 }
 
@@ -13,48 +26,99 @@ fn set_clear_color(mut commands: Commands, theme: Res<Theme>) {
     commands.insert_resource(ClearColor(theme.background_color.0));
 }
 
-#[derive(Resource)]
+#[derive(Default)]
+struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    type Asset = Theme;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data).await?;
+
+        let definition: ThemeDefinition = ron::from_str(&data)?;
+        let text_font = load_context.load(definition.text_font.clone());
+        let symbol_font = load_context.load(definition.symbol_font.clone());
+
+        Ok(Theme::new(&definition, text_font, symbol_font))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
+
+#[derive(Asset, Clone, Resource, TypePath)]
 pub struct Theme {
     pub button: ButtonTheme,
     pub label: LabelTheme,
     pub checkbox: CheckboxTheme,
     pub text_edit: TextEditTheme,
     pub progress_bar: ProgressBarTheme,
+    pub line_chart: LineChartTheme,
+    pub slider: SliderTheme,
+    pub scroll_view: ScrollViewTheme,
+    pub tooltip: TooltipTheme,
+    pub color_picker: ColorPickerTheme,
+    pub notification: NotificationTheme,
+    pub focus_outline: Outline,
     pub gap: GapTheme,
     pub padding: PaddingTheme,
     pub modal_background: BackgroundColor,
     pub popup_background: BackgroundColor,
     pub panel_background: BackgroundColor,
     pub background_color: BackgroundColor,
+
+    /// Multiplier applied to all font sizes.
+    text_scale: f32,
 }
 
 impl FromWorld for Theme {
     fn from_world(world: &mut World) -> Self {
+        let definition = ThemeDefinition::default();
         let asset_server = world.resource::<AssetServer>();
-        let text_handle = asset_server.load("base/fonts/FiraSans-Bold.ttf");
-        let symbol_handle = asset_server.load("base/fonts/NotoEmoji-Regular.ttf");
+        let text_font = asset_server.load(&definition.text_font);
+        let symbol_font = asset_server.load(&definition.symbol_font);
+        Self::new(&definition, text_font, symbol_font)
+    }
+}
+
+impl Theme {
+    fn new(
+        definition: &ThemeDefinition,
+        text_font: Handle<Font>,
+        symbol_font: Handle<Font>,
+    ) -> Self {
+        let colors = &definition.colors;
         Self {
             button: ButtonTheme {
                 normal: TextButtonTheme {
                     width: Val::Px(160.0),
                     height: Val::Px(35.0),
-                    font: text_handle.clone(),
+                    font: text_font.clone(),
                     font_size: 20.0,
-                    color: Color::srgb(0.9, 0.9, 0.9).into(),
+                    color: colors.button_text.0.into(),
                 },
                 large: TextButtonTheme {
                     width: Val::Px(180.0),
                     height: Val::Px(50.0),
-                    font: text_handle.clone(),
+                    font: text_font.clone(),
                     font_size: 25.0,
-                    color: Color::srgb(0.9, 0.9, 0.9).into(),
+                    color: colors.button_text.0.into(),
                 },
                 symbol: TextButtonTheme {
                     width: Val::Px(35.0),
                     height: Val::Px(35.0),
-                    font: symbol_handle.clone(),
+                    font: symbol_font.clone(),
                     font_size: 20.0,
-                    color: Color::srgb(0.9, 0.9, 0.9).into(),
+                    color: colors.button_text.0.into(),
                 },
                 image: ImageButtonTheme {
                     width: Val::Px(55.0),
@@ -62,31 +126,32 @@ impl FromWorld for Theme {
                     image_width: Val::Px(45.0),
                     image_height: Val::Px(45.0),
                 },
-                normal_background: Color::srgb(0.15, 0.15, 0.15).into(),
-                hovered_background: Color::srgb(0.25, 0.25, 0.25).into(),
-                pressed_background: Color::srgb(0.35, 0.75, 0.35).into(),
-                hovered_pressed_background: Color::srgb(0.25, 0.65, 0.25).into(),
+                normal_background: colors.button_normal.0.into(),
+                hovered_background: colors.button_hovered.0.into(),
+                pressed_background: colors.button_pressed.0.into(),
+                hovered_pressed_background: colors.button_hovered_pressed.0.into(),
+                disabled_background: colors.button_disabled.0.into(),
             },
             label: LabelTheme {
                 small: LabelTextTheme {
-                    font: text_handle.clone(),
+                    font: text_font.clone(),
                     font_size: 12.0,
-                    color: Color::srgb(0.1, 0.1, 0.1).into(),
+                    color: colors.label.0.into(),
                 },
                 normal: LabelTextTheme {
-                    font: text_handle.clone(),
+                    font: text_font.clone(),
                     font_size: 20.0,
-                    color: Color::srgb(0.1, 0.1, 0.1).into(),
+                    color: colors.label.0.into(),
                 },
                 large: LabelTextTheme {
-                    font: text_handle.clone(),
+                    font: text_font.clone(),
                     font_size: 30.0,
-                    color: Color::srgb(0.1, 0.1, 0.1).into(),
+                    color: colors.label.0.into(),
                 },
                 symbol: LabelTextTheme {
-                    font: symbol_handle,
+                    font: symbol_font,
                     font_size: 15.0,
-                    color: Color::srgb(0.1, 0.1, 0.1).into(),
+                    color: colors.label.0.into(),
                 },
             },
             checkbox: CheckboxTheme {
@@ -95,39 +160,102 @@ impl FromWorld for Theme {
                 button_height: Val::Px(20.0),
                 tick_width: Val::Px(14.0),
                 tick_height: Val::Px(14.0),
-                tick_color: Color::srgb(0.35, 0.75, 0.35).into(),
+                tick_color: colors.accent.0.into(),
             },
             text_edit: TextEditTheme {
                 min_width: Val::Px(200.0),
                 border: UiRect::all(Val::Px(5.0)),
                 padding: UiRect::all(Val::Px(5.0)),
-                font: text_handle,
+                font: text_font,
                 font_size: 20.0,
-                text_color: Color::srgb(0.9, 0.9, 0.9).into(),
-                background_color: Color::srgb(0.15, 0.15, 0.15).into(),
-                active_border: Color::srgb(0.35, 0.75, 0.35).into(),
-                inactive_border: Color::srgb(0.35, 0.35, 0.35).into(),
+                text_color: colors.text_edit_text.0.into(),
+                background_color: colors.text_edit_background.0.into(),
+                active_border: colors.accent.0.into(),
+                inactive_border: colors.text_edit_border.0.into(),
+                selection_color: colors.accent.0.with_alpha(0.4).into(),
             },
             progress_bar: ProgressBarTheme {
-                background_color: Color::srgb(0.5, 0.5, 0.5).into(),
-                fill_color: Color::srgb(0.35, 0.75, 0.35).into(),
+                background_color: colors.track.0.into(),
+                fill_color: colors.accent.0.into(),
+                warning_color: colors.warning.0.into(),
+                critical_color: colors.error.0.into(),
+                marker_width: 2.0,
+                marker_color: colors.label.0.into(),
+            },
+            line_chart: LineChartTheme {
+                point_size: 4.0,
+                background_color: colors.track.0.into(),
+                point_color: colors.accent.0.into(),
+            },
+            slider: SliderTheme {
+                width: Val::Px(200.0),
+                height: Val::Px(8.0),
+                handle_width: 14.0,
+                handle_height: Val::Px(20.0),
+                background_color: colors.track.0.into(),
+                handle_color: colors.accent.0.into(),
+                label_margin: Val::Px(definition.normal_gap),
+            },
+            scroll_view: ScrollViewTheme {
+                thumb_width: Val::Px(6.0),
+                thumb_color: colors.track.0.into(),
+            },
+            tooltip: TooltipTheme {
+                max_width: Val::Px(300.0),
+                padding: UiRect::all(Val::Px(definition.normal_padding)),
+                cursor_offset: Vec2::new(16.0, 16.0),
+                background_color: colors.popup_background.0.into(),
+            },
+            color_picker: ColorPickerTheme {
+                swatch_width: Val::Px(200.0),
+                swatch_height: Val::Px(40.0),
             },
+            notification: NotificationTheme {
+                width: Val::Px(300.0),
+                border: UiRect::left(Val::Px(5.0)),
+                padding: UiRect::all(Val::Px(definition.normal_padding)),
+                background_color: colors.popup_background.0.into(),
+                info_border: colors.accent.0.into(),
+                warning_border: colors.warning.0.into(),
+                error_border: colors.error.0.into(),
+            },
+            focus_outline: Outline::new(Val::Px(3.0), Val::Px(2.0), colors.accent.0),
             gap: GapTheme {
-                normal: Val::Px(10.0),
-                large: Val::Px(20.0),
+                normal: Val::Px(definition.normal_gap),
+                large: Val::Px(definition.large_gap),
             },
             padding: PaddingTheme {
-                normal: UiRect::all(Val::Px(8.0)),
-                global: UiRect::all(Val::Px(15.0)),
+                normal: UiRect::all(Val::Px(definition.normal_padding)),
+                global: UiRect::all(Val::Px(definition.global_padding)),
             },
-            modal_background: Color::srgba(1.0, 1.0, 1.0, 0.3).into(),
-            popup_background: Color::srgb(0.75, 0.75, 0.75).into(),
-            panel_background: Color::srgb(0.8, 0.8, 0.8).into(),
-            background_color: Color::srgb(0.9, 0.9, 0.9).into(),
+            modal_background: colors.modal_background.0.into(),
+            popup_background: colors.popup_background.0.into(),
+            panel_background: colors.panel_background.0.into(),
+            background_color: colors.background.0.into(),
+            text_scale: 1.0,
         }
     }
+
+    /// Scales all font sizes relative to their default values.
+    pub fn set_text_scale(&mut self, scale: f32) {
+        let factor = scale / self.text_scale;
+        for font_size in [
+            &mut self.button.normal.font_size,
+            &mut self.button.large.font_size,
+            &mut self.button.symbol.font_size,
+            &mut self.label.small.font_size,
+            &mut self.label.normal.font_size,
+            &mut self.label.large.font_size,
+            &mut self.label.symbol.font_size,
+            &mut self.text_edit.font_size,
+        ] {
+            *font_size *= factor;
+        }
+        self.text_scale = scale;
+    }
 }
 
+#[derive(Clone)]
 pub struct ButtonTheme {
     pub normal: TextButtonTheme,
     pub large: TextButtonTheme,
@@ -137,8 +265,10 @@ pub struct ButtonTheme {
     pub hovered_background: BackgroundColor,
     pub pressed_background: BackgroundColor,
     pub hovered_pressed_background: BackgroundColor,
+    pub disabled_background: BackgroundColor,
 }
 
+#[derive(Clone)]
 pub struct TextButtonTheme {
     pub width: Val,
     pub height: Val,
@@ -147,6 +277,7 @@ pub struct TextButtonTheme {
     pub color: TextColor,
 }
 
+#[derive(Clone)]
 pub struct ImageButtonTheme {
     pub width: Val,
     pub height: Val,
@@ -154,6 +285,7 @@ pub struct ImageButtonTheme {
     pub image_height: Val,
 }
 
+#[derive(Clone)]
 pub struct LabelTheme {
     pub small: LabelTextTheme,
     pub normal: LabelTextTheme,
@@ -161,12 +293,14 @@ pub struct LabelTheme {
     pub symbol: LabelTextTheme,
 }
 
+#[derive(Clone)]
 pub struct LabelTextTheme {
     pub font: Handle<Font>,
     pub font_size: f32,
     pub color: TextColor,
 }
 
+#[derive(Clone)]
 pub struct CheckboxTheme {
     pub column_gap: Val,
     pub button_width: Val,
@@ -176,6 +310,7 @@ pub struct CheckboxTheme {
     pub tick_color: BackgroundColor,
 }
 
+#[derive(Clone)]
 pub struct TextEditTheme {
     pub min_width: Val,
     pub border: UiRect,
@@ -186,19 +321,179 @@ pub struct TextEditTheme {
     pub background_color: BackgroundColor,
     pub inactive_border: BorderColor,
     pub active_border: BorderColor,
+    pub selection_color: BackgroundColor,
 }
 
+#[derive(Clone)]
 pub struct ProgressBarTheme {
     pub background_color: BackgroundColor,
     pub fill_color: BackgroundColor,
+    pub warning_color: BackgroundColor,
+    pub critical_color: BackgroundColor,
+    pub marker_width: f32,
+    pub marker_color: BackgroundColor,
+}
+
+#[derive(Clone)]
+pub struct LineChartTheme {
+    /// Stored in pixels to center points on their values.
+    pub point_size: f32,
+    pub background_color: BackgroundColor,
+    pub point_color: BackgroundColor,
 }
 
+#[derive(Clone)]
+pub struct SliderTheme {
+    pub width: Val,
+    pub height: Val,
+    /// Stored in pixels to center the handle on the value.
+    pub handle_width: f32,
+    pub handle_height: Val,
+    pub background_color: BackgroundColor,
+    pub handle_color: BackgroundColor,
+    /// Distance between the slider and its value label.
+    pub label_margin: Val,
+}
+
+#[derive(Clone)]
+pub struct ScrollViewTheme {
+    pub thumb_width: Val,
+    pub thumb_color: BackgroundColor,
+}
+
+#[derive(Clone)]
+pub struct TooltipTheme {
+    /// Longer text wraps.
+    pub max_width: Val,
+    pub padding: UiRect,
+    /// Position relative to the cursor in logical pixels.
+    pub cursor_offset: Vec2,
+    pub background_color: BackgroundColor,
+}
+
+#[derive(Clone)]
+pub struct ColorPickerTheme {
+    pub swatch_width: Val,
+    pub swatch_height: Val,
+}
+
+#[derive(Clone)]
+pub struct NotificationTheme {
+    pub width: Val,
+    /// Colored by the notification kind.
+    pub border: UiRect,
+    pub padding: UiRect,
+    pub background_color: BackgroundColor,
+    pub info_border: BorderColor,
+    pub warning_border: BorderColor,
+    pub error_border: BorderColor,
+}
+
+#[derive(Clone)]
 pub struct GapTheme {
     pub normal: Val,
     pub large: Val,
 }
 
+#[derive(Clone)]
 pub struct PaddingTheme {
     pub normal: UiRect,
     pub global: UiRect,
 }
+
+/// Customizable part of the theme.
+///
+/// Font paths are relative to the assets directory.
+#[derive(Deserialize)]
+#[serde(default)]
+struct ThemeDefinition {
+    text_font: String,
+    symbol_font: String,
+    normal_gap: f32,
+    large_gap: f32,
+    normal_padding: f32,
+    global_padding: f32,
+    colors: ThemeColors,
+}
+
+impl Default for ThemeDefinition {
+    fn default() -> Self {
+        Self {
+            text_font: "base/fonts/FiraSans-Bold.ttf".to_string(),
+            symbol_font: "base/fonts/NotoEmoji-Regular.ttf".to_string(),
+            normal_gap: 10.0,
+            large_gap: 20.0,
+            normal_padding: 8.0,
+            global_padding: 15.0,
+            colors: Default::default(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct ThemeColors {
+    button_text: HexColor,
+    button_normal: HexColor,
+    button_hovered: HexColor,
+    button_pressed: HexColor,
+    button_hovered_pressed: HexColor,
+    button_disabled: HexColor,
+    label: HexColor,
+    /// Highlights checkbox ticks, active text edits, slider handles, progress, chart points and focus.
+    accent: HexColor,
+    text_edit_text: HexColor,
+    text_edit_background: HexColor,
+    text_edit_border: HexColor,
+    /// Background of sliders, progress bars, charts and scrollbar thumbs.
+    track: HexColor,
+    warning: HexColor,
+    error: HexColor,
+    modal_background: HexColor,
+    popup_background: HexColor,
+    panel_background: HexColor,
+    background: HexColor,
+}
+
+impl Default for ThemeColors {
+    fn default() -> Self {
+        Self {
+            button_text: Color::srgb(0.9, 0.9, 0.9).into(),
+            button_normal: Color::srgb(0.15, 0.15, 0.15).into(),
+            button_hovered: Color::srgb(0.25, 0.25, 0.25).into(),
+            button_pressed: Color::srgb(0.35, 0.75, 0.35).into(),
+            button_hovered_pressed: Color::srgb(0.25, 0.65, 0.25).into(),
+            button_disabled: Color::srgb(0.45, 0.45, 0.45).into(),
+            label: Color::srgb(0.1, 0.1, 0.1).into(),
+            accent: Color::srgb(0.35, 0.75, 0.35).into(),
+            text_edit_text: Color::srgb(0.9, 0.9, 0.9).into(),
+            text_edit_background: Color::srgb(0.15, 0.15, 0.15).into(),
+            text_edit_border: Color::srgb(0.35, 0.35, 0.35).into(),
+            track: Color::srgb(0.5, 0.5, 0.5).into(),
+            warning: Color::srgb(0.9, 0.65, 0.15).into(),
+            error: Color::srgb(0.85, 0.25, 0.25).into(),
+            modal_background: Color::srgba(1.0, 1.0, 1.0, 0.3).into(),
+            popup_background: Color::srgb(0.75, 0.75, 0.75).into(),
+            panel_background: Color::srgb(0.8, 0.8, 0.8).into(),
+            background: Color::srgb(0.9, 0.9, 0.9).into(),
+        }
+    }
+}
+
+/// Color written in hex notation, such as `"#1a1a1a"` or `"#ffffff4d"`.
+#[derive(Clone, Copy)]
+struct HexColor(Color);
+
+impl From<Color> for HexColor {
+    fn from(value: Color) -> Self {
+        Self(value)
+    }
+}
+
+impl<'de> Deserialize<'de> for HexColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        let color = Srgba::hex(&hex).map_err(de::Error::custom)?;
+        Ok(Self(color.into()))
+    }
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, ui::RelativeCursorPosition, window::PrimaryWindow};
+
+use super::{label::LabelKind, theme::Theme};
+
+/// Shows [`Tooltip`] text near the cursor after hovering the node for a while.
+pub(super) struct TooltipPlugin;
+
+impl Plugin for TooltipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TooltipHover>()
+            .add_systems(Update, (show, follow).chain())
+            .add_systems(PostUpdate, restyle.run_if(resource_changed::<Theme>));
+    }
+}
+
+/// Delay before showing a tooltip.
+const DELAY: Duration = Duration::from_millis(500);
+
+fn show(
+    mut commands: Commands,
+    mut hover: ResMut<TooltipHover>,
+    time: Res<Time>,
+    theme: Res<Theme>,
+    root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    popup_entity: Option<Single<Entity, With<TooltipPopup>>>,
+    tooltips: Query<(Entity, &Tooltip, &RelativeCursorPosition)>,
+) {
+    let hovered = tooltips
+        .iter()
+        .find(|(.., cursor_position)| cursor_position.mouse_over());
+    let hovered_entity = hovered.map(|(entity, ..)| entity);
+
+    if hover.entity != hovered_entity {
+        hover.entity = hovered_entity;
+        hover.timer.reset();
+        if let Some(popup_entity) = popup_entity {
+            commands.entity(*popup_entity).despawn_recursive();
+        }
+        return;
+    }
+
+    let Some((entity, tooltip, _)) = hovered else {
+        return;
+    };
+    if popup_entity.is_some() {
+        return;
+    }
+
+    hover.timer.tick(time.delta());
+    if !hover.timer.just_finished() {
+        return;
+    }
+
+    debug!("showing tooltip for `{entity}`");
+    commands.entity(*root_entity).with_children(|parent| {
+        parent
+            .spawn((
+                TooltipPopup,
+                Node {
+                    position_type: PositionType::Absolute,
+                    max_width: theme.tooltip.max_width,
+                    padding: theme.tooltip.padding,
+                    ..Default::default()
+                },
+                GlobalZIndex(i32::MAX),
+                PickingBehavior::IGNORE,
+                theme.tooltip.background_color,
+            ))
+            .with_child((LabelKind::Small, Text::new(tooltip.0.clone())));
+    });
+}
+
+fn follow(
+    theme: Res<Theme>,
+    ui_scale: Res<UiScale>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut popups: Query<&mut Node, With<TooltipPopup>>,
+) {
+    let Some(cursor_position) = window.cursor_position() else {
+        return;
+    };
+
+    for mut node in &mut popups {
+        let position = cursor_position / ui_scale.0 + theme.tooltip.cursor_offset;
+        node.left = Val::Px(position.x);
+        node.top = Val::Px(position.y);
+    }
+}
+
+fn restyle(
+    theme: Res<Theme>,
+    mut popups: Query<(&mut Node, &mut BackgroundColor), With<TooltipPopup>>,
+) {
+    for (mut node, mut background_color) in &mut popups {
+        node.max_width = theme.tooltip.max_width;
+        node.padding = theme.tooltip.padding;
+        *background_color = theme.tooltip.background_color;
+    }
+}
+
+#[derive(Resource)]
+struct TooltipHover {
+    entity: Option<Entity>,
+    timer: Timer,
+}
+
+impl Default for TooltipHover {
+    fn default() -> Self {
+        Self {
+            entity: None,
+            timer: Timer::new(DELAY, TimerMode::Once),
+        }
+    }
+}
+
+/// Text displayed when hovering the node.
+///
+/// Long text wraps according to the theme.
+#[derive(Component)]
+#[require(Node, RelativeCursorPosition)]
+pub struct Tooltip(pub String);
+
+#[derive(Component)]
+struct TooltipPopup;